@@ -0,0 +1,208 @@
+//! Measures `try_decode_instructions!` and `try_decode_accounts!`, which
+//! dispatch on the discriminator through a `DiscriminatorIndex`, against the
+//! sequential decoding they replaced, which deserialized the data as every
+//! listed type in turn until one succeeded.
+//!
+//! Both decode the same 64 types with 8-byte discriminators, like an Anchor
+//! program, on data of the first and the last listed type and on data that
+//! matches no type. The `indexed` functions should take about the same time
+//! in the three cases, while the `sequential` ones grow with the position of
+//! the type in the list.
+//!
+//! Run with `cargo bench -p carbon-core --bench decode`.
+
+use {
+    carbon_core::{
+        account::DecodedAccount, borsh, deserialize::CarbonDeserialize,
+        instruction::DecodedInstruction, try_decode_accounts, try_decode_instructions,
+        CarbonDeserialize,
+    },
+    criterion::{criterion_group, criterion_main, Criterion},
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    std::hint::black_box,
+};
+
+/// Declares the decoded types and the indexed and sequential decoders of an
+/// instruction or account holding any of them.
+macro_rules! decoded_types {
+    ($($name:ident => $discriminator:tt),* $(,)?) => {
+        $(
+            #[derive(CarbonDeserialize, Debug)]
+            #[carbon(discriminator = $discriminator)]
+            #[allow(dead_code)]
+            struct $name {
+                amount: u64,
+                authority: [u8; 32],
+            }
+        )*
+
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum Decoded {
+            $($name($name)),*
+        }
+
+        const FIRST: &[u8] = <Type00 as CarbonDeserialize>::DISCRIMINATOR;
+        const LAST: &[u8] = <Type63 as CarbonDeserialize>::DISCRIMINATOR;
+
+        fn decode_instruction_indexed(
+            instruction: &Instruction,
+        ) -> Option<DecodedInstruction<Decoded>> {
+            try_decode_instructions!(instruction, $(Decoded::$name => $name),*)
+        }
+
+        fn decode_instruction_sequential(
+            instruction: &Instruction,
+        ) -> Option<DecodedInstruction<Decoded>> {
+            $(
+                if let Some(decoded) = <$name>::deserialize(instruction.data.as_slice()) {
+                    return Some(DecodedInstruction {
+                        program_id: instruction.program_id,
+                        accounts: instruction.accounts.clone(),
+                        data: Decoded::$name(decoded),
+                    });
+                }
+            )*
+            None
+        }
+
+        fn decode_account_indexed(account: &Account) -> Option<DecodedAccount<Decoded>> {
+            try_decode_accounts!(account, $(Decoded::$name => $name),*)
+        }
+
+        fn decode_account_sequential(account: &Account) -> Option<DecodedAccount<Decoded>> {
+            $(
+                if let Some(decoded) = <$name>::deserialize(account.data.as_slice()) {
+                    return Some(DecodedAccount {
+                        lamports: account.lamports,
+                        data: Decoded::$name(decoded),
+                        owner: account.owner,
+                        executable: account.executable,
+                        rent_epoch: account.rent_epoch,
+                    });
+                }
+            )*
+            None
+        }
+    };
+}
+
+decoded_types!(
+    Type00 => "0x0b075c1e2d3f4a6b",
+    Type01 => "0x306c5c1e2d3f4a6b",
+    Type02 => "0x55d15c1e2d3f4a6b",
+    Type03 => "0x7a365c1e2d3f4a6b",
+    Type04 => "0x9f9b5c1e2d3f4a6b",
+    Type05 => "0xc4005c1e2d3f4a6b",
+    Type06 => "0xe9655c1e2d3f4a6b",
+    Type07 => "0x0eca5c1e2d3f4a6b",
+    Type08 => "0x332f5c1e2d3f4a6b",
+    Type09 => "0x58945c1e2d3f4a6b",
+    Type10 => "0x7df95c1e2d3f4a6b",
+    Type11 => "0xa25e5c1e2d3f4a6b",
+    Type12 => "0xc7c35c1e2d3f4a6b",
+    Type13 => "0xec285c1e2d3f4a6b",
+    Type14 => "0x118d5c1e2d3f4a6b",
+    Type15 => "0x36f25c1e2d3f4a6b",
+    Type16 => "0x5b575c1e2d3f4a6b",
+    Type17 => "0x80bc5c1e2d3f4a6b",
+    Type18 => "0xa5215c1e2d3f4a6b",
+    Type19 => "0xca865c1e2d3f4a6b",
+    Type20 => "0xefeb5c1e2d3f4a6b",
+    Type21 => "0x14505c1e2d3f4a6b",
+    Type22 => "0x39b55c1e2d3f4a6b",
+    Type23 => "0x5e1a5c1e2d3f4a6b",
+    Type24 => "0x837f5c1e2d3f4a6b",
+    Type25 => "0xa8e45c1e2d3f4a6b",
+    Type26 => "0xcd495c1e2d3f4a6b",
+    Type27 => "0xf2ae5c1e2d3f4a6b",
+    Type28 => "0x17135c1e2d3f4a6b",
+    Type29 => "0x3c785c1e2d3f4a6b",
+    Type30 => "0x61dd5c1e2d3f4a6b",
+    Type31 => "0x86425c1e2d3f4a6b",
+    Type32 => "0xaba75c1e2d3f4a6b",
+    Type33 => "0xd00c5c1e2d3f4a6b",
+    Type34 => "0xf5715c1e2d3f4a6b",
+    Type35 => "0x1ad65c1e2d3f4a6b",
+    Type36 => "0x3f3b5c1e2d3f4a6b",
+    Type37 => "0x64a05c1e2d3f4a6b",
+    Type38 => "0x89055c1e2d3f4a6b",
+    Type39 => "0xae6a5c1e2d3f4a6b",
+    Type40 => "0xd3cf5c1e2d3f4a6b",
+    Type41 => "0xf8345c1e2d3f4a6b",
+    Type42 => "0x1d995c1e2d3f4a6b",
+    Type43 => "0x42fe5c1e2d3f4a6b",
+    Type44 => "0x67635c1e2d3f4a6b",
+    Type45 => "0x8cc85c1e2d3f4a6b",
+    Type46 => "0xb12d5c1e2d3f4a6b",
+    Type47 => "0xd6925c1e2d3f4a6b",
+    Type48 => "0xfbf75c1e2d3f4a6b",
+    Type49 => "0x205c5c1e2d3f4a6b",
+    Type50 => "0x45c15c1e2d3f4a6b",
+    Type51 => "0x6a265c1e2d3f4a6b",
+    Type52 => "0x8f8b5c1e2d3f4a6b",
+    Type53 => "0xb4f05c1e2d3f4a6b",
+    Type54 => "0xd9555c1e2d3f4a6b",
+    Type55 => "0xfeba5c1e2d3f4a6b",
+    Type56 => "0x231f5c1e2d3f4a6b",
+    Type57 => "0x48845c1e2d3f4a6b",
+    Type58 => "0x6de95c1e2d3f4a6b",
+    Type59 => "0x924e5c1e2d3f4a6b",
+    Type60 => "0xb7b35c1e2d3f4a6b",
+    Type61 => "0xdc185c1e2d3f4a6b",
+    Type62 => "0x017d5c1e2d3f4a6b",
+    Type63 => "0x26e25c1e2d3f4a6b",
+);
+
+fn data(discriminator: &[u8]) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.resize(discriminator.len() + 8 + 32, 0);
+    data
+}
+
+fn bench_decode_instruction(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("decode_instruction");
+
+    for (name, discriminator) in [("first", FIRST), ("last", LAST), ("unknown", &[255; 8][..])] {
+        let instruction = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: data(discriminator),
+        };
+        group.bench_function(format!("indexed/{name}"), |bencher| {
+            bencher.iter(|| decode_instruction_indexed(black_box(&instruction)))
+        });
+        group.bench_function(format!("sequential/{name}"), |bencher| {
+            bencher.iter(|| decode_instruction_sequential(black_box(&instruction)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_decode_account(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("decode_account");
+
+    for (name, discriminator) in [("first", FIRST), ("last", LAST), ("unknown", &[255; 8][..])] {
+        let account = Account {
+            lamports: 1_000_000,
+            data: data(discriminator),
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        group.bench_function(format!("indexed/{name}"), |bencher| {
+            bencher.iter(|| decode_account_indexed(black_box(&account)))
+        });
+        group.bench_function(format!("sequential/{name}"), |bencher| {
+            bencher.iter(|| decode_account_sequential(black_box(&account)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode_instruction, bench_decode_account);
+criterion_main!(benches);
@@ -0,0 +1,422 @@
+//! Measures the throughput of `Pipeline::run` on account and transaction
+//! updates, with several pipes sharing every update.
+//!
+//! Only running the pipeline is timed. Building it and the updates it replays
+//! is part of the setup of every iteration.
+//!
+//! The `dispatch` group compares how an update is handed to the pipes. The
+//! `cloned` functions copy the update for every pipe, like the pipeline did
+//! before updates were shared: each account pipe received its own copy of the
+//! account, the transaction metadata was built from a copy of the whole
+//! update, and every decoded instruction received a deep copy of its inner
+//! instructions. The `shared` functions copy what the pipeline copies now.
+//! Both run the same decoders and hand the processor input to `black_box`.
+//!
+//! Run with `cargo bench -p carbon-core --bench pipeline`.
+
+use {
+    async_trait::async_trait,
+    carbon_core::{
+        account::AccountMetadata,
+        account::{AccountDecoder, AccountProcessorInputType, DecodedAccount},
+        datasource::{
+            AccountUpdate, Datasource, DatasourceId, TransactionUpdate, Update, UpdateType,
+        },
+        error::CarbonResult,
+        instruction::{
+            DecodedInstruction, InstructionDecoder, InstructionProcessorInputType,
+            NestedInstruction, NestedInstructions,
+        },
+        metrics::MetricsCollection,
+        pipeline::Pipeline,
+        processor::Processor,
+        transaction::TransactionMetadata,
+        transformers,
+    },
+    criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput},
+    solana_account::Account,
+    solana_message::{
+        compiled_instruction::CompiledInstruction, legacy::Message, MessageHeader, VersionedMessage,
+    },
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_transaction::versioned::VersionedTransaction,
+    solana_transaction_status::{InnerInstruction, InnerInstructions, TransactionStatusMeta},
+    std::hint::black_box,
+    std::sync::{Arc, Mutex},
+    tokio_util::sync::CancellationToken,
+};
+
+const UPDATES: usize = 1_000;
+const PIPES: usize = 8;
+const ACCOUNT_DATA_SIZE: usize = 16 * 1024;
+const TOP_LEVEL_INSTRUCTIONS: u8 = 4;
+const INNER_INSTRUCTIONS: usize = 6;
+
+/// Sends a fixed list of updates once, moving them into the pipeline.
+struct FixtureDatasource {
+    updates: Mutex<Vec<Update>>,
+}
+
+impl FixtureDatasource {
+    fn new(updates: Vec<Update>) -> Self {
+        Self {
+            updates: Mutex::new(updates),
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for FixtureDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+        _cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let updates = std::mem::take(&mut *self.updates.lock().unwrap());
+        for update in updates {
+            if sender.send((update, id.clone())).await.is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::AccountUpdate, UpdateType::Transaction]
+    }
+}
+
+/// Decodes the accounts whose first byte is `tag`, like a program decoder
+/// matching on a discriminator.
+struct TagDecoder {
+    tag: u8,
+}
+
+impl<'a> AccountDecoder<'a> for TagDecoder {
+    type AccountType = u8;
+
+    fn decode_account(&self, account: &'a Account) -> Option<DecodedAccount<u8>> {
+        (account.data.first() == Some(&self.tag)).then(|| DecodedAccount {
+            lamports: account.lamports,
+            data: self.tag,
+            owner: account.owner,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        })
+    }
+}
+
+impl<'a> InstructionDecoder<'a> for TagDecoder {
+    type InstructionType = u8;
+
+    fn decode_instruction(
+        &self,
+        instruction: &'a solana_instruction::Instruction,
+    ) -> Option<DecodedInstruction<u8>> {
+        (instruction.data.first() == Some(&self.tag)).then(|| DecodedInstruction {
+            program_id: instruction.program_id,
+            data: self.tag,
+            accounts: instruction.accounts.clone(),
+        })
+    }
+}
+
+struct NoopProcessor;
+
+#[async_trait]
+impl Processor for NoopProcessor {
+    type InputType = AccountProcessorInputType<u8>;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        black_box(data);
+        Ok(())
+    }
+}
+
+struct NoopInstructionProcessor;
+
+#[async_trait]
+impl Processor for NoopInstructionProcessor {
+    type InputType = InstructionProcessorInputType<u8>;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        black_box(data);
+        Ok(())
+    }
+}
+
+fn account_updates() -> Vec<Update> {
+    (0..UPDATES)
+        .map(|index| {
+            let mut data = vec![0; ACCOUNT_DATA_SIZE];
+            data[0] = (index % PIPES) as u8;
+
+            Update::Account(AccountUpdate {
+                pubkey: Pubkey::new_unique(),
+                account: Account {
+                    lamports: 1_000_000,
+                    data,
+                    owner: Pubkey::new_unique(),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+                slot: index as u64,
+                transaction_signature: None,
+            })
+        })
+        .collect()
+}
+
+fn transaction_updates() -> Vec<Update> {
+    let account_keys: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+    let compiled_instruction = |tag: u8| CompiledInstruction {
+        program_id_index: 15,
+        accounts: (0..12).collect(),
+        data: [tag].into_iter().chain([0; 127]).collect(),
+    };
+
+    (0..UPDATES)
+        .map(|index| {
+            let message = Message {
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: account_keys.clone(),
+                recent_blockhash: Default::default(),
+                instructions: (0..TOP_LEVEL_INSTRUCTIONS)
+                    .map(|tag| compiled_instruction(tag % PIPES as u8))
+                    .collect(),
+            };
+            let inner_instructions = (0..TOP_LEVEL_INSTRUCTIONS)
+                .map(|instruction_index| InnerInstructions {
+                    index: instruction_index,
+                    instructions: (0..INNER_INSTRUCTIONS)
+                        .map(|inner_index| InnerInstruction {
+                            instruction: compiled_instruction((inner_index % PIPES) as u8),
+                            stack_height: Some(2 + (inner_index % 2) as u32),
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            Update::Transaction(Box::new(TransactionUpdate {
+                signature: Signature::default(),
+                transaction: VersionedTransaction {
+                    signatures: vec![Signature::default()],
+                    message: VersionedMessage::Legacy(message),
+                },
+                meta: TransactionStatusMeta {
+                    inner_instructions: Some(inner_instructions),
+                    log_messages: Some(vec!["Program log: benchmark".to_string(); 32]),
+                    ..Default::default()
+                },
+                is_vote: false,
+                slot: index as u64,
+                index: Some(index as u64),
+                block_time: None,
+                block_hash: None,
+            }))
+        })
+        .collect()
+}
+
+/// Copies an instruction tree, including every level of inner instructions.
+fn deep_clone(instructions: &NestedInstructions) -> NestedInstructions {
+    NestedInstructions(Arc::new(
+        instructions
+            .iter()
+            .map(|nested_instruction| NestedInstruction {
+                metadata: nested_instruction.metadata.clone(),
+                instruction: nested_instruction.instruction.clone(),
+                inner_instructions: deep_clone(&nested_instruction.inner_instructions),
+            })
+            .collect(),
+    ))
+}
+
+/// Hands an account update to every decoder, copying the account once per
+/// decoder when `cloned` is set and once per update otherwise.
+fn dispatch_account(update: &AccountUpdate, decoders: &[TagDecoder], cloned: bool) {
+    let account_metadata = AccountMetadata {
+        slot: update.slot,
+        pubkey: update.pubkey,
+        transaction_signature: update.transaction_signature,
+    };
+    let mut shared: Option<Arc<Account>> = None;
+
+    for decoder in decoders {
+        if cloned {
+            let account = update.account.clone();
+            if let Some(decoded_account) = decoder.decode_account(&account) {
+                black_box((account_metadata.clone(), decoded_account, account));
+            }
+        } else {
+            let account = shared.get_or_insert_with(|| Arc::new(update.account.clone()));
+            if let Some(decoded_account) = decoder.decode_account(&update.account) {
+                black_box((account_metadata.clone(), decoded_account, account.clone()));
+            }
+        }
+    }
+}
+
+/// Hands a transaction update to every decoder, copying the update and the
+/// inner instructions of every decoded instruction when `cloned` is set.
+fn dispatch_transaction(update: &TransactionUpdate, decoders: &[TagDecoder], cloned: bool) {
+    let transaction_metadata = Arc::new(
+        if cloned {
+            TransactionMetadata::try_from(update.clone())
+        } else {
+            TransactionMetadata::try_from(update)
+        }
+        .expect("failed to build the transaction metadata"),
+    );
+    let nested_instructions: NestedInstructions =
+        transformers::extract_instructions_with_metadata(&transaction_metadata, update)
+            .expect("failed to extract the instructions")
+            .into();
+
+    for decoder in decoders {
+        let mut instructions: Vec<&NestedInstruction> = nested_instructions.iter().rev().collect();
+        while let Some(nested_instruction) = instructions.pop() {
+            if let Some(decoded_instruction) =
+                decoder.decode_instruction(&nested_instruction.instruction)
+            {
+                let inner_instructions = if cloned {
+                    deep_clone(&nested_instruction.inner_instructions)
+                } else {
+                    nested_instruction.inner_instructions.clone()
+                };
+                black_box((
+                    nested_instruction.metadata.clone(),
+                    decoded_instruction,
+                    inner_instructions,
+                    nested_instruction.instruction.clone(),
+                ));
+            }
+            instructions.extend(nested_instruction.inner_instructions.iter().rev());
+        }
+    }
+}
+
+fn bench_dispatch(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("dispatch");
+    group.throughput(Throughput::Elements(UPDATES as u64));
+
+    let decoders: Vec<TagDecoder> = (0..PIPES)
+        .map(|tag| TagDecoder { tag: tag as u8 })
+        .collect();
+
+    let accounts: Vec<AccountUpdate> = account_updates()
+        .into_iter()
+        .filter_map(|update| match update {
+            Update::Account(account_update) => Some(account_update),
+            _ => None,
+        })
+        .collect();
+    for (name, cloned) in [
+        ("cloned_account_updates", true),
+        ("shared_account_updates", false),
+    ] {
+        group.bench_function(name, |bencher| {
+            bencher.iter(|| {
+                for update in &accounts {
+                    dispatch_account(update, &decoders, cloned);
+                }
+            })
+        });
+    }
+
+    let transactions: Vec<TransactionUpdate> = transaction_updates()
+        .into_iter()
+        .filter_map(|update| match update {
+            Update::Transaction(transaction_update) => Some(*transaction_update),
+            _ => None,
+        })
+        .collect();
+    for (name, cloned) in [
+        ("cloned_transaction_updates", true),
+        ("shared_transaction_updates", false),
+    ] {
+        group.bench_function(name, |bencher| {
+            bencher.iter(|| {
+                for update in &transactions {
+                    dispatch_transaction(update, &decoders, cloned);
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_pipeline(criterion: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build the runtime");
+
+    let mut group = criterion.benchmark_group("pipeline");
+    group.throughput(Throughput::Elements(UPDATES as u64));
+    group.sample_size(20);
+
+    let accounts = account_updates();
+    group.bench_function("account_updates", |bencher| {
+        bencher.iter_batched(
+            || {
+                let mut builder = Pipeline::builder()
+                    .datasource(FixtureDatasource::new(accounts.clone()))
+                    .ignore_shutdown_signals();
+                for tag in 0..PIPES {
+                    builder = builder.account(TagDecoder { tag: tag as u8 }, NoopProcessor);
+                }
+                builder.build().expect("failed to build the pipeline")
+            },
+            |mut pipeline| {
+                runtime
+                    .block_on(pipeline.run())
+                    .expect("failed to run the pipeline")
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    let transactions = transaction_updates();
+    group.bench_function("transaction_updates", |bencher| {
+        bencher.iter_batched(
+            || {
+                let mut builder = Pipeline::builder()
+                    .datasource(FixtureDatasource::new(transactions.clone()))
+                    .ignore_shutdown_signals();
+                for tag in 0..PIPES {
+                    builder = builder
+                        .instruction(TagDecoder { tag: tag as u8 }, NoopInstructionProcessor);
+                }
+                builder.build().expect("failed to build the pipeline")
+            },
+            |mut pipeline| {
+                runtime
+                    .block_on(pipeline.run())
+                    .expect("failed to run the pipeline")
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pipeline, bench_dispatch);
+criterion_main!(benches);
@@ -52,7 +52,8 @@
 
 use {
     crate::{
-        error::CarbonResult, filter::Filter, metrics::MetricsCollection, processor::Processor,
+        error::CarbonResult, error_policy::ErrorPolicy, filter::Filter, metrics::MetricsCollection,
+        processor::Processor,
     },
     async_trait::async_trait,
     solana_pubkey::Pubkey,
@@ -118,6 +119,12 @@ pub struct DecodedAccount<T> {
 ///
 /// - `AccountType`: The data type resulting from decoding the account, specific
 ///   to the application.
+///
+/// # Provided Methods
+///
+/// - `program_ids`: Returns the programs owning the accounts the decoder
+///   decodes, so that datasources can subscribe to them. Defaults to an empty
+///   list, meaning that the decoder may decode accounts of any owner.
 pub trait AccountDecoder<'a> {
     type AccountType;
 
@@ -125,13 +132,23 @@ pub trait AccountDecoder<'a> {
         &self,
         account: &'a solana_account::Account,
     ) -> Option<DecodedAccount<Self::AccountType>>;
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
 }
 
 /// The input type for the account processor.
 ///
 /// - `T`: The account type, as determined by the decoder.
-pub type AccountProcessorInputType<T> =
-    (AccountMetadata, DecodedAccount<T>, solana_account::Account);
+///
+/// The raw account is shared behind an `Arc` between every pipe processing
+/// the same update, so that it is not copied once per pipe.
+pub type AccountProcessorInputType<T> = (
+    AccountMetadata,
+    DecodedAccount<T>,
+    Arc<solana_account::Account>,
+);
 
 /// A processing pipe that decodes and processes Solana account updates.
 ///
@@ -155,10 +172,12 @@ pub type AccountProcessorInputType<T> =
 ///   should be processed. Each filter in this collection is applied to incoming
 ///   account updates, and only updates that pass all filters (return `true`)
 ///   will be processed. If this collection is empty, all updates are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
 pub struct AccountPipe<T: Send> {
-    pub decoder: Box<dyn for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static>,
+    pub decoder: Arc<dyn for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static>,
     pub processor: Box<dyn Processor<InputType = AccountProcessorInputType<T>> + Send + Sync>,
-    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    pub error_policy: ErrorPolicy,
 }
 
 /// An async trait for processing account updates.
@@ -168,38 +187,80 @@ pub struct AccountPipe<T: Send> {
 /// # Required Methods
 ///
 /// - `run`: Processes an account update and tracks the operation with metrics.
+///   The raw account is shared between every account pipe and handed to
+///   their processors without being copied.
 /// - `filters`: Returns a reference to the filters associated with this pipe,
 ///   which are used by the pipeline to determine which account updates should
 ///   be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `program_ids`: Returns the programs owning the accounts the pipe
+///   processes. Defaults to an empty list, meaning any owner.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+/// - `fork`: Returns a new instance of the pipe for another worker, sharing
+///   its decoder and filters but with a fork of its processor. Defaults to
+///   `None`, meaning the pipe is shared between the workers.
 #[async_trait]
 pub trait AccountPipes: Send + Sync {
     async fn run(
         &mut self,
-        account_with_metadata: (AccountMetadata, solana_account::Account),
+        account_metadata: &AccountMetadata,
+        account: &Arc<solana_account::Account>,
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()>;
 
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    fn fork(&self) -> Option<Box<dyn AccountPipes>> {
+        None
+    }
 }
 
 #[async_trait]
-impl<T: Send> AccountPipes for AccountPipe<T> {
+impl<T: Send + 'static> AccountPipes for AccountPipe<T> {
     async fn run(
         &mut self,
-        account_with_metadata: (AccountMetadata, solana_account::Account),
+        account_metadata: &AccountMetadata,
+        account: &Arc<solana_account::Account>,
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
-        log::trace!("AccountPipe::run(account_with_metadata: {account_with_metadata:?}, metrics)",);
+        log::trace!(
+            "AccountPipe::run(account_metadata: {account_metadata:?}, account: {account:?}, metrics)",
+        );
 
-        if let Some(decoded_account) = self.decoder.decode_account(&account_with_metadata.1) {
+        if let Some(decoded_account) = self.decoder.decode_account(account) {
             self.processor
                 .process(
                     (
-                        account_with_metadata.0.clone(),
+                        account_metadata.clone(),
                         decoded_account,
-                        account_with_metadata.1,
+                        Arc::clone(account),
                     ),
-                    metrics.clone(),
+                    metrics,
                 )
                 .await?;
         }
@@ -209,4 +270,33 @@ impl<T: Send> AccountPipes for AccountPipe<T> {
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
         &self.filters
     }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        self.decoder.program_ids()
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn AccountPipes>> {
+        Some(Box::new(AccountPipe {
+            decoder: self.decoder.clone(),
+            processor: self.processor.fork()?,
+            filters: self.filters.clone(),
+            error_policy: self.error_policy,
+        }))
+    }
 }
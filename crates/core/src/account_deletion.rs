@@ -10,8 +10,8 @@
 
 use {
     crate::{
-        datasource::AccountDeletion, error::CarbonResult, filter::Filter,
-        metrics::MetricsCollection, processor::Processor,
+        datasource::AccountDeletion, error::CarbonResult, error_policy::ErrorPolicy,
+        filter::Filter, metrics::MetricsCollection, processor::Processor,
     },
     async_trait::async_trait,
     std::sync::Arc,
@@ -67,6 +67,7 @@ use {
 ///   incoming account deletion events, and only events that pass all filters
 ///   (return `true`) will be processed. If this collection is empty, all
 ///   events are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
 ///
 /// # Notes
 ///
@@ -76,7 +77,8 @@ use {
 ///   managing updates.
 pub struct AccountDeletionPipe {
     pub processor: Box<dyn Processor<InputType = AccountDeletion> + Send + Sync>,
-    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    pub error_policy: ErrorPolicy,
 }
 
 /// An async trait for processing account deletions.
@@ -90,6 +92,16 @@ pub struct AccountDeletionPipe {
 /// - `filters`: Returns a reference to the filters associated with this pipe,
 ///   which are used by the pipeline to determine which account deletion events
 ///   should be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+/// - `fork`: Returns a new instance of the pipe for another worker, sharing
+///   its filters but with a fork of its processor. Defaults to `None`,
+///   meaning the pipe is shared between the workers.
 #[async_trait]
 pub trait AccountDeletionPipes: Send + Sync {
     async fn run(
@@ -99,6 +111,26 @@ pub trait AccountDeletionPipes: Send + Sync {
     ) -> CarbonResult<()>;
 
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    fn fork(&self) -> Option<Box<dyn AccountDeletionPipes>> {
+        None
+    }
 }
 
 #[async_trait]
@@ -118,4 +150,28 @@ impl AccountDeletionPipes for AccountDeletionPipe {
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
         &self.filters
     }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn AccountDeletionPipes>> {
+        Some(Box::new(AccountDeletionPipe {
+            processor: self.processor.fork()?,
+            filters: self.filters.clone(),
+            error_policy: self.error_policy,
+        }))
+    }
 }
@@ -0,0 +1,445 @@
+//! Provides account diff pipes, which hand processors the previous decoded
+//! state of an account alongside the new one.
+//!
+//! A diff pipe keeps the last decoded state of the accounts it saw in a
+//! bounded cache, so that processors can tell what changed, such as the
+//! reserves of a pool or the health of an obligation, without keeping a cache
+//! of their own. It is registered through `PipelineBuilder::account_diff`.
+//!
+//! # Behavior
+//!
+//! - The processor only runs when the decoded state changed: updates whose
+//!   decoded data, lamports and owner are equal to the cached ones are
+//!   skipped.
+//! - The previous state is `None` the first time an account is seen, or once
+//!   it was evicted from the cache.
+//! - Updates for a slot older than the cached state are skipped, so that an
+//!   update delivered late does not produce a diff going back in time.
+//! - An account that no longer decodes, for example because it was closed, is
+//!   removed from the cache.
+//! - The new state is only cached once the processor succeeded, so that an
+//!   update retried under `ErrorPolicy::Retry` is still processed as a change.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use async_trait::async_trait;
+//! use carbon_core::{
+//!     account_diff::AccountDiffProcessorInputType, error::CarbonResult,
+//!     metrics::MetricsCollection, processor::Processor,
+//! };
+//! use std::sync::Arc;
+//!
+//! struct ReservesProcessor;
+//!
+//! #[async_trait]
+//! impl Processor for ReservesProcessor {
+//!     type InputType = AccountDiffProcessorInputType<MyProgramAccount>;
+//!
+//!     async fn process(
+//!         &mut self,
+//!         (metadata, previous, current, _raw): Self::InputType,
+//!         _metrics: Arc<MetricsCollection>,
+//!     ) -> CarbonResult<()> {
+//!         if let (Some(previous), MyProgramAccount::Pool(pool)) = (previous, current.data) {
+//!             // Compare the reserves of `previous` and `pool`.
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! Pipeline::builder()
+//!     .datasource(account_subscription)
+//!     .account_diff(MyProgramDecoder, ReservesProcessor, DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+
+use {
+    crate::{
+        account::{AccountDecoder, AccountMetadata, AccountPipes, DecodedAccount},
+        error::CarbonResult,
+        error_policy::ErrorPolicy,
+        filter::Filter,
+        lru::LruMap,
+        metrics::MetricsCollection,
+        processor::Processor,
+    },
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// The default number of accounts an account diff pipe remembers.
+pub const DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY: usize = 10_000;
+
+/// The input type for the account diff processor.
+///
+/// - `AccountMetadata`: The metadata of the account update.
+/// - `Option<DecodedAccount<T>>`: The previous decoded state of the account,
+///   if it is cached.
+/// - `DecodedAccount<T>`: The new decoded state of the account.
+/// - `Arc<solana_account::Account>`: The raw account, shared with the other
+///   pipes processing the update.
+pub type AccountDiffProcessorInputType<T> = (
+    AccountMetadata,
+    Option<DecodedAccount<T>>,
+    DecodedAccount<T>,
+    Arc<solana_account::Account>,
+);
+
+/// Keeps the last decoded state of up to `capacity` accounts, evicting the
+/// least recently updated ones first.
+pub(crate) struct AccountCache<T> {
+    capacity: usize,
+    accounts: LruMap<Pubkey, (u64, DecodedAccount<T>)>,
+}
+
+impl<T> AccountCache<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            accounts: LruMap::new(),
+        }
+    }
+
+    /// Returns the number of accounts the cache remembers at most.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the cached state of an account and the slot it was updated in.
+    pub(crate) fn get(&self, pubkey: &Pubkey) -> Option<(u64, &DecodedAccount<T>)> {
+        self.accounts
+            .get(pubkey)
+            .map(|(slot, account)| (*slot, account))
+    }
+
+    /// Caches the state of an account, returning its previous state.
+    pub(crate) fn insert(
+        &mut self,
+        pubkey: Pubkey,
+        slot: u64,
+        account: DecodedAccount<T>,
+    ) -> Option<DecodedAccount<T>> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let previous = self.accounts.insert(pubkey, (slot, account));
+        if self.accounts.len() > self.capacity {
+            self.accounts.pop_least_recent();
+        }
+
+        previous.map(|(_, account)| account)
+    }
+
+    /// Removes an account from the cache.
+    pub(crate) fn remove(&mut self, pubkey: &Pubkey) {
+        self.accounts.remove(pubkey);
+    }
+}
+
+/// A pipe that decodes account updates and processes them along with the
+/// previous decoded state of the account.
+///
+/// # Type Parameters
+///
+/// - `T`: The data type of the decoded account information, as determined by
+///   the decoder. It is compared with `PartialEq` to detect changes.
+///
+/// # Fields
+///
+/// - `decoder`: An `AccountDecoder` that decodes raw account data into
+///   structured form.
+/// - `processor`: A `Processor` that handles the changes of decoded accounts.
+/// - `filters`: A collection of filters that determine which account updates
+///   should be processed. If this collection is empty, all updates are
+///   processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+pub struct AccountDiffPipe<T: Send> {
+    pub decoder: Arc<dyn for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static>,
+    pub processor: Box<dyn Processor<InputType = AccountDiffProcessorInputType<T>> + Send + Sync>,
+    pub filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    pub error_policy: ErrorPolicy,
+    cache: AccountCache<T>,
+}
+
+impl<T: Send> AccountDiffPipe<T> {
+    /// Creates an account diff pipe remembering up to `cache_capacity`
+    /// accounts.
+    pub fn new(
+        decoder: Arc<dyn for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static>,
+        processor: Box<dyn Processor<InputType = AccountDiffProcessorInputType<T>> + Send + Sync>,
+        filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+        error_policy: ErrorPolicy,
+        cache_capacity: usize,
+    ) -> Self {
+        Self {
+            decoder,
+            processor,
+            filters,
+            error_policy,
+            cache: AccountCache::new(cache_capacity),
+        }
+    }
+}
+
+/// Returns whether two decoded states of an account differ.
+fn has_changed<T: PartialEq>(previous: &DecodedAccount<T>, current: &DecodedAccount<T>) -> bool {
+    previous.data != current.data
+        || previous.lamports != current.lamports
+        || previous.owner != current.owner
+}
+
+#[async_trait]
+impl<T: Clone + PartialEq + Send + Sync + 'static> AccountPipes for AccountDiffPipe<T> {
+    async fn run(
+        &mut self,
+        account_metadata: &AccountMetadata,
+        account: &Arc<solana_account::Account>,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        log::trace!(
+            "AccountDiffPipe::run(account_metadata: {account_metadata:?}, account: {account:?}, metrics)",
+        );
+
+        let Some(decoded_account) = self.decoder.decode_account(account) else {
+            self.cache.remove(&account_metadata.pubkey);
+            return Ok(());
+        };
+
+        let previous = match self.cache.get(&account_metadata.pubkey) {
+            Some((slot, cached)) => {
+                if slot > account_metadata.slot || !has_changed(cached, &decoded_account) {
+                    return Ok(());
+                }
+                Some(cached.clone())
+            }
+            None => None,
+        };
+
+        self.processor
+            .process(
+                (
+                    account_metadata.clone(),
+                    previous,
+                    decoded_account.clone(),
+                    Arc::clone(account),
+                ),
+                metrics,
+            )
+            .await?;
+
+        // Only cached once processed, so that an update retried after the
+        // processor failed is still seen as a change.
+        self.cache.insert(
+            account_metadata.pubkey,
+            account_metadata.slot,
+            decoded_account,
+        );
+
+        Ok(())
+    }
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
+        &self.filters
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        self.decoder.program_ids()
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn AccountPipes>> {
+        Some(Box::new(AccountDiffPipe::new(
+            self.decoder.clone(),
+            self.processor.fork()?,
+            self.filters.clone(),
+            self.error_policy,
+            self.cache.capacity(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::error::Error, std::sync::Mutex};
+
+    /// Decodes the first byte of an account's data.
+    struct ByteDecoder;
+
+    impl<'a> AccountDecoder<'a> for ByteDecoder {
+        type AccountType = u8;
+
+        fn decode_account(
+            &self,
+            account: &'a solana_account::Account,
+        ) -> Option<DecodedAccount<Self::AccountType>> {
+            Some(DecodedAccount {
+                lamports: account.lamports,
+                data: *account.data.first()?,
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            })
+        }
+    }
+
+    /// Records the previous and current state of every change it is given,
+    /// failing the first `failures` times.
+    struct FlakyProcessor {
+        failures: usize,
+        changes: Arc<Mutex<Vec<(Option<u8>, u8)>>>,
+    }
+
+    #[async_trait]
+    impl Processor for FlakyProcessor {
+        type InputType = AccountDiffProcessorInputType<u8>;
+
+        async fn process(
+            &mut self,
+            (_metadata, previous, current, _raw): Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            self.changes
+                .lock()
+                .unwrap()
+                .push((previous.map(|previous| previous.data), current.data));
+
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(Error::Custom("processor failed".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn decoded(data: u8) -> DecodedAccount<u8> {
+        DecodedAccount {
+            lamports: 1,
+            data,
+            owner: Pubkey::default(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_returns_previous_state() {
+        let mut cache = AccountCache::new(10);
+        let pubkey = Pubkey::new_unique();
+
+        assert!(cache.insert(pubkey, 1, decoded(1)).is_none());
+        assert_eq!(cache.insert(pubkey, 2, decoded(2)).map(|a| a.data), Some(1));
+        assert_eq!(
+            cache.get(&pubkey).map(|(slot, a)| (slot, a.data)),
+            Some((2, 2))
+        );
+
+        cache.remove(&pubkey);
+        assert!(cache.get(&pubkey).is_none());
+        assert!(cache.accounts.is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_updated() {
+        let mut cache = AccountCache::new(2);
+        let (first, second, third) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        cache.insert(first, 1, decoded(1));
+        cache.insert(second, 1, decoded(1));
+        cache.insert(first, 2, decoded(2));
+        cache.insert(third, 2, decoded(1));
+
+        assert_eq!(cache.accounts.len(), 2);
+        assert!(cache.get(&second).is_none());
+        assert!(cache.get(&first).is_some());
+        assert!(cache.get(&third).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_retried_update_is_processed_again() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let mut pipe = AccountDiffPipe::new(
+            Arc::new(ByteDecoder),
+            Box::new(FlakyProcessor {
+                failures: 1,
+                changes: changes.clone(),
+            }),
+            Arc::new(Vec::new()),
+            ErrorPolicy::retry(3),
+            DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY,
+        );
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+        let pubkey = Pubkey::new_unique();
+        let update = |slot: u64, data: u8| {
+            (
+                AccountMetadata {
+                    slot,
+                    pubkey,
+                    transaction_signature: None,
+                },
+                Arc::new(solana_account::Account {
+                    data: vec![data],
+                    ..solana_account::Account::default()
+                }),
+            )
+        };
+
+        // Retried the way `ErrorPolicy::Retry` runs the pipe again.
+        let (metadata, account) = update(1, 1);
+        assert!(pipe
+            .run(&metadata, &account, metrics.clone())
+            .await
+            .is_err());
+        assert!(pipe.run(&metadata, &account, metrics.clone()).await.is_ok());
+
+        let (metadata, account) = update(2, 1);
+        pipe.run(&metadata, &account, metrics.clone())
+            .await
+            .unwrap();
+        let (metadata, account) = update(3, 2);
+        pipe.run(&metadata, &account, metrics.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![(None, 1), (None, 1), (Some(1), 2)]
+        );
+    }
+
+    #[test]
+    fn test_detects_changes() {
+        assert!(!has_changed(&decoded(1), &decoded(1)));
+        assert!(has_changed(&decoded(1), &decoded(2)));
+        assert!(has_changed(
+            &decoded(1),
+            &DecodedAccount {
+                lamports: 2,
+                ..decoded(1)
+            }
+        ));
+    }
+}
@@ -0,0 +1,478 @@
+//! Provides the `AccountStore`, an in-memory copy of the accounts a pipeline
+//! has seen, which processors can query while handling updates.
+//!
+//! Instruction processors often need the current state of related accounts,
+//! such as the pool state of a swap. When an `AccountStore` is set with
+//! `PipelineBuilder::account_store`, every account update received from any
+//! datasource is written to it, and every account deletion removes the
+//! account, before the update reaches the pipes. Processors hold a clone of
+//! the store and read it synchronously.
+//!
+//! # Seeding
+//!
+//! A store only knows the accounts that were updated since the pipeline
+//! started. `AccountStore::seed` fills it from a datasource that lists
+//! existing accounts, such as `GpaDatasource` or `SnapshotDatasource`, before
+//! the pipeline runs and without sending those accounts through the pipes.
+//!
+//! # Memory Cap
+//!
+//! The store keeps its estimated size, counting the data of every account
+//! plus a fixed overhead, under `AccountStoreConfig::max_bytes` by evicting
+//! the least recently updated accounts.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::account_store::{AccountStore, AccountStoreConfig};
+//!
+//! let store = AccountStore::new(AccountStoreConfig {
+//!     owners: vec![WHIRLPOOL_PROGRAM_ID],
+//!     ..Default::default()
+//! });
+//! store
+//!     .seed(GpaDatasource::new(rpc_url, WHIRLPOOL_PROGRAM_ID), metrics.clone())
+//!     .await?;
+//!
+//! Pipeline::builder()
+//!     .datasource(helius_websocket)
+//!     .account_store(store.clone())
+//!     .instruction(WhirlpoolDecoder, SwapProcessor { store })
+//!     .build()?
+//!     .run()
+//!     .await?;
+//!
+//! // In `SwapProcessor::process`:
+//! let pool = self.store.get_decoded(&pool_address, &WhirlpoolDecoder);
+//! ```
+//!
+//! # Metrics
+//!
+//! - `account_store_accounts`: A gauge of the number of stored accounts.
+//! - `account_store_bytes`: A gauge of the estimated size of the store.
+//! - `account_store_evictions`: A counter of the accounts evicted to stay
+//!   under the memory cap.
+
+use {
+    crate::{
+        account::{AccountDecoder, DecodedAccount},
+        datasource::{Datasource, DatasourceId, Update},
+        error::CarbonResult,
+        lru::LruMap,
+        metrics::MetricsCollection,
+    },
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    },
+    tokio::sync::mpsc,
+    tokio_util::sync::CancellationToken,
+};
+
+/// The default memory cap of an `AccountStore`, 1 GiB.
+pub const DEFAULT_ACCOUNT_STORE_MAX_BYTES: usize = 1 << 30;
+
+/// The estimated memory used by a stored account besides its data.
+const ACCOUNT_OVERHEAD_BYTES: usize = 256;
+
+/// Configures an `AccountStore`.
+///
+/// # Fields
+///
+/// - `max_bytes`: The estimated size the store is kept under, evicting the
+///   least recently updated accounts.
+/// - `owners`: The programs whose accounts are stored. If empty, every account
+///   is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStoreConfig {
+    pub max_bytes: usize,
+    pub owners: Vec<Pubkey>,
+}
+
+impl Default for AccountStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_ACCOUNT_STORE_MAX_BYTES,
+            owners: Vec::new(),
+        }
+    }
+}
+
+/// An account held by an `AccountStore`, with the slot it was last updated in.
+#[derive(Debug, Clone)]
+pub struct StoredAccount {
+    pub slot: u64,
+    pub account: Arc<Account>,
+}
+
+#[derive(Default)]
+struct StoreState {
+    accounts: LruMap<Pubkey, StoredAccount>,
+    owners: HashMap<Pubkey, HashSet<Pubkey>>,
+    bytes: usize,
+    evictions: u64,
+}
+
+impl StoreState {
+    fn size_of(account: &Account) -> usize {
+        account.data.len() + ACCOUNT_OVERHEAD_BYTES
+    }
+
+    fn remove(&mut self, pubkey: &Pubkey) {
+        if let Some(stored) = self.accounts.remove(pubkey) {
+            self.forget(pubkey, &stored);
+        }
+    }
+
+    /// Updates the size and owner index after `pubkey` left `accounts`.
+    fn forget(&mut self, pubkey: &Pubkey, stored: &StoredAccount) {
+        self.bytes -= Self::size_of(&stored.account);
+
+        let owner = stored.account.owner;
+        if let Some(owned) = self.owners.get_mut(&owner) {
+            owned.remove(pubkey);
+            if owned.is_empty() {
+                self.owners.remove(&owner);
+            }
+        }
+    }
+
+    /// Removes an account, unless its stored state is newer than `slot`.
+    fn remove_at(&mut self, pubkey: &Pubkey, slot: u64) {
+        if self
+            .accounts
+            .get(pubkey)
+            .is_some_and(|stored| stored.slot <= slot)
+        {
+            self.remove(pubkey);
+        }
+    }
+
+    fn insert(&mut self, pubkey: Pubkey, slot: u64, account: Account, max_bytes: usize) {
+        if let Some(stored) = self.accounts.get(&pubkey) {
+            if stored.slot > slot {
+                return;
+            }
+        }
+        self.remove(&pubkey);
+
+        self.bytes += Self::size_of(&account);
+        self.owners.entry(account.owner).or_default().insert(pubkey);
+        self.accounts.insert(
+            pubkey,
+            StoredAccount {
+                slot,
+                account: Arc::new(account),
+            },
+        );
+
+        while self.bytes > max_bytes {
+            let Some((evicted, stored)) = self.accounts.pop_least_recent() else {
+                break;
+            };
+            self.forget(&evicted, &stored);
+            self.evictions += 1;
+        }
+    }
+}
+
+/// An in-memory store of the latest state of accounts, shared between a
+/// pipeline and its processors.
+///
+/// Cloning an `AccountStore` is cheap and every clone reads and writes the
+/// same accounts.
+#[derive(Clone)]
+pub struct AccountStore {
+    config: Arc<AccountStoreConfig>,
+    state: Arc<RwLock<StoreState>>,
+}
+
+impl AccountStore {
+    /// Creates an empty `AccountStore`.
+    pub fn new(config: AccountStoreConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Arc::new(RwLock::new(StoreState::default())),
+        }
+    }
+
+    /// Returns the latest state of an account.
+    pub fn get(&self, pubkey: &Pubkey) -> Option<StoredAccount> {
+        self.read().accounts.get(pubkey).cloned()
+    }
+
+    /// Decodes the latest state of an account with `decoder`.
+    ///
+    /// Returns `None` if the account is not stored or the decoder does not
+    /// recognize it.
+    pub fn get_decoded<T>(
+        &self,
+        pubkey: &Pubkey,
+        decoder: &impl for<'a> AccountDecoder<'a, AccountType = T>,
+    ) -> Option<DecodedAccount<T>> {
+        let stored = self.get(pubkey)?;
+        decoder.decode_account(&stored.account)
+    }
+
+    /// Returns the stored accounts owned by `owner`.
+    pub fn get_by_owner(&self, owner: &Pubkey) -> Vec<(Pubkey, StoredAccount)> {
+        let state = self.read();
+        state
+            .owners
+            .get(owner)
+            .into_iter()
+            .flatten()
+            .filter_map(|pubkey| {
+                state
+                    .accounts
+                    .get(pubkey)
+                    .map(|stored| (*pubkey, stored.clone()))
+            })
+            .collect()
+    }
+
+    /// Decodes the stored accounts owned by `owner` with `decoder`, skipping
+    /// the ones it does not recognize.
+    pub fn get_decoded_by_owner<T>(
+        &self,
+        owner: &Pubkey,
+        decoder: &impl for<'a> AccountDecoder<'a, AccountType = T>,
+    ) -> Vec<(Pubkey, DecodedAccount<T>)> {
+        self.get_by_owner(owner)
+            .into_iter()
+            .filter_map(|(pubkey, stored)| {
+                decoder
+                    .decode_account(&stored.account)
+                    .map(|decoded| (pubkey, decoded))
+            })
+            .collect()
+    }
+
+    /// Returns the number of stored accounts.
+    pub fn len(&self) -> usize {
+        self.read().accounts.len()
+    }
+
+    /// Returns whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.read().accounts.is_empty()
+    }
+
+    /// Returns the estimated size of the store, in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.read().bytes
+    }
+
+    /// Fills the store from a datasource, until the datasource returns.
+    ///
+    /// Only account updates are stored; other updates are ignored. Use it with
+    /// datasources that list existing accounts and then return, such as
+    /// `GpaDatasource` or `SnapshotDatasource`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the datasource failed with.
+    pub async fn seed(
+        &self,
+        datasource: impl Datasource,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<usize> {
+        let (sender, mut receiver) = mpsc::channel(1_000);
+        let id = DatasourceId::new_named("account_store_seed");
+        let consume = datasource.consume(id, sender, CancellationToken::new(), metrics);
+        tokio::pin!(consume);
+
+        let mut seeded = 0;
+        let mut result = None;
+        loop {
+            tokio::select! {
+                consumed = &mut consume, if result.is_none() => {
+                    result = Some(consumed);
+                }
+                update = receiver.recv() => match update {
+                    Some((update @ Update::Account(_), _)) => {
+                        self.apply(&update);
+                        seeded += 1;
+                    }
+                    Some(_) => {}
+                    None => break,
+                },
+            }
+        }
+
+        result.unwrap_or(Ok(()))?;
+
+        log::info!("seeded the account store with {seeded} accounts.");
+
+        Ok(seeded)
+    }
+
+    /// Writes an account update to the store, or removes a deleted account.
+    ///
+    /// Account updates and deletions older than the stored state of the
+    /// account are ignored. An account updated to an owner that is not
+    /// tracked, such as an account closed to the System Program, is removed.
+    pub(crate) fn apply(&self, update: &Update) {
+        match update {
+            Update::Account(account_update) => {
+                if !self.config.owners.is_empty()
+                    && !self.config.owners.contains(&account_update.account.owner)
+                {
+                    self.write()
+                        .remove_at(&account_update.pubkey, account_update.slot);
+                    return;
+                }
+
+                self.write().insert(
+                    account_update.pubkey,
+                    account_update.slot,
+                    account_update.account.clone(),
+                    self.config.max_bytes,
+                );
+            }
+            Update::AccountDeletion(account_deletion) => {
+                self.write()
+                    .remove_at(&account_deletion.pubkey, account_deletion.slot);
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports the size of the store and the evictions since the last call.
+    pub(crate) async fn record_metrics(&self, metrics: &MetricsCollection) -> CarbonResult<()> {
+        let (accounts, bytes, evictions) = {
+            let mut state = self.write();
+            (
+                state.accounts.len(),
+                state.bytes,
+                std::mem::take(&mut state.evictions),
+            )
+        };
+
+        metrics
+            .update_gauge("account_store_accounts", accounts as f64)
+            .await?;
+        metrics
+            .update_gauge("account_store_bytes", bytes as f64)
+            .await?;
+        metrics
+            .increment_counter("account_store_evictions", evictions)
+            .await
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, StoreState> {
+        self.state.read().expect("account store lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, StoreState> {
+        self.state.write().expect("account store lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::datasource::{AccountDeletion, AccountUpdate},
+    };
+
+    fn account_update(pubkey: Pubkey, owner: Pubkey, slot: u64, data: Vec<u8>) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey,
+            account: Account {
+                data,
+                owner,
+                ..Account::default()
+            },
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    #[test]
+    fn test_keeps_latest_state_and_owner_index() {
+        let store = AccountStore::new(AccountStoreConfig::default());
+        let (pubkey, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        store.apply(&account_update(pubkey, owner, 10, vec![1]));
+        store.apply(&account_update(pubkey, owner, 9, vec![2]));
+        assert_eq!(store.get(&pubkey).unwrap().account.data, vec![1]);
+        assert_eq!(store.get_by_owner(&owner).len(), 1);
+
+        store.apply(&Update::AccountDeletion(AccountDeletion {
+            pubkey,
+            slot: 9,
+            transaction_signature: None,
+        }));
+        assert!(store.get(&pubkey).is_some());
+
+        store.apply(&Update::AccountDeletion(AccountDeletion {
+            pubkey,
+            slot: 11,
+            transaction_signature: None,
+        }));
+        assert!(store.get(&pubkey).is_none());
+        assert!(store.get_by_owner(&owner).is_empty());
+        assert_eq!(store.size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_updated_over_cap() {
+        let store = AccountStore::new(AccountStoreConfig {
+            max_bytes: 2 * (ACCOUNT_OVERHEAD_BYTES + 10),
+            owners: vec![],
+        });
+        let owner = Pubkey::new_unique();
+        let pubkeys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+
+        store.apply(&account_update(pubkeys[0], owner, 1, vec![0; 10]));
+        store.apply(&account_update(pubkeys[1], owner, 1, vec![0; 10]));
+        store.apply(&account_update(pubkeys[0], owner, 2, vec![0; 10]));
+        store.apply(&account_update(pubkeys[2], owner, 2, vec![0; 10]));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get(&pubkeys[1]).is_none());
+        assert_eq!(store.get_by_owner(&owner).len(), 2);
+    }
+
+    #[test]
+    fn test_ignores_other_owners() {
+        let owner = Pubkey::new_unique();
+        let store = AccountStore::new(AccountStoreConfig {
+            owners: vec![owner],
+            ..Default::default()
+        });
+
+        store.apply(&account_update(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+            vec![],
+        ));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_removes_accounts_moved_to_other_owners() {
+        let owner = Pubkey::new_unique();
+        let store = AccountStore::new(AccountStoreConfig {
+            owners: vec![owner],
+            ..Default::default()
+        });
+        let pubkey = Pubkey::new_unique();
+
+        store.apply(&account_update(pubkey, owner, 10, vec![1]));
+        store.apply(&account_update(pubkey, Pubkey::default(), 9, vec![]));
+        assert!(store.get(&pubkey).is_some());
+
+        store.apply(&account_update(pubkey, Pubkey::default(), 11, vec![]));
+        assert!(store.get(&pubkey).is_none());
+        assert!(store.get_by_owner(&owner).is_empty());
+    }
+}
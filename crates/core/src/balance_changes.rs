@@ -0,0 +1,556 @@
+//! Extracts the SOL and token balance changes of a transaction, and the token
+//! transfers made by each instruction.
+//!
+//! The status metadata of a transaction records the lamports of every account
+//! and the token amount of every token account, before and after the
+//! transaction. `TransactionMetadata::sol_balance_changes` and
+//! `TransactionMetadata::token_balance_changes` turn them into typed deltas,
+//! and `TransactionMetadata::owner_token_balance_changes` sums the token
+//! deltas per owner and mint. `NestedInstruction::token_transfers` follows the
+//! SPL Token and Token-2022 transfer instructions an instruction made, to
+//! attribute token movements to it.
+//!
+//! # Example
+//!
+//! ```ignore
+//! for change in transaction_metadata.owner_token_balance_changes() {
+//!     log::info!(
+//!         "{} received {} of mint {}",
+//!         change.owner,
+//!         change.change,
+//!         change.mint,
+//!     );
+//! }
+//!
+//! for transfer in nested_instruction.token_transfers() {
+//!     log::info!(
+//!         "{} -> {}: {} of mint {:?}",
+//!         transfer.source,
+//!         transfer.destination,
+//!         transfer.amount,
+//!         transfer.mint,
+//!     );
+//! }
+//! ```
+//!
+//! # Notes
+//!
+//! - Only accounts whose balance changed are returned.
+//! - `Transfer` instructions don't name their mint. The mint and decimals of
+//!   such transfers are looked up in the token balances of the transaction, and
+//!   are `None` if the source account has no recorded balance.
+//! - Token-2022 `TransferCheckedWithFee` instructions are returned with the fee
+//!   withheld from the destination. Confidential transfers are left out, as
+//!   their amounts are encrypted.
+//! - Token accounts without a recorded owner are left out of the owner
+//!   aggregation, but still appear in `token_balance_changes`.
+
+use {
+    crate::{instruction::NestedInstruction, transaction::TransactionMetadata},
+    solana_pubkey::Pubkey,
+    solana_transaction_status::TransactionTokenBalance,
+    std::{collections::BTreeMap, str::FromStr},
+};
+
+/// The address of the SPL Token program.
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// The address of the SPL Token-2022 program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+const TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+
+/// A change in the lamports of an account.
+///
+/// # Fields
+///
+/// - `account`: The account whose lamports changed.
+/// - `pre_balance`: The lamports of the account before the transaction.
+/// - `post_balance`: The lamports of the account after the transaction.
+/// - `change`: The lamports the account gained, or lost if negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolBalanceChange {
+    pub account: Pubkey,
+    pub pre_balance: u64,
+    pub post_balance: u64,
+    pub change: i128,
+}
+
+/// A change in the token amount of a token account.
+///
+/// # Fields
+///
+/// - `account`: The token account whose amount changed.
+/// - `owner`: The owner of the token account, if recorded.
+/// - `mint`: The mint of the token account.
+/// - `program_id`: The token program that owns the token account, if
+///   recorded.
+/// - `decimals`: The decimals of the mint.
+/// - `pre_amount`: The amount held before the transaction, in base units. Zero
+///   if the account was created by the transaction.
+/// - `post_amount`: The amount held after the transaction, in base units. Zero
+///   if the account was closed by the transaction.
+/// - `change`: The amount the account gained, or lost if negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBalanceChange {
+    pub account: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub program_id: Option<Pubkey>,
+    pub decimals: u8,
+    pub pre_amount: u64,
+    pub post_amount: u64,
+    pub change: i128,
+}
+
+/// The net change in the token amount an owner holds of a mint, summed over
+/// its token accounts.
+///
+/// # Fields
+///
+/// - `owner`: The owner of the token accounts.
+/// - `mint`: The mint of the token accounts.
+/// - `decimals`: The decimals of the mint.
+/// - `pre_amount`: The amount held before the transaction, in base units.
+/// - `post_amount`: The amount held after the transaction, in base units.
+/// - `change`: The amount the owner gained, or lost if negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerTokenBalanceChange {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub pre_amount: u128,
+    pub post_amount: u128,
+    pub change: i128,
+}
+
+/// A transfer made by an SPL Token or Token-2022 `Transfer`,
+/// `TransferChecked` or `TransferCheckedWithFee` instruction.
+///
+/// # Fields
+///
+/// - `program_id`: The token program that made the transfer.
+/// - `source`: The token account the tokens were taken from.
+/// - `destination`: The token account the tokens were sent to.
+/// - `authority`: The owner or delegate that authorized the transfer.
+/// - `mint`: The mint of the tokens, if known.
+/// - `amount`: The amount transferred, in base units.
+/// - `decimals`: The decimals of the mint, if known.
+/// - `fee`: The Token-2022 transfer fee withheld from the amount, for
+///   `TransferCheckedWithFee` instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenTransfer {
+    pub program_id: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub decimals: Option<u8>,
+    pub fee: Option<u64>,
+}
+
+/// The token balance of an account before and after a transaction.
+type TokenBalances<'a> = (
+    Option<&'a TransactionTokenBalance>,
+    Option<&'a TransactionTokenBalance>,
+);
+
+/// Returns the SOL balance changes of a transaction.
+pub fn sol_balance_changes(transaction_metadata: &TransactionMetadata) -> Vec<SolBalanceChange> {
+    let meta = &transaction_metadata.meta;
+
+    transaction_metadata
+        .account_keys()
+        .into_iter()
+        .zip(meta.pre_balances.iter().zip(&meta.post_balances))
+        .filter(|(_, (pre_balance, post_balance))| pre_balance != post_balance)
+        .map(
+            |(account, (&pre_balance, &post_balance))| SolBalanceChange {
+                account,
+                pre_balance,
+                post_balance,
+                change: post_balance as i128 - pre_balance as i128,
+            },
+        )
+        .collect()
+}
+
+/// Returns the token balance changes of a transaction, one per token account.
+pub fn token_balance_changes(
+    transaction_metadata: &TransactionMetadata,
+) -> Vec<TokenBalanceChange> {
+    let account_keys = transaction_metadata.account_keys();
+    let meta = &transaction_metadata.meta;
+    let mut balances: BTreeMap<u8, TokenBalances> = BTreeMap::new();
+
+    for balance in meta.pre_token_balances.iter().flatten() {
+        balances.entry(balance.account_index).or_default().0 = Some(balance);
+    }
+    for balance in meta.post_token_balances.iter().flatten() {
+        balances.entry(balance.account_index).or_default().1 = Some(balance);
+    }
+
+    balances
+        .into_iter()
+        .filter_map(|(account_index, (pre, post))| {
+            let account = *account_keys.get(account_index as usize)?;
+            let balance = post.or(pre)?;
+            let amount = |balance: Option<&TransactionTokenBalance>| {
+                balance.map_or(Some(0), |balance| {
+                    balance.ui_token_amount.amount.parse().ok()
+                })
+            };
+            let (pre_amount, post_amount) = (amount(pre)?, amount(post)?);
+            if pre_amount == post_amount {
+                return None;
+            }
+
+            Some(TokenBalanceChange {
+                account,
+                owner: Pubkey::from_str(&balance.owner).ok(),
+                mint: Pubkey::from_str(&balance.mint).ok()?,
+                program_id: Pubkey::from_str(&balance.program_id).ok(),
+                decimals: balance.ui_token_amount.decimals,
+                pre_amount,
+                post_amount,
+                change: post_amount as i128 - pre_amount as i128,
+            })
+        })
+        .collect()
+}
+
+/// Returns the token balance changes of a transaction summed per owner and
+/// mint, leaving out the owners whose amount of a mint did not change.
+pub fn owner_token_balance_changes(
+    transaction_metadata: &TransactionMetadata,
+) -> Vec<OwnerTokenBalanceChange> {
+    let mut changes: BTreeMap<(Pubkey, Pubkey), OwnerTokenBalanceChange> = BTreeMap::new();
+
+    for change in token_balance_changes(transaction_metadata) {
+        let Some(owner) = change.owner else {
+            continue;
+        };
+
+        let total = changes
+            .entry((owner, change.mint))
+            .or_insert(OwnerTokenBalanceChange {
+                owner,
+                mint: change.mint,
+                decimals: change.decimals,
+                pre_amount: 0,
+                post_amount: 0,
+                change: 0,
+            });
+        total.pre_amount += change.pre_amount as u128;
+        total.post_amount += change.post_amount as u128;
+        total.change += change.change;
+    }
+
+    changes
+        .into_values()
+        .filter(|change| change.change != 0)
+        .collect()
+}
+
+/// Returns the mint and decimals of a token account, from the token balances
+/// of a transaction.
+fn find_mint(transaction_metadata: &TransactionMetadata, account: &Pubkey) -> Option<(Pubkey, u8)> {
+    let account_index = transaction_metadata
+        .account_keys()
+        .iter()
+        .position(|key| key == account)?;
+    let meta = &transaction_metadata.meta;
+
+    meta.pre_token_balances
+        .iter()
+        .chain(&meta.post_token_balances)
+        .flatten()
+        .find(|balance| balance.account_index as usize == account_index)
+        .and_then(|balance| {
+            Some((
+                Pubkey::from_str(&balance.mint).ok()?,
+                balance.ui_token_amount.decimals,
+            ))
+        })
+}
+
+/// Decodes a token transfer from an instruction, if it is one.
+fn decode_transfer(nested_instruction: &NestedInstruction) -> Option<TokenTransfer> {
+    let instruction = &nested_instruction.instruction;
+    if instruction.program_id != TOKEN_PROGRAM_ID && instruction.program_id != TOKEN_2022_PROGRAM_ID
+    {
+        return None;
+    }
+
+    let (tag, mut data) = instruction.data.split_first()?;
+    if *tag == TRANSFER_FEE_EXTENSION {
+        if instruction.program_id != TOKEN_2022_PROGRAM_ID
+            || data.first() != Some(&TRANSFER_CHECKED_WITH_FEE)
+        {
+            return None;
+        }
+        data = &data[1..];
+    }
+    let u64_at = |offset: usize| {
+        data.get(offset..offset + 8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+    };
+    let amount = u64_at(0)?;
+    let account = |position: usize| instruction.accounts.get(position).map(|meta| meta.pubkey);
+
+    match *tag {
+        TRANSFER => {
+            let source = account(0)?;
+            let mint = find_mint(&nested_instruction.metadata.transaction_metadata, &source);
+
+            Some(TokenTransfer {
+                program_id: instruction.program_id,
+                source,
+                destination: account(1)?,
+                authority: account(2)?,
+                mint: mint.map(|(mint, _)| mint),
+                amount,
+                decimals: mint.map(|(_, decimals)| decimals),
+                fee: None,
+            })
+        }
+        TRANSFER_CHECKED | TRANSFER_FEE_EXTENSION => Some(TokenTransfer {
+            program_id: instruction.program_id,
+            source: account(0)?,
+            destination: account(2)?,
+            authority: account(3)?,
+            mint: Some(account(1)?),
+            amount,
+            decimals: Some(*data.get(8)?),
+            fee: if *tag == TRANSFER_FEE_EXTENSION {
+                Some(u64_at(9)?)
+            } else {
+                None
+            },
+        }),
+        _ => None,
+    }
+}
+
+impl NestedInstruction {
+    /// Returns the token transfers made by this instruction, including the ones
+    /// made by its inner instructions, in execution order.
+    pub fn token_transfers(&self) -> Vec<TokenTransfer> {
+        fn collect(nested_instruction: &NestedInstruction, transfers: &mut Vec<TokenTransfer>) {
+            transfers.extend(decode_transfer(nested_instruction));
+            for inner_instruction in nested_instruction.inner_instructions.iter() {
+                collect(inner_instruction, transfers);
+            }
+        }
+
+        let mut transfers = Vec::new();
+        collect(self, &mut transfers);
+        transfers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::instruction::{InstructionMetadata, NestedInstructions},
+        solana_account_decoder_client_types::token::UiTokenAmount,
+        solana_instruction::{AccountMeta, Instruction},
+        solana_message::{legacy::Message, VersionedMessage},
+        solana_transaction_status::TransactionStatusMeta,
+        std::sync::Arc,
+    };
+
+    const MINT: Pubkey = Pubkey::from_str_const("So11111111111111111111111111111111111111112");
+
+    fn token_balance(account_index: u8, owner: &Pubkey, amount: u64) -> TransactionTokenBalance {
+        TransactionTokenBalance {
+            account_index,
+            mint: MINT.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: None,
+                decimals: 9,
+                amount: amount.to_string(),
+                ui_amount_string: String::new(),
+            },
+            owner: owner.to_string(),
+            program_id: TOKEN_PROGRAM_ID.to_string(),
+        }
+    }
+
+    /// The fee payer, two token accounts, the token program, a receiver and
+    /// its token account.
+    fn account_keys() -> Vec<Pubkey> {
+        vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            TOKEN_PROGRAM_ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ]
+    }
+
+    fn transaction(account_keys: Vec<Pubkey>) -> TransactionMetadata {
+        let owner = account_keys[0];
+        let receiver = account_keys[4];
+
+        TransactionMetadata {
+            meta: TransactionStatusMeta {
+                pre_balances: vec![10_000, 2_000, 2_000, 1, 0, 2_000],
+                post_balances: vec![5_000, 2_000, 2_000, 1, 0, 2_000],
+                pre_token_balances: Some(vec![
+                    token_balance(1, &owner, 100),
+                    token_balance(5, &receiver, 10),
+                ]),
+                post_token_balances: Some(vec![
+                    token_balance(1, &owner, 40),
+                    token_balance(2, &receiver, 40),
+                    token_balance(5, &receiver, 30),
+                ]),
+                ..Default::default()
+            },
+            message: VersionedMessage::Legacy(Message {
+                account_keys,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_balance_changes() {
+        let account_keys = account_keys();
+        let transaction_metadata = transaction(account_keys.clone());
+
+        let sol_changes = sol_balance_changes(&transaction_metadata);
+        assert_eq!(sol_changes.len(), 1);
+        assert_eq!(sol_changes[0].account, account_keys[0]);
+        assert_eq!(sol_changes[0].change, -5_000);
+
+        let token_changes = token_balance_changes(&transaction_metadata);
+        assert_eq!(token_changes.len(), 3);
+        assert_eq!(token_changes[0].account, account_keys[1]);
+        assert_eq!(token_changes[0].owner, Some(account_keys[0]));
+        assert_eq!(token_changes[0].change, -60);
+        assert_eq!(token_changes[1].pre_amount, 0);
+        assert_eq!(token_changes[1].change, 40);
+        assert_eq!(token_changes[2].change, 20);
+
+        let mut owner_changes = owner_token_balance_changes(&transaction_metadata);
+        owner_changes.sort_by_key(|change| change.change);
+        assert_eq!(
+            owner_changes,
+            vec![
+                OwnerTokenBalanceChange {
+                    owner: account_keys[0],
+                    mint: MINT,
+                    decimals: 9,
+                    pre_amount: 100,
+                    post_amount: 40,
+                    change: -60,
+                },
+                OwnerTokenBalanceChange {
+                    owner: account_keys[4],
+                    mint: MINT,
+                    decimals: 9,
+                    pre_amount: 10,
+                    post_amount: 70,
+                    change: 60,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_transfers() {
+        let account_keys = account_keys();
+        let transaction_metadata = Arc::new(transaction(account_keys.clone()));
+        let nested =
+            |stack_height: u32, instruction: Instruction, inner: Vec<NestedInstruction>| {
+                NestedInstruction {
+                    metadata: InstructionMetadata {
+                        transaction_metadata: transaction_metadata.clone(),
+                        stack_height,
+                        index: 0,
+                        absolute_path: vec![],
+                    },
+                    instruction,
+                    inner_instructions: NestedInstructions(Arc::new(inner)),
+                }
+            };
+        let transfer = Instruction {
+            program_id: TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(account_keys[1], false),
+                AccountMeta::new(account_keys[2], false),
+                AccountMeta::new_readonly(account_keys[0], true),
+            ],
+            data: [&[TRANSFER][..], &60u64.to_le_bytes()].concat(),
+        };
+        let transfer_with_fee = Instruction {
+            program_id: TOKEN_2022_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(account_keys[1], false),
+                AccountMeta::new_readonly(MINT, false),
+                AccountMeta::new(account_keys[5], false),
+                AccountMeta::new_readonly(account_keys[0], true),
+            ],
+            data: [
+                &[TRANSFER_FEE_EXTENSION, TRANSFER_CHECKED_WITH_FEE][..],
+                &20u64.to_le_bytes(),
+                &[9],
+                &1u64.to_le_bytes(),
+            ]
+            .concat(),
+        };
+        let swap = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: vec![],
+        };
+
+        let transfers = nested(
+            1,
+            swap,
+            vec![
+                nested(2, transfer, vec![]),
+                nested(2, transfer_with_fee, vec![]),
+            ],
+        )
+        .token_transfers();
+
+        assert_eq!(
+            transfers,
+            vec![
+                TokenTransfer {
+                    program_id: TOKEN_PROGRAM_ID,
+                    source: account_keys[1],
+                    destination: account_keys[2],
+                    authority: account_keys[0],
+                    mint: Some(MINT),
+                    amount: 60,
+                    decimals: Some(9),
+                    fee: None,
+                },
+                TokenTransfer {
+                    program_id: TOKEN_2022_PROGRAM_ID,
+                    source: account_keys[1],
+                    destination: account_keys[5],
+                    authority: account_keys[0],
+                    mint: Some(MINT),
+                    amount: 20,
+                    decimals: Some(9),
+                    fee: Some(1),
+                },
+            ]
+        );
+    }
+}
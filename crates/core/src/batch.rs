@@ -0,0 +1,465 @@
+//! Provides the `BatchProcessor` trait and the `BatchingProcessor` adapter,
+//! which collect the inputs of a pipe and process them in batches.
+//!
+//! Sinks such as databases handle one large write far better than many small
+//! ones. A `BatchProcessor` receives a slice of inputs at once, and the
+//! `BatchingProcessor` adapter turns it into a regular `Processor` that can be
+//! added to any pipe. Inputs are buffered until either:
+//!
+//! - the buffer holds `max_batch_size` inputs, or
+//! - `max_linger` has elapsed since the first input of the batch was
+//!   buffered.
+//!
+//! The pipeline also flushes every pipe before committing a checkpoint and on
+//! shutdown, so buffered inputs are not lost when the pipeline stops.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use carbon_core::{batch::BatchConfig, pipeline::Pipeline};
+//!
+//! Pipeline::builder()
+//!     .datasource(block_crawler)
+//!     .instruction_batched(
+//!         TestProgramDecoder,
+//!         PostgresInstructionProcessor::new(pool),
+//!         BatchConfig {
+//!             max_batch_size: 5_000,
+//!             max_linger: Duration::from_secs(1),
+//!         },
+//!     )
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Metrics
+//!
+//! - `batch_size`: A histogram of the number of inputs per flushed batch.
+//! - `batch_flush_duration_milliseconds`: A histogram of the time spent
+//!   processing each batch.
+//! - `batches_failed`: A counter of the batches that failed to process.
+//!
+//! # Notes
+//!
+//! - A batch that fails to process is kept in the buffer and retried as a
+//!   whole with the next flush. If the flush was triggered by an input, that
+//!   input is taken back out of the batch and the error is returned for it,
+//!   to be handled by the pipe's `ErrorPolicy`; `ErrorPolicy::Retry` buffers
+//!   it again and retries the whole batch. Errors of flushes triggered by
+//!   `max_linger` are logged. Whenever a flush fails, the batch that stays
+//!   buffered is flushed again after another `max_linger`, even if no new
+//!   input arrives.
+//! - The checkpoint tracker only counts inputs as processed once a flush of
+//!   every pipe that started after they were buffered has succeeded, so a
+//!   checkpoint never covers inputs that are still buffered.
+
+use {
+    crate::{error::CarbonResult, metrics::MetricsCollection, processor::Processor},
+    async_trait::async_trait,
+    std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    },
+    tokio::sync::Mutex,
+};
+
+/// The default maximum number of inputs per batch.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1_000;
+
+/// The default maximum time an input waits in the buffer.
+pub const DEFAULT_MAX_LINGER: Duration = Duration::from_millis(500);
+
+/// Configures when a `BatchingProcessor` flushes its buffer.
+///
+/// # Fields
+///
+/// - `max_batch_size`: The number of buffered inputs that triggers a flush.
+/// - `max_linger`: The longest time an input waits in the buffer before the
+///   batch is flushed, even if it is not full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    pub max_batch_size: usize,
+    pub max_linger: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_linger: DEFAULT_MAX_LINGER,
+        }
+    }
+}
+
+/// A trait for processing inputs in batches.
+///
+/// # Type Parameters
+///
+/// - `InputType`: The type of a single input. A batch is a slice of inputs,
+///   in the order they were received.
+///
+/// # Required Methods
+///
+/// - `process_batch`: Processes a batch of inputs, for example with a single
+///   multi-row insert. The batch is borrowed, since it is kept and passed
+///   again if processing fails.
+///
+/// # Provided Methods
+///
+/// - `init` and `shutdown`: Called when the pipeline starts and stops, like
+///   the `Processor` hooks of the same name. Do nothing by default.
+///
+/// # Example
+///
+/// ```ignore
+/// use async_trait::async_trait;
+/// use carbon_core::{batch::BatchProcessor, error::CarbonResult, metrics::MetricsCollection};
+/// use std::sync::Arc;
+///
+/// struct TransferWriter { /* ... */ }
+///
+/// #[async_trait]
+/// impl BatchProcessor for TransferWriter {
+///     type InputType = InstructionProcessorInputType<TokenProgramInstruction>;
+///
+///     async fn process_batch(
+///         &mut self,
+///         batch: &[Self::InputType],
+///         metrics: Arc<MetricsCollection>,
+///     ) -> CarbonResult<()> {
+///         // Write the whole batch at once.
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait BatchProcessor {
+    type InputType;
+
+    async fn process_batch(
+        &mut self,
+        batch: &[Self::InputType],
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()>;
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+struct BatchState<P: BatchProcessor> {
+    processor: P,
+    buffer: Vec<P::InputType>,
+    /// Incremented on every flush, so that a linger timer can tell whether
+    /// the batch it was started for has already been flushed.
+    generation: u64,
+}
+
+impl<P: BatchProcessor + Send> BatchState<P>
+where
+    P::InputType: Send + Sync,
+{
+    /// Processes the buffered inputs as one batch. The buffer is only cleared
+    /// once the batch has been processed, so a failed batch is retried as a
+    /// whole by the next flush.
+    async fn flush(&mut self, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        self.generation += 1;
+        let batch_size = self.buffer.len();
+
+        let start = Instant::now();
+        let result = self
+            .processor
+            .process_batch(&self.buffer, metrics.clone())
+            .await;
+
+        metrics
+            .record_histogram("batch_size", batch_size as f64)
+            .await?;
+        metrics
+            .record_histogram(
+                "batch_flush_duration_milliseconds",
+                start.elapsed().as_millis() as f64,
+            )
+            .await?;
+
+        match result {
+            Ok(()) => self.buffer.clear(),
+            Err(_) => metrics.increment_counter("batches_failed", 1).await?,
+        }
+
+        result
+    }
+}
+
+/// Adapts a `BatchProcessor` into a `Processor`, buffering inputs and
+/// flushing them as batches according to a `BatchConfig`.
+///
+/// # Example
+///
+/// ```ignore
+/// use carbon_core::batch::{BatchConfig, BatchingProcessor};
+///
+/// let builder = PipelineBuilder::new().block_details(BatchingProcessor::new(
+///     BlockWriter::new(pool),
+///     BatchConfig::default(),
+/// ));
+/// ```
+pub struct BatchingProcessor<P: BatchProcessor> {
+    state: Arc<Mutex<BatchState<P>>>,
+    config: BatchConfig,
+}
+
+impl<P: BatchProcessor> BatchingProcessor<P> {
+    pub fn new(processor: P, config: BatchConfig) -> Self {
+        log::trace!(
+            "BatchingProcessor::new(processor, max_batch_size: {}, max_linger: {:?})",
+            config.max_batch_size,
+            config.max_linger
+        );
+
+        Self {
+            state: Arc::new(Mutex::new(BatchState {
+                processor,
+                buffer: Vec::with_capacity(config.max_batch_size),
+                generation: 0,
+            })),
+            config,
+        }
+    }
+}
+
+impl<P> BatchingProcessor<P>
+where
+    P: BatchProcessor + Send + 'static,
+    P::InputType: Send + Sync + 'static,
+{
+    /// Flushes the buffer once it has lingered for `max_linger`, unless it
+    /// was flushed in the meantime. A failed flush keeps the batch buffered
+    /// and waits another `max_linger` before flushing it again, so a failed
+    /// batch never stays buffered without a deadline.
+    fn schedule_linger_flush(&self, generation: u64, metrics: Arc<MetricsCollection>) {
+        let state_ref = Arc::downgrade(&self.state);
+        let max_linger = self.config.max_linger;
+
+        tokio::spawn(async move {
+            let mut generation = generation;
+            loop {
+                tokio::time::sleep(max_linger).await;
+
+                let Some(state) = state_ref.upgrade() else {
+                    return;
+                };
+                let mut state = state.lock().await;
+                if state.generation != generation {
+                    return;
+                }
+                match state.flush(&metrics).await {
+                    Ok(()) => return,
+                    Err(error) => {
+                        log::error!("failed to flush batch after linger: {error:?}");
+                        generation = state.generation;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<P> Processor for BatchingProcessor<P>
+where
+    P: BatchProcessor + Send + 'static,
+    P::InputType: Send + Sync + 'static,
+{
+    type InputType = P::InputType;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut state = self.state.lock().await;
+        state.buffer.push(data);
+
+        if state.buffer.len() >= self.config.max_batch_size {
+            let result = state.flush(&metrics).await;
+            if result.is_err() {
+                // The rest of the batch stays buffered. The input that
+                // triggered the flush is handed to the pipe's `ErrorPolicy`.
+                state.buffer.pop();
+                if !state.buffer.is_empty() {
+                    self.schedule_linger_flush(state.generation, metrics);
+                }
+            }
+            return result;
+        }
+
+        if state.buffer.len() == 1 {
+            self.schedule_linger_flush(state.generation, metrics);
+        }
+
+        Ok(())
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.state.lock().await.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        let mut state = self.state.lock().await;
+        let result = state.flush(&metrics).await;
+        if result.is_err() {
+            // The failed batch stays buffered, and is flushed again once it
+            // has lingered for `max_linger`.
+            self.schedule_linger_flush(state.generation, metrics);
+        }
+        result
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        let mut state = self.state.lock().await;
+        state.flush(&metrics).await?;
+        state.processor.shutdown(metrics).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    struct RecordingProcessor {
+        batches: Arc<std::sync::Mutex<Vec<Vec<u32>>>>,
+        /// The number of upcoming batches that fail.
+        failures: Arc<AtomicI64>,
+    }
+
+    #[async_trait]
+    impl BatchProcessor for RecordingProcessor {
+        type InputType = u32;
+
+        async fn process_batch(
+            &mut self,
+            batch: &[Self::InputType],
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            if self.failures.fetch_sub(1, Ordering::SeqCst) > 0 {
+                return Err(crate::error::Error::Custom("write failed".to_string()));
+            }
+            self.batches.lock().unwrap().push(batch.to_vec());
+            Ok(())
+        }
+    }
+
+    fn batching_processor(
+        config: BatchConfig,
+    ) -> (
+        BatchingProcessor<RecordingProcessor>,
+        Arc<std::sync::Mutex<Vec<Vec<u32>>>>,
+    ) {
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let processor = BatchingProcessor::new(
+            RecordingProcessor {
+                batches: batches.clone(),
+                failures: Arc::new(AtomicI64::new(0)),
+            },
+            config,
+        );
+        (processor, batches)
+    }
+
+    #[tokio::test]
+    async fn test_flushes_full_batches_and_on_demand() {
+        let (mut processor, batches) = batching_processor(BatchConfig {
+            max_batch_size: 2,
+            max_linger: Duration::from_secs(3600),
+        });
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+        for input in 0..5 {
+            processor.process(input, metrics.clone()).await.unwrap();
+        }
+        assert_eq!(*batches.lock().unwrap(), vec![vec![0, 1], vec![2, 3]]);
+
+        processor.flush(metrics).await.unwrap();
+        assert_eq!(batches.lock().unwrap().last(), Some(&vec![4]));
+    }
+
+    #[tokio::test]
+    async fn test_flushes_after_linger() {
+        let (mut processor, batches) = batching_processor(BatchConfig {
+            max_batch_size: 100,
+            max_linger: Duration::from_millis(20),
+        });
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+        processor.process(1, metrics.clone()).await.unwrap();
+        processor.process(2, metrics).await.unwrap();
+        assert!(batches.lock().unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1, 2]]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_failed_batch_as_a_whole() {
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut processor = BatchingProcessor::new(
+            RecordingProcessor {
+                batches: batches.clone(),
+                failures: Arc::new(AtomicI64::new(1)),
+            },
+            BatchConfig {
+                max_batch_size: 3,
+                max_linger: Duration::from_secs(3600),
+            },
+        );
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+        processor.process(1, metrics.clone()).await.unwrap();
+        processor.process(2, metrics.clone()).await.unwrap();
+        assert!(processor.process(3, metrics.clone()).await.is_err());
+        assert!(batches.lock().unwrap().is_empty());
+
+        // Retrying the failed input retries the whole batch.
+        processor.process(3, metrics).await.unwrap();
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1, 2, 3]]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_failed_batch_after_linger() {
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut processor = BatchingProcessor::new(
+            RecordingProcessor {
+                batches: batches.clone(),
+                failures: Arc::new(AtomicI64::new(2)),
+            },
+            BatchConfig {
+                max_batch_size: 3,
+                max_linger: Duration::from_millis(20),
+            },
+        );
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+        processor.process(1, metrics.clone()).await.unwrap();
+        processor.process(2, metrics.clone()).await.unwrap();
+        // The first flush fails, leaving both inputs buffered.
+        assert!(processor.process(3, metrics).await.is_err());
+
+        // The second flush fails on the linger deadline, the third succeeds
+        // on the deadline that the failure re-armed.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(*batches.lock().unwrap(), vec![vec![1, 2]]);
+    }
+}
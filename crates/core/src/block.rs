@@ -0,0 +1,431 @@
+//! Provides block pipes, which hand every transaction of a slot to a processor
+//! at once, in block order, together with the `BlockDetails` of the slot.
+//!
+//! Block pipes suit analytics that need a whole block, such as per-block
+//! volume or MEV detection relying on the position of each transaction. They
+//! are registered through `PipelineBuilder::block`.
+//!
+//! # Block Assembly
+//!
+//! The pipeline gathers the transactions of every slot, separately for each
+//! datasource, until the `BlockDetails` of the slot arrive. The block is then
+//! complete: its transactions are sorted by their `index` in the block and
+//! handed to the block pipes along with the block details. Transactions
+//! without an index keep the order they were received in, after the indexed
+//! ones.
+//!
+//! This relies on block-based datasources sending the `BlockDetails` of a
+//! block after its transactions, as `RpcBlockCrawler`, `RpcBlockSubscribe`,
+//! Jetstreamer and Yellowstone block subscriptions do.
+//!
+//! # Timeouts
+//!
+//! A slot whose block details never arrive, for example because its
+//! transactions came from a transaction-based datasource, is dropped once its
+//! first transaction is older than the block timeout (`DEFAULT_BLOCK_TIMEOUT`
+//! unless set with `PipelineBuilder::block_timeout`). Timeouts are checked
+//! four times per block timeout, and at most every
+//! `MIN_BLOCK_EXPIRY_INTERVAL`.
+//!
+//! The pipeline remembers the last `MAX_CLOSED_SLOTS` slots of every
+//! datasource whose block was delivered or dropped. Block details arriving
+//! late or twice for one of them, and transactions arriving after them, are
+//! dropped rather than delivered as an incomplete block.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use async_trait::async_trait;
+//! use carbon_core::{
+//!     block::BlockProcessorInputType, error::CarbonResult, metrics::MetricsCollection,
+//!     processor::Processor,
+//! };
+//! use std::sync::Arc;
+//!
+//! struct BlockVolumeProcessor;
+//!
+//! #[async_trait]
+//! impl Processor for BlockVolumeProcessor {
+//!     type InputType = BlockProcessorInputType;
+//!
+//!     async fn process(
+//!         &mut self,
+//!         (block_details, transactions): Self::InputType,
+//!         _metrics: Arc<MetricsCollection>,
+//!     ) -> CarbonResult<()> {
+//!         let fees: u64 = transactions.iter().map(|transaction| transaction.meta.fee).sum();
+//!         println!("slot {}: {} transactions, {fees} lamports of fees", block_details.slot, transactions.len());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! Pipeline::builder()
+//!     .datasource(block_crawler)
+//!     .block(BlockVolumeProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Metrics
+//!
+//! - `blocks_processed`: A counter of the blocks handed to the block pipes.
+//! - `blocks_timed_out`: A counter of the slots dropped because their block
+//!   details never arrived.
+//! - `block_details_dropped`: A counter of the block details that arrived for
+//!   a slot whose block was already delivered or dropped.
+
+use crate::datasource::{BlockDetails, DatasourceId, Update};
+use crate::error::CarbonResult;
+use crate::error_policy::ErrorPolicy;
+use crate::filter::Filter;
+use crate::metrics::MetricsCollection;
+use crate::processor::Processor;
+use crate::transaction::TransactionMetadata;
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The default time the pipeline waits for the block details of a slot after
+/// receiving its first transaction.
+pub const DEFAULT_BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The shortest interval at which the pipeline checks for expired blocks,
+/// whatever the block timeout.
+pub const MIN_BLOCK_EXPIRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The number of delivered or dropped slots remembered per datasource, so that
+/// late or duplicate updates for them are dropped.
+pub const MAX_CLOSED_SLOTS: usize = 4_096;
+
+/// The input type for the block processor.
+///
+/// - `BlockDetails`: The details of the block.
+/// - `Vec<Arc<TransactionMetadata>>`: The transactions of the block, sorted by
+///   their index in the block.
+pub type BlockProcessorInputType = (BlockDetails, Vec<Arc<TransactionMetadata>>);
+
+/// A pipe for processing whole blocks using a defined processor.
+///
+/// ## Fields
+///
+/// - `processor`: A `Processor` that processes complete blocks.
+/// - `filters`: A collection of filters that determine which blocks should be
+///   processed. Each filter in this collection is applied to incoming blocks,
+///   and only blocks that pass all filters (return `true`) will be processed.
+///   If this collection is empty, all blocks are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+pub struct BlockPipe {
+    pub processor: Box<dyn Processor<InputType = BlockProcessorInputType> + Send + Sync>,
+    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub error_policy: ErrorPolicy,
+}
+
+/// An async trait for processing whole blocks.
+///
+/// # Required Methods
+///
+/// - `run`: Processes a block and tracks the operation with metrics.
+/// - `filters`: Returns a reference to the filters associated with this pipe,
+///   which are used by the pipeline to determine which blocks should be
+///   processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+#[async_trait]
+pub trait BlockPipes: Send + Sync {
+    async fn run(
+        &mut self,
+        block: BlockProcessorInputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()>;
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BlockPipes for BlockPipe {
+    async fn run(
+        &mut self,
+        block: BlockProcessorInputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        log::trace!(
+            "BlockPipe::run(slot: {}, transactions: {}, metrics)",
+            block.0.slot,
+            block.1.len()
+        );
+
+        self.processor.process(block, metrics).await?;
+
+        Ok(())
+    }
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
+        &self.filters
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+}
+
+/// The transactions received so far for a slot of a datasource.
+struct PendingBlock {
+    first_received: Instant,
+    transactions: Vec<Arc<TransactionMetadata>>,
+}
+
+/// Gathers the transactions of every slot until its block details arrive.
+///
+/// The assembler observes updates in the order they were received, before
+/// they are dispatched, so that blocks are complete even when updates are
+/// processed concurrently. Completed blocks are kept until the block details
+/// update that completed them is processed.
+pub(crate) struct BlockAssembler {
+    timeout: Duration,
+    pending: HashMap<(DatasourceId, u64), PendingBlock>,
+    completed: HashMap<(DatasourceId, u64), Vec<Arc<TransactionMetadata>>>,
+    /// The slots of every datasource whose block was completed or expired.
+    closed: HashMap<DatasourceId, BTreeSet<u64>>,
+}
+
+impl BlockAssembler {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pending: HashMap::new(),
+            completed: HashMap::new(),
+            closed: HashMap::new(),
+        }
+    }
+
+    fn is_closed(&self, datasource_id: &DatasourceId, slot: u64) -> bool {
+        self.closed
+            .get(datasource_id)
+            .is_some_and(|slots| slots.contains(&slot))
+    }
+
+    fn close(&mut self, datasource_id: &DatasourceId, slot: u64) {
+        let slots = self.closed.entry(datasource_id.clone()).or_default();
+        slots.insert(slot);
+        while slots.len() > MAX_CLOSED_SLOTS {
+            slots.pop_first();
+        }
+    }
+
+    /// Adds a transaction to its pending block, or completes the block of a
+    /// slot when its block details arrive. Updates for a slot whose block was
+    /// already completed or expired are dropped.
+    pub(crate) fn observe(&mut self, update: &Update, datasource_id: &DatasourceId) {
+        match update {
+            Update::Transaction(transaction_update) => {
+                if self.is_closed(datasource_id, transaction_update.slot) {
+                    log::debug!(
+                        "dropping transaction {} of closed block {}",
+                        transaction_update.signature,
+                        transaction_update.slot
+                    );
+                    return;
+                }
+
+                let transaction = match TransactionMetadata::try_from(&**transaction_update) {
+                    Ok(transaction) => Arc::new(transaction),
+                    Err(error) => {
+                        log::warn!(
+                            "leaving transaction {} out of its block: {error}",
+                            transaction_update.signature
+                        );
+                        return;
+                    }
+                };
+
+                self.pending
+                    .entry((datasource_id.clone(), transaction_update.slot))
+                    .or_insert_with(|| PendingBlock {
+                        first_received: Instant::now(),
+                        transactions: Vec::new(),
+                    })
+                    .transactions
+                    .push(transaction);
+            }
+            Update::BlockDetails(block_details) => {
+                if self.is_closed(datasource_id, block_details.slot) {
+                    return;
+                }
+                self.close(datasource_id, block_details.slot);
+
+                let key = (datasource_id.clone(), block_details.slot);
+                let mut transactions = self
+                    .pending
+                    .remove(&key)
+                    .map(|pending| pending.transactions)
+                    .unwrap_or_default();
+
+                transactions.sort_by_key(|transaction| transaction.index.unwrap_or(u64::MAX));
+                self.completed.insert(key, transactions);
+            }
+            _ => {}
+        }
+    }
+
+    /// Takes the transactions of a completed block, or returns `None` if the
+    /// block details were dropped because the block was already completed or
+    /// expired.
+    pub(crate) fn take(
+        &mut self,
+        datasource_id: &DatasourceId,
+        slot: u64,
+    ) -> Option<Vec<Arc<TransactionMetadata>>> {
+        self.completed.remove(&(datasource_id.clone(), slot))
+    }
+
+    /// Drops the pending blocks whose first transaction is older than the
+    /// timeout, returning how many were dropped.
+    pub(crate) fn expire(&mut self) -> usize {
+        let timeout = self.timeout;
+        let expired: Vec<(DatasourceId, u64)> = self
+            .pending
+            .iter()
+            .filter(|(_, block)| block.first_received.elapsed() >= timeout)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for (datasource_id, slot) in &expired {
+            self.pending.remove(&(datasource_id.clone(), *slot));
+            self.close(datasource_id, *slot);
+        }
+
+        expired.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::datasource::TransactionUpdate,
+        solana_message::{legacy::Message, VersionedMessage},
+        solana_pubkey::Pubkey,
+        solana_signature::Signature,
+        solana_transaction::versioned::VersionedTransaction,
+    };
+
+    fn transaction(slot: u64, index: Option<u64>) -> Update {
+        Update::Transaction(Box::new(TransactionUpdate {
+            signature: Signature::new_unique(),
+            transaction: VersionedTransaction {
+                signatures: vec![],
+                message: VersionedMessage::Legacy(Message {
+                    account_keys: vec![Pubkey::new_unique()],
+                    ..Default::default()
+                }),
+            },
+            meta: Default::default(),
+            is_vote: false,
+            slot,
+            index,
+            block_time: None,
+            block_hash: None,
+        }))
+    }
+
+    fn block_details(slot: u64) -> Update {
+        Update::BlockDetails(BlockDetails {
+            slot,
+            block_hash: None,
+            previous_block_hash: None,
+            rewards: None,
+            num_reward_partitions: None,
+            block_time: None,
+            block_height: None,
+        })
+    }
+
+    #[test]
+    fn test_assembles_blocks_in_index_order() {
+        let mut assembler = BlockAssembler::new(DEFAULT_BLOCK_TIMEOUT);
+        let id = DatasourceId::new_named("crawler");
+        let other = DatasourceId::new_named("other");
+
+        assembler.observe(&transaction(10, Some(2)), &id);
+        assembler.observe(&transaction(10, None), &id);
+        assembler.observe(&transaction(10, Some(0)), &id);
+        assembler.observe(&transaction(11, Some(0)), &id);
+        assembler.observe(&transaction(10, Some(1)), &other);
+        assembler.observe(&block_details(10), &id);
+
+        let indexes: Vec<_> = assembler
+            .take(&id, 10)
+            .unwrap()
+            .iter()
+            .map(|transaction| transaction.index)
+            .collect();
+        assert_eq!(indexes, vec![Some(0), Some(2), None]);
+        assert!(assembler.take(&id, 10).is_none());
+        assert_eq!(assembler.pending.len(), 2);
+
+        assembler.observe(&block_details(12), &id);
+        assert_eq!(assembler.take(&id, 12).map(|block| block.len()), Some(0));
+    }
+
+    #[test]
+    fn test_drops_late_block_details_of_expired_blocks() {
+        let mut assembler = BlockAssembler::new(Duration::ZERO);
+        let id = DatasourceId::new_named("crawler");
+
+        assembler.observe(&transaction(10, Some(0)), &id);
+        assert_eq!(assembler.expire(), 1);
+
+        assembler.observe(&transaction(10, Some(1)), &id);
+        assembler.observe(&block_details(10), &id);
+        assert!(assembler.take(&id, 10).is_none());
+        assert!(assembler.pending.is_empty());
+    }
+
+    #[test]
+    fn test_drops_duplicate_block_details() {
+        let mut assembler = BlockAssembler::new(DEFAULT_BLOCK_TIMEOUT);
+        let id = DatasourceId::new_named("crawler");
+
+        assembler.observe(&transaction(10, Some(0)), &id);
+        assembler.observe(&block_details(10), &id);
+        assert_eq!(assembler.take(&id, 10).map(|block| block.len()), Some(1));
+
+        assembler.observe(&block_details(10), &id);
+        assert!(assembler.take(&id, 10).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::datasource::BlockDetails;
 use crate::error::CarbonResult;
+use crate::error_policy::ErrorPolicy;
 use crate::filter::Filter;
 use crate::metrics::MetricsCollection;
 use crate::processor::Processor;
@@ -20,9 +21,11 @@ use std::sync::Arc;
 ///   incoming block details updates, and only updates that pass all filters
 ///   (return `true`) will be processed. If this collection is empty, all
 ///   updates are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
 pub struct BlockDetailsPipe {
     pub processor: Box<dyn Processor<InputType = BlockDetails> + Send + Sync>,
     pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub error_policy: ErrorPolicy,
 }
 
 /// An async trait for processing block details.
@@ -36,6 +39,13 @@ pub struct BlockDetailsPipe {
 /// - `filters`: Returns a reference to the filters associated with this pipe,
 ///   which are used by the pipeline to determine which block details events
 ///   should be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
 #[async_trait]
 pub trait BlockDetailsPipes: Send + Sync {
     async fn run(
@@ -45,6 +55,22 @@ pub trait BlockDetailsPipes: Send + Sync {
     ) -> CarbonResult<()>;
 
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -64,4 +90,20 @@ impl BlockDetailsPipes for BlockDetailsPipe {
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
         &self.filters
     }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
 }
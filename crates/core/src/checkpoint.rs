@@ -0,0 +1,1012 @@
+//! Provides durable checkpoints that let a pipeline resume where it left off
+//! after a restart.
+//!
+//! The pipeline keeps track of how far it has processed the updates of each
+//! datasource and periodically commits that position to a `CheckpointStore`.
+//! Datasources that support resuming read their checkpoint from the same store
+//! when `consume` is called and start from there instead of their configured
+//! starting point.
+//!
+//! # Overview
+//!
+//! - **`Checkpoint`**: The last fully processed slot of a datasource, along with
+//!   the signature of the last transaction processed in or before that slot.
+//! - **`CheckpointStore`**: A trait for loading and committing checkpoints,
+//!   keyed by `DatasourceId`.
+//! - **`CursorHandle`**: Lets a datasource whose position isn't a slot, such
+//!   as the RPC transaction crawler, commit its own cursor once the updates it
+//!   sent before it have been processed.
+//! - **`FileCheckpointStore`**: Stores checkpoints in a JSON file on disk.
+//! - **`PostgresCheckpointStore`**: Stores checkpoints in a PostgreSQL table
+//!   (requires the `postgres` feature, see
+//!   `postgres::checkpoint`).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use carbon_core::{checkpoint::FileCheckpointStore, datasource::DatasourceId};
+//!
+//! let store = Arc::new(FileCheckpointStore::new("checkpoints.json")?);
+//!
+//! carbon_core::pipeline::Pipeline::builder()
+//!     .datasource_with_id(
+//!         block_crawler.with_checkpoint_store(store.clone()),
+//!         DatasourceId::new_named("block-crawler"),
+//!     )
+//!     .checkpoint_store(store)
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Notes
+//!
+//! - Checkpoints are keyed by `DatasourceId`, so datasources must be
+//!   registered with a stable, named ID (see `DatasourceId::new_named`) for
+//!   their checkpoints to be found again after a restart.
+//! - A slot is considered fully processed once every update received for it
+//!   has been processed and an update for a later slot has been received, or
+//!   all datasources have finished.
+//! - An update only counts as processed once the pipes have been flushed
+//!   after it, so that updates still buffered by a `BatchingProcessor` are
+//!   not checkpointed before they are written.
+//! - An update that a pipe failed to process only counts as processed once it
+//!   was stored by the pipeline's `DeadLetterSink`. Without one, the
+//!   checkpoint stays before the slot of the failed update, so that it is
+//!   processed again after a restart.
+
+use {
+    crate::{
+        datasource::{DatasourceId, Update},
+        error::{CarbonResult, Error},
+    },
+    async_trait::async_trait,
+    serde::{Deserialize, Serialize},
+    solana_signature::Signature,
+    std::{
+        collections::{BTreeMap, HashMap, VecDeque},
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// The position up to which a datasource's updates have been processed.
+///
+/// # Fields
+///
+/// - `slot`: The last slot for which every received update has been
+///   processed.
+/// - `signature`: The signature of the last transaction processed in or before
+///   `slot`, if any. Signature-based datasources such as the RPC transaction
+///   crawler resume from this signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub slot: u64,
+    pub signature: Option<Signature>,
+}
+
+/// Defines the interface for durable storage of checkpoints.
+///
+/// Implementations persist one `Checkpoint` per `DatasourceId`, and one
+/// cursor for datasources that track their own position, see `CursorHandle`.
+/// The pipeline calls `commit` and `commit_cursor` periodically while running
+/// and once more at shutdown, and datasources call `load` or `load_cursor`
+/// when they start consuming.
+///
+/// # Example
+///
+/// ```ignore
+/// use async_trait::async_trait;
+/// use carbon_core::{
+///     checkpoint::{Checkpoint, CheckpointStore},
+///     datasource::DatasourceId,
+///     error::CarbonResult,
+/// };
+///
+/// struct RedisCheckpointStore { /* ... */ }
+///
+/// #[async_trait]
+/// impl CheckpointStore for RedisCheckpointStore {
+///     async fn load(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<Checkpoint>> {
+///         // Read the checkpoint for `datasource_id`
+///     }
+///
+///     async fn commit(
+///         &self,
+///         datasource_id: &DatasourceId,
+///         checkpoint: Checkpoint,
+///     ) -> CarbonResult<()> {
+///         // Persist the checkpoint for `datasource_id`
+///     }
+///
+///     async fn load_cursor(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<String>> {
+///         // Read the cursor for `datasource_id`
+///     }
+///
+///     async fn commit_cursor(
+///         &self,
+///         datasource_id: &DatasourceId,
+///         cursor: String,
+///     ) -> CarbonResult<()> {
+///         // Persist the cursor for `datasource_id`
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Loads the last committed checkpoint of a datasource, if any.
+    async fn load(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<Checkpoint>>;
+
+    /// Persists the checkpoint of a datasource, replacing any previous one.
+    async fn commit(
+        &self,
+        datasource_id: &DatasourceId,
+        checkpoint: Checkpoint,
+    ) -> CarbonResult<()>;
+
+    /// Loads the last committed cursor of a datasource, if any.
+    async fn load_cursor(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<String>>;
+
+    /// Persists the cursor of a datasource, replacing any previous one.
+    ///
+    /// Cursors are stored separately from checkpoints, see `CursorHandle`.
+    async fn commit_cursor(&self, datasource_id: &DatasourceId, cursor: String)
+        -> CarbonResult<()>;
+}
+
+impl std::fmt::Debug for dyn CheckpointStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CheckpointStore")
+    }
+}
+
+/// The serialized form of a `Checkpoint`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredCheckpoint {
+    slot: u64,
+    signature: Option<String>,
+}
+
+impl From<Checkpoint> for StoredCheckpoint {
+    fn from(checkpoint: Checkpoint) -> Self {
+        Self {
+            slot: checkpoint.slot,
+            signature: checkpoint.signature.map(|signature| signature.to_string()),
+        }
+    }
+}
+
+impl TryFrom<StoredCheckpoint> for Checkpoint {
+    type Error = Error;
+
+    fn try_from(stored: StoredCheckpoint) -> Result<Self, Self::Error> {
+        let signature = stored
+            .signature
+            .map(|signature| {
+                Signature::from_str(&signature)
+                    .map_err(|err| Error::Custom(format!("invalid checkpoint signature: {err}")))
+            })
+            .transpose()?;
+
+        Ok(Checkpoint {
+            slot: stored.slot,
+            signature,
+        })
+    }
+}
+
+/// The serialized form of the checkpoints and cursors of a
+/// `FileCheckpointStore`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredCheckpoints {
+    #[serde(default)]
+    checkpoints: HashMap<String, StoredCheckpoint>,
+    #[serde(default)]
+    cursors: HashMap<String, String>,
+}
+
+/// A `CheckpointStore` that keeps all checkpoints in a single JSON file.
+///
+/// The file maps each datasource ID to its checkpoint and cursor. Commits
+/// rewrite the whole file through a temporary file and a rename, so a crash
+/// while writing never leaves a truncated file behind.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use carbon_core::checkpoint::FileCheckpointStore;
+///
+/// let store = Arc::new(FileCheckpointStore::new("/var/lib/indexer/checkpoints.json")?);
+/// ```
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    checkpoints: tokio::sync::Mutex<StoredCheckpoints>,
+}
+
+impl FileCheckpointStore {
+    /// Opens the checkpoint file at `path`, reading any checkpoints already
+    /// stored in it. The file is created on the first commit if it does not
+    /// exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn new(path: impl Into<PathBuf>) -> CarbonResult<Self> {
+        let path = path.into();
+        log::trace!("FileCheckpointStore::new(path: {path:?})");
+
+        let checkpoints = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data).map_err(|err| {
+                Error::Custom(format!("failed to parse checkpoint file {path:?}: {err}"))
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => StoredCheckpoints::default(),
+            Err(err) => {
+                return Err(Error::Custom(format!(
+                    "failed to read checkpoint file {path:?}: {err}"
+                )))
+            }
+        };
+
+        Ok(Self {
+            path,
+            checkpoints: tokio::sync::Mutex::new(checkpoints),
+        })
+    }
+
+    /// Returns the path of the checkpoint file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes `checkpoints` to the checkpoint file.
+    async fn write(&self, checkpoints: &StoredCheckpoints) -> CarbonResult<()> {
+        let data = serde_json::to_vec_pretty(checkpoints)
+            .map_err(|err| Error::Custom(format!("failed to serialize checkpoints: {err}")))?;
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let temporary_path = path.with_extension("tmp");
+            std::fs::write(&temporary_path, data)?;
+            std::fs::rename(&temporary_path, &path)
+        })
+        .await
+        .map_err(|err| Error::Custom(format!("failed to write checkpoint file: {err}")))?
+        .map_err(|err| Error::Custom(format!("failed to write checkpoint file: {err}")))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<Checkpoint>> {
+        self.checkpoints
+            .lock()
+            .await
+            .checkpoints
+            .get(datasource_id.as_str())
+            .cloned()
+            .map(Checkpoint::try_from)
+            .transpose()
+    }
+
+    async fn commit(
+        &self,
+        datasource_id: &DatasourceId,
+        checkpoint: Checkpoint,
+    ) -> CarbonResult<()> {
+        let mut checkpoints = self.checkpoints.lock().await;
+        checkpoints
+            .checkpoints
+            .insert(datasource_id.as_str().to_string(), checkpoint.into());
+        self.write(&checkpoints).await
+    }
+
+    async fn load_cursor(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<String>> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .await
+            .cursors
+            .get(datasource_id.as_str())
+            .cloned())
+    }
+
+    async fn commit_cursor(
+        &self,
+        datasource_id: &DatasourceId,
+        cursor: String,
+    ) -> CarbonResult<()> {
+        let mut checkpoints = self.checkpoints.lock().await;
+        checkpoints
+            .cursors
+            .insert(datasource_id.as_str().to_string(), cursor);
+        self.write(&checkpoints).await
+    }
+}
+
+/// The slot and signature of an update, used to track processing progress.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UpdatePosition {
+    slot: u64,
+    signature: Option<Signature>,
+}
+
+impl UpdatePosition {
+    pub(crate) fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    pub(crate) fn of(update: &Update) -> Self {
+        match update {
+            Update::Account(account_update) => Self {
+                slot: account_update.slot,
+                signature: None,
+            },
+            Update::Transaction(transaction_update) => Self {
+                slot: transaction_update.slot,
+                signature: Some(transaction_update.signature),
+            },
+            Update::AccountDeletion(account_deletion) => Self {
+                slot: account_deletion.slot,
+                signature: None,
+            },
+            Update::BlockDetails(block_details) => Self {
+                slot: block_details.slot,
+                signature: None,
+            },
+            Update::SlotStatus(slot_status) => Self {
+                slot: slot_status.slot,
+                signature: None,
+            },
+        }
+    }
+}
+
+/// A cursor waiting for the updates sent before it to be processed.
+#[derive(Debug)]
+struct PendingCursor {
+    /// The number of updates the datasource had sent before the cursor.
+    sent: u64,
+    /// The slots that still had unprocessed updates once the pipeline received
+    /// the updates sent before the cursor. `None` until it received them.
+    waiting_for: Option<Vec<u64>>,
+    cursor: String,
+}
+
+/// The processing progress of a single datasource.
+#[derive(Debug, Default)]
+struct DatasourceProgress {
+    /// Number of received but not yet processed updates per slot.
+    pending: BTreeMap<u64, usize>,
+    /// The highest slot received so far.
+    highest_slot: Option<u64>,
+    /// Processed updates that may still be buffered by a processor, waiting
+    /// for the next successful flush of the pipes.
+    unflushed: Vec<UpdatePosition>,
+    /// The last processed transaction signature per slot, from the last
+    /// committed checkpoint on.
+    signatures: BTreeMap<u64, Signature>,
+    /// The last checkpoint the store committed.
+    committed: Option<Checkpoint>,
+    /// The number of updates received from the datasource so far.
+    received: u64,
+    /// The number of updates the datasource reported as sent through its
+    /// `CursorHandle`.
+    sent: u64,
+    /// Cursors of the datasource that were not committed yet, oldest first.
+    cursors: VecDeque<PendingCursor>,
+}
+
+impl DatasourceProgress {
+    fn received(&mut self, position: UpdatePosition) {
+        *self.pending.entry(position.slot).or_default() += 1;
+        self.highest_slot = Some(
+            self.highest_slot
+                .map_or(position.slot, |highest| highest.max(position.slot)),
+        );
+
+        self.received += 1;
+        self.wait_for_pending();
+    }
+
+    fn processed(&mut self, position: UpdatePosition) {
+        self.unflushed.push(position);
+    }
+
+    fn flushed(&mut self, position: UpdatePosition) {
+        if let Some(count) = self.pending.get_mut(&position.slot) {
+            *count -= 1;
+            if *count == 0 {
+                self.pending.remove(&position.slot);
+            }
+        }
+
+        if let Some(signature) = position.signature {
+            self.signatures.insert(position.slot, signature);
+        }
+    }
+
+    /// Records a cursor of the datasource, placed after every update it sent
+    /// so far.
+    fn cursor(&mut self, cursor: String) {
+        self.cursors.push_back(PendingCursor {
+            sent: self.sent,
+            waiting_for: None,
+            cursor,
+        });
+        self.wait_for_pending();
+    }
+
+    /// Once every update sent before a cursor has been received, makes the
+    /// cursor wait for the slots that still have unprocessed updates.
+    ///
+    /// Updates received later may share these slots, so a cursor can wait
+    /// longer than needed, but never commits before an update sent before it
+    /// has been processed.
+    fn wait_for_pending(&mut self) {
+        for cursor in self.cursors.iter_mut() {
+            if cursor.waiting_for.is_none() && cursor.sent <= self.received {
+                cursor.waiting_for = Some(self.pending.keys().copied().collect());
+            }
+        }
+    }
+
+    /// Returns the newest cursor whose updates have all been processed,
+    /// dropping the older ones, which it replaces.
+    fn next_cursor(&mut self) -> Option<String> {
+        let ready =
+            self.cursors
+                .iter()
+                .take_while(|cursor| {
+                    cursor.waiting_for.as_ref().is_some_and(|slots| {
+                        slots.iter().all(|slot| !self.pending.contains_key(slot))
+                    })
+                })
+                .count();
+
+        self.cursors
+            .drain(..ready)
+            .last()
+            .map(|pending_cursor| pending_cursor.cursor)
+    }
+
+    /// Returns the highest slot for which every received update has been
+    /// processed. Unless `finished` is set, more updates may still arrive for
+    /// the highest slot seen, so it is never considered complete.
+    fn completed_slot(&self, finished: bool) -> Option<u64> {
+        let highest_slot = self.highest_slot?;
+        let limit = if finished {
+            highest_slot
+        } else {
+            highest_slot.checked_sub(1)?
+        };
+
+        match self.pending.keys().next() {
+            Some(&first_pending) => first_pending.checked_sub(1).map(|slot| slot.min(limit)),
+            None => Some(limit),
+        }
+    }
+
+    /// Returns the checkpoint to commit, if the datasource made progress since
+    /// the last committed one. The progress only moves on once the store
+    /// committed it, see `committed`.
+    fn next_checkpoint(&self, finished: bool) -> Option<Checkpoint> {
+        let slot = self.completed_slot(finished)?;
+
+        if self
+            .committed
+            .is_some_and(|committed| committed.slot >= slot)
+        {
+            return None;
+        }
+
+        let signature = self
+            .signatures
+            .range(..=slot)
+            .next_back()
+            .map(|(_, signature)| *signature)
+            .or(self.committed.and_then(|committed| committed.signature));
+
+        Some(Checkpoint { slot, signature })
+    }
+
+    /// Records a checkpoint the store committed, forgetting the signatures it
+    /// covers.
+    fn committed(&mut self, checkpoint: Checkpoint) {
+        self.signatures = self.signatures.split_off(&(checkpoint.slot + 1));
+        self.committed = Some(checkpoint);
+    }
+}
+
+/// Tracks the processing progress of every datasource and commits it to a
+/// `CheckpointStore`.
+///
+/// The pipeline reports each update when it is received and again once it has
+/// been processed. Since updates may be processed out of order in concurrent
+/// execution mode, a slot only becomes part of a checkpoint once all updates
+/// received for it, and for every slot before it, have been processed.
+///
+/// Processors such as `BatchingProcessor` may still hold a processed update
+/// in a buffer, so processed updates only count once a flush of the pipes
+/// that started after they were processed has succeeded, see
+/// `take_unflushed` and `flushed`.
+///
+/// Cursors reported through a `CursorHandle` are committed the same way, once
+/// every update sent before them has been processed.
+pub(crate) struct CheckpointTracker {
+    store: Arc<dyn CheckpointStore>,
+    progress: Arc<Mutex<HashMap<DatasourceId, DatasourceProgress>>>,
+}
+
+impl CheckpointTracker {
+    pub(crate) fn new(store: Arc<dyn CheckpointStore>) -> Self {
+        Self {
+            store,
+            progress: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the `CursorHandle` of a datasource.
+    pub(crate) fn cursor_handle(&self, datasource_id: &DatasourceId) -> CursorHandle {
+        CursorHandle {
+            datasource_id: datasource_id.clone(),
+            store: self.store.clone(),
+            progress: self.progress.clone(),
+        }
+    }
+
+    pub(crate) fn received(&self, datasource_id: &DatasourceId, position: UpdatePosition) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress
+                .entry(datasource_id.clone())
+                .or_default()
+                .received(position);
+        }
+    }
+
+    pub(crate) fn processed(&self, datasource_id: &DatasourceId, position: UpdatePosition) {
+        if let Ok(mut progress) = self.progress.lock() {
+            if let Some(datasource_progress) = progress.get_mut(datasource_id) {
+                datasource_progress.processed(position);
+            }
+        }
+    }
+
+    /// Takes the updates processed since the last call, before the pipes are
+    /// flushed. Once the flush succeeded, they are handed to `flushed`,
+    /// otherwise back to `restore`.
+    pub(crate) fn take_unflushed(&self) -> Vec<(DatasourceId, Vec<UpdatePosition>)> {
+        match self.progress.lock() {
+            Ok(mut progress) => progress
+                .iter_mut()
+                .filter(|(_, datasource_progress)| !datasource_progress.unflushed.is_empty())
+                .map(|(datasource_id, datasource_progress)| {
+                    (
+                        datasource_id.clone(),
+                        std::mem::take(&mut datasource_progress.unflushed),
+                    )
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Counts updates taken with `take_unflushed` as processed, once the
+    /// pipes have been flushed.
+    pub(crate) fn flushed(&self, unflushed: Vec<(DatasourceId, Vec<UpdatePosition>)>) {
+        if let Ok(mut progress) = self.progress.lock() {
+            for (datasource_id, positions) in unflushed {
+                if let Some(datasource_progress) = progress.get_mut(&datasource_id) {
+                    for position in positions {
+                        datasource_progress.flushed(position);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns updates taken with `take_unflushed` after a failed flush, so
+    /// that they are counted with the next successful one.
+    pub(crate) fn restore(&self, unflushed: Vec<(DatasourceId, Vec<UpdatePosition>)>) {
+        if let Ok(mut progress) = self.progress.lock() {
+            for (datasource_id, positions) in unflushed {
+                if let Some(datasource_progress) = progress.get_mut(&datasource_id) {
+                    datasource_progress.unflushed.extend(positions);
+                }
+            }
+        }
+    }
+
+    /// Records an update that was received but intentionally not processed,
+    /// such as a dropped duplicate.
+    pub(crate) fn skipped(&self, datasource_id: &DatasourceId, position: UpdatePosition) {
+        self.received(datasource_id, position);
+        self.processed(datasource_id, position);
+    }
+
+    /// Commits a new checkpoint for every datasource that made progress since
+    /// the last commit, along with the newest cursor whose updates have all
+    /// been processed.
+    ///
+    /// `finished` signals that no further updates will be received, in which
+    /// case the highest slot seen is committed as well once it has been
+    /// processed. Failed commits are logged and retried with the next commit.
+    pub(crate) async fn commit(&self, finished: bool) {
+        let commits: Vec<(DatasourceId, Option<Checkpoint>, Option<String>)> =
+            match self.progress.lock() {
+                Ok(mut progress) => progress
+                    .iter_mut()
+                    .map(|(datasource_id, datasource_progress)| {
+                        (
+                            datasource_id.clone(),
+                            datasource_progress.next_checkpoint(finished),
+                            datasource_progress.next_cursor(),
+                        )
+                    })
+                    .filter(|(_, checkpoint, cursor)| checkpoint.is_some() || cursor.is_some())
+                    .collect(),
+                Err(_) => return,
+            };
+
+        for (datasource_id, checkpoint, cursor) in commits {
+            if let Some(checkpoint) = checkpoint {
+                log::debug!("committing checkpoint for {datasource_id:?}: {checkpoint:?}");
+
+                match self.store.commit(&datasource_id, checkpoint).await {
+                    Ok(()) => {
+                        if let Ok(mut progress) = self.progress.lock() {
+                            if let Some(datasource_progress) = progress.get_mut(&datasource_id) {
+                                datasource_progress.committed(checkpoint);
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        log::error!("failed to commit checkpoint for {datasource_id:?}: {err:?}");
+                    }
+                }
+            }
+
+            if let Some(cursor) = cursor {
+                log::debug!("committing cursor for {datasource_id:?}: {cursor}");
+
+                if let Err(err) = self
+                    .store
+                    .commit_cursor(&datasource_id, cursor.clone())
+                    .await
+                {
+                    log::error!("failed to commit cursor for {datasource_id:?}: {err:?}");
+
+                    // Retried with the next commit, unless a newer cursor is
+                    // ready by then.
+                    if let Ok(mut progress) = self.progress.lock() {
+                        if let Some(datasource_progress) = progress.get_mut(&datasource_id) {
+                            datasource_progress.cursors.push_front(PendingCursor {
+                                sent: 0,
+                                waiting_for: Some(Vec::new()),
+                                cursor,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A handle through which a datasource commits its own cursor, for
+/// datasources whose position isn't described by a `Checkpoint`, such as the
+/// signatures a signature-based crawler has crawled up to.
+///
+/// The pipeline hands a `CursorHandle` to every datasource through
+/// `Datasource::set_cursor_handle` before `consume` is called, if it has a
+/// checkpoint store. The datasource reports every update it sent to the
+/// pipeline with `sent`, and its cursor with `commit`. The cursor is committed
+/// to the checkpoint store under the datasource's ID once every update sent
+/// before it has been processed, or stored by the pipeline's
+/// `DeadLetterSink`, and the pipes have been flushed after them.
+///
+/// # Example
+///
+/// ```ignore
+/// if let Some(cursor) = cursor_handle.load().await? {
+///     // Resume from `cursor`
+/// }
+///
+/// sender.send((update, id.clone())).await?;
+/// cursor_handle.sent();
+///
+/// cursor_handle.commit(cursor.to_string());
+/// ```
+///
+/// # Notes
+///
+/// - Like checkpoints, cursors are only found again after a restart if the
+///   datasource is registered with a stable, named ID.
+/// - Updates must be reported with `sent` only once they were handed to the
+///   pipeline's channel, and in the same task that calls `commit`, so that
+///   the cursor is placed after them.
+#[derive(Clone)]
+pub struct CursorHandle {
+    datasource_id: DatasourceId,
+    store: Arc<dyn CheckpointStore>,
+    progress: Arc<Mutex<HashMap<DatasourceId, DatasourceProgress>>>,
+}
+
+impl std::fmt::Debug for CursorHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorHandle")
+            .field("datasource_id", &self.datasource_id)
+            .finish()
+    }
+}
+
+impl CursorHandle {
+    /// Loads the last committed cursor of the datasource, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint store fails to load the cursor.
+    pub async fn load(&self) -> CarbonResult<Option<String>> {
+        self.store.load_cursor(&self.datasource_id).await
+    }
+
+    /// Reports an update the datasource sent to the pipeline.
+    pub fn sent(&self) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress.entry(self.datasource_id.clone()).or_default().sent += 1;
+        }
+    }
+
+    /// Commits `cursor` once every update reported with `sent` so far has
+    /// been processed.
+    pub fn commit(&self, cursor: String) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress
+                .entry(self.datasource_id.clone())
+                .or_default()
+                .cursor(cursor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::sync::atomic::{AtomicBool, Ordering},
+    };
+
+    /// A `CheckpointStore` in memory, whose commits fail while `failing` is
+    /// set.
+    #[derive(Default)]
+    struct MemoryCheckpointStore {
+        failing: AtomicBool,
+        checkpoints: Mutex<HashMap<DatasourceId, Checkpoint>>,
+        cursors: Mutex<HashMap<DatasourceId, String>>,
+    }
+
+    impl MemoryCheckpointStore {
+        fn check(&self) -> CarbonResult<()> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(Error::Custom("store unavailable".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl CheckpointStore for MemoryCheckpointStore {
+        async fn load(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<Checkpoint>> {
+            Ok(self.checkpoints.lock().unwrap().get(datasource_id).copied())
+        }
+
+        async fn commit(
+            &self,
+            datasource_id: &DatasourceId,
+            checkpoint: Checkpoint,
+        ) -> CarbonResult<()> {
+            self.check()?;
+            self.checkpoints
+                .lock()
+                .unwrap()
+                .insert(datasource_id.clone(), checkpoint);
+            Ok(())
+        }
+
+        async fn load_cursor(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<String>> {
+            Ok(self.cursors.lock().unwrap().get(datasource_id).cloned())
+        }
+
+        async fn commit_cursor(
+            &self,
+            datasource_id: &DatasourceId,
+            cursor: String,
+        ) -> CarbonResult<()> {
+            self.check()?;
+            self.cursors
+                .lock()
+                .unwrap()
+                .insert(datasource_id.clone(), cursor);
+            Ok(())
+        }
+    }
+
+    fn position(slot: u64) -> UpdatePosition {
+        UpdatePosition {
+            slot,
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_completed_slot_waits_for_later_slot() {
+        let mut progress = DatasourceProgress::default();
+        progress.received(position(10));
+        progress.flushed(position(10));
+
+        assert_eq!(progress.completed_slot(false), Some(9));
+        assert_eq!(progress.completed_slot(true), Some(10));
+
+        progress.received(position(11));
+        assert_eq!(progress.completed_slot(false), Some(10));
+    }
+
+    #[test]
+    fn test_completed_slot_stops_before_pending_updates() {
+        let mut progress = DatasourceProgress::default();
+        progress.received(position(10));
+        progress.received(position(11));
+        progress.received(position(12));
+        progress.received(position(13));
+        progress.flushed(position(10));
+        progress.flushed(position(12));
+
+        assert_eq!(progress.completed_slot(false), Some(10));
+
+        progress.flushed(position(11));
+        assert_eq!(progress.completed_slot(false), Some(12));
+    }
+
+    #[test]
+    fn test_waits_for_flush_before_completing_slot() {
+        let datasource_id = DatasourceId::new_named("source");
+        let tracker = CheckpointTracker::new(Arc::new(FileCheckpointStore {
+            path: PathBuf::new(),
+            checkpoints: tokio::sync::Mutex::new(StoredCheckpoints::default()),
+        }));
+        let completed_slot = |tracker: &CheckpointTracker| {
+            tracker.progress.lock().unwrap()[&datasource_id].completed_slot(true)
+        };
+
+        tracker.received(&datasource_id, position(10));
+        tracker.processed(&datasource_id, position(10));
+        assert_eq!(completed_slot(&tracker), Some(9));
+
+        let unflushed = tracker.take_unflushed();
+        tracker.restore(unflushed);
+        assert_eq!(completed_slot(&tracker), Some(9));
+
+        let unflushed = tracker.take_unflushed();
+        tracker.flushed(unflushed);
+        assert_eq!(completed_slot(&tracker), Some(10));
+    }
+
+    #[test]
+    fn test_next_checkpoint_keeps_last_signature() {
+        let signature = Signature::new_unique();
+        let mut progress = DatasourceProgress::default();
+
+        progress.received(UpdatePosition {
+            slot: 10,
+            signature: Some(signature),
+        });
+        progress.flushed(UpdatePosition {
+            slot: 10,
+            signature: Some(signature),
+        });
+        progress.received(position(11));
+        progress.flushed(position(11));
+        progress.received(position(12));
+
+        let checkpoint = Checkpoint {
+            slot: 11,
+            signature: Some(signature),
+        };
+        assert_eq!(progress.next_checkpoint(false), Some(checkpoint));
+
+        progress.committed(checkpoint);
+        assert_eq!(progress.next_checkpoint(false), None);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_signature_after_failed_commit() {
+        let datasource_id = DatasourceId::new_named("source");
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let tracker = CheckpointTracker::new(store.clone());
+        let signature = Signature::new_unique();
+        let transaction = UpdatePosition {
+            slot: 10,
+            signature: Some(signature),
+        };
+
+        tracker.received(&datasource_id, transaction);
+        tracker.processed(&datasource_id, transaction);
+        tracker.received(&datasource_id, position(11));
+        let unflushed = tracker.take_unflushed();
+        tracker.flushed(unflushed);
+
+        store.failing.store(true, Ordering::SeqCst);
+        tracker.commit(false).await;
+        assert_eq!(store.load(&datasource_id).await.unwrap(), None);
+
+        store.failing.store(false, Ordering::SeqCst);
+        tracker.commit(false).await;
+        assert_eq!(
+            store.load(&datasource_id).await.unwrap(),
+            Some(Checkpoint {
+                slot: 10,
+                signature: Some(signature),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commits_cursor_once_updates_before_it_are_processed() {
+        let datasource_id = DatasourceId::new_named("crawler");
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let tracker = CheckpointTracker::new(store.clone());
+        let cursor_handle = tracker.cursor_handle(&datasource_id);
+
+        // Both updates are sent before the cursor, but only the first one has
+        // been received by the pipeline yet.
+        cursor_handle.sent();
+        cursor_handle.sent();
+        cursor_handle.commit("first".to_string());
+        tracker.received(&datasource_id, position(12));
+        tracker.processed(&datasource_id, position(12));
+        let unflushed = tracker.take_unflushed();
+        tracker.flushed(unflushed);
+
+        tracker.commit(false).await;
+        assert_eq!(cursor_handle.load().await.unwrap(), None);
+
+        tracker.received(&datasource_id, position(11));
+        cursor_handle.sent();
+        cursor_handle.commit("second".to_string());
+        tracker.received(&datasource_id, position(10));
+        tracker.processed(&datasource_id, position(11));
+
+        // Processed updates only count once the pipes have been flushed.
+        tracker.commit(false).await;
+        assert_eq!(cursor_handle.load().await.unwrap(), None);
+
+        let unflushed = tracker.take_unflushed();
+        tracker.flushed(unflushed);
+        tracker.commit(false).await;
+        assert_eq!(
+            cursor_handle.load().await.unwrap(),
+            Some("first".to_string())
+        );
+
+        // A failed commit is retried with the next one.
+        tracker.processed(&datasource_id, position(10));
+        let unflushed = tracker.take_unflushed();
+        tracker.flushed(unflushed);
+        store.failing.store(true, Ordering::SeqCst);
+        tracker.commit(false).await;
+        store.failing.store(false, Ordering::SeqCst);
+        assert_eq!(
+            cursor_handle.load().await.unwrap(),
+            Some("first".to_string())
+        );
+
+        tracker.commit(true).await;
+        assert_eq!(
+            cursor_handle.load().await.unwrap(),
+            Some("second".to_string())
+        );
+    }
+}
@@ -39,7 +39,7 @@
 //! instruction handling within your application, benefiting from simplified
 //! parsing and type management capabilities.
 
-use {crate::instruction::DecodedInstruction, serde::Serialize};
+use {crate::instruction::DecodedInstruction, serde::Serialize, solana_pubkey::Pubkey};
 
 /// A trait for defining collections of Solana instructions, enabling parsing
 /// and type-based management.
@@ -82,6 +82,14 @@ use {crate::instruction::DecodedInstruction, serde::Serialize};
 /// - **Returns**: An instance of `Self::InstructionType`, representing the
 ///   specific type of instruction.
 ///
+/// ## Provided Methods
+///
+/// ### `program_ids`
+///
+/// Returns the programs whose instructions the collection decodes, so that
+/// datasources can subscribe to them. Defaults to an empty list, meaning that
+/// the collection may decode the instructions of any program.
+///
 /// ## Notes
 ///
 /// - This trait requires implementors to be thread-safe (`Send` and `Sync`) and
@@ -100,4 +108,8 @@ pub trait InstructionDecoderCollection:
         instruction: &solana_instruction::Instruction,
     ) -> Option<DecodedInstruction<Self>>;
     fn get_type(&self) -> Self::InstructionType;
+
+    fn program_ids() -> Vec<Pubkey> {
+        Vec::new()
+    }
 }
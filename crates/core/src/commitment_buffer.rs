@@ -0,0 +1,309 @@
+//! Provides a commitment-aware buffer that holds back the updates of each slot
+//! until the slot reaches a target commitment level.
+//!
+//! Datasources streaming at `processed` commitment deliver data quickly, but
+//! that data may still be rolled back. When the buffer is enabled through
+//! `PipelineBuilder::commitment_buffer`, the pipeline keeps the updates of
+//! every slot aside until a `SlotStatus` update reports that the slot reached
+//! the target commitment, and only then hands them to the pipes. Updates of
+//! slots reported as dead are discarded.
+//!
+//! # Overview
+//!
+//! - Slot status updates are passed through immediately, after being used to
+//!   release or discard buffered updates.
+//! - Updates for a slot that already reached the target commitment are passed
+//!   through without buffering.
+//! - When a slot is finalized, buffered slots below it that have not reached
+//!   the target commitment are on an abandoned fork, and their updates are
+//!   discarded.
+//!
+//! # Metrics
+//!
+//! - `updates_buffered`: A gauge of the number of updates currently held back.
+//! - `updates_discarded`: A counter of the updates discarded because their
+//!   slot died, was abandoned or was evicted from a full buffer.
+//!
+//! # Notes
+//!
+//! - At least one datasource must provide slot status updates (see
+//!   `UpdateType::SlotStatus`), otherwise no slot is ever released.
+//! - Memory use is bounded by `max_buffered_slots`. When more slots are
+//!   pending, the oldest pending slot is evicted and its updates discarded.
+
+use {
+    crate::datasource::{DatasourceId, SlotStatus, SlotStatusUpdate, Update},
+    std::collections::{BTreeMap, BTreeSet},
+};
+
+/// The default number of slots the commitment buffer holds updates for.
+pub const DEFAULT_MAX_BUFFERED_SLOTS: usize = 256;
+
+/// The commitment level a slot must reach before its updates are processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetCommitment {
+    /// Release the updates of a slot once it is confirmed.
+    Confirmed,
+    /// Release the updates of a slot once it is finalized.
+    #[default]
+    Finalized,
+}
+
+impl TargetCommitment {
+    fn is_reached_by(&self, status: SlotStatus) -> bool {
+        match self {
+            TargetCommitment::Confirmed => {
+                matches!(status, SlotStatus::Confirmed | SlotStatus::Finalized)
+            }
+            TargetCommitment::Finalized => status == SlotStatus::Finalized,
+        }
+    }
+}
+
+/// Configures the commitment buffer of a pipeline.
+///
+/// # Fields
+///
+/// - `target`: The commitment level a slot must reach before its updates are
+///   processed.
+/// - `max_buffered_slots`: The maximum number of slots to hold updates for.
+///   Finalization typically trails the tip by about 32 slots, so the default
+///   of 256 slots leaves room for delays in the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitmentBufferConfig {
+    pub target: TargetCommitment,
+    pub max_buffered_slots: usize,
+}
+
+impl Default for CommitmentBufferConfig {
+    fn default() -> Self {
+        Self {
+            target: TargetCommitment::default(),
+            max_buffered_slots: DEFAULT_MAX_BUFFERED_SLOTS,
+        }
+    }
+}
+
+/// The updates that can be processed after pushing an update into the
+/// buffer, along with the updates discarded along the way.
+#[derive(Debug, Default)]
+pub(crate) struct BufferOutput {
+    pub(crate) ready: Vec<(Update, DatasourceId)>,
+    pub(crate) discarded: Vec<(Update, DatasourceId)>,
+}
+
+/// Holds updates per slot until their slot reaches the target commitment.
+#[derive(Debug)]
+pub(crate) struct CommitmentBuffer {
+    config: CommitmentBufferConfig,
+    pending: BTreeMap<u64, Vec<(Update, DatasourceId)>>,
+    buffered: usize,
+    committed: BTreeSet<u64>,
+    dead: BTreeSet<u64>,
+    highest_finalized: Option<u64>,
+}
+
+impl CommitmentBuffer {
+    pub(crate) fn new(config: CommitmentBufferConfig) -> Self {
+        Self {
+            config,
+            pending: BTreeMap::new(),
+            buffered: 0,
+            committed: BTreeSet::new(),
+            dead: BTreeSet::new(),
+            highest_finalized: None,
+        }
+    }
+
+    /// Returns the number of updates currently held back.
+    pub(crate) fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Pushes an update into the buffer and returns the updates that are ready
+    /// to be processed.
+    pub(crate) fn push(&mut self, update: Update, datasource_id: DatasourceId) -> BufferOutput {
+        let mut output = BufferOutput::default();
+
+        let slot = match &update {
+            Update::SlotStatus(slot_status) => {
+                self.apply_slot_status(slot_status, &mut output);
+                output.ready.push((update, datasource_id));
+                return output;
+            }
+            Update::Account(account_update) => account_update.slot,
+            Update::Transaction(transaction_update) => transaction_update.slot,
+            Update::AccountDeletion(account_deletion) => account_deletion.slot,
+            Update::BlockDetails(block_details) => block_details.slot,
+        };
+
+        if self.committed.contains(&slot) {
+            output.ready.push((update, datasource_id));
+        } else if self.dead.contains(&slot)
+            || self
+                .highest_finalized
+                .is_some_and(|finalized| slot < finalized)
+        {
+            output.discarded.push((update, datasource_id));
+        } else {
+            self.pending
+                .entry(slot)
+                .or_default()
+                .push((update, datasource_id));
+            self.buffered += 1;
+
+            while self.pending.len() > self.config.max_buffered_slots {
+                if let Some((evicted_slot, updates)) = self.pending.pop_first() {
+                    log::warn!(
+                        "commitment buffer is full, discarding {} updates of slot {evicted_slot}",
+                        updates.len()
+                    );
+                    self.buffered -= updates.len();
+                    output.discarded.extend(updates);
+                }
+            }
+        }
+
+        output
+    }
+
+    fn apply_slot_status(&mut self, slot_status: &SlotStatusUpdate, output: &mut BufferOutput) {
+        let slot = slot_status.slot;
+
+        if slot_status.status == SlotStatus::Dead {
+            log::debug!(
+                "slot {slot} is dead ({:?}), discarding its updates",
+                slot_status.dead_error
+            );
+            self.dead.insert(slot);
+            if let Some(updates) = self.pending.remove(&slot) {
+                self.buffered -= updates.len();
+                output.discarded.extend(updates);
+            }
+            return;
+        }
+
+        if self.config.target.is_reached_by(slot_status.status) {
+            self.committed.insert(slot);
+            if let Some(updates) = self.pending.remove(&slot) {
+                self.buffered -= updates.len();
+                output.ready.extend(updates);
+            }
+        }
+
+        if slot_status.status == SlotStatus::Finalized
+            && self
+                .highest_finalized
+                .is_none_or(|finalized| slot > finalized)
+        {
+            self.highest_finalized = Some(slot);
+
+            let remaining = self.pending.split_off(&slot);
+            for (abandoned_slot, updates) in std::mem::replace(&mut self.pending, remaining) {
+                log::debug!(
+                    "slot {abandoned_slot} was abandoned, discarding {} updates",
+                    updates.len()
+                );
+                self.buffered -= updates.len();
+                output.discarded.extend(updates);
+            }
+
+            // Keep the statuses of recent slots around, so late updates for
+            // slots that were already released are still passed through.
+            let oldest_slot = slot.saturating_sub(self.config.max_buffered_slots as u64);
+            self.committed = self.committed.split_off(&oldest_slot);
+            self.dead = self.dead.split_off(&oldest_slot);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::datasource::{AccountUpdate, SlotStatusUpdate},
+        solana_account::Account,
+        solana_pubkey::Pubkey,
+    };
+
+    fn account_update(slot: u64) -> (Update, DatasourceId) {
+        (
+            Update::Account(AccountUpdate {
+                pubkey: Pubkey::new_unique(),
+                account: Account::default(),
+                slot,
+                transaction_signature: None,
+            }),
+            DatasourceId::new_named("test"),
+        )
+    }
+
+    fn slot_status(slot: u64, status: SlotStatus) -> (Update, DatasourceId) {
+        (
+            Update::SlotStatus(SlotStatusUpdate {
+                slot,
+                parent: None,
+                status,
+                dead_error: None,
+            }),
+            DatasourceId::new_named("test"),
+        )
+    }
+
+    fn push(
+        buffer: &mut CommitmentBuffer,
+        (update, datasource_id): (Update, DatasourceId),
+    ) -> BufferOutput {
+        buffer.push(update, datasource_id)
+    }
+
+    #[test]
+    fn test_releases_slot_at_target_commitment() {
+        let mut buffer = CommitmentBuffer::new(CommitmentBufferConfig {
+            target: TargetCommitment::Confirmed,
+            ..Default::default()
+        });
+
+        assert!(push(&mut buffer, account_update(10)).ready.is_empty());
+        assert!(push(&mut buffer, account_update(10)).ready.is_empty());
+        assert_eq!(buffer.buffered(), 2);
+
+        assert_eq!(
+            push(&mut buffer, slot_status(10, SlotStatus::Processed))
+                .ready
+                .len(),
+            1
+        );
+        assert_eq!(
+            push(&mut buffer, slot_status(10, SlotStatus::Confirmed))
+                .ready
+                .len(),
+            3
+        );
+        assert_eq!(buffer.buffered(), 0);
+
+        assert_eq!(push(&mut buffer, account_update(10)).ready.len(), 1);
+    }
+
+    #[test]
+    fn test_discards_dead_and_abandoned_slots() {
+        let mut buffer = CommitmentBuffer::new(CommitmentBufferConfig::default());
+
+        push(&mut buffer, account_update(10));
+        push(&mut buffer, account_update(11));
+        push(&mut buffer, account_update(12));
+
+        assert_eq!(
+            push(&mut buffer, slot_status(11, SlotStatus::Dead))
+                .discarded
+                .len(),
+            1
+        );
+        assert_eq!(push(&mut buffer, account_update(11)).discarded.len(), 1);
+
+        let output = push(&mut buffer, slot_status(12, SlotStatus::Finalized));
+        assert_eq!(output.ready.len(), 2);
+        assert_eq!(output.discarded.len(), 1);
+        assert_eq!(buffer.buffered(), 0);
+    }
+}
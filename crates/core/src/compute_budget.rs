@@ -0,0 +1,299 @@
+//! Summarizes the compute budget of a transaction: the limits and price set
+//! through ComputeBudget instructions, the fee split into its base and
+//! priority parts, and the compute units actually consumed.
+//!
+//! Use `TransactionMetadata::compute_budget` to get the `ComputeBudget` of a
+//! transaction from any processor.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let budget = transaction_metadata.compute_budget();
+//!
+//! if budget.compute_unit_price > 1_000_000 {
+//!     log::warn!(
+//!         "{} paid {} lamports of priority fees for {:?} compute units",
+//!         transaction_metadata.signature,
+//!         budget.priority_fee,
+//!         budget.compute_units_consumed,
+//!     );
+//! }
+//! ```
+//!
+//! # Notes
+//!
+//! - When a transaction does not set its compute unit limit, the runtime
+//!   grants `DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT` per instruction other
+//!   than ComputeBudget instructions, up to `MAX_COMPUTE_UNIT_LIMIT`. Recent
+//!   runtimes grant less to builtin programs, so the limit is an upper bound
+//!   in that case.
+//! - The base fee is `DEFAULT_LAMPORTS_PER_SIGNATURE` per signature of the
+//!   transaction and per signature verified by its precompile instructions,
+//!   the instructions of the `instruction::PRECOMPILE_PROGRAM_IDS` programs.
+//!   The priority fee is the rest of the fee charged, as recorded in the
+//!   transaction's status metadata, so it does not depend on the compute unit
+//!   limit.
+
+use {
+    crate::{instruction::PRECOMPILE_PROGRAM_IDS, transaction::TransactionMetadata},
+    solana_message::compiled_instruction::CompiledInstruction,
+    solana_pubkey::Pubkey,
+};
+
+/// The address of the ComputeBudget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+
+/// The compute units granted to an instruction when the transaction does not
+/// set its compute unit limit.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// The highest compute unit limit a transaction can have.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// The fee charged per signature, in lamports.
+pub const DEFAULT_LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// The compute budget of a transaction.
+///
+/// # Fields
+///
+/// - `requested_compute_unit_limit`: The limit set with a
+///   `SetComputeUnitLimit` instruction, if any.
+/// - `compute_unit_limit`: The compute unit limit the transaction ran with,
+///   capped at `MAX_COMPUTE_UNIT_LIMIT`.
+/// - `compute_unit_price`: The price of a compute unit, in micro-lamports, set
+///   with a `SetComputeUnitPrice` instruction. Zero if unset.
+/// - `loaded_accounts_data_size_limit`: The limit set with a
+///   `SetLoadedAccountsDataSizeLimit` instruction, in bytes, if any.
+/// - `heap_frame_size`: The heap size requested with a `RequestHeapFrame`
+///   instruction, in bytes, if any.
+/// - `total_fee`: The fee charged to the fee payer, in lamports.
+/// - `priority_fee`: The part of the fee paid for priority, the total fee
+///   minus the base fee, in lamports.
+/// - `base_fee`: The part of the fee paid per signature, including the
+///   signatures verified by precompile instructions, in lamports.
+/// - `compute_units_consumed`: The compute units the transaction consumed, if
+///   recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub requested_compute_unit_limit: Option<u32>,
+    pub compute_unit_limit: u32,
+    pub compute_unit_price: u64,
+    pub loaded_accounts_data_size_limit: Option<u32>,
+    pub heap_frame_size: Option<u32>,
+    pub total_fee: u64,
+    pub priority_fee: u64,
+    pub base_fee: u64,
+    pub compute_units_consumed: Option<u64>,
+}
+
+/// A ComputeBudget instruction, decoded from its Borsh encoding.
+enum ComputeBudgetInstruction {
+    RequestUnitsDeprecated { units: u32 },
+    RequestHeapFrame(u32),
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64),
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+impl ComputeBudgetInstruction {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let (tag, rest) = data.split_first()?;
+        let u32_at = |offset: usize| {
+            rest.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+        };
+
+        match tag {
+            0 => u32_at(0).map(|units| Self::RequestUnitsDeprecated { units }),
+            1 => u32_at(0).map(Self::RequestHeapFrame),
+            2 => u32_at(0).map(Self::SetComputeUnitLimit),
+            3 => rest.get(..8).map(|bytes| {
+                Self::SetComputeUnitPrice(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+            }),
+            4 => u32_at(0).map(Self::SetLoadedAccountsDataSizeLimit),
+            _ => None,
+        }
+    }
+}
+
+impl ComputeBudget {
+    /// Reads the compute budget of a transaction from its top-level
+    /// instructions and status metadata, with the default fee per signature.
+    pub fn from_transaction(transaction_metadata: &TransactionMetadata) -> Self {
+        Self::from_transaction_with_lamports_per_signature(
+            transaction_metadata,
+            DEFAULT_LAMPORTS_PER_SIGNATURE,
+        )
+    }
+
+    /// Reads the compute budget of a transaction from its top-level
+    /// instructions and status metadata, with a custom fee per signature.
+    pub fn from_transaction_with_lamports_per_signature(
+        transaction_metadata: &TransactionMetadata,
+        lamports_per_signature: u64,
+    ) -> Self {
+        let account_keys = transaction_metadata.message.static_account_keys();
+        let program_id = |instruction: &CompiledInstruction| {
+            account_keys.get(instruction.program_id_index as usize)
+        };
+
+        let mut budget = ComputeBudget::default();
+        let mut other_instructions = 0u32;
+        let mut signatures = transaction_metadata
+            .message
+            .header()
+            .num_required_signatures as u64;
+
+        for instruction in transaction_metadata.message.instructions() {
+            let Some(program_id) = program_id(instruction) else {
+                continue;
+            };
+
+            if PRECOMPILE_PROGRAM_IDS.contains(program_id) {
+                signatures += instruction.data.first().copied().unwrap_or_default() as u64;
+            }
+
+            if *program_id != COMPUTE_BUDGET_PROGRAM_ID {
+                other_instructions += 1;
+                continue;
+            }
+
+            match ComputeBudgetInstruction::decode(&instruction.data) {
+                Some(ComputeBudgetInstruction::RequestUnitsDeprecated { units, .. }) => {
+                    budget.requested_compute_unit_limit = Some(units);
+                }
+                Some(ComputeBudgetInstruction::RequestHeapFrame(bytes)) => {
+                    budget.heap_frame_size = Some(bytes);
+                }
+                Some(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => {
+                    budget.requested_compute_unit_limit = Some(units);
+                }
+                Some(ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports)) => {
+                    budget.compute_unit_price = micro_lamports;
+                }
+                Some(ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes)) => {
+                    budget.loaded_accounts_data_size_limit = Some(bytes);
+                }
+                None => {}
+            }
+        }
+
+        budget.compute_unit_limit = budget
+            .requested_compute_unit_limit
+            .unwrap_or_else(|| {
+                other_instructions.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+            })
+            .min(MAX_COMPUTE_UNIT_LIMIT);
+
+        budget.total_fee = transaction_metadata.meta.fee;
+        budget.base_fee = lamports_per_signature
+            .saturating_mul(signatures)
+            .min(budget.total_fee);
+        budget.priority_fee = budget.total_fee - budget.base_fee;
+        budget.compute_units_consumed = transaction_metadata.meta.compute_units_consumed;
+
+        budget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_message::{legacy::Message, MessageHeader, VersionedMessage},
+        solana_transaction_status::TransactionStatusMeta,
+    };
+
+    fn transaction(
+        instructions: Vec<Vec<u8>>,
+        other_instructions: usize,
+        fee: u64,
+    ) -> TransactionMetadata {
+        let mut compiled: Vec<_> = instructions
+            .into_iter()
+            .map(|data| CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![],
+                data,
+            })
+            .collect();
+        compiled.extend((0..other_instructions).map(|_| CompiledInstruction {
+            program_id_index: 2,
+            accounts: vec![],
+            data: vec![],
+        }));
+
+        TransactionMetadata {
+            meta: TransactionStatusMeta {
+                fee,
+                compute_units_consumed: Some(42_000),
+                ..Default::default()
+            },
+            message: VersionedMessage::Legacy(Message {
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    ..Default::default()
+                },
+                account_keys: vec![
+                    Pubkey::new_unique(),
+                    COMPUTE_BUDGET_PROGRAM_ID,
+                    Pubkey::new_unique(),
+                    PRECOMPILE_PROGRAM_IDS[0],
+                ],
+                instructions: compiled,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reads_limit_and_price() {
+        let limit = [&[2u8][..], &100_000u32.to_le_bytes()].concat();
+        let price = [&[3u8][..], &25_000u64.to_le_bytes()].concat();
+        let size = [&[4u8][..], &65_536u32.to_le_bytes()].concat();
+
+        let budget =
+            ComputeBudget::from_transaction(&transaction(vec![limit, price, size], 1, 7_500));
+
+        assert_eq!(budget.requested_compute_unit_limit, Some(100_000));
+        assert_eq!(budget.compute_unit_limit, 100_000);
+        assert_eq!(budget.compute_unit_price, 25_000);
+        assert_eq!(budget.loaded_accounts_data_size_limit, Some(65_536));
+        assert_eq!(budget.priority_fee, 2_500);
+        assert_eq!(budget.base_fee, 5_000);
+        assert_eq!(budget.compute_units_consumed, Some(42_000));
+    }
+
+    #[test]
+    fn test_defaults_limit_per_instruction() {
+        let price = [&[3u8][..], &1u64.to_le_bytes()].concat();
+
+        let budget = ComputeBudget::from_transaction(&transaction(vec![price], 3, 5_001));
+
+        assert_eq!(budget.requested_compute_unit_limit, None);
+        assert_eq!(budget.compute_unit_limit, 600_000);
+        assert_eq!(budget.priority_fee, 1);
+        assert_eq!(budget.base_fee, 5_000);
+    }
+
+    #[test]
+    fn test_charges_precompile_signatures_as_base_fee() {
+        let price = [&[3u8][..], &1u64.to_le_bytes()].concat();
+        let mut transaction = transaction(vec![price], 1, 15_100);
+        if let VersionedMessage::Legacy(message) = &mut transaction.message {
+            message.instructions.push(CompiledInstruction {
+                program_id_index: 3,
+                accounts: vec![],
+                data: vec![2, 0],
+            });
+        }
+
+        let budget = ComputeBudget::from_transaction(&transaction);
+
+        assert_eq!(budget.base_fee, 15_000);
+        assert_eq!(budget.priority_fee, 100);
+    }
+}
@@ -0,0 +1,652 @@
+//! Assembles a pipeline from a declarative TOML or YAML configuration.
+//!
+//! A `PipelineConfig` describes the datasources, metrics backends and pipes of
+//! a pipeline, the filters of each pipe, and pipeline settings such as the
+//! channel size and the shutdown strategy. `PipelineConfig::into_builder`
+//! resolves it into a `PipelineBuilder`.
+//!
+//! Datasources, metrics backends and pipes are referred to by name and
+//! created by the factories the application registers in a
+//! `PipelineRegistry`, since they live in their own crates. Each factory
+//! receives the `params` of its entry as a `serde_json::Value`, which it can
+//! deserialize into its own parameters. Filters are the built-in filters of
+//! the `filter` module and need no registration.
+//!
+//! This module requires the `config` feature.
+//!
+//! # Example
+//!
+//! ```toml
+//! channel_buffer_size = 10000
+//! shutdown_strategy = "process_pending"
+//! metrics_flush_interval = 5
+//!
+//! [[datasources]]
+//! kind = "yellowstone"
+//! id = "mainnet"
+//! params = { endpoint = "https://grpc.example.com", x_token = "..." }
+//!
+//! [[metrics]]
+//! kind = "log"
+//!
+//! [[pipes]]
+//! kind = "pumpfun_instructions"
+//! filters = [
+//!     { kind = "datasource", ids = ["mainnet"] },
+//!     { kind = "succeeded" },
+//!     { kind = "not", filter = { kind = "signer", signers = ["..."] } },
+//! ]
+//! ```
+//!
+//! ```ignore
+//! use carbon_core::{
+//!     config::{PipelineConfig, PipelineRegistry},
+//!     error::Error,
+//! };
+//! use std::sync::Arc;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct YellowstoneParams {
+//!     endpoint: String,
+//!     x_token: Option<String>,
+//! }
+//!
+//! let registry = PipelineRegistry::new()
+//!     .register_datasource("yellowstone", |params| {
+//!         let params: YellowstoneParams = serde_json::from_value(params)
+//!             .map_err(|err| Error::Custom(format!("Invalid yellowstone params: {err}")))?;
+//!         Ok(Arc::new(YellowstoneGrpcGeyserClient::new(
+//!             params.endpoint,
+//!             params.x_token,
+//!             /* ... */
+//!         )))
+//!     })
+//!     .register_metrics("log", |_params| Ok(Arc::new(LogMetrics::new())))
+//!     .register_pipe("pumpfun_instructions", |builder, _params, filters| {
+//!         Ok(builder.instruction_with_filters(PumpfunDecoder, PumpfunProcessor, filters))
+//!     });
+//!
+//! PipelineConfig::from_file("indexer.toml")?
+//!     .into_builder(&registry)?
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+
+use {
+    crate::{
+        datasource::{Datasource, DatasourceId},
+        error::{CarbonResult, Error},
+        filter::{
+            AccountDataSizeFilter, AccountKeyFilter, AccountOwnerFilter, AndFilter,
+            DatasourceFilter, FeePayerFilter, Filter, MemcmpFilter, NotFilter, OrFilter,
+            ProgramFilter, SignerFilter, SlotRangeFilter, TransactionStatusFilter, VoteFilter,
+        },
+        metrics::Metrics,
+        pipeline::{PipelineBuilder, ShutdownStrategy},
+    },
+    serde::Deserialize,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        path::Path,
+        str::FromStr,
+        sync::Arc,
+        time::Duration,
+    },
+};
+
+/// Creates a datasource from the `params` of a datasource entry.
+pub type DatasourceFactory =
+    Box<dyn Fn(serde_json::Value) -> CarbonResult<Arc<dyn Datasource + Send + Sync>> + Send + Sync>;
+
+/// Creates a metrics backend from the `params` of a metrics entry.
+pub type MetricsFactory =
+    Box<dyn Fn(serde_json::Value) -> CarbonResult<Arc<dyn Metrics>> + Send + Sync>;
+
+/// Adds a pipe to a builder, from the `params` and the resolved filters of a
+/// pipe entry.
+pub type PipeFactory = Box<
+    dyn Fn(
+            PipelineBuilder,
+            serde_json::Value,
+            Vec<Box<dyn Filter + Send + Sync + 'static>>,
+        ) -> CarbonResult<PipelineBuilder>
+        + Send
+        + Sync,
+>;
+
+/// The factories a `PipelineConfig` refers to by name.
+///
+/// The application registers its datasources, metrics backends and
+/// decoder/processor pairs under the names used in its configuration files.
+#[derive(Default)]
+pub struct PipelineRegistry {
+    datasources: HashMap<String, DatasourceFactory>,
+    metrics: HashMap<String, MetricsFactory>,
+    pipes: HashMap<String, PipeFactory>,
+}
+
+impl PipelineRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the factory of the datasources of kind `name`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The `kind` of the datasource entries the factory creates.
+    /// - `factory`: Creates a datasource from the `params` of an entry.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = PipelineRegistry::new().register_datasource("rpc_block_crawler", |params| {
+    ///     let params: CrawlerParams = serde_json::from_value(params)
+    ///         .map_err(|err| Error::Custom(format!("Invalid crawler params: {err}")))?;
+    ///     Ok(Arc::new(RpcBlockCrawler::new(params.rpc_url, params.start_slot, None, None, config)))
+    /// });
+    /// ```
+    pub fn register_datasource(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn(serde_json::Value) -> CarbonResult<Arc<dyn Datasource + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let name = name.into();
+        log::trace!("register_datasource(self, name: {name:?}, factory)");
+        self.datasources.insert(name, Box::new(factory));
+        self
+    }
+
+    /// Registers the factory of the metrics backends of kind `name`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The `kind` of the metrics entries the factory creates.
+    /// - `factory`: Creates a metrics backend from the `params` of an entry.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = PipelineRegistry::new()
+    ///     .register_metrics("prometheus", |_params| Ok(Arc::new(PrometheusMetrics::new())));
+    /// ```
+    pub fn register_metrics(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn(serde_json::Value) -> CarbonResult<Arc<dyn Metrics>> + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.into();
+        log::trace!("register_metrics(self, name: {name:?}, factory)");
+        self.metrics.insert(name, Box::new(factory));
+        self
+    }
+
+    /// Registers the factory of the pipes of kind `name`, usually a
+    /// decoder/processor pair.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The `kind` of the pipe entries the factory adds.
+    /// - `factory`: Adds a pipe to the builder, from the `params` of an entry
+    ///   and its filters.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = PipelineRegistry::new().register_pipe("token_accounts", |builder, _params, filters| {
+    ///     Ok(builder.account_with_filters(TokenProgramDecoder, TokenAccountProcessor, filters))
+    /// });
+    /// ```
+    pub fn register_pipe(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn(
+                PipelineBuilder,
+                serde_json::Value,
+                Vec<Box<dyn Filter + Send + Sync + 'static>>,
+            ) -> CarbonResult<PipelineBuilder>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let name = name.into();
+        log::trace!("register_pipe(self, name: {name:?}, factory)");
+        self.pipes.insert(name, Box::new(factory));
+        self
+    }
+}
+
+/// The configuration of a pipeline.
+///
+/// # Fields
+///
+/// - `datasources`: The datasources of the pipeline.
+/// - `metrics`: The metrics backends of the pipeline.
+/// - `pipes`: The pipes of the pipeline, in registration order.
+/// - `channel_buffer_size`: The size of the channel between the datasources
+///   and the pipeline. See `PipelineBuilder::channel_buffer_size`.
+/// - `shutdown_strategy`: `immediate` or `process_pending`. See
+///   `PipelineBuilder::shutdown_strategy`.
+/// - `drain_timeout_secs`: The time the pipeline may spend processing pending
+///   updates on shutdown. See `PipelineBuilder::drain_timeout`.
+/// - `metrics_flush_interval`: The interval at which metrics are flushed, in
+///   seconds. See `PipelineBuilder::metrics_flush_interval`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    #[serde(default)]
+    pub datasources: Vec<DatasourceConfig>,
+    #[serde(default)]
+    pub metrics: Vec<MetricsConfig>,
+    #[serde(default)]
+    pub pipes: Vec<PipeConfig>,
+    pub channel_buffer_size: Option<usize>,
+    pub shutdown_strategy: Option<ShutdownStrategy>,
+    pub drain_timeout_secs: Option<u64>,
+    pub metrics_flush_interval: Option<u64>,
+}
+
+/// A datasource entry.
+///
+/// # Fields
+///
+/// - `kind`: The name the datasource factory was registered under.
+/// - `id`: The name of the datasource, used by `datasource` filters. A unique
+///   id is generated if unset.
+/// - `params`: The parameters handed to the factory.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasourceConfig {
+    pub kind: String,
+    pub id: Option<String>,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// A metrics backend entry.
+///
+/// # Fields
+///
+/// - `kind`: The name the metrics factory was registered under.
+/// - `params`: The parameters handed to the factory.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    pub kind: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// A pipe entry.
+///
+/// # Fields
+///
+/// - `kind`: The name the pipe factory was registered under.
+/// - `filters`: The filters of the pipe.
+/// - `params`: The parameters handed to the factory.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipeConfig {
+    pub kind: String,
+    #[serde(default)]
+    pub filters: Vec<FilterConfig>,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// A built-in filter, tagged by `kind`. Public keys are base58 strings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FilterConfig {
+    /// A `DatasourceFilter` on the ids of datasource entries.
+    Datasource { ids: Vec<String> },
+    /// A `ProgramFilter`.
+    Program {
+        program_ids: Vec<String>,
+        #[serde(default = "default_include_inner_instructions")]
+        include_inner_instructions: bool,
+    },
+    /// A `SignerFilter`.
+    Signer { signers: Vec<String> },
+    /// A `FeePayerFilter`.
+    FeePayer { fee_payers: Vec<String> },
+    /// An `AccountKeyFilter`.
+    AccountKey { accounts: Vec<String> },
+    /// A `SlotRangeFilter` from `start`, included, to `end`, excluded.
+    SlotRange {
+        start: Option<u64>,
+        end: Option<u64>,
+    },
+    /// `TransactionStatusFilter::Succeeded`.
+    Succeeded,
+    /// `TransactionStatusFilter::Failed`.
+    Failed,
+    /// `VoteFilter::Vote`.
+    Vote,
+    /// `VoteFilter::NonVote`.
+    NonVote,
+    /// An `AccountOwnerFilter`.
+    AccountOwner { owners: Vec<String> },
+    /// An `AccountDataSizeFilter`.
+    AccountDataSize { data_size: usize },
+    /// A `MemcmpFilter`, with base58 encoded `bytes`.
+    Memcmp { offset: usize, bytes: String },
+    /// An `AndFilter`.
+    And { filters: Vec<FilterConfig> },
+    /// An `OrFilter`.
+    Or { filters: Vec<FilterConfig> },
+    /// A `NotFilter`.
+    Not { filter: Box<FilterConfig> },
+}
+
+fn default_include_inner_instructions() -> bool {
+    true
+}
+
+fn parse_pubkeys(pubkeys: &[String]) -> CarbonResult<Vec<Pubkey>> {
+    pubkeys
+        .iter()
+        .map(|pubkey| {
+            Pubkey::from_str(pubkey)
+                .map_err(|err| Error::Custom(format!("Invalid public key {pubkey:?}: {err}")))
+        })
+        .collect()
+}
+
+impl FilterConfig {
+    /// Checks that the `datasource` filters of this entry, including nested
+    /// ones, only refer to the ids in `datasource_ids`.
+    fn check_datasource_ids(&self, datasource_ids: &HashSet<&str>) -> CarbonResult<()> {
+        match self {
+            FilterConfig::Datasource { ids } => {
+                match ids.iter().find(|id| !datasource_ids.contains(id.as_str())) {
+                    Some(id) => Err(Error::Custom(format!(
+                        "Datasource filter refers to unknown datasource id {id:?}"
+                    ))),
+                    None => Ok(()),
+                }
+            }
+            FilterConfig::And { filters } | FilterConfig::Or { filters } => filters
+                .iter()
+                .try_for_each(|filter| filter.check_datasource_ids(datasource_ids)),
+            FilterConfig::Not { filter } => filter.check_datasource_ids(datasource_ids),
+            _ => Ok(()),
+        }
+    }
+
+    /// Creates the filter this entry describes.
+    pub fn resolve(&self) -> CarbonResult<Box<dyn Filter + Send + Sync + 'static>> {
+        let resolve_all = |filters: &[FilterConfig]| {
+            filters
+                .iter()
+                .map(FilterConfig::resolve)
+                .collect::<CarbonResult<Vec<_>>>()
+        };
+
+        Ok(match self {
+            FilterConfig::Datasource { ids } => Box::new(DatasourceFilter::new_many(
+                ids.iter().map(|id| DatasourceId::new_named(id)).collect(),
+            )),
+            FilterConfig::Program {
+                program_ids,
+                include_inner_instructions,
+            } => Box::new(
+                ProgramFilter::new_many(parse_pubkeys(program_ids)?)
+                    .include_inner_instructions(*include_inner_instructions),
+            ),
+            FilterConfig::Signer { signers } => {
+                Box::new(SignerFilter::new_many(parse_pubkeys(signers)?))
+            }
+            FilterConfig::FeePayer { fee_payers } => {
+                Box::new(FeePayerFilter::new_many(parse_pubkeys(fee_payers)?))
+            }
+            FilterConfig::AccountKey { accounts } => {
+                Box::new(AccountKeyFilter::new_many(parse_pubkeys(accounts)?))
+            }
+            FilterConfig::SlotRange { start, end } => Box::new(SlotRangeFilter {
+                start: start.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Included),
+                end: end.map_or(std::ops::Bound::Unbounded, std::ops::Bound::Excluded),
+            }),
+            FilterConfig::Succeeded => Box::new(TransactionStatusFilter::Succeeded),
+            FilterConfig::Failed => Box::new(TransactionStatusFilter::Failed),
+            FilterConfig::Vote => Box::new(VoteFilter::Vote),
+            FilterConfig::NonVote => Box::new(VoteFilter::NonVote),
+            FilterConfig::AccountOwner { owners } => {
+                Box::new(AccountOwnerFilter::new_many(parse_pubkeys(owners)?))
+            }
+            FilterConfig::AccountDataSize { data_size } => {
+                Box::new(AccountDataSizeFilter::new(*data_size))
+            }
+            FilterConfig::Memcmp { offset, bytes } => Box::new(MemcmpFilter::new(
+                *offset,
+                bs58::decode(bytes).into_vec().map_err(|err| {
+                    Error::Custom(format!("Invalid base58 memcmp bytes {bytes:?}: {err}"))
+                })?,
+            )),
+            FilterConfig::And { filters } => Box::new(AndFilter::new(resolve_all(filters)?)),
+            FilterConfig::Or { filters } => Box::new(OrFilter::new(resolve_all(filters)?)),
+            FilterConfig::Not { filter } => Box::new(NotFilter::new(filter.resolve()?)),
+        })
+    }
+}
+
+impl PipelineConfig {
+    /// Parses a configuration from a TOML document.
+    pub fn from_toml_str(config: &str) -> CarbonResult<Self> {
+        toml::from_str(config)
+            .map_err(|err| Error::Custom(format!("Invalid TOML pipeline config: {err}")))
+    }
+
+    /// Parses a configuration from a YAML document.
+    pub fn from_yaml_str(config: &str) -> CarbonResult<Self> {
+        serde_yaml_ng::from_str(config)
+            .map_err(|err| Error::Custom(format!("Invalid YAML pipeline config: {err}")))
+    }
+
+    /// Reads a configuration from a file, parsed as YAML if its extension is
+    /// `yaml` or `yml`, and as TOML otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> CarbonResult<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).map_err(|err| {
+            Error::Custom(format!(
+                "Failed to read pipeline config {}: {err}",
+                path.display()
+            ))
+        })?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => Self::from_yaml_str(&config),
+            _ => Self::from_toml_str(&config),
+        }
+    }
+
+    /// Resolves the configuration into a `PipelineBuilder`, creating its
+    /// datasources, metrics backends and pipes with the factories of
+    /// `registry`.
+    ///
+    /// The returned builder can be customized further before it is built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry refers to a kind that isn't registered,
+    /// if a filter is invalid or refers to an undeclared datasource id, or if
+    /// a factory fails.
+    pub fn into_builder(self, registry: &PipelineRegistry) -> CarbonResult<PipelineBuilder> {
+        log::trace!("into_builder(self: {self:?}, registry)");
+
+        let datasource_ids: HashSet<&str> = self
+            .datasources
+            .iter()
+            .filter_map(|datasource| datasource.id.as_deref())
+            .collect();
+        for filter in self.pipes.iter().flat_map(|pipe| &pipe.filters) {
+            filter.check_datasource_ids(&datasource_ids)?;
+        }
+
+        let mut builder = PipelineBuilder::new();
+
+        for datasource in self.datasources {
+            let factory = registry.datasources.get(&datasource.kind).ok_or_else(|| {
+                Error::Custom(format!("Unknown datasource kind {:?}", datasource.kind))
+            })?;
+            let id = datasource
+                .id
+                .map_or_else(DatasourceId::new_unique, |id| DatasourceId::new_named(&id));
+            builder.datasources.push((id, factory(datasource.params)?));
+        }
+
+        for metrics in self.metrics {
+            let factory = registry
+                .metrics
+                .get(&metrics.kind)
+                .ok_or_else(|| Error::Custom(format!("Unknown metrics kind {:?}", metrics.kind)))?;
+            builder = builder.metrics(factory(metrics.params)?);
+        }
+
+        for pipe in self.pipes {
+            let factory = registry
+                .pipes
+                .get(&pipe.kind)
+                .ok_or_else(|| Error::Custom(format!("Unknown pipe kind {:?}", pipe.kind)))?;
+            let filters = pipe
+                .filters
+                .iter()
+                .map(FilterConfig::resolve)
+                .collect::<CarbonResult<Vec<_>>>()?;
+            builder = factory(builder, pipe.params, filters)?;
+        }
+
+        if let Some(channel_buffer_size) = self.channel_buffer_size {
+            builder = builder.channel_buffer_size(channel_buffer_size);
+        }
+        if let Some(shutdown_strategy) = self.shutdown_strategy {
+            builder = builder.shutdown_strategy(shutdown_strategy);
+        }
+        if let Some(drain_timeout_secs) = self.drain_timeout_secs {
+            builder = builder.drain_timeout(Duration::from_secs(drain_timeout_secs));
+        }
+        if let Some(metrics_flush_interval) = self.metrics_flush_interval {
+            builder = builder.metrics_flush_interval(metrics_flush_interval);
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_toml_and_yaml() {
+        let toml = PipelineConfig::from_toml_str(
+            r#"
+            channel_buffer_size = 100
+            shutdown_strategy = "immediate"
+
+            [[datasources]]
+            kind = "rpc_block_crawler"
+            id = "mainnet"
+            params = { rpc_url = "http://localhost:8899", start_slot = 1 }
+
+            [[pipes]]
+            kind = "token_instructions"
+            filters = [
+                { kind = "non_vote" },
+                { kind = "not", filter = { kind = "slot_range", end = 10 } },
+            ]
+            "#,
+        )
+        .unwrap();
+        let yaml = PipelineConfig::from_yaml_str(
+            "
+channel_buffer_size: 100
+shutdown_strategy: immediate
+datasources:
+  - kind: rpc_block_crawler
+    id: mainnet
+    params:
+      rpc_url: http://localhost:8899
+      start_slot: 1
+pipes:
+  - kind: token_instructions
+    filters:
+      - kind: non_vote
+      - kind: not
+        filter:
+          kind: slot_range
+          end: 10
+",
+        )
+        .unwrap();
+
+        for config in [toml, yaml] {
+            assert_eq!(config.channel_buffer_size, Some(100));
+            assert_eq!(config.shutdown_strategy, Some(ShutdownStrategy::Immediate));
+            assert_eq!(config.datasources[0].params["start_slot"], 1);
+            assert!(matches!(
+                config.pipes[0].filters[1],
+                FilterConfig::Not { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rejects_unknown_kinds() {
+        let config = PipelineConfig::from_toml_str(
+            r#"
+            [[pipes]]
+            kind = "unregistered"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.into_builder(&PipelineRegistry::new()).is_err());
+        assert!(FilterConfig::Program {
+            program_ids: vec!["not a pubkey".to_string()],
+            include_inner_instructions: true,
+        }
+        .resolve()
+        .is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_datasource_ids() {
+        let config = |id: &str| {
+            PipelineConfig::from_toml_str(&format!(
+                r#"
+                [[datasources]]
+                kind = "test"
+                id = "mainnet"
+
+                [[pipes]]
+                kind = "test"
+                filters = [
+                    {{ kind = "not", filter = {{ kind = "datasource", ids = ["{id}"] }} }},
+                ]
+                "#
+            ))
+            .unwrap()
+        };
+        let registry = PipelineRegistry::new()
+            .register_datasource("test", |_| {
+                Err(Error::Custom("not created in this test".to_string()))
+            })
+            .register_pipe("test", |builder, _, _| Ok(builder));
+
+        let Err(Error::Custom(error)) = config("mainet").into_builder(&registry) else {
+            panic!("expected an unknown datasource id error");
+        };
+        assert!(error.contains("\"mainet\""));
+        let Err(Error::Custom(error)) = config("mainnet").into_builder(&registry) else {
+            panic!("expected the datasource factory error");
+        };
+        assert_eq!(error, "not created in this test");
+    }
+}
@@ -37,9 +37,14 @@ use solana_clock::Slot;
 use solana_program::hash::Hash;
 use solana_transaction_status::Rewards;
 use {
-    crate::{error::CarbonResult, metrics::MetricsCollection},
+    crate::{
+        checkpoint::CursorHandle,
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+    },
     async_trait::async_trait,
     chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
     solana_account::Account,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
@@ -75,6 +80,11 @@ pub struct DatasourceDisconnection {
 /// - `update_types`: Returns a list of `UpdateType` variants indicating the
 ///   types of updates the datasource can provide.
 ///
+/// # Provided Methods
+///
+/// - `set_cursor_handle`: Receives the `CursorHandle` through which the
+///   datasource commits its own cursor. Ignored by default.
+///
 /// # Example
 ///
 /// ```ignore
@@ -121,6 +131,13 @@ pub trait Datasource: Send + Sync {
     ) -> CarbonResult<()>;
 
     fn update_types(&self) -> Vec<UpdateType>;
+
+    /// Receives the `CursorHandle` of the datasource, before `consume` is
+    /// called, if the pipeline has a checkpoint store.
+    ///
+    /// Only datasources that track their position with a cursor instead of a
+    /// `Checkpoint` need to keep it, see `checkpoint::CursorHandle`.
+    fn set_cursor_handle(&self, _cursor_handle: CursorHandle) {}
 }
 
 /// A unique identifier for a datasource in the pipeline.
@@ -154,7 +171,7 @@ pub trait Datasource: Send + Sync {
 /// let datasource_id = DatasourceId::new_named("testnet");
 /// let filter = DatasourceFilter::new(datasource_id);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DatasourceId(String);
 
 impl DatasourceId {
@@ -206,6 +223,42 @@ impl DatasourceId {
     pub fn new_named(name: &str) -> Self {
         Self(name.to_string())
     }
+
+    /// Returns the datasource ID as a string slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use carbon_core::datasource::DatasourceId;
+    ///
+    /// let id = DatasourceId::new_named("mainnet-rpc");
+    /// assert_eq!(id.as_str(), "mainnet-rpc");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the name under which metrics of the datasource are reported.
+    ///
+    /// Named datasources report under their name. Datasources created with
+    /// `new_unique` get a new ID on every run, so they all report under
+    /// `unnamed` instead, keeping the metric series stable across runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use carbon_core::datasource::DatasourceId;
+    ///
+    /// assert_eq!(DatasourceId::new_named("mainnet-rpc").metric_name(), "mainnet-rpc");
+    /// assert_eq!(DatasourceId::new_unique().metric_name(), "unnamed");
+    /// ```
+    pub fn metric_name(&self) -> &str {
+        if uuid::Uuid::parse_str(&self.0).is_ok() {
+            "unnamed"
+        } else {
+            &self.0
+        }
+    }
 }
 
 /// Represents a data update in the `carbon-core` pipeline, encompassing
@@ -215,12 +268,59 @@ impl DatasourceId {
 /// - `Transaction`: Represents a transaction-related update, including
 ///   transaction metadata.
 /// - `AccountDeletion`: Represents an event where an account has been deleted.
-#[derive(Debug, Clone)]
+/// - `BlockDetails`: Represents the metadata of a block.
+/// - `SlotStatus`: Represents a change in the commitment status of a slot.
+///
+/// Updates implement `Serialize` and `Deserialize`, so they can be stored and
+/// read back, as done by `replay::RecordingDatasource`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Update {
     Account(AccountUpdate),
     Transaction(Box<TransactionUpdate>),
     AccountDeletion(AccountDeletion),
     BlockDetails(BlockDetails),
+    SlotStatus(SlotStatusUpdate),
+}
+
+impl Update {
+    /// Returns the slot the update belongs to.
+    pub fn slot(&self) -> u64 {
+        match self {
+            Update::Account(account_update) => account_update.slot,
+            Update::Transaction(transaction_update) => transaction_update.slot,
+            Update::AccountDeletion(account_deletion) => account_deletion.slot,
+            Update::BlockDetails(block_details) => block_details.slot,
+            Update::SlotStatus(slot_status) => slot_status.slot,
+        }
+    }
+}
+
+/// The version of the encoding of the updates written by `encode_update`.
+///
+/// It must be bumped whenever a field of `Update`, or of a type it holds, is
+/// added, removed, reordered or changes type.
+pub const PAYLOAD_SCHEMA_VERSION: u16 = 1;
+
+/// Encodes an update and the `DatasourceId` it was received from, so that
+/// they can be stored and sent to a pipeline again later.
+///
+/// Dead letters store their update with it, and recordings store every
+/// update they hold with it.
+///
+/// # Notes
+///
+/// - The encoding is the bincode encoding of `Update` and the types it holds,
+///   which has no field names or defaults. Stored updates should be kept
+///   along with the `PAYLOAD_SCHEMA_VERSION` they were encoded with.
+pub fn encode_update(update: &Update, datasource_id: &DatasourceId) -> CarbonResult<Vec<u8>> {
+    bincode::serialize(&(update, datasource_id))
+        .map_err(|err| Error::Custom(format!("failed to encode update: {err}")))
+}
+
+/// Decodes an update and its `DatasourceId` encoded by `encode_update`.
+pub fn decode_update(bytes: &[u8]) -> CarbonResult<(Update, DatasourceId)> {
+    bincode::deserialize(bytes)
+        .map_err(|err| Error::Custom(format!("failed to decode update: {err}")))
 }
 
 /// Enumerates the types of updates a datasource can provide.
@@ -230,11 +330,16 @@ pub enum Update {
 /// - `Transaction`: Indicates that the datasource provides transaction updates.
 /// - `AccountDeletion`: Indicates that the datasource provides account deletion
 ///   events.
+/// - `SlotStatus`: Indicates that the datasource provides slot status updates.
+/// - `BlockDetails`: Indicates that the datasource provides block details,
+///   sent after the transactions of their block.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UpdateType {
     AccountUpdate,
     Transaction,
     AccountDeletion,
+    SlotStatus,
+    BlockDetails,
 }
 
 /// Represents an update to a Solana account, including its public key, data,
@@ -248,7 +353,7 @@ pub enum UpdateType {
 /// - `account`: The new state of the account.
 /// - `slot`: The slot number in which this account update was recorded.
 /// - `transaction_signature`: Signature of the transaction that caused the update.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountUpdate {
     pub pubkey: Pubkey,
     pub account: Account,
@@ -267,8 +372,8 @@ pub struct AccountUpdate {
 /// - `rewards`: Optional rewards information associated with the block, such as staking rewards.
 /// - `num_reward_partitions`: Optional number of reward partitions in the block.
 /// - `block_time`: Optional Unix timestamp indicating when the block was processed.
-/// - `block_height`: Optional height of the block in the blockchain.#[derive(Debug, Clone)]
-#[derive(Debug, Clone)]
+/// - `block_height`: Optional height of the block in the blockchain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockDetails {
     pub slot: u64,
     pub block_hash: Option<Hash>,
@@ -279,6 +384,40 @@ pub struct BlockDetails {
     pub block_height: Option<u64>,
 }
 
+/// Represents the commitment status a slot has reached.
+///
+/// - `Processed`: The slot has been processed by the node.
+/// - `Confirmed`: The slot has been voted on by a supermajority of the cluster.
+/// - `Finalized`: The slot has been rooted and can no longer be rolled back.
+/// - `Dead`: The slot failed to replay and will never be confirmed. Updates
+///   previously received for it should be discarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SlotStatus {
+    Processed,
+    Confirmed,
+    Finalized,
+    Dead,
+}
+
+/// Represents a change in the commitment status of a slot.
+///
+/// The `SlotStatusUpdate` struct is emitted by datasources that stream updates
+/// at a low commitment level, so that the pipeline can tell when the data of a
+/// slot becomes confirmed or finalized, or when the slot is abandoned.
+///
+/// - `slot`: The slot whose status changed.
+/// - `parent`: The parent slot, if known.
+/// - `status`: The new status of the slot.
+/// - `dead_error`: The reason the slot is dead, if `status` is `Dead` and the
+///   datasource provides one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotStatusUpdate {
+    pub slot: u64,
+    pub parent: Option<u64>,
+    pub status: SlotStatus,
+    pub dead_error: Option<String>,
+}
+
 /// Represents the deletion of a Solana account, containing the account's public
 /// key and slot information.
 ///
@@ -289,7 +428,7 @@ pub struct BlockDetails {
 /// - `pubkey`: The public key of the deleted account.
 /// - `slot`: The slot number in which the account was deleted.
 /// - `transaction_signature`: Signature of the transaction that caused the update.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountDeletion {
     pub pubkey: Pubkey,
     pub slot: u64,
@@ -316,10 +455,11 @@ pub struct AccountDeletion {
 /// - `block_hash`: Block hash that can be used to detect a fork.
 ///
 /// Note: The `block_time` and `index` fields may not be available in all scenarios.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionUpdate {
     pub signature: Signature,
     pub transaction: VersionedTransaction, // TODO: replace with solana_transaction crate after 2.2.0 release
+    #[serde(with = "transaction_status_meta")]
     pub meta: TransactionStatusMeta,
     pub is_vote: bool,
     pub slot: u64,
@@ -327,3 +467,206 @@ pub struct TransactionUpdate {
     pub block_time: Option<i64>,
     pub block_hash: Option<Hash>,
 }
+
+/// Serializes `TransactionStatusMeta`, which does not implement `Serialize`
+/// and `Deserialize` itself, through a record mirroring its fields.
+mod transaction_status_meta {
+    use {
+        serde::{Deserialize, Deserializer, Serialize, Serializer},
+        solana_account_decoder_client_types::token::UiTokenAmount,
+        solana_message::{compiled_instruction::CompiledInstruction, v0::LoadedAddresses},
+        solana_pubkey::Pubkey,
+        solana_transaction_context::TransactionReturnData,
+        solana_transaction_error::TransactionError,
+        solana_transaction_status::{
+            InnerInstruction, InnerInstructions, Rewards, TransactionStatusMeta,
+            TransactionTokenBalance,
+        },
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct TransactionStatusMetaRecord {
+        status: Result<(), TransactionError>,
+        fee: u64,
+        pre_balances: Vec<u64>,
+        post_balances: Vec<u64>,
+        inner_instructions: Option<Vec<InnerInstructionsRecord>>,
+        log_messages: Option<Vec<String>>,
+        pre_token_balances: Option<Vec<TokenBalanceRecord>>,
+        post_token_balances: Option<Vec<TokenBalanceRecord>>,
+        rewards: Option<Rewards>,
+        loaded_writable_addresses: Vec<Pubkey>,
+        loaded_readonly_addresses: Vec<Pubkey>,
+        return_data: Option<(Pubkey, Vec<u8>)>,
+        compute_units_consumed: Option<u64>,
+        cost_units: Option<u64>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct InnerInstructionsRecord {
+        index: u8,
+        instructions: Vec<InnerInstructionRecord>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct InnerInstructionRecord {
+        program_id_index: u8,
+        accounts: Vec<u8>,
+        data: Vec<u8>,
+        stack_height: Option<u32>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct TokenBalanceRecord {
+        account_index: u8,
+        mint: String,
+        ui_amount: Option<f64>,
+        decimals: u8,
+        amount: String,
+        ui_amount_string: String,
+        owner: String,
+        program_id: String,
+    }
+
+    impl From<&TransactionStatusMeta> for TransactionStatusMetaRecord {
+        fn from(meta: &TransactionStatusMeta) -> Self {
+            Self {
+                status: meta.status.clone(),
+                fee: meta.fee,
+                pre_balances: meta.pre_balances.clone(),
+                post_balances: meta.post_balances.clone(),
+                inner_instructions: meta.inner_instructions.as_ref().map(|inner_instructions| {
+                    inner_instructions
+                        .iter()
+                        .map(|inner_instructions| InnerInstructionsRecord {
+                            index: inner_instructions.index,
+                            instructions: inner_instructions
+                                .instructions
+                                .iter()
+                                .map(|inner_instruction| InnerInstructionRecord {
+                                    program_id_index: inner_instruction
+                                        .instruction
+                                        .program_id_index,
+                                    accounts: inner_instruction.instruction.accounts.clone(),
+                                    data: inner_instruction.instruction.data.clone(),
+                                    stack_height: inner_instruction.stack_height,
+                                })
+                                .collect(),
+                        })
+                        .collect()
+                }),
+                log_messages: meta.log_messages.clone(),
+                pre_token_balances: meta
+                    .pre_token_balances
+                    .as_ref()
+                    .map(|balances| balances.iter().map(TokenBalanceRecord::from).collect()),
+                post_token_balances: meta
+                    .post_token_balances
+                    .as_ref()
+                    .map(|balances| balances.iter().map(TokenBalanceRecord::from).collect()),
+                rewards: meta.rewards.clone(),
+                loaded_writable_addresses: meta.loaded_addresses.writable.clone(),
+                loaded_readonly_addresses: meta.loaded_addresses.readonly.clone(),
+                return_data: meta
+                    .return_data
+                    .as_ref()
+                    .map(|return_data| (return_data.program_id, return_data.data.clone())),
+                compute_units_consumed: meta.compute_units_consumed,
+                cost_units: meta.cost_units,
+            }
+        }
+    }
+
+    impl From<TransactionStatusMetaRecord> for TransactionStatusMeta {
+        fn from(record: TransactionStatusMetaRecord) -> Self {
+            Self {
+                status: record.status,
+                fee: record.fee,
+                pre_balances: record.pre_balances,
+                post_balances: record.post_balances,
+                inner_instructions: record.inner_instructions.map(|inner_instructions| {
+                    inner_instructions
+                        .into_iter()
+                        .map(|inner_instructions| InnerInstructions {
+                            index: inner_instructions.index,
+                            instructions: inner_instructions
+                                .instructions
+                                .into_iter()
+                                .map(|inner_instruction| InnerInstruction {
+                                    instruction: CompiledInstruction {
+                                        program_id_index: inner_instruction.program_id_index,
+                                        accounts: inner_instruction.accounts,
+                                        data: inner_instruction.data,
+                                    },
+                                    stack_height: inner_instruction.stack_height,
+                                })
+                                .collect(),
+                        })
+                        .collect()
+                }),
+                log_messages: record.log_messages,
+                pre_token_balances: record
+                    .pre_token_balances
+                    .map(|balances| balances.into_iter().map(Into::into).collect()),
+                post_token_balances: record
+                    .post_token_balances
+                    .map(|balances| balances.into_iter().map(Into::into).collect()),
+                rewards: record.rewards,
+                loaded_addresses: LoadedAddresses {
+                    writable: record.loaded_writable_addresses,
+                    readonly: record.loaded_readonly_addresses,
+                },
+                return_data: record
+                    .return_data
+                    .map(|(program_id, data)| TransactionReturnData { program_id, data }),
+                compute_units_consumed: record.compute_units_consumed,
+                cost_units: record.cost_units,
+            }
+        }
+    }
+
+    impl From<&TransactionTokenBalance> for TokenBalanceRecord {
+        fn from(balance: &TransactionTokenBalance) -> Self {
+            Self {
+                account_index: balance.account_index,
+                mint: balance.mint.clone(),
+                ui_amount: balance.ui_token_amount.ui_amount,
+                decimals: balance.ui_token_amount.decimals,
+                amount: balance.ui_token_amount.amount.clone(),
+                ui_amount_string: balance.ui_token_amount.ui_amount_string.clone(),
+                owner: balance.owner.clone(),
+                program_id: balance.program_id.clone(),
+            }
+        }
+    }
+
+    impl From<TokenBalanceRecord> for TransactionTokenBalance {
+        fn from(record: TokenBalanceRecord) -> Self {
+            Self {
+                account_index: record.account_index,
+                mint: record.mint,
+                ui_token_amount: UiTokenAmount {
+                    ui_amount: record.ui_amount,
+                    decimals: record.decimals,
+                    amount: record.amount,
+                    ui_amount_string: record.ui_amount_string,
+                },
+                owner: record.owner,
+                program_id: record.program_id,
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        meta: &TransactionStatusMeta,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        TransactionStatusMetaRecord::from(meta).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TransactionStatusMeta, D::Error> {
+        TransactionStatusMetaRecord::deserialize(deserializer).map(Into::into)
+    }
+}
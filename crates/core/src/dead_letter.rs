@@ -0,0 +1,482 @@
+//! Provides dead-letter sinks that collect the updates a pipe failed to
+//! process, so they can be inspected and replayed later.
+//!
+//! When a pipe's `ErrorPolicy` gives up on an update, the pipeline wraps the
+//! update and the error in a `DeadLetter` and hands it to the sink configured
+//! through `PipelineBuilder::dead_letter_sink`.
+//!
+//! # Provided Sinks
+//!
+//! - **`JsonlDeadLetterSink`**: Appends one JSON record per line to a file.
+//! - **`PostgresDeadLetterSink`**: Inserts records into the
+//!   `carbon_dead_letters` table (requires the `postgres` feature, see
+//!   `postgres::dead_letter`).
+//!
+//! Both store a `DeadLetterRecord`, which identifies the failed update by its
+//! slot and transaction signature or account pubkey, and carries the update
+//! itself, encoded with `datasource::encode_update`.
+//!
+//! # Replaying
+//!
+//! `DeadLetterReplayDatasource` sends the updates of a list of records to a
+//! pipeline again, for example once the bug that made a pipe fail is fixed.
+//! `read_jsonl_dead_letters` reads the records written by a
+//! `JsonlDeadLetterSink`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use carbon_core::{
+//!     dead_letter::{read_jsonl_dead_letters, DeadLetterReplayDatasource, JsonlDeadLetterSink},
+//!     error_policy::ErrorPolicy,
+//! };
+//!
+//! carbon_core::pipeline::Pipeline::builder()
+//!     .datasource(transaction_crawler)
+//!     .dead_letter_sink(Arc::new(JsonlDeadLetterSink::new("dead_letters.jsonl")))
+//!     .error_policy(ErrorPolicy::retry(3))
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//!
+//! // Later, once the processor is fixed, replay the dead letters.
+//! let records = read_jsonl_dead_letters("dead_letters.jsonl")?;
+//! carbon_core::pipeline::Pipeline::builder()
+//!     .datasource(DeadLetterReplayDatasource::new(records))
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Metrics
+//!
+//! - `updates_dead_lettered`: A counter of the dead letters sent to the sink.
+//! - `dead_letters_failed`: A counter of the dead letters the sink failed to
+//!   store. These are only logged.
+//!
+//! # Notes
+//!
+//! - An update that several pipes gave up on is dead-lettered once per pipe.
+//!   `DeadLetterReplayDatasource` sends it only once, so every pipe processes
+//!   it again, including the ones that succeeded the first time.
+
+use {
+    crate::{
+        datasource::{decode_update, encode_update, Datasource, DatasourceId, Update, UpdateType},
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+    },
+    async_trait::async_trait,
+    base64::{engine::general_purpose::STANDARD, Engine},
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashSet,
+        fmt,
+        io::{BufRead, BufReader, Write},
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+    tokio_util::sync::CancellationToken,
+};
+
+/// An update that a pipe failed to process.
+///
+/// # Fields
+///
+/// - `pipe`: The pipe that failed, such as `instruction[0]` for the first
+///   instruction pipe added to the pipeline.
+/// - `datasource_id`: The datasource the update was received from.
+/// - `update`: The update that failed to process.
+/// - `error`: The error returned by the last attempt.
+/// - `attempts`: The number of attempts made.
+/// - `failed_at`: When the last attempt failed.
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub pipe: String,
+    pub datasource_id: DatasourceId,
+    pub update: Update,
+    pub error: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// A serializable summary of a `DeadLetter`, as stored by the provided
+/// sinks.
+///
+/// # Fields
+///
+/// - `update_type`: The kind of update, such as `transaction` or `account`.
+/// - `slot`: The slot of the update.
+/// - `signature`: The signature of the transaction, for transaction updates
+///   and account updates caused by a transaction.
+/// - `pubkey`: The pubkey of the account, for account updates and deletions.
+/// - `payload`: The update and its `DatasourceId`, as encoded by
+///   `datasource::encode_update`, in base64. `None` if the update could not be
+///   encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterRecord {
+    pub pipe: String,
+    pub datasource_id: String,
+    pub update_type: String,
+    pub slot: u64,
+    pub signature: Option<String>,
+    pub pubkey: Option<String>,
+    pub error: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+    #[serde(default)]
+    pub payload: Option<String>,
+}
+
+impl DeadLetterRecord {
+    /// Decodes the update stored in the record, along with the `DatasourceId`
+    /// it was received from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record has no payload, or if it can't be
+    /// decoded.
+    pub fn update(&self) -> CarbonResult<(Update, DatasourceId)> {
+        let payload = self.payload.as_ref().ok_or_else(|| {
+            Error::Custom(format!(
+                "dead letter of {} at slot {} has no payload",
+                self.update_type, self.slot
+            ))
+        })?;
+        let bytes = STANDARD
+            .decode(payload)
+            .map_err(|err| Error::Custom(format!("failed to decode dead letter payload: {err}")))?;
+
+        decode_update(&bytes)
+    }
+}
+
+impl From<&DeadLetter> for DeadLetterRecord {
+    fn from(dead_letter: &DeadLetter) -> Self {
+        let (update_type, slot, signature, pubkey) = match &dead_letter.update {
+            Update::Account(account_update) => (
+                "account",
+                account_update.slot,
+                account_update.transaction_signature,
+                Some(account_update.pubkey),
+            ),
+            Update::Transaction(transaction_update) => (
+                "transaction",
+                transaction_update.slot,
+                Some(transaction_update.signature),
+                None,
+            ),
+            Update::AccountDeletion(account_deletion) => (
+                "account_deletion",
+                account_deletion.slot,
+                account_deletion.transaction_signature,
+                Some(account_deletion.pubkey),
+            ),
+            Update::BlockDetails(block_details) => {
+                ("block_details", block_details.slot, None, None)
+            }
+            Update::SlotStatus(slot_status) => ("slot_status", slot_status.slot, None, None),
+        };
+
+        let payload = match encode_update(&dead_letter.update, &dead_letter.datasource_id) {
+            Ok(bytes) => Some(STANDARD.encode(bytes)),
+            Err(err) => {
+                log::error!("failed to encode dead-lettered update: {err:?}");
+                None
+            }
+        };
+
+        Self {
+            pipe: dead_letter.pipe.clone(),
+            datasource_id: dead_letter.datasource_id.as_str().to_string(),
+            update_type: update_type.to_string(),
+            slot,
+            signature: signature.map(|signature| signature.to_string()),
+            pubkey: pubkey.map(|pubkey| pubkey.to_string()),
+            error: dead_letter.error.clone(),
+            attempts: dead_letter.attempts,
+            failed_at: dead_letter.failed_at,
+            payload,
+        }
+    }
+}
+
+/// Defines the interface for storing dead letters.
+///
+/// # Example
+///
+/// ```ignore
+/// use async_trait::async_trait;
+/// use carbon_core::{
+///     dead_letter::{DeadLetter, DeadLetterSink},
+///     error::CarbonResult,
+/// };
+///
+/// struct KafkaDeadLetterSink { /* ... */ }
+///
+/// #[async_trait]
+/// impl DeadLetterSink for KafkaDeadLetterSink {
+///     async fn send(&self, dead_letter: DeadLetter) -> CarbonResult<()> {
+///         // Publish the dead letter to a topic.
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    async fn send(&self, dead_letter: DeadLetter) -> CarbonResult<()>;
+}
+
+impl fmt::Debug for dyn DeadLetterSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DeadLetterSink")
+    }
+}
+
+/// A `DeadLetterSink` that appends a `DeadLetterRecord` per line to a JSONL
+/// file.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use carbon_core::dead_letter::JsonlDeadLetterSink;
+///
+/// let sink = Arc::new(JsonlDeadLetterSink::new("/var/lib/indexer/dead_letters.jsonl"));
+/// ```
+pub struct JsonlDeadLetterSink {
+    path: Arc<PathBuf>,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonlDeadLetterSink {
+    /// Creates a sink appending to the file at `path`. The file is created on
+    /// the first dead letter if it does not exist yet.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        log::trace!("JsonlDeadLetterSink::new(path: {path:?})");
+
+        Self {
+            path: Arc::new(path),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Returns the path of the dead-letter file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for JsonlDeadLetterSink {
+    async fn send(&self, dead_letter: DeadLetter) -> CarbonResult<()> {
+        let mut line = serde_json::to_vec(&DeadLetterRecord::from(&dead_letter))
+            .map_err(|err| Error::Custom(format!("failed to serialize dead letter: {err}")))?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().await;
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path.as_path())
+                .and_then(|mut file| file.write_all(&line))
+                .map_err(|err| {
+                    Error::Custom(format!("failed to write dead letter to {path:?}: {err}"))
+                })
+        })
+        .await
+        .map_err(|err| Error::Custom(format!("dead letter write task failed: {err}")))?
+    }
+}
+
+/// Reads the records written by a `JsonlDeadLetterSink` to the file at
+/// `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read, or if a line is not a valid
+/// `DeadLetterRecord`.
+pub fn read_jsonl_dead_letters(path: impl AsRef<Path>) -> CarbonResult<Vec<DeadLetterRecord>> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path)
+        .map_err(|err| Error::Custom(format!("failed to open {path:?}: {err}")))?;
+
+    BufReader::new(file)
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|line| {
+            let line =
+                line.map_err(|err| Error::Custom(format!("failed to read {path:?}: {err}")))?;
+            serde_json::from_str(&line)
+                .map_err(|err| Error::Custom(format!("invalid dead letter in {path:?}: {err}")))
+        })
+        .collect()
+}
+
+/// A datasource that sends the updates stored in dead-letter records to the
+/// pipeline again.
+///
+/// Updates are sent in the order of the records, with the `DatasourceId` they
+/// were originally received from, and an update dead-lettered by several
+/// pipes is sent once. Records without a payload are skipped with a warning.
+///
+/// # Example
+///
+/// ```ignore
+/// use carbon_core::dead_letter::{read_jsonl_dead_letters, DeadLetterReplayDatasource};
+///
+/// let datasource = DeadLetterReplayDatasource::new(read_jsonl_dead_letters("dead_letters.jsonl")?);
+/// ```
+pub struct DeadLetterReplayDatasource {
+    records: Vec<DeadLetterRecord>,
+}
+
+impl DeadLetterReplayDatasource {
+    pub fn new(records: Vec<DeadLetterRecord>) -> Self {
+        log::trace!(
+            "DeadLetterReplayDatasource::new(records: {})",
+            records.len()
+        );
+
+        Self { records }
+    }
+}
+
+#[async_trait]
+impl Datasource for DeadLetterReplayDatasource {
+    async fn consume(
+        &self,
+        _id: DatasourceId,
+        sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut sent = HashSet::new();
+
+        for record in &self.records {
+            let Some(payload) = &record.payload else {
+                log::warn!(
+                    "skipping dead letter of {} at slot {} without a payload",
+                    record.update_type,
+                    record.slot
+                );
+                continue;
+            };
+            if !sent.insert(payload.as_str()) {
+                continue;
+            }
+
+            let update = record.update()?;
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                result = sender.send(update) => {
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![
+            UpdateType::AccountUpdate,
+            UpdateType::Transaction,
+            UpdateType::AccountDeletion,
+            UpdateType::BlockDetails,
+            UpdateType::SlotStatus,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::datasource::AccountUpdate, solana_account::Account, solana_pubkey::Pubkey,
+    };
+
+    fn dead_letter(pipe: &str, update: Update) -> DeadLetter {
+        DeadLetter {
+            pipe: pipe.to_string(),
+            datasource_id: DatasourceId::new_named("test"),
+            update,
+            error: "failed".to_string(),
+            attempts: 1,
+            failed_at: Utc::now(),
+        }
+    }
+
+    fn account_update(slot: u64) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey: Pubkey::new_unique(),
+            account: Account {
+                lamports: slot,
+                ..Account::default()
+            },
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_replays_dead_lettered_updates() {
+        let path = std::env::temp_dir().join(format!(
+            "carbon-dead-letters-{}.jsonl",
+            uuid::Uuid::new_v4()
+        ));
+        let sink = JsonlDeadLetterSink::new(&path);
+
+        let (first, second) = (account_update(10), account_update(11));
+        sink.send(dead_letter("account[0]", first.clone()))
+            .await
+            .unwrap();
+        sink.send(dead_letter("account[1]", first.clone()))
+            .await
+            .unwrap();
+        sink.send(dead_letter("account[0]", second.clone()))
+            .await
+            .unwrap();
+
+        let records = read_jsonl_dead_letters(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 3);
+
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(10);
+        DeadLetterReplayDatasource::new(records)
+            .consume(
+                DatasourceId::new_named("replay"),
+                sender,
+                CancellationToken::new(),
+                Arc::new(MetricsCollection::new(vec![])),
+            )
+            .await
+            .unwrap();
+
+        let mut replayed = Vec::new();
+        while let Some((update, datasource_id)) = receiver.recv().await {
+            assert_eq!(datasource_id, DatasourceId::new_named("test"));
+            replayed.push(update);
+        }
+        assert_eq!(replayed.len(), 2);
+        for (replayed, expected) in replayed.iter().zip([&first, &second]) {
+            match (replayed, expected) {
+                (Update::Account(replayed), Update::Account(expected)) => {
+                    assert_eq!(replayed.pubkey, expected.pubkey);
+                    assert_eq!(replayed.account, expected.account);
+                    assert_eq!(replayed.slot, expected.slot);
+                }
+                _ => panic!("expected account updates"),
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Provides a deduplication stage that drops updates delivered more than once,
+//! typically by redundant datasources streaming the same data.
+//!
+//! When enabled through `PipelineBuilder::deduplication`, every update is
+//! checked against the updates already seen within a window of recent slots
+//! before it reaches the pipes:
+//!
+//! - Transactions are identified by their signature.
+//! - Account updates are identified by their pubkey, slot and a hash of the
+//!   account data.
+//! - Slot status updates are identified by their slot and status.
+//!
+//! Account deletions and block details are always passed through.
+//!
+//! # Metrics
+//!
+//! Every dropped duplicate increments the `updates_duplicate` counter as well
+//! as `updates_duplicate.{datasource_id}`, counting how many duplicates each
+//! datasource supplied. Datasources without a name are counted together as
+//! `updates_duplicate.unnamed`, see `DatasourceId::metric_name`.
+//!
+//! # Notes
+//!
+//! - Memory use is bounded by the slot window: the keys of slots more than
+//!   `slot_window` slots behind the highest slot seen are evicted.
+//! - Updates for slots that have already been evicted can no longer be
+//!   recognized as duplicates and are passed through.
+
+use {
+    crate::datasource::{SlotStatus, Update},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::{
+        collections::{BTreeMap, HashSet},
+        hash::{DefaultHasher, Hash, Hasher},
+    },
+};
+
+/// The default number of slots the deduplication stage remembers.
+pub const DEFAULT_DEDUPLICATION_SLOT_WINDOW: u64 = 150;
+
+/// Configures the deduplication stage of a pipeline.
+///
+/// # Fields
+///
+/// - `slot_window`: The number of slots, counting back from the highest slot
+///   seen, for which update keys are kept. Redundant datasources rarely drift
+///   apart by more than a few slots, so the default of 150 slots (about a
+///   minute) leaves plenty of margin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeduplicationConfig {
+    pub slot_window: u64,
+}
+
+impl Default for DeduplicationConfig {
+    fn default() -> Self {
+        Self {
+            slot_window: DEFAULT_DEDUPLICATION_SLOT_WINDOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum UpdateKey {
+    Transaction(Signature),
+    Account { pubkey: Pubkey, data_hash: u64 },
+    SlotStatus(SlotStatus),
+}
+
+/// Remembers the keys of recently seen updates, grouped by slot.
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+    slot_window: u64,
+    highest_slot: u64,
+    seen: BTreeMap<u64, HashSet<UpdateKey>>,
+}
+
+impl Deduplicator {
+    pub(crate) fn new(config: DeduplicationConfig) -> Self {
+        Self {
+            slot_window: config.slot_window,
+            highest_slot: 0,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Records `update` and returns whether an identical update has already
+    /// been seen within the slot window.
+    pub(crate) fn is_duplicate(&mut self, update: &Update) -> bool {
+        let (slot, key) = match update {
+            Update::Transaction(transaction_update) => (
+                transaction_update.slot,
+                UpdateKey::Transaction(transaction_update.signature),
+            ),
+            Update::Account(account_update) => {
+                let mut hasher = DefaultHasher::new();
+                account_update.account.data.hash(&mut hasher);
+
+                (
+                    account_update.slot,
+                    UpdateKey::Account {
+                        pubkey: account_update.pubkey,
+                        data_hash: hasher.finish(),
+                    },
+                )
+            }
+            Update::SlotStatus(slot_status) => {
+                (slot_status.slot, UpdateKey::SlotStatus(slot_status.status))
+            }
+            Update::AccountDeletion(_) | Update::BlockDetails(_) => return false,
+        };
+
+        let oldest_slot = self.highest_slot.saturating_sub(self.slot_window);
+        if slot < oldest_slot {
+            return false;
+        }
+
+        if slot > self.highest_slot {
+            self.highest_slot = slot;
+            let oldest_slot = slot.saturating_sub(self.slot_window);
+            self.seen = self.seen.split_off(&oldest_slot);
+        }
+
+        !self.seen.entry(slot).or_default().insert(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::datasource::AccountUpdate, solana_account::Account};
+
+    fn account_update(pubkey: Pubkey, slot: u64, data: Vec<u8>) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey,
+            account: Account {
+                data,
+                ..Account::default()
+            },
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    #[test]
+    fn test_drops_repeated_account_updates() {
+        let mut deduplicator = Deduplicator::new(DeduplicationConfig::default());
+        let pubkey = Pubkey::new_unique();
+
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 10, vec![1])));
+        assert!(deduplicator.is_duplicate(&account_update(pubkey, 10, vec![1])));
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 10, vec![2])));
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 11, vec![1])));
+    }
+
+    #[test]
+    fn test_evicts_slots_outside_window() {
+        let mut deduplicator = Deduplicator::new(DeduplicationConfig { slot_window: 5 });
+        let pubkey = Pubkey::new_unique();
+
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 10, vec![1])));
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 20, vec![1])));
+        assert_eq!(deduplicator.seen.len(), 1);
+        assert!(!deduplicator.is_duplicate(&account_update(pubkey, 10, vec![1])));
+        assert!(deduplicator.is_duplicate(&account_update(pubkey, 20, vec![1])));
+    }
+}
@@ -13,6 +13,9 @@
 //! - **`extract_discriminator`**: A function that separates a discriminator
 //!   from the rest of a byte slice, used for parsing data with prefixed
 //!   discriminators.
+//! - **`DiscriminatorIndex`**: A lookup from discriminators to the types they
+//!   identify, used to dispatch decoding in one step instead of trying every
+//!   type in turn.
 //! - **`ArrangeAccounts`**: A trait that allows for defining a specific
 //!   arrangement of accounts, suitable for handling Solana account metadata in
 //!   a customized way.
@@ -27,8 +30,10 @@
 //!   Solana instructions.
 
 use std::{
+    collections::HashMap,
     io::{Error, ErrorKind, Read, Result},
     ops::Deref,
+    sync::OnceLock,
 };
 /// A trait for custom deserialization of types from byte slices.
 ///
@@ -89,6 +94,112 @@ pub fn extract_discriminator(length: usize, data: &[u8]) -> Option<(&[u8], &[u8]
     Some((&data[..length], &data[length..]))
 }
 
+/// A lookup from the discriminators of a list of types to their positions in
+/// the list.
+///
+/// Decoders use it to find the types whose discriminator prefixes the data in
+/// one step, instead of deserializing the data as every type in turn:
+/// `try_decode_instructions!` and `try_decode_accounts!` index the
+/// `DISCRIMINATOR` of `CarbonDeserialize` types, and the decoders generated
+/// from Codama IDLs index the discriminators of their IDL. Both then decode
+/// each candidate with a `match` on its position. Discriminators of any length are
+/// supported, such as 8-byte Anchor discriminators and 1-byte native ones,
+/// and may be mixed within a list.
+///
+/// # Example
+///
+/// ```rust
+/// use carbon_core::deserialize::DiscriminatorIndex;
+///
+/// let index = DiscriminatorIndex::new(&[&[1], &[2], &[2, 7]]);
+///
+/// assert_eq!(index.candidates(&[2, 7, 0]).collect::<Vec<_>>(), vec![2, 1]);
+/// ```
+///
+/// # Notes
+///
+/// - Types with an empty discriminator match any data. They are returned
+///   after the types whose discriminator matched, in their order in the list.
+#[derive(Debug, Clone, Default)]
+pub struct DiscriminatorIndex {
+    lengths: Vec<usize>,
+    positions: HashMap<&'static [u8], Vec<usize>>,
+    fallback: Vec<usize>,
+}
+
+impl DiscriminatorIndex {
+    /// Indexes a list of discriminators by their bytes.
+    pub fn new(discriminators: &[&'static [u8]]) -> Self {
+        let mut index = Self::default();
+
+        for (position, discriminator) in discriminators.iter().enumerate() {
+            if discriminator.is_empty() {
+                index.fallback.push(position);
+                continue;
+            }
+            if !index.lengths.contains(&discriminator.len()) {
+                index.lengths.push(discriminator.len());
+            }
+            index
+                .positions
+                .entry(discriminator)
+                .or_default()
+                .push(position);
+        }
+
+        index.lengths.sort_unstable_by(|a, b| b.cmp(a));
+        index
+    }
+
+    /// Returns the positions of the types that `data` may be decoded as.
+    ///
+    /// The types whose discriminator prefixes `data` come first, the longest
+    /// discriminators first, followed by the types with an empty
+    /// discriminator.
+    pub fn candidates<'a>(&'a self, data: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.lengths
+            .iter()
+            .filter_map(|length| data.get(..*length))
+            .filter_map(|discriminator| self.positions.get(discriminator))
+            .flatten()
+            .chain(self.fallback.iter())
+            .copied()
+    }
+}
+
+/// A `DiscriminatorIndex` built on first use, so that it can be declared as a
+/// `static`.
+///
+/// # Example
+///
+/// ```ignore
+/// static INDEX: LazyDiscriminatorIndex = LazyDiscriminatorIndex::new(&[
+///     <Deposit as CarbonDeserialize>::DISCRIMINATOR,
+///     <Withdraw as CarbonDeserialize>::DISCRIMINATOR,
+/// ]);
+///
+/// let candidates = INDEX.get().candidates(data);
+/// ```
+#[derive(Debug)]
+pub struct LazyDiscriminatorIndex {
+    discriminators: &'static [&'static [u8]],
+    index: OnceLock<DiscriminatorIndex>,
+}
+
+impl LazyDiscriminatorIndex {
+    pub const fn new(discriminators: &'static [&'static [u8]]) -> Self {
+        Self {
+            discriminators,
+            index: OnceLock::new(),
+        }
+    }
+
+    pub fn get(&self) -> &DiscriminatorIndex {
+        self.index
+            .get_or_init(|| DiscriminatorIndex::new(self.discriminators))
+    }
+}
+
 /// A trait for defining a custom arrangement of Solana account metadata.
 ///
 /// The `ArrangeAccounts` trait provides an interface for structuring account
@@ -189,3 +300,26 @@ impl crate::borsh::BorshDeserialize for U64PrefixString {
         })?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discriminator_index_candidates() {
+        let index = DiscriminatorIndex::new(&[&[1, 2, 3, 4, 5, 6, 7, 8], &[], &[9], &[9, 0], &[9]]);
+
+        assert_eq!(
+            index
+                .candidates(&[1, 2, 3, 4, 5, 6, 7, 8, 0])
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            index.candidates(&[9, 0, 1]).collect::<Vec<_>>(),
+            vec![3, 2, 4, 1]
+        );
+        assert_eq!(index.candidates(&[9]).collect::<Vec<_>>(), vec![2, 4, 1]);
+        assert_eq!(index.candidates(&[]).collect::<Vec<_>>(), vec![1]);
+    }
+}
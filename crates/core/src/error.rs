@@ -44,6 +44,8 @@ pub enum Error {
     MissingInstructionData,
     #[error("Failed to consume datasource ({0})")]
     FailedToConsumeDatasource(String),
+    #[error("Pipeline halted ({0})")]
+    PipelineHalted(String),
     #[error("Custom error: {0}")]
     Custom(String),
 }
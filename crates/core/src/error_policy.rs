@@ -0,0 +1,123 @@
+//! Defines the `ErrorPolicy` that decides what happens when a pipe fails to
+//! process an update.
+//!
+//! Every pipe carries its own policy, so a flaky database sink can be retried
+//! while a best-effort logger is simply skipped. A failing pipe never prevents
+//! the other pipes from receiving the same update.
+//!
+//! # Policies
+//!
+//! - **`Skip`**: Log the error and move on. This is the default.
+//! - **`Retry`**: Run the pipe again with exponential backoff, up to
+//!   `max_attempts` attempts in total, before giving up and moving on. An
+//!   instruction pipe only runs the failed instruction again, not the inner
+//!   or sibling instructions that already succeeded, and the pipe is not
+//!   locked while backing off.
+//! - **`Halt`**: Stop the pipeline. The failed update is not checkpointed, so a
+//!   pipeline resuming from a checkpoint processes it again.
+//!
+//! Updates that still fail after a `Skip` or `Retry` policy gave up are sent
+//! to the pipeline's dead-letter sink, if one is configured (see the
+//! `dead_letter` module).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::{error_policy::ErrorPolicy, pipeline::Pipeline};
+//!
+//! Pipeline::builder()
+//!     .datasource(transaction_crawler)
+//!     .error_policy(ErrorPolicy::retry(5))
+//!     .instruction(TestProgramDecoder, PostgresInstructionProcessor::new(pool))
+//!     .error_policy(ErrorPolicy::Skip)
+//!     .instruction(TestProgramDecoder, LogInstructionProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Metrics
+//!
+//! - `pipe_retries`: A counter of the attempts retried after a failure.
+
+use std::time::Duration;
+
+/// The default delay before the first retry of a `Retry` policy.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The default upper bound on the delay between retries of a `Retry` policy.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Decides how a pipe reacts when it fails to process an update.
+///
+/// - `Skip`: The failure is logged and the update is dead-lettered.
+/// - `Retry`: The pipe is run again up to `max_attempts` attempts in total,
+///   waiting `initial_backoff` before the first retry and doubling the delay
+///   after each further failure, up to `max_backoff`. The update is
+///   dead-lettered if the last attempt fails too.
+/// - `Halt`: The pipeline stops, leaving the update unprocessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    #[default]
+    Skip,
+    Retry {
+        max_attempts: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    },
+    Halt,
+}
+
+impl ErrorPolicy {
+    /// Creates a `Retry` policy making up to `max_attempts` attempts, with the
+    /// default backoff.
+    pub fn retry(max_attempts: u32) -> Self {
+        ErrorPolicy::Retry {
+            max_attempts,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Returns how long to wait before the next attempt, after `attempts`
+    /// attempts have failed, or `None` if the pipe should not be run again.
+    pub fn backoff(&self, attempts: u32) -> Option<Duration> {
+        match self {
+            ErrorPolicy::Retry {
+                max_attempts,
+                initial_backoff,
+                max_backoff,
+            } if attempts < *max_attempts => {
+                let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+                Some(initial_backoff.saturating_mul(factor).min(*max_backoff))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff_doubles_up_to_max() {
+        let policy = ErrorPolicy::Retry {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+        };
+
+        assert_eq!(policy.backoff(1), Some(Duration::from_millis(100)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_millis(200)));
+        assert_eq!(policy.backoff(3), Some(Duration::from_millis(300)));
+        assert_eq!(policy.backoff(4), Some(Duration::from_millis(300)));
+        assert_eq!(policy.backoff(5), None);
+    }
+
+    #[test]
+    fn test_skip_and_halt_never_retry() {
+        assert_eq!(ErrorPolicy::Skip.backoff(1), None);
+        assert_eq!(ErrorPolicy::Halt.backoff(1), None);
+    }
+}
@@ -0,0 +1,663 @@
+//! Provides event pipes, which decode the events emitted by programs and hand
+//! them to processors along with the instruction that emitted them.
+//!
+//! Programs emit events in two ways:
+//!
+//! - **Self-CPI events**: Anchor's `emit_cpi!` invokes the program itself
+//!   with an instruction whose data is `EVENT_IX_TAG` followed by the event.
+//!   The event is attributed to the instruction that made the invocation.
+//! - **Log events**: Anchor's `emit!` logs the event as a base64
+//!   `Program data:` line. The event is attributed to the instruction that
+//!   logged it.
+//!
+//! Events are handed to processors in the order they were emitted, which is
+//! recovered from the log tree of the transaction (see the `logs` module). The
+//! log tree is parsed once per transaction and shared by its instructions.
+//!
+//! When the processor fails on an event and the pipe's `ErrorPolicy` retries
+//! the instruction, the events processed before the failure are not handed to
+//! the processor again; the retry resumes at the event that failed.
+//!
+//! An `EventDecoder` decodes the event from either source, so processors
+//! don't need to match a `CpiEvent` instruction variant or call
+//! `InstructionMetadata::decode_log_events` themselves. Event pipes are
+//! registered through `PipelineBuilder::event`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::event::EventDecoder;
+//! use solana_pubkey::Pubkey;
+//!
+//! enum PumpfunEvent {
+//!     Trade(TradeEventEvent),
+//!     Create(CreateEventEvent),
+//! }
+//!
+//! struct PumpfunEventDecoder;
+//!
+//! impl EventDecoder for PumpfunEventDecoder {
+//!     type EventType = PumpfunEvent;
+//!
+//!     fn decode_event(&self, program_id: &Pubkey, data: &[u8]) -> Option<PumpfunEvent> {
+//!         if *program_id != PUMPFUN_PROGRAM_ID {
+//!             return None;
+//!         }
+//!
+//!         TradeEventEvent::decode(data)
+//!             .map(PumpfunEvent::Trade)
+//!             .or_else(|| CreateEventEvent::decode(data).map(PumpfunEvent::Create))
+//!     }
+//! }
+//!
+//! Pipeline::builder()
+//!     .datasource(transaction_crawler)
+//!     .event(PumpfunEventDecoder, PumpfunEventProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+
+use {
+    crate::{
+        error::CarbonResult,
+        error_policy::ErrorPolicy,
+        filter::Filter,
+        instruction::{InstructionMetadata, InstructionPipes, NestedInstruction},
+        logs::InstructionLogs,
+        metrics::MetricsCollection,
+        processor::Processor,
+        transaction::TransactionMetadata,
+    },
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// The tag that starts the data of Anchor self-CPI event instructions.
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// How an event was emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSource {
+    /// A self-CPI event instruction, as emitted by `emit_cpi!`.
+    Cpi,
+    /// A `Program data:` log, as emitted by `emit!`.
+    Log,
+}
+
+/// A decoded event, with the program that emitted it.
+///
+/// # Fields
+///
+/// - `program_id`: The program that emitted the event.
+/// - `data`: The decoded event, of type `T`.
+/// - `source`: Whether the event was emitted through a self-CPI or a log.
+#[derive(Debug, Clone)]
+pub struct DecodedEvent<T> {
+    pub program_id: Pubkey,
+    pub data: T,
+    pub source: EventSource,
+}
+
+/// A trait for decoding program events into a structured type.
+///
+/// # Required Methods
+///
+/// - `decode_event`: Decodes the data of an event emitted by `program_id`.
+///   The data starts with the discriminator of the event, whether it came
+///   from a self-CPI instruction, without its `EVENT_IX_TAG`, or from a log.
+///
+/// # Provided Methods
+///
+/// - `program_ids`: Returns the programs whose events the decoder decodes.
+///   Defaults to an empty list, meaning any program.
+pub trait EventDecoder {
+    type EventType;
+
+    fn decode_event(&self, program_id: &Pubkey, data: &[u8]) -> Option<Self::EventType>;
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
+}
+
+/// The input type for the event processor.
+///
+/// - `InstructionMetadata`: The metadata of the instruction that emitted the
+///   event.
+/// - `DecodedEvent<T>`: The decoded event.
+pub type EventProcessorInputType<T> = (InstructionMetadata, DecodedEvent<T>);
+
+/// The log tree of the transaction an `EventPipe` last ran for.
+struct CachedLogTree {
+    transaction_metadata: Arc<TransactionMetadata>,
+    log_tree: Arc<Vec<InstructionLogs>>,
+}
+
+/// The number of events of an instruction that were processed before the
+/// processor failed, so that a retry of the instruction skips them.
+struct DeliveredEvents {
+    transaction_metadata: Arc<TransactionMetadata>,
+    absolute_path: Vec<u8>,
+    count: usize,
+}
+
+impl DeliveredEvents {
+    fn matches(&self, metadata: &InstructionMetadata) -> bool {
+        Arc::ptr_eq(&self.transaction_metadata, &metadata.transaction_metadata)
+            && self.absolute_path == metadata.absolute_path
+    }
+}
+
+/// A pipe that decodes the events emitted by instructions and processes them.
+///
+/// # Fields
+///
+/// - `decoder`: An `EventDecoder` that decodes the events.
+/// - `processor`: A `Processor` that handles the decoded events.
+/// - `filters`: A collection of filters that determine which instruction
+///   updates are searched for events. If this collection is empty, all
+///   updates are searched.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+///
+/// # Notes
+///
+/// - Only the last failed instruction is remembered. If a pipe instance
+///   shared by several workers fails on another instruction before a retry,
+///   the events processed before the first failure are handed to the
+///   processor again.
+pub struct EventPipe<T: Send> {
+    pub decoder: Arc<dyn EventDecoder<EventType = T> + Send + Sync + 'static>,
+    pub processor:
+        Box<dyn Processor<InputType = EventProcessorInputType<T>> + Send + Sync + 'static>,
+    pub filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    pub error_policy: ErrorPolicy,
+    log_tree: Option<CachedLogTree>,
+    delivered: Option<DeliveredEvents>,
+}
+
+impl<T: Send> EventPipe<T> {
+    pub fn new(
+        decoder: Arc<dyn EventDecoder<EventType = T> + Send + Sync + 'static>,
+        processor: Box<
+            dyn Processor<InputType = EventProcessorInputType<T>> + Send + Sync + 'static,
+        >,
+        filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+        error_policy: ErrorPolicy,
+    ) -> Self {
+        Self {
+            decoder,
+            processor,
+            filters,
+            error_policy,
+            log_tree: None,
+            delivered: None,
+        }
+    }
+
+    /// Returns the log tree of the transaction of `metadata`, parsing it only
+    /// if the pipe last ran for another transaction.
+    fn log_tree(&mut self, metadata: &InstructionMetadata) -> Arc<Vec<InstructionLogs>> {
+        match &self.log_tree {
+            Some(cached)
+                if Arc::ptr_eq(&cached.transaction_metadata, &metadata.transaction_metadata) =>
+            {
+                cached.log_tree.clone()
+            }
+            _ => {
+                let log_tree = Arc::new(metadata.transaction_metadata.log_tree());
+                self.log_tree = Some(CachedLogTree {
+                    transaction_metadata: metadata.transaction_metadata.clone(),
+                    log_tree: log_tree.clone(),
+                });
+                log_tree
+            }
+        }
+    }
+
+    /// Decodes the events emitted by an instruction, in the order they were
+    /// emitted, given the log tree of its transaction.
+    ///
+    /// The position of self-CPI events among log events comes from the log
+    /// offsets of the inner instructions. Self-CPI events of instructions
+    /// missing from the logs, such as when the logs were truncated, come
+    /// after the log events.
+    fn decode_events(
+        &self,
+        nested_instruction: &NestedInstruction,
+        log_tree: &[InstructionLogs],
+    ) -> Vec<DecodedEvent<T>> {
+        let program_id = nested_instruction.instruction.program_id;
+        let (logs, inner_instruction_log_offsets) = nested_instruction
+            .metadata
+            .logs_in(log_tree)
+            .map(|logs| {
+                (
+                    logs.logs.as_slice(),
+                    logs.inner_instruction_log_offsets.as_slice(),
+                )
+            })
+            .unwrap_or_default();
+
+        let decode = |data: &[u8], source| {
+            self.decoder
+                .decode_event(&program_id, data)
+                .map(|data| DecodedEvent {
+                    program_id,
+                    data,
+                    source,
+                })
+        };
+        let decode_log = |log: &String| {
+            let data = log.strip_prefix("Program data: ")?;
+            let data =
+                base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data).ok()?;
+            decode(&data, EventSource::Log)
+        };
+
+        let mut events = Vec::new();
+        let mut logs = logs.iter().enumerate().peekable();
+
+        for (position, inner_instruction) in
+            nested_instruction.inner_instructions.iter().enumerate()
+        {
+            let offset = inner_instruction_log_offsets
+                .get(position)
+                .copied()
+                .unwrap_or(usize::MAX);
+            while let Some((_, log)) = logs.next_if(|(index, _)| *index < offset) {
+                events.extend(decode_log(log));
+            }
+
+            if inner_instruction.instruction.program_id == program_id {
+                if let Some(data) = inner_instruction
+                    .instruction
+                    .data
+                    .strip_prefix(&EVENT_IX_TAG)
+                {
+                    events.extend(decode(data, EventSource::Cpi));
+                }
+            }
+        }
+        events.extend(logs.filter_map(|(_, log)| decode_log(log)));
+
+        events
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> InstructionPipes<'_> for EventPipe<T> {
+    async fn run(
+        &mut self,
+        nested_instruction: &NestedInstruction,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        log::trace!("EventPipe::run(nested_instruction: {nested_instruction:?}, metrics)");
+
+        let metadata = &nested_instruction.metadata;
+        let log_tree = self.log_tree(metadata);
+        let events = self.decode_events(nested_instruction, &log_tree);
+
+        let retried = self
+            .delivered
+            .as_ref()
+            .is_some_and(|delivered| delivered.matches(metadata));
+        let skipped = match &self.delivered {
+            Some(delivered) if retried => delivered.count,
+            _ => 0,
+        };
+
+        for (position, event) in events.into_iter().enumerate().skip(skipped) {
+            if let Err(error) = self
+                .processor
+                .process((metadata.clone(), event), metrics.clone())
+                .await
+            {
+                self.delivered = Some(DeliveredEvents {
+                    transaction_metadata: metadata.transaction_metadata.clone(),
+                    absolute_path: metadata.absolute_path.clone(),
+                    count: position,
+                });
+                return Err(error);
+            }
+        }
+
+        if retried {
+            self.delivered = None;
+        }
+
+        Ok(())
+    }
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
+        &self.filters
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        self.decoder.program_ids()
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn for<'b> InstructionPipes<'b>>> {
+        Some(Box::new(EventPipe::new(
+            self.decoder.clone(),
+            self.processor.fork()?,
+            self.filters.clone(),
+            self.error_policy,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            datasource::{Datasource, DatasourceId, TransactionUpdate, Update, UpdateType},
+            instruction::NestedInstructions,
+            pipeline::Pipeline,
+            transaction::TransactionMetadata,
+        },
+        solana_instruction::Instruction,
+        solana_message::{
+            compiled_instruction::CompiledInstruction, legacy::Message, VersionedMessage,
+        },
+        solana_signature::Signature,
+        solana_transaction::versioned::VersionedTransaction,
+        solana_transaction_status::{InnerInstruction, InnerInstructions, TransactionStatusMeta},
+        std::sync::Mutex,
+        tokio::sync::mpsc,
+        tokio_util::sync::CancellationToken,
+    };
+
+    struct TestDecoder;
+
+    impl EventDecoder for TestDecoder {
+        type EventType = u8;
+
+        fn decode_event(&self, _program_id: &Pubkey, data: &[u8]) -> Option<u8> {
+            data.first().copied()
+        }
+    }
+
+    type Events = Arc<Mutex<Vec<(u8, EventSource)>>>;
+
+    /// Records the data and source of the events it processes.
+    struct RecordingProcessor(Events);
+
+    #[async_trait]
+    impl Processor for RecordingProcessor {
+        type InputType = EventProcessorInputType<u8>;
+
+        async fn process(
+            &mut self,
+            (_metadata, event): Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            self.0.lock().unwrap().push((event.data, event.source));
+            Ok(())
+        }
+    }
+
+    /// Records the data of the events it processes, failing once on the event
+    /// `fail_on`.
+    struct FailingProcessor {
+        fail_on: Option<u8>,
+        events: Events,
+    }
+
+    #[async_trait]
+    impl Processor for FailingProcessor {
+        type InputType = EventProcessorInputType<u8>;
+
+        async fn process(
+            &mut self,
+            (_metadata, event): Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            if self.fail_on == Some(event.data) {
+                self.fail_on = None;
+                return Err(crate::error::Error::Custom("processor failed".to_string()));
+            }
+            self.events.lock().unwrap().push((event.data, event.source));
+            Ok(())
+        }
+    }
+
+    /// Sends a single transaction update.
+    struct TransactionDatasource(TransactionUpdate);
+
+    #[async_trait]
+    impl Datasource for TransactionDatasource {
+        async fn consume(
+            &self,
+            id: DatasourceId,
+            sender: mpsc::Sender<(Update, DatasourceId)>,
+            _cancellation_token: CancellationToken,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            let _ = sender
+                .send((Update::Transaction(Box::new(self.0.clone())), id))
+                .await;
+            Ok(())
+        }
+
+        fn update_types(&self) -> Vec<UpdateType> {
+            vec![UpdateType::Transaction]
+        }
+    }
+
+    /// A transaction whose only instruction logs event 1, emits event 9
+    /// through a self-CPI, then logs event 2.
+    fn transaction(program_id: Pubkey) -> (VersionedMessage, TransactionStatusMeta) {
+        let message = VersionedMessage::Legacy(Message {
+            account_keys: vec![Pubkey::new_unique(), program_id],
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![],
+                data: vec![],
+            }],
+            ..Default::default()
+        });
+        let meta = TransactionStatusMeta {
+            log_messages: Some(vec![
+                format!("Program {program_id} invoke [1]"),
+                "Program data: AQ==".to_string(),
+                format!("Program {program_id} invoke [2]"),
+                format!("Program {program_id} success"),
+                "Program data: Ag==".to_string(),
+                format!("Program {program_id} success"),
+            ]),
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![InnerInstruction {
+                    instruction: CompiledInstruction {
+                        program_id_index: 1,
+                        accounts: vec![],
+                        data: [&EVENT_IX_TAG[..], &[9]].concat(),
+                    },
+                    stack_height: Some(2),
+                }],
+            }]),
+            ..Default::default()
+        };
+
+        (message, meta)
+    }
+
+    fn nested(
+        transaction_metadata: &Arc<TransactionMetadata>,
+        absolute_path: Vec<u8>,
+        program_id: Pubkey,
+        data: Vec<u8>,
+        inner: Vec<NestedInstruction>,
+    ) -> NestedInstruction {
+        NestedInstruction {
+            metadata: InstructionMetadata {
+                transaction_metadata: transaction_metadata.clone(),
+                stack_height: absolute_path.len() as u32,
+                index: 0,
+                absolute_path,
+            },
+            instruction: Instruction {
+                program_id,
+                accounts: vec![],
+                data,
+            },
+            inner_instructions: NestedInstructions(Arc::new(inner)),
+        }
+    }
+
+    #[test]
+    fn test_decodes_events_in_emission_order() {
+        let program_id = Pubkey::new_unique();
+        let (message, meta) = transaction(program_id);
+        let transaction_metadata = Arc::new(TransactionMetadata {
+            meta,
+            message,
+            ..Default::default()
+        });
+        let event_instruction = nested(
+            &transaction_metadata,
+            vec![0, 0],
+            program_id,
+            [&EVENT_IX_TAG[..], &[9]].concat(),
+            vec![],
+        );
+        let instruction = nested(
+            &transaction_metadata,
+            vec![0],
+            program_id,
+            vec![],
+            vec![event_instruction],
+        );
+        let pipe = EventPipe::new(
+            Arc::new(TestDecoder),
+            Box::new(RecordingProcessor(Events::default())),
+            Arc::default(),
+            ErrorPolicy::default(),
+        );
+
+        let events = pipe.decode_events(&instruction, &transaction_metadata.log_tree());
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.data, event.source))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, EventSource::Log),
+                (9, EventSource::Cpi),
+                (2, EventSource::Log)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_resumes_at_failed_event() {
+        let program_id = Pubkey::new_unique();
+        let (message, meta) = transaction(program_id);
+        let transaction_metadata = Arc::new(TransactionMetadata {
+            meta,
+            message,
+            ..Default::default()
+        });
+        let event_instruction = nested(
+            &transaction_metadata,
+            vec![0, 0],
+            program_id,
+            [&EVENT_IX_TAG[..], &[9]].concat(),
+            vec![],
+        );
+        let instruction = nested(
+            &transaction_metadata,
+            vec![0],
+            program_id,
+            vec![],
+            vec![event_instruction],
+        );
+        let events = Events::default();
+        let mut pipe = EventPipe::new(
+            Arc::new(TestDecoder),
+            Box::new(FailingProcessor {
+                fail_on: Some(9),
+                events: events.clone(),
+            }),
+            Arc::default(),
+            ErrorPolicy::retry(2),
+        );
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+
+        assert!(pipe.run(&instruction, metrics.clone()).await.is_err());
+        pipe.run(&instruction, metrics.clone()).await.unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (1, EventSource::Log),
+                (9, EventSource::Cpi),
+                (2, EventSource::Log)
+            ]
+        );
+
+        // The log tree was parsed once for both runs.
+        let log_tree = pipe.log_tree(&instruction.metadata);
+        assert!(Arc::ptr_eq(
+            &log_tree,
+            &pipe.log_tree(&instruction.inner_instructions[0].metadata)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_processes_events() {
+        let program_id = Pubkey::new_unique();
+        let (message, meta) = transaction(program_id);
+        let events = Events::default();
+
+        Pipeline::builder()
+            .datasource(TransactionDatasource(TransactionUpdate {
+                signature: Signature::default(),
+                transaction: VersionedTransaction {
+                    signatures: vec![Signature::default()],
+                    message,
+                },
+                meta,
+                is_vote: false,
+                slot: 1,
+                index: Some(0),
+                block_time: None,
+                block_hash: None,
+            }))
+            .event(TestDecoder, RecordingProcessor(events.clone()))
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (1, EventSource::Log),
+                (9, EventSource::Cpi),
+                (2, EventSource::Log)
+            ]
+        );
+    }
+}
@@ -11,6 +11,13 @@
 //! The filtering system consists of:
 //! - A `Filter` trait that defines the interface for all filters
 //! - Concrete implementations like `DatasourceFilter` for common use cases
+//! - Built-in filters on the content of updates: `ProgramFilter`,
+//!   `SignerFilter`, `FeePayerFilter`, `AccountKeyFilter`, `SlotRangeFilter`,
+//!   `TransactionStatusFilter`, `VoteFilter`, `AccountOwnerFilter`,
+//!   `AccountDataSizeFilter` and `MemcmpFilter`
+//! - The `AndFilter`, `OrFilter` and `NotFilter` combinators, which compose
+//!   filters, leaving out the filters that don't inspect the kind of update at
+//!   hand (see `Filter::inspects`)
 //! - Integration with the pipeline to apply filters before processing
 //!
 //! # Examples
@@ -34,6 +41,21 @@
 //! let filter = DatasourceFilter::new_many(ids);
 //! ```
 //!
+//! Combining filters:
+//! ```
+//! use carbon_core::filter::{NotFilter, OrFilter, ProgramFilter, VoteFilter};
+//! use solana_pubkey::Pubkey;
+//!
+//! // Non-vote transactions that don't invoke either program.
+//! let filters = vec![
+//!     Box::new(VoteFilter::NonVote) as Box<dyn carbon_core::filter::Filter + Send + Sync>,
+//!     Box::new(NotFilter::new(Box::new(OrFilter::new(vec![
+//!         Box::new(ProgramFilter::new(Pubkey::new_unique())),
+//!         Box::new(ProgramFilter::new(Pubkey::new_unique())),
+//!     ])))),
+//! ];
+//! ```
+//!
 //! Custom filter implementation:
 //! ```
 //! use carbon_core::{
@@ -64,10 +86,35 @@
 
 use crate::{
     account::AccountMetadata,
-    datasource::{AccountDeletion, BlockDetails, DatasourceId},
+    datasource::{AccountDeletion, BlockDetails, DatasourceId, SlotStatusUpdate},
     instruction::{NestedInstruction, NestedInstructions},
     transaction::TransactionMetadata,
 };
+use solana_pubkey::Pubkey;
+use std::{
+    iter::Peekable,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// A kind of update a filter can inspect, one per method of `Filter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterTarget {
+    /// Account updates, see `Filter::filter_account`.
+    Account,
+    /// Instructions, see `Filter::filter_instruction`.
+    Instruction,
+    /// Transactions, see `Filter::filter_transaction`.
+    Transaction,
+    /// Account deletions, see `Filter::filter_account_deletion`.
+    AccountDeletion,
+    /// Block details, see `Filter::filter_block_details`.
+    BlockDetails,
+    /// Slot status updates, see `Filter::filter_slot_status`.
+    SlotStatus,
+    /// Whole blocks, see `Filter::filter_block`.
+    Block,
+}
 
 /// A trait for filtering updates in the carbon-core pipeline.
 ///
@@ -82,6 +129,9 @@ use crate::{
 /// - `filter_transaction`: Filters transaction updates
 /// - `filter_account_deletion`: Filters account deletion updates
 /// - `filter_block_details`: Filters block details updates
+/// - `filter_slot_status`: Filters slot status updates
+/// - `filter_block`: Filters whole blocks delivered to block pipes
+/// - `inspects`: Tells which kinds of updates the filter looks at
 ///
 /// # Implementation Notes
 ///
@@ -90,6 +140,9 @@ use crate::{
 /// - Return `false` for update types you want to skip
 /// - Consider the datasource ID when making filtering decisions
 /// - Keep filtering logic efficient as it runs for every update
+/// - Override `inspects` if the filter lets some kinds of updates through
+///   without looking at them, so `OrFilter` and `NotFilter` leave it out for
+///   these kinds
 ///
 /// # Examples
 ///
@@ -229,6 +282,85 @@ pub trait Filter {
     ) -> bool {
         true
     }
+
+    /// Filters slot status updates based on datasource ID and slot status.
+    ///
+    /// This method is called for each slot status update before processing.
+    /// Return `true` to process the slot status update, or `false` to skip it.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource_id` - The ID of the datasource that produced this update
+    /// * `slot_status` - The slot status update to be processed
+    ///
+    /// # Returns
+    ///
+    /// `true` if the slot status update should be processed, `false` otherwise.
+    fn filter_slot_status(
+        &self,
+        _datasource_id: &DatasourceId,
+        _slot_status: &SlotStatusUpdate,
+    ) -> bool {
+        true
+    }
+
+    /// Filters whole blocks based on datasource ID, block details and the
+    /// transactions of the block.
+    ///
+    /// This method is called for each completed block before it is handed to
+    /// the block pipes. Return `true` to process the block, or `false` to skip
+    /// it.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource_id` - The ID of the datasource that produced this block
+    /// * `block_details` - The details of the block
+    /// * `transactions` - The transactions of the block, in block order
+    ///
+    /// # Returns
+    ///
+    /// `true` if the block should be processed, `false` otherwise.
+    fn filter_block(
+        &self,
+        _datasource_id: &DatasourceId,
+        _block_details: &BlockDetails,
+        _transactions: &[Arc<TransactionMetadata>],
+    ) -> bool {
+        true
+    }
+
+    /// Returns whether the filter looks at a kind of update.
+    ///
+    /// Combinators leave a filter out for the kinds of updates it doesn't
+    /// inspect: an `OrFilter` lets an update through if any of the filters
+    /// inspecting it does, and a `NotFilter` lets through the updates its
+    /// filter doesn't inspect. Defaults to `true` for every kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The kind of update
+    ///
+    /// # Returns
+    ///
+    /// `true` if the result of the matching `filter_*` method depends on the
+    /// update, `false` if it always lets the update through.
+    fn inspects(&self, _target: FilterTarget) -> bool {
+        true
+    }
+
+    /// Returns whether the filter lets through only vote or only non-vote
+    /// transactions and instructions.
+    ///
+    /// `PipelineBuilder::programs` uses it to tell datasources subscribing by
+    /// program whether a pipe needs vote transactions. Defaults to `None`.
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if only vote transactions pass, `Some(false)` if only
+    /// non-vote transactions pass, and `None` if both can pass.
+    fn votes(&self) -> Option<bool> {
+        None
+    }
 }
 
 /// A filter that allows updates from specific datasources.
@@ -444,4 +576,1140 @@ impl Filter for DatasourceFilter {
     ) -> bool {
         self.allowed_datasources.contains(datasource_id)
     }
+
+    /// Filters slot status updates based on the datasource ID.
+    ///
+    /// Returns `true` if the slot status update comes from an allowed datasource,
+    /// `false` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource_id` - The ID of the datasource that produced this update
+    /// * `_slot_status` - Slot status data (unused in this implementation)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the datasource ID is in the allowed list, `false` otherwise.
+    fn filter_slot_status(
+        &self,
+        datasource_id: &DatasourceId,
+        _slot_status: &SlotStatusUpdate,
+    ) -> bool {
+        self.allowed_datasources.contains(datasource_id)
+    }
+
+    /// Filters blocks based on the datasource ID.
+    ///
+    /// Returns `true` if the block comes from an allowed datasource, `false`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource_id` - The ID of the datasource that produced this block
+    /// * `_block_details` - Block details data (unused in this implementation)
+    /// * `_transactions` - Block transactions (unused in this implementation)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the datasource ID is in the allowed list, `false` otherwise.
+    fn filter_block(
+        &self,
+        datasource_id: &DatasourceId,
+        _block_details: &BlockDetails,
+        _transactions: &[Arc<TransactionMetadata>],
+    ) -> bool {
+        self.allowed_datasources.contains(datasource_id)
+    }
+}
+
+/// Returns the filters that inspect a kind of update.
+fn inspecting(
+    filters: &[Box<dyn Filter + Send + Sync + 'static>],
+    target: FilterTarget,
+) -> Peekable<impl Iterator<Item = &(dyn Filter + Send + Sync + 'static)>> {
+    filters
+        .iter()
+        .map(|filter| filter.as_ref())
+        .filter(move |filter| filter.inspects(target))
+        .peekable()
+}
+
+/// Implements every `Filter` method of a combinator by applying `$combine`,
+/// such as `all` or `any`, to the results of the filters inspecting the kind
+/// of update. Updates no filter inspects pass.
+macro_rules! combine_filters {
+    ($combine:ident) => {
+        fn inspects(&self, target: FilterTarget) -> bool {
+            self.filters.iter().any(|filter| filter.inspects(target))
+        }
+
+        fn filter_account(
+            &self,
+            datasource_id: &DatasourceId,
+            account_metadata: &AccountMetadata,
+            account: &solana_account::Account,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::Account);
+            filters.peek().is_none()
+                || filters.$combine(|filter| {
+                    filter.filter_account(datasource_id, account_metadata, account)
+                })
+        }
+
+        fn filter_instruction(
+            &self,
+            datasource_id: &DatasourceId,
+            nested_instruction: &NestedInstruction,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::Instruction);
+            filters.peek().is_none()
+                || filters
+                    .$combine(|filter| filter.filter_instruction(datasource_id, nested_instruction))
+        }
+
+        fn filter_transaction(
+            &self,
+            datasource_id: &DatasourceId,
+            transaction_metadata: &TransactionMetadata,
+            nested_instructions: &NestedInstructions,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::Transaction);
+            filters.peek().is_none()
+                || filters.$combine(|filter| {
+                    filter.filter_transaction(
+                        datasource_id,
+                        transaction_metadata,
+                        nested_instructions,
+                    )
+                })
+        }
+
+        fn filter_account_deletion(
+            &self,
+            datasource_id: &DatasourceId,
+            account_deletion: &AccountDeletion,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::AccountDeletion);
+            filters.peek().is_none()
+                || filters.$combine(|filter| {
+                    filter.filter_account_deletion(datasource_id, account_deletion)
+                })
+        }
+
+        fn filter_block_details(
+            &self,
+            datasource_id: &DatasourceId,
+            block_details: &BlockDetails,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::BlockDetails);
+            filters.peek().is_none()
+                || filters
+                    .$combine(|filter| filter.filter_block_details(datasource_id, block_details))
+        }
+
+        fn filter_slot_status(
+            &self,
+            datasource_id: &DatasourceId,
+            slot_status: &SlotStatusUpdate,
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::SlotStatus);
+            filters.peek().is_none()
+                || filters.$combine(|filter| filter.filter_slot_status(datasource_id, slot_status))
+        }
+
+        fn filter_block(
+            &self,
+            datasource_id: &DatasourceId,
+            block_details: &BlockDetails,
+            transactions: &[Arc<TransactionMetadata>],
+        ) -> bool {
+            let mut filters = inspecting(&self.filters, FilterTarget::Block);
+            filters.peek().is_none()
+                || filters.$combine(|filter| {
+                    filter.filter_block(datasource_id, block_details, transactions)
+                })
+        }
+    };
+}
+
+/// A filter that lets an update through only if all of its filters do.
+///
+/// Pipes already require all of their filters to pass, so `AndFilter` is
+/// mostly useful inside an `OrFilter` or a `NotFilter`.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::{AndFilter, TransactionStatusFilter, VoteFilter};
+///
+/// let filter = AndFilter::new(vec![
+///     Box::new(TransactionStatusFilter::Succeeded),
+///     Box::new(VoteFilter::NonVote),
+/// ]);
+/// ```
+pub struct AndFilter {
+    /// The filters that must all let an update through.
+    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+}
+
+impl AndFilter {
+    /// Creates a filter that lets an update through if all `filters` do.
+    pub fn new(filters: Vec<Box<dyn Filter + Send + Sync + 'static>>) -> Self {
+        Self { filters }
+    }
+}
+
+impl Filter for AndFilter {
+    combine_filters!(all);
+
+    fn votes(&self) -> Option<bool> {
+        self.filters.iter().find_map(|filter| filter.votes())
+    }
+}
+
+/// A filter that lets an update through if any of the filters inspecting it
+/// does.
+///
+/// Filters are left out for the kinds of updates they don't inspect, so that,
+/// for instance, an `OrFilter` of a `ProgramFilter` and an
+/// `AccountOwnerFilter` applies the `ProgramFilter` alone to transactions.
+/// Updates none of its filters inspect pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::{FeePayerFilter, OrFilter, ProgramFilter};
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = OrFilter::new(vec![
+///     Box::new(ProgramFilter::new(Pubkey::new_unique())),
+///     Box::new(FeePayerFilter::new(Pubkey::new_unique())),
+/// ]);
+/// ```
+pub struct OrFilter {
+    /// The filters of which at least one must let an update through.
+    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+}
+
+impl OrFilter {
+    /// Creates a filter that lets an update through if any of `filters`
+    /// does.
+    pub fn new(filters: Vec<Box<dyn Filter + Send + Sync + 'static>>) -> Self {
+        Self { filters }
+    }
+}
+
+impl Filter for OrFilter {
+    combine_filters!(any);
+
+    fn votes(&self) -> Option<bool> {
+        let mut votes =
+            inspecting(&self.filters, FilterTarget::Transaction).map(|filter| filter.votes());
+        let first = votes.next().flatten()?;
+        votes.all(|votes| votes == Some(first)).then_some(first)
+    }
+}
+
+/// A filter that lets an update through only if its inner filter does not.
+///
+/// # Notes
+///
+/// Updates the inner filter doesn't inspect pass, so that, for instance,
+/// negating an `AccountOwnerFilter` doesn't block transactions.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::{NotFilter, SignerFilter};
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = NotFilter::new(Box::new(SignerFilter::new(Pubkey::new_unique())));
+/// ```
+pub struct NotFilter {
+    /// The filter to negate.
+    pub filter: Box<dyn Filter + Send + Sync + 'static>,
+}
+
+impl NotFilter {
+    /// Creates a filter that lets an update through if `filter` does not.
+    pub fn new(filter: Box<dyn Filter + Send + Sync + 'static>) -> Self {
+        Self { filter }
+    }
+}
+
+impl Filter for NotFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        self.filter.inspects(target)
+    }
+
+    fn votes(&self) -> Option<bool> {
+        self.filter.votes().map(|votes| !votes)
+    }
+
+    fn filter_account(
+        &self,
+        datasource_id: &DatasourceId,
+        account_metadata: &AccountMetadata,
+        account: &solana_account::Account,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::Account)
+            || !self
+                .filter
+                .filter_account(datasource_id, account_metadata, account)
+    }
+
+    fn filter_instruction(
+        &self,
+        datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::Instruction)
+            || !self
+                .filter
+                .filter_instruction(datasource_id, nested_instruction)
+    }
+
+    fn filter_transaction(
+        &self,
+        datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        nested_instructions: &NestedInstructions,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::Transaction)
+            || !self.filter.filter_transaction(
+                datasource_id,
+                transaction_metadata,
+                nested_instructions,
+            )
+    }
+
+    fn filter_account_deletion(
+        &self,
+        datasource_id: &DatasourceId,
+        account_deletion: &AccountDeletion,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::AccountDeletion)
+            || !self
+                .filter
+                .filter_account_deletion(datasource_id, account_deletion)
+    }
+
+    fn filter_block_details(
+        &self,
+        datasource_id: &DatasourceId,
+        block_details: &BlockDetails,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::BlockDetails)
+            || !self
+                .filter
+                .filter_block_details(datasource_id, block_details)
+    }
+
+    fn filter_slot_status(
+        &self,
+        datasource_id: &DatasourceId,
+        slot_status: &SlotStatusUpdate,
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::SlotStatus)
+            || !self.filter.filter_slot_status(datasource_id, slot_status)
+    }
+
+    fn filter_block(
+        &self,
+        datasource_id: &DatasourceId,
+        block_details: &BlockDetails,
+        transactions: &[Arc<TransactionMetadata>],
+    ) -> bool {
+        !self.filter.inspects(FilterTarget::Block)
+            || !self
+                .filter
+                .filter_block(datasource_id, block_details, transactions)
+    }
+}
+
+/// A filter that lets through instructions and transactions of specific
+/// programs.
+///
+/// An instruction passes if it, or by default any of its inner instructions,
+/// was executed by one of the programs. A transaction passes if any of its
+/// instructions does. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::ProgramFilter;
+/// use solana_pubkey::Pubkey;
+///
+/// let program_id = Pubkey::new_unique();
+///
+/// // Transactions invoking the program, directly or through a CPI.
+/// let filter = ProgramFilter::new(program_id);
+///
+/// // Transactions invoking the program directly.
+/// let filter = ProgramFilter::new(program_id).include_inner_instructions(false);
+/// ```
+pub struct ProgramFilter {
+    /// The programs whose instructions are let through.
+    pub program_ids: Vec<Pubkey>,
+    /// Whether to look at inner instructions. Defaults to `true`.
+    pub include_inner_instructions: bool,
+}
+
+impl ProgramFilter {
+    /// Creates a filter for the instructions of a single program.
+    pub fn new(program_id: Pubkey) -> Self {
+        Self::new_many(vec![program_id])
+    }
+
+    /// Creates a filter for the instructions of several programs.
+    pub fn new_many(program_ids: Vec<Pubkey>) -> Self {
+        Self {
+            program_ids,
+            include_inner_instructions: true,
+        }
+    }
+
+    /// Sets whether inner instructions are looked at.
+    pub fn include_inner_instructions(mut self, include_inner_instructions: bool) -> Self {
+        self.include_inner_instructions = include_inner_instructions;
+        self
+    }
+
+    fn matches(&self, nested_instruction: &NestedInstruction) -> bool {
+        self.program_ids
+            .contains(&nested_instruction.instruction.program_id)
+            || (self.include_inner_instructions
+                && nested_instruction
+                    .inner_instructions
+                    .iter()
+                    .any(|inner_instruction| self.matches(inner_instruction)))
+    }
+}
+
+impl Filter for ProgramFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Instruction | FilterTarget::Transaction
+        )
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.matches(nested_instruction)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        _transaction_metadata: &TransactionMetadata,
+        nested_instructions: &NestedInstructions,
+    ) -> bool {
+        nested_instructions
+            .iter()
+            .any(|nested_instruction| self.matches(nested_instruction))
+    }
+}
+
+/// Returns whether `account` signed a transaction.
+fn is_signer(transaction_metadata: &TransactionMetadata, account: &Pubkey) -> bool {
+    let num_signers = transaction_metadata
+        .message
+        .header()
+        .num_required_signatures as usize;
+
+    transaction_metadata
+        .message
+        .static_account_keys()
+        .iter()
+        .take(num_signers)
+        .any(|signer| signer == account)
+}
+
+/// A filter that lets through instructions and transactions signed by
+/// specific accounts. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::SignerFilter;
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = SignerFilter::new(Pubkey::new_unique());
+/// ```
+pub struct SignerFilter {
+    /// The accounts of which at least one must have signed the transaction.
+    pub signers: Vec<Pubkey>,
+}
+
+impl SignerFilter {
+    /// Creates a filter for transactions signed by a single account.
+    pub fn new(signer: Pubkey) -> Self {
+        Self::new_many(vec![signer])
+    }
+
+    /// Creates a filter for transactions signed by any of several accounts.
+    pub fn new_many(signers: Vec<Pubkey>) -> Self {
+        Self { signers }
+    }
+
+    fn matches(&self, transaction_metadata: &TransactionMetadata) -> bool {
+        self.signers
+            .iter()
+            .any(|signer| is_signer(transaction_metadata, signer))
+    }
+}
+
+impl Filter for SignerFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Instruction | FilterTarget::Transaction
+        )
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.matches(&nested_instruction.metadata.transaction_metadata)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        self.matches(transaction_metadata)
+    }
+}
+
+/// A filter that lets through instructions and transactions paid for by
+/// specific accounts. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::FeePayerFilter;
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = FeePayerFilter::new(Pubkey::new_unique());
+/// ```
+pub struct FeePayerFilter {
+    /// The accounts of which one must have paid for the transaction.
+    pub fee_payers: Vec<Pubkey>,
+}
+
+impl FeePayerFilter {
+    /// Creates a filter for transactions paid for by a single account.
+    pub fn new(fee_payer: Pubkey) -> Self {
+        Self::new_many(vec![fee_payer])
+    }
+
+    /// Creates a filter for transactions paid for by any of several accounts.
+    pub fn new_many(fee_payers: Vec<Pubkey>) -> Self {
+        Self { fee_payers }
+    }
+}
+
+impl Filter for FeePayerFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Instruction | FilterTarget::Transaction
+        )
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.fee_payers
+            .contains(&nested_instruction.metadata.transaction_metadata.fee_payer)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        self.fee_payers.contains(&transaction_metadata.fee_payer)
+    }
+}
+
+/// A filter that lets through updates touching specific accounts.
+///
+/// - Account updates and deletions pass if they are for one of the accounts.
+/// - Instructions pass if one of the accounts is among their accounts or the
+///   accounts of their inner instructions.
+/// - Transactions pass if one of the accounts is among their account keys,
+///   including the ones loaded from address lookup tables.
+///
+/// Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::AccountKeyFilter;
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = AccountKeyFilter::new(Pubkey::new_unique());
+/// ```
+pub struct AccountKeyFilter {
+    /// The accounts of which at least one must be touched.
+    pub accounts: Vec<Pubkey>,
+}
+
+impl AccountKeyFilter {
+    /// Creates a filter for updates touching a single account.
+    pub fn new(account: Pubkey) -> Self {
+        Self::new_many(vec![account])
+    }
+
+    /// Creates a filter for updates touching any of several accounts.
+    pub fn new_many(accounts: Vec<Pubkey>) -> Self {
+        Self { accounts }
+    }
+
+    fn matches(&self, nested_instruction: &NestedInstruction) -> bool {
+        nested_instruction
+            .instruction
+            .accounts
+            .iter()
+            .any(|account_meta| self.accounts.contains(&account_meta.pubkey))
+            || nested_instruction
+                .inner_instructions
+                .iter()
+                .any(|inner_instruction| self.matches(inner_instruction))
+    }
+}
+
+impl Filter for AccountKeyFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Account
+                | FilterTarget::Instruction
+                | FilterTarget::Transaction
+                | FilterTarget::AccountDeletion
+        )
+    }
+
+    fn filter_account(
+        &self,
+        _datasource_id: &DatasourceId,
+        account_metadata: &AccountMetadata,
+        _account: &solana_account::Account,
+    ) -> bool {
+        self.accounts.contains(&account_metadata.pubkey)
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.matches(nested_instruction)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        transaction_metadata
+            .account_keys()
+            .iter()
+            .any(|account| self.accounts.contains(account))
+    }
+
+    fn filter_account_deletion(
+        &self,
+        _datasource_id: &DatasourceId,
+        account_deletion: &AccountDeletion,
+    ) -> bool {
+        self.accounts.contains(&account_deletion.pubkey)
+    }
+}
+
+/// A filter that lets through updates within a range of slots.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::SlotRangeFilter;
+///
+/// // Slots 300_000_000 and later.
+/// let filter = SlotRangeFilter::new(300_000_000..);
+///
+/// // Slots 300_000_000 to 300_001_000, included.
+/// let filter = SlotRangeFilter::new(300_000_000..=300_001_000);
+/// ```
+pub struct SlotRangeFilter {
+    /// The first slot of the range.
+    pub start: Bound<u64>,
+    /// The last slot of the range.
+    pub end: Bound<u64>,
+}
+
+impl SlotRangeFilter {
+    /// Creates a filter for the updates of the slots in `range`.
+    pub fn new(range: impl RangeBounds<u64>) -> Self {
+        Self {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        }
+    }
+
+    fn contains(&self, slot: u64) -> bool {
+        (self.start, self.end).contains(&slot)
+    }
+}
+
+impl Filter for SlotRangeFilter {
+    fn filter_account(
+        &self,
+        _datasource_id: &DatasourceId,
+        account_metadata: &AccountMetadata,
+        _account: &solana_account::Account,
+    ) -> bool {
+        self.contains(account_metadata.slot)
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.contains(nested_instruction.metadata.transaction_metadata.slot)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        self.contains(transaction_metadata.slot)
+    }
+
+    fn filter_account_deletion(
+        &self,
+        _datasource_id: &DatasourceId,
+        account_deletion: &AccountDeletion,
+    ) -> bool {
+        self.contains(account_deletion.slot)
+    }
+
+    fn filter_block_details(
+        &self,
+        _datasource_id: &DatasourceId,
+        block_details: &BlockDetails,
+    ) -> bool {
+        self.contains(block_details.slot)
+    }
+
+    fn filter_slot_status(
+        &self,
+        _datasource_id: &DatasourceId,
+        slot_status: &SlotStatusUpdate,
+    ) -> bool {
+        self.contains(slot_status.slot)
+    }
+
+    fn filter_block(
+        &self,
+        _datasource_id: &DatasourceId,
+        block_details: &BlockDetails,
+        _transactions: &[Arc<TransactionMetadata>],
+    ) -> bool {
+        self.contains(block_details.slot)
+    }
+}
+
+/// A filter that lets through instructions and transactions depending on
+/// whether the transaction succeeded. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::TransactionStatusFilter;
+///
+/// let filter = TransactionStatusFilter::Failed;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionStatusFilter {
+    /// Lets through successful transactions.
+    Succeeded,
+    /// Lets through failed transactions.
+    Failed,
+}
+
+impl TransactionStatusFilter {
+    fn matches(&self, transaction_metadata: &TransactionMetadata) -> bool {
+        transaction_metadata.meta.status.is_ok() == (*self == Self::Succeeded)
+    }
+}
+
+impl Filter for TransactionStatusFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Instruction | FilterTarget::Transaction
+        )
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.matches(&nested_instruction.metadata.transaction_metadata)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        self.matches(transaction_metadata)
+    }
+}
+
+/// The address of the Vote program.
+pub(crate) const VOTE_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Vote111111111111111111111111111111111111111");
+
+/// A filter that lets through instructions and transactions depending on
+/// whether the transaction is a vote, that is, whether it invokes the Vote
+/// program. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::VoteFilter;
+///
+/// let filter = VoteFilter::NonVote;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteFilter {
+    /// Lets through vote transactions.
+    Vote,
+    /// Lets through transactions that are not votes.
+    NonVote,
+}
+
+impl VoteFilter {
+    fn matches(&self, transaction_metadata: &TransactionMetadata) -> bool {
+        let account_keys = transaction_metadata.message.static_account_keys();
+        let is_vote = transaction_metadata
+            .message
+            .instructions()
+            .iter()
+            .any(|instruction| {
+                account_keys.get(instruction.program_id_index as usize) == Some(&VOTE_PROGRAM_ID)
+            });
+
+        is_vote == (*self == Self::Vote)
+    }
+}
+
+impl Filter for VoteFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        matches!(
+            target,
+            FilterTarget::Instruction | FilterTarget::Transaction
+        )
+    }
+
+    fn votes(&self) -> Option<bool> {
+        Some(*self == Self::Vote)
+    }
+
+    fn filter_instruction(
+        &self,
+        _datasource_id: &DatasourceId,
+        nested_instruction: &NestedInstruction,
+    ) -> bool {
+        self.matches(&nested_instruction.metadata.transaction_metadata)
+    }
+
+    fn filter_transaction(
+        &self,
+        _datasource_id: &DatasourceId,
+        transaction_metadata: &TransactionMetadata,
+        _nested_instructions: &NestedInstructions,
+    ) -> bool {
+        self.matches(transaction_metadata)
+    }
+}
+
+/// A filter that lets through updates of accounts owned by specific programs.
+/// Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::AccountOwnerFilter;
+/// use solana_pubkey::Pubkey;
+///
+/// let filter = AccountOwnerFilter::new(Pubkey::new_unique());
+/// ```
+pub struct AccountOwnerFilter {
+    /// The programs of which one must own the account.
+    pub owners: Vec<Pubkey>,
+}
+
+impl AccountOwnerFilter {
+    /// Creates a filter for accounts owned by a single program.
+    pub fn new(owner: Pubkey) -> Self {
+        Self::new_many(vec![owner])
+    }
+
+    /// Creates a filter for accounts owned by any of several programs.
+    pub fn new_many(owners: Vec<Pubkey>) -> Self {
+        Self { owners }
+    }
+}
+
+impl Filter for AccountOwnerFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        target == FilterTarget::Account
+    }
+
+    fn filter_account(
+        &self,
+        _datasource_id: &DatasourceId,
+        _account_metadata: &AccountMetadata,
+        account: &solana_account::Account,
+    ) -> bool {
+        self.owners.contains(&account.owner)
+    }
+}
+
+/// A filter that lets through updates of accounts whose data has a specific
+/// size, in bytes. Other updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::AccountDataSizeFilter;
+///
+/// // SPL Token accounts.
+/// let filter = AccountDataSizeFilter::new(165);
+/// ```
+pub struct AccountDataSizeFilter {
+    /// The size of the account data.
+    pub data_size: usize,
+}
+
+impl AccountDataSizeFilter {
+    /// Creates a filter for accounts whose data is `data_size` bytes long.
+    pub fn new(data_size: usize) -> Self {
+        Self { data_size }
+    }
+}
+
+impl Filter for AccountDataSizeFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        target == FilterTarget::Account
+    }
+
+    fn filter_account(
+        &self,
+        _datasource_id: &DatasourceId,
+        _account_metadata: &AccountMetadata,
+        account: &solana_account::Account,
+    ) -> bool {
+        account.data.len() == self.data_size
+    }
+}
+
+/// A filter that lets through updates of accounts whose data contains
+/// specific bytes at an offset, like the `memcmp` filter of the RPC. Other
+/// updates always pass.
+///
+/// # Examples
+///
+/// ```
+/// use carbon_core::filter::MemcmpFilter;
+///
+/// // Accounts whose data starts with an Anchor discriminator.
+/// let filter = MemcmpFilter::new(0, vec![241, 154, 109, 4, 17, 177, 109, 188]);
+/// ```
+pub struct MemcmpFilter {
+    /// The position of the bytes in the account data.
+    pub offset: usize,
+    /// The bytes the account data must contain at `offset`.
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    /// Creates a filter for accounts whose data contains `bytes` at `offset`.
+    pub fn new(offset: usize, bytes: Vec<u8>) -> Self {
+        Self { offset, bytes }
+    }
+}
+
+impl Filter for MemcmpFilter {
+    fn inspects(&self, target: FilterTarget) -> bool {
+        target == FilterTarget::Account
+    }
+
+    fn filter_account(
+        &self,
+        _datasource_id: &DatasourceId,
+        _account_metadata: &AccountMetadata,
+        account: &solana_account::Account,
+    ) -> bool {
+        account
+            .data
+            .get(self.offset..)
+            .is_some_and(|data| data.starts_with(&self.bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::instruction::InstructionMetadata, solana_instruction::Instruction};
+
+    fn account(owner: Pubkey, data: Vec<u8>) -> solana_account::Account {
+        solana_account::Account {
+            owner,
+            data,
+            ..Default::default()
+        }
+    }
+
+    fn nested_instruction(program_id: Pubkey, inner: Vec<NestedInstruction>) -> NestedInstruction {
+        NestedInstruction {
+            metadata: InstructionMetadata {
+                transaction_metadata: Arc::default(),
+                stack_height: 1,
+                index: 0,
+                absolute_path: vec![],
+            },
+            instruction: Instruction {
+                program_id,
+                accounts: vec![],
+                data: vec![],
+            },
+            inner_instructions: NestedInstructions(Arc::new(inner)),
+        }
+    }
+
+    #[test]
+    fn test_combinators() {
+        let datasource_id = DatasourceId::new_named("test");
+        let owner = Pubkey::new_unique();
+        let metadata = AccountMetadata {
+            slot: 0,
+            pubkey: Pubkey::new_unique(),
+            transaction_signature: None,
+        };
+        let filter = OrFilter::new(vec![
+            Box::new(AndFilter::new(vec![
+                Box::new(AccountOwnerFilter::new(owner)),
+                Box::new(MemcmpFilter::new(1, vec![2, 3])),
+            ])),
+            Box::new(NotFilter::new(Box::new(AccountDataSizeFilter::new(3)))),
+        ]);
+
+        assert!(filter.filter_account(&datasource_id, &metadata, &account(owner, vec![1, 2, 3])));
+        assert!(filter.filter_account(&datasource_id, &metadata, &account(owner, vec![2])));
+        assert!(!filter.filter_account(&datasource_id, &metadata, &account(owner, vec![1, 2, 4])));
+        assert!(!filter.filter_account(
+            &datasource_id,
+            &metadata,
+            &account(Pubkey::new_unique(), vec![1, 2, 3])
+        ));
+    }
+
+    #[test]
+    fn test_program_filter_inner_instructions() {
+        let datasource_id = DatasourceId::new_named("test");
+        let program_id = Pubkey::new_unique();
+        let instruction = nested_instruction(
+            Pubkey::new_unique(),
+            vec![nested_instruction(program_id, vec![])],
+        );
+
+        assert!(ProgramFilter::new(program_id).filter_instruction(&datasource_id, &instruction));
+        assert!(!ProgramFilter::new(program_id)
+            .include_inner_instructions(false)
+            .filter_instruction(&datasource_id, &instruction));
+    }
+
+    #[test]
+    fn test_combinators_leave_out_filters_not_inspecting_updates() {
+        let datasource_id = DatasourceId::new_named("test");
+        let program_id = Pubkey::new_unique();
+        let owner_filter = || Box::new(AccountOwnerFilter::new(Pubkey::new_unique()));
+        let filter = OrFilter::new(vec![
+            Box::new(ProgramFilter::new(program_id)),
+            owner_filter(),
+        ]);
+        let transaction_metadata = TransactionMetadata::default();
+        let transaction =
+            |program_id| NestedInstructions(Arc::new(vec![nested_instruction(program_id, vec![])]));
+
+        assert!(filter.filter_transaction(
+            &datasource_id,
+            &transaction_metadata,
+            &transaction(program_id)
+        ));
+        assert!(!filter.filter_transaction(
+            &datasource_id,
+            &transaction_metadata,
+            &transaction(Pubkey::new_unique())
+        ));
+
+        let filter = NotFilter::new(owner_filter());
+        assert!(filter.filter_transaction(
+            &datasource_id,
+            &transaction_metadata,
+            &transaction(program_id)
+        ));
+        assert!(!filter.inspects(FilterTarget::Transaction));
+    }
+
+    #[test]
+    fn test_account_key_filter_inner_instructions() {
+        let datasource_id = DatasourceId::new_named("test");
+        let account = Pubkey::new_unique();
+        let mut inner_instruction = nested_instruction(Pubkey::new_unique(), vec![]);
+        inner_instruction
+            .instruction
+            .accounts
+            .push(solana_instruction::AccountMeta::new(account, false));
+        let instruction = nested_instruction(Pubkey::new_unique(), vec![inner_instruction]);
+
+        assert!(AccountKeyFilter::new(account).filter_instruction(&datasource_id, &instruction));
+        assert!(!AccountKeyFilter::new(Pubkey::new_unique())
+            .filter_instruction(&datasource_id, &instruction));
+    }
+
+    #[test]
+    fn test_slot_range_filter() {
+        let datasource_id = DatasourceId::new_named("test");
+        let deletion = |slot| AccountDeletion {
+            pubkey: Pubkey::new_unique(),
+            slot,
+            transaction_signature: None,
+        };
+        let filter = SlotRangeFilter::new(10..20);
+
+        assert!(!filter.filter_account_deletion(&datasource_id, &deletion(9)));
+        assert!(filter.filter_account_deletion(&datasource_id, &deletion(10)));
+        assert!(!filter.filter_account_deletion(&datasource_id, &deletion(20)));
+    }
 }
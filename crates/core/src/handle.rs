@@ -0,0 +1,299 @@
+//! Provides the `PipelineHandle`, which changes the pipes and datasources of a
+//! running pipeline.
+//!
+//! `Pipeline::spawn` runs a pipeline in its own task and returns a handle to
+//! it. Through the handle, pipes and datasources can be added and removed
+//! while updates keep flowing, for example to start indexing a newly launched
+//! program without restarting the indexer.
+//!
+//! # Pipe Identifiers
+//!
+//! Every pipe is identified by a `PipeId`, made of its kind and its position
+//! among the pipes of that kind, in the order they were added. The first
+//! instruction pipe added to the builder is `instruction[0]`, the next one
+//! added at runtime is `instruction[1]`, and so on. Identifiers are never
+//! reused, even after a pipe is removed. The same names are used in logs and
+//! dead letters.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::{
+//!     datasource::DatasourceId, error_policy::ErrorPolicy, instruction::InstructionPipe,
+//! };
+//! use std::sync::Arc;
+//!
+//! let handle = Pipeline::builder()
+//!     .datasource_with_id(helius_websocket, DatasourceId::new_named("helius"))
+//!     .instruction(TokenProgramDecoder, TokenProgramProcessor)
+//!     .build()?
+//!     .spawn();
+//!
+//! let pipe_id = handle
+//!     .add_instruction_pipe(Box::new(InstructionPipe {
+//!         decoder: Arc::new(NewProgramDecoder),
+//!         processor: Box::new(NewProgramProcessor),
+//!         filters: Arc::default(),
+//!         error_policy: ErrorPolicy::Skip,
+//!     }))
+//!     .await?;
+//!
+//! println!("{:#?}", handle.topology().await?);
+//!
+//! handle.remove_pipe(pipe_id).await?;
+//! handle.join().await?;
+//! ```
+//!
+//! # Notes
+//!
+//! - Added pipes are initialized before they receive updates, and removed
+//!   pipes are flushed and shut down once their in-flight update is
+//!   processed. See the lifecycle hooks of `Processor`.
+//! - Added pipes only receive updates that arrive after they were added.
+//! - While the handle is held, the pipeline keeps running without any
+//!   datasource, so datasources can be swapped, and a pipeline can be spawned
+//!   without datasources. Once the handle is joined or dropped, the pipeline
+//!   stops when its last datasource stopped.
+//! - A datasource removed and added again under the same `DatasourceId`
+//!   reports the status of the new datasource only.
+
+use {
+    crate::{
+        account::AccountPipes,
+        datasource::{Datasource, DatasourceId},
+        error::{CarbonResult, Error},
+        instruction::InstructionPipes,
+        supervisor::DatasourceStatus,
+        transaction::TransactionPipes,
+    },
+    std::{fmt, sync::Arc},
+    tokio::{
+        sync::{mpsc, oneshot},
+        task::JoinHandle,
+    },
+};
+
+/// The default number of commands that can be queued for a running pipeline.
+pub const DEFAULT_COMMAND_BUFFER_SIZE: usize = 64;
+
+/// The kind of a pipe, which decides the updates it receives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipeKind {
+    Account,
+    AccountDeletion,
+    BlockDetails,
+    Block,
+    SlotStatus,
+    Rollback,
+    Instruction,
+    Transaction,
+}
+
+impl PipeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipeKind::Account => "account",
+            PipeKind::AccountDeletion => "account_deletion",
+            PipeKind::BlockDetails => "block_details",
+            PipeKind::Block => "block",
+            PipeKind::SlotStatus => "slot_status",
+            PipeKind::Rollback => "rollback",
+            PipeKind::Instruction => "instruction",
+            PipeKind::Transaction => "transaction",
+        }
+    }
+}
+
+/// Identifies a pipe of a pipeline, such as `instruction[0]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PipeId {
+    pub kind: PipeKind,
+    pub index: usize,
+}
+
+impl fmt::Display for PipeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.kind.as_str(), self.index)
+    }
+}
+
+/// The pipes and datasources of a pipeline.
+///
+/// # Fields
+///
+/// - `datasources`: Every datasource with its latest status, in the order
+///   they were added.
+/// - `pipes`: Every pipe, grouped by kind, in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineTopology {
+    pub datasources: Vec<(DatasourceId, DatasourceStatus)>,
+    pub pipes: Vec<PipeId>,
+}
+
+/// A change requested through a `PipelineHandle`, applied by the run loop of
+/// the pipeline.
+pub(crate) enum PipelineCommand {
+    AddAccountPipe(Box<dyn AccountPipes>, oneshot::Sender<CarbonResult<PipeId>>),
+    AddInstructionPipe(
+        Box<dyn for<'a> InstructionPipes<'a>>,
+        oneshot::Sender<CarbonResult<PipeId>>,
+    ),
+    AddTransactionPipe(
+        Box<dyn for<'a> TransactionPipes<'a>>,
+        oneshot::Sender<CarbonResult<PipeId>>,
+    ),
+    RemovePipe(PipeId, oneshot::Sender<CarbonResult<()>>),
+    AddDatasource(
+        DatasourceId,
+        Arc<dyn Datasource + Send + Sync>,
+        oneshot::Sender<CarbonResult<()>>,
+    ),
+    RemoveDatasource(DatasourceId, oneshot::Sender<CarbonResult<()>>),
+    Topology(oneshot::Sender<PipelineTopology>),
+}
+
+/// A handle to a pipeline started with `Pipeline::spawn`.
+///
+/// Every method waits until the running pipeline applied the change, and
+/// fails if the pipeline is no longer running.
+pub struct PipelineHandle {
+    commands: mpsc::Sender<PipelineCommand>,
+    task: JoinHandle<CarbonResult<()>>,
+}
+
+impl PipelineHandle {
+    pub(crate) fn new(
+        commands: mpsc::Sender<PipelineCommand>,
+        task: JoinHandle<CarbonResult<()>>,
+    ) -> Self {
+        Self { commands, task }
+    }
+
+    /// Adds an account pipe, returning its `PipeId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipe fails to initialize or the pipeline is no
+    /// longer running.
+    pub async fn add_account_pipe(&self, pipe: Box<dyn AccountPipes>) -> CarbonResult<PipeId> {
+        log::trace!("PipelineHandle::add_account_pipe(pipe)");
+        self.request(|reply| PipelineCommand::AddAccountPipe(pipe, reply))
+            .await?
+    }
+
+    /// Adds an instruction pipe, returning its `PipeId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipe fails to initialize or the pipeline is no
+    /// longer running.
+    pub async fn add_instruction_pipe(
+        &self,
+        pipe: Box<dyn for<'a> InstructionPipes<'a>>,
+    ) -> CarbonResult<PipeId> {
+        log::trace!("PipelineHandle::add_instruction_pipe(pipe)");
+        self.request(|reply| PipelineCommand::AddInstructionPipe(pipe, reply))
+            .await?
+    }
+
+    /// Adds a transaction pipe, returning its `PipeId`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipe fails to initialize or the pipeline is no
+    /// longer running.
+    pub async fn add_transaction_pipe(
+        &self,
+        pipe: Box<dyn for<'a> TransactionPipes<'a>>,
+    ) -> CarbonResult<PipeId> {
+        log::trace!("PipelineHandle::add_transaction_pipe(pipe)");
+        self.request(|reply| PipelineCommand::AddTransactionPipe(pipe, reply))
+            .await?
+    }
+
+    /// Removes a pipe, flushing and shutting it down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no pipe has this `PipeId` or the pipeline is no
+    /// longer running.
+    pub async fn remove_pipe(&self, pipe_id: PipeId) -> CarbonResult<()> {
+        log::trace!("PipelineHandle::remove_pipe(pipe_id: {pipe_id})");
+        self.request(|reply| PipelineCommand::RemovePipe(pipe_id, reply))
+            .await?
+    }
+
+    /// Adds a datasource and starts consuming it under the pipeline's
+    /// `RestartPolicy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a datasource with the same `DatasourceId` exists or
+    /// the pipeline is no longer running.
+    pub async fn add_datasource(
+        &self,
+        id: DatasourceId,
+        datasource: impl Datasource + 'static,
+    ) -> CarbonResult<()> {
+        log::trace!("PipelineHandle::add_datasource(id: {id:?}, datasource)");
+        self.request(|reply| PipelineCommand::AddDatasource(id, Arc::new(datasource), reply))
+            .await?
+    }
+
+    /// Stops and removes a datasource. Updates it already sent are still
+    /// processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no datasource has this `DatasourceId` or the
+    /// pipeline is no longer running.
+    pub async fn remove_datasource(&self, id: DatasourceId) -> CarbonResult<()> {
+        log::trace!("PipelineHandle::remove_datasource(id: {id:?})");
+        self.request(|reply| PipelineCommand::RemoveDatasource(id, reply))
+            .await?
+    }
+
+    /// Lists the current datasources and pipes of the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pipeline is no longer running.
+    pub async fn topology(&self) -> CarbonResult<PipelineTopology> {
+        self.request(PipelineCommand::Topology).await
+    }
+
+    /// Returns whether the pipeline task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Waits for the pipeline to stop, returning the result of its run.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the pipeline stopped with, or an error if its task
+    /// panicked.
+    pub async fn join(self) -> CarbonResult<()> {
+        drop(self.commands);
+
+        self.task
+            .await
+            .map_err(|err| Error::Custom(format!("pipeline task failed: {err}")))?
+    }
+
+    async fn request<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> PipelineCommand,
+    ) -> CarbonResult<T> {
+        let (reply, response) = oneshot::channel();
+
+        self.commands
+            .send(command(reply))
+            .await
+            .map_err(|_| Error::Custom("pipeline is no longer running".to_string()))?;
+
+        response
+            .await
+            .map_err(|_| Error::Custom("pipeline is no longer running".to_string()))
+    }
+}
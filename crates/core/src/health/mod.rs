@@ -0,0 +1,349 @@
+//! Tracks the health of a running pipeline, and serves it over HTTP with the
+//! `health` feature.
+//!
+//! Every `Pipeline` keeps a `PipelineStatus` up to date while it runs. It
+//! combines the state of every datasource, as reported by their supervisors,
+//! with the progress of the pipeline itself:
+//!
+//! - the highest slot seen in an update, overall and per datasource,
+//! - the number of updates waiting in the pipeline's channel
+//!   (`updates_queued`),
+//! - the time since the last update that every pipe processed successfully.
+//!
+//! # Liveness and Readiness
+//!
+//! - A pipeline is **live** unless a pipe with `ErrorPolicy::Halt` stopped it
+//!   or, with a `PipelineBuilder::liveness_timeout`, it runs but processed no
+//!   update within the timeout, for example because a pipe is stuck.
+//! - A pipeline is **ready** while it runs, is not halted and at least one of
+//!   its datasources is running.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::health::server::serve;
+//!
+//! let mut pipeline = Pipeline::builder()
+//!     .datasource(helius_websocket)
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?;
+//!
+//! tokio::spawn(serve("0.0.0.0:8080".parse()?, pipeline.status()));
+//!
+//! pipeline.run().await?;
+//! ```
+
+#[cfg(feature = "health")]
+pub mod server;
+
+use {
+    crate::{
+        datasource::DatasourceId,
+        supervisor::{DatasourceHealth, DatasourceStatus},
+    },
+    serde::Serialize,
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+/// The live status of a pipeline, shared between the pipeline and health
+/// checks.
+///
+/// Obtain it through `Pipeline::status`. It stays up to date across runs of
+/// the pipeline.
+#[derive(Debug)]
+pub struct PipelineStatus {
+    datasources: Arc<DatasourceHealth>,
+    created_at: Instant,
+    running: AtomicBool,
+    halted: AtomicBool,
+    /// The highest slot seen, plus one, or zero if no update was seen yet.
+    last_slot: AtomicU64,
+    /// The highest slot seen per datasource.
+    datasource_slots: Mutex<HashMap<DatasourceId, u64>>,
+    updates_queued: AtomicUsize,
+    /// Milliseconds between `created_at` and the last successfully processed
+    /// update, plus one, or zero if no update was processed yet.
+    last_processed_at: AtomicU64,
+    /// Milliseconds between `created_at` and the last start of the pipeline.
+    started_at: AtomicU64,
+    /// How long a running pipeline may go without processing an update
+    /// before it is no longer live.
+    liveness_timeout: Option<Duration>,
+}
+
+/// A snapshot of a `PipelineStatus`, as served by the `/status` endpoint.
+///
+/// # Fields
+///
+/// - `last_slot`: The highest slot seen in an update from any datasource.
+/// - `seconds_since_last_processed_update`: The time since the last update
+///   every pipe processed successfully, or `None` if there was none yet.
+/// - `datasources`: The state and highest slot of every datasource, in the
+///   order they were added to the pipeline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusReport {
+    pub live: bool,
+    pub ready: bool,
+    pub running: bool,
+    pub last_slot: Option<u64>,
+    pub updates_queued: usize,
+    pub seconds_since_last_processed_update: Option<f64>,
+    pub datasources: Vec<DatasourceReport>,
+}
+
+/// The state of a single datasource in a `StatusReport`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasourceReport {
+    pub id: String,
+    pub last_slot: Option<u64>,
+    #[serde(flatten)]
+    pub status: DatasourceStatus,
+}
+
+impl PipelineStatus {
+    pub(crate) fn new(
+        datasources: Arc<DatasourceHealth>,
+        liveness_timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            datasources,
+            created_at: Instant::now(),
+            running: AtomicBool::new(false),
+            halted: AtomicBool::new(false),
+            last_slot: AtomicU64::new(0),
+            datasource_slots: Mutex::new(HashMap::new()),
+            updates_queued: AtomicUsize::new(0),
+            last_processed_at: AtomicU64::new(0),
+            started_at: AtomicU64::new(0),
+            liveness_timeout,
+        }
+    }
+
+    /// Returns whether the pipeline is live, meaning it was not halted and,
+    /// with a liveness timeout, processed an update within the timeout while
+    /// it runs. A pipeline that just started counts from its start.
+    pub fn is_live(&self) -> bool {
+        !self.halted.load(Ordering::Relaxed) && !self.is_stale()
+    }
+
+    fn is_stale(&self) -> bool {
+        let Some(liveness_timeout) = self.liveness_timeout else {
+            return false;
+        };
+        if !self.is_running() {
+            return false;
+        }
+
+        let last_activity = self
+            .last_processed_at
+            .load(Ordering::Relaxed)
+            .saturating_sub(1)
+            .max(self.started_at.load(Ordering::Relaxed));
+        let idle = self
+            .created_at
+            .elapsed()
+            .saturating_sub(Duration::from_millis(last_activity));
+
+        idle > liveness_timeout
+    }
+
+    /// Returns whether the pipeline is ready, meaning it runs, was not halted
+    /// and at least one of its datasources is running.
+    pub fn is_ready(&self) -> bool {
+        self.is_running() && self.is_live() && self.datasources.running() > 0
+    }
+
+    /// Returns whether `Pipeline::run` is in progress.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /// Returns the highest slot seen in an update, if any.
+    pub fn last_slot(&self) -> Option<u64> {
+        self.last_slot.load(Ordering::Relaxed).checked_sub(1)
+    }
+
+    /// Returns the highest slot seen in an update from a datasource, if any.
+    pub fn datasource_last_slot(&self, id: &DatasourceId) -> Option<u64> {
+        self.datasource_slots
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(id)
+            .copied()
+    }
+
+    /// Returns the number of updates waiting in the pipeline's channel.
+    pub fn updates_queued(&self) -> usize {
+        self.updates_queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns the time since the last update that every pipe processed
+    /// successfully, if any.
+    pub fn time_since_last_processed_update(&self) -> Option<Duration> {
+        let last_processed_at = self
+            .last_processed_at
+            .load(Ordering::Relaxed)
+            .checked_sub(1)?;
+
+        Some(
+            self.created_at
+                .elapsed()
+                .saturating_sub(Duration::from_millis(last_processed_at)),
+        )
+    }
+
+    /// Returns the state of every datasource.
+    pub fn datasources(&self) -> &DatasourceHealth {
+        &self.datasources
+    }
+
+    /// Takes a snapshot of the status.
+    pub fn report(&self) -> StatusReport {
+        StatusReport {
+            live: self.is_live(),
+            ready: self.is_ready(),
+            running: self.is_running(),
+            last_slot: self.last_slot(),
+            updates_queued: self.updates_queued(),
+            seconds_since_last_processed_update: self
+                .time_since_last_processed_update()
+                .map(|elapsed| elapsed.as_secs_f64()),
+            datasources: self
+                .datasources
+                .statuses()
+                .into_iter()
+                .map(|(id, status)| DatasourceReport {
+                    id: id.as_str().to_string(),
+                    last_slot: self.datasource_last_slot(&id),
+                    status,
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn started(&self) {
+        self.halted.store(false, Ordering::Relaxed);
+        self.started_at.store(
+            self.created_at.elapsed().as_millis() as u64,
+            Ordering::Relaxed,
+        );
+        self.running.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stopped(&self) {
+        self.running.store(false, Ordering::Relaxed);
+        self.updates_queued.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn halted(&self) {
+        self.halted.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn observe_slot(&self, datasource_id: &DatasourceId, slot: u64) {
+        self.last_slot
+            .fetch_max(slot.saturating_add(1), Ordering::Relaxed);
+
+        let mut datasource_slots = self
+            .datasource_slots
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match datasource_slots.get_mut(datasource_id) {
+            Some(last_slot) => *last_slot = (*last_slot).max(slot),
+            None => {
+                datasource_slots.insert(datasource_id.clone(), slot);
+            }
+        }
+    }
+
+    pub(crate) fn set_updates_queued(&self, updates_queued: usize) {
+        self.updates_queued.store(updates_queued, Ordering::Relaxed);
+    }
+
+    pub(crate) fn processed(&self) {
+        let elapsed = self.created_at.elapsed().as_millis() as u64;
+        self.last_processed_at
+            .store(elapsed.saturating_add(1), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_requires_a_running_datasource() {
+        let datasources = Arc::new(DatasourceHealth::default());
+        let status = PipelineStatus::new(datasources.clone(), None);
+        let id = DatasourceId::new_named("mainnet");
+
+        status.started();
+        assert!(status.is_live());
+        assert!(!status.is_ready());
+
+        datasources.register(&id);
+        assert!(status.is_ready());
+
+        status.halted();
+        assert!(!status.is_live());
+        assert!(!status.is_ready());
+    }
+
+    #[test]
+    fn test_report() {
+        let datasources = Arc::new(DatasourceHealth::default());
+        let status = PipelineStatus::new(datasources.clone(), None);
+        let mainnet = DatasourceId::new_named("mainnet");
+        let backfill = DatasourceId::new_named("backfill");
+        datasources.register(&mainnet);
+        datasources.register(&backfill);
+        assert_eq!(status.last_slot(), None);
+        assert_eq!(status.time_since_last_processed_update(), None);
+
+        status.observe_slot(&mainnet, 0);
+        status.observe_slot(&mainnet, 42);
+        status.observe_slot(&backfill, 7);
+        status.set_updates_queued(3);
+        status.processed();
+
+        let report = status.report();
+        assert_eq!(report.last_slot, Some(42));
+        assert_eq!(report.updates_queued, 3);
+        assert!(report.seconds_since_last_processed_update.is_some());
+        assert_eq!(
+            report
+                .datasources
+                .iter()
+                .map(|datasource| datasource.last_slot)
+                .collect::<Vec<_>>(),
+            vec![Some(42), Some(7)]
+        );
+    }
+
+    #[test]
+    fn test_not_live_without_processed_updates_within_timeout() {
+        let status = PipelineStatus::new(
+            Arc::new(DatasourceHealth::default()),
+            Some(Duration::from_millis(50)),
+        );
+
+        status.started();
+        assert!(status.is_live());
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!status.is_live());
+
+        status.processed();
+        assert!(status.is_live());
+
+        status.stopped();
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(status.is_live());
+    }
+}
@@ -0,0 +1,71 @@
+//! Serves the `PipelineStatus` of a pipeline over HTTP.
+//!
+//! # Endpoints
+//!
+//! - `GET /health/live`: `200 OK` while the pipeline is live, `503 Service
+//!   Unavailable` once it was halted or, with a liveness timeout, processed
+//!   no update within the timeout.
+//! - `GET /health/ready`: `200 OK` while the pipeline is ready, `503 Service
+//!   Unavailable` otherwise.
+//! - `GET /status`: The `StatusReport` of the pipeline, as JSON.
+
+use {
+    crate::{
+        error::{CarbonResult, Error},
+        health::{PipelineStatus, StatusReport},
+    },
+    axum::{http::StatusCode, routing::get, Extension, Json, Router},
+    std::{net::SocketAddr, sync::Arc},
+};
+
+/// Builds a `Router` serving the health endpoints of a pipeline.
+///
+/// Use it to mount the endpoints in an existing axum application, or `serve`
+/// to run them on their own.
+pub fn health_router(status: Arc<PipelineStatus>) -> Router {
+    async fn live(Extension(status): Extension<Arc<PipelineStatus>>) -> StatusCode {
+        if status.is_live() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+
+    async fn ready(Extension(status): Extension<Arc<PipelineStatus>>) -> StatusCode {
+        if status.is_ready() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+
+    async fn report(Extension(status): Extension<Arc<PipelineStatus>>) -> Json<StatusReport> {
+        Json(status.report())
+    }
+
+    Router::new()
+        .route("/health/live", get(live))
+        .route("/health/ready", get(ready))
+        .route("/status", get(report))
+        .layer(Extension(status))
+}
+
+/// Serves the health endpoints of a pipeline on `address` until the task is
+/// dropped.
+///
+/// # Errors
+///
+/// Returns an error if the address can't be bound or the server fails.
+pub async fn serve(address: SocketAddr, status: Arc<PipelineStatus>) -> CarbonResult<()> {
+    log::info!("serving pipeline health on {address}");
+
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|err| {
+            Error::Custom(format!("failed to bind health server to {address}: {err}"))
+        })?;
+
+    axum::serve(listener, health_router(status))
+        .await
+        .map_err(|err| Error::Custom(format!("health server failed: {err}")))
+}
@@ -21,8 +21,9 @@
 
 use {
     crate::{
-        deserialize::CarbonDeserialize, error::CarbonResult, filter::Filter,
-        metrics::MetricsCollection, processor::Processor, transaction::TransactionMetadata,
+        deserialize::CarbonDeserialize, error::CarbonResult, error_policy::ErrorPolicy,
+        filter::Filter, logs::InstructionLogs, metrics::MetricsCollection, processor::Processor,
+        transaction::TransactionMetadata,
     },
     async_trait::async_trait,
     serde::{Deserialize, Serialize},
@@ -70,12 +71,14 @@ enum LogType {
 }
 
 /// Known Solana precompile programs that don't emit invoke logs.
-/// These programs execute signature verification without logging.
+/// These programs execute signature verification without logging, and the
+/// number of signatures they verify is the first byte of their instruction
+/// data.
 /// See: https://solana.com/docs/core/programs#precompile-programs
-const PRECOMPILE_PROGRAMS: &[&str] = &[
-    "Ed25519SigVerify111111111111111111111111111",
-    "KeccakSecp256k11111111111111111111111111111",
-    "Secp256r1SigVerify1111111111111111111111111",
+pub const PRECOMPILE_PROGRAM_IDS: [Pubkey; 3] = [
+    Pubkey::from_str_const("Ed25519SigVerify111111111111111111111111111"),
+    Pubkey::from_str_const("KeccakSecp256k11111111111111111111111111111"),
+    Pubkey::from_str_const("Secp256r1SigVerify1111111111111111111111111"),
 ];
 
 impl InstructionMetadata {
@@ -96,6 +99,50 @@ impl InstructionMetadata {
             .collect()
     }
 
+    /// Returns the logs of this instruction, parsed from the transaction's log
+    /// messages, with its compute units, outcome and own log lines.
+    ///
+    /// # Returns
+    ///
+    /// The `InstructionLogs` of the instruction, or `None` if the transaction
+    /// has no log messages, the logs were truncated before the instruction,
+    /// or the instruction is a precompile, which doesn't log.
+    pub fn logs(&self) -> Option<InstructionLogs> {
+        self.logs_in(&self.transaction_metadata.log_tree()).cloned()
+    }
+
+    /// Returns the logs of this instruction within `log_tree`, the log tree of
+    /// its transaction as returned by `TransactionMetadata::log_tree`.
+    ///
+    /// Unlike `logs`, this doesn't parse the logs again, so the tree can be
+    /// parsed once and shared by every instruction of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The `InstructionLogs` of the instruction, or `None` in the same cases
+    /// as `logs`.
+    pub fn logs_in<'a>(&self, log_tree: &'a [InstructionLogs]) -> Option<&'a InstructionLogs> {
+        let (outer_index, inner_path) = self.absolute_path.split_first()?;
+
+        let account_keys = self.transaction_metadata.message.static_account_keys();
+        let outer_instruction = self
+            .transaction_metadata
+            .message
+            .instructions()
+            .get(*outer_index as usize)?;
+        if account_keys
+            .get(outer_instruction.program_id_index as usize)
+            .is_some_and(|program_id| PRECOMPILE_PROGRAM_IDS.contains(program_id))
+        {
+            return None;
+        }
+
+        let position =
+            (*outer_index as usize).checked_sub(self.count_precompiles_before_index())?;
+
+        log_tree.get(position)?.get(inner_path)
+    }
+
     /// Extracts the `data` from log messages associated with this instruction.
     ///
     /// This method filters the transaction's log messages to return only those
@@ -103,7 +150,7 @@ impl InstructionMetadata {
     /// absolute path within the instruction stack.
     ///
     /// Returns `Vec<Vec<u8>>` containing the `data` bytes (base64 encoded) from log messages.
-    fn extract_event_log_data(&self) -> Vec<Vec<u8>> {
+    pub(crate) fn extract_event_log_data(&self) -> Vec<Vec<u8>> {
         let logs = match &self.transaction_metadata.meta.log_messages {
             Some(logs) => logs,
             None => return Vec::new(),
@@ -187,11 +234,11 @@ impl InstructionMetadata {
             if idx >= outer_index {
                 break;
             }
-            if let Some(program_id) = account_keys.get(ix.program_id_index as usize) {
-                let program_id_str = program_id.to_string();
-                if PRECOMPILE_PROGRAMS.contains(&program_id_str.as_str()) {
-                    precompile_count += 1;
-                }
+            if account_keys
+                .get(ix.program_id_index as usize)
+                .is_some_and(|program_id| PRECOMPILE_PROGRAM_IDS.contains(program_id))
+            {
+                precompile_count += 1;
             }
         }
 
@@ -264,6 +311,13 @@ pub struct DecodedInstruction<T> {
 ///
 /// - `decode_instruction`: Decodes a raw Solana `Instruction` into a
 ///   `DecodedInstruction`.
+///
+/// # Provided Methods
+///
+/// - `program_ids`: Returns the programs whose instructions the decoder
+///   decodes, so that datasources can subscribe to them. Defaults to an empty
+///   list, meaning that the decoder may decode the instructions of any
+///   program.
 pub trait InstructionDecoder<'a> {
     type InstructionType;
 
@@ -271,6 +325,10 @@ pub trait InstructionDecoder<'a> {
         &self,
         instruction: &'a solana_instruction::Instruction,
     ) -> Option<DecodedInstruction<Self::InstructionType>>;
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
 }
 
 /// The input type for the instruction processor.
@@ -302,27 +360,42 @@ pub type InstructionProcessorInputType<T> = (
 ///   incoming instruction updates, and only updates that pass all filters
 ///   (return `true`) will be processed. If this collection is empty, all
 ///   updates are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
 pub struct InstructionPipe<T: Send> {
     pub decoder:
-        Box<dyn for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static>,
+        Arc<dyn for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static>,
     pub processor:
         Box<dyn Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync + 'static>,
-    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    pub error_policy: ErrorPolicy,
 }
 
 /// An async trait for processing instructions within nested contexts.
 ///
-/// The `InstructionPipes` trait allows for recursive processing of instructions
-/// that may contain nested instructions. This enables complex, hierarchical
-/// instruction handling for transactions.
+/// The pipeline runs the pipe for every instruction of a transaction that
+/// passes its filters, and then for each of the inner instructions of that
+/// instruction, depth first, so that a retry under the pipe's `ErrorPolicy`
+/// only repeats the instruction that failed.
 ///
 /// # Required Methods
 ///
-/// - `run`: Processes a `NestedInstruction`, recursively processing any inner
-///   instructions.
+/// - `run`: Processes a single `NestedInstruction`. Its inner instructions are
+///   run separately by the pipeline.
 /// - `filters`: Returns a reference to the filters associated with this pipe,
 ///   which are used by the pipeline to determine which instruction updates
 ///   should be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `program_ids`: Returns the programs whose instructions the pipe
+///   processes. Defaults to an empty list, meaning any program.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+/// - `fork`: Returns a new instance of the pipe for another worker, sharing
+///   its decoder and filters but with a fork of its processor. Defaults to
+///   `None`, meaning the pipe is shared between the workers.
 #[async_trait]
 pub trait InstructionPipes<'a>: Send + Sync {
     async fn run(
@@ -331,6 +404,30 @@ pub trait InstructionPipes<'a>: Send + Sync {
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()>;
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    fn fork(&self) -> Option<Box<dyn for<'b> InstructionPipes<'b>>> {
+        None
+    }
 }
 
 #[async_trait]
@@ -346,6 +443,8 @@ impl<T: Send + 'static> InstructionPipes<'_> for InstructionPipe<T> {
             .decoder
             .decode_instruction(&nested_instruction.instruction)
         {
+            // Cloning the inner instructions only shares them, see
+            // `NestedInstructions`.
             self.processor
                 .process(
                     (
@@ -359,16 +458,41 @@ impl<T: Send + 'static> InstructionPipes<'_> for InstructionPipe<T> {
                 .await?;
         }
 
-        for nested_inner_instruction in nested_instruction.inner_instructions.iter() {
-            self.run(nested_inner_instruction, metrics.clone()).await?;
-        }
-
         Ok(())
     }
 
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
         &self.filters
     }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        self.decoder.program_ids()
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn for<'b> InstructionPipes<'b>>> {
+        Some(Box::new(InstructionPipe {
+            decoder: self.decoder.clone(),
+            processor: self.processor.fork()?,
+            filters: self.filters.clone(),
+            error_policy: self.error_policy,
+        }))
+    }
 }
 
 /// Represents a nested instruction with metadata, including potential inner
@@ -391,8 +515,14 @@ pub struct NestedInstruction {
     pub inner_instructions: NestedInstructions,
 }
 
-#[derive(Debug, Default)]
-pub struct NestedInstructions(pub Vec<NestedInstruction>);
+/// The instructions of a transaction, nested by stack height.
+///
+/// The instructions are shared behind an `Arc`, so cloning `NestedInstructions`
+/// is cheap. This lets every decoded instruction carry its inner instructions
+/// to the processor without copying the instruction tree. Mutating shared
+/// instructions, through `push` or `DerefMut`, copies them first.
+#[derive(Debug, Default, Clone)]
+pub struct NestedInstructions(pub Arc<Vec<NestedInstruction>>);
 
 impl NestedInstructions {
     pub fn len(&self) -> usize {
@@ -404,7 +534,7 @@ impl NestedInstructions {
     }
 
     pub fn push(&mut self, nested_instruction: NestedInstruction) {
-        self.0.push(nested_instruction);
+        Arc::make_mut(&mut self.0).push(nested_instruction);
     }
 }
 
@@ -418,13 +548,7 @@ impl Deref for NestedInstructions {
 
 impl DerefMut for NestedInstructions {
     fn deref_mut(&mut self) -> &mut [NestedInstruction] {
-        &mut self.0[..]
-    }
-}
-
-impl Clone for NestedInstructions {
-    fn clone(&self) -> Self {
-        NestedInstructions(self.0.clone())
+        &mut Arc::make_mut(&mut self.0)[..]
     }
 }
 
@@ -433,7 +557,7 @@ impl IntoIterator for NestedInstructions {
     type IntoIter = std::vec::IntoIter<NestedInstruction>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        Arc::unwrap_or_clone(self.0).into_iter()
     }
 }
 
@@ -525,7 +649,7 @@ impl UnsafeNestedBuilder {
             }
         }
 
-        NestedInstructions(self.nested_ixs)
+        NestedInstructions(Arc::new(self.nested_ixs))
     }
 }
 
@@ -834,6 +958,63 @@ mod tests {
         assert_eq!(extracted[0], b"test_data");
     }
 
+    #[test]
+    fn test_logs_with_precompile_offset() {
+        use solana_message::compiled_instruction::CompiledInstruction;
+
+        let ed25519 = Pubkey::from_str("Ed25519SigVerify111111111111111111111111111").unwrap();
+        let target_program = Pubkey::new_unique();
+        let logs = vec![
+            "Program ComputeBudget111111111111111111111111111 invoke [1]".to_string(),
+            "Program ComputeBudget111111111111111111111111111 success".to_string(),
+            format!("Program {} invoke [1]", target_program),
+            "Program log: Instruction: Swap".to_string(),
+            format!(
+                "Program {} consumed 1200 of 200000 compute units",
+                target_program
+            ),
+            format!(
+                "Program {} failed: custom program error: 0x1771",
+                target_program
+            ),
+        ];
+        let instructions = (1..=3)
+            .map(|program_id_index| CompiledInstruction {
+                program_id_index,
+                accounts: vec![],
+                data: vec![],
+            })
+            .collect::<Vec<_>>();
+        let account_keys = vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            ed25519,
+            target_program,
+        ];
+
+        let metadata = create_metadata_with_message(
+            vec![2],
+            1,
+            logs.clone(),
+            account_keys.clone(),
+            instructions.clone(),
+        );
+        let instruction_logs = metadata.logs().expect("instruction logs");
+        assert_eq!(instruction_logs.program_id, target_program);
+        assert_eq!(instruction_logs.compute_units_consumed, Some(1200));
+        assert_eq!(
+            instruction_logs.status,
+            crate::logs::InstructionStatus::Failed("custom program error: 0x1771".to_string())
+        );
+        assert_eq!(
+            instruction_logs.logs,
+            vec!["Program log: Instruction: Swap"]
+        );
+
+        let precompile = create_metadata_with_message(vec![1], 1, logs, account_keys, instructions);
+        assert!(precompile.logs().is_none());
+    }
+
     #[test]
     fn test_extract_event_log_data_cpi_with_precompile_offset() {
         use solana_message::compiled_instruction::CompiledInstruction;
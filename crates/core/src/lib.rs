@@ -19,13 +19,52 @@
 //! - **[`account_deletion`]**: Handles the deletion of accounts and processes
 //!   these events in the pipeline.
 //!
+//! - **[`account_diff`]**: Provides account diff pipes, which hand processors
+//!   the previous decoded state of an account alongside the new one, keeping
+//!   a bounded cache of recently updated accounts.
+//!
+//! - **[`account_store`]**: Provides the `AccountStore`, an in-memory copy of
+//!   the accounts a pipeline has seen, which processors can query.
+//!
+//! - **[`balance_changes`]**: Extracts the SOL and token balance changes of a
+//!   transaction, and the token transfers made by each instruction.
+//!
+//! - **[`batch`]**: Provides the `BatchProcessor` trait and an adapter that
+//!   buffers the inputs of a pipe and processes them in batches, flushing by
+//!   size, by time and on shutdown.
+//!
+//! - **[`block`]**: Provides block pipes, which receive every transaction of a
+//!   slot at once, in block order, together with its block details.
+//!
+//! - **[`checkpoint`]**: Provides durable checkpoints that record how far each
+//!   datasource has been processed, allowing pipelines to resume from the last
+//!   committed slot or signature after a restart.
+//!
 //! - **[`collection`]**: Defines collections for instruction decoding, allowing
 //!   for customized instruction parsers that handle specific instruction sets.
 //!
+//! - **[`commitment_buffer`]**: Provides a buffer that holds back the updates of
+//!   each slot until the slot reaches a target commitment level, discarding the
+//!   updates of dead or abandoned slots.
+//!
+//! - **[`compute_budget`]**: Summarizes the compute unit limit and price of a
+//!   transaction and splits its fee into base and priority fees.
+//!
+//! - **[`config`]**: Assembles a pipeline from a TOML or YAML configuration
+//!   describing its datasources, filters, metrics backends and settings, with
+//!   the `config` feature.
+//!
 //! - **[`datasource`]**: Provides data ingestion capabilities, enabling the
 //!   integration of external data sources into the pipeline. Supports
 //!   Solana-specific data structures.
 //!
+//! - **[`dead_letter`]**: Provides sinks that collect the updates a pipe
+//!   failed to process, so they can be inspected and replayed later.
+//!
+//! - **[`dedup`]**: Provides a deduplication stage that drops transactions and
+//!   account updates delivered more than once, for example by redundant
+//!   datasources streaming the same data.
+//!
 //! - **[`deserialize`]**: Contains utilities for data deserialization,
 //!   including helper functions for parsing Solana transactions and other
 //!   binary data formats.
@@ -33,16 +72,36 @@
 //! - **[`error`]**: Defines error types used throughout the crate, providing
 //!   consistent error handling for the framework.
 //!
+//! - **[`error_policy`]**: Defines the per-pipe `ErrorPolicy` deciding whether
+//!   a failed update is skipped, retried with backoff or halts the pipeline.
+//!
+//! - **[`event`]**: Provides event pipes, which decode the events programs
+//!   emit through self-CPI instructions or `Program data:` logs and hand them
+//!   to processors with the instruction that emitted them.
+//!
 //! - **[`filter`]**: Provides a flexible filtering system that allows selective
 //!   processing of updates based on various criteria such as datasource ID,
 //!   update content, or custom logic. Filters can be applied to different
 //!   types of updates (accounts, instructions, transactions, account deletions,
 //!   and block details) to control which updates are processed by specific pipes.
 //!
+//! - **[`handle`]**: Provides the `PipelineHandle` returned by
+//!   `Pipeline::spawn`, which adds and removes pipes and datasources while the
+//!   pipeline runs and lists its topology.
+//!
+//! - **[`health`]**: Tracks the status of a running pipeline, such as the
+//!   state of its datasources and the time since the last processed update,
+//!   and serves liveness, readiness and status endpoints with the `health`
+//!   feature.
+//!
 //! - **[`instruction`]**: Supports instruction parsing and processing within
 //!   transactions. This module includes structures and traits for decoding and
 //!   handling transaction instructions.
 //!
+//! - **[`logs`]**: Parses the log messages of a transaction into a tree of
+//!   per-instruction logs, with the compute units, outcome and log lines of
+//!   every instruction.
+//!
 //! - **[`metrics`]**: Facilitates performance monitoring and metric recording
 //!   within the pipeline. Metrics can be customized and are recorded at each
 //!   processing stage for monitoring and debugging purposes.
@@ -61,11 +120,24 @@
 //!   in the pipeline. This module allows for the creation of custom data
 //!   processors that can be integrated into various stages of the pipeline.
 //!
+//! - **[`replay`]**: Records the updates emitted by a datasource to a file and
+//!   replays them later, in full or for a range of slots.
+//!
 //! - **[`schema`]**: Defines transaction schemas, allowing for structured
 //!   parsing and validation of transaction data based on specified rules.
 //!   Supports complex nested instruction matching for comprehensive transaction
 //!   analysis.
 //!
+//! - **[`rollback`]**: Tracks the parent chain of recent slots and notifies
+//!   processors when the updates of a dead or orphaned slot must be rolled
+//!   back.
+//!
+//! - **[`slot_status`]**: Handles slot status updates, which report when a slot
+//!   becomes confirmed, finalized or dead.
+//!
+//! - **[`supervisor`]**: Restarts datasources that fail according to a
+//!   `RestartPolicy` and reports the state of every datasource.
+//!
 //! - **[`transaction`]**: Manages transaction data, including metadata
 //!   extraction and parsing. This module supports transaction validation and
 //!   processing, enabling detailed transaction insights.
@@ -125,24 +197,47 @@
 
 pub mod account;
 pub mod account_deletion;
+pub mod account_diff;
+pub mod account_store;
 pub mod account_utils;
+pub mod balance_changes;
+pub mod batch;
+pub mod block;
 mod block_details;
+pub mod checkpoint;
 pub mod collection;
+pub mod commitment_buffer;
+pub mod compute_budget;
+#[cfg(feature = "config")]
+pub mod config;
 pub mod datasource;
+pub mod dead_letter;
+pub mod dedup;
 pub mod deserialize;
 pub mod error;
+pub mod error_policy;
+pub mod event;
 pub mod filter;
 #[cfg(feature = "graphql")]
 pub mod graphql;
+pub mod handle;
+pub mod health;
 pub mod instruction;
+pub mod logs;
+mod lru;
 pub mod metrics;
 pub mod pipeline;
 #[cfg(feature = "postgres")]
 pub mod postgres;
 pub mod processor;
+pub mod replay;
+pub mod rollback;
 pub mod schema;
+pub mod slot_status;
+pub mod supervisor;
 pub mod transaction;
 pub mod transformers;
+mod worker_pool;
 
 pub use borsh;
 #[cfg(feature = "macros")]
@@ -0,0 +1,286 @@
+//! Parses the log messages of a transaction into a tree of per-instruction
+//! logs.
+//!
+//! The runtime logs every instruction between an invoke line and a success or
+//! failure line, with the lines of the instructions it invokes nested in
+//! between:
+//!
+//! ```text
+//! Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]
+//! Program log: Instruction: Route
+//! Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]
+//! Program log: Instruction: Transfer
+//! Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 180000 compute units
+//! Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success
+//! Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 consumed 25000 of 200000 compute units
+//! Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success
+//! ```
+//!
+//! `parse_log_tree` turns these lines into one `InstructionLogs` per
+//! top-level instruction, each holding the compute units it consumed, whether
+//! it succeeded, its own log lines and the logs of its inner instructions.
+//! Use `TransactionMetadata::log_tree` for the whole transaction, or
+//! `InstructionMetadata::logs` for the instruction being processed.
+//!
+//! # Notes
+//!
+//! - Precompiles, such as the Ed25519 program, don't log anything and have no
+//!   entry in the tree.
+//! - When the runtime truncated the logs, the instructions whose logs were cut
+//!   off have an `InstructionStatus::Unknown` status.
+
+use {solana_pubkey::Pubkey, std::str::FromStr};
+
+/// The outcome of an instruction, as reported by its logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionStatus {
+    /// The instruction succeeded.
+    Success,
+    /// The instruction failed with the given error, such as
+    /// `custom program error: 0x1`.
+    Failed(String),
+    /// The logs end before the outcome of the instruction, usually because
+    /// they were truncated.
+    Unknown,
+}
+
+/// The logs of a single instruction and of the instructions it invoked.
+///
+/// # Fields
+///
+/// - `program_id`: The program that executed the instruction.
+/// - `stack_height`: The depth of the instruction, where 1 is a top-level
+///   instruction.
+/// - `compute_units_consumed`: The compute units the instruction consumed,
+///   including its inner instructions, if logged.
+/// - `compute_units_available`: The compute units that were available to the
+///   instruction when it started, if logged.
+/// - `status`: Whether the instruction succeeded.
+/// - `logs`: The lines logged by the instruction itself, such as
+///   `Program log: ...` and `Program data: ...`, excluding the invoke,
+///   compute unit and outcome lines and the lines of inner instructions.
+/// - `inner_instructions`: The logs of the instructions it invoked, in order.
+/// - `inner_instruction_log_offsets`: For each inner instruction, the number
+///   of `logs` the instruction had logged when it invoked it, which tells
+///   the order of its own lines and its inner instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionLogs {
+    pub program_id: Pubkey,
+    pub stack_height: usize,
+    pub compute_units_consumed: Option<u64>,
+    pub compute_units_available: Option<u64>,
+    pub status: InstructionStatus,
+    pub logs: Vec<String>,
+    pub inner_instructions: Vec<InstructionLogs>,
+    pub inner_instruction_log_offsets: Vec<usize>,
+}
+
+impl InstructionLogs {
+    fn new(program_id: Pubkey, stack_height: usize) -> Self {
+        Self {
+            program_id,
+            stack_height,
+            compute_units_consumed: None,
+            compute_units_available: None,
+            status: InstructionStatus::Unknown,
+            logs: Vec::new(),
+            inner_instructions: Vec::new(),
+            inner_instruction_log_offsets: Vec::new(),
+        }
+    }
+
+    /// Returns the compute units consumed by the instruction itself, without
+    /// those consumed by its inner instructions.
+    pub fn own_compute_units_consumed(&self) -> Option<u64> {
+        let inner: u64 = self
+            .inner_instructions
+            .iter()
+            .filter_map(|inner| inner.compute_units_consumed)
+            .sum();
+
+        self.compute_units_consumed
+            .map(|consumed| consumed.saturating_sub(inner))
+    }
+
+    /// Returns whether the instruction succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status == InstructionStatus::Success
+    }
+
+    /// Returns the logs of the instruction at `path`, relative to this one,
+    /// where each element is the position of an inner instruction among its
+    /// siblings.
+    pub fn get(&self, path: &[u8]) -> Option<&InstructionLogs> {
+        path.iter().try_fold(self, |logs, position| {
+            logs.inner_instructions.get(*position as usize)
+        })
+    }
+}
+
+/// A line of a transaction's logs emitted by the runtime on behalf of a
+/// program.
+enum RuntimeLog<'a> {
+    Invoke(Pubkey, usize),
+    Consumed(u64, u64),
+    Success,
+    Failed(&'a str),
+}
+
+fn parse_runtime_log(log: &str) -> Option<RuntimeLog<'_>> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, rest) = rest.split_once(' ')?;
+    // Lines logged by programs, such as `Program log: ...`, have a label
+    // ending with a colon where the runtime puts a program id.
+    if program_id.ends_with(':') {
+        return None;
+    }
+    let program_id = Pubkey::from_str(program_id).unwrap_or_default();
+
+    if let Some(height) = rest
+        .strip_prefix("invoke [")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return height
+            .parse()
+            .ok()
+            .map(|height| RuntimeLog::Invoke(program_id, height));
+    }
+
+    if let Some(rest) = rest
+        .strip_prefix("consumed ")
+        .and_then(|rest| rest.strip_suffix(" compute units"))
+    {
+        let (consumed, available) = rest.split_once(" of ")?;
+        return Some(RuntimeLog::Consumed(
+            consumed.parse().ok()?,
+            available.parse().ok()?,
+        ));
+    }
+
+    if rest == "success" {
+        return Some(RuntimeLog::Success);
+    }
+
+    rest.strip_prefix("failed: ")
+        .or_else(|| rest.strip_prefix("failed"))
+        .map(RuntimeLog::Failed)
+}
+
+/// Parses the log messages of a transaction into one `InstructionLogs` per
+/// top-level instruction that logged anything.
+pub fn parse_log_tree(logs: &[String]) -> Vec<InstructionLogs> {
+    fn finish(
+        roots: &mut Vec<InstructionLogs>,
+        stack: &mut Vec<InstructionLogs>,
+        instruction: InstructionLogs,
+    ) {
+        match stack.last_mut() {
+            Some(parent) => {
+                parent.inner_instruction_log_offsets.push(parent.logs.len());
+                parent.inner_instructions.push(instruction);
+            }
+            None => roots.push(instruction),
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<InstructionLogs> = Vec::new();
+
+    for log in logs {
+        match parse_runtime_log(log) {
+            Some(RuntimeLog::Invoke(program_id, stack_height)) => {
+                stack.push(InstructionLogs::new(program_id, stack_height));
+            }
+            Some(RuntimeLog::Consumed(consumed, available)) => {
+                if let Some(current) = stack.last_mut() {
+                    current.compute_units_consumed = Some(consumed);
+                    current.compute_units_available = Some(available);
+                }
+            }
+            Some(RuntimeLog::Success) => {
+                if let Some(mut current) = stack.pop() {
+                    current.status = InstructionStatus::Success;
+                    finish(&mut roots, &mut stack, current);
+                }
+            }
+            Some(RuntimeLog::Failed(error)) => {
+                if let Some(mut current) = stack.pop() {
+                    current.status = InstructionStatus::Failed(error.to_string());
+                    finish(&mut roots, &mut stack, current);
+                }
+            }
+            None => {
+                if let Some(current) = stack.last_mut() {
+                    current.logs.push(log.clone());
+                }
+            }
+        }
+    }
+
+    while let Some(current) = stack.pop() {
+        finish(&mut roots, &mut stack, current);
+    }
+
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    #[test]
+    fn test_parses_nested_instructions() {
+        let tree = parse_log_tree(&[
+            format!("Program {JUPITER} invoke [1]"),
+            "Program log: Instruction: Route".to_string(),
+            format!("Program {TOKEN} invoke [2]"),
+            "Program log: Instruction: Transfer".to_string(),
+            format!("Program {TOKEN} consumed 4645 of 180000 compute units"),
+            format!("Program {TOKEN} success"),
+            "Program data: AQID".to_string(),
+            format!("Program {JUPITER} consumed 25000 of 200000 compute units"),
+            format!("Program {JUPITER} success"),
+            format!("Program {TOKEN} invoke [1]"),
+            format!("Program {TOKEN} consumed 3000 of 175000 compute units"),
+            format!("Program {TOKEN} failed: custom program error: 0x1"),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+
+        let route = &tree[0];
+        assert_eq!(route.program_id.to_string(), JUPITER);
+        assert_eq!(route.compute_units_consumed, Some(25000));
+        assert_eq!(route.own_compute_units_consumed(), Some(20355));
+        assert!(route.is_success());
+        assert_eq!(
+            route.logs,
+            vec!["Program log: Instruction: Route", "Program data: AQID"]
+        );
+        assert_eq!(route.inner_instruction_log_offsets, vec![1]);
+        assert_eq!(
+            route.get(&[0]).map(|transfer| &transfer.logs),
+            Some(&vec!["Program log: Instruction: Transfer".to_string()])
+        );
+
+        assert_eq!(
+            tree[1].status,
+            InstructionStatus::Failed("custom program error: 0x1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keeps_truncated_instructions() {
+        let tree = parse_log_tree(&[
+            format!("Program {JUPITER} invoke [1]"),
+            format!("Program {TOKEN} invoke [2]"),
+            "Log truncated".to_string(),
+        ]);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].status, InstructionStatus::Unknown);
+        assert_eq!(tree[0].inner_instructions[0].logs, vec!["Log truncated"]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Provides `LruMap`, the map behind the bounded caches of the pipeline.
+//!
+//! An `LruMap` remembers the order in which its entries were last inserted, so
+//! that the owner can evict the least recently inserted entry once it grows
+//! past its bound. The bound itself is left to the owner, since
+//! `account_diff::AccountDiffPipe` caps its cache by number of accounts and
+//! `account_store::AccountStore` by their estimated size.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
+
+/// An entry of an `LruMap`, with the position of its last insertion.
+struct LruEntry<V> {
+    last_inserted: u64,
+    value: V,
+}
+
+/// A map that keeps track of the order in which its entries were last
+/// inserted.
+pub(crate) struct LruMap<K, V> {
+    clock: u64,
+    entries: HashMap<K, LruEntry<V>>,
+    recency: BTreeMap<u64, K>,
+}
+
+impl<K, V> Default for LruMap<K, V> {
+    fn default() -> Self {
+        Self {
+            clock: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash, V> LruMap<K, V> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the map is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value of a key, without changing its position.
+    pub(crate) fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    /// Inserts a value as the most recent entry, returning the previous value
+    /// of the key.
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.clock += 1;
+        self.recency.insert(self.clock, key);
+
+        let previous = self.entries.insert(
+            key,
+            LruEntry {
+                last_inserted: self.clock,
+                value,
+            },
+        )?;
+        self.recency.remove(&previous.last_inserted);
+
+        Some(previous.value)
+    }
+
+    /// Removes a key, returning its value.
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_inserted);
+
+        Some(entry.value)
+    }
+
+    /// Removes the least recently inserted entry.
+    pub(crate) fn pop_least_recent(&mut self) -> Option<(K, V)> {
+        let (_, key) = self.recency.pop_first()?;
+        let entry = self.entries.remove(&key)?;
+
+        Some((key, entry.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pops_least_recently_inserted() {
+        let mut map = LruMap::new();
+
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(1, "c"), Some("a"));
+        assert_eq!(map.insert(3, "d"), None);
+
+        assert_eq!(map.pop_least_recent(), Some((2, "b")));
+        assert_eq!(map.remove(&3), Some("d"));
+        assert_eq!(map.get(&1), Some(&"c"));
+        assert_eq!(map.pop_least_recent(), Some((1, "c")));
+        assert_eq!(map.pop_least_recent(), None);
+        assert!(map.is_empty());
+        assert!(map.recency.is_empty());
+    }
+}
@@ -40,6 +40,22 @@
 //!   on performance data.
 //! - **metrics_flush_interval**: Specifies how frequently metrics are flushed.
 //!   Defaults to 5 seconds if unset.
+//! - **deduplication**: An optional deduplication stage that drops repeated
+//!   transactions and account updates before they reach the pipes.
+//! - **commitment_buffer**: An optional buffer that holds back the updates of
+//!   each slot until it reaches a target commitment.
+//! - **checkpoint_store**: An optional `CheckpointStore` the pipeline commits
+//!   its progress to, every `checkpoint_interval` seconds (5 by default).
+//! - **flush_interval**: Specifies how frequently the processors of the pipes
+//!   are flushed. Defaults to 5 seconds if unset.
+//! - **rollback_pipes**: Pipes notified when the updates of a dead or orphaned
+//!   slot must be rolled back.
+//! - **dead_letter_sink**: An optional `DeadLetterSink` receiving the updates
+//!   that pipes failed to process, according to their `ErrorPolicy`.
+//! - **block_pipes**: Pipes receiving every transaction of a slot at once,
+//!   once its block details arrive. See the `block` module.
+//! - **account_store**: An optional `AccountStore` kept up to date with every
+//!   account update, which processors can query.
 //!
 //! ## Notes
 //!
@@ -50,9 +66,31 @@
 //! - Proper metric collection and flushing are essential for monitoring
 //!   pipeline performance, especially in production environments.
 
+use crate::account_diff::{AccountDiffPipe, AccountDiffProcessorInputType};
+use crate::account_store::AccountStore;
+use crate::batch::{BatchConfig, BatchProcessor, BatchingProcessor};
+use crate::block::{
+    BlockAssembler, BlockPipe, BlockPipes, BlockProcessorInputType, DEFAULT_BLOCK_TIMEOUT,
+    MIN_BLOCK_EXPIRY_INTERVAL,
+};
 use crate::block_details::{BlockDetailsPipe, BlockDetailsPipes};
-use crate::datasource::{BlockDetails, DatasourceId};
-use crate::filter::Filter;
+use crate::checkpoint::{CheckpointStore, CheckpointTracker, UpdatePosition};
+use crate::commitment_buffer::{CommitmentBuffer, CommitmentBufferConfig};
+use crate::datasource::{BlockDetails, DatasourceId, SlotStatusUpdate, UpdateType};
+use crate::dead_letter::{DeadLetter, DeadLetterSink};
+use crate::dedup::{DeduplicationConfig, Deduplicator};
+use crate::error_policy::ErrorPolicy;
+use crate::event::{EventDecoder, EventPipe, EventProcessorInputType};
+use crate::filter::{Filter, VOTE_PROGRAM_ID};
+use crate::handle::{
+    PipeId, PipeKind, PipelineCommand, PipelineHandle, PipelineTopology,
+    DEFAULT_COMMAND_BUFFER_SIZE,
+};
+use crate::health::PipelineStatus;
+use crate::rollback::{ForkTracker, RollbackEvent, RollbackPipe, RollbackPipes};
+use crate::slot_status::{SlotStatusPipe, SlotStatusPipes};
+use crate::supervisor::{self, DatasourceHealth, RestartPolicy};
+use crate::worker_pool::WorkerPool;
 use {
     crate::{
         account::{
@@ -61,7 +99,7 @@ use {
         account_deletion::{AccountDeletionPipe, AccountDeletionPipes},
         collection::InstructionDecoderCollection,
         datasource::{AccountDeletion, Datasource, Update},
-        error::CarbonResult,
+        error::{CarbonResult, Error},
         instruction::{
             InstructionDecoder, InstructionPipe, InstructionPipes, InstructionProcessorInputType,
             InstructionsWithMetadata, NestedInstructions,
@@ -69,12 +107,23 @@ use {
         metrics::{Metrics, MetricsCollection},
         processor::Processor,
         schema::TransactionSchema,
-        transaction::{TransactionPipe, TransactionPipes, TransactionProcessorInputType},
+        transaction::{
+            TransactionMetadata, TransactionPipe, TransactionPipes, TransactionProcessorInputType,
+        },
         transformers,
     },
     core::time,
     serde::de::DeserializeOwned,
-    std::{convert::TryInto, sync::Arc, time::Instant},
+    solana_pubkey::Pubkey,
+    std::{
+        collections::BTreeSet,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, OnceLock, RwLock,
+        },
+        time::{Duration, Instant},
+    },
+    tokio::sync::{mpsc, Mutex, MutexGuard},
     tokio_util::sync::CancellationToken,
 };
 
@@ -100,7 +149,11 @@ use {
 ///
 /// - `ProcessPending` is the default variant, enabling the pipeline to ensure
 ///   that no updates are lost during shutdown.
-#[derive(Default, PartialEq, Debug)]
+/// - A shutdown is triggered by SIGINT and, on Unix, by SIGTERM.
+/// - The time spent processing pending updates can be bounded with
+///   `PipelineBuilder::drain_timeout`.
+#[derive(Default, PartialEq, Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShutdownStrategy {
     /// Stop the whole pipeline immediately.
     Immediate,
@@ -109,6 +162,121 @@ pub enum ShutdownStrategy {
     ProcessPending,
 }
 
+/// Completes when the process receives SIGINT or, on Unix, SIGTERM, returning
+/// the name of the signal.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => "SIGINT",
+                    _ = sigterm.recv() => "SIGTERM",
+                }
+            }
+            Err(error) => {
+                log::warn!(
+                    "failed to listen for SIGTERM, only SIGINT triggers a shutdown: {error:?}"
+                );
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "SIGINT"
+    }
+}
+
+/// Defines how the pipeline executes its pipes for incoming updates.
+///
+/// `ExecutionMode` determines whether updates are processed one at a time by
+/// the pipeline's main loop or spread over a pool of worker tasks:
+///
+/// - `Sequential`: Every update is routed through all pipes before the next
+///   update is taken off the channel. This is the default behavior.
+/// - `Concurrent`: Updates are dispatched to a fixed number of workers, each
+///   with its own bounded queue. Updates are assigned to a worker by key (the
+///   pubkey for account updates and deletions, the signature for transactions
+///   and the slot for block details), so updates sharing a key are always
+///   processed in the order they were received.
+///
+/// # Notes
+///
+/// - In `Concurrent` mode every worker runs its own instance of each pipe
+///   whose processor can be forked, see `Processor::fork`, so that a slow
+///   pipe only holds up the updates of its own worker. Pipes that can't be
+///   forked are shared between the workers behind a lock, so they still see
+///   one update at a time.
+/// - No ordering is guaranteed between updates with different keys.
+/// - The depth of every worker queue is reported through the
+///   `worker_{index}_updates_queued` gauge.
+#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ExecutionMode {
+    /// Process each update through all pipes before taking the next one.
+    #[default]
+    Sequential,
+    /// Spread updates over `workers` tasks, keeping ordering per key.
+    Concurrent {
+        /// The number of worker tasks.
+        workers: usize,
+        /// The number of updates that can be queued for each worker.
+        worker_queue_size: usize,
+    },
+}
+
+/// The programs the pipes of a pipeline process, as returned by
+/// `PipelineBuilder::programs`.
+///
+/// Datasources can build their subscription filters from it, so that they
+/// only stream the transactions and accounts the pipeline needs.
+///
+/// # Fields
+///
+/// - `instruction_programs`: The programs whose instructions are decoded by
+///   the instruction, event and transaction pipes. `None` if one of these pipes
+///   processes the transactions of any program, such as a pipe whose decoder
+///   does not declare its program ids, or if block pipes are registered.
+/// - `account_owners`: The programs owning the accounts decoded by the account
+///   pipes. `None` if one of these pipes processes accounts of any owner.
+/// - `include_votes`: Whether a pipe needs vote transactions: an instruction,
+///   event or transaction pipe decodes the Vote program or has a filter
+///   letting only votes through (see `Filter::votes`), or block pipes are
+///   registered.
+///
+/// # Notes
+///
+/// - An empty set means that no pipe needs updates of that kind, so the
+///   datasource doesn't need to subscribe to them at all.
+/// - Account deletions carry no owner, so account deletion pipes are not
+///   taken into account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelinePrograms {
+    pub instruction_programs: Option<BTreeSet<Pubkey>>,
+    pub account_owners: Option<BTreeSet<Pubkey>>,
+    pub include_votes: bool,
+}
+
+/// Returns the union of the program ids of several pipes, or `None` if one of
+/// them accepts any program.
+fn union_program_ids(
+    program_ids: impl IntoIterator<Item = Vec<Pubkey>>,
+) -> Option<BTreeSet<Pubkey>> {
+    let mut union = BTreeSet::new();
+    for program_ids in program_ids {
+        if program_ids.is_empty() {
+            return None;
+        }
+        union.extend(program_ids);
+    }
+    Some(union)
+}
+
 /// The default size of the channel buffer for the pipeline.
 ///
 /// This constant defines the default number of updates that can be queued in
@@ -159,6 +327,12 @@ pub const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 1_000;
 ///   deletion events.
 /// - `block_details_pipes`: A vector of `BlockDetailsPipes` to handle
 ///   block details.
+/// - `block_pipes`: A vector of `BlockPipes` receiving every transaction of a
+///   slot at once, in block order, when its block details arrive.
+/// - `slot_status_pipes`: A vector of `SlotStatusPipes` to handle slot status
+///   updates.
+/// - `rollback_pipes`: A vector of `RollbackPipes` notified when the updates of
+///   a slot must be rolled back. If any are set, the pipeline tracks forks.
 /// - `instruction_pipes`: A vector of `InstructionPipes` for processing
 ///   instructions within transactions. These pipes work with nested
 ///   instructions and are generically defined to support varied instruction
@@ -173,6 +347,41 @@ pub const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 1_000;
 ///   used.
 /// - `channel_buffer_size`: The size of the channel buffer for the pipeline. If
 ///   not set, a default size of 10_000 will be used.
+/// - `execution_mode`: Whether updates are processed sequentially or spread
+///   over a pool of workers. See [`ExecutionMode`].
+/// - `deduplication`: An optional `DeduplicationConfig`. If set, transactions
+///   and account updates that were already received, from any datasource, are
+///   dropped before reaching the pipes.
+/// - `commitment_buffer`: An optional `CommitmentBufferConfig`. If set, the
+///   updates of each slot are held back until the slot reaches the target
+///   commitment, and discarded if the slot dies.
+/// - `checkpoint_store`: An optional `CheckpointStore` that the progress of
+///   every datasource is committed to.
+/// - `checkpoint_interval`: An optional interval, in seconds, defining how
+///   frequently checkpoints are committed. If `None`, the default interval of
+///   5 seconds is used.
+/// - `flush_interval`: An optional interval, in seconds, defining how
+///   frequently the processors of the pipes are flushed. If `None`, the
+///   default interval of 5 seconds is used.
+/// - `dead_letter_sink`: An optional `DeadLetterSink` that receives the updates
+///   pipes gave up on under their `ErrorPolicy`.
+/// - `restart_policy`: The `RestartPolicy` deciding whether and when failed
+///   datasources are restarted.
+/// - `datasource_health`: The latest state of every datasource, updated by
+///   their supervisors while the pipeline runs.
+/// - `drain_timeout`: An optional upper bound on the time spent processing
+///   pending updates after a shutdown signal with
+///   `ShutdownStrategy::ProcessPending`. If `None`, the pipeline waits until
+///   every pending update is processed.
+/// - `status`: The live `PipelineStatus` of the pipeline, used for health
+///   checks.
+/// - `block_timeout`: An optional time after which a slot whose block details
+///   never arrived is dropped by the block pipes. If `None`,
+///   `DEFAULT_BLOCK_TIMEOUT` is used.
+/// - `account_store`: An optional `AccountStore` that every account update
+///   is written to before it reaches the pipes.
+/// - `ignore_shutdown_signals`: Whether SIGINT and SIGTERM are left to the
+///   embedding application instead of shutting the pipeline down.
 ///
 /// ## Example
 ///
@@ -213,6 +422,9 @@ pub struct Pipeline {
     pub account_pipes: Vec<Box<dyn AccountPipes>>,
     pub account_deletion_pipes: Vec<Box<dyn AccountDeletionPipes>>,
     pub block_details_pipes: Vec<Box<dyn BlockDetailsPipes>>,
+    pub block_pipes: Vec<Box<dyn BlockPipes>>,
+    pub slot_status_pipes: Vec<Box<dyn SlotStatusPipes>>,
+    pub rollback_pipes: Vec<Box<dyn RollbackPipes>>,
     pub instruction_pipes: Vec<Box<dyn for<'a> InstructionPipes<'a>>>,
     pub transaction_pipes: Vec<Box<dyn for<'a> TransactionPipes<'a>>>,
     pub metrics: Arc<MetricsCollection>,
@@ -220,6 +432,20 @@ pub struct Pipeline {
     pub datasource_cancellation_token: Option<CancellationToken>,
     pub shutdown_strategy: ShutdownStrategy,
     pub channel_buffer_size: usize,
+    pub execution_mode: ExecutionMode,
+    pub deduplication: Option<DeduplicationConfig>,
+    pub commitment_buffer: Option<CommitmentBufferConfig>,
+    pub checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    pub checkpoint_interval: Option<u64>,
+    pub flush_interval: Option<u64>,
+    pub dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+    pub restart_policy: RestartPolicy,
+    pub datasource_health: Arc<DatasourceHealth>,
+    pub drain_timeout: Option<Duration>,
+    pub status: Arc<PipelineStatus>,
+    pub block_timeout: Option<Duration>,
+    pub account_store: Option<AccountStore>,
+    pub ignore_shutdown_signals: bool,
 }
 
 impl Pipeline {
@@ -259,6 +485,9 @@ impl Pipeline {
             account_pipes: Vec::new(),
             account_deletion_pipes: Vec::new(),
             block_details_pipes: Vec::new(),
+            block_pipes: Vec::new(),
+            slot_status_pipes: Vec::new(),
+            rollback_pipes: Vec::new(),
             instruction_pipes: Vec::new(),
             transaction_pipes: Vec::new(),
             metrics: MetricsCollection::default(),
@@ -266,9 +495,40 @@ impl Pipeline {
             datasource_cancellation_token: None,
             shutdown_strategy: ShutdownStrategy::default(),
             channel_buffer_size: DEFAULT_CHANNEL_BUFFER_SIZE,
+            execution_mode: ExecutionMode::default(),
+            deduplication: None,
+            commitment_buffer: None,
+            checkpoint_store: None,
+            checkpoint_interval: None,
+            flush_interval: None,
+            dead_letter_sink: None,
+            error_policy: ErrorPolicy::default(),
+            restart_policy: RestartPolicy::default(),
+            drain_timeout: None,
+            liveness_timeout: None,
+            block_timeout: None,
+            account_store: None,
+            ignore_shutdown_signals: false,
         }
     }
 
+    /// Returns the `DatasourceHealth` tracking the state of every datasource.
+    ///
+    /// The returned handle stays up to date while the pipeline runs, so it can
+    /// be taken before calling `run` and handed to a health check.
+    pub fn datasource_health(&self) -> Arc<DatasourceHealth> {
+        self.datasource_health.clone()
+    }
+
+    /// Returns the `PipelineStatus` used to check the health of the pipeline.
+    ///
+    /// Like `datasource_health`, the returned handle stays up to date while
+    /// the pipeline runs. With the `health` feature, it can be served over
+    /// HTTP with `health::server::serve`.
+    pub fn status(&self) -> Arc<PipelineStatus> {
+        self.status.clone()
+    }
+
     /// Runs the `Pipeline`, processing updates from data sources and handling
     /// metrics.
     ///
@@ -284,7 +544,8 @@ impl Pipeline {
     ///
     /// - Initializes metrics and sets up an interval for periodic metric
     ///   flushing.
-    /// - Spawns tasks for each data source to continuously consume updates.
+    /// - Spawns a supervised task for each data source to continuously consume
+    ///   updates, restarting it according to the `restart_policy` if it fails.
     /// - Processes updates according to their type (e.g., Account, Transaction,
     ///   or AccountDeletion).
     /// - Records performance metrics such as update processing times, and
@@ -296,7 +557,10 @@ impl Pipeline {
     /// - Required update types (e.g., `AccountUpdate`, `AccountDeletion`,
     ///   `Transaction`) are not provided by any data source, causing a mismatch
     ///   in expected data processing capabilities.
-    /// - A data source encounters an error while consuming updates.
+    /// - Every data source stopped and at least one of them failed permanently
+    ///   under the `restart_policy`, in which case the error of the first data
+    ///   source that failed is returned and every failed data source is
+    ///   logged.
     /// - An error occurs during metrics flushing or processing of updates.
     ///
     /// # Example
@@ -326,7 +590,69 @@ impl Pipeline {
     ///   `metrics_flush_interval`.
     /// - The `run` method operates in an infinite loop, handling updates until
     ///   a termination condition occurs.
+    /// - With `ExecutionMode::Concurrent`, updates are handed to a worker pool
+    ///   instead of being processed inline. On shutdown the workers finish
+    ///   their queued updates, unless the shutdown strategy is `Immediate`.
+    /// - Processors are initialized before any datasource starts, flushed
+    ///   every `flush_interval` seconds and shut down once all pending updates
+    ///   are processed, see the lifecycle hooks of `Processor`. If one of them
+    ///   fails to initialize, the ones already initialized are shut down again
+    ///   in reverse order.
+    /// - SIGINT and, on Unix, SIGTERM trigger a shutdown following the
+    ///   `shutdown_strategy`, bounded by the `drain_timeout` if one is set.
+    /// - With a `checkpoint_store` configured, the progress of every datasource
+    ///   is committed every `checkpoint_interval` seconds and once more after
+    ///   all pending updates have been processed.
+    /// - If a pipe with `ErrorPolicy::Halt` fails, the datasources are
+    ///   cancelled, queued updates are dropped and `run` returns
+    ///   `Error::PipelineHalted`.
     pub async fn run(&mut self) -> CarbonResult<()> {
+        self.run_with_commands(None).await
+    }
+
+    /// Runs the `Pipeline` in a new task, returning a `PipelineHandle` that
+    /// adds and removes pipes and datasources while it runs.
+    ///
+    /// The pipeline runs exactly like with `run`, whose result is returned by
+    /// `PipelineHandle::join`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::datasource::DatasourceId;
+    ///
+    /// let handle = Pipeline::builder()
+    ///     .datasource_with_id(helius_websocket, DatasourceId::new_named("helius"))
+    ///     .instruction(TestProgramDecoder, TestProgramProcessor)
+    ///     .build()?
+    ///     .spawn();
+    ///
+    /// handle
+    ///     .add_datasource(DatasourceId::new_named("backfill"), block_crawler)
+    ///     .await?;
+    ///
+    /// handle.join().await?;
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - This method must be called within a Tokio runtime.
+    /// - See the `handle` module for how pipes are identified and when added
+    ///   or removed pipes start or stop receiving updates.
+    pub fn spawn(mut self) -> PipelineHandle {
+        log::trace!("spawn(self)");
+
+        let (command_sender, command_receiver) = mpsc::channel(DEFAULT_COMMAND_BUFFER_SIZE);
+        let task =
+            tokio::spawn(async move { self.run_with_commands(Some(command_receiver)).await });
+
+        PipelineHandle::new(command_sender, task)
+    }
+
+    async fn run_with_commands(
+        &mut self,
+        mut commands: Option<mpsc::Receiver<PipelineCommand>>,
+    ) -> CarbonResult<()> {
         log::info!("starting pipeline. num_datasources: {}, num_metrics: {}, num_account_pipes: {}, num_account_deletion_pipes: {}, num_instruction_pipes: {}, num_transaction_pipes: {}",
             self.datasources.len(),
             self.metrics.metrics.len(),
@@ -339,6 +665,16 @@ impl Pipeline {
         log::trace!("run(self)");
 
         self.metrics.initialize_metrics().await?;
+
+        let pipes = PipeSet::take_from(self);
+        if let Err(error) = pipes.init(&self.metrics).await {
+            log::error!("failed to initialize the pipeline: {error:?}");
+            pipes.restore_into(self);
+            return Err(error);
+        }
+        let pipes = Arc::new(pipes);
+        self.status.started();
+
         let (update_sender, mut update_receiver) =
             tokio::sync::mpsc::channel::<(Update, DatasourceId)>(self.channel_buffer_size);
 
@@ -347,106 +683,265 @@ impl Pipeline {
             .clone()
             .unwrap_or_default();
 
-        for datasource in &self.datasources {
-            let datasource_cancellation_token_clone = datasource_cancellation_token.clone();
-            let sender_clone = update_sender.clone();
-            let datasource_clone = Arc::clone(&datasource.1);
-            let datasource_id = datasource.0.clone();
-            let metrics_collection = self.metrics.clone();
-
-            tokio::spawn(async move {
-                if let Err(e) = datasource_clone
-                    .consume(
-                        datasource_id,
-                        sender_clone,
-                        datasource_cancellation_token_clone,
-                        metrics_collection,
-                    )
-                    .await
-                {
-                    log::error!("error consuming datasource: {e:?}");
-                }
-            });
+        let mut datasource_tokens = Vec::with_capacity(self.datasources.len());
+        for (datasource_id, datasource) in &self.datasources {
+            let cancellation_token = datasource_cancellation_token.child_token();
+            self.spawn_datasource(
+                datasource_id.clone(),
+                Arc::clone(datasource),
+                update_sender.clone(),
+                cancellation_token.clone(),
+                &pipes,
+            );
+            datasource_tokens.push((datasource_id.clone(), cancellation_token));
+        }
+
+        // Only datasources keep the channel open, so that it closes once the
+        // last of them stops. Datasources added at runtime get a new sender.
+        // While a `PipelineHandle` can still add datasources, the pipeline
+        // keeps a sender itself, so that it keeps running without any.
+        let weak_update_sender = update_sender.downgrade();
+        let mut handle_update_sender = commands.is_some().then_some(update_sender);
+
+        if self.commitment_buffer.is_some()
+            && !self
+                .datasources
+                .iter()
+                .any(|(_, datasource)| datasource.update_types().contains(&UpdateType::SlotStatus))
+        {
+            log::warn!("commitment buffer is enabled, but no datasource provides slot status updates. Buffered updates will never be released.");
+        }
+
+        if !pipes.rollback_pipes.is_empty()
+            && !self
+                .datasources
+                .iter()
+                .any(|(_, datasource)| datasource.update_types().contains(&UpdateType::SlotStatus))
+        {
+            log::warn!("rollback pipes are set, but no datasource provides slot status updates. No rollback will be emitted.");
+        }
+
+        if !pipes.block_pipes.is_empty()
+            && !self.datasources.iter().any(|(_, datasource)| {
+                datasource
+                    .update_types()
+                    .contains(&UpdateType::BlockDetails)
+            })
+        {
+            log::warn!("block pipes are set, but no datasource provides block details. No block will be completed.");
         }
 
-        drop(update_sender);
+        let mut fork_tracker = (!pipes.rollback_pipes.is_empty()).then(ForkTracker::new);
+
+        let worker_pool = match self.execution_mode {
+            ExecutionMode::Sequential => None,
+            ExecutionMode::Concurrent {
+                workers,
+                worker_queue_size,
+            } => Some(WorkerPool::spawn(
+                workers,
+                worker_queue_size,
+                pipes.clone(),
+                self.metrics.clone(),
+            )),
+        };
 
         let mut interval = tokio::time::interval(time::Duration::from_secs(
             self.metrics_flush_interval.unwrap_or(5),
         ));
 
+        let mut checkpoint_interval = tokio::time::interval(time::Duration::from_secs(
+            self.checkpoint_interval.unwrap_or(5),
+        ));
+
+        let mut flush_interval =
+            tokio::time::interval(time::Duration::from_secs(self.flush_interval.unwrap_or(5)));
+
+        // Pending blocks are checked four times per block timeout, so that a
+        // block is dropped at most a quarter of the timeout after it expired.
+        let mut block_expiry_interval = tokio::time::interval(
+            (self.block_timeout.unwrap_or(DEFAULT_BLOCK_TIMEOUT) / 4)
+                .max(MIN_BLOCK_EXPIRY_INTERVAL),
+        );
+        let mut all_updates_received = false;
+
+        let mut deduplicator = self.deduplication.map(Deduplicator::new);
+        let mut commitment_buffer = self.commitment_buffer.map(CommitmentBuffer::new);
+
+        let shutdown_signal = shutdown_signal();
+        tokio::pin!(shutdown_signal);
+        let mut shutdown_requested = false;
+        let mut drain_deadline: Option<tokio::time::Instant> = None;
+        let mut drain_timed_out = false;
+
         loop {
+            if pipes.halted.is_cancelled() {
+                break;
+            }
+
             tokio::select! {
+                _ = pipes.halted.cancelled() => {
+                    break;
+                }
                 _ = datasource_cancellation_token.cancelled() => {
                     log::trace!("datasource cancellation token cancelled, shutting down.");
-                    self.metrics.flush_metrics().await?;
-                    self.metrics.shutdown_metrics().await?;
                     break;
                 }
-                _ = tokio::signal::ctrl_c() => {
-                    log::trace!("received SIGINT, shutting down.");
+                signal = &mut shutdown_signal, if !shutdown_requested && !self.ignore_shutdown_signals => {
+                    log::trace!("received {signal}, shutting down.");
+                    shutdown_requested = true;
                     datasource_cancellation_token.cancel();
 
                     if self.shutdown_strategy == ShutdownStrategy::Immediate {
                         log::info!("shutting down the pipeline immediately.");
-                        self.metrics.flush_metrics().await?;
-                        self.metrics.shutdown_metrics().await?;
                         break;
                     } else {
                         log::info!("shutting down the pipeline after processing pending updates.");
+                        drain_deadline = self
+                            .drain_timeout
+                            .map(|drain_timeout| tokio::time::Instant::now() + drain_timeout);
                     }
                 }
+                _ = tokio::time::sleep_until(drain_deadline.unwrap_or_else(tokio::time::Instant::now)), if drain_deadline.is_some() => {
+                    log::warn!("drain timeout elapsed, dropping pending updates.");
+                    drain_timed_out = true;
+                    break;
+                }
                 _ = interval.tick() => {
+                    if let Some(account_store) = &pipes.account_store {
+                        account_store.record_metrics(&self.metrics).await?;
+                    }
                     self.metrics.flush_metrics().await?;
                 }
+                _ = flush_interval.tick() => {
+                    pipes.flush(&self.metrics).await?;
+                }
+                _ = block_expiry_interval.tick(), if !pipes.block_pipes.is_empty() => {
+                    pipes.expire_blocks(&self.metrics).await?;
+                }
+                command = next_command(&mut commands) => {
+                    match command {
+                        Some(command) => {
+                            self.apply_command(
+                                command,
+                                &pipes,
+                                &weak_update_sender,
+                                &datasource_cancellation_token,
+                                &mut datasource_tokens,
+                            )
+                            .await;
+                        }
+                        None => {
+                            commands = None;
+                            drop(handle_update_sender.take());
+                        }
+                    }
+                }
+                _ = checkpoint_interval.tick(), if pipes.checkpoints.is_some() => {
+                    if let Some(checkpoints) = &pipes.checkpoints {
+                        pipes.flush(&self.metrics).await?;
+                        checkpoints.commit(false).await;
+                    }
+                }
                 update = update_receiver.recv() => {
                     match update {
                         Some((update, datasource_id)) => {
                             self
                                 .metrics.increment_counter("updates_received", 1)
                                 .await?;
+                            self.status
+                                .observe_slot(&datasource_id, UpdatePosition::of(&update).slot());
 
-                            let start = Instant::now();
-                            let process_result = self.process(update.clone(), datasource_id.clone()).await;
-                            let time_taken_nanoseconds = start.elapsed().as_nanos();
-                            let time_taken_milliseconds = time_taken_nanoseconds / 1_000_000;
+                            if deduplicator
+                                .as_mut()
+                                .is_some_and(|deduplicator| deduplicator.is_duplicate(&update))
+                            {
+                                log::trace!("dropping duplicate update from {datasource_id:?}");
 
-                            self
-                                .metrics
-                                .record_histogram("updates_process_time_nanoseconds", time_taken_nanoseconds as f64)
-                                .await?;
+                                if let Some(checkpoints) = &pipes.checkpoints {
+                                    checkpoints.skipped(&datasource_id, UpdatePosition::of(&update));
+                                }
 
-                            self
-                                .metrics
-                                .record_histogram("updates_process_time_milliseconds", time_taken_milliseconds as f64)
-                                .await?;
+                                self.metrics.increment_counter("updates_duplicate", 1).await?;
+                                self
+                                    .metrics
+                                    .increment_counter(
+                                        &format!("updates_duplicate.{}", datasource_id.metric_name()),
+                                        1,
+                                    )
+                                    .await?;
 
-                            match process_result {
-                                Ok(_) => {
-                                    self
-                                        .metrics.increment_counter("updates_successful", 1)
-                                        .await?;
+                                continue;
+                            }
+
+                            // Registered before the update may be held back by
+                            // the commitment buffer, so that no checkpoint is
+                            // committed past it until it is processed.
+                            if let Some(checkpoints) = &pipes.checkpoints {
+                                checkpoints.received(&datasource_id, UpdatePosition::of(&update));
+                            }
+
+                            if let Some(fork_tracker) = fork_tracker.as_mut() {
+                                for rollback in fork_tracker.observe(&update) {
+                                    if let Some(worker_pool) = &worker_pool {
+                                        worker_pool.barrier().await?;
+                                    }
 
-                                    log::trace!("processed update")
+                                    pipes.handle_rollback(rollback, &self.metrics).await?;
                                 }
-                                Err(error) => {
-                                    log::error!("error processing update ({update:?}): {error:?}");
-                                    self.metrics.increment_counter("updates_failed", 1).await?;
+                            }
+
+                            match commitment_buffer {
+                                Some(ref mut commitment_buffer) => {
+                                    let output = commitment_buffer.push(update, datasource_id);
+
+                                    for (update, datasource_id) in output.ready {
+                                        if let Some(fork_tracker) = fork_tracker.as_mut() {
+                                            fork_tracker.delivered(&update);
+                                        }
+
+                                        pipes
+                                            .route(update, datasource_id, worker_pool.as_ref(), &self.metrics)
+                                            .await?;
+                                    }
+
+                                    if !output.discarded.is_empty() {
+                                        if let Some(checkpoints) = &pipes.checkpoints {
+                                            for (update, datasource_id) in &output.discarded {
+                                                checkpoints.processed(datasource_id, UpdatePosition::of(update));
+                                            }
+                                        }
+
+                                        self
+                                            .metrics
+                                            .increment_counter("updates_discarded", output.discarded.len() as u64)
+                                            .await?;
+                                    }
+
+                                    self
+                                        .metrics
+                                        .update_gauge("updates_buffered", commitment_buffer.buffered() as f64)
+                                        .await?;
                                 }
-                            };
+                                None => {
+                                    if let Some(fork_tracker) = fork_tracker.as_mut() {
+                                        fork_tracker.delivered(&update);
+                                    }
 
-                            self
-                                .metrics.increment_counter("updates_processed", 1)
-                                .await?;
+                                    pipes
+                                        .route(update, datasource_id, worker_pool.as_ref(), &self.metrics)
+                                        .await?;
+                                }
+                            }
 
+                            self.status.set_updates_queued(update_receiver.len());
                             self
                                 .metrics.update_gauge("updates_queued", update_receiver.len() as f64)
                                 .await?;
                         }
                         None => {
                             log::info!("update_receiver closed, shutting down.");
-                            self.metrics.flush_metrics().await?;
-                            self.metrics.shutdown_metrics().await?;
+                            all_updates_received = true;
                             break;
                         }
                     }
@@ -454,297 +949,2169 @@ impl Pipeline {
             }
         }
 
+        drop(commands);
+
+        if pipes.halted.is_cancelled() {
+            datasource_cancellation_token.cancel();
+        }
+
+        if let Some(worker_pool) = worker_pool {
+            if self.shutdown_strategy == ShutdownStrategy::Immediate
+                || pipes.halted.is_cancelled()
+                || drain_timed_out
+            {
+                worker_pool.abort().await;
+            } else {
+                worker_pool.drain(drain_deadline).await;
+            }
+        }
+
+        pipes.flush(&self.metrics).await?;
+        pipes.shutdown(&self.metrics).await?;
+
+        let halt_reason = pipes.halt_reason().map(str::to_string);
+        let failed_datasources = if all_updates_received {
+            self.datasource_health.failed()
+        } else {
+            Vec::new()
+        };
+
+        if let Some(checkpoints) = &pipes.checkpoints {
+            checkpoints
+                .commit(
+                    all_updates_received && halt_reason.is_none() && failed_datasources.is_empty(),
+                )
+                .await;
+        }
+
+        self.status.stopped();
+
+        self.metrics.flush_metrics().await?;
+        self.metrics.shutdown_metrics().await?;
+
+        match Arc::try_unwrap(pipes) {
+            Ok(pipes) => pipes.restore_into(self),
+            Err(_) => log::warn!("pipes are still shared after shutdown, not restoring them."),
+        }
+
+        if let Some(halt_reason) = halt_reason {
+            log::error!("pipeline halted: {halt_reason}");
+            return Err(Error::PipelineHalted(halt_reason));
+        }
+
+        if !failed_datasources.is_empty() {
+            let failures = failed_datasources
+                .iter()
+                .map(|(id, error)| format!("{}: {error}", id.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            log::error!(
+                "datasources failed permanently, stopping the pipeline. failed: {failures}"
+            );
+
+            return Err(match self.datasource_health.take_first_error() {
+                Some((_, error)) => error,
+                None => Error::FailedToConsumeDatasource(failures),
+            });
+        }
+
         log::info!("pipeline shutdown complete.");
 
         Ok(())
     }
 
-    /// Processes a single update and routes it through the appropriate pipeline
-    /// stages.
-    ///
-    /// The `process` method takes an `Update` and determines its type, then
-    /// routes it through the corresponding pipes for handling account
-    /// updates, transactions, or account deletions. It also records metrics
-    /// for processed updates, providing insights into the processing
-    /// workload and performance.
-    ///
-    /// ## Functionality
-    ///
-    /// - **Account Updates**: Passes account updates through the
-    ///   `account_pipes`. Each pipe processes the account metadata and the
-    ///   updated account state.
-    /// - **Transaction Updates**: Extracts transaction metadata and
-    ///   instructions, nests them if needed, and routes them through
-    ///   `instruction_pipes` and `transaction_pipes`.
-    /// - **Account Deletions**: Sends account deletion events through the
-    ///   `account_deletion_pipes`.
-    ///
-    /// The method also updates metrics counters for each type of update,
-    /// tracking how many updates have been processed in each category.
-    ///
-    /// # Parameters
-    ///
-    /// - `update`: An `Update` variant representing the type of data received.
-    ///   This can be an `Account`, `Transaction`, or `AccountDeletion`, each
-    ///   triggering different processing logic within the pipeline.
-    /// - `datasource_id`: The ID of the datasource that produced this update.
-    ///   This is used by filters to determine whether the update should be
-    ///   processed by specific pipes.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `CarbonResult<()>`, indicating `Ok(())` on successful
-    /// processing or an error if processing fails at any stage.
-    ///
-    /// # Notes
-    ///
-    /// - This method is asynchronous and should be awaited within a Tokio
-    ///   runtime.
-    /// - Each type of update (account, transaction, account deletion) requires
-    ///   its own set of pipes, so ensure that appropriate pipes are configured
-    ///   based on the data types expected from the data sources.
-    /// - Metrics are recorded after each successful processing stage to track
-    ///   processing volumes and identify potential bottlenecks in real-time.
-    /// - Filters are applied to each pipe before processing, allowing for
-    ///   selective update processing based on datasource ID and other criteria.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if any of the pipes fail during processing, or if an
-    /// issue arises while incrementing counters or updating metrics. Handle
-    /// errors gracefully to ensure continuous pipeline operation.
-    async fn process(&mut self, update: Update, datasource_id: DatasourceId) -> CarbonResult<()> {
-        log::trace!("process(self, update: {update:?}, datasource_id: {datasource_id:?})");
-        match update {
-            Update::Account(account_update) => {
-                let account_metadata = AccountMetadata {
-                    slot: account_update.slot,
-                    pubkey: account_update.pubkey,
-                    transaction_signature: account_update.transaction_signature,
-                };
-
-                for pipe in self.account_pipes.iter_mut() {
-                    if pipe.filters().iter().all(|filter| {
-                        filter.filter_account(
-                            &datasource_id,
-                            &account_metadata,
-                            &account_update.account,
-                        )
-                    }) {
-                        pipe.run(
-                            (account_metadata.clone(), account_update.account.clone()),
-                            self.metrics.clone(),
-                        )
-                        .await?;
-                    }
-                }
-
-                self.metrics
-                    .increment_counter("account_updates_processed", 1)
-                    .await?;
-            }
-            Update::Transaction(transaction_update) => {
-                let transaction_metadata = Arc::new((*transaction_update).clone().try_into()?);
+    /// Starts consuming a datasource in a supervised task, handing it its
+    /// `CursorHandle` first if the pipeline has a checkpoint store.
+    fn spawn_datasource(
+        &self,
+        datasource_id: DatasourceId,
+        datasource: Arc<dyn Datasource + Send + Sync>,
+        update_sender: mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        pipes: &PipeSet,
+    ) {
+        let generation = self.datasource_health.register(&datasource_id);
 
-                let instructions_with_metadata: InstructionsWithMetadata =
-                    transformers::extract_instructions_with_metadata(
-                        &transaction_metadata,
-                        &transaction_update,
-                    )?;
+        if let Some(checkpoints) = &pipes.checkpoints {
+            datasource.set_cursor_handle(checkpoints.cursor_handle(&datasource_id));
+        }
 
-                let nested_instructions: NestedInstructions = instructions_with_metadata.into();
+        tokio::spawn(supervisor::supervise(
+            datasource_id,
+            generation,
+            datasource,
+            update_sender,
+            cancellation_token,
+            self.metrics.clone(),
+            self.restart_policy,
+            self.datasource_health.clone(),
+        ));
+    }
 
-                for pipe in self.instruction_pipes.iter_mut() {
-                    for nested_instruction in nested_instructions.iter() {
-                        if pipe.filters().iter().all(|filter| {
-                            filter.filter_instruction(&datasource_id, nested_instruction)
-                        }) {
-                            pipe.run(nested_instruction, self.metrics.clone()).await?;
-                        }
+    /// Applies a command received through a `PipelineHandle`, replying with
+    /// its result.
+    async fn apply_command(
+        &mut self,
+        command: PipelineCommand,
+        pipes: &PipeSet,
+        update_sender: &mpsc::WeakSender<(Update, DatasourceId)>,
+        datasource_cancellation_token: &CancellationToken,
+        datasource_tokens: &mut Vec<(DatasourceId, CancellationToken)>,
+    ) {
+        macro_rules! add_to {
+            ($list:expr, $pipe:expr) => {{
+                let mut instances = $list.instances($pipe);
+                let mut initialized = Ok(());
+                for (worker, instance) in instances.iter_mut().enumerate() {
+                    initialized = instance
+                        .init(self.metrics.clone())
+                        .await
+                        .map_err(|error| (worker, error));
+                    if initialized.is_err() {
+                        break;
                     }
                 }
-
-                for pipe in self.transaction_pipes.iter_mut() {
-                    if pipe.filters().iter().all(|filter| {
-                        filter.filter_transaction(
-                            &datasource_id,
-                            &transaction_metadata,
-                            &nested_instructions,
-                        )
-                    }) {
-                        pipe.run(
-                            transaction_metadata.clone(),
-                            &nested_instructions,
-                            self.metrics.clone(),
-                        )
-                        .await?;
+                if let Err((failed_worker, _)) = initialized {
+                    for instance in instances[..failed_worker].iter_mut().rev() {
+                        if let Err(error) = instance.shutdown(self.metrics.clone()).await {
+                            log::error!(
+                                "failed to shut down {} pipe: {error:?}",
+                                $list.kind.as_str()
+                            );
+                        }
                     }
                 }
-
-                self.metrics
-                    .increment_counter("transaction_updates_processed", 1)
-                    .await?;
-            }
-            Update::AccountDeletion(account_deletion) => {
-                for pipe in self.account_deletion_pipes.iter_mut() {
-                    if pipe.filters().iter().all(|filter| {
-                        filter.filter_account_deletion(&datasource_id, &account_deletion)
-                    }) {
-                        pipe.run(account_deletion.clone(), self.metrics.clone())
-                            .await?;
+                match initialized.map_err(|(_, error)| error) {
+                    Ok(()) => {
+                        let pipe_id = $list.push(instances);
+                        log::info!("added {pipe_id} to the pipeline.");
+                        Ok(pipe_id)
                     }
+                    Err(error) => Err(Error::Custom(format!(
+                        "failed to initialize {} pipe: {error}",
+                        $list.kind.as_str()
+                    ))),
                 }
+            }};
+        }
 
-                self.metrics
-                    .increment_counter("account_deletions_processed", 1)
-                    .await?;
+        match command {
+            PipelineCommand::AddAccountPipe(pipe, reply) => {
+                let _ = reply.send(add_to!(pipes.account_pipes, pipe));
             }
-            Update::BlockDetails(block_details) => {
-                for pipe in self.block_details_pipes.iter_mut() {
-                    if pipe
-                        .filters()
-                        .iter()
-                        .all(|filter| filter.filter_block_details(&datasource_id, &block_details))
-                    {
-                        pipe.run(block_details.clone(), self.metrics.clone())
-                            .await?;
-                    }
-                }
-
-                self.metrics
-                    .increment_counter("block_details_processed", 1)
-                    .await?;
+            PipelineCommand::AddInstructionPipe(pipe, reply) => {
+                let _ = reply.send(add_to!(pipes.instruction_pipes, pipe));
             }
-        };
-
-        Ok(())
+            PipelineCommand::AddTransactionPipe(pipe, reply) => {
+                let _ = reply.send(add_to!(pipes.transaction_pipes, pipe));
+            }
+            PipelineCommand::RemovePipe(pipe_id, reply) => {
+                let _ = reply.send(pipes.remove(pipe_id, &self.metrics).await);
+            }
+            PipelineCommand::AddDatasource(datasource_id, datasource, reply) => {
+                let result = if datasource_cancellation_token.is_cancelled() {
+                    Err(Error::Custom("pipeline is shutting down".to_string()))
+                } else if self.datasources.iter().any(|(id, _)| *id == datasource_id) {
+                    Err(Error::Custom(format!(
+                        "datasource {} already exists",
+                        datasource_id.as_str()
+                    )))
+                } else if let Some(sender) = update_sender.upgrade() {
+                    let cancellation_token = datasource_cancellation_token.child_token();
+                    self.spawn_datasource(
+                        datasource_id.clone(),
+                        datasource.clone(),
+                        sender,
+                        cancellation_token.clone(),
+                        pipes,
+                    );
+                    self.datasources.push((datasource_id.clone(), datasource));
+                    datasource_tokens.push((datasource_id.clone(), cancellation_token));
+                    log::info!("added datasource {datasource_id:?} to the pipeline.");
+                    Ok(())
+                } else {
+                    Err(Error::Custom("pipeline is shutting down".to_string()))
+                };
+                let _ = reply.send(result);
+            }
+            PipelineCommand::RemoveDatasource(datasource_id, reply) => {
+                let result = match datasource_tokens
+                    .iter()
+                    .position(|(id, _)| *id == datasource_id)
+                {
+                    Some(position) => {
+                        let (_, cancellation_token) = datasource_tokens.remove(position);
+                        cancellation_token.cancel();
+                        self.datasources.retain(|(id, _)| *id != datasource_id);
+                        self.datasource_health.remove(&datasource_id);
+                        log::info!("removed datasource {datasource_id:?} from the pipeline.");
+                        Ok(())
+                    }
+                    None => Err(Error::Custom(format!(
+                        "unknown datasource {}",
+                        datasource_id.as_str()
+                    ))),
+                };
+                let _ = reply.send(result);
+            }
+            PipelineCommand::Topology(reply) => {
+                let _ = reply.send(PipelineTopology {
+                    datasources: self.datasource_health.statuses(),
+                    pipes: pipes.ids(),
+                });
+            }
+        }
     }
 }
 
-/// A builder for constructing a `Pipeline` instance with customized data
-/// sources, processing pipes, and metrics.
-///
-/// The `PipelineBuilder` struct offers a flexible way to assemble a `Pipeline`
-/// by allowing configuration of its components, such as data sources, account
-/// and transaction pipes, deletion handling, and metrics. Using the builder
-/// pattern, you can add the desired elements incrementally and then finalize
-/// with a call to `build`.
-///
-/// ## Overview
-///
-/// The `PipelineBuilder` supports the following components:
-/// - **Datasources**: Sources of data updates, such as account information and
-///   transactions.
-/// - **Account Pipes**: For processing account updates from data sources.
-/// - **Account Deletion Pipes**: For handling account deletion updates.
-/// - **Instruction Pipes**: For handling instructions associated with
-///   transactions.
-/// - **Transaction Pipes**: For handling full transaction data.
-/// - **Metrics**: Collects and reports performance data, such as update
-///   processing times.
-/// - **Metrics Flush Interval**: Optional interval defining how often to flush
-///   metrics data.
-///
-/// Each component can be added through method chaining, enhancing code
-/// readability and maintainability.
-///
-/// # Example
-///
-/// ```ignore
-/// use std::sync::Arc;
+/// Waits for the next command of a `PipelineHandle`, or forever if the
+/// pipeline has no handle.
+async fn next_command(
+    commands: &mut Option<mpsc::Receiver<PipelineCommand>>,
+) -> Option<PipelineCommand> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// A pipe of a running `Pipeline`, with its `PipeId`.
 ///
-/// carbon_core::pipeline::Pipeline::builder()
-/// .datasource(transaction_crawler)
-/// .metrics(Arc::new(LogMetrics::new()))
-/// .metrics(Arc::new(PrometheusMetrics::new()))
-/// .instruction(
-///    TestProgramDecoder,
-///    TestProgramProcessor
-/// );
-/// // ...
-/// ```
+/// A pipe that can be forked has one instance per worker, each behind its own
+/// lock. Otherwise its single instance is shared by every worker.
+pub(crate) struct PipeEntry<P: ?Sized> {
+    id: PipeId,
+    instances: Vec<Mutex<Box<P>>>,
+    /// Set once the pipe was removed, so that updates dispatched before its
+    /// removal no longer reach it.
+    removed: AtomicBool,
+}
+
+impl<P: ?Sized> PipeEntry<P> {
+    /// Locks the instance of the pipe used by `worker`, or returns `None` if
+    /// the pipe was removed in the meantime.
+    async fn lock(&self, worker: usize) -> Option<MutexGuard<'_, Box<P>>> {
+        let pipe = self.instances[worker % self.instances.len()].lock().await;
+        (!self.removed.load(Ordering::Acquire)).then_some(pipe)
+    }
+}
+
+/// The pipes of one kind in a `PipeSet`.
 ///
-/// # Fields
+/// Updates are processed against a snapshot of the list, so that pipes can be
+/// added or removed while other updates are in flight without blocking them.
+/// Every pipe gets the next free index of its kind, which is never reused.
+pub(crate) struct PipeList<P: ?Sized> {
+    kind: PipeKind,
+    entries: RwLock<Arc<Vec<Arc<PipeEntry<P>>>>>,
+    next_index: AtomicUsize,
+    workers: usize,
+    fork: fn(&P) -> Option<Box<P>>,
+}
+
+impl<P: ?Sized> PipeList<P> {
+    fn new(
+        kind: PipeKind,
+        pipes: Vec<Box<P>>,
+        workers: usize,
+        fork: fn(&P) -> Option<Box<P>>,
+    ) -> Self {
+        let list = Self {
+            kind,
+            entries: RwLock::new(Arc::new(Vec::with_capacity(pipes.len()))),
+            next_index: AtomicUsize::new(0),
+            workers,
+            fork,
+        };
+
+        for pipe in pipes {
+            list.push(list.instances(pipe));
+        }
+
+        list
+    }
+
+    /// Returns the instances of a pipe: the pipe itself, followed by a fork
+    /// for every other worker, or only the pipe if it can't be forked.
+    fn instances(&self, pipe: Box<P>) -> Vec<Box<P>> {
+        let forks: Option<Vec<Box<P>>> = (1..self.workers).map(|_| (self.fork)(&pipe)).collect();
+
+        match forks {
+            Some(forks) => std::iter::once(pipe).chain(forks).collect(),
+            None => vec![pipe],
+        }
+    }
+
+    /// Returns the pipes of the list at the time of the call.
+    fn snapshot(&self) -> Arc<Vec<Arc<PipeEntry<P>>>> {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Appends a pipe, given its instances, returning its `PipeId`.
+    fn push(&self, instances: Vec<Box<P>>) -> PipeId {
+        let id = PipeId {
+            kind: self.kind,
+            index: self.next_index.fetch_add(1, Ordering::Relaxed),
+        };
+
+        let mut entries = self
+            .entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut updated = Vec::clone(&entries);
+        updated.push(Arc::new(PipeEntry {
+            id,
+            instances: instances.into_iter().map(Mutex::new).collect(),
+            removed: AtomicBool::new(false),
+        }));
+        *entries = Arc::new(updated);
+
+        id
+    }
+
+    /// Removes a pipe, waiting for the updates its instances are processing,
+    /// if any.
+    ///
+    /// The returned entry is no longer reachable by updates, so the caller
+    /// can flush and shut the pipe down.
+    async fn remove(&self, pipe_id: &PipeId) -> Option<Arc<PipeEntry<P>>> {
+        let entry = {
+            let mut entries = self
+                .entries
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let position = entries.iter().position(|entry| entry.id == *pipe_id)?;
+            let mut updated = Vec::clone(&entries);
+            let entry = updated.remove(position);
+            *entries = Arc::new(updated);
+            entry
+        };
+
+        let mut instances = Vec::with_capacity(entry.instances.len());
+        for instance in &entry.instances {
+            instances.push(instance.lock().await);
+        }
+        entry.removed.store(true, Ordering::Release);
+        drop(instances);
+
+        Some(entry)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.snapshot().is_empty()
+    }
+
+    fn ids(&self) -> Vec<PipeId> {
+        self.snapshot().iter().map(|entry| entry.id).collect()
+    }
+
+    /// Returns the pipes, in the order they were added, dropping the forks
+    /// made for the workers.
+    fn into_inner(self) -> Vec<Box<P>> {
+        let entries = self
+            .entries
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        Arc::unwrap_or_clone(entries)
+            .into_iter()
+            .filter_map(|entry| match Arc::try_unwrap(entry) {
+                Ok(entry) => entry.instances.into_iter().next().map(Mutex::into_inner),
+                Err(entry) => {
+                    log::warn!("{} is still shared after shutdown, dropping it.", entry.id);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// The pipes of a running `Pipeline`.
 ///
-/// - `datasources`: A collection of `Datasource` objects wrapped in `Arc` for
-///   shared ownership across threads. Each `Datasource` provides updates to the
-///   pipeline.
-/// - `account_pipes`: A collection of `AccountPipes` to handle account updates.
-/// - `account_deletion_pipes`: A collection of `AccountDeletionPipes` for
-///   processing account deletions.
-/// - `instruction_pipes`: A collection of `InstructionPipes` to process
-///   instructions in transactions.
-/// - `transaction_pipes`: A collection of `TransactionPipes` to process full
-///   transaction data.
-/// - `metrics`: A vector of `Metrics` implementations for tracking pipeline
-///   performance.
-/// - `metrics_flush_interval`: An optional interval (in seconds) for flushing
-///   metrics data. If not set, a default flush interval will be used.
-/// - `datasource_cancellation_token`: An optional `CancellationToken` for
-///   canceling datasource. If not set, a default `CancellationToken` will be
-///   used.
-/// - `channel_buffer_size`: The size of the channel buffer for the pipeline. If
-///   not set, a default size of 10_000 will be used.
+/// While the pipeline runs, its pipes are moved into a `PipeSet` so that they
+/// can be shared with the worker pool. Every pipe that can be forked gets an
+/// instance per worker, and every instance sits behind its own lock, which
+/// lets workers make progress in parallel while each instance still processes
+/// one update at a time.
 ///
-/// # Returns
+/// The `PipeSet` also owns the pipeline's `CheckpointTracker`, if any, so that
+/// every processed update is reported regardless of which task processed it,
+/// as well as its `DeadLetterSink` and the halt state set by pipes with
+/// `ErrorPolicy::Halt`.
 ///
-/// After configuring the builder, call `build` to create a `Pipeline` instance.
-/// The builder will return a `CarbonResult<Pipeline>`, which will either
-/// contain the configured pipeline or an error if configuration failed.
+/// Pipes can be added and removed through a `PipelineHandle` while the
+/// pipeline runs, see `PipeList`.
+pub(crate) struct PipeSet {
+    account_pipes: PipeList<dyn AccountPipes>,
+    account_deletion_pipes: PipeList<dyn AccountDeletionPipes>,
+    block_details_pipes: PipeList<dyn BlockDetailsPipes>,
+    block_pipes: PipeList<dyn BlockPipes>,
+    slot_status_pipes: PipeList<dyn SlotStatusPipes>,
+    rollback_pipes: PipeList<dyn RollbackPipes>,
+    instruction_pipes: PipeList<dyn for<'a> InstructionPipes<'a>>,
+    transaction_pipes: PipeList<dyn for<'a> TransactionPipes<'a>>,
+    checkpoints: Option<CheckpointTracker>,
+    dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+    halted: CancellationToken,
+    halt_reason: OnceLock<String>,
+    status: Arc<PipelineStatus>,
+    blocks: std::sync::Mutex<BlockAssembler>,
+    account_store: Option<AccountStore>,
+}
+
+/// A pipe that gave up on an update under its `ErrorPolicy`.
+struct PipeFailure {
+    pipe: String,
+    error: Error,
+    attempts: u32,
+}
+
+/// Runs the instance of the pipe of `$entry` used by `$worker` under its
+/// `ErrorPolicy`, evaluating `$run` with `$pipe` bound to the locked instance
+/// again with backoff for as long as the policy allows.
 ///
-/// # Notes
+/// The instance is only locked for the duration of each attempt, so that
+/// other workers sharing it are not stalled while a retry backs off.
 ///
-/// - The builder pattern allows for method chaining, making it easy to
-///   incrementally add components to the `Pipeline`.
-/// - Ensure that each component matches the data and update types expected by
-///   your application.
-#[derive(Default)]
-pub struct PipelineBuilder {
-    pub datasources: Vec<(DatasourceId, Arc<dyn Datasource + Send + Sync>)>,
-    pub account_pipes: Vec<Box<dyn AccountPipes>>,
-    pub account_deletion_pipes: Vec<Box<dyn AccountDeletionPipes>>,
-    pub block_details_pipes: Vec<Box<dyn BlockDetailsPipes>>,
-    pub instruction_pipes: Vec<Box<dyn for<'a> InstructionPipes<'a>>>,
-    pub transaction_pipes: Vec<Box<dyn for<'a> TransactionPipes<'a>>>,
-    pub metrics: MetricsCollection,
-    pub metrics_flush_interval: Option<u64>,
-    pub datasource_cancellation_token: Option<CancellationToken>,
-    pub shutdown_strategy: ShutdownStrategy,
-    pub channel_buffer_size: usize,
+/// Evaluates to `Ok(())` once the pipe succeeded or gave up, in which case the
+/// failure is pushed to `$failures`, or was removed from the pipeline, and to
+/// `Err(Error::PipelineHalted)` if the policy halts the pipeline.
+macro_rules! run_with_policy {
+    ($entry:expr, $worker:expr, $failures:expr, $metrics:expr, |$pipe:ident| $run:expr) => {{
+        let name = $entry.id.to_string();
+        let mut attempts = 0;
+
+        loop {
+            let Some(mut $pipe) = $entry.lock($worker).await else {
+                break Ok(());
+            };
+            let error_policy = $pipe.error_policy();
+            attempts += 1;
+
+            let result = $run.await;
+            drop($pipe);
+
+            match result {
+                Ok(()) => break Ok(()),
+                Err(error) => match error_policy.backoff(attempts) {
+                    Some(backoff) => {
+                        log::warn!(
+                            "{name} failed (attempt {attempts}), retrying in {backoff:?}: {error:?}"
+                        );
+                        $metrics.increment_counter("pipe_retries", 1).await?;
+                        tokio::time::sleep(backoff).await;
+                    }
+                    None if error_policy == ErrorPolicy::Halt => {
+                        break Err(Error::PipelineHalted(format!("{name} failed: {error}")));
+                    }
+                    None => {
+                        $failures.push(PipeFailure {
+                            pipe: name,
+                            error,
+                            attempts,
+                        });
+                        break Ok(());
+                    }
+                },
+            }
+        }
+    }};
 }
 
-impl PipelineBuilder {
-    /// Creates a new `PipelineBuilder` with empty collections for datasources,
-    /// pipes, and metrics.
+/// Evaluates `$body` for every instance of every pipe of a `PipeSet`, with
+/// `$pipe` bound to the locked instance and `$name` to the name of the pipe,
+/// such as `instruction[0]`. With a leading `rev`, the instances are visited
+/// in reverse order.
+macro_rules! for_each_pipe {
+    ($pipes:expr, |$name:ident, $pipe:ident| $body:block) => {
+        for_each_pipe!(@kind $pipes.account_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.account_deletion_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.block_details_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.block_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.slot_status_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.rollback_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.instruction_pipes, $name, $pipe, $body);
+        for_each_pipe!(@kind $pipes.transaction_pipes, $name, $pipe, $body);
+    };
+    (rev $pipes:expr, |$name:ident, $pipe:ident| $body:block) => {
+        for_each_pipe!(@rev $pipes.transaction_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.instruction_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.rollback_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.slot_status_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.block_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.block_details_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.account_deletion_pipes, $name, $pipe, $body);
+        for_each_pipe!(@rev $pipes.account_pipes, $name, $pipe, $body);
+    };
+    (@kind $list:expr, $name:ident, $pipe:ident, $body:block) => {
+        for entry in $list.snapshot().iter() {
+            let $name = entry.id.to_string();
+            for worker in 0..entry.instances.len() {
+                let Some(mut $pipe) = entry.lock(worker).await else {
+                    break;
+                };
+                $body
+            }
+        }
+    };
+    (@rev $list:expr, $name:ident, $pipe:ident, $body:block) => {
+        for entry in $list.snapshot().iter().rev() {
+            let $name = entry.id.to_string();
+            for worker in (0..entry.instances.len()).rev() {
+                let Some(mut $pipe) = entry.lock(worker).await else {
+                    break;
+                };
+                $body
+            }
+        }
+    };
+}
+
+impl PipeSet {
+    pub(crate) fn take_from(pipeline: &mut Pipeline) -> Self {
+        let workers = match pipeline.execution_mode {
+            ExecutionMode::Sequential => 1,
+            ExecutionMode::Concurrent { workers, .. } => workers,
+        };
+
+        Self {
+            account_pipes: PipeList::new(
+                PipeKind::Account,
+                std::mem::take(&mut pipeline.account_pipes),
+                workers,
+                |pipe| pipe.fork(),
+            ),
+            account_deletion_pipes: PipeList::new(
+                PipeKind::AccountDeletion,
+                std::mem::take(&mut pipeline.account_deletion_pipes),
+                workers,
+                |pipe| pipe.fork(),
+            ),
+            block_details_pipes: PipeList::new(
+                PipeKind::BlockDetails,
+                std::mem::take(&mut pipeline.block_details_pipes),
+                workers,
+                |_| None,
+            ),
+            block_pipes: PipeList::new(
+                PipeKind::Block,
+                std::mem::take(&mut pipeline.block_pipes),
+                workers,
+                |_| None,
+            ),
+            slot_status_pipes: PipeList::new(
+                PipeKind::SlotStatus,
+                std::mem::take(&mut pipeline.slot_status_pipes),
+                workers,
+                |_| None,
+            ),
+            rollback_pipes: PipeList::new(
+                PipeKind::Rollback,
+                std::mem::take(&mut pipeline.rollback_pipes),
+                workers,
+                |_| None,
+            ),
+            instruction_pipes: PipeList::new(
+                PipeKind::Instruction,
+                std::mem::take(&mut pipeline.instruction_pipes),
+                workers,
+                |pipe| pipe.fork(),
+            ),
+            transaction_pipes: PipeList::new(
+                PipeKind::Transaction,
+                std::mem::take(&mut pipeline.transaction_pipes),
+                workers,
+                |pipe| pipe.fork(),
+            ),
+            checkpoints: pipeline
+                .checkpoint_store
+                .clone()
+                .map(CheckpointTracker::new),
+            dead_letter_sink: pipeline.dead_letter_sink.clone(),
+            halted: CancellationToken::new(),
+            halt_reason: OnceLock::new(),
+            status: pipeline.status.clone(),
+            blocks: std::sync::Mutex::new(BlockAssembler::new(
+                pipeline.block_timeout.unwrap_or(DEFAULT_BLOCK_TIMEOUT),
+            )),
+            account_store: pipeline.account_store.clone(),
+        }
+    }
+
+    fn restore_into(self, pipeline: &mut Pipeline) {
+        pipeline.account_pipes = self.account_pipes.into_inner();
+        pipeline.account_deletion_pipes = self.account_deletion_pipes.into_inner();
+        pipeline.block_details_pipes = self.block_details_pipes.into_inner();
+        pipeline.block_pipes = self.block_pipes.into_inner();
+        pipeline.slot_status_pipes = self.slot_status_pipes.into_inner();
+        pipeline.rollback_pipes = self.rollback_pipes.into_inner();
+        pipeline.instruction_pipes = self.instruction_pipes.into_inner();
+        pipeline.transaction_pipes = self.transaction_pipes.into_inner();
+    }
+
+    /// Hands an update to the worker pool, if any, or processes it in place.
+    ///
+    /// The update must already have been reported to the checkpoint tracker,
+    /// so that its slot is not checkpointed while the update is still queued.
+    /// If there are block pipes, it is also added to the block of its slot,
+    /// so that blocks are assembled in the order updates were received even
+    /// when workers process them concurrently. Likewise, the account store is
+    /// updated before any pipe processes the update.
+    async fn route(
+        &self,
+        update: Update,
+        datasource_id: DatasourceId,
+        worker_pool: Option<&WorkerPool>,
+        metrics: &Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        if !self.block_pipes.is_empty() {
+            self.blocks
+                .lock()
+                .expect("block assembler lock poisoned")
+                .observe(&update, &datasource_id);
+        }
+
+        if let Some(account_store) = &self.account_store {
+            account_store.apply(&update);
+        }
+
+        match worker_pool {
+            Some(worker_pool) => worker_pool.dispatch(update, datasource_id).await,
+            None => self.handle_update(update, datasource_id, 0, metrics).await,
+        }
+    }
+
+    /// Hands a rollback event to every rollback pipe.
+    ///
+    /// Like updates, rollback events that fail to process are logged and
+    /// counted under `slot_rollbacks_failed` rather than returned, unless the
+    /// failing pipe's `ErrorPolicy` halts the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if recording the metrics fails.
+    async fn handle_rollback(
+        &self,
+        rollback: RollbackEvent,
+        metrics: &Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        if self.halted.is_cancelled() {
+            return Ok(());
+        }
+
+        log::debug!("rolling back slot {}: {:?}", rollback.slot, rollback.reason);
+
+        let mut failures = Vec::new();
+        for entry in self.rollback_pipes.snapshot().iter() {
+            let result = run_with_policy!(entry, 0, failures, metrics, |pipe| pipe
+                .run(rollback.clone(), metrics.clone()));
+
+            if let Err(error) = result {
+                self.halt(error);
+                return Ok(());
+            }
+        }
+
+        for failure in failures {
+            log::error!(
+                "error processing rollback of slot {} in {}: {:?}",
+                rollback.slot,
+                failure.pipe,
+                failure.error
+            );
+            metrics
+                .increment_counter("slot_rollbacks_failed", 1)
+                .await?;
+        }
+
+        metrics.increment_counter("slot_rollbacks", 1).await
+    }
+
+    /// Initializes every pipe, in the order they were added.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first pipe that fails to initialize.
+    async fn init(&self, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        let mut result = Ok(());
+        let mut initialized = 0;
+        let mut instances = 0;
+
+        for_each_pipe!(self, |name, pipe| {
+            instances += 1;
+            if result.is_ok() {
+                match pipe.init(metrics.clone()).await {
+                    Ok(()) => initialized += 1,
+                    Err(error) => {
+                        result = Err(Error::Custom(format!(
+                            "failed to initialize {name}: {error}"
+                        )))
+                    }
+                }
+            }
+        });
+
+        if result.is_err() {
+            // Shut the pipes that were initialized down again, in the reverse
+            // order of their initialization.
+            for_each_pipe!(rev self, |name, pipe| {
+                instances -= 1;
+                if instances < initialized {
+                    if let Err(error) = pipe.shutdown(metrics.clone()).await {
+                        log::error!("failed to shut down {name}: {error:?}");
+                    }
+                }
+            });
+        }
+
+        result
+    }
+
+    /// Flushes every pipe, writing out the inputs their processors buffer.
+    ///
+    /// Flush failures are logged and counted under `pipe_flushes_failed`
+    /// rather than returned. The inputs of a failed flush stay buffered and
+    /// are retried with the next flush. Updates processed before the flush
+    /// only count towards a checkpoint once every pipe flushed successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if recording the metrics fails.
+    async fn flush(&self, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        let unflushed = self
+            .checkpoints
+            .as_ref()
+            .map(|checkpoints| checkpoints.take_unflushed());
+        let mut failed = false;
+
+        for_each_pipe!(self, |name, pipe| {
+            if let Err(error) = pipe.flush(metrics.clone()).await {
+                log::error!("failed to flush {name}: {error:?}");
+                metrics.increment_counter("pipe_flushes_failed", 1).await?;
+                failed = true;
+            }
+        });
+
+        if let (Some(checkpoints), Some(unflushed)) = (&self.checkpoints, unflushed) {
+            if failed {
+                checkpoints.restore(unflushed);
+            } else {
+                checkpoints.flushed(unflushed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shuts every pipe down, once the pipeline has stopped.
+    ///
+    /// Failures are logged and counted under `pipe_shutdowns_failed`, so that
+    /// one failing pipe does not keep the others from shutting down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if recording the metrics fails.
+    async fn shutdown(&self, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        for_each_pipe!(self, |name, pipe| {
+            if let Err(error) = pipe.shutdown(metrics.clone()).await {
+                log::error!("failed to shut down {name}: {error:?}");
+                metrics
+                    .increment_counter("pipe_shutdowns_failed", 1)
+                    .await?;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Drops the slots whose block details did not arrive within the block
+    /// timeout, counting them under `blocks_timed_out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if recording the metrics fails.
+    async fn expire_blocks(&self, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        let expired = self
+            .blocks
+            .lock()
+            .expect("block assembler lock poisoned")
+            .expire();
+
+        if expired > 0 {
+            log::warn!("dropped {expired} incomplete block(s) after the block timeout.");
+            metrics
+                .increment_counter("blocks_timed_out", expired as u64)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `PipeId` of every pipe, grouped by kind.
+    fn ids(&self) -> Vec<PipeId> {
+        [
+            self.account_pipes.ids(),
+            self.account_deletion_pipes.ids(),
+            self.block_details_pipes.ids(),
+            self.block_pipes.ids(),
+            self.slot_status_pipes.ids(),
+            self.rollback_pipes.ids(),
+            self.instruction_pipes.ids(),
+            self.transaction_pipes.ids(),
+        ]
+        .concat()
+    }
+
+    /// Removes a pipe while the pipeline runs, then flushes and shuts it down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no pipe has this `PipeId`, or if flushing or
+    /// shutting the pipe down fails. The pipe is removed either way.
+    async fn remove(&self, pipe_id: PipeId, metrics: &Arc<MetricsCollection>) -> CarbonResult<()> {
+        macro_rules! remove_from {
+            ($list:expr) => {{
+                let entry = $list
+                    .remove(&pipe_id)
+                    .await
+                    .ok_or_else(|| Error::Custom(format!("unknown pipe {pipe_id}")))?;
+                let mut result = Ok(());
+                for instance in &entry.instances {
+                    let mut pipe = instance.lock().await;
+                    let flushed = pipe.flush(metrics.clone()).await;
+                    let shut_down = pipe.shutdown(metrics.clone()).await;
+                    result = result.and(flushed).and(shut_down);
+                }
+                result
+            }};
+        }
+
+        let result = match pipe_id.kind {
+            PipeKind::Account => remove_from!(self.account_pipes),
+            PipeKind::AccountDeletion => remove_from!(self.account_deletion_pipes),
+            PipeKind::BlockDetails => remove_from!(self.block_details_pipes),
+            PipeKind::Block => remove_from!(self.block_pipes),
+            PipeKind::SlotStatus => remove_from!(self.slot_status_pipes),
+            PipeKind::Rollback => remove_from!(self.rollback_pipes),
+            PipeKind::Instruction => remove_from!(self.instruction_pipes),
+            PipeKind::Transaction => remove_from!(self.transaction_pipes),
+        };
+
+        log::info!("removed {pipe_id} from the pipeline.");
+
+        result.map_err(|error| Error::Custom(format!("failed to shut down {pipe_id}: {error}")))
+    }
+
+    /// Processes a single update and records its outcome in the metrics.
+    ///
+    /// Processing errors are logged and counted under `updates_failed` rather
+    /// than returned, so that one failing update does not stop the pipeline.
+    /// Pipes that gave up on the update under their `ErrorPolicy` hand it to
+    /// the dead-letter sink, if any.
+    ///
+    /// The update is only reported as processed to the checkpoint tracker if
+    /// every pipe processed it, or if every failure was stored by the
+    /// dead-letter sink. Otherwise the checkpoint is held back before its
+    /// slot, so that the update is processed again after a restart.
+    ///
+    /// If a pipe's `ErrorPolicy` halts the pipeline, the update is left
+    /// unprocessed, so that it isn't checkpointed, and every later update is
+    /// ignored.
+    ///
+    /// The update is processed by the instances of the pipes used by
+    /// `worker`, which is always `0` in sequential mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if recording the metrics fails.
+    pub(crate) async fn handle_update(
+        &self,
+        update: Update,
+        datasource_id: DatasourceId,
+        worker: usize,
+        metrics: &Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        if self.halted.is_cancelled() {
+            return Ok(());
+        }
+
+        let position = self
+            .checkpoints
+            .as_ref()
+            .map(|_| UpdatePosition::of(&update));
+
+        let start = Instant::now();
+        let process_result = self.process(&update, &datasource_id, worker, metrics).await;
+        let time_taken_nanoseconds = start.elapsed().as_nanos();
+        let time_taken_milliseconds = time_taken_nanoseconds / 1_000_000;
+
+        metrics
+            .record_histogram(
+                "updates_process_time_nanoseconds",
+                time_taken_nanoseconds as f64,
+            )
+            .await?;
+
+        metrics
+            .record_histogram(
+                "updates_process_time_milliseconds",
+                time_taken_milliseconds as f64,
+            )
+            .await?;
+
+        let acknowledged = match process_result {
+            Ok(failures) if failures.is_empty() => {
+                metrics.increment_counter("updates_successful", 1).await?;
+                self.status.processed();
+
+                log::trace!("processed update");
+                true
+            }
+            Ok(failures) => {
+                let mut dead_lettered = true;
+                for failure in failures {
+                    log::error!(
+                        "error processing update in {} after {} attempt(s) ({update:?}): {:?}",
+                        failure.pipe,
+                        failure.attempts,
+                        failure.error
+                    );
+                    dead_lettered &= self
+                        .dead_letter(failure, &update, &datasource_id, metrics)
+                        .await?;
+                }
+                metrics.increment_counter("updates_failed", 1).await?;
+                dead_lettered
+            }
+            Err(error @ Error::PipelineHalted(_)) => {
+                log::error!("error processing update ({update:?}): {error:?}");
+                metrics.increment_counter("updates_failed", 1).await?;
+                self.halt(error);
+                return Ok(());
+            }
+            Err(error) => {
+                log::error!("error processing update ({update:?}): {error:?}");
+                metrics.increment_counter("updates_failed", 1).await?;
+                false
+            }
+        };
+
+        if let (Some(checkpoints), Some(position)) = (&self.checkpoints, position) {
+            if acknowledged {
+                checkpoints.processed(&datasource_id, position);
+            } else {
+                log::warn!(
+                    "holding the checkpoint of {datasource_id:?} back before slot {}, since an update of it was lost.",
+                    position.slot()
+                );
+            }
+        }
+
+        metrics.increment_counter("updates_processed", 1).await?;
+
+        Ok(())
+    }
+
+    /// Sends an update a pipe gave up on to the dead-letter sink, if any,
+    /// returning whether it was stored.
+    ///
+    /// Failing to store the dead letter is logged and counted under
+    /// `dead_letters_failed`.
+    async fn dead_letter(
+        &self,
+        failure: PipeFailure,
+        update: &Update,
+        datasource_id: &DatasourceId,
+        metrics: &Arc<MetricsCollection>,
+    ) -> CarbonResult<bool> {
+        let Some(dead_letter_sink) = &self.dead_letter_sink else {
+            return Ok(false);
+        };
+
+        let dead_letter = DeadLetter {
+            pipe: failure.pipe,
+            datasource_id: datasource_id.clone(),
+            update: update.clone(),
+            error: failure.error.to_string(),
+            attempts: failure.attempts,
+            failed_at: chrono::Utc::now(),
+        };
+
+        match dead_letter_sink.send(dead_letter).await {
+            Ok(()) => {
+                metrics
+                    .increment_counter("updates_dead_lettered", 1)
+                    .await?;
+                Ok(true)
+            }
+            Err(error) => {
+                log::error!("failed to store dead letter: {error:?}");
+                metrics.increment_counter("dead_letters_failed", 1).await?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Stops the processing of further updates, keeping the first reason
+    /// given.
+    fn halt(&self, error: Error) {
+        log::error!("halting the pipeline: {error}");
+        let _ = self.halt_reason.set(error.to_string());
+        self.halted.cancel();
+        self.status.halted();
+    }
+
+    /// Returns why the pipeline was halted, if it was.
+    fn halt_reason(&self) -> Option<&str> {
+        self.halt_reason.get().map(String::as_str)
+    }
+
+    /// Processes a single update and routes it through the appropriate pipeline
+    /// stages.
+    ///
+    /// The `process` method takes an `Update` and determines its type, then
+    /// routes it through the corresponding pipes for handling account
+    /// updates, transactions, or account deletions. It also records metrics
+    /// for processed updates, providing insights into the processing
+    /// workload and performance.
+    ///
+    /// ## Functionality
+    ///
+    /// - **Account Updates**: Passes account updates through the
+    ///   `account_pipes`. Each pipe processes the account metadata and the
+    ///   updated account state.
+    /// - **Transaction Updates**: Extracts transaction metadata and
+    ///   instructions, nests them if needed, and routes them through
+    ///   `instruction_pipes` and `transaction_pipes`.
+    /// - **Account Deletions**: Sends account deletion events through the
+    ///   `account_deletion_pipes`.
+    ///
+    /// The method also updates metrics counters for each type of update,
+    /// tracking how many updates have been processed in each category.
+    ///
+    /// # Parameters
+    ///
+    /// - `update`: An `Update` variant representing the type of data received.
+    ///   This can be an `Account`, `Transaction`, or `AccountDeletion`, each
+    ///   triggering different processing logic within the pipeline.
+    /// - `datasource_id`: The ID of the datasource that produced this update.
+    ///   This is used by filters to determine whether the update should be
+    ///   processed by specific pipes.
+    /// - `worker`: The worker processing the update, which selects the
+    ///   instance of every pipe.
+    /// - `metrics`: The metrics collection passed on to every pipe.
+    ///
+    /// # Returns
+    ///
+    /// Returns the failures of the pipes that gave up on the update under
+    /// their `ErrorPolicy`. A failing pipe does not prevent the remaining
+    /// pipes from processing the update.
+    ///
+    /// # Notes
+    ///
+    /// - This method is asynchronous and should be awaited within a Tokio
+    ///   runtime.
+    /// - Each type of update (account, transaction, account deletion) requires
+    ///   its own set of pipes, so ensure that appropriate pipes are configured
+    ///   based on the data types expected from the data sources.
+    /// - Metrics are recorded after each successful processing stage to track
+    ///   processing volumes and identify potential bottlenecks in real-time.
+    /// - Filters are applied to each pipe before processing, allowing for
+    ///   selective update processing based on datasource ID and other criteria.
+    /// - Each pipe instance is locked while it runs, so concurrent callers never
+    ///   run the same instance at the same time.
+    /// - The update is borrowed by every pipe. The raw account of an account
+    ///   update is copied at most once and shared behind an `Arc` by every
+    ///   processor, and the `TransactionMetadata` and `NestedInstructions` of a
+    ///   transaction are built once and shared.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::PipelineHalted` if a pipe with `ErrorPolicy::Halt`
+    /// fails, or an error if the transaction cannot be prepared for the pipes
+    /// or recording the metrics fails.
+    async fn process(
+        &self,
+        update: &Update,
+        datasource_id: &DatasourceId,
+        worker: usize,
+        metrics: &Arc<MetricsCollection>,
+    ) -> CarbonResult<Vec<PipeFailure>> {
+        log::trace!(
+            "process(self, update: {update:?}, datasource_id: {datasource_id:?}, worker: {worker})"
+        );
+        let mut failures = Vec::new();
+
+        match update {
+            Update::Account(account_update) => {
+                let account_metadata = AccountMetadata {
+                    slot: account_update.slot,
+                    pubkey: account_update.pubkey,
+                    transaction_signature: account_update.transaction_signature,
+                };
+                // Copied once and shared by the processors of every pipe.
+                let mut account: Option<Arc<solana_account::Account>> = None;
+
+                for entry in self.account_pipes.snapshot().iter() {
+                    let Some(pipe) = entry.lock(worker).await else {
+                        continue;
+                    };
+                    let matches = pipe.filters().iter().all(|filter| {
+                        filter.filter_account(
+                            datasource_id,
+                            &account_metadata,
+                            &account_update.account,
+                        )
+                    });
+                    drop(pipe);
+
+                    if matches {
+                        let account =
+                            account.get_or_insert_with(|| Arc::new(account_update.account.clone()));
+                        run_with_policy!(entry, worker, failures, metrics, |pipe| pipe.run(
+                            &account_metadata,
+                            account,
+                            metrics.clone()
+                        ))?;
+                    }
+                }
+
+                metrics
+                    .increment_counter("account_updates_processed", 1)
+                    .await?;
+            }
+            Update::Transaction(transaction_update) => {
+                let transaction_metadata =
+                    Arc::new(TransactionMetadata::try_from(&**transaction_update)?);
+
+                let instructions_with_metadata: InstructionsWithMetadata =
+                    transformers::extract_instructions_with_metadata(
+                        &transaction_metadata,
+                        transaction_update,
+                    )?;
+
+                let nested_instructions: NestedInstructions = instructions_with_metadata.into();
+
+                for entry in self.instruction_pipes.snapshot().iter() {
+                    for nested_instruction in nested_instructions.iter() {
+                        let Some(pipe) = entry.lock(worker).await else {
+                            break;
+                        };
+                        let matches = pipe.filters().iter().all(|filter| {
+                            filter.filter_instruction(datasource_id, nested_instruction)
+                        });
+                        drop(pipe);
+
+                        if !matches {
+                            continue;
+                        }
+
+                        // The pipe runs for the instruction and each of its
+                        // inner instructions separately, so that a retry only
+                        // repeats the instruction that failed.
+                        let mut instructions = vec![nested_instruction];
+                        while let Some(instruction) = instructions.pop() {
+                            run_with_policy!(entry, worker, failures, metrics, |pipe| pipe
+                                .run(instruction, metrics.clone()))?;
+                            instructions.extend(instruction.inner_instructions.iter().rev());
+                        }
+                    }
+                }
+
+                for entry in self.transaction_pipes.snapshot().iter() {
+                    let Some(pipe) = entry.lock(worker).await else {
+                        continue;
+                    };
+                    let matches = pipe.filters().iter().all(|filter| {
+                        filter.filter_transaction(
+                            datasource_id,
+                            &transaction_metadata,
+                            &nested_instructions,
+                        )
+                    });
+                    drop(pipe);
+
+                    if matches {
+                        run_with_policy!(entry, worker, failures, metrics, |pipe| pipe.run(
+                            transaction_metadata.clone(),
+                            &nested_instructions,
+                            metrics.clone(),
+                        ))?;
+                    }
+                }
+
+                metrics
+                    .increment_counter("transaction_updates_processed", 1)
+                    .await?;
+            }
+            Update::AccountDeletion(account_deletion) => {
+                for entry in self.account_deletion_pipes.snapshot().iter() {
+                    let Some(pipe) = entry.lock(worker).await else {
+                        continue;
+                    };
+                    let matches = pipe.filters().iter().all(|filter| {
+                        filter.filter_account_deletion(datasource_id, &account_deletion)
+                    });
+                    drop(pipe);
+
+                    if matches {
+                        run_with_policy!(entry, worker, failures, metrics, |pipe| pipe
+                            .run(account_deletion.clone(), metrics.clone()))?;
+                    }
+                }
+
+                metrics
+                    .increment_counter("account_deletions_processed", 1)
+                    .await?;
+            }
+            Update::BlockDetails(block_details) => {
+                for entry in self.block_details_pipes.snapshot().iter() {
+                    let Some(pipe) = entry.lock(worker).await else {
+                        continue;
+                    };
+                    let matches = pipe
+                        .filters()
+                        .iter()
+                        .all(|filter| filter.filter_block_details(datasource_id, &block_details));
+                    drop(pipe);
+
+                    if matches {
+                        run_with_policy!(entry, worker, failures, metrics, |pipe| pipe
+                            .run(block_details.clone(), metrics.clone()))?;
+                    }
+                }
+
+                metrics
+                    .increment_counter("block_details_processed", 1)
+                    .await?;
+
+                let transactions = if self.block_pipes.is_empty() {
+                    None
+                } else {
+                    let transactions = self
+                        .blocks
+                        .lock()
+                        .expect("block assembler lock poisoned")
+                        .take(datasource_id, block_details.slot);
+                    if transactions.is_none() {
+                        log::warn!(
+                            "dropping block details of closed block {} from {datasource_id:?}",
+                            block_details.slot
+                        );
+                        metrics
+                            .increment_counter("block_details_dropped", 1)
+                            .await?;
+                    }
+                    transactions
+                };
+
+                if let Some(transactions) = transactions {
+                    for entry in self.block_pipes.snapshot().iter() {
+                        let Some(pipe) = entry.lock(worker).await else {
+                            continue;
+                        };
+                        let matches = pipe.filters().iter().all(|filter| {
+                            filter.filter_block(datasource_id, block_details, &transactions)
+                        });
+                        drop(pipe);
+
+                        if matches {
+                            run_with_policy!(entry, worker, failures, metrics, |pipe| pipe.run(
+                                (block_details.clone(), transactions.clone()),
+                                metrics.clone()
+                            ))?;
+                        }
+                    }
+
+                    metrics.increment_counter("blocks_processed", 1).await?;
+                }
+            }
+            Update::SlotStatus(slot_status) => {
+                for entry in self.slot_status_pipes.snapshot().iter() {
+                    let Some(pipe) = entry.lock(worker).await else {
+                        continue;
+                    };
+                    let matches = pipe
+                        .filters()
+                        .iter()
+                        .all(|filter| filter.filter_slot_status(datasource_id, &slot_status));
+                    drop(pipe);
+
+                    if matches {
+                        run_with_policy!(entry, worker, failures, metrics, |pipe| pipe
+                            .run(slot_status.clone(), metrics.clone()))?;
+                    }
+                }
+
+                metrics
+                    .increment_counter("slot_status_updates_processed", 1)
+                    .await?;
+            }
+        };
+
+        Ok(failures)
+    }
+}
+
+/// A builder for constructing a `Pipeline` instance with customized data
+/// sources, processing pipes, and metrics.
+///
+/// The `PipelineBuilder` struct offers a flexible way to assemble a `Pipeline`
+/// by allowing configuration of its components, such as data sources, account
+/// and transaction pipes, deletion handling, and metrics. Using the builder
+/// pattern, you can add the desired elements incrementally and then finalize
+/// with a call to `build`.
+///
+/// ## Overview
+///
+/// The `PipelineBuilder` supports the following components:
+/// - **Datasources**: Sources of data updates, such as account information and
+///   transactions.
+/// - **Account Pipes**: For processing account updates from data sources.
+/// - **Account Deletion Pipes**: For handling account deletion updates.
+/// - **Instruction Pipes**: For handling instructions associated with
+///   transactions.
+/// - **Transaction Pipes**: For handling full transaction data.
+/// - **Metrics**: Collects and reports performance data, such as update
+///   processing times.
+/// - **Metrics Flush Interval**: Optional interval defining how often to flush
+///   metrics data.
+///
+/// Each component can be added through method chaining, enhancing code
+/// readability and maintainability.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+///
+/// carbon_core::pipeline::Pipeline::builder()
+/// .datasource(transaction_crawler)
+/// .metrics(Arc::new(LogMetrics::new()))
+/// .metrics(Arc::new(PrometheusMetrics::new()))
+/// .instruction(
+///    TestProgramDecoder,
+///    TestProgramProcessor
+/// );
+/// // ...
+/// ```
+///
+/// # Fields
+///
+/// - `datasources`: A collection of `Datasource` objects wrapped in `Arc` for
+///   shared ownership across threads. Each `Datasource` provides updates to the
+///   pipeline.
+/// - `account_pipes`: A collection of `AccountPipes` to handle account updates.
+/// - `account_deletion_pipes`: A collection of `AccountDeletionPipes` for
+///   processing account deletions.
+/// - `instruction_pipes`: A collection of `InstructionPipes` to process
+///   instructions in transactions.
+/// - `transaction_pipes`: A collection of `TransactionPipes` to process full
+///   transaction data.
+/// - `metrics`: A vector of `Metrics` implementations for tracking pipeline
+///   performance.
+/// - `metrics_flush_interval`: An optional interval (in seconds) for flushing
+///   metrics data. If not set, a default flush interval will be used.
+/// - `datasource_cancellation_token`: An optional `CancellationToken` for
+///   canceling datasource. If not set, a default `CancellationToken` will be
+///   used.
+/// - `channel_buffer_size`: The size of the channel buffer for the pipeline. If
+///   not set, a default size of 10_000 will be used.
+/// - `execution_mode`: Whether updates are processed sequentially or spread
+///   over a pool of workers. Defaults to `ExecutionMode::Sequential`.
+/// - `deduplication`: An optional `DeduplicationConfig` enabling the
+///   deduplication stage. Disabled by default.
+/// - `commitment_buffer`: An optional `CommitmentBufferConfig` enabling the
+///   commitment buffer. Disabled by default.
+/// - `checkpoint_store`: An optional `CheckpointStore` to commit the progress
+///   of every datasource to.
+/// - `checkpoint_interval`: An optional interval (in seconds) for committing
+///   checkpoints. If not set, a default interval of 5 seconds will be used.
+/// - `flush_interval`: An optional interval (in seconds) for flushing the
+///   processors of the pipes. If not set, a default interval of 5 seconds
+///   will be used.
+/// - `rollback_pipes`: A collection of `RollbackPipes` notified when the
+///   updates of a dead or orphaned slot must be rolled back.
+/// - `dead_letter_sink`: An optional `DeadLetterSink` for the updates pipes
+///   gave up on.
+/// - `error_policy`: The `ErrorPolicy` given to the pipes added from then on.
+///   Defaults to `ErrorPolicy::Skip`.
+/// - `restart_policy`: The `RestartPolicy` applied to failed datasources.
+///   Defaults to `RestartPolicy::default()`.
+/// - `drain_timeout`: An optional upper bound on the time spent processing
+///   pending updates on shutdown. Unbounded by default.
+/// - `liveness_timeout`: An optional time after which a running pipeline
+///   that processed no update is no longer live. Disabled by default.
+/// - `block_pipes`: A collection of `BlockPipes` receiving whole blocks.
+/// - `block_timeout`: An optional time the block pipes wait for the block
+///   details of a slot. Defaults to `DEFAULT_BLOCK_TIMEOUT`.
+/// - `account_store`: An optional `AccountStore` kept up to date with every
+///   account update. Disabled by default.
+/// - `ignore_shutdown_signals`: Whether the pipeline ignores SIGINT and
+///   SIGTERM. Defaults to `false`.
+///
+/// # Returns
+///
+/// After configuring the builder, call `build` to create a `Pipeline` instance.
+/// The builder will return a `CarbonResult<Pipeline>`, which will either
+/// contain the configured pipeline or an error if configuration failed.
+///
+/// # Notes
+///
+/// - The builder pattern allows for method chaining, making it easy to
+///   incrementally add components to the `Pipeline`.
+/// - Ensure that each component matches the data and update types expected by
+///   your application.
+#[derive(Default)]
+pub struct PipelineBuilder {
+    pub datasources: Vec<(DatasourceId, Arc<dyn Datasource + Send + Sync>)>,
+    pub account_pipes: Vec<Box<dyn AccountPipes>>,
+    pub account_deletion_pipes: Vec<Box<dyn AccountDeletionPipes>>,
+    pub block_details_pipes: Vec<Box<dyn BlockDetailsPipes>>,
+    pub block_pipes: Vec<Box<dyn BlockPipes>>,
+    pub slot_status_pipes: Vec<Box<dyn SlotStatusPipes>>,
+    pub rollback_pipes: Vec<Box<dyn RollbackPipes>>,
+    pub instruction_pipes: Vec<Box<dyn for<'a> InstructionPipes<'a>>>,
+    pub transaction_pipes: Vec<Box<dyn for<'a> TransactionPipes<'a>>>,
+    pub metrics: MetricsCollection,
+    pub metrics_flush_interval: Option<u64>,
+    pub datasource_cancellation_token: Option<CancellationToken>,
+    pub shutdown_strategy: ShutdownStrategy,
+    pub channel_buffer_size: usize,
+    pub execution_mode: ExecutionMode,
+    pub deduplication: Option<DeduplicationConfig>,
+    pub commitment_buffer: Option<CommitmentBufferConfig>,
+    pub checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    pub checkpoint_interval: Option<u64>,
+    pub flush_interval: Option<u64>,
+    pub dead_letter_sink: Option<Arc<dyn DeadLetterSink>>,
+    pub error_policy: ErrorPolicy,
+    pub restart_policy: RestartPolicy,
+    pub drain_timeout: Option<Duration>,
+    pub liveness_timeout: Option<Duration>,
+    pub block_timeout: Option<Duration>,
+    pub account_store: Option<AccountStore>,
+    pub ignore_shutdown_signals: bool,
+}
+
+impl PipelineBuilder {
+    /// Creates a new `PipelineBuilder` with empty collections for datasources,
+    /// pipes, and metrics.
+    ///
+    /// This method initializes a `PipelineBuilder` instance, allowing you to
+    /// configure each component of a `Pipeline` before building it. The
+    /// builder pattern offers flexibility in adding data sources, account
+    /// and transaction handling pipes, deletion processing, and metrics
+    /// collection features.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new();
+    /// ```
+    pub fn new() -> Self {
+        log::trace!("PipelineBuilder::new()");
+        Self::default()
+    }
+
+    /// Adds a datasource to the pipeline.
+    ///
+    /// The datasource is responsible for providing updates, such as account and
+    /// transaction data, to the pipeline. Multiple datasources can be added
+    /// to handle various types of updates.
+    ///
+    /// # Parameters
+    ///
+    /// - `datasource`: The data source to add, implementing the `Datasource`
+    ///   trait.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .datasource(MyDatasource::new());
+    /// ```
+    pub fn datasource(mut self, datasource: impl Datasource + 'static) -> Self {
+        log::trace!("datasource(self, datasource: {:?})", stringify!(datasource));
+        self.datasources
+            .push((DatasourceId::new_unique(), Arc::new(datasource)));
+        self
+    }
+
+    /// Adds a datasource to the pipeline with a specific ID.
+    ///
+    /// This method allows you to assign a custom ID to a datasource, which is
+    /// useful for filtering updates based on their source. The ID can be used
+    /// with filters to selectively process updates from specific datasources.
+    ///
+    /// # Parameters
+    ///
+    /// - `datasource`: The data source to add, implementing the `Datasource`
+    ///   trait
+    /// - `id`: The `DatasourceId` to assign to this datasource
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{pipeline::PipelineBuilder, datasource::DatasourceId};
+    ///
+    /// let mainnet_id = DatasourceId::new_named("mainnet");
+    /// let builder = PipelineBuilder::new()
+    ///     .datasource_with_id(mainnet_id, MyDatasource::new());
+    /// ```
+    pub fn datasource_with_id(
+        mut self,
+        datasource: impl Datasource + 'static,
+        id: DatasourceId,
+    ) -> Self {
+        log::trace!(
+            "datasource_with_id(self, id: {:?}, datasource: {:?})",
+            id,
+            stringify!(datasource)
+        );
+        self.datasources.push((id, Arc::new(datasource)));
+        self
+    }
+
+    /// Sets the shutdown strategy for the pipeline.
+    ///
+    /// This method configures how the pipeline should handle shutdowns. The
+    /// shutdown strategy defines whether the pipeline should terminate
+    /// immediately or continue processing pending updates after terminating
+    /// the data sources.
+    ///
+    /// # Parameters
+    ///
+    /// - `shutdown_strategy`: A variant of [`ShutdownStrategy`] that determines
+    ///   how the pipeline should handle shutdowns.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Self`, allowing for method chaining.
+    ///
+    /// # Notes
+    ///
+    /// - Use `ShutdownStrategy::Immediate` to stop the entire pipeline
+    ///   instantly, including all active processing tasks.
+    /// - Use `ShutdownStrategy::ProcessPending` (the default) to terminate data
+    ///   sources first and allow the pipeline to finish processing any updates
+    ///   that are still pending.
+    pub fn shutdown_strategy(mut self, shutdown_strategy: ShutdownStrategy) -> Self {
+        log::trace!("shutdown_strategy(self, shutdown_strategy: {shutdown_strategy:?})");
+        self.shutdown_strategy = shutdown_strategy;
+        self
+    }
+
+    /// Sets how long the pipeline may spend processing pending updates after
+    /// a shutdown signal.
+    ///
+    /// With `ShutdownStrategy::ProcessPending`, the pipeline stops its
+    /// datasources on SIGINT or SIGTERM and keeps processing the updates that
+    /// were already received. Once `drain_timeout` has elapsed, the remaining
+    /// updates are dropped and the pipeline shuts down as with
+    /// `ShutdownStrategy::Immediate`.
+    ///
+    /// # Parameters
+    ///
+    /// - `drain_timeout`: The maximum time spent draining pending updates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .drain_timeout(Duration::from_secs(25));
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Set it below the grace period of your orchestrator, such as
+    ///   Kubernetes' `terminationGracePeriodSeconds`, so that processors are
+    ///   still flushed and shut down before the process is killed.
+    /// - Updates dropped this way are not checkpointed, so a pipeline resuming
+    ///   from a checkpoint processes them again.
+    pub fn drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        log::trace!("drain_timeout(self, drain_timeout: {drain_timeout:?})");
+        self.drain_timeout = Some(drain_timeout);
+        self
+    }
+
+    /// Sets how long the pipeline may run without processing an update
+    /// before its `PipelineStatus` reports it as not live.
+    ///
+    /// Without a liveness timeout, only a pipe halting the pipeline makes it
+    /// not live, so a pipeline stuck on a pipe or a datasource that stopped
+    /// delivering updates is still reported live. The time is counted from
+    /// the last update every pipe processed, or from the start of the
+    /// pipeline if none was processed yet.
+    ///
+    /// # Parameters
+    ///
+    /// - `liveness_timeout`: The longest time allowed between two processed
+    ///   updates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .liveness_timeout(Duration::from_secs(120));
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Choose a timeout well above the longest quiet period of the
+    ///   datasources, as a pipeline watching a rarely used program is
+    ///   otherwise restarted for being idle.
+    pub fn liveness_timeout(mut self, liveness_timeout: Duration) -> Self {
+        log::trace!("liveness_timeout(self, liveness_timeout: {liveness_timeout:?})");
+        self.liveness_timeout = Some(liveness_timeout);
+        self
+    }
+
+    /// Stops the pipeline from listening for SIGINT and SIGTERM.
+    ///
+    /// By default, the pipeline installs handlers for both signals and shuts
+    /// down according to its `ShutdownStrategy` when one is received. When the
+    /// pipeline is embedded in an application or a test that handles signals
+    /// itself, the pipeline can instead be stopped through its
+    /// `datasource_cancellation_token`, or runs until every datasource has
+    /// finished.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .ignore_shutdown_signals();
+    /// ```
+    pub fn ignore_shutdown_signals(mut self) -> Self {
+        log::trace!("ignore_shutdown_signals(self)");
+        self.ignore_shutdown_signals = true;
+        self
+    }
+
+    /// Sets how long the block pipes wait for the block details of a slot.
+    ///
+    /// A slot whose block details did not arrive within `block_timeout` of its
+    /// first transaction is dropped and counted under `blocks_timed_out`.
+    /// Timeouts are checked four times per `block_timeout`.
+    ///
+    /// # Parameters
+    ///
+    /// - `block_timeout`: The maximum time a slot is kept waiting for its
+    ///   block details. Defaults to `DEFAULT_BLOCK_TIMEOUT`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .block_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn block_timeout(mut self, block_timeout: Duration) -> Self {
+        log::trace!("block_timeout(self, block_timeout: {block_timeout:?})");
+        self.block_timeout = Some(block_timeout);
+        self
+    }
+
+    /// Adds an account pipe to process account updates.
+    ///
+    /// Account pipes decode and process updates to accounts within the
+    /// pipeline. This method requires both an `AccountDecoder` and a
+    /// `Processor` to handle decoded account data.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `AccountDecoder` that decodes the account data.
+    /// - `processor`: A `Processor` that processes the decoded account data.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .account(MyAccountDecoder, MyAccountProcessor);
+    /// ```
+    pub fn account<T: Send + Sync + 'static>(
+        mut self,
+        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = AccountProcessorInputType<T>> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!(
+            "account(self, decoder: {:?}, processor: {:?})",
+            stringify!(decoder),
+            stringify!(processor)
+        );
+        self.account_pipes.push(Box::new(AccountPipe {
+            decoder: Arc::new(decoder),
+            processor: Box::new(processor),
+            filters: Arc::default(),
+            error_policy: self.error_policy,
+        }));
+        self
+    }
+
+    /// Adds an account pipe that processes decoded accounts in batches.
+    ///
+    /// The `BatchProcessor` receives the decoded accounts as a slice, flushed
+    /// once `max_batch_size` accounts are buffered or `max_linger` has passed
+    /// since the first of them. Pending accounts are also flushed before
+    /// every checkpoint commit and on shutdown.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `AccountDecoder` that decodes the account data.
+    /// - `processor`: A `BatchProcessor` that processes batches of decoded
+    ///   account data.
+    /// - `config`: The `BatchConfig` deciding when a batch is flushed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{batch::BatchConfig, pipeline::PipelineBuilder};
+    ///
+    /// let builder = PipelineBuilder::new().account_batched(
+    ///     MyAccountDecoder,
+    ///     PostgresAccountProcessor::new(pool),
+    ///     BatchConfig::default(),
+    /// );
+    /// ```
+    pub fn account_batched<T: Send + Sync + 'static>(
+        self,
+        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
+        processor: impl BatchProcessor<InputType = AccountProcessorInputType<T>> + Send + Sync + 'static,
+        config: BatchConfig,
+    ) -> Self {
+        log::trace!("account_batched(self, decoder, processor, config: {config:?})");
+        self.account(decoder, BatchingProcessor::new(processor, config))
+    }
+
+    /// Adds an account pipe with filters to process account updates selectively.
+    ///
+    /// This method creates an account pipe that only processes updates that pass
+    /// all the specified filters. Filters can be used to selectively process
+    /// updates based on criteria such as datasource ID, account properties, or
+    /// other custom logic.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `AccountDecoder` that decodes the account data
+    /// - `processor`: A `Processor` that processes the decoded account data
+    /// - `filters`: A collection of filters that determine which account updates
+    ///   should be processed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{
+    ///     pipeline::PipelineBuilder,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    /// };
+    ///
+    /// let mainnet_id = DatasourceId::new_named("mainnet");
+    /// let filter = DatasourceFilter::new(mainnet_id);
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .account_with_filters(MyAccountDecoder, MyAccountProcessor, filters);
+    /// ```
+    pub fn account_with_filters<T: Send + Sync + 'static>(
+        mut self,
+        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = AccountProcessorInputType<T>> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    ) -> Self {
+        log::trace!(
+            "account_with_filters(self, decoder: {:?}, processor: {:?}, filters: {:?})",
+            stringify!(decoder),
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.account_pipes.push(Box::new(AccountPipe {
+            decoder: Arc::new(decoder),
+            processor: Box::new(processor),
+            filters: Arc::new(filters),
+            error_policy: self.error_policy,
+        }));
+        self
+    }
+
+    /// Adds an account diff pipe, which processes decoded accounts along with
+    /// their previous decoded state.
+    ///
+    /// The pipe keeps the last decoded state of up to `cache_capacity`
+    /// accounts, evicting the least recently updated ones first, and only
+    /// runs the processor when the decoded state of an account changed. See
+    /// the `account_diff` module.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `AccountDecoder` that decodes the account data.
+    /// - `processor`: A `Processor` that processes the previous and new
+    ///   decoded account data.
+    /// - `cache_capacity`: The number of accounts whose state is remembered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{
+    ///     account_diff::DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY, pipeline::PipelineBuilder,
+    /// };
+    ///
+    /// let builder = PipelineBuilder::new().account_diff(
+    ///     MyAccountDecoder,
+    ///     MyAccountDiffProcessor,
+    ///     DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY,
+    /// );
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Accounts evicted from the cache are handed to the processor without
+    ///   a previous state the next time they change, so size the cache for the
+    ///   number of accounts you expect to track.
+    pub fn account_diff<T: Clone + PartialEq + Send + Sync + 'static>(
+        self,
+        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = AccountDiffProcessorInputType<T>> + Send + Sync + 'static,
+        cache_capacity: usize,
+    ) -> Self {
+        log::trace!(
+            "account_diff(self, decoder: {:?}, processor: {:?}, cache_capacity: {cache_capacity:?})",
+            stringify!(decoder),
+            stringify!(processor)
+        );
+        self.account_diff_with_filters(decoder, processor, cache_capacity, vec![])
+    }
+
+    /// Adds an account diff pipe with filters to process account changes
+    /// selectively.
+    ///
+    /// Updates that don't pass every filter are neither processed nor cached.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `AccountDecoder` that decodes the account data
+    /// - `processor`: A `Processor` that processes the previous and new
+    ///   decoded account data
+    /// - `cache_capacity`: The number of accounts whose state is remembered
+    /// - `filters`: A collection of filters that determine which account updates
+    ///   should be processed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{
+    ///     account_diff::DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    ///     pipeline::PipelineBuilder,
+    /// };
+    ///
+    /// let filter = DatasourceFilter::new(DatasourceId::new_named("mainnet"));
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    ///
+    /// let builder = PipelineBuilder::new().account_diff_with_filters(
+    ///     MyAccountDecoder,
+    ///     MyAccountDiffProcessor,
+    ///     DEFAULT_ACCOUNT_DIFF_CACHE_CAPACITY,
+    ///     filters,
+    /// );
+    /// ```
+    pub fn account_diff_with_filters<T: Clone + PartialEq + Send + Sync + 'static>(
+        mut self,
+        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = AccountDiffProcessorInputType<T>> + Send + Sync + 'static,
+        cache_capacity: usize,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    ) -> Self {
+        log::trace!(
+            "account_diff_with_filters(self, decoder: {:?}, processor: {:?}, cache_capacity: {cache_capacity:?}, filters: {:?})",
+            stringify!(decoder),
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.account_pipes.push(Box::new(AccountDiffPipe::new(
+            Arc::new(decoder),
+            Box::new(processor),
+            Arc::new(filters),
+            self.error_policy,
+            cache_capacity,
+        )));
+        self
+    }
+
+    /// Adds an account deletion pipe to handle account deletion events.
+    ///
+    /// Account deletion pipes process deletions of accounts, with a `Processor`
+    /// to handle the deletion events as they occur.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes account deletion events.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .account_deletions(MyAccountDeletionProcessor);
+    /// ```
+    pub fn account_deletions(
+        mut self,
+        processor: impl Processor<InputType = AccountDeletion> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!(
+            "account_deletions(self, processor: {:?})",
+            stringify!(processor)
+        );
+        self.account_deletion_pipes
+            .push(Box::new(AccountDeletionPipe {
+                processor: Box::new(processor),
+                filters: Arc::default(),
+                error_policy: self.error_policy,
+            }));
+        self
+    }
+
+    /// Adds an account deletion pipe with filters to handle account deletion events selectively.
+    ///
+    /// This method creates an account deletion pipe that only processes deletion
+    /// events that pass all the specified filters. Filters can be used to
+    /// selectively process deletions based on criteria such as datasource ID or
+    /// other custom logic.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes account deletion events
+    /// - `filters`: A collection of filters that determine which account deletion
+    ///   events should be processed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{
+    ///     pipeline::PipelineBuilder,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    /// };
+    ///
+    /// let mainnet_id = DatasourceId::new_named("mainnet");
+    /// let filter = DatasourceFilter::new(mainnet_id);
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .account_deletions_with_filters(MyAccountDeletionProcessor, filters);
+    /// ```
+    pub fn account_deletions_with_filters(
+        mut self,
+        processor: impl Processor<InputType = AccountDeletion> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    ) -> Self {
+        log::trace!(
+            "account_deletions_with_filters(self, processor: {:?}, filters: {:?})",
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.account_deletion_pipes
+            .push(Box::new(AccountDeletionPipe {
+                processor: Box::new(processor),
+                filters: Arc::new(filters),
+                error_policy: self.error_policy,
+            }));
+        self
+    }
+
+    /// Adds a block details pipe to handle block details updates.
+    ///
+    /// Block details pipes process updates related to block metadata, such as
+    /// slot, block hash, and rewards, with a `Processor` to handle the updates.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes block details updates.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .block_details(MyBlockDetailsProcessor);
+    /// ```
+    pub fn block_details(
+        mut self,
+        processor: impl Processor<InputType = BlockDetails> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!(
+            "block_details(self, processor: {:?})",
+            stringify!(processor)
+        );
+        self.block_details_pipes.push(Box::new(BlockDetailsPipe {
+            processor: Box::new(processor),
+            filters: vec![],
+            error_policy: self.error_policy,
+        }));
+        self
+    }
+
+    /// Adds a block details pipe with filters to handle block details updates selectively.
+    ///
+    /// This method creates a block details pipe that only processes updates that
+    /// pass all the specified filters. Filters can be used to selectively process
+    /// block details updates based on criteria such as datasource ID, block height,
+    /// or other custom logic.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes block details updates
+    /// - `filters`: A collection of filters that determine which block details
+    ///   updates should be processed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{
+    ///     pipeline::PipelineBuilder,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    /// };
+    ///
+    /// let mainnet_id = DatasourceId::new_named("mainnet");
+    /// let filter = DatasourceFilter::new(mainnet_id);
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .block_details_with_filters(MyBlockDetailsProcessor, filters);
+    /// ```
+    pub fn block_details_with_filters(
+        mut self,
+        processor: impl Processor<InputType = BlockDetails> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    ) -> Self {
+        log::trace!(
+            "block_details_with_filters(self, processor: {:?}, filters: {:?})",
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.block_details_pipes.push(Box::new(BlockDetailsPipe {
+            processor: Box::new(processor),
+            filters,
+            error_policy: self.error_policy,
+        }));
+        self
+    }
+
+    /// Adds a block pipe to process every transaction of a slot at once.
+    ///
+    /// The pipeline gathers the transactions of each slot until its block
+    /// details arrive, then hands them to the processor together with the
+    /// block details, sorted by their index in the block.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes whole blocks.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .datasource(block_crawler)
+    ///     .block(MyBlockProcessor);
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Blocks are only assembled from datasources that emit block details
+    ///   after the transactions of each block, such as block crawlers and
+    ///   block subscriptions.
+    /// - Slots whose block details don't arrive within the `block_timeout` are
+    ///   dropped.
+    pub fn block(
+        mut self,
+        processor: impl Processor<InputType = BlockProcessorInputType> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!("block(self, processor: {:?})", stringify!(processor));
+        self.block_pipes.push(Box::new(BlockPipe {
+            processor: Box::new(processor),
+            filters: vec![],
+            error_policy: self.error_policy,
+        }));
+        self
+    }
+
+    /// Adds a block pipe with filters to process whole blocks selectively.
     ///
-    /// This method initializes a `PipelineBuilder` instance, allowing you to
-    /// configure each component of a `Pipeline` before building it. The
-    /// builder pattern offers flexibility in adding data sources, account
-    /// and transaction handling pipes, deletion processing, and metrics
-    /// collection features.
+    /// This method creates a block pipe that only processes blocks that pass
+    /// all the specified filters, through `Filter::filter_block`.
+    ///
+    /// # Parameters
+    ///
+    /// - `processor`: A `Processor` that processes whole blocks
+    /// - `filters`: A collection of filters that determine which blocks should
+    ///   be processed
     ///
     /// # Example
     ///
-    /// ```rust
-    /// use carbon_core::pipeline::PipelineBuilder;
+    /// ```ignore
+    /// use carbon_core::{
+    ///     pipeline::PipelineBuilder,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    /// };
     ///
-    /// let builder = PipelineBuilder::new();
+    /// let filter = DatasourceFilter::new(DatasourceId::new_named("crawler"));
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .block_with_filters(MyBlockProcessor, filters);
     /// ```
-    pub fn new() -> Self {
-        log::trace!("PipelineBuilder::new()");
-        Self::default()
+    pub fn block_with_filters(
+        mut self,
+        processor: impl Processor<InputType = BlockProcessorInputType> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    ) -> Self {
+        log::trace!(
+            "block_with_filters(self, processor: {:?}, filters: {:?})",
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.block_pipes.push(Box::new(BlockPipe {
+            processor: Box::new(processor),
+            filters,
+            error_policy: self.error_policy,
+        }));
+        self
     }
 
-    /// Adds a datasource to the pipeline.
+    /// Adds a slot status pipe to handle slot status updates.
     ///
-    /// The datasource is responsible for providing updates, such as account and
-    /// transaction data, to the pipeline. Multiple datasources can be added
-    /// to handle various types of updates.
+    /// Slot status pipes are notified whenever a slot becomes confirmed,
+    /// finalized or dead, provided a datasource emits slot status updates.
     ///
     /// # Parameters
     ///
-    /// - `datasource`: The data source to add, implementing the `Datasource`
-    ///   trait.
+    /// - `processor`: A `Processor` that processes slot status updates.
     ///
     /// # Example
     ///
@@ -752,89 +3119,112 @@ impl PipelineBuilder {
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .datasource(MyDatasource::new());
+    ///     .slot_status(MySlotStatusProcessor);
     /// ```
-    pub fn datasource(mut self, datasource: impl Datasource + 'static) -> Self {
-        log::trace!("datasource(self, datasource: {:?})", stringify!(datasource));
-        self.datasources
-            .push((DatasourceId::new_unique(), Arc::new(datasource)));
+    pub fn slot_status(
+        mut self,
+        processor: impl Processor<InputType = SlotStatusUpdate> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!("slot_status(self, processor: {:?})", stringify!(processor));
+        self.slot_status_pipes.push(Box::new(SlotStatusPipe {
+            processor: Box::new(processor),
+            filters: vec![],
+            error_policy: self.error_policy,
+        }));
         self
     }
 
-    /// Adds a datasource to the pipeline with a specific ID.
-    ///
-    /// This method allows you to assign a custom ID to a datasource, which is
-    /// useful for filtering updates based on their source. The ID can be used
-    /// with filters to selectively process updates from specific datasources.
+    /// Adds a slot status pipe with filters to handle slot status updates
+    /// selectively.
     ///
     /// # Parameters
     ///
-    /// - `datasource`: The data source to add, implementing the `Datasource`
-    ///   trait
-    /// - `id`: The `DatasourceId` to assign to this datasource
+    /// - `processor`: A `Processor` that processes slot status updates
+    /// - `filters`: A collection of filters that determine which slot status
+    ///   updates should be processed
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use carbon_core::{pipeline::PipelineBuilder, datasource::DatasourceId};
+    /// use carbon_core::{
+    ///     pipeline::PipelineBuilder,
+    ///     datasource::DatasourceId,
+    ///     filter::DatasourceFilter,
+    /// };
+    ///
+    /// let filter = DatasourceFilter::new(DatasourceId::new_named("yellowstone"));
+    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
     ///
-    /// let mainnet_id = DatasourceId::new_named("mainnet");
     /// let builder = PipelineBuilder::new()
-    ///     .datasource_with_id(mainnet_id, MyDatasource::new());
+    ///     .slot_status_with_filters(MySlotStatusProcessor, filters);
     /// ```
-    pub fn datasource_with_id(
+    pub fn slot_status_with_filters(
         mut self,
-        datasource: impl Datasource + 'static,
-        id: DatasourceId,
+        processor: impl Processor<InputType = SlotStatusUpdate> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
     ) -> Self {
         log::trace!(
-            "datasource_with_id(self, id: {:?}, datasource: {:?})",
-            id,
-            stringify!(datasource)
+            "slot_status_with_filters(self, processor: {:?}, filters: {:?})",
+            stringify!(processor),
+            stringify!(filters)
         );
-        self.datasources.push((id, Arc::new(datasource)));
+        self.slot_status_pipes.push(Box::new(SlotStatusPipe {
+            processor: Box::new(processor),
+            filters,
+            error_policy: self.error_policy,
+        }));
         self
     }
 
-    /// Sets the shutdown strategy for the pipeline.
+    /// Adds a rollback pipe, notified when the updates of a slot must be
+    /// rolled back.
     ///
-    /// This method configures how the pipeline should handle shutdowns. The
-    /// shutdown strategy defines whether the pipeline should terminate
-    /// immediately or continue processing pending updates after terminating
-    /// the data sources.
+    /// Adding a rollback pipe enables fork tracking: the pipeline follows the
+    /// parent chain of recent slots and emits a `RollbackEvent` for every slot
+    /// it received updates for that is reported dead or is orphaned by a
+    /// finalized slot. Processors that store data at `processed` or
+    /// `confirmed` commitment can use these events to undo what they wrote.
     ///
     /// # Parameters
     ///
-    /// - `shutdown_strategy`: A variant of [`ShutdownStrategy`] that determines
-    ///   how the pipeline should handle shutdowns.
+    /// - `processor`: A `Processor` that processes rollback events.
     ///
-    /// # Returns
+    /// # Example
     ///
-    /// Returns `Self`, allowing for method chaining.
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .slot_status(MySlotStatusProcessor)
+    ///     .rollback(MyRollbackProcessor);
+    /// ```
     ///
     /// # Notes
     ///
-    /// - Use `ShutdownStrategy::Immediate` to stop the entire pipeline
-    ///   instantly, including all active processing tasks.
-    /// - Use `ShutdownStrategy::ProcessPending` (the default) to terminate data
-    ///   sources first and allow the pipeline to finish processing any updates
-    ///   that are still pending.
-    pub fn shutdown_strategy(mut self, shutdown_strategy: ShutdownStrategy) -> Self {
-        log::trace!("shutdown_strategy(self, shutdown_strategy: {shutdown_strategy:?})");
-        self.shutdown_strategy = shutdown_strategy;
+    /// - Fork tracking requires a datasource that provides slot status updates,
+    ///   see the `rollback` module for details.
+    pub fn rollback(
+        mut self,
+        processor: impl Processor<InputType = RollbackEvent> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!("rollback(self, processor: {:?})", stringify!(processor));
+        self.rollback_pipes.push(Box::new(RollbackPipe {
+            processor: Box::new(processor),
+            error_policy: self.error_policy,
+        }));
         self
     }
 
-    /// Adds an account pipe to process account updates.
+    /// Adds an instruction pipe to process instructions within transactions.
     ///
-    /// Account pipes decode and process updates to accounts within the
-    /// pipeline. This method requires both an `AccountDecoder` and a
-    /// `Processor` to handle decoded account data.
+    /// Instruction pipes decode and process individual instructions,
+    /// enabling specialized handling of various instruction types.
     ///
     /// # Parameters
     ///
-    /// - `decoder`: An `AccountDecoder` that decodes the account data.
-    /// - `processor`: A `Processor` that processes the decoded account data.
+    /// - `decoder`: An `InstructionDecoder` for decoding instructions from
+    ///   transaction data.
+    /// - `processor`: A `Processor` that processes decoded instruction data.
     ///
     /// # Example
     ///
@@ -842,38 +3232,79 @@ impl PipelineBuilder {
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .account(MyAccountDecoder, MyAccountProcessor);
+    ///     .instruction(MyDecoder, MyInstructionProcessor);
     /// ```
-    pub fn account<T: Send + Sync + 'static>(
+    pub fn instruction<T: Send + Sync + 'static>(
         mut self,
-        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
-        processor: impl Processor<InputType = AccountProcessorInputType<T>> + Send + Sync + 'static,
+        decoder: impl for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync + 'static,
     ) -> Self {
         log::trace!(
-            "account(self, decoder: {:?}, processor: {:?})",
+            "instruction(self, decoder: {:?}, processor: {:?})",
             stringify!(decoder),
             stringify!(processor)
         );
-        self.account_pipes.push(Box::new(AccountPipe {
-            decoder: Box::new(decoder),
+        self.instruction_pipes.push(Box::new(InstructionPipe {
+            decoder: Arc::new(decoder),
             processor: Box::new(processor),
-            filters: vec![],
+            filters: Arc::default(),
+            error_policy: self.error_policy,
         }));
         self
     }
 
-    /// Adds an account pipe with filters to process account updates selectively.
+    /// Adds an instruction pipe that processes decoded instructions in
+    /// batches.
     ///
-    /// This method creates an account pipe that only processes updates that pass
-    /// all the specified filters. Filters can be used to selectively process
-    /// updates based on criteria such as datasource ID, account properties, or
-    /// other custom logic.
+    /// The `BatchProcessor` receives the decoded instructions as a slice,
+    /// flushed once `max_batch_size` instructions are buffered or
+    /// `max_linger` has passed since the first of them. Pending instructions
+    /// are also flushed before every checkpoint commit and on shutdown.
     ///
     /// # Parameters
     ///
-    /// - `decoder`: An `AccountDecoder` that decodes the account data
-    /// - `processor`: A `Processor` that processes the decoded account data
-    /// - `filters`: A collection of filters that determine which account updates
+    /// - `decoder`: An `InstructionDecoder` that decodes the instructions.
+    /// - `processor`: A `BatchProcessor` that processes batches of decoded
+    ///   instructions.
+    /// - `config`: The `BatchConfig` deciding when a batch is flushed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{batch::BatchConfig, pipeline::PipelineBuilder};
+    ///
+    /// let builder = PipelineBuilder::new().instruction_batched(
+    ///     MyDecoder,
+    ///     PostgresInstructionProcessor::new(pool),
+    ///     BatchConfig::default(),
+    /// );
+    /// ```
+    pub fn instruction_batched<T: Send + Sync + 'static>(
+        self,
+        decoder: impl for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static,
+        processor: impl BatchProcessor<InputType = InstructionProcessorInputType<T>>
+            + Send
+            + Sync
+            + 'static,
+        config: BatchConfig,
+    ) -> Self {
+        log::trace!("instruction_batched(self, decoder, processor, config: {config:?})");
+        self.instruction(decoder, BatchingProcessor::new(processor, config))
+    }
+
+    /// Adds an instruction pipe with filters to process instructions selectively.
+    ///
+    /// This method creates an instruction pipe that only processes instructions
+    /// that pass all the specified filters. Filters can be used to selectively
+    /// process instructions based on criteria such as datasource ID, instruction
+    /// type, or other custom logic.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `InstructionDecoder` for decoding instructions from
+    ///   transaction data
+    /// - `processor`: A `Processor` that processes decoded instruction data
+    /// - `filters`: A collection of filters that determine which instructions
     ///   should be processed
     ///
     /// # Example
@@ -890,36 +3321,39 @@ impl PipelineBuilder {
     /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .account_with_filters(MyAccountDecoder, MyAccountProcessor, filters);
+    ///     .instruction_with_filters(MyDecoder, MyInstructionProcessor, filters);
     /// ```
-    pub fn account_with_filters<T: Send + Sync + 'static>(
+    pub fn instruction_with_filters<T: Send + Sync + 'static>(
         mut self,
-        decoder: impl for<'a> AccountDecoder<'a, AccountType = T> + Send + Sync + 'static,
-        processor: impl Processor<InputType = AccountProcessorInputType<T>> + Send + Sync + 'static,
+        decoder: impl for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync + 'static,
         filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
     ) -> Self {
         log::trace!(
-            "account_with_filters(self, decoder: {:?}, processor: {:?}, filters: {:?})",
+            "instruction_with_filters(self, decoder: {:?}, processor: {:?}, filters: {:?})",
             stringify!(decoder),
             stringify!(processor),
             stringify!(filters)
         );
-        self.account_pipes.push(Box::new(AccountPipe {
-            decoder: Box::new(decoder),
+        self.instruction_pipes.push(Box::new(InstructionPipe {
+            decoder: Arc::new(decoder),
             processor: Box::new(processor),
-            filters,
+            filters: Arc::new(filters),
+            error_policy: self.error_policy,
         }));
         self
     }
 
-    /// Adds an account deletion pipe to handle account deletion events.
+    /// Adds an event pipe, which decodes the events emitted by instructions,
+    /// through self-CPI event instructions or `Program data:` logs.
     ///
-    /// Account deletion pipes process deletions of accounts, with a `Processor`
-    /// to handle the deletion events as they occur.
+    /// The processor receives each event along with the `InstructionMetadata`
+    /// of the instruction that emitted it. See the `event` module.
     ///
     /// # Parameters
     ///
-    /// - `processor`: A `Processor` that processes account deletion events.
+    /// - `decoder`: An `EventDecoder` that decodes the events.
+    /// - `processor`: A `Processor` that processes the decoded events.
     ///
     /// # Example
     ///
@@ -927,36 +3361,122 @@ impl PipelineBuilder {
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .account_deletions(MyAccountDeletionProcessor);
+    ///     .event(MyEventDecoder, MyEventProcessor);
     /// ```
-    pub fn account_deletions(
+    pub fn event<T: Send + Sync + 'static>(
+        self,
+        decoder: impl EventDecoder<EventType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = EventProcessorInputType<T>> + Send + Sync + 'static,
+    ) -> Self {
+        log::trace!(
+            "event(self, decoder: {:?}, processor: {:?})",
+            stringify!(decoder),
+            stringify!(processor)
+        );
+        self.event_with_filters(decoder, processor, vec![])
+    }
+
+    /// Adds an event pipe with filters, which decide which instructions are
+    /// searched for events.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: An `EventDecoder` that decodes the events
+    /// - `processor`: A `Processor` that processes the decoded events
+    /// - `filters`: A collection of filters that determine which instruction
+    ///   updates are searched for events
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::{filter::TransactionStatusFilter, pipeline::PipelineBuilder};
+    ///
+    /// let filters = vec![
+    ///     Box::new(TransactionStatusFilter::Succeeded) as Box<dyn carbon_core::filter::Filter>,
+    /// ];
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .event_with_filters(MyEventDecoder, MyEventProcessor, filters);
+    /// ```
+    pub fn event_with_filters<T: Send + Sync + 'static>(
         mut self,
-        processor: impl Processor<InputType = AccountDeletion> + Send + Sync + 'static,
+        decoder: impl EventDecoder<EventType = T> + Send + Sync + 'static,
+        processor: impl Processor<InputType = EventProcessorInputType<T>> + Send + Sync + 'static,
+        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
     ) -> Self {
         log::trace!(
-            "account_deletions(self, processor: {:?})",
+            "event_with_filters(self, decoder: {:?}, processor: {:?}, filters: {:?})",
+            stringify!(decoder),
+            stringify!(processor),
+            stringify!(filters)
+        );
+        self.instruction_pipes.push(Box::new(EventPipe::new(
+            Arc::new(decoder),
+            Box::new(processor),
+            Arc::new(filters),
+            self.error_policy,
+        )));
+        self
+    }
+
+    /// Adds a transaction pipe for processing full transaction data.
+    ///
+    /// This method requires a transaction schema for decoding and a `Processor`
+    /// to handle the processed transaction data.
+    ///
+    /// # Parameters
+    ///
+    /// - `schema`: A `TransactionSchema` used to match and interpret
+    ///   transaction data.
+    /// - `processor`: A `Processor` that processes the decoded transaction
+    ///   data.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::PipelineBuilder;
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .transaction(MY_SCHEMA.clone(), MyTransactionProcessor);
+    /// ```
+    pub fn transaction<T, U>(
+        mut self,
+        processor: impl Processor<InputType = TransactionProcessorInputType<T, U>>
+            + Send
+            + Sync
+            + 'static,
+        schema: Option<TransactionSchema<T>>,
+    ) -> Self
+    where
+        T: InstructionDecoderCollection + 'static,
+        U: DeserializeOwned + Send + Sync + 'static,
+    {
+        log::trace!(
+            "transaction(self, schema: {:?}, processor: {:?})",
+            stringify!(schema),
             stringify!(processor)
         );
-        self.account_deletion_pipes
-            .push(Box::new(AccountDeletionPipe {
-                processor: Box::new(processor),
-                filters: vec![],
-            }));
+        self.transaction_pipes.push(Box::new(
+            TransactionPipe::<T, U>::new(schema, processor, vec![])
+                .with_error_policy(self.error_policy),
+        ));
         self
     }
 
-    /// Adds an account deletion pipe with filters to handle account deletion events selectively.
+    /// Adds a transaction pipe with filters for processing full transaction data selectively.
     ///
-    /// This method creates an account deletion pipe that only processes deletion
-    /// events that pass all the specified filters. Filters can be used to
-    /// selectively process deletions based on criteria such as datasource ID or
-    /// other custom logic.
+    /// This method creates a transaction pipe that only processes transactions
+    /// that pass all the specified filters. Filters can be used to selectively
+    /// process transactions based on criteria such as datasource ID, transaction
+    /// type, or other custom logic.
     ///
     /// # Parameters
     ///
-    /// - `processor`: A `Processor` that processes account deletion events
-    /// - `filters`: A collection of filters that determine which account deletion
-    ///   events should be processed
+    /// - `processor`: A `Processor` that processes the decoded transaction data
+    /// - `schema`: A `TransactionSchema` used to match and interpret
+    ///   transaction data
+    /// - `filters`: A collection of filters that determine which transactions
+    ///   should be processed
     ///
     /// # Example
     ///
@@ -972,328 +3492,334 @@ impl PipelineBuilder {
     /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .account_deletions_with_filters(MyAccountDeletionProcessor, filters);
+    ///     .transaction_with_filters(MyTransactionProcessor, MY_SCHEMA.clone(), filters);
     /// ```
-    pub fn account_deletions_with_filters(
+    pub fn transaction_with_filters<T, U>(
         mut self,
-        processor: impl Processor<InputType = AccountDeletion> + Send + Sync + 'static,
+        processor: impl Processor<InputType = TransactionProcessorInputType<T, U>>
+            + Send
+            + Sync
+            + 'static,
+        schema: Option<TransactionSchema<T>>,
         filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
-    ) -> Self {
+    ) -> Self
+    where
+        T: InstructionDecoderCollection + 'static,
+        U: DeserializeOwned + Send + Sync + 'static,
+    {
         log::trace!(
-            "account_deletions_with_filters(self, processor: {:?}, filters: {:?})",
+            "transaction_with_filters(self, schema: {:?}, processor: {:?}, filters: {:?})",
+            stringify!(schema),
             stringify!(processor),
             stringify!(filters)
         );
-        self.account_deletion_pipes
-            .push(Box::new(AccountDeletionPipe {
-                processor: Box::new(processor),
-                filters,
-            }));
+        self.transaction_pipes.push(Box::new(
+            TransactionPipe::<T, U>::new(schema, processor, filters)
+                .with_error_policy(self.error_policy),
+        ));
         self
     }
 
-    /// Adds a block details pipe to handle block details updates.
+    /// Adds a metrics component to the pipeline for performance tracking.
     ///
-    /// Block details pipes process updates related to block metadata, such as
-    /// slot, block hash, and rewards, with a `Processor` to handle the updates.
+    /// This component collects and reports on pipeline metrics, providing
+    /// insights into performance and operational statistics.
     ///
     /// # Parameters
     ///
-    /// - `processor`: A `Processor` that processes block details updates.
+    /// - `metrics`: An instance of a `Metrics` implementation, used to gather
+    ///   and report metrics.
     ///
     /// # Example
     ///
     /// ```ignore
+    /// use std::sync::Arc;
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .block_details(MyBlockDetailsProcessor);
+    ///     .metrics(Arc::new(LogMetrics::new()));
     /// ```
-    pub fn block_details(
-        mut self,
-        processor: impl Processor<InputType = BlockDetails> + Send + Sync + 'static,
-    ) -> Self {
-        log::trace!(
-            "block_details(self, processor: {:?})",
-            stringify!(processor)
-        );
-        self.block_details_pipes.push(Box::new(BlockDetailsPipe {
-            processor: Box::new(processor),
-            filters: vec![],
-        }));
+    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        log::trace!("metrics(self, metrics: {:?})", stringify!(metrics));
+        self.metrics.metrics.push(metrics);
         self
     }
 
-    /// Adds a block details pipe with filters to handle block details updates selectively.
+    /// Sets the interval for flushing metrics data.
     ///
-    /// This method creates a block details pipe that only processes updates that
-    /// pass all the specified filters. Filters can be used to selectively process
-    /// block details updates based on criteria such as datasource ID, block height,
-    /// or other custom logic.
+    /// This value defines the frequency, in seconds, at which metrics data is
+    /// flushed from memory. If not set, a default interval is used.
     ///
     /// # Parameters
     ///
-    /// - `processor`: A `Processor` that processes block details updates
-    /// - `filters`: A collection of filters that determine which block details
-    ///   updates should be processed
+    /// - `interval`: The flush interval for metrics, in seconds.
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// use carbon_core::{
-    ///     pipeline::PipelineBuilder,
-    ///     datasource::DatasourceId,
-    ///     filter::DatasourceFilter,
-    /// };
+    /// ```rust
+    /// use carbon_core::pipeline::PipelineBuilder;
     ///
-    /// let mainnet_id = DatasourceId::new_named("mainnet");
-    /// let filter = DatasourceFilter::new(mainnet_id);
-    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    /// let builder = PipelineBuilder::new()
+    ///     .metrics_flush_interval(60);
+    /// ```
+    pub fn metrics_flush_interval(mut self, interval: u64) -> Self {
+        log::trace!("metrics_flush_interval(self, interval: {interval:?})");
+        self.metrics_flush_interval = Some(interval);
+        self
+    }
+
+    /// Sets the cancellation token for cancelling datasource on demand.
+    ///
+    /// This value is used to cancel datasource on demand.
+    /// If not set, a default `CancellationToken` is used.
+    ///
+    /// # Parameters
+    ///
+    /// - `cancellation_token`: An instance of `CancellationToken`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use carbon_core::pipeline::PipelineBuilder;
+    /// use tokio_util::sync::CancellationToken;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .block_details_with_filters(MyBlockDetailsProcessor, filters);
+    ///     .datasource_cancellation_token(CancellationToken::new());
     /// ```
-    pub fn block_details_with_filters(
-        mut self,
-        processor: impl Processor<InputType = BlockDetails> + Send + Sync + 'static,
-        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
-    ) -> Self {
+    pub fn datasource_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
         log::trace!(
-            "block_details_with_filters(self, processor: {:?}, filters: {:?})",
-            stringify!(processor),
-            stringify!(filters)
+            "datasource_cancellation_token(self, cancellation_token: {cancellation_token:?})"
         );
-        self.block_details_pipes.push(Box::new(BlockDetailsPipe {
-            processor: Box::new(processor),
-            filters,
-        }));
+        self.datasource_cancellation_token = Some(cancellation_token);
         self
     }
 
-    /// Adds an instruction pipe to process instructions within transactions.
+    /// Sets the size of the channel buffer for the pipeline.
     ///
-    /// Instruction pipes decode and process individual instructions,
-    /// enabling specialized handling of various instruction types.
+    /// This value defines the maximum number of updates that can be queued in
+    /// the pipeline's channel buffer. If not set, a default size of 10_000
+    /// will be used.
     ///
     /// # Parameters
     ///
-    /// - `decoder`: An `InstructionDecoder` for decoding instructions from
-    ///   transaction data.
-    /// - `processor`: A `Processor` that processes decoded instruction data.
+    /// - `size`: The size of the channel buffer for the pipeline.
     ///
     /// # Example
     ///
-    /// ```ignore
+    /// ```rust
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .instruction(MyDecoder, MyInstructionProcessor);
+    ///     .channel_buffer_size(1000);
     /// ```
-    pub fn instruction<T: Send + Sync + 'static>(
-        mut self,
-        decoder: impl for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static,
-        processor: impl Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync + 'static,
-    ) -> Self {
-        log::trace!(
-            "instruction(self, decoder: {:?}, processor: {:?})",
-            stringify!(decoder),
-            stringify!(processor)
-        );
-        self.instruction_pipes.push(Box::new(InstructionPipe {
-            decoder: Box::new(decoder),
-            processor: Box::new(processor),
-            filters: vec![],
-        }));
+    pub fn channel_buffer_size(mut self, size: usize) -> Self {
+        log::trace!("channel_buffer_size(self, size: {size:?})");
+        self.channel_buffer_size = size;
         self
     }
 
-    /// Adds an instruction pipe with filters to process instructions selectively.
+    /// Sets the execution mode for the pipeline.
     ///
-    /// This method creates an instruction pipe that only processes instructions
-    /// that pass all the specified filters. Filters can be used to selectively
-    /// process instructions based on criteria such as datasource ID, instruction
-    /// type, or other custom logic.
+    /// By default the pipeline processes updates sequentially. Use
+    /// `ExecutionMode::Concurrent` to spread updates over a pool of workers
+    /// while keeping updates for the same pubkey or signature in order.
     ///
     /// # Parameters
     ///
-    /// - `decoder`: An `InstructionDecoder` for decoding instructions from
-    ///   transaction data
-    /// - `processor`: A `Processor` that processes decoded instruction data
-    /// - `filters`: A collection of filters that determine which instructions
-    ///   should be processed
+    /// - `execution_mode`: A variant of [`ExecutionMode`] that determines how
+    ///   updates are dispatched to the pipes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use carbon_core::pipeline::{ExecutionMode, PipelineBuilder};
+    ///
+    /// let builder = PipelineBuilder::new().execution_mode(ExecutionMode::Concurrent {
+    ///     workers: 8,
+    ///     worker_queue_size: 1_000,
+    /// });
+    /// ```
+    pub fn execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        log::trace!("execution_mode(self, execution_mode: {execution_mode:?})");
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    /// Sets the `AccountStore` kept up to date by the pipeline.
+    ///
+    /// Every account update received from any datasource is written to the
+    /// store, and every account deletion removes the account, before the
+    /// update reaches the pipes. Hand clones of the store to processors that
+    /// need the current state of related accounts.
+    ///
+    /// # Parameters
+    ///
+    /// - `account_store`: The `AccountStore` to keep up to date.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use carbon_core::{
-    ///     pipeline::PipelineBuilder,
-    ///     datasource::DatasourceId,
-    ///     filter::DatasourceFilter,
-    /// };
+    /// use carbon_core::account_store::{AccountStore, AccountStoreConfig};
     ///
-    /// let mainnet_id = DatasourceId::new_named("mainnet");
-    /// let filter = DatasourceFilter::new(mainnet_id);
-    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    /// let store = AccountStore::new(AccountStoreConfig::default());
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .instruction_with_filters(MyDecoder, MyInstructionProcessor, filters);
+    ///     .account_store(store.clone())
+    ///     .instruction(MyDecoder, MyProcessor { store });
     /// ```
-    pub fn instruction_with_filters<T: Send + Sync + 'static>(
-        mut self,
-        decoder: impl for<'a> InstructionDecoder<'a, InstructionType = T> + Send + Sync + 'static,
-        processor: impl Processor<InputType = InstructionProcessorInputType<T>> + Send + Sync + 'static,
-        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
-    ) -> Self {
-        log::trace!(
-            "instruction_with_filters(self, decoder: {:?}, processor: {:?}, filters: {:?})",
-            stringify!(decoder),
-            stringify!(processor),
-            stringify!(filters)
-        );
-        self.instruction_pipes.push(Box::new(InstructionPipe {
-            decoder: Box::new(decoder),
-            processor: Box::new(processor),
-            filters,
-        }));
+    ///
+    /// # Notes
+    ///
+    /// - Duplicates dropped by the deduplication stage and updates held back
+    ///   by the commitment buffer reach the store only once they are passed
+    ///   on to the pipes.
+    pub fn account_store(mut self, account_store: AccountStore) -> Self {
+        log::trace!("account_store(self, account_store)");
+        self.account_store = Some(account_store);
         self
     }
 
-    /// Adds a transaction pipe for processing full transaction data.
+    /// Enables the deduplication stage for the pipeline.
     ///
-    /// This method requires a transaction schema for decoding and a `Processor`
-    /// to handle the processed transaction data.
+    /// With deduplication enabled, transactions are dropped if their signature
+    /// was already received, and account updates are dropped if an update with
+    /// the same pubkey, slot and data was already received, from any
+    /// datasource. This makes it possible to run redundant datasources side by
+    /// side without processing every update twice.
     ///
     /// # Parameters
     ///
-    /// - `schema`: A `TransactionSchema` used to match and interpret
-    ///   transaction data.
-    /// - `processor`: A `Processor` that processes the decoded transaction
-    ///   data.
+    /// - `config`: A [`DeduplicationConfig`] defining how many slots of
+    ///   updates are remembered.
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// use carbon_core::pipeline::PipelineBuilder;
+    /// ```rust
+    /// use carbon_core::{dedup::DeduplicationConfig, pipeline::PipelineBuilder};
     ///
-    /// let builder = PipelineBuilder::new()
-    ///     .transaction(MY_SCHEMA.clone(), MyTransactionProcessor);
+    /// let builder = PipelineBuilder::new().deduplication(DeduplicationConfig::default());
     /// ```
-    pub fn transaction<T, U>(
-        mut self,
-        processor: impl Processor<InputType = TransactionProcessorInputType<T, U>>
-            + Send
-            + Sync
-            + 'static,
-        schema: Option<TransactionSchema<T>>,
-    ) -> Self
-    where
-        T: InstructionDecoderCollection + 'static,
-        U: DeserializeOwned + Send + Sync + 'static,
-    {
-        log::trace!(
-            "transaction(self, schema: {:?}, processor: {:?})",
-            stringify!(schema),
-            stringify!(processor)
-        );
-        self.transaction_pipes
-            .push(Box::new(TransactionPipe::<T, U>::new(
-                schema,
-                processor,
-                vec![],
-            )));
+    ///
+    /// # Notes
+    ///
+    /// - Dropped duplicates are counted by the `updates_duplicate` counter and
+    ///   per datasource by `updates_duplicate.{datasource_id}`, or
+    ///   `updates_duplicate.unnamed` for datasources without a name.
+    pub fn deduplication(mut self, config: DeduplicationConfig) -> Self {
+        log::trace!("deduplication(self, config: {config:?})");
+        self.deduplication = Some(config);
+        self
+    }
+
+    /// Enables the commitment buffer for the pipeline.
+    ///
+    /// With the commitment buffer enabled, the updates of each slot are held
+    /// back until a slot status update reports that the slot reached the
+    /// target commitment, and are discarded if the slot is reported dead or
+    /// ends up on an abandoned fork. This allows a datasource streaming at
+    /// `processed` commitment to feed pipes that only want confirmed or
+    /// finalized data.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: A [`CommitmentBufferConfig`] defining the target commitment
+    ///   and the maximum number of buffered slots.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use carbon_core::{
+    ///     commitment_buffer::{CommitmentBufferConfig, TargetCommitment},
+    ///     pipeline::PipelineBuilder,
+    /// };
+    ///
+    /// let builder = PipelineBuilder::new().commitment_buffer(CommitmentBufferConfig {
+    ///     target: TargetCommitment::Finalized,
+    ///     ..Default::default()
+    /// });
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - At least one datasource must emit slot status updates, otherwise no
+    ///   update is ever released.
+    pub fn commitment_buffer(mut self, config: CommitmentBufferConfig) -> Self {
+        log::trace!("commitment_buffer(self, config: {config:?})");
+        self.commitment_buffer = Some(config);
         self
     }
 
-    /// Adds a transaction pipe with filters for processing full transaction data selectively.
+    /// Sets the store that the pipeline commits checkpoints to.
     ///
-    /// This method creates a transaction pipe that only processes transactions
-    /// that pass all the specified filters. Filters can be used to selectively
-    /// process transactions based on criteria such as datasource ID, transaction
-    /// type, or other custom logic.
+    /// While running, the pipeline tracks the last fully processed slot (and
+    /// transaction signature) of every datasource and periodically commits it
+    /// to the store. Datasources that support resuming can be given the same
+    /// store to continue from their last checkpoint after a restart.
     ///
     /// # Parameters
     ///
-    /// - `processor`: A `Processor` that processes the decoded transaction data
-    /// - `schema`: A `TransactionSchema` used to match and interpret
-    ///   transaction data
-    /// - `filters`: A collection of filters that determine which transactions
-    ///   should be processed
+    /// - `checkpoint_store`: An `Arc<dyn CheckpointStore>` to commit
+    ///   checkpoints to.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use carbon_core::{
-    ///     pipeline::PipelineBuilder,
-    ///     datasource::DatasourceId,
-    ///     filter::DatasourceFilter,
-    /// };
+    /// use std::sync::Arc;
+    /// use carbon_core::{checkpoint::FileCheckpointStore, datasource::DatasourceId};
     ///
-    /// let mainnet_id = DatasourceId::new_named("mainnet");
-    /// let filter = DatasourceFilter::new(mainnet_id);
-    /// let filters = vec![Box::new(filter) as Box<dyn carbon_core::filter::Filter>];
+    /// let store = Arc::new(FileCheckpointStore::new("checkpoints.json")?);
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .transaction_with_filters(MyTransactionProcessor, MY_SCHEMA.clone(), filters);
+    ///     .datasource_with_id(
+    ///         block_crawler.with_checkpoint_store(store.clone()),
+    ///         DatasourceId::new_named("block-crawler"),
+    ///     )
+    ///     .checkpoint_store(store);
     /// ```
-    pub fn transaction_with_filters<T, U>(
-        mut self,
-        processor: impl Processor<InputType = TransactionProcessorInputType<T, U>>
-            + Send
-            + Sync
-            + 'static,
-        schema: Option<TransactionSchema<T>>,
-        filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
-    ) -> Self
-    where
-        T: InstructionDecoderCollection + 'static,
-        U: DeserializeOwned + Send + Sync + 'static,
-    {
+    ///
+    /// # Notes
+    ///
+    /// - Checkpoints are keyed by `DatasourceId`. Register datasources with
+    ///   `datasource_with_id` and a stable name, since IDs generated by
+    ///   `datasource` change on every run.
+    pub fn checkpoint_store(mut self, checkpoint_store: Arc<dyn CheckpointStore>) -> Self {
         log::trace!(
-            "transaction_with_filters(self, schema: {:?}, processor: {:?}, filters: {:?})",
-            stringify!(schema),
-            stringify!(processor),
-            stringify!(filters)
+            "checkpoint_store(self, checkpoint_store: {:?})",
+            stringify!(checkpoint_store)
         );
-        self.transaction_pipes
-            .push(Box::new(TransactionPipe::<T, U>::new(
-                schema, processor, filters,
-            )));
+        self.checkpoint_store = Some(checkpoint_store);
         self
     }
 
-    /// Adds a metrics component to the pipeline for performance tracking.
-    ///
-    /// This component collects and reports on pipeline metrics, providing
-    /// insights into performance and operational statistics.
+    /// Sets the interval for committing checkpoints.
     ///
     /// # Parameters
     ///
-    /// - `metrics`: An instance of a `Metrics` implementation, used to gather
-    ///   and report metrics.
+    /// - `interval`: The duration (in seconds) between checkpoint commits.
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// use std::sync::Arc;
+    /// ```rust
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .metrics(Arc::new(LogMetrics::new()));
+    ///     .checkpoint_interval(30);
     /// ```
-    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
-        log::trace!("metrics(self, metrics: {:?})", stringify!(metrics));
-        self.metrics.metrics.push(metrics);
+    pub fn checkpoint_interval(mut self, interval: u64) -> Self {
+        log::trace!("checkpoint_interval(self, interval: {interval:?})");
+        self.checkpoint_interval = Some(interval);
         self
     }
 
-    /// Sets the interval for flushing metrics data.
+    /// Sets the interval for flushing the processors of the pipes.
     ///
-    /// This value defines the frequency, in seconds, at which metrics data is
-    /// flushed from memory. If not set, a default interval is used.
+    /// Processors that buffer their inputs, such as a `BatchingProcessor`,
+    /// write them out on every flush. The pipes are also flushed before every
+    /// checkpoint commit and on shutdown.
     ///
     /// # Parameters
     ///
-    /// - `interval`: The flush interval for metrics, in seconds.
+    /// - `interval`: The duration (in seconds) between flushes.
     ///
     /// # Example
     ///
@@ -1301,64 +3827,173 @@ impl PipelineBuilder {
     /// use carbon_core::pipeline::PipelineBuilder;
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .metrics_flush_interval(60);
+    ///     .flush_interval(1);
     /// ```
-    pub fn metrics_flush_interval(mut self, interval: u64) -> Self {
-        log::trace!("metrics_flush_interval(self, interval: {interval:?})");
-        self.metrics_flush_interval = Some(interval);
+    pub fn flush_interval(mut self, interval: u64) -> Self {
+        log::trace!("flush_interval(self, interval: {interval:?})");
+        self.flush_interval = Some(interval);
         self
     }
 
-    /// Sets the cancellation token for cancelling datasource on demand.
+    /// Sets the `ErrorPolicy` of the pipes added after this call.
     ///
-    /// This value is used to cancel datasource on demand.
-    /// If not set, a default `CancellationToken` is used.
+    /// Pipes keep the policy that was set when they were added, so different
+    /// pipes can be given different policies by calling this method between
+    /// them. Pipes added before any call use `ErrorPolicy::Skip`.
     ///
     /// # Parameters
     ///
-    /// - `cancellation_token`: An instance of `CancellationToken`.
+    /// - `error_policy`: The `ErrorPolicy` to apply when a pipe fails to
+    ///   process an update.
     ///
     /// # Example
     ///
-    /// ```rust
-    /// use carbon_core::pipeline::PipelineBuilder;
-    /// use tokio_util::sync::CancellationToken;
+    /// ```ignore
+    /// use carbon_core::{error_policy::ErrorPolicy, pipeline::PipelineBuilder};
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .datasource_cancellation_token(CancellationToken::new());
+    ///     .error_policy(ErrorPolicy::retry(5))
+    ///     .instruction(MyDecoder, MyDatabaseProcessor)
+    ///     .error_policy(ErrorPolicy::Skip)
+    ///     .instruction(MyDecoder, MyLogProcessor);
     /// ```
-    pub fn datasource_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+    pub fn error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        log::trace!("error_policy(self, error_policy: {error_policy:?})");
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Sets the sink receiving the updates that pipes failed to process.
+    ///
+    /// An update is dead-lettered once per pipe that gave up on it, either
+    /// right away under `ErrorPolicy::Skip` or after the last attempt of
+    /// `ErrorPolicy::Retry`.
+    ///
+    /// # Parameters
+    ///
+    /// - `dead_letter_sink`: The `DeadLetterSink` to send failed updates to.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use std::sync::Arc;
+    /// use carbon_core::{dead_letter::JsonlDeadLetterSink, pipeline::PipelineBuilder};
+    ///
+    /// let builder = PipelineBuilder::new()
+    ///     .dead_letter_sink(Arc::new(JsonlDeadLetterSink::new("dead_letters.jsonl")));
+    /// ```
+    pub fn dead_letter_sink(mut self, dead_letter_sink: Arc<dyn DeadLetterSink>) -> Self {
         log::trace!(
-            "datasource_cancellation_token(self, cancellation_token: {cancellation_token:?})"
+            "dead_letter_sink(self, dead_letter_sink: {:?})",
+            stringify!(dead_letter_sink)
         );
-        self.datasource_cancellation_token = Some(cancellation_token);
+        self.dead_letter_sink = Some(dead_letter_sink);
         self
     }
 
-    /// Sets the size of the channel buffer for the pipeline.
-    ///
-    /// This value defines the maximum number of updates that can be queued in
-    /// the pipeline's channel buffer. If not set, a default size of 10_000
-    /// will be used.
+    /// Sets the `RestartPolicy` applied to datasources that fail.
     ///
     /// # Parameters
     ///
-    /// - `size`: The size of the channel buffer for the pipeline.
+    /// - `restart_policy`: The `RestartPolicy` deciding whether and when a
+    ///   datasource whose `consume` returned an error is started again.
     ///
     /// # Example
     ///
-    /// ```rust
-    /// use carbon_core::pipeline::PipelineBuilder;
+    /// ```ignore
+    /// use carbon_core::{pipeline::PipelineBuilder, supervisor::RestartPolicy};
     ///
     /// let builder = PipelineBuilder::new()
-    ///     .channel_buffer_size(1000);
+    ///     .restart_policy(RestartPolicy {
+    ///         max_restarts: None,
+    ///         ..RestartPolicy::default()
+    ///     });
     /// ```
-    pub fn channel_buffer_size(mut self, size: usize) -> Self {
-        log::trace!("channel_buffer_size(self, size: {size:?})");
-        self.channel_buffer_size = size;
+    ///
+    /// # Notes
+    ///
+    /// - Datasources are restarted by calling `consume` again, so datasources
+    ///   that keep track of their position resume from where they stopped, and
+    ///   others start over.
+    /// - Use `RestartPolicy::never()` to disable restarts.
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        log::trace!("restart_policy(self, restart_policy: {restart_policy:?})");
+        self.restart_policy = restart_policy;
         self
     }
 
+    /// Returns the programs the pipes added so far process.
+    ///
+    /// The program ids are collected from the decoders of the instruction,
+    /// event, transaction and account pipes. Datasources that subscribe by
+    /// program, such as Yellowstone gRPC, can build their filters from them
+    /// instead of repeating the program ids of the decoders.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use carbon_core::pipeline::Pipeline;
+    ///
+    /// let builder = Pipeline::builder()
+    ///     .instruction(KaminoLendingDecoder, KaminoLendingInstructionProcessor)
+    ///     .account(KaminoLendingDecoder, KaminoLendingAccountProcessor);
+    ///
+    /// let programs = builder.programs();
+    /// let datasource = YellowstoneGrpcGeyserClient::new(
+    ///     endpoint,
+    ///     x_token,
+    ///     Some(CommitmentLevel::Confirmed),
+    ///     account_filters_for_programs(&programs),
+    ///     transaction_filters_for_programs(&programs),
+    ///     Default::default(),
+    ///     Arc::new(RwLock::new(HashSet::new())),
+    ///     Default::default(),
+    ///     None,
+    ///     None,
+    /// );
+    ///
+    /// builder.datasource(datasource).build()?.run().await?;
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Call it once every pipe has been added, as pipes added afterwards
+    ///   are not taken into account.
+    pub fn programs(&self) -> PipelinePrograms {
+        log::trace!("programs(self)");
+
+        let instruction_programs = if self.block_pipes.is_empty() {
+            union_program_ids(
+                self.instruction_pipes
+                    .iter()
+                    .map(|pipe| pipe.program_ids())
+                    .chain(self.transaction_pipes.iter().map(|pipe| pipe.program_ids())),
+            )
+        } else {
+            None
+        };
+
+        let include_votes = !self.block_pipes.is_empty()
+            || instruction_programs
+                .as_ref()
+                .is_some_and(|programs| programs.contains(&VOTE_PROGRAM_ID))
+            || self
+                .instruction_pipes
+                .iter()
+                .map(|pipe| pipe.filters())
+                .chain(self.transaction_pipes.iter().map(|pipe| pipe.filters()))
+                .flatten()
+                .any(|filter| filter.votes() == Some(true));
+
+        PipelinePrograms {
+            instruction_programs,
+            account_owners: union_program_ids(
+                self.account_pipes.iter().map(|pipe| pipe.program_ids()),
+            ),
+            include_votes,
+        }
+    }
+
     /// Builds and returns a `Pipeline` configured with the specified
     /// components.
     ///
@@ -1397,11 +4032,29 @@ impl PipelineBuilder {
     /// ```
     pub fn build(self) -> CarbonResult<Pipeline> {
         log::trace!("build(self)");
+        if let ExecutionMode::Concurrent {
+            workers,
+            worker_queue_size,
+        } = self.execution_mode
+        {
+            if workers == 0 || worker_queue_size == 0 {
+                return Err(Error::Custom(
+                    "concurrent execution requires at least one worker and a non-zero queue size"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let datasource_health = Arc::new(DatasourceHealth::default());
+
         Ok(Pipeline {
             datasources: self.datasources,
             account_pipes: self.account_pipes,
             account_deletion_pipes: self.account_deletion_pipes,
             block_details_pipes: self.block_details_pipes,
+            block_pipes: self.block_pipes,
+            slot_status_pipes: self.slot_status_pipes,
+            rollback_pipes: self.rollback_pipes,
             instruction_pipes: self.instruction_pipes,
             transaction_pipes: self.transaction_pipes,
             shutdown_strategy: self.shutdown_strategy,
@@ -1409,6 +4062,516 @@ impl PipelineBuilder {
             metrics_flush_interval: self.metrics_flush_interval,
             datasource_cancellation_token: self.datasource_cancellation_token,
             channel_buffer_size: self.channel_buffer_size,
+            execution_mode: self.execution_mode,
+            deduplication: self.deduplication,
+            commitment_buffer: self.commitment_buffer,
+            checkpoint_store: self.checkpoint_store,
+            checkpoint_interval: self.checkpoint_interval,
+            flush_interval: self.flush_interval,
+            dead_letter_sink: self.dead_letter_sink,
+            restart_policy: self.restart_policy,
+            datasource_health: datasource_health.clone(),
+            drain_timeout: self.drain_timeout,
+            status: Arc::new(PipelineStatus::new(
+                datasource_health,
+                self.liveness_timeout,
+            )),
+            block_timeout: self.block_timeout,
+            account_store: self.account_store,
+            ignore_shutdown_signals: self.ignore_shutdown_signals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            account::DecodedAccount, datasource::AccountUpdate, instruction::DecodedInstruction,
+        },
+        async_trait::async_trait,
+        solana_account::Account,
+    };
+
+    struct UnitDecoder;
+
+    impl AccountDecoder<'_> for UnitDecoder {
+        type AccountType = ();
+
+        fn decode_account(&self, account: &Account) -> Option<DecodedAccount<()>> {
+            Some(DecodedAccount {
+                lamports: account.lamports,
+                data: (),
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            })
+        }
+    }
+
+    /// Sends `updates` account updates, then waits for cancellation if
+    /// `wait_for_cancellation` is set.
+    struct AccountsDatasource {
+        updates: u64,
+        wait_for_cancellation: bool,
+    }
+
+    #[async_trait]
+    impl Datasource for AccountsDatasource {
+        async fn consume(
+            &self,
+            id: DatasourceId,
+            sender: mpsc::Sender<(Update, DatasourceId)>,
+            cancellation_token: CancellationToken,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            for slot in 0..self.updates {
+                let update = Update::Account(AccountUpdate {
+                    pubkey: Pubkey::new_unique(),
+                    account: Account::default(),
+                    slot,
+                    transaction_signature: None,
+                });
+                if sender.send((update, id.clone())).await.is_err() {
+                    return Ok(());
+                }
+            }
+
+            if self.wait_for_cancellation {
+                cancellation_token.cancelled().await;
+            }
+            Ok(())
+        }
+
+        fn update_types(&self) -> Vec<UpdateType> {
+            vec![UpdateType::AccountUpdate]
+        }
+    }
+
+    type Events = Arc<std::sync::Mutex<Vec<String>>>;
+
+    /// Records its lifecycle hooks as `{name}.{hook}` events.
+    struct LifecycleProcessor {
+        name: &'static str,
+        events: Events,
+        fail_init: bool,
+        delay: Duration,
+    }
+
+    impl LifecycleProcessor {
+        fn new(name: &'static str, events: &Events) -> Self {
+            Self {
+                name,
+                events: events.clone(),
+                fail_init: false,
+                delay: Duration::ZERO,
+            }
+        }
+
+        fn record(&self, hook: &str) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{}.{hook}", self.name));
+        }
+    }
+
+    #[async_trait]
+    impl Processor for LifecycleProcessor {
+        type InputType = AccountProcessorInputType<()>;
+
+        async fn process(
+            &mut self,
+            _input: Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            tokio::time::sleep(self.delay).await;
+            self.record("process");
+            Ok(())
+        }
+
+        async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+            if self.fail_init {
+                return Err(Error::Custom("init failed".to_string()));
+            }
+            self.record("init");
+            Ok(())
+        }
+
+        async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+            self.record("flush");
+            Ok(())
+        }
+
+        async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+            self.record("shutdown");
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_lifecycle_hooks_in_order() {
+        let events = Events::default();
+
+        Pipeline::builder()
+            .datasource(AccountsDatasource {
+                updates: 1,
+                wait_for_cancellation: false,
+            })
+            .account(UnitDecoder, LifecycleProcessor::new("pipe", &events))
+            .flush_interval(3600)
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap().clone();
+        assert_eq!(events.first().map(String::as_str), Some("pipe.init"));
+        assert!(events.contains(&"pipe.process".to_string()));
+        assert_eq!(
+            &events[events.len() - 2..],
+            ["pipe.flush".to_string(), "pipe.shutdown".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shuts_down_initialized_pipes_when_init_fails() {
+        let events = Events::default();
+
+        let result = Pipeline::builder()
+            .datasource(AccountsDatasource {
+                updates: 1,
+                wait_for_cancellation: false,
+            })
+            .account(UnitDecoder, LifecycleProcessor::new("first", &events))
+            .account(UnitDecoder, LifecycleProcessor::new("second", &events))
+            .account(
+                UnitDecoder,
+                LifecycleProcessor {
+                    fail_init: true,
+                    ..LifecycleProcessor::new("third", &events)
+                },
+            )
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .run()
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "first.init",
+                "second.init",
+                "second.shutdown",
+                "first.shutdown"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_sigterm_drains_until_drain_timeout() {
+        // Registering a listener first keeps the test process alive if
+        // SIGTERM is raised before the pipeline listens for it.
+        let _sigterm =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).unwrap();
+        let events = Events::default();
+
+        let mut pipeline = Pipeline::builder()
+            .datasource(AccountsDatasource {
+                updates: 100,
+                wait_for_cancellation: true,
+            })
+            .account(
+                UnitDecoder,
+                LifecycleProcessor {
+                    delay: Duration::from_millis(20),
+                    ..LifecycleProcessor::new("pipe", &events)
+                },
+            )
+            .drain_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let run = tokio::spawn(async move { pipeline.run().await });
+
+        let started = Instant::now();
+        while !run.is_finished() {
+            std::process::Command::new("kill")
+                .args(["-TERM", &std::process::id().to_string()])
+                .status()
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "pipeline did not stop"
+            );
+        }
+
+        run.await.unwrap().unwrap();
+        let events = events.lock().unwrap();
+        let processed = events
+            .iter()
+            .filter(|event| *event == "pipe.process")
+            .count();
+        assert!(processed < 100, "pending updates were not dropped");
+        assert_eq!(events.last().map(String::as_str), Some("pipe.shutdown"));
+    }
+
+    /// Waits until `event` was recorded `count` times.
+    async fn wait_for_event(events: &Events, event: &str, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|recorded| *recorded == event)
+                .count()
+                < count
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
         })
+        .await
+        .unwrap_or_else(|_| panic!("{event} was not recorded {count} times"));
+    }
+
+    #[tokio::test]
+    async fn test_adds_and_removes_pipes_at_runtime() {
+        let events = Events::default();
+        let handle = Pipeline::builder()
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .spawn();
+
+        let pipe_id = handle
+            .add_account_pipe(Box::new(AccountPipe {
+                decoder: Arc::new(UnitDecoder),
+                processor: Box::new(LifecycleProcessor::new("added", &events)),
+                filters: Arc::default(),
+                error_policy: ErrorPolicy::Skip,
+            }))
+            .await
+            .unwrap();
+        assert_eq!(pipe_id.to_string(), "account[0]");
+        assert_eq!(*events.lock().unwrap(), ["added.init"]);
+
+        let accounts = DatasourceId::new_named("accounts");
+        handle
+            .add_datasource(
+                accounts.clone(),
+                AccountsDatasource {
+                    updates: 3,
+                    wait_for_cancellation: true,
+                },
+            )
+            .await
+            .unwrap();
+        wait_for_event(&events, "added.process", 3).await;
+
+        let topology = handle.topology().await.unwrap();
+        assert_eq!(
+            topology.datasources,
+            [(accounts.clone(), supervisor::DatasourceStatus::Running)]
+        );
+        assert_eq!(topology.pipes, [pipe_id]);
+
+        handle.remove_pipe(pipe_id).await.unwrap();
+        assert!(handle.remove_pipe(pipe_id).await.is_err());
+        assert!(handle.topology().await.unwrap().pipes.is_empty());
+        let recorded = events.lock().unwrap().clone();
+        assert_eq!(
+            &recorded[recorded.len() - 2..],
+            ["added.flush", "added.shutdown"]
+        );
+
+        handle.remove_datasource(accounts.clone()).await.unwrap();
+        assert!(handle.remove_datasource(accounts).await.is_err());
+        assert!(handle.topology().await.unwrap().datasources.is_empty());
+
+        handle.join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_swaps_datasources_without_stopping() {
+        let events = Events::default();
+        let handle = Pipeline::builder()
+            .account(UnitDecoder, LifecycleProcessor::new("pipe", &events))
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .spawn();
+        let id = DatasourceId::new_named("swapped");
+
+        for round in 1..=3 {
+            handle
+                .add_datasource(
+                    id.clone(),
+                    AccountsDatasource {
+                        updates: 1,
+                        wait_for_cancellation: true,
+                    },
+                )
+                .await
+                .unwrap();
+            wait_for_event(&events, "pipe.process", round).await;
+            handle.remove_datasource(id.clone()).await.unwrap();
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!handle.is_finished());
+
+        // Re-added right after its removal, the datasource keeps the status of
+        // its new supervisor once the old one stopped.
+        handle
+            .add_datasource(
+                id.clone(),
+                AccountsDatasource {
+                    updates: 0,
+                    wait_for_cancellation: true,
+                },
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            handle.topology().await.unwrap().datasources,
+            [(id.clone(), supervisor::DatasourceStatus::Running)]
+        );
+
+        handle.remove_datasource(id).await.unwrap();
+        handle.join().await.unwrap();
+    }
+
+    /// Decodes nothing, but declares the programs it decodes.
+    struct ProgramsDecoder(Vec<Pubkey>);
+
+    impl InstructionDecoder<'_> for ProgramsDecoder {
+        type InstructionType = ();
+
+        fn decode_instruction(
+            &self,
+            _instruction: &solana_instruction::Instruction,
+        ) -> Option<DecodedInstruction<()>> {
+            None
+        }
+
+        fn program_ids(&self) -> Vec<Pubkey> {
+            self.0.clone()
+        }
+    }
+
+    impl AccountDecoder<'_> for ProgramsDecoder {
+        type AccountType = ();
+
+        fn decode_account(&self, _account: &Account) -> Option<DecodedAccount<()>> {
+            None
+        }
+
+        fn program_ids(&self) -> Vec<Pubkey> {
+            self.0.clone()
+        }
+    }
+
+    struct NoopProcessor<T>(std::marker::PhantomData<T>);
+
+    impl<T> NoopProcessor<T> {
+        fn new() -> Self {
+            Self(std::marker::PhantomData)
+        }
+    }
+
+    #[async_trait]
+    impl<T: Send + Sync> Processor for NoopProcessor<T> {
+        type InputType = T;
+
+        async fn process(
+            &mut self,
+            _input: Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_union_program_ids() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(union_program_ids(Vec::new()), Some(BTreeSet::new()));
+        assert_eq!(
+            union_program_ids([vec![a], vec![a, b]]),
+            Some(BTreeSet::from([a, b]))
+        );
+        assert_eq!(union_program_ids([vec![a], vec![]]), None);
+    }
+
+    #[test]
+    fn test_programs() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let programs = Pipeline::builder().programs();
+        assert_eq!(
+            programs,
+            PipelinePrograms {
+                instruction_programs: Some(BTreeSet::new()),
+                account_owners: Some(BTreeSet::new()),
+                include_votes: false,
+            }
+        );
+
+        let programs = Pipeline::builder()
+            .instruction(ProgramsDecoder(vec![a]), NoopProcessor::new())
+            .account(ProgramsDecoder(vec![b]), NoopProcessor::new())
+            .programs();
+        assert_eq!(
+            programs,
+            PipelinePrograms {
+                instruction_programs: Some(BTreeSet::from([a])),
+                account_owners: Some(BTreeSet::from([b])),
+                include_votes: false,
+            }
+        );
+
+        let programs = Pipeline::builder()
+            .instruction(ProgramsDecoder(vec![a]), NoopProcessor::new())
+            .instruction(ProgramsDecoder(vec![]), NoopProcessor::new())
+            .account(ProgramsDecoder(vec![]), NoopProcessor::new())
+            .programs();
+        assert_eq!(programs.instruction_programs, None);
+        assert_eq!(programs.account_owners, None);
+    }
+
+    #[test]
+    fn test_programs_include_votes_when_a_pipe_needs_them() {
+        let program = Pubkey::new_unique();
+
+        let programs = Pipeline::builder()
+            .instruction_with_filters(
+                ProgramsDecoder(vec![program]),
+                NoopProcessor::new(),
+                vec![Box::new(crate::filter::VoteFilter::NonVote)],
+            )
+            .programs();
+        assert!(!programs.include_votes);
+
+        let programs = Pipeline::builder()
+            .instruction_with_filters(
+                ProgramsDecoder(vec![program]),
+                NoopProcessor::new(),
+                vec![Box::new(crate::filter::VoteFilter::Vote)],
+            )
+            .programs();
+        assert!(programs.include_votes);
+
+        let programs = Pipeline::builder()
+            .instruction(ProgramsDecoder(vec![VOTE_PROGRAM_ID]), NoopProcessor::new())
+            .programs();
+        assert!(programs.include_votes);
     }
 }
@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use solana_signature::Signature;
+
+use crate::{
+    checkpoint::{Checkpoint, CheckpointStore},
+    datasource::DatasourceId,
+    error::{CarbonResult, Error},
+};
+
+/// A `CheckpointStore` that keeps checkpoints in the `carbon_checkpoints`
+/// table and cursors in the `carbon_datasource_cursors` table.
+///
+/// Run `CheckpointsMigration` before using the store to create the tables.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use carbon_core::postgres::checkpoint::PostgresCheckpointStore;
+///
+/// let store = Arc::new(PostgresCheckpointStore::new(pool.clone()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PostgresCheckpointStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresCheckpointStore {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckpointStore for PostgresCheckpointStore {
+    async fn load(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<Checkpoint>> {
+        let row: Option<(i64, Option<String>)> = sqlx::query_as(
+            r#"SELECT slot, signature FROM carbon_checkpoints WHERE datasource_id = $1"#,
+        )
+        .bind(datasource_id.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Custom(e.to_string()))?;
+
+        let Some((slot, signature)) = row else {
+            return Ok(None);
+        };
+
+        let signature = signature
+            .map(|signature| {
+                Signature::from_str(&signature)
+                    .map_err(|e| Error::Custom(format!("invalid checkpoint signature: {e}")))
+            })
+            .transpose()?;
+
+        Ok(Some(Checkpoint {
+            slot: slot as u64,
+            signature,
+        }))
+    }
+
+    async fn commit(
+        &self,
+        datasource_id: &DatasourceId,
+        checkpoint: Checkpoint,
+    ) -> CarbonResult<()> {
+        sqlx::query(r#"INSERT INTO carbon_checkpoints (datasource_id, slot, signature, updated_at) VALUES ($1, $2, $3, NOW()) ON CONFLICT (datasource_id) DO UPDATE SET slot = $2, signature = $3, updated_at = NOW()"#)
+            .bind(datasource_id.as_str())
+            .bind(checkpoint.slot as i64)
+            .bind(checkpoint.signature.map(|signature| signature.to_string()))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Custom(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn load_cursor(&self, datasource_id: &DatasourceId) -> CarbonResult<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"SELECT cursor FROM carbon_datasource_cursors WHERE datasource_id = $1"#,
+        )
+        .bind(datasource_id.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Custom(e.to_string()))?;
+
+        Ok(row.map(|(cursor,)| cursor))
+    }
+
+    async fn commit_cursor(
+        &self,
+        datasource_id: &DatasourceId,
+        cursor: String,
+    ) -> CarbonResult<()> {
+        sqlx::query(r#"INSERT INTO carbon_datasource_cursors (datasource_id, cursor, updated_at) VALUES ($1, $2, NOW()) ON CONFLICT (datasource_id) DO UPDATE SET cursor = $2, updated_at = NOW()"#)
+            .bind(datasource_id.as_str())
+            .bind(cursor)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Custom(e.to_string()))?;
+        Ok(())
+    }
+}
+
+pub struct CheckpointsMigrationOperation;
+
+#[async_trait::async_trait]
+impl sqlx_migrator::Operation<sqlx::Postgres> for CheckpointsMigrationOperation {
+    async fn up(
+        &self,
+        connection: &mut sqlx::PgConnection,
+    ) -> Result<(), sqlx_migrator::error::Error> {
+        sqlx::query(
+            r#"CREATE TABLE IF NOT EXISTS carbon_checkpoints (
+            datasource_id TEXT NOT NULL,
+            slot BIGINT NOT NULL,
+            signature TEXT,
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            PRIMARY KEY (datasource_id)
+        )"#,
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        sqlx::query(
+            r#"CREATE TABLE IF NOT EXISTS carbon_datasource_cursors (
+            datasource_id TEXT NOT NULL,
+            cursor TEXT NOT NULL,
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            PRIMARY KEY (datasource_id)
+        )"#,
+        )
+        .execute(connection)
+        .await?;
+        Ok(())
+    }
+
+    async fn down(
+        &self,
+        connection: &mut sqlx::PgConnection,
+    ) -> Result<(), sqlx_migrator::error::Error> {
+        sqlx::query(r#"DROP TABLE IF EXISTS carbon_datasource_cursors"#)
+            .execute(&mut *connection)
+            .await?;
+        sqlx::query(r#"DROP TABLE IF EXISTS carbon_checkpoints"#)
+            .execute(connection)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct CheckpointsMigration;
+
+impl sqlx_migrator::Migration<sqlx::Postgres> for CheckpointsMigration {
+    fn app(&self) -> &str {
+        "carbon_core"
+    }
+
+    fn name(&self) -> &str {
+        "checkpoints"
+    }
+
+    fn operations(&self) -> Vec<Box<dyn sqlx_migrator::Operation<sqlx::Postgres>>> {
+        vec![Box::new(CheckpointsMigrationOperation)]
+    }
+
+    fn parents(&self) -> Vec<Box<dyn sqlx_migrator::Migration<sqlx::Postgres>>> {
+        vec![]
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    dead_letter::{DeadLetter, DeadLetterRecord, DeadLetterSink},
+    error::{CarbonResult, Error},
+};
+
+/// A `DeadLetterSink` that inserts dead letters into the
+/// `carbon_dead_letters` table.
+///
+/// Run `DeadLettersMigration` before using the sink to create the table. The
+/// `payload` column holds the update itself, which can be replayed through a
+/// `DeadLetterReplayDatasource`.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use carbon_core::postgres::dead_letter::PostgresDeadLetterSink;
+///
+/// let sink = Arc::new(PostgresDeadLetterSink::new(pool.clone()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PostgresDeadLetterSink {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresDeadLetterSink {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeadLetterSink for PostgresDeadLetterSink {
+    async fn send(&self, dead_letter: DeadLetter) -> CarbonResult<()> {
+        let record = DeadLetterRecord::from(&dead_letter);
+
+        sqlx::query(r#"INSERT INTO carbon_dead_letters (pipe, datasource_id, update_type, slot, signature, pubkey, error, attempts, failed_at, payload) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, to_timestamp($9::DOUBLE PRECISION / 1000), $10)"#)
+            .bind(record.pipe)
+            .bind(record.datasource_id)
+            .bind(record.update_type)
+            .bind(record.slot as i64)
+            .bind(record.signature)
+            .bind(record.pubkey)
+            .bind(record.error)
+            .bind(record.attempts as i32)
+            .bind(record.failed_at.timestamp_millis())
+            .bind(record.payload)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Custom(e.to_string()))?;
+        Ok(())
+    }
+}
+
+pub struct DeadLettersMigrationOperation;
+
+#[async_trait::async_trait]
+impl sqlx_migrator::Operation<sqlx::Postgres> for DeadLettersMigrationOperation {
+    async fn up(
+        &self,
+        connection: &mut sqlx::PgConnection,
+    ) -> Result<(), sqlx_migrator::error::Error> {
+        sqlx::query(
+            r#"CREATE TABLE IF NOT EXISTS carbon_dead_letters (
+            id BIGSERIAL PRIMARY KEY,
+            pipe TEXT NOT NULL,
+            datasource_id TEXT NOT NULL,
+            update_type TEXT NOT NULL,
+            slot BIGINT NOT NULL,
+            signature TEXT,
+            pubkey TEXT,
+            error TEXT NOT NULL,
+            attempts INTEGER NOT NULL,
+            failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+            payload TEXT
+        )"#,
+        )
+        .execute(connection)
+        .await?;
+        Ok(())
+    }
+
+    async fn down(
+        &self,
+        connection: &mut sqlx::PgConnection,
+    ) -> Result<(), sqlx_migrator::error::Error> {
+        sqlx::query(r#"DROP TABLE IF EXISTS carbon_dead_letters"#)
+            .execute(connection)
+            .await?;
+        Ok(())
+    }
+}
+
+pub struct DeadLettersMigration;
+
+impl sqlx_migrator::Migration<sqlx::Postgres> for DeadLettersMigration {
+    fn app(&self) -> &str {
+        "carbon_core"
+    }
+
+    fn name(&self) -> &str {
+        "dead_letters"
+    }
+
+    fn operations(&self) -> Vec<Box<dyn sqlx_migrator::Operation<sqlx::Postgres>>> {
+        vec![Box::new(DeadLettersMigrationOperation)]
+    }
+
+    fn parents(&self) -> Vec<Box<dyn sqlx_migrator::Migration<sqlx::Postgres>>> {
+        vec![]
+    }
+}
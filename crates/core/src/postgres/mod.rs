@@ -1,3 +1,5 @@
+pub mod checkpoint;
+pub mod dead_letter;
 pub mod metadata;
 pub mod operations;
 pub mod primitives;
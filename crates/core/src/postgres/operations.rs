@@ -8,8 +8,37 @@
 //! which are used to define and interact with PostgreSQL tables.
 //!
 
+use std::{collections::HashSet, hash::Hash};
+
 use crate::error::CarbonResult;
 
+/// The maximum number of rows written by a single multi-row statement.
+pub const MAX_ROWS_PER_STATEMENT: usize = 1_000;
+
+/// The maximum number of bind parameters PostgreSQL accepts per statement.
+pub const MAX_BIND_PARAMETERS: usize = 65_535;
+
+/// Returns how many rows of `columns` bind parameters each fit into a single
+/// multi-row statement.
+pub fn rows_per_statement(columns: usize) -> usize {
+    (MAX_BIND_PARAMETERS / columns.max(1)).clamp(1, MAX_ROWS_PER_STATEMENT)
+}
+
+/// Keeps only the last of the rows sharing a key, in their original order.
+///
+/// A single `INSERT ... ON CONFLICT DO UPDATE` can't update the same row
+/// twice, so a batch must not contain the same key more than once.
+pub fn last_per_key<R, K: Eq + Hash>(rows: &[R], key: impl Fn(&R) -> K) -> Vec<&R> {
+    let mut seen = HashSet::new();
+    let mut latest: Vec<&R> = rows
+        .iter()
+        .rev()
+        .filter(|row| seen.insert(key(row)))
+        .collect();
+    latest.reverse();
+    latest
+}
+
 /// A trait for defining PostgreSQL tables.
 pub trait Table {
     fn table() -> &'static str;
@@ -23,9 +52,24 @@ pub trait Insert {
 }
 
 /// A trait for defining PostgreSQL upsert operations.
+///
+/// `upsert_many` upserts the rows one by one unless overridden. `AccountRow`,
+/// `InstructionRow` and the generated decoder rows override it with
+/// multi-row `INSERT ... ON CONFLICT` statements, see `last_per_key` and
+/// `rows_per_statement`.
 #[async_trait::async_trait]
 pub trait Upsert {
     async fn upsert(&self, pool: &sqlx::PgPool) -> CarbonResult<()>;
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        for row in rows {
+            row.upsert(pool).await?;
+        }
+        Ok(())
+    }
 }
 
 /// A trait for defining PostgreSQL delete operations.
@@ -42,3 +86,24 @@ pub trait LookUp: Sized {
 
     async fn lookup(key: Self::Key, pool: &sqlx::PgPool) -> CarbonResult<Option<Self>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_per_key_keeps_latest_rows_in_order() {
+        let rows = [(1, "a"), (2, "b"), (1, "c"), (3, "d")];
+
+        let latest = last_per_key(&rows, |row| row.0);
+
+        assert_eq!(latest, vec![&(2, "b"), &(1, "c"), &(3, "d")]);
+    }
+
+    #[test]
+    fn test_rows_per_statement_stays_within_bind_limit() {
+        assert_eq!(rows_per_statement(3), MAX_ROWS_PER_STATEMENT);
+        assert_eq!(rows_per_statement(100), 655);
+        assert_eq!(rows_per_statement(100_000), 1);
+    }
+}
@@ -3,15 +3,23 @@ use std::sync::Arc;
 
 use crate::{
     account::{AccountMetadata, AccountProcessorInputType},
-    error::CarbonResult,
+    batch::BatchProcessor,
+    error::{CarbonResult, Error},
     instruction::{InstructionMetadata, InstructionProcessorInputType},
     metrics::MetricsCollection,
     postgres::{
         operations::Upsert,
+        primitives::U64,
         rows::{AccountRow, InstructionRow},
     },
+    rollback::RollbackEvent,
 };
 
+/// Upserts decoded accounts into their table.
+///
+/// As a `BatchProcessor`, a whole batch is written with `Upsert::upsert_many`,
+/// see `PipelineBuilder::account_batched`. Used as a plain `Processor`, each
+/// account is upserted on its own.
 pub struct PostgresAccountProcessor<T, W> {
     pool: sqlx::PgPool,
     _phantom: std::marker::PhantomData<(T, W)>,
@@ -27,28 +35,33 @@ impl<T, W> PostgresAccountProcessor<T, W> {
 }
 
 #[async_trait::async_trait]
-impl<T, W> crate::processor::Processor for PostgresAccountProcessor<T, W>
+impl<T, W> BatchProcessor for PostgresAccountProcessor<T, W>
 where
     T: Clone + Send + Sync + 'static,
-    W: From<(T, AccountMetadata)> + Upsert + Send + 'static,
+    W: From<(T, AccountMetadata)> + Upsert + Send + Sync + 'static,
 {
     type InputType = AccountProcessorInputType<T>;
 
-    async fn process(
+    async fn process_batch(
         &mut self,
-        input: Self::InputType,
+        batch: &[Self::InputType],
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
-        let (metadata, decoded_account, _raw) = input;
+        let batch_size = batch.len() as u64;
 
         let start = std::time::Instant::now();
 
-        let wrapper = W::from((decoded_account.data, metadata));
+        let rows: Vec<_> = batch
+            .iter()
+            .map(|(metadata, decoded_account, _raw)| {
+                W::from((decoded_account.data.clone(), metadata.clone()))
+            })
+            .collect();
 
-        match wrapper.upsert(&self.pool).await {
+        match W::upsert_many(&rows, &self.pool).await {
             Ok(()) => {
                 metrics
-                    .increment_counter("postgres.accounts.upsert.upserted", 1)
+                    .increment_counter("postgres.accounts.upsert.upserted", batch_size)
                     .await?;
                 metrics
                     .record_histogram(
@@ -60,14 +73,37 @@ where
             }
             Err(e) => {
                 metrics
-                    .increment_counter("postgres.accounts.upsert.failed", 1)
+                    .increment_counter("postgres.accounts.upsert.failed", batch_size)
                     .await?;
-                return Err(e);
+                Err(e)
             }
         }
     }
 }
 
+#[async_trait::async_trait]
+impl<T, W> crate::processor::Processor for PostgresAccountProcessor<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: From<(T, AccountMetadata)> + Upsert + Send + Sync + 'static,
+{
+    type InputType = AccountProcessorInputType<T>;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.process_batch(std::slice::from_ref(&input), metrics)
+            .await
+    }
+}
+
+/// Upserts decoded accounts into the generic `accounts` table.
+///
+/// As a `BatchProcessor`, a whole batch is written with `Upsert::upsert_many`,
+/// see `PipelineBuilder::account_batched`. Used as a plain `Processor`, each
+/// account is upserted on its own.
 pub struct PostgresJsonAccountProcessor<T> {
     pool: sqlx::PgPool,
     _phantom: std::marker::PhantomData<T>,
@@ -83,27 +119,32 @@ impl<T> PostgresJsonAccountProcessor<T> {
 }
 
 #[async_trait::async_trait]
-impl<T> crate::processor::Processor for PostgresJsonAccountProcessor<T>
+impl<T> BatchProcessor for PostgresJsonAccountProcessor<T>
 where
     T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + Unpin + 'static,
 {
     type InputType = AccountProcessorInputType<T>;
 
-    async fn process(
+    async fn process_batch(
         &mut self,
-        input: Self::InputType,
+        batch: &[Self::InputType],
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
-        let (metadata, decoded_account, _raw) = input;
-
-        let account_row = AccountRow::from_parts(decoded_account.data, metadata);
+        let batch_size = batch.len() as u64;
 
         let start = std::time::Instant::now();
 
-        match account_row.upsert(&self.pool).await {
+        let rows: Vec<_> = batch
+            .iter()
+            .map(|(metadata, decoded_account, _raw)| {
+                AccountRow::from_parts(decoded_account.data.clone(), metadata.clone())
+            })
+            .collect();
+
+        match AccountRow::upsert_many(&rows, &self.pool).await {
             Ok(()) => {
                 metrics
-                    .increment_counter("postgres.accounts.upsert.upserted", 1)
+                    .increment_counter("postgres.accounts.upsert.upserted", batch_size)
                     .await?;
                 metrics
                     .record_histogram(
@@ -115,14 +156,36 @@ where
             }
             Err(e) => {
                 metrics
-                    .increment_counter("postgres.accounts.upsert.failed", 1)
+                    .increment_counter("postgres.accounts.upsert.failed", batch_size)
                     .await?;
-                return Err(e);
+                Err(e)
             }
         }
     }
 }
 
+#[async_trait::async_trait]
+impl<T> crate::processor::Processor for PostgresJsonAccountProcessor<T>
+where
+    T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + Unpin + 'static,
+{
+    type InputType = AccountProcessorInputType<T>;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.process_batch(std::slice::from_ref(&input), metrics)
+            .await
+    }
+}
+
+/// Upserts decoded instructions into their table.
+///
+/// As a `BatchProcessor`, a whole batch is written with `Upsert::upsert_many`,
+/// see `PipelineBuilder::instruction_batched`. Used as a plain `Processor`, each
+/// instruction is upserted on its own.
 pub struct PostgresInstructionProcessor<T, W> {
     pool: sqlx::PgPool,
     _phantom: std::marker::PhantomData<(T, W)>,
@@ -138,32 +201,39 @@ impl<T, W> PostgresInstructionProcessor<T, W> {
 }
 
 #[async_trait::async_trait]
-impl<T, W> crate::processor::Processor for PostgresInstructionProcessor<T, W>
+impl<T, W> BatchProcessor for PostgresInstructionProcessor<T, W>
 where
     T: Clone + Send + Sync + 'static,
-    W: From<(T, InstructionMetadata, Vec<AccountMeta>)> + Upsert + Send + 'static,
+    W: From<(T, InstructionMetadata, Vec<AccountMeta>)> + Upsert + Send + Sync + 'static,
 {
     type InputType = InstructionProcessorInputType<T>;
 
-    async fn process(
+    async fn process_batch(
         &mut self,
-        input: Self::InputType,
+        batch: &[Self::InputType],
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
-        let (metadata, decoded_instruction, _nested_instructions, _raw) = input;
+        let batch_size = batch.len() as u64;
 
         let start = std::time::Instant::now();
 
-        let wrapper = W::from((
-            decoded_instruction.data,
-            metadata,
-            decoded_instruction.accounts,
-        ));
+        let rows: Vec<_> = batch
+            .iter()
+            .map(
+                |(metadata, decoded_instruction, _nested_instructions, _raw)| {
+                    W::from((
+                        decoded_instruction.data.clone(),
+                        metadata.clone(),
+                        decoded_instruction.accounts.clone(),
+                    ))
+                },
+            )
+            .collect();
 
-        match wrapper.upsert(&self.pool).await {
+        match W::upsert_many(&rows, &self.pool).await {
             Ok(()) => {
                 metrics
-                    .increment_counter("postgres.instructions.upsert.upserted", 1)
+                    .increment_counter("postgres.instructions.upsert.upserted", batch_size)
                     .await?;
                 metrics
                     .record_histogram(
@@ -175,14 +245,37 @@ where
             }
             Err(e) => {
                 metrics
-                    .increment_counter("postgres.instructions.upsert.failed", 1)
+                    .increment_counter("postgres.instructions.upsert.failed", batch_size)
                     .await?;
-                return Err(e);
+                Err(e)
             }
         }
     }
 }
 
+#[async_trait::async_trait]
+impl<T, W> crate::processor::Processor for PostgresInstructionProcessor<T, W>
+where
+    T: Clone + Send + Sync + 'static,
+    W: From<(T, InstructionMetadata, Vec<AccountMeta>)> + Upsert + Send + Sync + 'static,
+{
+    type InputType = InstructionProcessorInputType<T>;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.process_batch(std::slice::from_ref(&input), metrics)
+            .await
+    }
+}
+
+/// Upserts decoded instructions into the generic `instructions` table.
+///
+/// As a `BatchProcessor`, a whole batch is written with `Upsert::upsert_many`,
+/// see `PipelineBuilder::instruction_batched`. Used as a plain `Processor`, each
+/// instruction is upserted on its own.
 pub struct PostgresJsonInstructionProcessor<T> {
     pool: sqlx::PgPool,
     _phantom: std::marker::PhantomData<T>,
@@ -198,31 +291,38 @@ impl<T> PostgresJsonInstructionProcessor<T> {
 }
 
 #[async_trait::async_trait]
-impl<T> crate::processor::Processor for PostgresJsonInstructionProcessor<T>
+impl<T> BatchProcessor for PostgresJsonInstructionProcessor<T>
 where
     T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + Unpin + 'static,
 {
     type InputType = InstructionProcessorInputType<T>;
 
-    async fn process(
+    async fn process_batch(
         &mut self,
-        input: Self::InputType,
+        batch: &[Self::InputType],
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
-        let (metadata, decoded_instruction, _nested_instructions, _raw) = input;
-
-        let instruction_row = InstructionRow::from_parts(
-            decoded_instruction.data,
-            metadata,
-            decoded_instruction.accounts,
-        );
+        let batch_size = batch.len() as u64;
 
         let start = std::time::Instant::now();
 
-        match instruction_row.upsert(&self.pool).await {
+        let rows: Vec<_> = batch
+            .iter()
+            .map(
+                |(metadata, decoded_instruction, _nested_instructions, _raw)| {
+                    InstructionRow::from_parts(
+                        decoded_instruction.data.clone(),
+                        metadata.clone(),
+                        decoded_instruction.accounts.clone(),
+                    )
+                },
+            )
+            .collect();
+
+        match InstructionRow::upsert_many(&rows, &self.pool).await {
             Ok(()) => {
                 metrics
-                    .increment_counter("postgres.instructions.upsert.upserted", 1)
+                    .increment_counter("postgres.instructions.upsert.upserted", batch_size)
                     .await?;
                 metrics
                     .record_histogram(
@@ -234,10 +334,135 @@ where
             }
             Err(e) => {
                 metrics
-                    .increment_counter("postgres.instructions.upsert.failed", 1)
+                    .increment_counter("postgres.instructions.upsert.failed", batch_size)
                     .await?;
-                return Err(e);
+                Err(e)
             }
         }
     }
 }
+
+#[async_trait::async_trait]
+impl<T> crate::processor::Processor for PostgresJsonInstructionProcessor<T>
+where
+    T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + Unpin + 'static,
+{
+    type InputType = InstructionProcessorInputType<T>;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.process_batch(std::slice::from_ref(&input), metrics)
+            .await
+    }
+}
+
+/// Rolls back the rows written at orphaned or dead slots.
+///
+/// For every `RollbackEvent`, the rows whose `__slot` column matches the
+/// rolled back slot are deleted from each of the configured tables, such as
+/// the `instructions` table.
+///
+/// The rows of all tables are deleted in a single transaction, so that a
+/// failed rollback leaves every table as it was.
+///
+/// Table names are quoted as identifiers, so they are matched case-sensitively
+/// and can't inject SQL. Schema-qualified names such as `schema.table` are
+/// quoted part by part.
+///
+/// Tables keyed by something else than the slot, like the `accounts` table,
+/// only keep the latest state of each row. Deleting from them removes the row
+/// until the next update rather than restoring its previous state.
+pub struct PostgresRollbackProcessor {
+    pool: sqlx::PgPool,
+    tables: Vec<String>,
+}
+
+impl PostgresRollbackProcessor {
+    pub fn new(pool: sqlx::PgPool, tables: Vec<String>) -> Self {
+        Self { pool, tables }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::processor::Processor for PostgresRollbackProcessor {
+    type InputType = RollbackEvent;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let start = std::time::Instant::now();
+
+        let result = async {
+            let mut transaction = self.pool.begin().await?;
+            let mut deleted = 0;
+
+            for table in &self.tables {
+                deleted += sqlx::query(&format!(
+                    "DELETE FROM {} WHERE __slot = $1",
+                    quote_table(table)
+                ))
+                .bind(U64::from(input.slot))
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected();
+            }
+
+            transaction.commit().await?;
+            Ok::<_, sqlx::Error>(deleted)
+        }
+        .await;
+
+        match result {
+            Ok(deleted) => {
+                metrics
+                    .increment_counter("postgres.rollback.deleted", deleted)
+                    .await?;
+            }
+            Err(e) => {
+                metrics
+                    .increment_counter("postgres.rollback.failed", 1)
+                    .await?;
+                return Err(Error::Custom(e.to_string()));
+            }
+        }
+
+        metrics
+            .record_histogram(
+                "postgres.rollback.duration_milliseconds",
+                start.elapsed().as_millis() as f64,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Quotes a table name as an identifier, quoting each part of a
+/// schema-qualified name separately.
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotes_each_part_of_table_name() {
+        assert_eq!(quote_table("instructions"), r#""instructions""#);
+        assert_eq!(quote_table("indexer.swaps"), r#""indexer"."swaps""#);
+        assert_eq!(
+            quote_table(r#"swaps"; DROP TABLE accounts; --"#),
+            r#""swaps""; DROP TABLE accounts; --""#
+        );
+    }
+}
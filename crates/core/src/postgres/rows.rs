@@ -6,7 +6,7 @@ use crate::{
     instruction::InstructionMetadata,
     postgres::{
         metadata::{AccountRowMetadata, InstructionRowMetadata},
-        operations::{Delete, Insert, LookUp, Upsert},
+        operations::{last_per_key, Delete, Insert, LookUp, Upsert, MAX_ROWS_PER_STATEMENT},
         primitives::{Pubkey, U32},
     },
 };
@@ -64,6 +64,34 @@ impl<
             .map_err(|e| crate::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = last_per_key(rows, |row| row.metadata.pubkey.0);
+
+        for chunk in rows.chunks(MAX_ROWS_PER_STATEMENT) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO accounts (__pubkey, __slot, data) "#,
+            );
+            query.push_values(chunk, |mut values, row| {
+                values
+                    .push_bind(row.metadata.pubkey)
+                    .push_bind(row.metadata.slot.clone())
+                    .push_bind(row.data.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (__pubkey) DO UPDATE SET __slot = EXCLUDED.__slot, data = EXCLUDED.data"#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| crate::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -217,6 +245,43 @@ impl<
             .map_err(|e| crate::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = last_per_key(rows, |row| {
+            (
+                row.metadata.signature.clone(),
+                row.metadata.instruction_index.0,
+                row.metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(MAX_ROWS_PER_STATEMENT) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO instructions (__signature, __instruction_index, __stack_height, __slot, data, accounts) "#,
+            );
+            query.push_values(chunk, |mut values, row| {
+                values
+                    .push_bind(row.metadata.signature.clone())
+                    .push_bind(row.metadata.instruction_index)
+                    .push_bind(row.metadata.stack_height)
+                    .push_bind(row.metadata.slot.clone())
+                    .push_bind(row.data.clone())
+                    .push_bind(row.accounts.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (__signature, __instruction_index, __stack_height) DO UPDATE SET __slot = EXCLUDED.__slot, data = EXCLUDED.data, accounts = EXCLUDED.accounts"#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| crate::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -30,6 +30,23 @@
 //!   asynchronous and should be implemented to define how data should be
 //!   processed in your specific use case.
 //!
+//! ### Provided Methods
+//!
+//! The pipeline calls these lifecycle hooks on every processor. They do
+//! nothing by default.
+//!
+//! - `init`: Called once when the pipeline starts, before any datasource is
+//!   consumed. An error aborts the start of the pipeline.
+//! - `flush`: Writes out any data the processor buffers internally. The
+//!   pipeline calls it on every metrics flush tick, before committing
+//!   checkpoints and on shutdown.
+//! - `shutdown`: Called once when the pipeline stops, after the last `flush`,
+//!   to release connections or other resources.
+//! - `fork`: Returns a new instance of the processor. With
+//!   `ExecutionMode::Concurrent`, every worker gets its own fork, so that the
+//!   workers don't wait on each other. Processors that can't be forked are
+//!   shared between the workers, which then take turns.
+//!
 //! ## Parameters
 //!
 //! - `data`: An instance of the type specified by `InputType`. This represents
@@ -76,6 +93,17 @@ use {
 /// - `process`: Processes the specified `InputType` data asynchronously,
 ///   optionally updating associated metrics.
 ///
+/// # Provided Methods
+///
+/// - `init`: Prepares the processor before the first update, for example by
+///   opening connections.
+/// - `flush`: Writes out any buffered data, such as the pending batch of a
+///   `BatchingProcessor`. Processors that don't buffer can ignore it.
+/// - `shutdown`: Releases the processor's resources once the pipeline stops.
+/// - `fork`: Returns a new, uninitialized instance of the processor for
+///   another worker, or `None` if the processor can't be forked, which is the
+///   default.
+///
 /// # Example
 ///
 /// ```ignore
@@ -116,4 +144,20 @@ pub trait Processor {
         data: Self::InputType,
         metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()>;
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    fn fork(&self) -> Option<Box<dyn Processor<InputType = Self::InputType> + Send + Sync>> {
+        None
+    }
 }
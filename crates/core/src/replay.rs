@@ -0,0 +1,1036 @@
+//! Records the updates emitted by a datasource to a file and replays them
+//! later.
+//!
+//! Recordings make it possible to debug an incident with the exact updates a
+//! production pipeline received, or to reprocess a range of slots after
+//! fixing a decoder, without depending on the original datasource.
+//!
+//! # Overview
+//!
+//! - **`RecordingDatasource`**: Wraps any `Datasource` and appends every
+//!   update it emits, along with its `DatasourceId`, to a recording file
+//!   before forwarding it to the pipeline.
+//! - **`ReplayDatasource`**: Reads a recording back, optionally restricted to
+//!   a range of slots, either as fast as the pipeline accepts the updates or
+//!   at the pace they were recorded at (see `ReplayPace`).
+//!
+//! # File Format
+//!
+//! A recording is an append-only file starting with the 8-byte
+//! `RECORDING_MAGIC`, the format version as a little-endian `u16` and the
+//! payload schema version as a little-endian `u16`, followed by one frame per
+//! update:
+//!
+//! - `length`: The length of the payload, as a little-endian `u32`.
+//! - `slot`: The slot of the update, as a little-endian `u64`.
+//! - `recorded_at`: When the update was recorded, in Unix microseconds, as a
+//!   little-endian `u64`.
+//! - `payload`: The update and its `DatasourceId`, as encoded by
+//!   `datasource::encode_update`.
+//!
+//! Next to the recording, a slot index file with the `.idx` extension
+//! appended holds a `(slot, offset)` pair of little-endian `u64`s for every
+//! frame whose slot is higher than the slot of every frame before it, which
+//! lets a replay seek to the first frame of a slot range.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::replay::{RecordingDatasource, ReplayDatasource, ReplayPace};
+//!
+//! // Record what a live datasource emits while processing it.
+//! carbon_core::pipeline::Pipeline::builder()
+//!     .datasource(RecordingDatasource::new(yellowstone_datasource, "mainnet.rec")?)
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//!
+//! // Later, reprocess slots 300_000_000 to 300_001_000 of the recording.
+//! carbon_core::pipeline::Pipeline::builder()
+//!     .datasource(
+//!         ReplayDatasource::new("mainnet.rec")
+//!             .slot_range(300_000_000..=300_001_000)
+//!             .pace(ReplayPace::FullSpeed),
+//!     )
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Notes
+//!
+//! - Replayed updates are sent with the `DatasourceId` they were recorded
+//!   with, so `DatasourceFilter`s keep working on replays.
+//! - A frame left incomplete by a crash is dropped when the recording is
+//!   opened again for recording, and ignored when it is replayed.
+//! - Payloads are the bincode encoding of `Update` and the types it holds,
+//!   which has no field names or defaults. Recordings made with another
+//!   `PAYLOAD_SCHEMA_VERSION` are rejected rather than misread.
+//! - A recording file must only be written to by one `RecordingDatasource`
+//!   at a time.
+
+use {
+    crate::{
+        checkpoint::CursorHandle,
+        datasource::{
+            decode_update, encode_update, Datasource, DatasourceId, Update, UpdateType,
+            PAYLOAD_SCHEMA_VERSION,
+        },
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+    },
+    async_trait::async_trait,
+    std::{
+        fs::{File, OpenOptions},
+        io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write},
+        ops::{Bound, RangeBounds},
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio_util::sync::CancellationToken,
+};
+
+/// The bytes every recording file starts with.
+pub const RECORDING_MAGIC: [u8; 8] = *b"CARBNREC";
+
+/// The version of the recording format written by `RecordingDatasource`,
+/// covering the file header, the frames and the slot index.
+pub const RECORDING_VERSION: u16 = 2;
+
+const HEADER_LEN: u64 = 12;
+const FRAME_HEADER_LEN: usize = 20;
+const INDEX_ENTRY_LEN: usize = 16;
+const REPLAY_READ_AHEAD: usize = 1_000;
+
+/// How fast a `ReplayDatasource` sends the recorded updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayPace {
+    /// Send the updates as fast as the pipeline accepts them.
+    #[default]
+    FullSpeed,
+    /// Keep the time that elapsed between the updates when they were
+    /// recorded.
+    RealTime,
+}
+
+/// A datasource that records every update emitted by the datasource it
+/// wraps.
+///
+/// Updates are queued for the recording before they are forwarded to the
+/// pipeline, and written to the file by a blocking task. If the recording
+/// cannot be written, the wrapped datasource is cancelled and `consume`
+/// fails.
+pub struct RecordingDatasource<D> {
+    datasource: D,
+    writer: Arc<Mutex<RecordingWriter>>,
+}
+
+impl<D: Datasource> RecordingDatasource<D> {
+    /// Wraps `datasource`, recording its updates to the file at `path`.
+    ///
+    /// The file is created if it does not exist yet. Otherwise, its format
+    /// version is checked and new updates are appended to it.
+    pub fn new(datasource: D, path: impl AsRef<Path>) -> CarbonResult<Self> {
+        let path = path.as_ref();
+        log::trace!("RecordingDatasource::new(datasource, path: {path:?})");
+
+        Ok(Self {
+            datasource,
+            writer: Arc::new(Mutex::new(RecordingWriter::open(path)?)),
+        })
+    }
+
+    /// Spawns the blocking task writing the frames it receives to the
+    /// recording, flushing it once the frame channel closes or a write fails.
+    fn spawn_writer(
+        &self,
+        mut frame_receiver: tokio::sync::mpsc::Receiver<(u64, u64, Vec<u8>)>,
+    ) -> tokio::task::JoinHandle<CarbonResult<()>> {
+        let writer = self.writer.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut writer = writer
+                .lock()
+                .map_err(|_| Error::Custom("recording writer lock poisoned".to_string()))?;
+
+            let mut result = Ok(());
+            while let Some((slot, recorded_at, payload)) = frame_receiver.blocking_recv() {
+                result = writer.write_frame(slot, recorded_at, &payload);
+                if result.is_err() {
+                    break;
+                }
+            }
+
+            result.and(writer.flush())
+        })
+    }
+}
+
+#[async_trait]
+impl<D: Datasource> Datasource for RecordingDatasource<D> {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let (inner_sender, mut inner_receiver) = tokio::sync::mpsc::channel(sender.max_capacity());
+        let (frame_sender, frame_receiver) = tokio::sync::mpsc::channel(sender.max_capacity());
+        let inner_cancellation_token = cancellation_token.child_token();
+        let writer = self.spawn_writer(frame_receiver);
+
+        let consume = self.datasource.consume(
+            id,
+            inner_sender,
+            inner_cancellation_token.clone(),
+            metrics.clone(),
+        );
+        let record = async move {
+            let result = async {
+                let writer_stopped = || Error::Custom("recording writer stopped".to_string());
+
+                loop {
+                    // The writer stops on the first failed write, which is
+                    // reported without waiting for the next update.
+                    let received = tokio::select! {
+                        received = inner_receiver.recv() => received,
+                        _ = frame_sender.closed() => return Err(writer_stopped()),
+                    };
+                    let Some((update, datasource_id)) = received else {
+                        break;
+                    };
+
+                    let payload = encode_update(&update, &datasource_id)?;
+                    let recorded_at = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_micros() as u64;
+                    frame_sender
+                        .send((update.slot(), recorded_at, payload))
+                        .await
+                        .map_err(|_| writer_stopped())?;
+                    metrics
+                        .increment_counter("replay_updates_recorded", 1)
+                        .await?;
+
+                    if sender.send((update, datasource_id)).await.is_err() {
+                        break;
+                    }
+                }
+
+                Ok::<_, Error>(())
+            }
+            .await;
+
+            // The wrapped datasource may ignore send errors, so it is
+            // cancelled rather than left running once nothing reads its
+            // updates anymore.
+            inner_cancellation_token.cancel();
+            result
+        };
+
+        let (consume_result, record_result) = tokio::join!(consume, record);
+        let write_result = writer
+            .await
+            .map_err(|err| Error::Custom(format!("recording write task failed: {err}")))?;
+
+        write_result.and(record_result).and(consume_result)
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        self.datasource.update_types()
+    }
+
+    fn set_cursor_handle(&self, cursor_handle: CursorHandle) {
+        self.datasource.set_cursor_handle(cursor_handle);
+    }
+}
+
+/// A datasource that replays the updates of a recording.
+///
+/// The datasource finishes once every update in its slot range has been
+/// sent.
+///
+/// # Example
+///
+/// ```ignore
+/// use carbon_core::replay::{ReplayDatasource, ReplayPace};
+///
+/// let datasource = ReplayDatasource::new("mainnet.rec")
+///     .slot_range(300_000_000..)
+///     .pace(ReplayPace::RealTime);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayDatasource {
+    pub path: PathBuf,
+    pub start_slot: Bound<u64>,
+    pub end_slot: Bound<u64>,
+    pub pace: ReplayPace,
+}
+
+impl ReplayDatasource {
+    /// Creates a datasource replaying every update of the recording at
+    /// `path`, at full speed.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        log::trace!("ReplayDatasource::new(path: {path:?})");
+
+        Self {
+            path,
+            start_slot: Bound::Unbounded,
+            end_slot: Bound::Unbounded,
+            pace: ReplayPace::default(),
+        }
+    }
+
+    /// Restricts the replay to the updates whose slot is within `slots`.
+    pub fn slot_range(mut self, slots: impl RangeBounds<u64>) -> Self {
+        self.start_slot = slots.start_bound().cloned();
+        self.end_slot = slots.end_bound().cloned();
+        self
+    }
+
+    /// Sets how fast the recorded updates are sent.
+    pub fn pace(mut self, pace: ReplayPace) -> Self {
+        self.pace = pace;
+        self
+    }
+
+    fn first_slot(&self) -> u64 {
+        match self.start_slot {
+            Bound::Included(slot) => slot,
+            Bound::Excluded(slot) => slot.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Datasource for ReplayDatasource {
+    async fn consume(
+        &self,
+        _id: DatasourceId,
+        sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let (frame_sender, mut frame_receiver) = tokio::sync::mpsc::channel(REPLAY_READ_AHEAD);
+        let path = self.path.clone();
+        let first_slot = self.first_slot();
+        let slots = (self.start_slot, self.end_slot);
+
+        let reader = tokio::task::spawn_blocking(move || -> CarbonResult<()> {
+            let mut reader = RecordingReader::open(&path)?;
+            reader.seek_to_slot(first_slot)?;
+
+            while let Some(frame) = reader.next_frame()? {
+                if !slots.contains(&frame.slot) {
+                    continue;
+                }
+
+                let (update, datasource_id) = decode_update(&frame.payload)?;
+                if frame_sender
+                    .blocking_send((frame.recorded_at, update, datasource_id))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+
+            Ok(())
+        });
+
+        let mut pace_origin: Option<(u64, tokio::time::Instant)> = None;
+
+        loop {
+            let frame = tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                frame = frame_receiver.recv() => frame,
+            };
+            let Some((recorded_at, update, datasource_id)) = frame else {
+                break;
+            };
+
+            if self.pace == ReplayPace::RealTime {
+                let (first_recorded_at, started_at) =
+                    *pace_origin.get_or_insert((recorded_at, tokio::time::Instant::now()));
+                let deadline = started_at
+                    + Duration::from_micros(recorded_at.saturating_sub(first_recorded_at));
+
+                tokio::select! {
+                    _ = cancellation_token.cancelled() => break,
+                    _ = tokio::time::sleep_until(deadline) => {}
+                }
+            }
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                result = sender.send((update, datasource_id)) => {
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            }
+
+            metrics
+                .increment_counter("replay_updates_replayed", 1)
+                .await?;
+        }
+
+        drop(frame_receiver);
+
+        reader
+            .await
+            .map_err(|err| Error::Custom(format!("replay read task failed: {err}")))?
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![
+            UpdateType::AccountUpdate,
+            UpdateType::Transaction,
+            UpdateType::AccountDeletion,
+            UpdateType::BlockDetails,
+            UpdateType::SlotStatus,
+        ]
+    }
+}
+
+fn index_path(path: &Path) -> PathBuf {
+    let mut index_path = path.as_os_str().to_owned();
+    index_path.push(".idx");
+    PathBuf::from(index_path)
+}
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> Error {
+    Error::Custom(format!("failed to {action} recording {path:?}: {err}"))
+}
+
+fn check_header(header: &[u8; HEADER_LEN as usize], path: &Path) -> CarbonResult<()> {
+    if header[..8] != RECORDING_MAGIC {
+        return Err(Error::Custom(format!("{path:?} is not a recording")));
+    }
+
+    let version = u16::from_le_bytes([header[8], header[9]]);
+    if version != RECORDING_VERSION {
+        return Err(Error::Custom(format!(
+            "recording {path:?} has unsupported format version {version}"
+        )));
+    }
+
+    let schema_version = u16::from_le_bytes([header[10], header[11]]);
+    if schema_version != PAYLOAD_SCHEMA_VERSION {
+        return Err(Error::Custom(format!(
+            "recording {path:?} has unsupported payload schema version {schema_version}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// A frame read back from a recording.
+struct Frame {
+    slot: u64,
+    recorded_at: u64,
+    payload: Vec<u8>,
+}
+
+/// Appends frames to a recording and keeps its slot index up to date.
+struct RecordingWriter {
+    path: PathBuf,
+    data: BufWriter<File>,
+    index: File,
+    offset: u64,
+    pending_index: Vec<u8>,
+    highest_slot: Option<u64>,
+}
+
+impl RecordingWriter {
+    /// Opens the recording at `path` for appending, creating it if needed.
+    ///
+    /// The frames of an existing recording are scanned to drop a trailing
+    /// incomplete frame and rebuild the slot index.
+    fn open(path: &Path) -> CarbonResult<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| io_error("open", path, err))?;
+        let len = file
+            .metadata()
+            .map_err(|err| io_error("open", path, err))?
+            .len();
+
+        let mut index = Vec::new();
+        let mut highest_slot = None;
+        let offset = if len == 0 {
+            let mut header = RECORDING_MAGIC.to_vec();
+            header.extend_from_slice(&RECORDING_VERSION.to_le_bytes());
+            header.extend_from_slice(&PAYLOAD_SCHEMA_VERSION.to_le_bytes());
+            file.write_all(&header)
+                .map_err(|err| io_error("write", path, err))?;
+            HEADER_LEN
+        } else {
+            let mut reader = RecordingReader::new(file, path)?;
+            while let Some((slot, offset)) = reader.skip_frame()? {
+                if highest_slot.is_none_or(|highest_slot| slot > highest_slot) {
+                    highest_slot = Some(slot);
+                    index.extend_from_slice(&slot.to_le_bytes());
+                    index.extend_from_slice(&offset.to_le_bytes());
+                }
+            }
+
+            let end = reader.offset;
+            file = reader.reader.into_inner();
+            if end < len {
+                log::warn!("dropping an incomplete frame at the end of recording {path:?}");
+                file.set_len(end)
+                    .map_err(|err| io_error("truncate", path, err))?;
+            }
+            end
+        };
+
+        let index_path = index_path(path);
+        let mut index_file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&index_path)
+            .map_err(|err| io_error("open the index of", path, err))?;
+        index_file
+            .write_all(&index)
+            .map_err(|err| io_error("write the index of", path, err))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            data: BufWriter::new(file),
+            index: index_file,
+            offset,
+            pending_index: Vec::new(),
+            highest_slot,
+        })
+    }
+
+    fn write_frame(&mut self, slot: u64, recorded_at: u64, payload: &[u8]) -> CarbonResult<()> {
+        let length = u32::try_from(payload.len())
+            .map_err(|_| Error::Custom(format!("update of slot {slot} is too large to record")))?;
+        let frame_offset = self.offset;
+
+        let mut frame_header = [0u8; FRAME_HEADER_LEN];
+        frame_header[..4].copy_from_slice(&length.to_le_bytes());
+        frame_header[4..12].copy_from_slice(&slot.to_le_bytes());
+        frame_header[12..].copy_from_slice(&recorded_at.to_le_bytes());
+
+        self.data
+            .write_all(&frame_header)
+            .and_then(|_| self.data.write_all(payload))
+            .map_err(|err| io_error("write", &self.path, err))?;
+        self.offset += (FRAME_HEADER_LEN + payload.len()) as u64;
+
+        if self
+            .highest_slot
+            .is_none_or(|highest_slot| slot > highest_slot)
+        {
+            self.highest_slot = Some(slot);
+            self.pending_index.extend_from_slice(&slot.to_le_bytes());
+            self.pending_index
+                .extend_from_slice(&frame_offset.to_le_bytes());
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the buffered frames, then the index entries pointing to them.
+    fn flush(&mut self) -> CarbonResult<()> {
+        self.data
+            .flush()
+            .map_err(|err| io_error("write", &self.path, err))?;
+        self.index
+            .write_all(&self.pending_index)
+            .map_err(|err| io_error("write the index of", &self.path, err))?;
+        self.pending_index.clear();
+        Ok(())
+    }
+}
+
+/// Reads the frames of a recording in order.
+struct RecordingReader {
+    path: PathBuf,
+    reader: BufReader<File>,
+    offset: u64,
+}
+
+impl RecordingReader {
+    fn open(path: &Path) -> CarbonResult<Self> {
+        let file = File::open(path).map_err(|err| io_error("open", path, err))?;
+        Self::new(file, path)
+    }
+
+    fn new(mut file: File, path: &Path) -> CarbonResult<Self> {
+        let mut header = [0u8; HEADER_LEN as usize];
+        file.seek(SeekFrom::Start(0))
+            .and_then(|_| file.read_exact(&mut header))
+            .map_err(|err| io_error("read", path, err))?;
+        check_header(&header, path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            reader: BufReader::new(file),
+            offset: HEADER_LEN,
+        })
+    }
+
+    /// Moves to the first frame that can belong to `slot` or a later slot,
+    /// using the slot index if there is one.
+    fn seek_to_slot(&mut self, slot: u64) -> CarbonResult<()> {
+        let index = match std::fs::read(index_path(&self.path)) {
+            Ok(index) => index,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(io_error("read the index of", &self.path, err)),
+        };
+
+        let entries = index.chunks_exact(INDEX_ENTRY_LEN).map(|entry| {
+            let (slot, offset) = entry.split_at(8);
+            (
+                u64::from_le_bytes(slot.try_into().expect("index slot is 8 bytes")),
+                u64::from_le_bytes(offset.try_into().expect("index offset is 8 bytes")),
+            )
+        });
+
+        // The first frame of a slot at or after `slot` is indexed. If the
+        // index stops before it, the frames after the last indexed one are
+        // scanned instead.
+        let mut start = HEADER_LEN;
+        for (indexed_slot, offset) in entries {
+            start = offset;
+            if indexed_slot >= slot {
+                break;
+            }
+        }
+
+        self.reader
+            .seek(SeekFrom::Start(start))
+            .map_err(|err| io_error("seek in", &self.path, err))?;
+        self.offset = start;
+        Ok(())
+    }
+
+    /// Reads the header of the next frame, returning `None` at the end of the
+    /// recording or if the frame is incomplete.
+    fn next_frame_header(&mut self) -> CarbonResult<Option<(u32, u64, u64)>> {
+        let mut frame_header = [0u8; FRAME_HEADER_LEN];
+        if !self.read_complete(&mut frame_header)? {
+            return Ok(None);
+        }
+
+        Ok(Some((
+            u32::from_le_bytes(frame_header[..4].try_into().expect("length is 4 bytes")),
+            u64::from_le_bytes(frame_header[4..12].try_into().expect("slot is 8 bytes")),
+            u64::from_le_bytes(frame_header[12..].try_into().expect("timestamp is 8 bytes")),
+        )))
+    }
+
+    fn next_frame(&mut self) -> CarbonResult<Option<Frame>> {
+        let Some((length, slot, recorded_at)) = self.next_frame_header()? else {
+            return Ok(None);
+        };
+
+        let mut payload = vec![0u8; length as usize];
+        if !self.read_complete(&mut payload)? {
+            return Ok(None);
+        }
+        self.offset += (FRAME_HEADER_LEN + payload.len()) as u64;
+
+        Ok(Some(Frame {
+            slot,
+            recorded_at,
+            payload,
+        }))
+    }
+
+    /// Skips over the next complete frame, returning its slot and offset.
+    fn skip_frame(&mut self) -> CarbonResult<Option<(u64, u64)>> {
+        let Some((length, slot, _)) = self.next_frame_header()? else {
+            return Ok(None);
+        };
+
+        let mut payload = (&mut self.reader).take(length as u64);
+        let skipped = std::io::copy(&mut payload, &mut std::io::sink())
+            .map_err(|err| io_error("read", &self.path, err))?;
+        if skipped < length as u64 {
+            return Ok(None);
+        }
+
+        let offset = self.offset;
+        self.offset += FRAME_HEADER_LEN as u64 + skipped;
+        Ok(Some((slot, offset)))
+    }
+
+    /// Fills `buf`, returning `false` if the recording ends first.
+    fn read_complete(&mut self, buf: &mut [u8]) -> CarbonResult<bool> {
+        match self.reader.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(err) => Err(io_error("read", &self.path, err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::datasource::{
+            AccountDeletion, AccountUpdate, BlockDetails, SlotStatus, SlotStatusUpdate,
+            TransactionUpdate,
+        },
+        solana_account::Account,
+        solana_account_decoder_client_types::token::UiTokenAmount,
+        solana_hash::Hash,
+        solana_message::{
+            compiled_instruction::CompiledInstruction,
+            v0::{self, LoadedAddresses, MessageAddressTableLookup},
+            MessageHeader, VersionedMessage,
+        },
+        solana_pubkey::Pubkey,
+        solana_signature::Signature,
+        solana_transaction::versioned::VersionedTransaction,
+        solana_transaction_context::TransactionReturnData,
+        solana_transaction_error::TransactionError,
+        solana_transaction_status::{
+            InnerInstruction, InnerInstructions, Reward, TransactionStatusMeta,
+            TransactionTokenBalance,
+        },
+        tokio::time::Instant,
+    };
+
+    fn recording_path() -> PathBuf {
+        std::env::temp_dir().join(format!("carbon-replay-{}.rec", uuid::Uuid::new_v4()))
+    }
+
+    fn remove_recording(path: &Path) {
+        let _ = std::fs::remove_file(index_path(path));
+        let _ = std::fs::remove_file(path);
+    }
+
+    fn rewards() -> Vec<Reward> {
+        vec![Reward {
+            pubkey: Pubkey::new_unique().to_string(),
+            lamports: -5,
+            post_balance: 10,
+            reward_type: None,
+            commission: Some(3),
+        }]
+    }
+
+    fn token_balance(account_index: u8) -> TransactionTokenBalance {
+        TransactionTokenBalance {
+            account_index,
+            mint: Pubkey::new_unique().to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(1.5),
+                decimals: 6,
+                amount: "1500000".to_string(),
+                ui_amount_string: "1.5".to_string(),
+            },
+            owner: Pubkey::new_unique().to_string(),
+            program_id: Pubkey::new_unique().to_string(),
+        }
+    }
+
+    /// One update of every kind, with every optional field set.
+    fn updates() -> Vec<Update> {
+        let meta = TransactionStatusMeta {
+            status: Err(TransactionError::AccountNotFound),
+            fee: 5_000,
+            pre_balances: vec![10, 20],
+            post_balances: vec![5, 25],
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![InnerInstruction {
+                    instruction: CompiledInstruction {
+                        program_id_index: 1,
+                        accounts: vec![0, 2],
+                        data: vec![1, 2, 3],
+                    },
+                    stack_height: Some(2),
+                }],
+            }]),
+            log_messages: Some(vec!["Program log: test".to_string()]),
+            pre_token_balances: Some(vec![token_balance(0)]),
+            post_token_balances: Some(vec![token_balance(2)]),
+            rewards: Some(rewards()),
+            loaded_addresses: LoadedAddresses {
+                writable: vec![Pubkey::new_unique()],
+                readonly: vec![Pubkey::new_unique()],
+            },
+            return_data: Some(TransactionReturnData {
+                program_id: Pubkey::new_unique(),
+                data: vec![4, 5],
+            }),
+            compute_units_consumed: Some(1_000),
+            cost_units: Some(2_000),
+        };
+        let message = VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            recent_blockhash: Hash::new_unique(),
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0],
+                data: vec![9],
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![1],
+            }],
+        });
+
+        vec![
+            Update::Account(AccountUpdate {
+                pubkey: Pubkey::new_unique(),
+                account: Account {
+                    lamports: 7,
+                    data: vec![1, 2, 3],
+                    owner: Pubkey::new_unique(),
+                    executable: true,
+                    rent_epoch: 8,
+                },
+                slot: 1,
+                transaction_signature: Some(Signature::from([1; 64])),
+            }),
+            Update::Transaction(Box::new(TransactionUpdate {
+                signature: Signature::from([2; 64]),
+                transaction: VersionedTransaction {
+                    signatures: vec![Signature::from([2; 64])],
+                    message,
+                },
+                meta,
+                is_vote: true,
+                slot: 2,
+                index: Some(3),
+                block_time: Some(-4),
+                block_hash: Some(Hash::new_unique()),
+            })),
+            Update::AccountDeletion(AccountDeletion {
+                pubkey: Pubkey::new_unique(),
+                slot: 3,
+                transaction_signature: Some(Signature::from([3; 64])),
+            }),
+            Update::BlockDetails(BlockDetails {
+                slot: 4,
+                block_hash: Some(Hash::new_unique()),
+                previous_block_hash: Some(Hash::new_unique()),
+                rewards: Some(rewards()),
+                num_reward_partitions: Some(5),
+                block_time: Some(6),
+                block_height: Some(7),
+            }),
+            Update::SlotStatus(SlotStatusUpdate {
+                slot: 5,
+                parent: Some(4),
+                status: SlotStatus::Dead,
+                dead_error: Some("dead".to_string()),
+            }),
+        ]
+    }
+
+    /// Sends `updates`, waiting `interval` before each one after the first,
+    /// then waits for cancellation if `wait_for_cancellation` is set. Send
+    /// errors are ignored.
+    struct UpdatesDatasource {
+        updates: Vec<Update>,
+        interval: Duration,
+        wait_for_cancellation: bool,
+    }
+
+    #[async_trait]
+    impl Datasource for UpdatesDatasource {
+        async fn consume(
+            &self,
+            id: DatasourceId,
+            sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+            cancellation_token: CancellationToken,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            for (position, update) in self.updates.iter().enumerate() {
+                if position > 0 {
+                    tokio::time::sleep(self.interval).await;
+                }
+                let _ = sender.send((update.clone(), id.clone())).await;
+            }
+
+            if self.wait_for_cancellation {
+                cancellation_token.cancelled().await;
+            }
+            Ok(())
+        }
+
+        fn update_types(&self) -> Vec<UpdateType> {
+            vec![]
+        }
+    }
+
+    /// Runs `datasource` to completion, returning the updates it sent.
+    async fn consume(datasource: &dyn Datasource) -> CarbonResult<Vec<(Update, DatasourceId)>> {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(100);
+        datasource
+            .consume(
+                DatasourceId::new_named("test"),
+                sender,
+                CancellationToken::new(),
+                Arc::new(MetricsCollection::new(vec![])),
+            )
+            .await?;
+
+        let mut updates = Vec::new();
+        while let Ok(update) = receiver.try_recv() {
+            updates.push(update);
+        }
+        Ok(updates)
+    }
+
+    fn debug<T: std::fmt::Debug>(value: &T) -> String {
+        format!("{value:?}")
+    }
+
+    fn slot_status(slot: u64) -> Update {
+        Update::SlotStatus(SlotStatusUpdate {
+            slot,
+            parent: Some(slot - 1),
+            status: SlotStatus::Confirmed,
+            dead_error: None,
+        })
+    }
+
+    fn read_slots(path: &Path, first_slot: u64) -> Vec<u64> {
+        let mut reader = RecordingReader::open(path).unwrap();
+        reader.seek_to_slot(first_slot).unwrap();
+
+        let mut slots = Vec::new();
+        while let Some(frame) = reader.next_frame().unwrap() {
+            let (update, _) = decode_update(&frame.payload).unwrap();
+            slots.push(update.slot());
+        }
+        slots
+    }
+
+    #[test]
+    fn test_seeks_to_slot_and_drops_incomplete_frame() {
+        let path = std::env::temp_dir().join(format!("carbon-replay-{}.rec", uuid::Uuid::new_v4()));
+        let datasource_id = DatasourceId::new_named("test");
+
+        let mut writer = RecordingWriter::open(&path).unwrap();
+        for slot in [10, 12, 11, 13] {
+            let payload = encode_update(&slot_status(slot), &datasource_id).unwrap();
+            writer.write_frame(slot, 0, &payload).unwrap();
+        }
+        writer.flush().unwrap();
+        writer.data.write_all(&[1, 2, 3]).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(read_slots(&path, 0), vec![10, 12, 11, 13]);
+        assert_eq!(read_slots(&path, 11), vec![12, 11, 13]);
+        assert_eq!(read_slots(&path, 13), vec![13]);
+
+        let mut writer = RecordingWriter::open(&path).unwrap();
+        let payload = encode_update(&slot_status(14), &datasource_id).unwrap();
+        writer.write_frame(14, 0, &payload).unwrap();
+        drop(writer);
+
+        assert_eq!(read_slots(&path, 14), vec![14]);
+
+        let _ = std::fs::remove_file(index_path(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_decodes_encoded_updates() {
+        let datasource_id = DatasourceId::new_named("test");
+
+        for update in updates() {
+            let decoded = decode_update(&encode_update(&update, &datasource_id).unwrap()).unwrap();
+
+            assert_eq!(debug(&decoded), debug(&(update, datasource_id.clone())));
+        }
+    }
+
+    #[test]
+    fn test_rejects_other_payload_schema_versions() {
+        let path = recording_path();
+        drop(RecordingWriter::open(&path).unwrap());
+
+        let mut recording = std::fs::read(&path).unwrap();
+        recording[10..12].copy_from_slice(&(PAYLOAD_SCHEMA_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, recording).unwrap();
+
+        assert!(RecordingReader::open(&path).is_err());
+        remove_recording(&path);
+    }
+
+    #[tokio::test]
+    async fn test_replays_recorded_updates() {
+        let path = recording_path();
+        let interval = Duration::from_millis(50);
+        let recording = RecordingDatasource::new(
+            UpdatesDatasource {
+                updates: updates(),
+                interval,
+                wait_for_cancellation: false,
+            },
+            &path,
+        )
+        .unwrap();
+
+        let recorded = consume(&recording).await.unwrap();
+        assert_eq!(
+            debug(&recorded),
+            debug(&consume(&ReplayDatasource::new(&path)).await.unwrap())
+        );
+
+        let started_at = Instant::now();
+        let replayed = consume(
+            &ReplayDatasource::new(&path)
+                .slot_range(2..=4)
+                .pace(ReplayPace::RealTime),
+        )
+        .await
+        .unwrap();
+        assert!(started_at.elapsed() >= interval);
+        assert_eq!(debug(&replayed), debug(&recorded[1..4]));
+
+        remove_recording(&path);
+    }
+
+    #[tokio::test]
+    async fn test_recording_fails_when_the_recording_cannot_be_written() {
+        let path = recording_path();
+        let recording = RecordingDatasource::new(
+            UpdatesDatasource {
+                updates: updates(),
+                interval: Duration::ZERO,
+                wait_for_cancellation: true,
+            },
+            &path,
+        )
+        .unwrap();
+        // Writes to a read-only handle fail.
+        recording.writer.lock().unwrap().data = BufWriter::new(File::open(&path).unwrap());
+
+        let result = tokio::time::timeout(Duration::from_secs(10), consume(&recording)).await;
+
+        assert!(matches!(result, Ok(Err(_))));
+        remove_recording(&path);
+    }
+}
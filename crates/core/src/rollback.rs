@@ -0,0 +1,491 @@
+//! Provides fork rollback notifications, letting processors undo the effects
+//! of updates from slots that did not make it onto the canonical chain.
+//!
+//! Datasources streaming at `processed` or `confirmed` commitment may deliver
+//! updates from slots that are later abandoned. When rollback pipes are
+//! registered through `PipelineBuilder::rollback`, the pipeline tracks the
+//! parent of every recent slot and emits a `RollbackEvent` for each slot whose
+//! updates were handed to the pipes but that turned out to be orphaned.
+//!
+//! # Overview
+//!
+//! - Parent links are taken from the `parent` of slot status updates and from
+//!   the `previous_block_hash` of block details, matched against the block
+//!   hashes reported by block details and transactions.
+//! - A slot reported as dead is rolled back immediately.
+//! - When a slot is finalized, its ancestors are walked back. Every tracked
+//!   slot below the finalized slot that is not one of its ancestors is rolled
+//!   back as orphaned.
+//!
+//! Rollback events are only emitted for slots whose updates reached the pipes,
+//! so slots whose updates the commitment buffer discarded are not rolled back.
+//! They are emitted in slot order, after every update received before
+//! them has been processed, including in concurrent execution mode.
+//!
+//! # Metrics
+//!
+//! - `slot_rollbacks`: A counter of the rollback events emitted.
+//! - `slot_rollbacks_failed`: A counter of the rollback events a pipe failed to
+//!   process.
+//!
+//! # Notes
+//!
+//! - At least one datasource must provide slot status updates (see
+//!   `UpdateType::SlotStatus`), and parent links must be available, either
+//!   through slot status updates or block details. Slots whose ancestry is
+//!   unknown when a later slot is finalized are dropped without a rollback.
+//! - Memory use is bounded: at most `MAX_TRACKED_SLOTS` slots are tracked, and
+//!   every slot below the highest finalized slot is pruned.
+
+use crate::datasource::{SlotStatus, SlotStatusUpdate, Update};
+use crate::error::CarbonResult;
+use crate::error_policy::ErrorPolicy;
+use crate::metrics::MetricsCollection;
+use crate::processor::Processor;
+use async_trait::async_trait;
+use solana_program::hash::Hash;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// The maximum number of slots the fork tracker keeps parent links for.
+pub const MAX_TRACKED_SLOTS: usize = 1024;
+
+/// Describes why a slot was rolled back.
+///
+/// - `Dead`: The slot was reported as dead by a datasource.
+/// - `Orphaned`: The slot is not an ancestor of `finalized_slot`, and can
+///   therefore never be finalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollbackReason {
+    Dead,
+    Orphaned { finalized_slot: u64 },
+}
+
+/// Notifies processors that the updates of a slot must be rolled back.
+///
+/// # Fields
+///
+/// - `slot`: The slot whose updates must be rolled back.
+/// - `block_hash`: The hash of the rolled back block, if it was reported.
+/// - `reason`: Why the slot was rolled back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackEvent {
+    pub slot: u64,
+    pub block_hash: Option<Hash>,
+    pub reason: RollbackReason,
+}
+
+/// A pipe for processing rollback events using a defined processor.
+///
+/// ## Fields
+///
+/// - `processor`: A `Processor` that undoes the updates of rolled back slots,
+///   for example by deleting the rows stored for them.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+pub struct RollbackPipe {
+    pub processor: Box<dyn Processor<InputType = RollbackEvent> + Send + Sync>,
+    pub error_policy: ErrorPolicy,
+}
+
+/// An async trait for processing rollback events.
+///
+/// # Required Methods
+///
+/// - `run`: Processes a rollback event and tracks the operation with metrics.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+#[async_trait]
+pub trait RollbackPipes: Send + Sync {
+    async fn run(
+        &mut self,
+        rollback: RollbackEvent,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RollbackPipes for RollbackPipe {
+    async fn run(
+        &mut self,
+        rollback: RollbackEvent,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        log::trace!("RollbackPipe::run(rollback: {rollback:?}, metrics)");
+
+        self.processor.process(rollback, metrics).await?;
+
+        Ok(())
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+}
+
+#[derive(Debug, Default)]
+struct TrackedSlot {
+    parent: Option<u64>,
+    block_hash: Option<Hash>,
+    previous_block_hash: Option<Hash>,
+    /// Whether any update of the slot was handed to the pipes.
+    delivered: bool,
+}
+
+/// Tracks the parent chain of recent slots and detects the slots that have to
+/// be rolled back.
+#[derive(Debug, Default)]
+pub(crate) struct ForkTracker {
+    slots: BTreeMap<u64, TrackedSlot>,
+    slots_by_hash: HashMap<Hash, u64>,
+    highest_finalized: Option<u64>,
+}
+
+impl ForkTracker {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the parent links carried by `update`, as soon as the pipeline
+    /// receives it, and returns the rollback events it triggers.
+    pub(crate) fn observe(&mut self, update: &Update) -> Vec<RollbackEvent> {
+        match update {
+            Update::SlotStatus(slot_status) => return self.apply_slot_status(slot_status),
+            Update::Account(account_update) => {
+                self.track(account_update.slot);
+            }
+            Update::AccountDeletion(account_deletion) => {
+                self.track(account_deletion.slot);
+            }
+            Update::Transaction(transaction_update) => {
+                self.track(transaction_update.slot);
+                if let Some(block_hash) = transaction_update.block_hash {
+                    self.record_block_hash(transaction_update.slot, block_hash);
+                }
+            }
+            Update::BlockDetails(block_details) => {
+                let tracked = self.track(block_details.slot);
+                if block_details.previous_block_hash.is_some() {
+                    tracked.previous_block_hash = block_details.previous_block_hash;
+                }
+                if let Some(block_hash) = block_details.block_hash {
+                    self.record_block_hash(block_details.slot, block_hash);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Records that `update` was handed to the pipes, so that its slot is
+    /// rolled back if it turns out to be orphaned.
+    pub(crate) fn delivered(&mut self, update: &Update) {
+        let slot = match update {
+            Update::SlotStatus(_) => return,
+            Update::Account(account_update) => account_update.slot,
+            Update::AccountDeletion(account_deletion) => account_deletion.slot,
+            Update::Transaction(transaction_update) => transaction_update.slot,
+            Update::BlockDetails(block_details) => block_details.slot,
+        };
+
+        if self
+            .highest_finalized
+            .is_none_or(|finalized| slot > finalized)
+        {
+            self.track(slot).delivered = true;
+        }
+    }
+
+    fn track(&mut self, slot: u64) -> &mut TrackedSlot {
+        if !self.slots.contains_key(&slot) {
+            while self.slots.len() >= MAX_TRACKED_SLOTS {
+                if let Some((evicted_slot, evicted)) = self.slots.pop_first() {
+                    log::warn!("fork tracker is full, no longer tracking slot {evicted_slot}");
+                    self.forget_hash(&evicted);
+                }
+            }
+        }
+
+        self.slots.entry(slot).or_default()
+    }
+
+    fn record_block_hash(&mut self, slot: u64, block_hash: Hash) {
+        if let Some(tracked) = self.slots.get_mut(&slot) {
+            tracked.block_hash = Some(block_hash);
+            self.slots_by_hash.insert(block_hash, slot);
+        }
+    }
+
+    fn forget_hash(&mut self, tracked: &TrackedSlot) {
+        if let Some(block_hash) = tracked.block_hash {
+            self.slots_by_hash.remove(&block_hash);
+        }
+    }
+
+    fn parent_of(&self, slot: u64) -> Option<u64> {
+        let tracked = self.slots.get(&slot)?;
+
+        tracked.parent.or_else(|| {
+            tracked
+                .previous_block_hash
+                .and_then(|previous_block_hash| self.slots_by_hash.get(&previous_block_hash))
+                .copied()
+        })
+    }
+
+    fn apply_slot_status(&mut self, slot_status: &SlotStatusUpdate) -> Vec<RollbackEvent> {
+        let slot = slot_status.slot;
+
+        if self
+            .highest_finalized
+            .is_some_and(|finalized| slot <= finalized)
+        {
+            return Vec::new();
+        }
+
+        match slot_status.status {
+            SlotStatus::Dead => {
+                let Some(tracked) = self.slots.remove(&slot) else {
+                    return Vec::new();
+                };
+                self.forget_hash(&tracked);
+
+                if tracked.delivered {
+                    vec![RollbackEvent {
+                        slot,
+                        block_hash: tracked.block_hash,
+                        reason: RollbackReason::Dead,
+                    }]
+                } else {
+                    Vec::new()
+                }
+            }
+            SlotStatus::Finalized => {
+                let tracked = self.track(slot);
+                if slot_status.parent.is_some() {
+                    tracked.parent = slot_status.parent;
+                }
+                self.finalize(slot)
+            }
+            SlotStatus::Processed | SlotStatus::Confirmed => {
+                if let Some(parent) = slot_status.parent {
+                    self.track(slot).parent = Some(parent);
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    fn finalize(&mut self, finalized_slot: u64) -> Vec<RollbackEvent> {
+        self.highest_finalized = Some(finalized_slot);
+
+        let oldest_slot = self
+            .slots
+            .first_key_value()
+            .map_or(finalized_slot, |(slot, _)| *slot);
+
+        // Walk back the ancestors of the finalized slot, up to the oldest
+        // tracked slot or the first slot whose parent is unknown. The ancestry
+        // of the slots below that point can't be determined.
+        let mut ancestors = HashSet::new();
+        let mut resolved_from = finalized_slot;
+        while resolved_from > oldest_slot {
+            match self.parent_of(resolved_from) {
+                Some(parent) if parent < resolved_from => {
+                    ancestors.insert(parent);
+                    resolved_from = parent;
+                }
+                _ => break,
+            }
+        }
+
+        let remaining = self.slots.split_off(&finalized_slot);
+        let pruned = std::mem::replace(&mut self.slots, remaining);
+
+        let mut rollbacks = Vec::new();
+        for (slot, tracked) in pruned {
+            self.forget_hash(&tracked);
+
+            if slot > resolved_from && !ancestors.contains(&slot) && tracked.delivered {
+                log::debug!("slot {slot} was orphaned by finalized slot {finalized_slot}");
+                rollbacks.push(RollbackEvent {
+                    slot,
+                    block_hash: tracked.block_hash,
+                    reason: RollbackReason::Orphaned { finalized_slot },
+                });
+            }
+        }
+
+        rollbacks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::datasource::{AccountUpdate, BlockDetails},
+        solana_account::Account,
+        solana_pubkey::Pubkey,
+    };
+
+    fn account_update(slot: u64) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey: Pubkey::new_unique(),
+            account: Account::default(),
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    fn slot_status(slot: u64, parent: Option<u64>, status: SlotStatus) -> Update {
+        Update::SlotStatus(SlotStatusUpdate {
+            slot,
+            parent,
+            status,
+            dead_error: None,
+        })
+    }
+
+    fn block_details(slot: u64, block_hash: Hash, previous_block_hash: Hash) -> Update {
+        Update::BlockDetails(BlockDetails {
+            slot,
+            block_hash: Some(block_hash),
+            previous_block_hash: Some(previous_block_hash),
+            rewards: None,
+            num_reward_partitions: None,
+            block_time: None,
+            block_height: None,
+        })
+    }
+
+    fn deliver(tracker: &mut ForkTracker, update: Update) {
+        tracker.observe(&update);
+        tracker.delivered(&update);
+    }
+
+    #[test]
+    fn test_rolls_back_dead_slot() {
+        let mut tracker = ForkTracker::new();
+
+        deliver(&mut tracker, account_update(10));
+        assert!(tracker
+            .observe(&slot_status(11, Some(10), SlotStatus::Dead))
+            .is_empty());
+
+        let rollbacks = tracker.observe(&slot_status(10, Some(9), SlotStatus::Dead));
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(rollbacks[0].slot, 10);
+        assert_eq!(rollbacks[0].reason, RollbackReason::Dead);
+    }
+
+    #[test]
+    fn test_rolls_back_orphaned_slots_on_finalization() {
+        let mut tracker = ForkTracker::new();
+
+        // 100 -> 101 -> 102 is abandoned in favor of 100 -> 103.
+        tracker.observe(&slot_status(100, Some(99), SlotStatus::Processed));
+        deliver(&mut tracker, account_update(100));
+        tracker.observe(&slot_status(101, Some(100), SlotStatus::Processed));
+        deliver(&mut tracker, account_update(101));
+        tracker.observe(&slot_status(102, Some(101), SlotStatus::Processed));
+        deliver(&mut tracker, account_update(102));
+        deliver(&mut tracker, account_update(103));
+        deliver(&mut tracker, account_update(104));
+
+        let rollbacks = tracker.observe(&slot_status(103, Some(100), SlotStatus::Finalized));
+        let slots: Vec<u64> = rollbacks.iter().map(|rollback| rollback.slot).collect();
+        assert_eq!(slots, vec![101, 102]);
+        assert_eq!(
+            rollbacks[0].reason,
+            RollbackReason::Orphaned {
+                finalized_slot: 103
+            }
+        );
+
+        assert_eq!(
+            tracker.slots.keys().copied().collect::<Vec<_>>(),
+            vec![103, 104]
+        );
+    }
+
+    #[test]
+    fn test_follows_block_hashes() {
+        let mut tracker = ForkTracker::new();
+        let (hash_10, hash_11, hash_12) =
+            (Hash::new_unique(), Hash::new_unique(), Hash::new_unique());
+
+        deliver(&mut tracker, block_details(10, hash_10, Hash::new_unique()));
+        deliver(&mut tracker, block_details(11, hash_11, hash_10));
+        deliver(&mut tracker, block_details(12, hash_12, hash_10));
+
+        let rollbacks = tracker.observe(&slot_status(12, None, SlotStatus::Finalized));
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(rollbacks[0].slot, 11);
+        assert_eq!(rollbacks[0].block_hash, Some(hash_11));
+    }
+
+    #[test]
+    fn test_drops_slots_with_unknown_ancestry() {
+        let mut tracker = ForkTracker::new();
+
+        deliver(&mut tracker, account_update(10));
+        deliver(&mut tracker, account_update(11));
+
+        assert!(tracker
+            .observe(&slot_status(12, None, SlotStatus::Finalized))
+            .is_empty());
+        assert!(tracker.slots.range(..12).next().is_none());
+    }
+
+    #[test]
+    fn test_skips_slots_without_delivered_updates() {
+        let mut tracker = ForkTracker::new();
+
+        // The updates of slot 11 were held back and never reached the pipes.
+        tracker.observe(&slot_status(10, Some(9), SlotStatus::Processed));
+        deliver(&mut tracker, account_update(10));
+        tracker.observe(&slot_status(11, Some(10), SlotStatus::Processed));
+        tracker.observe(&account_update(11));
+        tracker.observe(&slot_status(12, Some(10), SlotStatus::Processed));
+
+        assert!(tracker
+            .observe(&slot_status(12, Some(10), SlotStatus::Finalized))
+            .is_empty());
+        assert!(tracker
+            .observe(&slot_status(13, Some(12), SlotStatus::Dead))
+            .is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+//! Provides the pipe for processing slot status updates.
+//!
+//! Slot status updates are emitted by datasources that stream data at a low
+//! commitment level, and report when a slot becomes confirmed, finalized or
+//! dead. The `SlotStatusPipe` hands them to a `Processor`, which makes it
+//! possible to react to these transitions, for example to mark previously
+//! stored data as final.
+
+use crate::datasource::SlotStatusUpdate;
+use crate::error::CarbonResult;
+use crate::error_policy::ErrorPolicy;
+use crate::filter::Filter;
+use crate::metrics::MetricsCollection;
+use crate::processor::Processor;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A pipe for processing slot status updates using a defined processor.
+///
+/// ## Fields
+///
+/// - `processor`: A `Processor` that processes slot status updates.
+/// - `filters`: A collection of filters that determine which slot status
+///   updates should be processed. Only updates that pass all filters (return
+///   `true`) will be processed. If this collection is empty, all updates are
+///   processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+pub struct SlotStatusPipe {
+    pub processor: Box<dyn Processor<InputType = SlotStatusUpdate> + Send + Sync>,
+    pub filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    pub error_policy: ErrorPolicy,
+}
+
+/// An async trait for processing slot status updates.
+///
+/// # Required Methods
+///
+/// - `run`: Processes a slot status update and tracks the operation with
+///   metrics.
+/// - `filters`: Returns a reference to the filters associated with this pipe,
+///   which are used by the pipeline to determine which slot status updates
+///   should be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+#[async_trait]
+pub trait SlotStatusPipes: Send + Sync {
+    async fn run(
+        &mut self,
+        slot_status: SlotStatusUpdate,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()>;
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SlotStatusPipes for SlotStatusPipe {
+    async fn run(
+        &mut self,
+        slot_status: SlotStatusUpdate,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        log::trace!("SlotStatus::run(slot_status: {slot_status:?}, metrics)");
+
+        self.processor.process(slot_status, metrics).await?;
+
+        Ok(())
+    }
+
+    fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
+        &self.filters
+    }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+}
@@ -0,0 +1,623 @@
+//! Supervises the datasources of a running pipeline, restarting the ones that
+//! fail according to a `RestartPolicy`.
+//!
+//! Every datasource's `consume` runs in its own supervised task. When it
+//! returns an error, the supervisor waits for a backoff delay and calls
+//! `consume` again, until the datasource has been restarted `max_restarts`
+//! times in a row. A datasource that ran for at least `reset_after` before
+//! failing starts counting its restarts from zero again.
+//!
+//! A datasource whose `consume` returns `Ok`, or that fails while the pipeline
+//! is shutting down, is not restarted.
+//!
+//! # Datasource Health
+//!
+//! The state of every datasource is published to the pipeline's
+//! `DatasourceHealth`, which can be inspected while the pipeline runs through
+//! `Pipeline::datasource_health`. Once no datasource is running anymore and at
+//! least one of them failed permanently, the pipeline stops and `run` returns
+//! the error of the first datasource that failed permanently. The failed
+//! datasources are logged by their ID.
+//!
+//! Every time a datasource is registered, it gets a new generation, and its
+//! supervisor only updates the status of its own generation. A datasource
+//! removed and added again under the same ID is therefore not overwritten by
+//! the old supervisor stopping.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use carbon_core::{pipeline::Pipeline, supervisor::RestartPolicy};
+//!
+//! Pipeline::builder()
+//!     .datasource(helius_websocket)
+//!     .restart_policy(RestartPolicy {
+//!         max_restarts: Some(20),
+//!         initial_backoff: Duration::from_millis(500),
+//!         max_backoff: Duration::from_secs(60),
+//!         reset_after: Duration::from_secs(300),
+//!     })
+//!     .instruction(TestProgramDecoder, TestProgramProcessor)
+//!     .build()?
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! # Metrics
+//!
+//! - `datasources_running`: A gauge of the datasources currently running.
+//! - `datasource_restarts`: A counter of datasource restarts, also recorded
+//!   per datasource as `datasource_restarts.{id}`, or
+//!   `datasource_restarts.unnamed` for datasources without a name.
+//! - `datasources_failed`: A counter of the datasources that failed
+//!   permanently.
+
+use {
+    crate::{
+        datasource::{Datasource, DatasourceId, Update},
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+    },
+    serde::Serialize,
+    std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        time::{Duration, Instant},
+    },
+    tokio_util::sync::CancellationToken,
+};
+
+/// The default number of consecutive restarts before a datasource is
+/// considered permanently down.
+pub const DEFAULT_MAX_RESTARTS: u32 = 10;
+
+/// The default delay before the first restart of a datasource.
+pub const DEFAULT_INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The default upper bound on the delay between restarts of a datasource.
+pub const DEFAULT_MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The default time a datasource must run before its restart count is reset.
+pub const DEFAULT_RESTART_RESET_AFTER: Duration = Duration::from_secs(300);
+
+/// Decides whether and when a failed datasource is restarted.
+///
+/// # Fields
+///
+/// - `max_restarts`: The number of consecutive restarts after which the
+///   datasource is considered permanently down, or `None` to restart it
+///   forever.
+/// - `initial_backoff`: The delay before the first restart. The delay doubles
+///   after every further failure.
+/// - `max_backoff`: The upper bound on the delay between restarts.
+/// - `reset_after`: How long a datasource must run before failing for its
+///   restart count to be reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub max_restarts: Option<u32>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub reset_after: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: Some(DEFAULT_MAX_RESTARTS),
+            initial_backoff: DEFAULT_INITIAL_RESTART_BACKOFF,
+            max_backoff: DEFAULT_MAX_RESTART_BACKOFF,
+            reset_after: DEFAULT_RESTART_RESET_AFTER,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Creates a policy that never restarts a failed datasource.
+    pub fn never() -> Self {
+        Self {
+            max_restarts: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Returns how long to wait before restarting a datasource that has
+    /// already been restarted `restarts` times in a row, or `None` if it
+    /// should not be restarted again.
+    pub fn backoff(&self, restarts: u32) -> Option<Duration> {
+        if self
+            .max_restarts
+            .is_some_and(|max_restarts| restarts >= max_restarts)
+        {
+            return None;
+        }
+
+        let factor = 2u32.saturating_pow(restarts);
+        Some(
+            self.initial_backoff
+                .saturating_mul(factor)
+                .min(self.max_backoff),
+        )
+    }
+}
+
+/// The state of a supervised datasource.
+///
+/// - `Running`: `consume` is running.
+/// - `Restarting`: `consume` failed and is waiting to be restarted, for the
+///   `restarts`-th time in a row.
+/// - `Stopped`: `consume` returned without an error, or the pipeline is
+///   shutting down.
+/// - `Failed`: `consume` failed and the `RestartPolicy` gave up on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DatasourceStatus {
+    Running,
+    Restarting { restarts: u32, error: String },
+    Stopped,
+    Failed { error: String },
+}
+
+/// The latest `DatasourceStatus` of every datasource of a pipeline.
+///
+/// # Example
+///
+/// ```ignore
+/// let health = pipeline.datasource_health();
+///
+/// tokio::spawn(pipeline.run());
+///
+/// for (id, status) in health.statuses() {
+///     println!("{id:?}: {status:?}");
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct DatasourceHealth {
+    /// Every datasource with the generation of its supervisor and its status.
+    statuses: RwLock<Vec<(DatasourceId, u64, DatasourceStatus)>>,
+    next_generation: AtomicU64,
+    /// The errors of the datasources that failed permanently, handed back by
+    /// `Pipeline::run`.
+    errors: Mutex<Vec<(DatasourceId, Error)>>,
+}
+
+impl DatasourceHealth {
+    /// Returns the status of every datasource, in the order they were added to
+    /// the pipeline.
+    pub fn statuses(&self) -> Vec<(DatasourceId, DatasourceStatus)> {
+        self.statuses
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(id, _, status)| (id.clone(), status.clone()))
+            .collect()
+    }
+
+    /// Returns the status of a datasource, if it is known.
+    pub fn status(&self, id: &DatasourceId) -> Option<DatasourceStatus> {
+        self.statuses
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .find(|(datasource_id, _, _)| datasource_id == id)
+            .map(|(_, _, status)| status.clone())
+    }
+
+    /// Returns the number of datasources that are running or waiting to be
+    /// restarted.
+    pub fn active(&self) -> usize {
+        self.statuses
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter(|(_, _, status)| {
+                matches!(
+                    status,
+                    DatasourceStatus::Running | DatasourceStatus::Restarting { .. }
+                )
+            })
+            .count()
+    }
+
+    /// Returns the datasources that failed permanently, with their last error.
+    pub fn failed(&self) -> Vec<(DatasourceId, String)> {
+        self.statuses
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter_map(|(id, _, status)| match status {
+                DatasourceStatus::Failed { error } => Some((id.clone(), error.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Starts tracking a datasource as running, adding it if it is unknown,
+    /// and returns the generation its supervisor updates the status of.
+    pub(crate) fn register(&self, id: &DatasourceId) -> u64 {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        let mut statuses = self
+            .statuses
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match statuses
+            .iter_mut()
+            .find(|(datasource_id, _, _)| datasource_id == id)
+        {
+            Some((_, current_generation, current)) => {
+                *current_generation = generation;
+                *current = DatasourceStatus::Running;
+            }
+            None => statuses.push((id.clone(), generation, DatasourceStatus::Running)),
+        }
+        drop(statuses);
+
+        self.forget_error(id);
+
+        generation
+    }
+
+    /// Updates the status of a registered datasource, returning whether it
+    /// was updated. Datasources that were removed from the pipeline, or
+    /// registered again since `generation`, are left out.
+    pub(crate) fn set(&self, id: &DatasourceId, generation: u64, status: DatasourceStatus) -> bool {
+        let mut statuses = self
+            .statuses
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match statuses
+            .iter_mut()
+            .find(|(datasource_id, current_generation, _)| {
+                datasource_id == id && *current_generation == generation
+            }) {
+            Some((_, _, current)) => {
+                *current = status;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks a datasource as failed permanently, keeping its error.
+    pub(crate) fn fail(&self, id: &DatasourceId, generation: u64, error: Error) {
+        let failed = self.set(
+            id,
+            generation,
+            DatasourceStatus::Failed {
+                error: error.to_string(),
+            },
+        );
+
+        if failed {
+            self.errors
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push((id.clone(), error));
+        }
+    }
+
+    /// Takes the error of the datasource that failed permanently first.
+    pub(crate) fn take_first_error(&self) -> Option<(DatasourceId, Error)> {
+        let mut errors = self
+            .errors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        (!errors.is_empty()).then(|| errors.remove(0))
+    }
+
+    fn forget_error(&self, id: &DatasourceId) {
+        self.errors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|(datasource_id, _)| datasource_id != id);
+    }
+
+    /// Stops tracking a datasource removed from the pipeline.
+    pub(crate) fn remove(&self, id: &DatasourceId) {
+        self.statuses
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|(datasource_id, _, _)| datasource_id != id);
+
+        self.forget_error(id);
+    }
+
+    /// Returns the number of datasources that are running.
+    pub fn running(&self) -> usize {
+        self.statuses
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .filter(|(_, _, status)| *status == DatasourceStatus::Running)
+            .count()
+    }
+}
+
+/// Runs a datasource, restarting it according to `restart_policy` until it
+/// stops, fails permanently or `cancellation_token` is cancelled. Only the
+/// status of the datasource's `generation` is updated.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn supervise(
+    id: DatasourceId,
+    generation: u64,
+    datasource: Arc<dyn Datasource + Send + Sync>,
+    sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+    cancellation_token: CancellationToken,
+    metrics: Arc<MetricsCollection>,
+    restart_policy: RestartPolicy,
+    health: Arc<DatasourceHealth>,
+) {
+    let mut restarts = 0;
+
+    loop {
+        set_status(
+            &health,
+            &metrics,
+            &id,
+            generation,
+            DatasourceStatus::Running,
+        )
+        .await;
+
+        let started = Instant::now();
+        let result = datasource
+            .consume(
+                id.clone(),
+                sender.clone(),
+                cancellation_token.clone(),
+                metrics.clone(),
+            )
+            .await;
+
+        let error = match result {
+            Ok(()) => {
+                log::info!("datasource {id:?} stopped.");
+                set_status(
+                    &health,
+                    &metrics,
+                    &id,
+                    generation,
+                    DatasourceStatus::Stopped,
+                )
+                .await;
+                return;
+            }
+            Err(error) if cancellation_token.is_cancelled() => {
+                log::error!("error consuming datasource {id:?} during shutdown: {error:?}");
+                set_status(
+                    &health,
+                    &metrics,
+                    &id,
+                    generation,
+                    DatasourceStatus::Stopped,
+                )
+                .await;
+                return;
+            }
+            Err(error) => error,
+        };
+
+        if started.elapsed() >= restart_policy.reset_after {
+            restarts = 0;
+        }
+
+        let Some(backoff) = restart_policy.backoff(restarts) else {
+            log::error!(
+                "error consuming datasource {id:?}, giving up after {restarts} restarts: {error:?}"
+            );
+            health.fail(&id, generation, error);
+            record(
+                metrics
+                    .update_gauge("datasources_running", health.running() as f64)
+                    .await,
+            );
+            record(metrics.increment_counter("datasources_failed", 1).await);
+            return;
+        };
+
+        restarts += 1;
+        log::warn!(
+            "error consuming datasource {id:?}, restarting in {backoff:?} (restart {restarts}): {error:?}"
+        );
+        set_status(
+            &health,
+            &metrics,
+            &id,
+            generation,
+            DatasourceStatus::Restarting {
+                restarts,
+                error: error.to_string(),
+            },
+        )
+        .await;
+        record(metrics.increment_counter("datasource_restarts", 1).await);
+        record(
+            metrics
+                .increment_counter(&format!("datasource_restarts.{}", id.metric_name()), 1)
+                .await,
+        );
+
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = cancellation_token.cancelled() => {
+                set_status(&health, &metrics, &id, generation, DatasourceStatus::Stopped).await;
+                return;
+            }
+        }
+    }
+}
+
+async fn set_status(
+    health: &DatasourceHealth,
+    metrics: &MetricsCollection,
+    id: &DatasourceId,
+    generation: u64,
+    status: DatasourceStatus,
+) {
+    health.set(id, generation, status);
+    record(
+        metrics
+            .update_gauge("datasources_running", health.running() as f64)
+            .await,
+    );
+}
+
+fn record(result: CarbonResult<()>) {
+    if let Err(error) = result {
+        log::error!("failed to record datasource metrics: {error:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{datasource::UpdateType, error::Error},
+        async_trait::async_trait,
+        std::sync::atomic::{AtomicU32, Ordering},
+    };
+
+    struct FlakyDatasource {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Datasource for FlakyDatasource {
+        async fn consume(
+            &self,
+            _id: DatasourceId,
+            _sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+            _cancellation_token: CancellationToken,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(Error::FailedToConsumeDatasource("flaky".to_string()));
+            }
+            Ok(())
+        }
+
+        fn update_types(&self) -> Vec<UpdateType> {
+            vec![]
+        }
+    }
+
+    async fn run(failures: u32, max_restarts: u32) -> (DatasourceStatus, u32) {
+        let datasource = Arc::new(FlakyDatasource {
+            failures,
+            calls: AtomicU32::new(0),
+        });
+        let health = Arc::new(DatasourceHealth::default());
+        let id = DatasourceId::new_named("flaky");
+        let (sender, _receiver) = tokio::sync::mpsc::channel(1);
+        let generation = health.register(&id);
+
+        supervise(
+            id.clone(),
+            generation,
+            datasource.clone(),
+            sender,
+            CancellationToken::new(),
+            Arc::new(MetricsCollection::new(vec![])),
+            RestartPolicy {
+                max_restarts: Some(max_restarts),
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+                reset_after: Duration::from_secs(60),
+            },
+            health.clone(),
+        )
+        .await;
+
+        (
+            health.status(&id).unwrap(),
+            datasource.calls.load(Ordering::SeqCst),
+        )
+    }
+
+    #[test]
+    fn test_restart_backoff() {
+        let policy = RestartPolicy {
+            max_restarts: Some(3),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(3),
+            reset_after: Duration::from_secs(60),
+        };
+
+        assert_eq!(policy.backoff(0), Some(Duration::from_secs(1)));
+        assert_eq!(policy.backoff(1), Some(Duration::from_secs(2)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_secs(3)));
+        assert_eq!(policy.backoff(3), None);
+        assert_eq!(RestartPolicy::never().backoff(0), None);
+    }
+
+    #[tokio::test]
+    async fn test_restarts_until_stopped() {
+        assert_eq!(run(2, 3).await, (DatasourceStatus::Stopped, 3));
+    }
+
+    #[tokio::test]
+    async fn test_fails_after_max_restarts() {
+        let (status, calls) = run(5, 2).await;
+
+        assert!(matches!(status, DatasourceStatus::Failed { .. }));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_ignores_status_of_previous_generation() {
+        let health = DatasourceHealth::default();
+        let id = DatasourceId::new_named("swapped");
+
+        let removed = health.register(&id);
+        health.remove(&id);
+        let added = health.register(&id);
+
+        assert!(!health.set(&id, removed, DatasourceStatus::Stopped));
+        health.fail(
+            &id,
+            removed,
+            Error::FailedToConsumeDatasource("removed".to_string()),
+        );
+        assert_eq!(health.status(&id), Some(DatasourceStatus::Running));
+        assert!(health.take_first_error().is_none());
+
+        assert!(health.set(&id, added, DatasourceStatus::Stopped));
+        assert_eq!(health.status(&id), Some(DatasourceStatus::Stopped));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_returns_error_of_failed_datasource() {
+        let result = crate::pipeline::Pipeline::builder()
+            .datasource_with_id(
+                FlakyDatasource {
+                    failures: 1,
+                    calls: AtomicU32::new(0),
+                },
+                DatasourceId::new_named("failing"),
+            )
+            .datasource_with_id(
+                FlakyDatasource {
+                    failures: 0,
+                    calls: AtomicU32::new(0),
+                },
+                DatasourceId::new_named("stopping"),
+            )
+            .restart_policy(RestartPolicy::never())
+            .ignore_shutdown_signals()
+            .build()
+            .unwrap()
+            .run()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::FailedToConsumeDatasource(message)) if message == "flaky"
+        ));
+    }
+}
@@ -34,9 +34,13 @@ use solana_program::hash::Hash;
 
 use {
     crate::{
+        balance_changes::{self, OwnerTokenBalanceChange, SolBalanceChange, TokenBalanceChange},
         collection::InstructionDecoderCollection,
+        compute_budget::ComputeBudget,
         error::CarbonResult,
+        error_policy::ErrorPolicy,
         instruction::{DecodedInstruction, InstructionMetadata, NestedInstruction},
+        logs::{parse_log_tree, InstructionLogs},
         metrics::MetricsCollection,
         processor::Processor,
         schema::{ParsedInstruction, TransactionSchema},
@@ -79,6 +83,58 @@ pub struct TransactionMetadata {
     pub block_hash: Option<Hash>,
 }
 
+impl TransactionMetadata {
+    /// Returns the account keys of the transaction, including the ones loaded
+    /// from address lookup tables, in the order the status metadata refers to
+    /// them.
+    pub fn account_keys(&self) -> Vec<Pubkey> {
+        let loaded_addresses = &self.meta.loaded_addresses;
+        let mut account_keys = self.message.static_account_keys().to_vec();
+        account_keys.extend_from_slice(&loaded_addresses.writable);
+        account_keys.extend_from_slice(&loaded_addresses.readonly);
+        account_keys
+    }
+
+    /// Parses the log messages of the transaction into one `InstructionLogs`
+    /// per top-level instruction, with the compute units, outcome and log
+    /// lines of every instruction.
+    ///
+    /// Returns an empty vector if the transaction has no log messages. See the
+    /// `logs` module.
+    pub fn log_tree(&self) -> Vec<InstructionLogs> {
+        self.meta
+            .log_messages
+            .as_deref()
+            .map(parse_log_tree)
+            .unwrap_or_default()
+    }
+
+    /// Returns the compute budget of the transaction: its compute unit limit
+    /// and price, its fee split into base and priority fees, and the compute
+    /// units it consumed. See the `compute_budget` module.
+    pub fn compute_budget(&self) -> ComputeBudget {
+        ComputeBudget::from_transaction(self)
+    }
+
+    /// Returns the lamport changes of the accounts of the transaction. See the
+    /// `balance_changes` module.
+    pub fn sol_balance_changes(&self) -> Vec<SolBalanceChange> {
+        balance_changes::sol_balance_changes(self)
+    }
+
+    /// Returns the token amount changes of the token accounts of the
+    /// transaction. See the `balance_changes` module.
+    pub fn token_balance_changes(&self) -> Vec<TokenBalanceChange> {
+        balance_changes::token_balance_changes(self)
+    }
+
+    /// Returns the token amount changes of the transaction summed per owner
+    /// and mint. See the `balance_changes` module.
+    pub fn owner_token_balance_changes(&self) -> Vec<OwnerTokenBalanceChange> {
+        balance_changes::owner_token_balance_changes(self)
+    }
+}
+
 /// Tries convert transaction update into the metadata.
 ///
 /// This function retrieves core metadata such as the transaction's slot,
@@ -103,6 +159,38 @@ impl TryFrom<crate::datasource::TransactionUpdate> for TransactionMetadata {
     type Error = crate::error::Error;
 
     fn try_from(value: crate::datasource::TransactionUpdate) -> Result<Self, Self::Error> {
+        log::trace!("try_from(transaction_update: {value:?})");
+        let fee_payer = *value
+            .transaction
+            .message
+            .static_account_keys()
+            .first()
+            .ok_or(crate::error::Error::MissingFeePayer)?;
+
+        Ok(TransactionMetadata {
+            slot: value.slot,
+            signature: value.signature,
+            fee_payer,
+            meta: value.meta,
+            message: value.transaction.message,
+            index: value.index,
+            block_time: value.block_time,
+            block_hash: value.block_hash,
+        })
+    }
+}
+
+/// Builds `TransactionMetadata` from a borrowed `TransactionUpdate`, copying
+/// only the status metadata and the message rather than the whole update.
+///
+/// # Errors
+///
+/// Returns an error if the fee payer cannot be extracted from the transaction's
+/// account keys.
+impl TryFrom<&crate::datasource::TransactionUpdate> for TransactionMetadata {
+    type Error = crate::error::Error;
+
+    fn try_from(value: &crate::datasource::TransactionUpdate) -> Result<Self, Self::Error> {
         log::trace!("try_from(transaction_update: {value:?})");
         let accounts = value.transaction.message.static_account_keys();
 
@@ -154,10 +242,13 @@ pub type TransactionProcessorInputType<T, U = ()> = (
 ///   incoming transaction updates, and only updates that pass all filters
 ///   (return `true`) will be processed. If this collection is empty, all
 ///   updates are processed.
+/// - `error_policy`: The `ErrorPolicy` applied when the processor fails.
+///   Defaults to `ErrorPolicy::Skip`, see `with_error_policy`.
 pub struct TransactionPipe<T: InstructionDecoderCollection, U> {
     schema: Option<TransactionSchema<T>>,
     processor: Box<dyn Processor<InputType = TransactionProcessorInputType<T, U>> + Send + Sync>,
-    filters: Vec<Box<dyn Filter + Send + Sync + 'static>>,
+    filters: Arc<Vec<Box<dyn Filter + Send + Sync + 'static>>>,
+    error_policy: ErrorPolicy,
 }
 
 /// Represents a parsed transaction, including its metadata and parsed
@@ -199,10 +290,17 @@ impl<T: InstructionDecoderCollection, U> TransactionPipe<T, U> {
         Self {
             schema,
             processor: Box::new(processor),
-            filters,
+            filters: Arc::new(filters),
+            error_policy: ErrorPolicy::default(),
         }
     }
 
+    /// Sets the `ErrorPolicy` applied when the processor fails.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
     /// Matches parsed instructions against the schema and returns the data as
     /// type `U`.
     ///
@@ -276,6 +374,18 @@ pub fn parse_instructions<T: InstructionDecoderCollection>(
 /// - `filters`: Returns a reference to the filters associated with this pipe,
 ///   which are used by the pipeline to determine which transaction updates
 ///   should be processed.
+///
+/// # Provided Methods
+///
+/// - `error_policy`: Returns the `ErrorPolicy` the pipeline applies when this
+///   pipe fails. Defaults to `ErrorPolicy::Skip`.
+/// - `program_ids`: Returns the programs whose transactions the pipe
+///   processes. Defaults to an empty list, meaning any program.
+/// - `init`, `flush` and `shutdown`: Forward the lifecycle hooks of the
+///   pipeline to the processor. Do nothing by default.
+/// - `fork`: Returns a new instance of the pipe for another worker, sharing
+///   its schema and filters but with a fork of its processor. Defaults to
+///   `None`, meaning the pipe is shared between the workers.
 #[async_trait]
 pub trait TransactionPipes<'a>: Send + Sync {
     async fn run(
@@ -286,6 +396,30 @@ pub trait TransactionPipes<'a>: Send + Sync {
     ) -> CarbonResult<()>;
 
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>>;
+
+    fn error_policy(&self) -> ErrorPolicy {
+        ErrorPolicy::default()
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        Vec::new()
+    }
+
+    async fn init(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, _metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    fn fork(&self) -> Option<Box<dyn for<'b> TransactionPipes<'b>>> {
+        None
+    }
 }
 
 #[async_trait]
@@ -325,4 +459,33 @@ where
     fn filters(&self) -> &Vec<Box<dyn Filter + Send + Sync + 'static>> {
         &self.filters
     }
+
+    fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        T::program_ids()
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+
+    fn fork(&self) -> Option<Box<dyn for<'b> TransactionPipes<'b>>> {
+        Some(Box::new(TransactionPipe {
+            schema: self.schema.clone(),
+            processor: self.processor.fork()?,
+            filters: self.filters.clone(),
+            error_policy: self.error_policy,
+        }))
+    }
 }
@@ -0,0 +1,393 @@
+//! Provides the worker pool behind the pipeline's concurrent execution mode.
+//!
+//! The `WorkerPool` spreads updates over a fixed number of worker tasks, each
+//! fed through its own bounded queue. Updates are assigned to a worker by key:
+//!
+//! - Account updates and account deletions by `pubkey`.
+//! - Transactions by `signature`.
+//! - Block details and slot status updates by `slot`.
+//!
+//! Since a worker processes its queue in order, updates sharing a key are
+//! always processed in the order they were received, while updates with
+//! different keys may be processed in parallel.
+//!
+//! The depth of every worker queue is reported through the
+//! `worker_{index}_updates_queued` gauge.
+//!
+//! `WorkerPool::barrier` waits until every update queued so far has been
+//! processed, which the pipeline uses to order rollback events after the
+//! updates they roll back.
+
+use {
+    crate::{
+        datasource::{DatasourceId, Update},
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+        pipeline::PipeSet,
+    },
+    std::{
+        hash::{DefaultHasher, Hash, Hasher},
+        sync::Arc,
+    },
+    tokio::{
+        sync::{mpsc, oneshot},
+        task::JoinHandle,
+    },
+};
+
+/// A message queued on a worker.
+enum WorkerMessage {
+    /// An update to process.
+    Update(Update, DatasourceId),
+    /// A barrier, acknowledged once every message queued before it has been
+    /// processed.
+    Barrier(oneshot::Sender<()>),
+}
+
+/// A fixed-size pool of workers that process updates through a shared
+/// `PipeSet`, each with its own instance of the pipes that can be forked.
+pub(crate) struct WorkerPool {
+    senders: Vec<mpsc::Sender<WorkerMessage>>,
+    handles: Vec<JoinHandle<()>>,
+    metrics: Arc<MetricsCollection>,
+}
+
+impl WorkerPool {
+    /// Spawns `workers` tasks, each with a queue of `worker_queue_size`
+    /// updates.
+    pub(crate) fn spawn(
+        workers: usize,
+        worker_queue_size: usize,
+        pipes: Arc<PipeSet>,
+        metrics: Arc<MetricsCollection>,
+    ) -> Self {
+        log::trace!(
+            "WorkerPool::spawn(workers: {workers}, worker_queue_size: {worker_queue_size})"
+        );
+
+        let mut senders = Vec::with_capacity(workers);
+        let mut handles = Vec::with_capacity(workers);
+
+        for index in 0..workers {
+            let (sender, mut receiver) = mpsc::channel::<WorkerMessage>(worker_queue_size);
+            let pipes = pipes.clone();
+            let metrics = metrics.clone();
+
+            handles.push(tokio::spawn(async move {
+                let queue_gauge = queue_gauge_name(index);
+
+                while let Some(message) = receiver.recv().await {
+                    let (update, datasource_id) = match message {
+                        WorkerMessage::Update(update, datasource_id) => (update, datasource_id),
+                        WorkerMessage::Barrier(acknowledge) => {
+                            let _ = acknowledge.send(());
+                            continue;
+                        }
+                    };
+
+                    let slot = update.slot();
+                    let datasource = datasource_id.clone();
+                    if let Err(error) = pipes
+                        .handle_update(update, datasource_id, index, &metrics)
+                        .await
+                    {
+                        log::error!(
+                            "worker {index} failed to process update at slot {slot} from datasource {datasource:?}: {error:?}"
+                        );
+                    }
+
+                    if let Err(error) = metrics
+                        .update_gauge(&queue_gauge, receiver.len() as f64)
+                        .await
+                    {
+                        log::error!("worker {index} failed to record metrics: {error:?}");
+                    }
+                }
+
+                log::trace!("worker {index} stopped.");
+            }));
+            senders.push(sender);
+        }
+
+        Self {
+            senders,
+            handles,
+            metrics,
+        }
+    }
+
+    /// Queues an update on the worker responsible for its key.
+    ///
+    /// Waits for room in the worker's queue if it is full, which propagates
+    /// backpressure to the pipeline's update channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is no longer running or if recording the
+    /// queue depth fails.
+    pub(crate) async fn dispatch(
+        &self,
+        update: Update,
+        datasource_id: DatasourceId,
+    ) -> CarbonResult<()> {
+        let index = worker_index(&update, self.senders.len());
+        let sender = &self.senders[index];
+
+        sender
+            .send(WorkerMessage::Update(update, datasource_id))
+            .await
+            .map_err(|_| Error::Custom(format!("worker {index} is no longer running")))?;
+
+        self.metrics
+            .update_gauge(
+                &queue_gauge_name(index),
+                (sender.max_capacity() - sender.capacity()) as f64,
+            )
+            .await
+    }
+
+    /// Waits until every update queued before the call has been processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worker is no longer running.
+    pub(crate) async fn barrier(&self) -> CarbonResult<()> {
+        let mut acknowledgements = Vec::with_capacity(self.senders.len());
+
+        for (index, sender) in self.senders.iter().enumerate() {
+            let (acknowledge, acknowledgement) = oneshot::channel();
+            sender
+                .send(WorkerMessage::Barrier(acknowledge))
+                .await
+                .map_err(|_| Error::Custom(format!("worker {index} is no longer running")))?;
+            acknowledgements.push((index, acknowledgement));
+        }
+
+        for (index, acknowledgement) in acknowledgements {
+            acknowledgement
+                .await
+                .map_err(|_| Error::Custom(format!("worker {index} is no longer running")))?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the worker queues and waits until every queued update has been
+    /// processed, or until `deadline`, after which the remaining workers are
+    /// aborted.
+    pub(crate) async fn drain(self, deadline: Option<tokio::time::Instant>) {
+        drop(self.senders);
+
+        for mut handle in self.handles {
+            let result = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, &mut handle).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        log::warn!(
+                            "drain timeout elapsed, dropping the updates queued for a worker."
+                        );
+                        handle.abort();
+                        continue;
+                    }
+                },
+                None => handle.await,
+            };
+
+            if let Err(error) = result {
+                log::error!("worker task failed: {error:?}");
+            }
+        }
+    }
+
+    /// Stops all workers immediately, dropping any queued updates.
+    pub(crate) async fn abort(self) {
+        drop(self.senders);
+
+        for handle in &self.handles {
+            handle.abort();
+        }
+
+        for handle in self.handles {
+            let _ = handle.await;
+        }
+    }
+}
+
+fn queue_gauge_name(index: usize) -> String {
+    format!("worker_{index}_updates_queued")
+}
+
+/// Returns the index of the worker responsible for `update`.
+fn worker_index(update: &Update, workers: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+
+    match update {
+        Update::Account(account_update) => account_update.pubkey.hash(&mut hasher),
+        Update::Transaction(transaction_update) => transaction_update.signature.hash(&mut hasher),
+        Update::AccountDeletion(account_deletion) => account_deletion.pubkey.hash(&mut hasher),
+        Update::BlockDetails(block_details) => block_details.slot.hash(&mut hasher),
+        Update::SlotStatus(slot_status) => slot_status.slot.hash(&mut hasher),
+    }
+
+    (hasher.finish() % workers as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            account::{AccountDecoder, AccountProcessorInputType, DecodedAccount},
+            datasource::AccountUpdate,
+            pipeline::{ExecutionMode, Pipeline},
+            processor::Processor,
+        },
+        async_trait::async_trait,
+        solana_account::Account,
+        solana_pubkey::Pubkey,
+        std::time::Duration,
+        tokio::sync::Barrier,
+    };
+
+    struct SlotDecoder;
+
+    impl AccountDecoder<'_> for SlotDecoder {
+        type AccountType = ();
+
+        fn decode_account(&self, account: &Account) -> Option<DecodedAccount<()>> {
+            Some(DecodedAccount {
+                lamports: account.lamports,
+                data: (),
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            })
+        }
+    }
+
+    #[derive(Clone)]
+    struct RecordingProcessor {
+        processed: Arc<std::sync::Mutex<Vec<(Pubkey, u64)>>>,
+        barrier: Option<Arc<Barrier>>,
+    }
+
+    #[async_trait]
+    impl Processor for RecordingProcessor {
+        type InputType = AccountProcessorInputType<()>;
+
+        async fn process(
+            &mut self,
+            (metadata, _, _): Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            if let Some(barrier) = &self.barrier {
+                barrier.wait().await;
+            }
+            tokio::task::yield_now().await;
+            self.processed
+                .lock()
+                .unwrap()
+                .push((metadata.pubkey, metadata.slot));
+            Ok(())
+        }
+
+        fn fork(&self) -> Option<Box<dyn Processor<InputType = Self::InputType> + Send + Sync>> {
+            Some(Box::new(self.clone()))
+        }
+    }
+
+    fn account_update(pubkey: Pubkey, slot: u64) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey,
+            account: Account::default(),
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    fn worker_pool(workers: usize, processor: RecordingProcessor) -> WorkerPool {
+        let mut pipeline = Pipeline::builder()
+            .account(SlotDecoder, processor)
+            .execution_mode(ExecutionMode::Concurrent {
+                workers,
+                worker_queue_size: 16,
+            })
+            .build()
+            .unwrap();
+
+        WorkerPool::spawn(
+            workers,
+            16,
+            Arc::new(PipeSet::take_from(&mut pipeline)),
+            Arc::new(MetricsCollection::new(vec![])),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_keeps_order_per_key() {
+        let processed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let pool = worker_pool(
+            4,
+            RecordingProcessor {
+                processed: processed.clone(),
+                barrier: None,
+            },
+        );
+        let pubkeys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+
+        for slot in 0..20 {
+            for pubkey in &pubkeys {
+                pool.dispatch(account_update(*pubkey, slot), DatasourceId::new_unique())
+                    .await
+                    .unwrap();
+            }
+        }
+        pool.drain(None).await;
+
+        let processed = processed.lock().unwrap();
+        assert_eq!(processed.len(), 160);
+        for pubkey in &pubkeys {
+            let slots: Vec<u64> = processed
+                .iter()
+                .filter(|(key, _)| key == pubkey)
+                .map(|(_, slot)| *slot)
+                .collect();
+            assert_eq!(slots, (0..20).collect::<Vec<_>>());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_different_keys_in_parallel() {
+        let pubkeys = {
+            let first = Pubkey::new_unique();
+            let second = std::iter::repeat_with(Pubkey::new_unique)
+                .find(|pubkey| {
+                    worker_index(&account_update(*pubkey, 0), 2)
+                        != worker_index(&account_update(first, 0), 2)
+                })
+                .unwrap();
+            [first, second]
+        };
+        let processed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        // Both updates wait for each other, so they are only processed if
+        // the two workers run the pipe at the same time.
+        let pool = worker_pool(
+            2,
+            RecordingProcessor {
+                processed: processed.clone(),
+                barrier: Some(Arc::new(Barrier::new(2))),
+            },
+        );
+
+        for pubkey in pubkeys {
+            pool.dispatch(account_update(pubkey, 1), DatasourceId::new_unique())
+                .await
+                .unwrap();
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), pool.drain(None))
+            .await
+            .expect("workers were serialized");
+        assert_eq!(processed.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,93 @@
+//! # Account Decoding Module
+//!
+//! The `try_decode_accounts` module provides the `try_decode_accounts!` macro,
+//! the account counterpart of `try_decode_instructions!`.
+//!
+//! ## Usage
+//!
+//! To use the `try_decode_accounts!` macro, specify the account to decode
+//! along with a series of variant-type pairs. The macro looks up the types
+//! whose discriminator prefixes the account data and decodes the account as
+//! the first of them that succeeds. If no match is found, `None` is returned.
+
+/// Attempts to decode an account into a specific variant type.
+///
+/// The `try_decode_accounts!` macro takes an account and tries to decode its
+/// data into one of the provided variant types. If decoding is successful, it
+/// returns a `DecodedAccount` object with the decoded data wrapped in the
+/// specified variant. If none of the variant types match, it returns `None`.
+///
+/// # Syntax
+///
+/// ```ignore
+/// try_decode_accounts!(account, VariantA => TypeA, VariantB => TypeB, ...);
+/// ```
+///
+/// # Example
+///
+/// ```ignore
+/// use carbon_macros::try_decode_accounts;
+///
+/// let account = Account { /* initialize with lamports, data, owner, ... */ };
+///
+/// let decoded = try_decode_accounts!(
+///     account,
+///     MyAccount::Pool => Pool,
+///     MyAccount::Position => Position,
+/// );
+/// ```
+///
+/// # Parameters
+///
+/// - `$account`: The account being decoded, a `solana_account::Account`.
+/// - `$variant`: Enum variants that wrap the deserialized data. A variant may
+///   hold the type itself or a `Box` of it.
+/// - `$ty`: The type for each variant, which must implement
+///   `CarbonDeserialize`.
+///
+/// # Returns
+///
+/// Returns an `Option<DecodedAccount>` that contains the decoded account
+/// wrapped in the specified variant type if decoding is successful. If no
+/// variant type matches, it returns `None`.
+///
+/// # Notes
+///
+/// - Types are looked up by their `DISCRIMINATOR` exactly like in
+///   `try_decode_instructions!`, so longer discriminators are tried before
+///   shorter ones sharing their prefix, and types with an empty discriminator
+///   are tried last.
+#[macro_export]
+macro_rules! try_decode_accounts {
+    ($account:expr, $($variant:path => $ty:ty),* $(,)?) => {{
+        use carbon_core::deserialize::CarbonDeserialize;
+
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                $(<$ty as CarbonDeserialize>::DISCRIMINATOR),*
+            ]);
+
+        let account = &$account;
+        let data = account.data.as_slice();
+        let mut decoded = None;
+
+        for position in INDEX.get().candidates(data) {
+            decoded = carbon_core::match_position!(
+                position,
+                $(<$ty>::deserialize(data).map(|decoded_account| $variant(decoded_account.into()))),*
+            );
+
+            if decoded.is_some() {
+                break;
+            }
+        }
+
+        decoded.map(|decoded_account| carbon_core::account::DecodedAccount {
+            lamports: account.lamports,
+            data: decoded_account,
+            owner: account.owner,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        })
+    }};
+}
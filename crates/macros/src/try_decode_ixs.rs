@@ -7,9 +7,10 @@
 //! ## Usage
 //!
 //! To use the `try_decode_instructions!` macro, specify the instruction to
-//! decode along with a series of variant-type pairs. The macro attempts to
-//! decode the instruction into each type sequentially, returning the first
-//! successful match. If no match is found, `None` is returned.
+//! decode along with a series of variant-type pairs. The macro looks up the
+//! types whose discriminator prefixes the instruction data and decodes the
+//! instruction as the first of them that succeeds. If no match is found,
+//! `None` is returned.
 
 /// Attempts to decode an instruction into a specific variant type.
 ///
@@ -54,8 +55,9 @@
 ///   byte slice compatible with the deserialization process.
 /// - `$variant`: Enum variants that wrap the deserialized data. These variants
 ///   should correspond to valid instruction types within the context.
-/// - `$ty`: The type for each variant, which must implement a `deserialize`
-///   method to convert the instruction data into the appropriate form.
+/// - `$ty`: The type for each variant, which must implement
+///   `CarbonDeserialize` to convert the instruction data into the appropriate
+///   form.
 ///
 /// # Returns
 ///
@@ -65,11 +67,17 @@
 ///
 /// # Notes
 ///
-/// - Ensure that each `$ty` type implements a `deserialize` method, as this is
-///   necessary for the macro to attempt decoding. The deserialization method
-///   should handle byte slices.
-/// - The macro iterates over each variant type sequentially, returning the
-///   first successful match. If no types match, `None` is returned.
+/// - Ensure that each `$ty` type implements `CarbonDeserialize`, as its
+///   `DISCRIMINATOR` and `deserialize` method are necessary for the macro to
+///   attempt decoding.
+/// - The macro dispatches on the `DISCRIMINATOR` of each type through a
+///   `LazyDiscriminatorIndex`, built on the first call, and a `match` on the
+///   position of each candidate type, so only the types whose discriminator
+///   prefixes the data are deserialized. Longer discriminators are tried
+///   first, falling back to shorter ones if the data doesn't deserialize,
+///   types sharing a discriminator are tried in the order they are listed,
+///   and types with an empty discriminator are tried last. If no types
+///   match, `None` is returned.
 /// - This macro is especially useful for processing complex transactions where
 ///   multiple instruction types are possible, improving flexibility and
 ///   reducing boilerplate code.
@@ -77,17 +85,31 @@
 macro_rules! try_decode_instructions {
     ($instruction:expr, $($variant:path => $ty:ty),* $(,)?) => {{
         use carbon_core::deserialize::CarbonDeserialize;
-        $(
-            if let Some(decoded_instruction) = <$ty>::deserialize($instruction.data.as_slice()) {
-                Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: $instruction.program_id,
-                    accounts: $instruction.accounts.clone(),
-                    data: $variant(decoded_instruction),
-                })
-            } else
-        )*
-        {
-            None
+
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                $(<$ty as CarbonDeserialize>::DISCRIMINATOR),*
+            ]);
+
+        let instruction = &$instruction;
+        let data = instruction.data.as_slice();
+        let mut decoded = None;
+
+        for position in INDEX.get().candidates(data) {
+            decoded = carbon_core::match_position!(
+                position,
+                $(<$ty>::deserialize(data).map($variant)),*
+            );
+
+            if decoded.is_some() {
+                break;
+            }
         }
+
+        decoded.map(|decoded_instruction| carbon_core::instruction::DecodedInstruction {
+            program_id: instruction.program_id,
+            accounts: instruction.accounts.clone(),
+            data: decoded_instruction,
+        })
     }};
 }
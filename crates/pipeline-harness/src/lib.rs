@@ -0,0 +1,442 @@
+//! An in-process harness for running pipelines in tests.
+//!
+//! The harness feeds a fixed list of updates through a pipeline and lets the
+//! test inspect what its processors received:
+//!
+//! - `read_transaction_update` and `read_account_update` load fixtures into
+//!   `Update`s.
+//! - `MockDatasource` sends those updates, in order, and then finishes, which
+//!   lets the pipeline run to completion.
+//! - `Recorder` is a processor that keeps every input it receives.
+//! - `Recording` wraps the processor under test, keeping every input it
+//!   receives in a `Recorder` before handing it to the processor.
+//! - `run_pipeline` runs a pipeline over the updates sequentially, without
+//!   listening for SIGINT or SIGTERM.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use carbon_core::pipeline::Pipeline;
+//! use carbon_pipeline_harness::{read_transaction_update, run_pipeline, Recording};
+//!
+//! let processor = Recording::new(MyProgramProcessor::new(database.clone()));
+//! let recorder = processor.recorder();
+//! let builder = Pipeline::builder().instruction(MyProgramDecoder, processor);
+//!
+//! run_pipeline(builder, vec![read_transaction_update("tests/fixtures/swap_tx.json")?]).await?;
+//!
+//! assert_eq!(recorder.len(), 1);
+//! assert_eq!(database.swaps().len(), 1);
+//! ```
+//!
+//! # Notes
+//!
+//! The harness is meant for the tests of decoders and applications. It
+//! depends on `carbon-core`, so the unit tests of `carbon-core` can't use it
+//! and define their own datasources and processors instead. The fixture
+//! readers it builds on live in `carbon-test-utils`, which doesn't depend on
+//! `carbon-core`.
+
+use async_trait::async_trait;
+use carbon_core::{
+    datasource::{AccountUpdate, Datasource, DatasourceId, TransactionUpdate, Update, UpdateType},
+    error::CarbonResult,
+    metrics::MetricsCollection,
+    pipeline::{ExecutionMode, PipelineBuilder},
+    processor::Processor,
+    transformers::transaction_metadata_from_original_meta,
+};
+use carbon_test_utils::read_account;
+use solana_pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tokio_util::sync::CancellationToken;
+
+/// The address of the Vote program.
+const VOTE_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Vote111111111111111111111111111111111111111");
+
+/// A datasource that sends a fixed list of updates, in order, and then
+/// finishes.
+///
+/// The pipeline stops once every datasource has finished and all updates have
+/// been processed, so a pipeline whose only datasource is a `MockDatasource`
+/// runs to completion on its own.
+#[derive(Debug, Clone, Default)]
+pub struct MockDatasource {
+    pub updates: Vec<Update>,
+}
+
+impl MockDatasource {
+    pub fn new(updates: Vec<Update>) -> Self {
+        Self { updates }
+    }
+}
+
+#[async_trait]
+impl Datasource for MockDatasource {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: tokio::sync::mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        for update in self.updates.iter().cloned() {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break,
+                result = sender.send((update, id.clone())) => {
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        let mut update_types = Vec::new();
+        for update in &self.updates {
+            let update_type = match update {
+                Update::Account(_) => UpdateType::AccountUpdate,
+                Update::Transaction(_) => UpdateType::Transaction,
+                Update::AccountDeletion(_) => UpdateType::AccountDeletion,
+                Update::SlotStatus(_) => UpdateType::SlotStatus,
+                Update::BlockDetails(_) => UpdateType::BlockDetails,
+            };
+            if !update_types.contains(&update_type) {
+                update_types.push(update_type);
+            }
+        }
+        update_types
+    }
+}
+
+/// A processor that records every input it receives.
+///
+/// Clones share the same records, so a clone can be handed to the pipeline
+/// while the original is kept to inspect the records once it has run.
+pub struct Recorder<T> {
+    records: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T> Recorder<T> {
+    pub fn new() -> Self {
+        Self {
+            records: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns the number of inputs recorded so far.
+    pub fn len(&self) -> usize {
+        self.records.lock().expect("recorder lock poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes and returns the inputs recorded so far, in the order they
+    /// were received.
+    pub fn take(&self) -> Vec<T> {
+        std::mem::take(&mut *self.records.lock().expect("recorder lock poisoned"))
+    }
+
+    fn record(&self, data: T) {
+        self.records
+            .lock()
+            .expect("recorder lock poisoned")
+            .push(data);
+    }
+}
+
+impl<T: Clone> Recorder<T> {
+    /// Returns a copy of the inputs recorded so far, in the order they were
+    /// received.
+    pub fn records(&self) -> Vec<T> {
+        self.records.lock().expect("recorder lock poisoned").clone()
+    }
+}
+
+impl<T> Clone for Recorder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            records: self.records.clone(),
+        }
+    }
+}
+
+impl<T> Default for Recorder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Processor for Recorder<T> {
+    type InputType = T;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.record(data);
+        Ok(())
+    }
+}
+
+/// A processor that records every input it receives, then hands it to the
+/// processor it wraps.
+///
+/// Inputs are recorded before the wrapped processor runs, so the inputs it
+/// failed on are recorded too. The lifecycle hooks are forwarded as is.
+pub struct Recording<P: Processor> {
+    processor: P,
+    recorder: Recorder<P::InputType>,
+}
+
+impl<P: Processor> Recording<P> {
+    pub fn new(processor: P) -> Self {
+        Self {
+            processor,
+            recorder: Recorder::new(),
+        }
+    }
+
+    /// Returns a `Recorder` sharing the records of this processor, to inspect
+    /// them once the pipeline has run.
+    pub fn recorder(&self) -> Recorder<P::InputType> {
+        self.recorder.clone()
+    }
+}
+
+#[async_trait]
+impl<P> Processor for Recording<P>
+where
+    P: Processor + Send + Sync,
+    P::InputType: Clone + Send + 'static,
+{
+    type InputType = P::InputType;
+
+    async fn process(
+        &mut self,
+        data: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        self.recorder.record(data.clone());
+        self.processor.process(data, metrics).await
+    }
+
+    async fn init(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.init(metrics).await
+    }
+
+    async fn flush(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.flush(metrics).await
+    }
+
+    async fn shutdown(&mut self, metrics: Arc<MetricsCollection>) -> CarbonResult<()> {
+        self.processor.shutdown(metrics).await
+    }
+}
+
+/// Runs the pipeline configured by `builder` over `updates` until every
+/// update has been processed.
+///
+/// The updates are sent by a `MockDatasource` added to the builder and
+/// processed sequentially, one after the other, whatever the execution mode
+/// of the builder. The pipeline ignores SIGINT and SIGTERM, so tests can run
+/// it alongside each other.
+pub async fn run_pipeline(builder: PipelineBuilder, updates: Vec<Update>) -> CarbonResult<()> {
+    builder
+        .datasource(MockDatasource::new(updates))
+        .execution_mode(ExecutionMode::Sequential)
+        .ignore_shutdown_signals()
+        .build()?
+        .run()
+        .await
+}
+
+/// Reads a transaction fixture into an `Update`.
+///
+/// The fixture is the result of an RPC `getTransaction` request made with the
+/// `base64` encoding. Its `meta` is in the format read by
+/// `carbon_test_utils::read_transaction_meta`. The transaction is a vote if
+/// it invokes the Vote program.
+pub fn read_transaction_update<P: AsRef<Path>>(tx_path: P) -> anyhow::Result<Update> {
+    let data = fs::read(tx_path).map_err(|e| anyhow::anyhow!("Couldn't read fixture: {e}"))?;
+
+    let encoded_tx = serde_json::from_slice::<EncodedConfirmedTransactionWithStatusMeta>(&data)
+        .map_err(|e| anyhow::anyhow!("Couldn't deserialize fixture: {e}"))?;
+
+    let transaction = encoded_tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow::anyhow!("Couldn't decode fixture transaction"))?;
+    let meta = encoded_tx
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow::anyhow!("Fixture transaction has no meta"))?;
+    let meta = transaction_metadata_from_original_meta(meta)
+        .map_err(|e| anyhow::anyhow!("Couldn't convert fixture meta: {e}"))?;
+    let signature = *transaction
+        .signatures
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Fixture transaction has no signature"))?;
+    let account_keys = transaction.message.static_account_keys();
+    let is_vote = transaction
+        .message
+        .instructions()
+        .iter()
+        .any(|instruction| {
+            account_keys.get(instruction.program_id_index as usize) == Some(&VOTE_PROGRAM_ID)
+        });
+
+    Ok(Update::Transaction(Box::new(TransactionUpdate {
+        signature,
+        transaction,
+        meta,
+        is_vote,
+        slot: encoded_tx.slot,
+        index: None,
+        block_time: encoded_tx.block_time,
+        block_hash: None,
+    })))
+}
+
+/// Reads an account fixture, in the format read by
+/// `carbon_test_utils::read_account`, into an `Update` for the account at
+/// `pubkey`.
+pub fn read_account_update<P: AsRef<Path>>(
+    acc_path: P,
+    pubkey: Pubkey,
+    slot: u64,
+) -> anyhow::Result<Update> {
+    Ok(Update::Account(AccountUpdate {
+        pubkey,
+        account: read_account(acc_path)?,
+        slot,
+        transaction_signature: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        carbon_core::{
+            account::{AccountDecoder, AccountProcessorInputType, DecodedAccount},
+            datasource::{SlotStatus, SlotStatusUpdate},
+            error::Error,
+            pipeline::Pipeline,
+        },
+        solana_account::Account,
+    };
+
+    struct LamportsDecoder;
+
+    impl AccountDecoder<'_> for LamportsDecoder {
+        type AccountType = u64;
+
+        fn decode_account(&self, account: &Account) -> Option<DecodedAccount<u64>> {
+            Some(DecodedAccount {
+                lamports: account.lamports,
+                data: account.lamports,
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            })
+        }
+    }
+
+    /// Sums the lamports of the accounts it processes, failing on empty ones.
+    #[derive(Clone, Default)]
+    struct LamportsProcessor {
+        total: Arc<Mutex<u64>>,
+    }
+
+    #[async_trait]
+    impl Processor for LamportsProcessor {
+        type InputType = AccountProcessorInputType<u64>;
+
+        async fn process(
+            &mut self,
+            (_metadata, account, _raw_account): Self::InputType,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            if account.data == 0 {
+                return Err(Error::Custom("empty account".to_string()));
+            }
+            *self.total.lock().unwrap() += account.data;
+            Ok(())
+        }
+    }
+
+    fn account_update(lamports: u64, slot: u64) -> Update {
+        Update::Account(AccountUpdate {
+            pubkey: Pubkey::new_unique(),
+            account: Account {
+                lamports,
+                ..Default::default()
+            },
+            slot,
+            transaction_signature: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_recording_forwards_inputs_to_the_processor() {
+        let processor = LamportsProcessor::default();
+        let recording = Recording::new(processor.clone());
+        let recorder = recording.recorder();
+
+        run_pipeline(
+            Pipeline::builder().account(LamportsDecoder, recording),
+            vec![
+                account_update(1, 1),
+                account_update(0, 2),
+                account_update(2, 3),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*processor.total.lock().unwrap(), 3);
+        assert_eq!(
+            recorder
+                .take()
+                .into_iter()
+                .map(|(metadata, account, _)| (metadata.slot, account.data))
+                .collect::<Vec<_>>(),
+            [(1, 1), (2, 0), (3, 2)]
+        );
+        assert!(recorder.is_empty());
+    }
+
+    #[test]
+    fn test_mock_datasource_update_types() {
+        let datasource = MockDatasource::new(vec![
+            account_update(1, 1),
+            Update::SlotStatus(SlotStatusUpdate {
+                slot: 1,
+                parent: None,
+                status: SlotStatus::Processed,
+                dead_error: None,
+            }),
+            account_update(2, 2),
+        ]);
+
+        assert_eq!(
+            datasource.update_types(),
+            [UpdateType::AccountUpdate, UpdateType::SlotStatus]
+        );
+    }
+}
@@ -116,6 +116,8 @@ use {
 ///   deserialization will return `None` if there is a mismatch.
 /// - The macro will panic if the discriminator is invalid or not provided
 ///   correctly as a hex string when expected.
+/// - The discriminator is exposed as `CarbonDeserialize::DISCRIMINATOR`, which
+///   `try_decode_instructions!` indexes to dispatch on it in one step.
 ///
 /// # Errors
 ///
@@ -692,6 +694,7 @@ pub fn instruction_decoder_collection(input: TokenStream) -> TokenStream {
     let mut program_variants = Vec::new();
     let mut parse_instruction_arms = Vec::new();
     let mut get_type_arms = Vec::new();
+    let mut program_ids_extends = Vec::new();
 
     for entry in entries {
         let program_variant = entry.program_variant;
@@ -731,6 +734,17 @@ pub fn instruction_decoder_collection(input: TokenStream) -> TokenStream {
                 #instruction_types_enum_name::#program_variant(instruction.get_instruction_type())
             }
         });
+
+        program_ids_extends.push(quote! {
+            let decoder_program_ids =
+                carbon_core::instruction::InstructionDecoder::program_ids(&#decoder_expr);
+            // An empty list means that the decoder accepts any program, and
+            // so does the collection.
+            if decoder_program_ids.is_empty() {
+                return Vec::new();
+            }
+            program_ids.extend(decoder_program_ids);
+        });
     }
 
     let expanded = quote! {
@@ -764,6 +778,12 @@ pub fn instruction_decoder_collection(input: TokenStream) -> TokenStream {
                     #(#get_type_arms),*
                 }
             }
+
+            fn program_ids() -> Vec<solana_pubkey::Pubkey> {
+                let mut program_ids = Vec::new();
+                #(#program_ids_extends)*
+                program_ids
+            }
         }
     };
 
@@ -801,6 +821,7 @@ pub fn instruction_decoder_collection_fast(input: TokenStream) -> TokenStream {
     let mut parse_instruction_match_arms = Vec::new();
     let mut fallback_decode_blocks = Vec::new();
     let mut get_type_arms = Vec::new();
+    let mut program_ids_extends = Vec::new();
 
     for entry in entries {
         let program_variant = entry.program_variant;
@@ -863,6 +884,17 @@ pub fn instruction_decoder_collection_fast(input: TokenStream) -> TokenStream {
                 #instruction_types_enum_name::#program_variant(instruction.get_instruction_type())
             }
         });
+
+        program_ids_extends.push(quote! {
+            let decoder_program_ids =
+                carbon_core::instruction::InstructionDecoder::program_ids(&#decoder_expr);
+            // An empty list means that the decoder accepts any program, and
+            // so does the collection.
+            if decoder_program_ids.is_empty() {
+                return Vec::new();
+            }
+            program_ids.extend(decoder_program_ids);
+        });
     }
 
     let expanded = quote! {
@@ -901,6 +933,12 @@ pub fn instruction_decoder_collection_fast(input: TokenStream) -> TokenStream {
                     #(#get_type_arms),*
                 }
             }
+
+            fn program_ids() -> Vec<solana_pubkey::Pubkey> {
+                let mut program_ids = Vec::new();
+                #(#program_ids_extends)*
+                program_ids
+            }
         }
     };
 
@@ -1039,3 +1077,47 @@ pub fn instruction_type_derive(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Expands to a `match` on a position, whose arm for position `i` evaluates
+/// the `i`-th of the following expressions.
+///
+/// `try_decode_instructions!` and `try_decode_accounts!` use it to decode a
+/// candidate of a `DiscriminatorIndex` in one step, since `macro_rules!`
+/// macros cannot number their arguments.
+///
+/// # Syntax
+///
+/// ```ignore
+/// match_position!(position, expr_0, expr_1, ...)
+/// ```
+///
+/// # Return
+///
+/// Returns a `TokenStream` containing the `match`. Positions without an
+/// expression evaluate to `None`, so the expressions must return `Option`s.
+#[doc(hidden)]
+#[proc_macro]
+pub fn match_position(input: TokenStream) -> TokenStream {
+    let exprs = parse_macro_input!(
+        input with syn::punctuated::Punctuated::<syn::Expr, Token![,]>::parse_terminated
+    );
+    let mut exprs = exprs.into_iter();
+
+    let Some(position) = exprs.next() else {
+        return syn::Error::new(Span::call_site(), "expected a position")
+            .to_compile_error()
+            .into();
+    };
+
+    let arms = exprs.enumerate().map(|(index, expr)| {
+        let index = proc_macro2::Literal::usize_unsuffixed(index);
+        quote! { #index => #expr, }
+    });
+
+    TokenStream::from(quote! {
+        match #position {
+            #(#arms)*
+            _ => None,
+        }
+    })
+}
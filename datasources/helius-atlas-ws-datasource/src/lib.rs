@@ -7,10 +7,13 @@ use {
         },
         error::CarbonResult,
         metrics::MetricsCollection,
+        pipeline::PipelinePrograms,
     },
     futures::StreamExt,
     helius::{
-        types::{Cluster, RpcTransactionsConfig},
+        types::{
+            Cluster, RpcTransactionsConfig, TransactionSubscribeFilter, TransactionSubscribeOptions,
+        },
         Helius,
     },
     solana_account::Account,
@@ -57,6 +60,49 @@ impl Filters {
             transactions,
         })
     }
+
+    /// Creates filters that subscribe to the transactions involving the
+    /// programs of a pipeline, as returned by `PipelineBuilder::programs`.
+    ///
+    /// # Parameters
+    ///
+    /// - `programs`: The programs of the pipeline.
+    /// - `accounts`: The accounts to subscribe to. Account subscriptions are
+    ///   made per account, so they can't be derived from the account owners.
+    /// - `options`: The options of the transaction subscription.
+    ///
+    /// # Notes
+    ///
+    /// - If the instruction and transaction pipes accept transactions of any
+    ///   program, the subscription includes every transaction.
+    /// - If the pipeline has no instruction or transaction pipes, no
+    ///   transactions are subscribed to, so `accounts` can't be empty.
+    pub fn for_programs(
+        programs: &PipelinePrograms,
+        accounts: Vec<Pubkey>,
+        options: TransactionSubscribeOptions,
+    ) -> CarbonResult<Self> {
+        let account_include = match &programs.instruction_programs {
+            None => None,
+            Some(programs) if programs.is_empty() => return Self::new(accounts, None),
+            Some(programs) => Some(programs.iter().map(ToString::to_string).collect()),
+        };
+
+        Self::new(
+            accounts,
+            Some(RpcTransactionsConfig {
+                filter: TransactionSubscribeFilter {
+                    account_include,
+                    account_exclude: None,
+                    account_required: None,
+                    vote: None,
+                    failed: None,
+                    signature: None,
+                },
+                options,
+            }),
+        )
+    }
 }
 
 pub struct HeliusWebsocket {
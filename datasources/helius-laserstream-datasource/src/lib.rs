@@ -1,13 +1,18 @@
+pub use carbon_yellowstone_grpc_datasource::geyser::{
+    account_filters_for_programs, transaction_filters_for_programs,
+};
+
 use {
     async_trait::async_trait,
     carbon_core::{
         datasource::{
-            AccountDeletion, AccountUpdate, Datasource, DatasourceId, TransactionUpdate, Update,
-            UpdateType,
+            AccountDeletion, AccountUpdate, Datasource, DatasourceId, SlotStatus, SlotStatusUpdate,
+            TransactionUpdate, Update, UpdateType,
         },
         error::CarbonResult,
         metrics::MetricsCollection,
     },
+    carbon_yellowstone_grpc_datasource::geyser::send_subscribe_update_slot,
     futures::{sink::SinkExt, StreamExt},
     solana_account::Account,
     solana_pubkey::Pubkey,
@@ -39,6 +44,7 @@ use {
 
 const MAX_RECONNECTION_ATTEMPTS: u32 = 10;
 const RECONNECTION_DELAY_MS: u64 = 3000;
+const SLOT_STATUS_FILTER: &str = "carbon-slot-status";
 
 #[derive(Debug)]
 pub struct LaserStreamGeyserClient {
@@ -50,6 +56,9 @@ pub struct LaserStreamGeyserClient {
     pub block_filters: BlockFilters,
     pub account_deletions_tracked: Arc<RwLock<HashSet<Pubkey>>>,
     pub geyser_config: LaserStreamClientConfig,
+    /// Whether to subscribe to slot status changes and emit them as
+    /// `Update::SlotStatus`. Default: false.
+    pub slot_status_updates: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -104,8 +113,16 @@ impl LaserStreamGeyserClient {
             block_filters,
             account_deletions_tracked,
             geyser_config,
+            slot_status_updates: false,
         }
     }
+
+    /// Subscribes to slot status changes and emits them as `Update::SlotStatus`,
+    /// e.g. to drive the pipeline's commitment buffer.
+    pub fn with_slot_status_updates(mut self) -> Self {
+        self.slot_status_updates = true;
+        self
+    }
 }
 
 impl LaserStreamClientConfig {
@@ -180,6 +197,7 @@ impl Datasource for LaserStreamGeyserClient {
         let retain_block_failed_transactions = block_failed_transactions.unwrap_or(true);
         let geyser_config = self.geyser_config.clone();
         let replay_enabled = geyser_config.replay_enabled;
+        let slot_status_updates = self.slot_status_updates;
 
         let builder = GeyserGrpcClient::build_from_shared(endpoint.clone())
             .map_err(|err| carbon_core::error::Error::FailedToConsumeDatasource(err.to_string()))?
@@ -211,6 +229,16 @@ impl Datasource for LaserStreamGeyserClient {
                 from_slot: None,
             };
 
+            if slot_status_updates {
+                subscribe_request.slots.insert(
+                    SLOT_STATUS_FILTER.to_string(),
+                    SubscribeRequestFilterSlots {
+                        filter_by_commitment: Some(false),
+                        ..Default::default()
+                    },
+                );
+            }
+
             let internal_slot_sub_id = if replay_enabled {
                 let slot_id = format!(
                     "internal-{}",
@@ -306,16 +334,17 @@ impl Datasource for LaserStreamGeyserClient {
                                                 }
                                             }
                                             Some(UpdateOneof::Slot(slot_update)) => {
-                                                if replay_enabled {
-                                                    tracked_slot = slot_update.slot;
-                                                }
-
-                                                // Skip if this slot update is EXCLUSIVELY from our internal subscription
+                                                // Only track slots from our internal subscription, which
+                                                // follows the subscription's commitment level
                                                 if let Some(ref internal_id) = internal_slot_sub_id {
-                                                    if msg.filters.len() == 1 && msg.filters.contains(internal_id) {
-                                                        continue;
+                                                    if msg.filters.contains(internal_id) {
+                                                        tracked_slot = slot_update.slot;
                                                     }
                                                 }
+
+                                                if msg.filters.iter().any(|filter| filter == SLOT_STATUS_FILTER) {
+                                                    send_subscribe_update_slot(slot_update, &metrics, "laserstream_slot_status_updates_received", &sender, id_for_loop.clone()).await
+                                                }
                                             }
                                             Some(UpdateOneof::Ping(_)) => {
                                                 match subscribe_tx
@@ -365,11 +394,15 @@ impl Datasource for LaserStreamGeyserClient {
     }
 
     fn update_types(&self) -> Vec<UpdateType> {
-        vec![
+        let mut update_types = vec![
             UpdateType::AccountUpdate,
             UpdateType::Transaction,
             UpdateType::AccountDeletion,
-        ]
+        ];
+        if self.slot_status_updates {
+            update_types.push(UpdateType::SlotStatus);
+        }
+        update_types
     }
 }
 
@@ -151,7 +151,14 @@ impl Datasource for JetstreamerDatasource {
     }
 
     fn update_types(&self) -> Vec<carbon_core::datasource::UpdateType> {
-        vec![UpdateType::Transaction]
+        let mut update_types = Vec::new();
+        if self.filter.include_transactions {
+            update_types.push(UpdateType::Transaction);
+        }
+        if self.filter.include_blocks {
+            update_types.push(UpdateType::BlockDetails);
+        }
+        update_types
     }
 }
 
@@ -260,7 +267,7 @@ impl JetstreamerDatasource {
                     meta: transaction.transaction_status_meta,
                     is_vote: transaction.is_vote,
                     slot: transaction.slot,
-                    index: None,
+                    index: Some(transaction.transaction_slot_index as u64),
                     block_time: None,
                     block_hash: None,
                 })),
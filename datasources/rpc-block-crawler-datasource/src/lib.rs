@@ -5,7 +5,8 @@ use std::str::FromStr;
 use {
     async_trait::async_trait,
     carbon_core::{
-        datasource::{Datasource, TransactionUpdate, Update, UpdateType},
+        checkpoint::CheckpointStore,
+        datasource::{BlockDetails, Datasource, TransactionUpdate, Update, UpdateType},
         error::CarbonResult,
         metrics::MetricsCollection,
         transformers::transaction_metadata_from_original_meta,
@@ -31,6 +32,11 @@ const BLOCK_INTERVAL: Duration = Duration::from_millis(100);
 
 /// RpcBlockCrawler is a datasource that crawls the Solana blockchain for blocks and sends them to the sender.
 /// It uses a channel to send blocks to the task processor.
+///
+/// When given a checkpoint store via `with_checkpoint_store`, the crawler resumes
+/// from the slot after the last checkpoint committed for its datasource ID instead
+/// of `start_slot`. Blocks are fetched concurrently but sent in slot order, so every
+/// block up to the checkpoint has been sent and processed.
 pub struct RpcBlockCrawler {
     pub rpc_url: String,
     pub start_slot: u64,
@@ -39,6 +45,7 @@ pub struct RpcBlockCrawler {
     pub block_config: RpcBlockConfig,
     pub max_concurrent_requests: usize,
     pub channel_buffer_size: usize,
+    pub checkpoint_store: Option<Arc<dyn CheckpointStore>>,
 }
 
 impl RpcBlockCrawler {
@@ -59,6 +66,30 @@ impl RpcBlockCrawler {
             block_interval: block_interval.unwrap_or(BLOCK_INTERVAL),
             max_concurrent_requests: max_concurrent_requests.unwrap_or(MAX_CONCURRENT_REQUESTS),
             channel_buffer_size: channel_buffer_size.unwrap_or(CHANNEL_BUFFER_SIZE),
+            checkpoint_store: None,
+        }
+    }
+
+    /// Resumes crawling from the checkpoint stored for this datasource, if any.
+    pub fn with_checkpoint_store(mut self, checkpoint_store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
+    async fn resume_slot(&self, id: &DatasourceId) -> CarbonResult<u64> {
+        let Some(checkpoint_store) = &self.checkpoint_store else {
+            return Ok(self.start_slot);
+        };
+
+        match checkpoint_store.load(id).await? {
+            Some(checkpoint) => {
+                let resume_slot = (checkpoint.slot + 1).max(self.start_slot);
+                log::info!(
+                    "resuming block crawler from slot {resume_slot} (checkpoint: {checkpoint:?})"
+                );
+                Ok(resume_slot)
+            }
+            None => Ok(self.start_slot),
         }
     }
 }
@@ -79,10 +110,11 @@ impl Datasource for RpcBlockCrawler {
                 .unwrap_or(CommitmentConfig::confirmed()),
         ));
         let (block_sender, block_receiver) = mpsc::channel(self.channel_buffer_size);
+        let start_slot = self.resume_slot(&id).await?;
 
         let block_fetcher = block_fetcher(
             rpc_client,
-            self.start_slot,
+            start_slot,
             self.end_slot,
             self.block_interval,
             self.block_config,
@@ -111,7 +143,7 @@ impl Datasource for RpcBlockCrawler {
     }
 
     fn update_types(&self) -> Vec<UpdateType> {
-        vec![UpdateType::Transaction]
+        vec![UpdateType::Transaction, UpdateType::BlockDetails]
     }
 }
 
@@ -225,7 +257,9 @@ fn block_fetcher(
                         }
                     }
                 })
-                .buffer_unordered(max_concurrent_requests)
+                // Blocks are sent in slot order, even if a later block is fetched
+                // first, so that a checkpoint never skips a block still being fetched.
+                .buffered(max_concurrent_requests)
                 .for_each(|result| async {
                     if let Some((slot, block)) = result {
                         if let Err(e) = block_sender.send((slot, block)).await {
@@ -323,12 +357,29 @@ fn task_processor(
                                     .await
                                     .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
 
-                                if let Err(err) = sender.try_send((update, id_for_loop.clone())) {
+                                if let Err(err) = sender.send((update, id_for_loop.clone())).await {
                                     log::error!("Error sending transaction update: {err:?}");
                                     break;
                                 }
                             }
                         }
+
+                        // Block details are sent after the transactions of the block, so
+                        // that they mark the block as complete for block pipes.
+                        let block_details = Update::BlockDetails(BlockDetails {
+                            slot,
+                            block_hash,
+                            previous_block_hash: Hash::from_str(&block.previous_blockhash).ok(),
+                            rewards: block.rewards,
+                            num_reward_partitions: block.num_reward_partitions,
+                            block_time: block.block_time,
+                            block_height: block.block_height,
+                        });
+
+                        if let Err(err) = sender.send((block_details, id_for_loop.clone())).await {
+                            log::error!("Error sending block details: {err:?}");
+                        }
+
                         metrics
                             .record_histogram(
                                 "block_crawler_block_process_time_nanoseconds",
@@ -511,4 +562,43 @@ mod tests {
             "Test should exit because block fetcher completed"
         );
     }
+
+    #[tokio::test]
+    async fn test_resumes_after_committed_checkpoint() {
+        let path = std::env::temp_dir().join(format!(
+            "carbon-block-crawler-checkpoints-{}.json",
+            std::process::id()
+        ));
+        let checkpoint_store = Arc::new(
+            carbon_core::checkpoint::FileCheckpointStore::new(&path)
+                .expect("Failed to open the checkpoint store"),
+        );
+        let id = DatasourceId::new_named("block_crawler");
+
+        let crawler = RpcBlockCrawler::new(
+            "http://localhost:8899".to_string(),
+            100,
+            None,
+            None,
+            RpcBlockConfig::default(),
+            Some(10),
+            None,
+        )
+        .with_checkpoint_store(checkpoint_store.clone());
+        assert_eq!(crawler.resume_slot(&id).await.unwrap(), 100);
+
+        checkpoint_store
+            .commit(
+                &id,
+                carbon_core::checkpoint::Checkpoint {
+                    slot: 150,
+                    signature: None,
+                },
+            )
+            .await
+            .expect("Failed to commit the checkpoint");
+        assert_eq!(crawler.resume_slot(&id).await.unwrap(), 151);
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
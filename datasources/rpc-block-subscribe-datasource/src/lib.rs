@@ -1,4 +1,6 @@
-use carbon_core::datasource::{BlockDetails, DatasourceDisconnection, DatasourceId};
+use carbon_core::datasource::{
+    BlockDetails, DatasourceDisconnection, DatasourceId, SlotStatus, SlotStatusUpdate,
+};
 use chrono::Utc;
 use solana_hash::Hash;
 use std::str::FromStr;
@@ -9,6 +11,7 @@ use {
         datasource::{Datasource, TransactionUpdate, Update, UpdateType},
         error::CarbonResult,
         metrics::MetricsCollection,
+        pipeline::PipelinePrograms,
         transformers::transaction_metadata_from_original_meta,
     },
     core::time::Duration,
@@ -43,6 +46,32 @@ impl Filters {
             block_subscribe_config,
         }
     }
+
+    /// Creates filters that subscribe to the blocks containing transactions
+    /// of the programs of a pipeline, as returned by
+    /// `PipelineBuilder::programs`.
+    ///
+    /// # Notes
+    ///
+    /// - Block subscriptions can only be filtered by a single program, so the
+    ///   filter is `MentionsAccountOrProgram` when the pipeline processes the
+    ///   transactions of exactly one program, and `All` otherwise.
+    pub fn for_programs(
+        programs: &PipelinePrograms,
+        block_subscribe_config: Option<RpcBlockSubscribeConfig>,
+    ) -> Self {
+        let block_filter = match &programs.instruction_programs {
+            Some(programs) if programs.len() == 1 => programs
+                .first()
+                .map(|program| {
+                    RpcBlockSubscribeFilter::MentionsAccountOrProgram(program.to_string())
+                })
+                .unwrap_or(RpcBlockSubscribeFilter::All),
+            _ => RpcBlockSubscribeFilter::All,
+        };
+
+        Self::new(block_filter, block_subscribe_config)
+    }
 }
 
 /// Default timeout for detecting stale connections (30 seconds)
@@ -54,6 +83,9 @@ pub struct RpcBlockSubscribe {
     pub disconnect_notifier: Option<mpsc::Sender<DatasourceDisconnection>>,
     /// Timeout for detecting hung/stale connections. Default: 30 seconds.
     pub stream_timeout: Duration,
+    /// Whether to emit an `Update::SlotStatus` for every received block,
+    /// reporting the commitment level of the subscription. Default: false.
+    pub slot_status_updates: bool,
 }
 
 impl RpcBlockSubscribe {
@@ -65,6 +97,7 @@ impl RpcBlockSubscribe {
             filters,
             disconnect_notifier: None,
             stream_timeout: Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS),
+            slot_status_updates: false,
         }
     }
 
@@ -80,6 +113,7 @@ impl RpcBlockSubscribe {
             filters,
             disconnect_notifier: Some(disconnect_notifier),
             stream_timeout: Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS),
+            slot_status_updates: false,
         }
     }
 
@@ -88,6 +122,29 @@ impl RpcBlockSubscribe {
         self.stream_timeout = timeout;
         self
     }
+
+    /// Emits an `Update::SlotStatus` after the updates of every block.
+    ///
+    /// Blocks are only delivered once they reach the subscription's commitment
+    /// level (finalized unless configured otherwise), so the emitted status
+    /// is that commitment level.
+    pub fn with_slot_status_updates(mut self) -> Self {
+        self.slot_status_updates = true;
+        self
+    }
+
+    fn block_slot_status(&self) -> SlotStatus {
+        match self
+            .filters
+            .block_subscribe_config
+            .as_ref()
+            .and_then(|config| config.commitment)
+        {
+            Some(commitment) if commitment.is_confirmed() => SlotStatus::Confirmed,
+            Some(commitment) if !commitment.is_finalized() => SlotStatus::Processed,
+            _ => SlotStatus::Finalized,
+        }
+    }
 }
 
 #[async_trait]
@@ -104,6 +161,7 @@ impl Datasource for RpcBlockSubscribe {
         let mut last_disconnect_time = None;
         let mut last_slot_before_disconnect = None;
         let disconnect_tx_clone = self.disconnect_notifier.clone();
+        let block_slot_status = self.block_slot_status();
 
         loop {
             if cancellation_token.is_cancelled() {
@@ -220,21 +278,6 @@ impl Datasource for RpcBlockSubscribe {
                                     let block_hash = Hash::from_str(&block.blockhash).ok();
                                     let previous_block_hash = Hash::from_str(&block.previous_blockhash).ok();
 
-                                    let block_deteils = Update::BlockDetails( BlockDetails {
-                                                slot,
-                                                block_hash,
-                                                previous_block_hash,
-                                                rewards: block.rewards,
-                                                num_reward_partitions: block.num_reward_partitions,
-                                                block_time: block.block_time,
-                                                block_height: block.block_height,
-                                    });
-
-                                    if let Err(err) = sender_clone.try_send((block_deteils, id_for_loop.clone())) {
-                                        log::error!("Error sending block details: {err:?}");
-                                        break;
-                                    }
-
                                     if let Some(transactions) = block.transactions {
                                         for (tx_index, encoded_transaction_with_status_meta) in transactions.into_iter().enumerate() {
                                             let start_time = std::time::Instant::now();
@@ -282,13 +325,44 @@ impl Datasource for RpcBlockSubscribe {
                                                 .await
                                                 .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
 
-                                            if let Err(err) = sender_clone.try_send((update, id_for_loop.clone())) {
+                                            if let Err(err) = sender_clone.send((update, id_for_loop.clone())).await {
                                                 log::error!("Error sending transaction update: {err:?}");
                                                 break;
                                             }
                                         }
                                     }
 
+                                    // Block details are sent after the transactions of the block, so
+                                    // that they mark the block as complete for block pipes.
+                                    let block_details = Update::BlockDetails(BlockDetails {
+                                        slot,
+                                        block_hash,
+                                        previous_block_hash,
+                                        rewards: block.rewards,
+                                        num_reward_partitions: block.num_reward_partitions,
+                                        block_time: block.block_time,
+                                        block_height: block.block_height,
+                                    });
+
+                                    if let Err(err) = sender_clone.send((block_details, id_for_loop.clone())).await {
+                                        log::error!("Error sending block details: {err:?}");
+                                        break;
+                                    }
+
+                                    if self.slot_status_updates {
+                                        let slot_status = Update::SlotStatus(SlotStatusUpdate {
+                                            slot,
+                                            parent: Some(block.parent_slot),
+                                            status: block_slot_status,
+                                            dead_error: None,
+                                        });
+
+                                        if let Err(err) = sender_clone.send((slot_status, id_for_loop.clone())).await {
+                                            log::error!("Error sending slot status update: {err:?}");
+                                            break;
+                                        }
+                                    }
+
                                     metrics
                                         .record_histogram(
                                             "block_subscribe_block_process_time_nanoseconds",
@@ -318,6 +392,10 @@ impl Datasource for RpcBlockSubscribe {
     }
 
     fn update_types(&self) -> Vec<UpdateType> {
-        vec![UpdateType::Transaction]
+        let mut update_types = vec![UpdateType::Transaction, UpdateType::BlockDetails];
+        if self.slot_status_updates {
+            update_types.push(UpdateType::SlotStatus);
+        }
+        update_types
     }
 }
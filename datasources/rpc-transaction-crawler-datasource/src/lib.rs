@@ -1,8 +1,9 @@
 use {
     async_trait::async_trait,
     carbon_core::{
+        checkpoint::CursorHandle,
         datasource::{Datasource, DatasourceId, TransactionUpdate, Update, UpdateType},
-        error::CarbonResult,
+        error::{CarbonResult, Error},
         metrics::MetricsCollection,
         transformers::transaction_metadata_from_original_meta,
     },
@@ -17,7 +18,12 @@ use {
     solana_transaction_status::{
         EncodedConfirmedTransactionWithStatusMeta, UiLoadedAddresses, UiTransactionEncoding,
     },
-    std::{collections::HashSet, str::FromStr, sync::Arc, time::Duration},
+    std::{
+        collections::HashSet,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
     tokio::{
         sync::mpsc::{self, Receiver, Sender},
         task::JoinHandle,
@@ -134,12 +140,118 @@ impl ConnectionConfig {
     }
 }
 
+/// The progress of the crawler through the signatures of its account.
+///
+/// `getSignaturesForAddress` returns signatures from newest to oldest, so the
+/// crawler walks them in passes: each pass starts at the newest signature, or
+/// at `Filters::before_signature` for the first one, and pages back until the
+/// `until` signature. The newest signature of a pass becomes the `until`
+/// signature of the next one, so that only new transactions are crawled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CrawlCursor {
+    /// The oldest signature crawled in the current pass, which the next
+    /// request starts before. `None` at the start of a pass.
+    before: Option<Signature>,
+    /// The signature the current pass stops at.
+    until: Option<Signature>,
+    /// The newest signature crawled in the current pass.
+    newest: Option<Signature>,
+}
+
+impl CrawlCursor {
+    fn new(filters: &Filters) -> Self {
+        Self {
+            before: filters.before_signature,
+            until: filters.until_signature,
+            newest: None,
+        }
+    }
+
+    /// Records a batch of signatures, given its newest and oldest signature.
+    fn advance(&mut self, newest: Signature, oldest: Signature) {
+        if self.newest.is_none() {
+            self.newest = Some(newest);
+        }
+        self.before = Some(oldest);
+    }
+
+    /// Ends the current pass once no signature is left between `before` and
+    /// `until`, so that the next pass stops at the newest signature of this
+    /// one.
+    fn finish_pass(&mut self) {
+        self.before = None;
+        if let Some(newest) = self.newest.take() {
+            self.until = Some(newest);
+        }
+    }
+
+    /// Encodes the cursor as `before:until:newest`, leaving out the ends
+    /// that are not set.
+    fn encode(&self) -> String {
+        let end = |signature: Option<Signature>| {
+            signature
+                .map(|signature| signature.to_string())
+                .unwrap_or_default()
+        };
+
+        format!(
+            "{}:{}:{}",
+            end(self.before),
+            end(self.until),
+            end(self.newest)
+        )
+    }
+
+    /// Decodes a cursor encoded with `encode`.
+    fn decode(encoded: &str) -> CarbonResult<Self> {
+        let invalid = || Error::Custom(format!("invalid crawl cursor: {encoded}"));
+        let end = |end: Option<&str>| match end.ok_or_else(invalid)? {
+            "" => Ok(None),
+            signature => Signature::from_str(signature)
+                .map(Some)
+                .map_err(|_| invalid()),
+        };
+
+        let mut ends = encoded.split(':');
+        let cursor = Self {
+            before: end(ends.next())?,
+            until: end(ends.next())?,
+            newest: end(ends.next())?,
+        };
+        if ends.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(cursor)
+    }
+}
+
+/// A transaction, or its signature, on its way from the signature fetcher to
+/// the task processor, or the cursor of the crawl after the transactions
+/// before it.
+enum Crawled<T> {
+    Transaction(T),
+    Cursor(CrawlCursor),
+}
+
+/// RpcTransactionCrawler is a datasource that crawls the transactions of an
+/// account through `getSignaturesForAddress` and sends them to the sender.
+///
+/// When the pipeline has a checkpoint store, the crawler commits its cursor,
+/// see `CrawlCursor`, through its `CursorHandle` once the transactions before
+/// it have been processed. After a restart it finishes the pass it was in,
+/// crawling the older transactions it had not reached yet, before starting a
+/// new one. The crawler must be registered with a named `DatasourceId` for
+/// its cursor to be found again.
+///
+/// Transactions are sent in the order of their signatures, newest first.
 pub struct RpcTransactionCrawler {
     pub rpc_url: String,
     pub account: Pubkey,
     pub connection_config: ConnectionConfig,
     pub filters: Filters,
     pub commitment: Option<CommitmentConfig>,
+    cursor_handle: Mutex<Option<CursorHandle>>,
 }
 
 impl RpcTransactionCrawler {
@@ -156,6 +268,7 @@ impl RpcTransactionCrawler {
             connection_config,
             filters,
             commitment,
+            cursor_handle: Mutex::new(None),
         }
     }
 }
@@ -175,6 +288,21 @@ impl Datasource for RpcTransactionCrawler {
         ));
         let account = self.account;
         let filters = self.filters.clone();
+
+        let cursor_handle = self
+            .cursor_handle
+            .lock()
+            .ok()
+            .and_then(|cursor_handle| cursor_handle.clone());
+
+        let mut cursor = CrawlCursor::new(&filters);
+        if let Some(cursor_handle) = &cursor_handle {
+            if let Some(stored_cursor) = cursor_handle.load().await? {
+                cursor = CrawlCursor::decode(&stored_cursor)?;
+                log::info!("resuming transaction crawler from {cursor:?}");
+            }
+        }
+
         let sender = sender.clone();
         let commitment = self.commitment;
 
@@ -194,7 +322,7 @@ impl Datasource for RpcTransactionCrawler {
             account,
             self.connection_config.clone(),
             signature_sender,
-            filters.clone(),
+            cursor,
             commitment,
             cancellation_token.clone(),
             metrics.clone(),
@@ -215,6 +343,7 @@ impl Datasource for RpcTransactionCrawler {
             sender,
             id,
             filters,
+            cursor_handle,
             cancellation_token.clone(),
             metrics.clone(),
             self.connection_config.clone(),
@@ -234,6 +363,12 @@ impl Datasource for RpcTransactionCrawler {
     fn update_types(&self) -> Vec<UpdateType> {
         vec![UpdateType::Transaction]
     }
+
+    fn set_cursor_handle(&self, cursor_handle: CursorHandle) {
+        if let Ok(mut current) = self.cursor_handle.lock() {
+            *current = Some(cursor_handle);
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -241,20 +376,17 @@ fn signature_fetcher(
     rpc_client: Arc<RpcClient>,
     account: Pubkey,
     connection_config: ConnectionConfig,
-    signature_sender: Sender<Signature>,
-    filters: Filters,
+    signature_sender: Sender<Crawled<Signature>>,
+    cursor: CrawlCursor,
     commitment: Option<CommitmentConfig>,
     cancellation_token: CancellationToken,
     metrics: Arc<MetricsCollection>,
 ) -> JoinHandle<()> {
     let rpc_client = Arc::clone(&rpc_client);
-    let filters = filters.clone();
     let signature_sender = signature_sender.clone();
 
     tokio::spawn(async move {
-        let mut last_fetched_signature = filters.before_signature;
-        let mut until_signature = filters.until_signature;
-        let mut most_recent_signature: Option<Signature> = None;
+        let mut cursor = cursor;
         loop {
             tokio::select! {
                 _ = cancellation_token.cancelled() => {
@@ -269,8 +401,8 @@ fn signature_fetcher(
                         match rpc_client.get_signatures_for_address_with_config(
                             &account,
                             GetConfirmedSignaturesForAddress2Config {
-                                before: last_fetched_signature,
-                                until: until_signature,
+                                before: cursor.before,
+                                until: cursor.until,
                                 limit: Some(connection_config.batch_limit),
                                 commitment: Some(commitment.unwrap_or(CommitmentConfig::confirmed())),
                             }
@@ -280,51 +412,44 @@ fn signature_fetcher(
 
                                 if signatures.is_empty() {
                                     // no more signatures to fetch, so we've gone through
-                                    // all transactions that have been sent up until we started polling for signatures
-                                    // update `last_fetched_signature` to None so we can detect newly sent transactions
-                                    last_fetched_signature = None;
-                                    if most_recent_signature.is_some() {
-                                            // set the `until` signature to the most recent signature
-                                            // this will prevent reindexing old transactions
-                                            until_signature = most_recent_signature;
-                                            // set the most recent signature to None
-                                            // this will prevent reindexing old transactions
-                                            // after we run out of new
-                                            most_recent_signature = None;
-                                    }
-                                     tokio::time::sleep(connection_config.polling_interval).await;
-                                    break;
-                                }
-
-                                // if we have not seen a signature, then update the most recent signature
-                                // on subsequent loop's, this will prevent us from reindexing already seen transactions
-                                if most_recent_signature.is_none() {
-                                    match Signature::from_str(&signatures[0].signature) {
-                                        Ok(sig) => most_recent_signature = Some(sig),
-                                        Err(e) => {
-                                            log::error!("Invalid signature: {e:?}");
+                                    // all transactions that have been sent up until we started polling for signatures.
+                                    // the next pass starts from the newest signature again and stops at the newest
+                                    // signature of this one, so that only new transactions are crawled
+                                    let previous_cursor = cursor;
+                                    cursor.finish_pass();
+                                    if cursor != previous_cursor {
+                                        if let Err(e) = signature_sender.send(Crawled::Cursor(cursor)).await {
+                                            log::error!("Failed to send crawl cursor: {e:?}");
                                         }
                                     }
+                                    tokio::time::sleep(connection_config.polling_interval).await;
+                                    break;
                                 }
 
-                                for sig_info in signatures.iter() {
-                                    let signature = match Signature::from_str(&sig_info.signature) {
-                                        Ok(sig) => sig,
+                                let parsed_signatures: Vec<Signature> = signatures
+                                    .iter()
+                                    .filter_map(|sig_info| match Signature::from_str(&sig_info.signature) {
+                                        Ok(sig) => Some(sig),
                                         Err(e) => {
                                             log::error!("Invalid signature: {e:?}");
-                                            continue;
+                                            None
                                         }
-                                    };
+                                    })
+                                    .collect();
 
-                                    if let Err(e) = signature_sender.send(signature).await {
+                                for signature in parsed_signatures.iter() {
+                                    if let Err(e) = signature_sender.send(Crawled::Transaction(*signature)).await {
                                         log::error!("Failed to send signature: {e:?}");
                                         break;
                                     }
                                 }
 
-                                last_fetched_signature = signatures
-                                    .last()
-                                    .and_then(|s| Signature::from_str(&s.signature).ok());
+                                if let (Some(newest), Some(oldest)) = (parsed_signatures.first(), parsed_signatures.last()) {
+                                    cursor.advance(*newest, *oldest);
+                                    if let Err(e) = signature_sender.send(Crawled::Cursor(cursor)).await {
+                                        log::error!("Failed to send crawl cursor: {e:?}");
+                                    }
+                                }
 
                                 let time_taken = start.elapsed().as_millis();
 
@@ -365,8 +490,8 @@ fn signature_fetcher(
 
 fn transaction_fetcher(
     rpc_client: Arc<RpcClient>,
-    signature_receiver: Receiver<Signature>,
-    transaction_sender: Sender<(Signature, EncodedConfirmedTransactionWithStatusMeta)>,
+    signature_receiver: Receiver<Crawled<Signature>>,
+    transaction_sender: Sender<Crawled<(Signature, EncodedConfirmedTransactionWithStatusMeta)>>,
     connection_config: ConnectionConfig,
     commitment: Option<CommitmentConfig>,
     cancellation_token: CancellationToken,
@@ -379,17 +504,21 @@ fn transaction_fetcher(
     tokio::spawn(async move {
         let fetch_stream_task = async {
             let fetch_stream = async_stream::stream! {
-                while let Some(signature) = signature_receiver.recv().await {
-                    yield signature;
+                while let Some(item) = signature_receiver.recv().await {
+                    yield item;
                 }
             };
 
             fetch_stream
-                .map(|signature| {
+                .map(|item| {
                     let metrics = metrics.clone();
                     let connection_config = connection_config.clone();
                     let rpc_client = Arc::clone(&rpc_client);
                     async move {
+                        let signature = match item {
+                            Crawled::Transaction(signature) => signature,
+                            Crawled::Cursor(cursor) => return Some(Crawled::Cursor(cursor)),
+                        };
                         let start = Instant::now();
                         let mut retries = 0;
                         let mut backoff = connection_config.retry_config.initial_backoff_ms;
@@ -416,7 +545,7 @@ fn transaction_fetcher(
                                         .await
                                         .expect("Error recording metric");
 
-                                    return Some((signature, tx));
+                                    return Some(Crawled::Transaction((signature, tx)));
                                 }
                                 Err(e) => {
                                     if retries >= connection_config.retry_config.max_retries {
@@ -442,18 +571,20 @@ fn transaction_fetcher(
                         }
                     }
                 })
-                .buffer_unordered(connection_config.max_concurrent_requests)
+                // Keeps the transactions in the order of their signatures, so
+                // that a cursor is only committed after the transactions
+                // before it.
+                .buffered(connection_config.max_concurrent_requests)
                 .for_each(|result| async {
-                    metrics
-                        .increment_counter("transaction_crawler_transactions_fetched", 1)
-                        .await
-                        .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
-
-                    if let Some((signature, fetched_transaction)) = result {
-                        if let Err(e) = transaction_sender
-                            .send((signature, fetched_transaction))
+                    if !matches!(result, Some(Crawled::Cursor(_))) {
+                        metrics
+                            .increment_counter("transaction_crawler_transactions_fetched", 1)
                             .await
-                        {
+                            .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
+                    }
+
+                    if let Some(item) = result {
+                        if let Err(e) = transaction_sender.send(item).await {
                             log::error!("Failed to send transaction: {e:?}");
                         }
                     }
@@ -470,11 +601,13 @@ fn transaction_fetcher(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn task_processor(
-    transaction_receiver: Receiver<(Signature, EncodedConfirmedTransactionWithStatusMeta)>,
+    transaction_receiver: Receiver<Crawled<(Signature, EncodedConfirmedTransactionWithStatusMeta)>>,
     sender: Sender<(Update, DatasourceId)>,
     id: DatasourceId,
     filters: Filters,
+    cursor_handle: Option<CursorHandle>,
     cancellation_token: CancellationToken,
     metrics: Arc<MetricsCollection>,
     connection_config: ConnectionConfig,
@@ -490,7 +623,16 @@ fn task_processor(
                     log::info!("Cancelling RPC Crawler task processor...");
                     break;
                 }
-                Some((signature, fetched_transaction)) = transaction_receiver.recv() => {
+                Some(item) = transaction_receiver.recv() => {
+                    let (signature, fetched_transaction) = match item {
+                        Crawled::Transaction(fetched) => fetched,
+                        Crawled::Cursor(cursor) => {
+                            if let Some(cursor_handle) = &cursor_handle {
+                                cursor_handle.commit(cursor.encode());
+                            }
+                            continue;
+                        }
+                    };
                     let start = Instant::now();
                     let transaction = fetched_transaction.transaction;
 
@@ -587,8 +729,101 @@ fn task_processor(
                             continue;
                         }
                     }
+
+                    if let Some(cursor_handle) = &cursor_handle {
+                        cursor_handle.sent();
+                    }
                 }
             }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pages through `history`, ordered newest first, the way
+    /// `getSignaturesForAddress` does.
+    fn page(history: &[Signature], cursor: &CrawlCursor, limit: usize) -> Vec<Signature> {
+        let start = cursor
+            .before
+            .map(|before| history.iter().position(|s| *s == before).unwrap() + 1)
+            .unwrap_or(0);
+        history[start..]
+            .iter()
+            .take_while(|s| Some(**s) != cursor.until)
+            .take(limit)
+            .copied()
+            .collect()
+    }
+
+    /// Crawls up to `batches` batches, committing the encoded cursor after
+    /// each one and at the end of a pass, and returns the crawled signatures.
+    fn crawl(
+        history: &[Signature],
+        cursor: &mut CrawlCursor,
+        batches: usize,
+        committed: &mut String,
+    ) -> Vec<Signature> {
+        let mut crawled = Vec::new();
+        for _ in 0..batches {
+            let signatures = page(history, cursor, 3);
+            if signatures.is_empty() {
+                cursor.finish_pass();
+                *committed = cursor.encode();
+                break;
+            }
+            crawled.extend(signatures.iter().copied());
+            cursor.advance(signatures[0], *signatures.last().unwrap());
+            *committed = cursor.encode();
+        }
+        crawled
+    }
+
+    #[test]
+    fn test_resumes_crawl_after_restart() {
+        let mut committed = String::new();
+        let mut history: Vec<Signature> = (0..10).map(|_| Signature::new_unique()).collect();
+
+        // Stop partway through the first pass.
+        let mut cursor = CrawlCursor::new(&Filters::new(None, None, None));
+        let mut crawled = crawl(&history, &mut cursor, 2, &mut committed);
+        assert_eq!(crawled.len(), 6);
+
+        // After a restart, the older history is crawled before the pass ends.
+        let mut cursor = CrawlCursor::decode(&committed).unwrap();
+        crawled.extend(crawl(&history, &mut cursor, 10, &mut committed));
+        assert_eq!(crawled, history);
+        assert_eq!(cursor.until, Some(history[0]));
+
+        // The next pass, after another restart, only crawls new signatures.
+        let new_signatures: Vec<Signature> = (0..2).map(|_| Signature::new_unique()).collect();
+        history.splice(0..0, new_signatures.iter().copied());
+        let mut cursor = CrawlCursor::decode(&committed).unwrap();
+        assert_eq!(
+            crawl(&history, &mut cursor, 10, &mut committed),
+            new_signatures
+        );
+        assert_eq!(cursor.until, Some(new_signatures[0]));
+    }
+
+    #[test]
+    fn test_decodes_encoded_cursor() {
+        let cursor = CrawlCursor {
+            before: Some(Signature::new_unique()),
+            until: None,
+            newest: Some(Signature::new_unique()),
+        };
+        assert_eq!(CrawlCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert_eq!(
+            CrawlCursor::decode(&CrawlCursor::default().encode()).unwrap(),
+            CrawlCursor::default()
+        );
+
+        assert!(CrawlCursor::decode("").is_err());
+        assert!(CrawlCursor::decode("::").is_ok());
+        assert!(CrawlCursor::decode(":::").is_err());
+        assert!(CrawlCursor::decode("invalid::").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Helpers shared by the datasources built on the Yellowstone gRPC Geyser
+//! protocol, such as this crate's `YellowstoneGrpcGeyserClient` and the Helius
+//! LaserStream datasource.
+
+use {
+    carbon_core::{
+        datasource::{DatasourceId, SlotStatus, SlotStatusUpdate, Update},
+        metrics::MetricsCollection,
+        pipeline::PipelinePrograms,
+    },
+    solana_pubkey::Pubkey,
+    std::collections::{BTreeSet, HashMap},
+    tokio::sync::mpsc::Sender,
+    yellowstone_grpc_proto::geyser::{
+        SlotStatus as GeyserSlotStatus, SubscribeRequestFilterAccounts,
+        SubscribeRequestFilterTransactions, SubscribeUpdateSlot,
+    },
+};
+
+/// The name of the subscription filters built from the programs of a
+/// pipeline.
+pub const PIPELINE_PROGRAMS_FILTER: &str = "carbon-pipeline-programs";
+
+/// Converts a Geyser slot update into an `Update::SlotStatus` and sends it.
+///
+/// Intermediate statuses such as `SlotFirstShredReceived` are ignored.
+///
+/// # Parameters
+///
+/// - `slot_update`: The slot update received from the Geyser stream.
+/// - `metrics`: The metrics collection to record the update in.
+/// - `metric`: The name of the counter incremented for every update sent.
+/// - `sender`: The channel of the pipeline.
+/// - `id`: The ID of the datasource sending the update.
+pub async fn send_subscribe_update_slot(
+    slot_update: SubscribeUpdateSlot,
+    metrics: &MetricsCollection,
+    metric: &str,
+    sender: &Sender<(Update, DatasourceId)>,
+    id: DatasourceId,
+) {
+    let status = match GeyserSlotStatus::try_from(slot_update.status) {
+        Ok(GeyserSlotStatus::SlotProcessed) => SlotStatus::Processed,
+        Ok(GeyserSlotStatus::SlotConfirmed) => SlotStatus::Confirmed,
+        Ok(GeyserSlotStatus::SlotFinalized) => SlotStatus::Finalized,
+        Ok(GeyserSlotStatus::SlotDead) => SlotStatus::Dead,
+        _ => return,
+    };
+
+    let update = Update::SlotStatus(SlotStatusUpdate {
+        slot: slot_update.slot,
+        parent: slot_update.parent,
+        status,
+        dead_error: slot_update.dead_error,
+    });
+
+    // Slot status updates are sent without `try_send`, since a lost status would
+    // keep the slot's updates buffered until they are evicted.
+    if let Err(e) = sender.send((update, id)).await {
+        log::error!(
+            "Failed to send slot status update for slot {}: {e:?}",
+            slot_update.slot
+        );
+    }
+
+    metrics
+        .increment_counter(metric, 1)
+        .await
+        .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
+}
+
+/// Builds the account filters that subscribe to the accounts owned by the
+/// programs of a pipeline, as returned by `PipelineBuilder::programs`.
+///
+/// # Notes
+///
+/// - If the account pipes accept accounts of any owner, the filter matches
+///   every account.
+/// - If the pipeline has no account pipes, no filter is returned, so no
+///   accounts are streamed.
+pub fn account_filters_for_programs(
+    programs: &PipelinePrograms,
+) -> HashMap<String, SubscribeRequestFilterAccounts> {
+    let Some(owners) = program_strings(&programs.account_owners) else {
+        return HashMap::new();
+    };
+
+    HashMap::from([(
+        PIPELINE_PROGRAMS_FILTER.to_string(),
+        SubscribeRequestFilterAccounts {
+            account: vec![],
+            owner: owners,
+            filters: vec![],
+            nonempty_txn_signature: None,
+        },
+    )])
+}
+
+/// Builds the transaction filters that subscribe to the transactions
+/// involving the programs of a pipeline, as returned by
+/// `PipelineBuilder::programs`.
+///
+/// # Notes
+///
+/// - Vote transactions are only streamed if a pipe needs them, see
+///   `PipelinePrograms::include_votes`.
+/// - If the instruction and transaction pipes accept transactions of any
+///   program, the filter matches every transaction.
+/// - If the pipeline has no instruction or transaction pipes, no filter is
+///   returned, so no transactions are streamed.
+pub fn transaction_filters_for_programs(
+    programs: &PipelinePrograms,
+) -> HashMap<String, SubscribeRequestFilterTransactions> {
+    let Some(account_include) = program_strings(&programs.instruction_programs) else {
+        return HashMap::new();
+    };
+
+    HashMap::from([(
+        PIPELINE_PROGRAMS_FILTER.to_string(),
+        SubscribeRequestFilterTransactions {
+            vote: if programs.include_votes {
+                None
+            } else {
+                Some(false)
+            },
+            failed: None,
+            signature: None,
+            account_include,
+            account_exclude: vec![],
+            account_required: vec![],
+        },
+    )])
+}
+
+/// Returns the programs of a `PipelinePrograms` set as filter strings, where
+/// an empty list matches any program, or `None` if no program is needed.
+fn program_strings(programs: &Option<BTreeSet<Pubkey>>) -> Option<Vec<String>> {
+    match programs {
+        None => Some(vec![]),
+        Some(programs) if programs.is_empty() => None,
+        Some(programs) => Some(programs.iter().map(ToString::to_string).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn programs(
+        instruction_programs: Option<BTreeSet<Pubkey>>,
+        account_owners: Option<BTreeSet<Pubkey>>,
+        include_votes: bool,
+    ) -> PipelinePrograms {
+        PipelinePrograms {
+            instruction_programs,
+            account_owners,
+            include_votes,
+        }
+    }
+
+    #[test]
+    fn test_account_filters_for_programs() {
+        let program = Pubkey::new_unique();
+
+        let filters = account_filters_for_programs(&programs(None, Some(BTreeSet::new()), false));
+        assert!(filters.is_empty());
+
+        let filters = account_filters_for_programs(&programs(None, None, false));
+        assert!(filters[PIPELINE_PROGRAMS_FILTER].owner.is_empty());
+
+        let filters =
+            account_filters_for_programs(&programs(None, Some(BTreeSet::from([program])), false));
+        assert_eq!(
+            filters[PIPELINE_PROGRAMS_FILTER].owner,
+            vec![program.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_transaction_filters_for_programs() {
+        let program = Pubkey::new_unique();
+
+        let filters =
+            transaction_filters_for_programs(&programs(Some(BTreeSet::new()), None, false));
+        assert!(filters.is_empty());
+
+        let filters = transaction_filters_for_programs(&programs(None, None, false));
+        assert!(filters[PIPELINE_PROGRAMS_FILTER].account_include.is_empty());
+        assert_eq!(filters[PIPELINE_PROGRAMS_FILTER].vote, Some(false));
+
+        let filters = transaction_filters_for_programs(&programs(
+            Some(BTreeSet::from([program])),
+            None,
+            true,
+        ));
+        assert_eq!(
+            filters[PIPELINE_PROGRAMS_FILTER].account_include,
+            vec![program.to_string()]
+        );
+        assert_eq!(filters[PIPELINE_PROGRAMS_FILTER].vote, None);
+    }
+}
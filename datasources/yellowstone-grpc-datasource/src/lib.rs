@@ -1,9 +1,15 @@
+pub mod geyser;
+
+pub use crate::geyser::{account_filters_for_programs, transaction_filters_for_programs};
+
 use {
+    crate::geyser::send_subscribe_update_slot,
     async_trait::async_trait,
     carbon_core::{
+        checkpoint::CheckpointStore,
         datasource::{
-            AccountDeletion, AccountUpdate, Datasource, DatasourceDisconnection, DatasourceId,
-            TransactionUpdate, Update, UpdateType,
+            AccountDeletion, AccountUpdate, BlockDetails, Datasource, DatasourceDisconnection,
+            DatasourceId, TransactionUpdate, Update, UpdateType,
         },
         error::CarbonResult,
         metrics::MetricsCollection,
@@ -11,11 +17,13 @@ use {
     chrono::{DateTime, Utc},
     futures::{sink::SinkExt, StreamExt},
     solana_account::Account,
+    solana_hash::Hash,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
     std::{
         collections::{HashMap, HashSet},
         convert::TryFrom,
+        str::FromStr,
         sync::Arc,
         time::Duration,
     },
@@ -27,8 +35,8 @@ use {
         geyser::{
             subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
             SubscribeRequestFilterAccounts, SubscribeRequestFilterBlocks,
-            SubscribeRequestFilterTransactions, SubscribeRequestPing, SubscribeUpdateAccountInfo,
-            SubscribeUpdateTransactionInfo,
+            SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+            SubscribeUpdateAccountInfo, SubscribeUpdateTransactionInfo,
         },
         tonic::{codec::CompressionEncoding, transport::ClientTlsConfig},
     },
@@ -37,6 +45,8 @@ use {
 /// Default timeout for detecting stale connections (30 seconds)
 pub const DEFAULT_STREAM_TIMEOUT_SECS: u64 = 30;
 
+const SLOT_STATUS_FILTER: &str = "carbon-slot-status";
+
 #[derive(Debug)]
 pub struct YellowstoneGrpcGeyserClient {
     pub endpoint: String,
@@ -50,6 +60,12 @@ pub struct YellowstoneGrpcGeyserClient {
     pub disconnect_notifier: Option<mpsc::Sender<DatasourceDisconnection>>,
     /// Timeout for detecting hung/stale connections. Default: 30 seconds.
     pub stream_timeout: Duration,
+    /// Store to resume from. When set, the first subscription replays from the
+    /// slot after the last committed checkpoint via `from_slot`.
+    pub checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    /// Whether to subscribe to slot status changes and emit them as
+    /// `Update::SlotStatus`. Default: false.
+    pub slot_status_updates: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -109,8 +125,24 @@ impl YellowstoneGrpcGeyserClient {
             disconnect_notifier,
             stream_timeout: stream_timeout
                 .unwrap_or(Duration::from_secs(DEFAULT_STREAM_TIMEOUT_SECS)),
+            checkpoint_store: None,
+            slot_status_updates: false,
         }
     }
+
+    /// Resumes the subscription from the checkpoint stored for this datasource,
+    /// if any. Requires a Yellowstone endpoint that supports `from_slot`.
+    pub fn with_checkpoint_store(mut self, checkpoint_store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
+    /// Subscribes to slot status changes and emits them as `Update::SlotStatus`,
+    /// e.g. to drive the pipeline's commitment buffer.
+    pub fn with_slot_status_updates(mut self) -> Self {
+        self.slot_status_updates = true;
+        self
+    }
 }
 
 impl YellowstoneGrpcClientConfig {
@@ -198,9 +230,28 @@ impl Datasource for YellowstoneGrpcGeyserClient {
         let disconnect_tx_clone = self.disconnect_notifier.clone();
         let stream_timeout = self.stream_timeout;
 
+        let mut slots = HashMap::new();
+        if self.slot_status_updates {
+            slots.insert(
+                SLOT_STATUS_FILTER.to_string(),
+                SubscribeRequestFilterSlots {
+                    filter_by_commitment: Some(false),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let from_slot = match &self.checkpoint_store {
+            Some(checkpoint_store) => checkpoint_store.load(&id).await?.map(|checkpoint| {
+                log::info!("resuming Yellowstone gRPC subscription from checkpoint {checkpoint:?}");
+                checkpoint.slot + 1
+            }),
+            None => None,
+        };
+
         tokio::spawn(async move {
-            let subscribe_request = SubscribeRequest {
-                slots: HashMap::new(),
+            let mut subscribe_request = SubscribeRequest {
+                slots,
                 accounts: account_filters,
                 transactions: transaction_filters,
                 transactions_status: HashMap::new(),
@@ -210,7 +261,7 @@ impl Datasource for YellowstoneGrpcGeyserClient {
                 commitment: commitment.map(|x| x as i32),
                 accounts_data_slice: vec![],
                 ping: None,
-                from_slot: None,
+                from_slot,
             };
 
             let id_for_loop = id.clone();
@@ -228,6 +279,8 @@ impl Datasource for YellowstoneGrpcGeyserClient {
                     result = geyser_client.subscribe_with_request(Some(subscribe_request.clone())) => {
                         match result {
                             Ok((mut subscribe_tx, mut stream)) => {
+                                // Only the first subscription resumes from the checkpoint.
+                                subscribe_request.from_slot = None;
                                 let mut first_message_after_reconnect = last_disconnect_time.is_some();
 
                                 loop {
@@ -336,6 +389,26 @@ impl Datasource for YellowstoneGrpcGeyserClient {
                                                     )
                                                     .await;
                                                 }
+
+                                                send_subscribe_update_block_details(
+                                                    BlockDetails {
+                                                        slot: block_update.slot,
+                                                        block_hash: Hash::from_str(&block_update.blockhash).ok(),
+                                                        previous_block_hash: Hash::from_str(&block_update.parent_blockhash).ok(),
+                                                        rewards: None,
+                                                        num_reward_partitions: None,
+                                                        block_time,
+                                                        block_height: block_update.block_height.map(|height| height.block_height),
+                                                    },
+                                                    &metrics,
+                                                    &sender,
+                                                    id_for_loop.clone(),
+                                                )
+                                                .await
+                                            }
+
+                                            Some(UpdateOneof::Slot(slot_update)) => {
+                                                send_subscribe_update_slot(slot_update, &metrics, "yellowstone_grpc_slot_status_updates_received", &sender, id_for_loop.clone()).await
                                             }
 
                                             Some(UpdateOneof::Ping(_)) => {
@@ -389,14 +462,39 @@ impl Datasource for YellowstoneGrpcGeyserClient {
     }
 
     fn update_types(&self) -> Vec<UpdateType> {
-        vec![
+        let mut update_types = vec![
             UpdateType::AccountUpdate,
             UpdateType::Transaction,
             UpdateType::AccountDeletion,
-        ]
+            UpdateType::BlockDetails,
+        ];
+        if self.slot_status_updates {
+            update_types.push(UpdateType::SlotStatus);
+        }
+        update_types
     }
 }
 
+async fn send_subscribe_update_block_details(
+    block_details: BlockDetails,
+    metrics: &MetricsCollection,
+    sender: &Sender<(Update, DatasourceId)>,
+    id: DatasourceId,
+) {
+    let slot = block_details.slot;
+
+    // Block details are sent after the transactions of the block, and without
+    // `try_send`, since they mark the block as complete for block pipes.
+    if let Err(e) = sender.send((Update::BlockDetails(block_details), id)).await {
+        log::error!("Failed to send block details for slot {slot}: {e:?}");
+    }
+
+    metrics
+        .increment_counter("yellowstone_grpc_block_details_received", 1)
+        .await
+        .unwrap_or_else(|value| log::error!("Error recording metric: {value}"));
+}
+
 async fn send_subscribe_account_update_info(
     account_update_info: Option<SubscribeUpdateAccountInfo>,
     metrics: &MetricsCollection,
@@ -517,7 +615,9 @@ async fn send_subscribe_update_transaction_info(
             block_time,
             block_hash: None,
         }));
-        if let Err(e) = sender.try_send((update, id)) {
+        // Sent without `try_send`, so that no transaction is missing from the
+        // block its block details complete.
+        if let Err(e) = sender.send((update, id)).await {
             log::error!(
                 "Failed to send transaction update with signature {signature:?} at slot {slot}: {e:?}"
             );
@@ -18,6 +18,10 @@ pub enum AddressLookupTableAccount {
 impl AccountDecoder<'_> for AddressLookupTableDecoder {
     type AccountType = AddressLookupTableAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
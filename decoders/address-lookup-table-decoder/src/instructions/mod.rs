@@ -27,6 +27,10 @@ pub enum AddressLookupTableInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for AddressLookupTableDecoder {
     type InstructionType = AddressLookupTableInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -5,6 +5,11 @@ pub enum SplAssociatedTokenAccountAccount {}
 
 impl AccountDecoder<'_> for SplAssociatedTokenAccountDecoder {
     type AccountType = SplAssociatedTokenAccountAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         _account: &solana_account::Account,
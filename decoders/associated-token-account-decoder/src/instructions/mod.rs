@@ -24,6 +24,10 @@ pub enum SplAssociatedTokenAccountInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SplAssociatedTokenAccountDecoder {
     type InstructionType = SplAssociatedTokenAccountInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -39,6 +39,10 @@ pub enum BonkswapAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for BonkswapDecoder {
     type AccountType = BonkswapAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -49,60 +53,29 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for BonkswapDecoder {
 
         let data = account.data.as_slice();
 
-        // Try to decode Farm
-        {
-            if let Some(decoded) = farm::Farm::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: BonkswapAccount::Farm(decoded),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        // Try to decode PoolV2
-        {
-            if let Some(decoded) = pool_v2::PoolV2::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: BonkswapAccount::PoolV2(decoded),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        // Try to decode Pool
-        {
-            if let Some(decoded) = pool::Pool::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: BonkswapAccount::Pool(decoded),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        // Try to decode Provider
-        {
-            if let Some(decoded) = provider::Provider::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: BonkswapAccount::Provider(decoded),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        // Try to decode State
-        {
-            if let Some(decoded) = state::State::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[161, 156, 211, 253, 250, 64, 53, 250],
+                &[91, 12, 214, 87, 7, 185, 167, 55],
+                &[241, 154, 109, 4, 17, 177, 109, 188],
+                &[164, 180, 71, 17, 75, 216, 80, 195],
+                &[216, 146, 107, 94, 104, 75, 182, 177],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => farm::Farm::decode(data).map(BonkswapAccount::Farm),
+                1 => pool_v2::PoolV2::decode(data).map(BonkswapAccount::PoolV2),
+                2 => pool::Pool::decode(data).map(BonkswapAccount::Pool),
+                3 => provider::Provider::decode(data).map(BonkswapAccount::Provider),
+                4 => state::State::decode(data).map(BonkswapAccount::State),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: BonkswapAccount::State(decoded),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
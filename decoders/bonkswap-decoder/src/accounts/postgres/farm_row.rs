@@ -244,6 +244,77 @@ impl carbon_core::postgres::operations::Upsert for FarmRow {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(14)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO farm_account (
+                        "pool",
+                        "tokens",
+                        "token_accounts",
+                        "supply",
+                        "supply_left",
+                        "accumulated_seconds_per_share",
+                        "offset_seconds_per_share",
+                        "start_time",
+                        "end_time",
+                        "last_update",
+                        "bump",
+                        "farm_type",
+                                    __pubkey, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.pool)
+                    .push_bind(row.tokens.clone())
+                    .push_bind(row.token_accounts.clone())
+                    .push_bind(row.supply.clone())
+                    .push_bind(row.supply_left.clone())
+                    .push_bind(row.accumulated_seconds_per_share.clone())
+                    .push_bind(row.offset_seconds_per_share.clone())
+                    .push_bind(row.start_time.clone())
+                    .push_bind(row.end_time.clone())
+                    .push_bind(row.last_update.clone())
+                    .push_bind(row.bump)
+                    .push_bind(row.farm_type.clone())
+                    .push_bind(row.metadata.pubkey)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __pubkey
+                    ) DO UPDATE SET
+                        "pool" = EXCLUDED."pool",
+                        "tokens" = EXCLUDED."tokens",
+                        "token_accounts" = EXCLUDED."token_accounts",
+                        "supply" = EXCLUDED."supply",
+                        "supply_left" = EXCLUDED."supply_left",
+                        "accumulated_seconds_per_share" = EXCLUDED."accumulated_seconds_per_share",
+                        "offset_seconds_per_share" = EXCLUDED."offset_seconds_per_share",
+                        "start_time" = EXCLUDED."start_time",
+                        "end_time" = EXCLUDED."end_time",
+                        "last_update" = EXCLUDED."last_update",
+                        "bump" = EXCLUDED."bump",
+                        "farm_type" = EXCLUDED."farm_type",
+                                    __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -125,4 +125,57 @@ impl carbon_core::postgres::operations::Upsert for BonkswapAccountWithMetadata {
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut farm_rows = Vec::new();
+        let mut pool_v2_rows = Vec::new();
+        let mut pool_rows = Vec::new();
+        let mut provider_rows = Vec::new();
+        let mut state_rows = Vec::new();
+
+        for BonkswapAccountWithMetadata(account, metadata) in rows {
+            match account {
+                BonkswapAccount::Farm(account) => {
+                    farm_rows.push(farm_row::FarmRow::from_parts(
+                        account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapAccount::PoolV2(account) => {
+                    pool_v2_rows.push(pool_v2_row::PoolV2Row::from_parts(
+                        account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapAccount::Pool(account) => {
+                    pool_rows.push(pool_row::PoolRow::from_parts(
+                        account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapAccount::Provider(account) => {
+                    provider_rows.push(provider_row::ProviderRow::from_parts(
+                        account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapAccount::State(account) => {
+                    state_rows.push(state_row::StateRow::from_parts(
+                        account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        farm_row::FarmRow::upsert_many(&farm_rows, pool).await?;
+        pool_v2_row::PoolV2Row::upsert_many(&pool_v2_rows, pool).await?;
+        pool_row::PoolRow::upsert_many(&pool_rows, pool).await?;
+        provider_row::ProviderRow::upsert_many(&provider_rows, pool).await?;
+        state_row::StateRow::upsert_many(&state_rows, pool).await?;
+        Ok(())
+    }
 }
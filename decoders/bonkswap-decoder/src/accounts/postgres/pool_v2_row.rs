@@ -367,6 +367,128 @@ impl carbon_core::postgres::operations::Upsert for PoolV2Row {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(31)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO pool_v2_account (
+                        "token_x",
+                        "token_y",
+                        "pool_x_account",
+                        "pool_y_account",
+                        "admin",
+                        "project_owner",
+                        "token_x_reserve",
+                        "token_y_reserve",
+                        "self_shares",
+                        "all_shares",
+                        "buyback_amount_x",
+                        "buyback_amount_y",
+                        "project_amount_x",
+                        "project_amount_y",
+                        "mercanti_amount_x",
+                        "mercanti_amount_y",
+                        "lp_accumulator_x",
+                        "lp_accumulator_y",
+                        "const_k",
+                        "price",
+                        "lp_fee",
+                        "buyback_fee",
+                        "project_fee",
+                        "mercanti_fee",
+                        "farm_count",
+                        "pool_bump",
+                        "lp_token",
+                        "lp_token_mint_bump",
+                        "padding",
+                                    __pubkey, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.token_x)
+                    .push_bind(row.token_y)
+                    .push_bind(row.pool_x_account)
+                    .push_bind(row.pool_y_account)
+                    .push_bind(row.admin)
+                    .push_bind(row.project_owner)
+                    .push_bind(row.token_x_reserve.clone())
+                    .push_bind(row.token_y_reserve.clone())
+                    .push_bind(row.self_shares.clone())
+                    .push_bind(row.all_shares.clone())
+                    .push_bind(row.buyback_amount_x.clone())
+                    .push_bind(row.buyback_amount_y.clone())
+                    .push_bind(row.project_amount_x.clone())
+                    .push_bind(row.project_amount_y.clone())
+                    .push_bind(row.mercanti_amount_x.clone())
+                    .push_bind(row.mercanti_amount_y.clone())
+                    .push_bind(row.lp_accumulator_x.clone())
+                    .push_bind(row.lp_accumulator_y.clone())
+                    .push_bind(row.const_k.clone())
+                    .push_bind(row.price.clone())
+                    .push_bind(row.lp_fee.clone())
+                    .push_bind(row.buyback_fee.clone())
+                    .push_bind(row.project_fee.clone())
+                    .push_bind(row.mercanti_fee.clone())
+                    .push_bind(row.farm_count.clone())
+                    .push_bind(row.pool_bump)
+                    .push_bind(row.lp_token)
+                    .push_bind(row.lp_token_mint_bump)
+                    .push_bind(row.padding.clone())
+                    .push_bind(row.metadata.pubkey)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __pubkey
+                    ) DO UPDATE SET
+                        "token_x" = EXCLUDED."token_x",
+                        "token_y" = EXCLUDED."token_y",
+                        "pool_x_account" = EXCLUDED."pool_x_account",
+                        "pool_y_account" = EXCLUDED."pool_y_account",
+                        "admin" = EXCLUDED."admin",
+                        "project_owner" = EXCLUDED."project_owner",
+                        "token_x_reserve" = EXCLUDED."token_x_reserve",
+                        "token_y_reserve" = EXCLUDED."token_y_reserve",
+                        "self_shares" = EXCLUDED."self_shares",
+                        "all_shares" = EXCLUDED."all_shares",
+                        "buyback_amount_x" = EXCLUDED."buyback_amount_x",
+                        "buyback_amount_y" = EXCLUDED."buyback_amount_y",
+                        "project_amount_x" = EXCLUDED."project_amount_x",
+                        "project_amount_y" = EXCLUDED."project_amount_y",
+                        "mercanti_amount_x" = EXCLUDED."mercanti_amount_x",
+                        "mercanti_amount_y" = EXCLUDED."mercanti_amount_y",
+                        "lp_accumulator_x" = EXCLUDED."lp_accumulator_x",
+                        "lp_accumulator_y" = EXCLUDED."lp_accumulator_y",
+                        "const_k" = EXCLUDED."const_k",
+                        "price" = EXCLUDED."price",
+                        "lp_fee" = EXCLUDED."lp_fee",
+                        "buyback_fee" = EXCLUDED."buyback_fee",
+                        "project_fee" = EXCLUDED."project_fee",
+                        "mercanti_fee" = EXCLUDED."mercanti_fee",
+                        "farm_count" = EXCLUDED."farm_count",
+                        "pool_bump" = EXCLUDED."pool_bump",
+                        "lp_token" = EXCLUDED."lp_token",
+                        "lp_token_mint_bump" = EXCLUDED."lp_token_mint_bump",
+                        "padding" = EXCLUDED."padding",
+                                    __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
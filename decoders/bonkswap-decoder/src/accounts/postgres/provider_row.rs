@@ -195,6 +195,77 @@ impl carbon_core::postgres::operations::Upsert for ProviderRow {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(14)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO provider_account (
+                        "token_x",
+                        "token_y",
+                        "owner",
+                        "shares",
+                        "last_fee_accumulator_x",
+                        "last_fee_accumulator_y",
+                        "last_seconds_per_share",
+                        "last_withdraw_time",
+                        "tokens_owed_x",
+                        "tokens_owed_y",
+                        "current_farm_count",
+                        "bump",
+                                    __pubkey, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.token_x)
+                    .push_bind(row.token_y)
+                    .push_bind(row.owner)
+                    .push_bind(row.shares.clone())
+                    .push_bind(row.last_fee_accumulator_x.clone())
+                    .push_bind(row.last_fee_accumulator_y.clone())
+                    .push_bind(row.last_seconds_per_share.clone())
+                    .push_bind(row.last_withdraw_time.clone())
+                    .push_bind(row.tokens_owed_x.clone())
+                    .push_bind(row.tokens_owed_y.clone())
+                    .push_bind(row.current_farm_count.clone())
+                    .push_bind(row.bump)
+                    .push_bind(row.metadata.pubkey)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __pubkey
+                    ) DO UPDATE SET
+                        "token_x" = EXCLUDED."token_x",
+                        "token_y" = EXCLUDED."token_y",
+                        "owner" = EXCLUDED."owner",
+                        "shares" = EXCLUDED."shares",
+                        "last_fee_accumulator_x" = EXCLUDED."last_fee_accumulator_x",
+                        "last_fee_accumulator_y" = EXCLUDED."last_fee_accumulator_y",
+                        "last_seconds_per_share" = EXCLUDED."last_seconds_per_share",
+                        "last_withdraw_time" = EXCLUDED."last_withdraw_time",
+                        "tokens_owed_x" = EXCLUDED."tokens_owed_x",
+                        "tokens_owed_y" = EXCLUDED."tokens_owed_y",
+                        "current_farm_count" = EXCLUDED."current_farm_count",
+                        "bump" = EXCLUDED."bump",
+                                    __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -124,6 +124,53 @@ impl carbon_core::postgres::operations::Upsert for StateRow {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(6)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO state_account (
+                        "admin",
+                        "program_authority",
+                        "bump",
+                        "nonce",
+                                    __pubkey, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.admin)
+                    .push_bind(row.program_authority)
+                    .push_bind(row.bump)
+                    .push_bind(row.nonce)
+                    .push_bind(row.metadata.pubkey)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __pubkey
+                    ) DO UPDATE SET
+                        "admin" = EXCLUDED."admin",
+                        "program_authority" = EXCLUDED."program_authority",
+                        "bump" = EXCLUDED."bump",
+                        "nonce" = EXCLUDED."nonce",
+                                    __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,6 +81,10 @@ pub enum BonkswapInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for BonkswapDecoder {
     type InstructionType = BonkswapInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -91,192 +95,67 @@ impl carbon_core::instruction::InstructionDecoder<'_> for BonkswapDecoder {
 
         let data = instruction.data.as_slice();
 
-        // Try to decode CreatePool
-        {
-            if let Some(decoded) = create_pool::CreatePool::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreatePool(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode CreateProvider
-        {
-            if let Some(decoded) = create_provider::CreateProvider::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreateProvider(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode CreateState
-        {
-            if let Some(decoded) = create_state::CreateState::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreateState(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode AddTokens
-        {
-            if let Some(decoded) = add_tokens::AddTokens::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::AddTokens(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawBuyback
-        {
-            if let Some(decoded) = withdraw_buyback::WithdrawBuyback::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawBuyback(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode Swap
-        {
-            if let Some(decoded) = swap::Swap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::Swap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawShares
-        {
-            if let Some(decoded) = withdraw_shares::WithdrawShares::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawShares(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawLpFee
-        {
-            if let Some(decoded) = withdraw_lp_fee::WithdrawLpFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawLpFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawProjectFee
-        {
-            if let Some(decoded) = withdraw_project_fee::WithdrawProjectFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawProjectFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode CreateFarm
-        {
-            if let Some(decoded) = create_farm::CreateFarm::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreateFarm(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode CreateDualFarm
-        {
-            if let Some(decoded) = create_dual_farm::CreateDualFarm::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreateDualFarm(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode CreateTripleFarm
-        {
-            if let Some(decoded) = create_triple_farm::CreateTripleFarm::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::CreateTripleFarm(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawRewards
-        {
-            if let Some(decoded) = withdraw_rewards::WithdrawRewards::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawRewards(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode ClosePool
-        {
-            if let Some(decoded) = close_pool::ClosePool::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::ClosePool(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode WithdrawMercantiFee
-        {
-            if let Some(decoded) = withdraw_mercanti_fee::WithdrawMercantiFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::WithdrawMercantiFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode AddSupply
-        {
-            if let Some(decoded) = add_supply::AddSupply::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::AddSupply(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode UpdateFees
-        {
-            if let Some(decoded) = update_fees::UpdateFees::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::UpdateFees(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode ResetFarm
-        {
-            if let Some(decoded) = reset_farm::ResetFarm::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: BonkswapInstruction::ResetFarm(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        // Try to decode UpdateRewardTokens
-        {
-            if let Some(decoded) = update_reward_tokens::UpdateRewardTokens::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[233, 146, 209, 142, 207, 104, 64, 188],
+                &[74, 53, 211, 174, 38, 168, 227, 177],
+                &[214, 211, 209, 79, 107, 105, 247, 222],
+                &[28, 218, 30, 209, 175, 155, 153, 240],
+                &[188, 75, 30, 198, 99, 43, 12, 54],
+                &[248, 198, 158, 145, 225, 117, 135, 200],
+                &[176, 104, 154, 105, 250, 80, 68, 244],
+                &[149, 161, 2, 213, 195, 147, 42, 65],
+                &[130, 201, 142, 156, 159, 207, 168, 22],
+                &[74, 59, 128, 160, 87, 174, 153, 194],
+                &[42, 180, 103, 138, 206, 43, 208, 98],
+                &[154, 26, 180, 145, 18, 201, 135, 171],
+                &[10, 214, 219, 139, 205, 22, 251, 21],
+                &[140, 189, 209, 23, 239, 62, 239, 11],
+                &[253, 229, 129, 37, 47, 72, 11, 240],
+                &[80, 102, 70, 57, 235, 88, 239, 8],
+                &[225, 27, 13, 6, 69, 84, 172, 191],
+                &[47, 77, 233, 117, 118, 55, 61, 113],
+                &[249, 236, 71, 74, 104, 58, 225, 28],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => create_pool::CreatePool::decode(data).map(BonkswapInstruction::CreatePool),
+                1 => create_provider::CreateProvider::decode(data)
+                    .map(BonkswapInstruction::CreateProvider),
+                2 => create_state::CreateState::decode(data).map(BonkswapInstruction::CreateState),
+                3 => add_tokens::AddTokens::decode(data).map(BonkswapInstruction::AddTokens),
+                4 => withdraw_buyback::WithdrawBuyback::decode(data)
+                    .map(BonkswapInstruction::WithdrawBuyback),
+                5 => swap::Swap::decode(data).map(BonkswapInstruction::Swap),
+                6 => withdraw_shares::WithdrawShares::decode(data)
+                    .map(BonkswapInstruction::WithdrawShares),
+                7 => withdraw_lp_fee::WithdrawLpFee::decode(data)
+                    .map(BonkswapInstruction::WithdrawLpFee),
+                8 => withdraw_project_fee::WithdrawProjectFee::decode(data)
+                    .map(BonkswapInstruction::WithdrawProjectFee),
+                9 => create_farm::CreateFarm::decode(data).map(BonkswapInstruction::CreateFarm),
+                10 => create_dual_farm::CreateDualFarm::decode(data)
+                    .map(BonkswapInstruction::CreateDualFarm),
+                11 => create_triple_farm::CreateTripleFarm::decode(data)
+                    .map(BonkswapInstruction::CreateTripleFarm),
+                12 => withdraw_rewards::WithdrawRewards::decode(data)
+                    .map(BonkswapInstruction::WithdrawRewards),
+                13 => close_pool::ClosePool::decode(data).map(BonkswapInstruction::ClosePool),
+                14 => withdraw_mercanti_fee::WithdrawMercantiFee::decode(data)
+                    .map(BonkswapInstruction::WithdrawMercantiFee),
+                15 => add_supply::AddSupply::decode(data).map(BonkswapInstruction::AddSupply),
+                16 => update_fees::UpdateFees::decode(data).map(BonkswapInstruction::UpdateFees),
+                17 => reset_farm::ResetFarm::decode(data).map(BonkswapInstruction::ResetFarm),
+                18 => update_reward_tokens::UpdateRewardTokens::decode(data)
+                    .map(BonkswapInstruction::UpdateRewardTokens),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: BonkswapInstruction::UpdateRewardTokens(decoded),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -105,6 +105,55 @@ impl carbon_core::postgres::operations::Upsert for AddTokensRow {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(6)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO add_tokens_instruction (
+                        "delta_x",
+                        "delta_y",
+                                    __signature, __instruction_index, __stack_height, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.delta_x.clone())
+                    .push_bind(row.delta_y.clone())
+                    .push_bind(row.metadata.signature.clone())
+                    .push_bind(row.metadata.instruction_index)
+                    .push_bind(row.metadata.stack_height)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __signature, __instruction_index
+                    ) DO UPDATE SET
+                        "delta_x" = EXCLUDED."delta_x",
+                        "delta_y" = EXCLUDED."delta_y",
+                                    __instruction_index = EXCLUDED.__instruction_index,
+            __stack_height = EXCLUDED.__stack_height,
+            __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
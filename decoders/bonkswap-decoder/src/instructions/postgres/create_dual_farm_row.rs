@@ -129,6 +129,61 @@ impl carbon_core::postgres::operations::Upsert for CreateDualFarmRow {
 
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(8)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO create_dual_farm_instruction (
+                        "supply_marco",
+                        "supply_project_first",
+                        "duration",
+                        "bump",
+                                    __signature, __instruction_index, __stack_height, __slot
+                    ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.supply_marco.clone())
+                    .push_bind(row.supply_project_first.clone())
+                    .push_bind(row.duration.clone())
+                    .push_bind(row.bump)
+                    .push_bind(row.metadata.signature.clone())
+                    .push_bind(row.metadata.instruction_index)
+                    .push_bind(row.metadata.stack_height)
+                    .push_bind(row.metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                        __signature, __instruction_index
+                    ) DO UPDATE SET
+                        "supply_marco" = EXCLUDED."supply_marco",
+                        "supply_project_first" = EXCLUDED."supply_project_first",
+                        "duration" = EXCLUDED."duration",
+                        "bump" = EXCLUDED."bump",
+                                    __instruction_index = EXCLUDED.__instruction_index,
+            __stack_height = EXCLUDED.__stack_height,
+            __slot = EXCLUDED.__slot
+                    "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -406,4 +406,196 @@ impl carbon_core::postgres::operations::Upsert for BonkswapInstructionWithMetada
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut create_pool_rows = Vec::new();
+        let mut create_provider_rows = Vec::new();
+        let mut create_state_rows = Vec::new();
+        let mut add_tokens_rows = Vec::new();
+        let mut withdraw_buyback_rows = Vec::new();
+        let mut swap_rows = Vec::new();
+        let mut withdraw_shares_rows = Vec::new();
+        let mut withdraw_lp_fee_rows = Vec::new();
+        let mut withdraw_project_fee_rows = Vec::new();
+        let mut create_farm_rows = Vec::new();
+        let mut create_dual_farm_rows = Vec::new();
+        let mut create_triple_farm_rows = Vec::new();
+        let mut withdraw_rewards_rows = Vec::new();
+        let mut close_pool_rows = Vec::new();
+        let mut withdraw_mercanti_fee_rows = Vec::new();
+        let mut add_supply_rows = Vec::new();
+        let mut update_fees_rows = Vec::new();
+        let mut reset_farm_rows = Vec::new();
+        let mut update_reward_tokens_rows = Vec::new();
+
+        for BonkswapInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                BonkswapInstruction::CreatePool(instruction) => {
+                    create_pool_rows.push(create_pool_row::CreatePoolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::CreateProvider(instruction) => {
+                    create_provider_rows.push(create_provider_row::CreateProviderRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::CreateState(instruction) => {
+                    create_state_rows.push(create_state_row::CreateStateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::AddTokens(instruction) => {
+                    add_tokens_rows.push(add_tokens_row::AddTokensRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::WithdrawBuyback(instruction) => {
+                    withdraw_buyback_rows.push(
+                        withdraw_buyback_row::WithdrawBuybackRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::Swap(instruction) => {
+                    swap_rows.push(swap_row::SwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::WithdrawShares(instruction) => {
+                    withdraw_shares_rows.push(withdraw_shares_row::WithdrawSharesRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::WithdrawLpFee(instruction) => {
+                    withdraw_lp_fee_rows.push(withdraw_lp_fee_row::WithdrawLpFeeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::WithdrawProjectFee(instruction) => {
+                    withdraw_project_fee_rows.push(
+                        withdraw_project_fee_row::WithdrawProjectFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::CreateFarm(instruction) => {
+                    create_farm_rows.push(create_farm_row::CreateFarmRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::CreateDualFarm(instruction) => {
+                    create_dual_farm_rows.push(
+                        create_dual_farm_row::CreateDualFarmRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::CreateTripleFarm(instruction) => {
+                    create_triple_farm_rows.push(
+                        create_triple_farm_row::CreateTripleFarmRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::WithdrawRewards(instruction) => {
+                    withdraw_rewards_rows.push(
+                        withdraw_rewards_row::WithdrawRewardsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::ClosePool(instruction) => {
+                    close_pool_rows.push(close_pool_row::ClosePoolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::WithdrawMercantiFee(instruction) => {
+                    withdraw_mercanti_fee_rows.push(
+                        withdraw_mercanti_fee_row::WithdrawMercantiFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                BonkswapInstruction::AddSupply(instruction) => {
+                    add_supply_rows.push(add_supply_row::AddSupplyRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::UpdateFees(instruction) => {
+                    update_fees_rows.push(update_fees_row::UpdateFeesRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::ResetFarm(instruction) => {
+                    reset_farm_rows.push(reset_farm_row::ResetFarmRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                BonkswapInstruction::UpdateRewardTokens(instruction) => {
+                    update_reward_tokens_rows.push(
+                        update_reward_tokens_row::UpdateRewardTokensRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        create_pool_row::CreatePoolRow::upsert_many(&create_pool_rows, pool).await?;
+        create_provider_row::CreateProviderRow::upsert_many(&create_provider_rows, pool).await?;
+        create_state_row::CreateStateRow::upsert_many(&create_state_rows, pool).await?;
+        add_tokens_row::AddTokensRow::upsert_many(&add_tokens_rows, pool).await?;
+        withdraw_buyback_row::WithdrawBuybackRow::upsert_many(&withdraw_buyback_rows, pool).await?;
+        swap_row::SwapRow::upsert_many(&swap_rows, pool).await?;
+        withdraw_shares_row::WithdrawSharesRow::upsert_many(&withdraw_shares_rows, pool).await?;
+        withdraw_lp_fee_row::WithdrawLpFeeRow::upsert_many(&withdraw_lp_fee_rows, pool).await?;
+        withdraw_project_fee_row::WithdrawProjectFeeRow::upsert_many(
+            &withdraw_project_fee_rows,
+            pool,
+        )
+        .await?;
+        create_farm_row::CreateFarmRow::upsert_many(&create_farm_rows, pool).await?;
+        create_dual_farm_row::CreateDualFarmRow::upsert_many(&create_dual_farm_rows, pool).await?;
+        create_triple_farm_row::CreateTripleFarmRow::upsert_many(&create_triple_farm_rows, pool)
+            .await?;
+        withdraw_rewards_row::WithdrawRewardsRow::upsert_many(&withdraw_rewards_rows, pool).await?;
+        close_pool_row::ClosePoolRow::upsert_many(&close_pool_rows, pool).await?;
+        withdraw_mercanti_fee_row::WithdrawMercantiFeeRow::upsert_many(
+            &withdraw_mercanti_fee_rows,
+            pool,
+        )
+        .await?;
+        add_supply_row::AddSupplyRow::upsert_many(&add_supply_rows, pool).await?;
+        update_fees_row::UpdateFeesRow::upsert_many(&update_fees_rows, pool).await?;
+        reset_farm_row::ResetFarmRow::upsert_many(&reset_farm_rows, pool).await?;
+        update_reward_tokens_row::UpdateRewardTokensRow::upsert_many(
+            &update_reward_tokens_rows,
+            pool,
+        )
+        .await?;
+        Ok(())
+    }
 }
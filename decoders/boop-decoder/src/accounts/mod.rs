@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -18,6 +17,11 @@ pub enum BoopAccount {
 
 impl AccountDecoder<'_> for BoopDecoder {
     type AccountType = BoopAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -26,50 +30,11 @@ impl AccountDecoder<'_> for BoopDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = amm_config::AmmConfig::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BoopAccount::AmmConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            bonding_curve::BondingCurve::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BoopAccount::BondingCurve(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = config::Config::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BoopAccount::Config(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            locked_cp_liquidity_state::LockedCpLiquidityState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BoopAccount::LockedCpLiquidityState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            BoopAccount::AmmConfig => amm_config::AmmConfig,
+            BoopAccount::BondingCurve => bonding_curve::BondingCurve,
+            BoopAccount::Config => config::Config,
+            BoopAccount::LockedCpLiquidityState => locked_cp_liquidity_state::LockedCpLiquidityState,
+        )
     }
 }
@@ -130,6 +130,10 @@ pub enum BoopInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for BoopDecoder {
     type InstructionType = BoopInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
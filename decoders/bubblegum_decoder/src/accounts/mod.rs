@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use super::BubblegumDecoder;
 pub mod tree_config;
@@ -12,31 +11,18 @@ pub enum BubblegumAccount {
 
 impl AccountDecoder<'_> for BubblegumDecoder {
     type AccountType = BubblegumAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
     ) -> Option<carbon_core::account::DecodedAccount<Self::AccountType>> {
-        if let Some(decoded_account) = tree_config::TreeConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BubblegumAccount::TreeConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = voucher::Voucher::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: BubblegumAccount::Voucher(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            BubblegumAccount::TreeConfig => tree_config::TreeConfig,
+            BubblegumAccount::Voucher => voucher::Voucher,
+        )
     }
 }
@@ -86,6 +86,10 @@ pub enum BubblegumInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for BubblegumDecoder {
     type InstructionType = BubblegumInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
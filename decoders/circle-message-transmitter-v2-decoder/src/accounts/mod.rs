@@ -1,7 +1,4 @@
-use {
-    super::MessageTransmitterV2Decoder,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MessageTransmitterV2Decoder, carbon_core::account::AccountDecoder};
 pub mod message_sent;
 pub mod message_transmitter;
 pub mod used_nonce;
@@ -14,44 +11,19 @@ pub enum MessageTransmitterV2Account {
 
 impl AccountDecoder<'_> for MessageTransmitterV2Decoder {
     type AccountType = MessageTransmitterV2Account;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
     ) -> Option<carbon_core::account::DecodedAccount<Self::AccountType>> {
-        if let Some(decoded_account) =
-            message_sent::MessageSent::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MessageTransmitterV2Account::MessageSent(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            message_transmitter::MessageTransmitter::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MessageTransmitterV2Account::MessageTransmitter(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = used_nonce::UsedNonce::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MessageTransmitterV2Account::UsedNonce(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MessageTransmitterV2Account::MessageSent => message_sent::MessageSent,
+            MessageTransmitterV2Account::MessageTransmitter => message_transmitter::MessageTransmitter,
+            MessageTransmitterV2Account::UsedNonce => used_nonce::UsedNonce,
+        )
     }
 }
@@ -72,6 +72,10 @@ pub enum MessageTransmitterV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MessageTransmitterV2Decoder {
     type InstructionType = MessageTransmitterV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -116,6 +116,10 @@ pub enum TokenMessengerMinterV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for TokenMessengerMinterV2Decoder {
     type InstructionType = TokenMessengerMinterV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
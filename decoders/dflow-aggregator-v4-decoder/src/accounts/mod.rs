@@ -20,6 +20,10 @@ pub enum SwapOrchestratorAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for SwapOrchestratorDecoder {
     type AccountType = SwapOrchestratorAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -30,11 +34,22 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for SwapOrchestratorDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = order::Order::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[&[
+                134, 173, 223, 185, 77, 86, 28, 51,
+            ]]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => order::Order::decode(data)
+                    .map(|decoded| SwapOrchestratorAccount::Order(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: SwapOrchestratorAccount::Order(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
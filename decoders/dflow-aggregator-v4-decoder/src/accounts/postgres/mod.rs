@@ -73,4 +73,25 @@ impl carbon_core::postgres::operations::Upsert for SwapOrchestratorAccountWithMe
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut order_rows = Vec::new();
+
+        for SwapOrchestratorAccountWithMetadata(account, metadata) in rows {
+            match account {
+                SwapOrchestratorAccount::Order(account) => {
+                    order_rows.push(order_row::OrderRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        order_row::OrderRow::upsert_many(&order_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -236,6 +236,83 @@ impl carbon_core::postgres::operations::Upsert for OrderRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(16)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO order_account (
+                "closer",
+                "output_token_account",
+                "return_input_token_account",
+                "return_rent_to",
+                "id",
+                "quoted_out_amount",
+                "last_fillable_slot",
+                "slippage_bps",
+                "bump",
+                "vault_bump",
+                "flags",
+                "padding1",
+                "padding2",
+                "padding3",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.closer)
+                    .push_bind(row.output_token_account)
+                    .push_bind(row.return_input_token_account)
+                    .push_bind(row.return_rent_to)
+                    .push_bind(row.id.clone())
+                    .push_bind(row.quoted_out_amount.clone())
+                    .push_bind(row.last_fillable_slot.clone())
+                    .push_bind(row.slippage_bps)
+                    .push_bind(row.bump)
+                    .push_bind(row.vault_bump)
+                    .push_bind(row.flags)
+                    .push_bind(row.padding1)
+                    .push_bind(row.padding2)
+                    .push_bind(row.padding3)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(row.account_metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "closer" = EXCLUDED."closer",
+                "output_token_account" = EXCLUDED."output_token_account",
+                "return_input_token_account" = EXCLUDED."return_input_token_account",
+                "return_rent_to" = EXCLUDED."return_rent_to",
+                "id" = EXCLUDED."id",
+                "quoted_out_amount" = EXCLUDED."quoted_out_amount",
+                "last_fillable_slot" = EXCLUDED."last_fillable_slot",
+                "slippage_bps" = EXCLUDED."slippage_bps",
+                "bump" = EXCLUDED."bump",
+                "vault_bump" = EXCLUDED."vault_bump",
+                "flags" = EXCLUDED."flags",
+                "padding1" = EXCLUDED."padding1",
+                "padding2" = EXCLUDED."padding2",
+                "padding3" = EXCLUDED."padding3",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
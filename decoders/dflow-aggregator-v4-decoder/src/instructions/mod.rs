@@ -67,6 +67,10 @@ pub enum SwapOrchestratorInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SwapOrchestratorDecoder {
     type InstructionType = SwapOrchestratorInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -77,150 +81,51 @@ impl carbon_core::instruction::InstructionDecoder<'_> for SwapOrchestratorDecode
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = close_order::CloseOrder::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::CloseOrder(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_referral_token_account_idempotent::CreateReferralTokenAccountIdempotent::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::CreateReferralTokenAccountIdempotent(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fill_order::FillOrder::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::FillOrder(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = open_order::OpenOrder::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::OpenOrder(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap::Swap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::Swap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap2::Swap2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::Swap2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap2_with_destination::Swap2WithDestination::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::Swap2WithDestination(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                swap2_with_destination_native::Swap2WithDestinationNative::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::Swap2WithDestinationNative(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_with_destination::SwapWithDestination::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::SwapWithDestination(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                swap_with_destination_native::SwapWithDestinationNative::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::SwapWithDestinationNative(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_fee::TransferFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::TransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_sol::TransferSol::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::TransferSol(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_to_sponsor::TransferToSponsor::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::TransferToSponsor(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = unwrap_sol::UnwrapSol::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::UnwrapSol(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = wrap_sol::WrapSol::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::WrapSol(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[90, 103, 209, 28, 7, 63, 168, 4],
+                &[46, 232, 41, 144, 85, 37, 170, 175],
+                &[232, 122, 115, 25, 199, 143, 136, 162],
+                &[206, 88, 88, 143, 38, 136, 50, 224],
+                &[248, 198, 158, 145, 225, 117, 135, 200],
+                &[65, 75, 63, 76, 235, 91, 91, 136],
+                &[95, 123, 213, 246, 122, 1, 86, 231],
+                &[222, 100, 184, 146, 186, 196, 105, 165],
+                &[168, 172, 24, 77, 197, 156, 135, 101],
+                &[205, 77, 127, 108, 241, 32, 196, 195],
+                &[129, 164, 196, 21, 177, 48, 180, 162],
+                &[78, 10, 236, 247, 109, 117, 21, 76],
+                &[155, 179, 130, 151, 196, 139, 253, 163],
+                &[99, 40, 14, 105, 45, 107, 172, 201],
+                &[47, 62, 155, 172, 131, 205, 37, 201],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => close_order::CloseOrder::decode(data).map(SwapOrchestratorInstruction::CloseOrder),
+                1 => create_referral_token_account_idempotent::CreateReferralTokenAccountIdempotent::decode(data).map(SwapOrchestratorInstruction::CreateReferralTokenAccountIdempotent),
+                2 => fill_order::FillOrder::decode(data).map(SwapOrchestratorInstruction::FillOrder),
+                3 => open_order::OpenOrder::decode(data).map(SwapOrchestratorInstruction::OpenOrder),
+                4 => swap::Swap::decode(data).map(SwapOrchestratorInstruction::Swap),
+                5 => swap2::Swap2::decode(data).map(SwapOrchestratorInstruction::Swap2),
+                6 => swap2_with_destination::Swap2WithDestination::decode(data).map(SwapOrchestratorInstruction::Swap2WithDestination),
+                7 => swap2_with_destination_native::Swap2WithDestinationNative::decode(data).map(SwapOrchestratorInstruction::Swap2WithDestinationNative),
+                8 => swap_with_destination::SwapWithDestination::decode(data).map(SwapOrchestratorInstruction::SwapWithDestination),
+                9 => swap_with_destination_native::SwapWithDestinationNative::decode(data).map(SwapOrchestratorInstruction::SwapWithDestinationNative),
+                10 => transfer_fee::TransferFee::decode(data).map(SwapOrchestratorInstruction::TransferFee),
+                11 => transfer_sol::TransferSol::decode(data).map(SwapOrchestratorInstruction::TransferSol),
+                12 => transfer_to_sponsor::TransferToSponsor::decode(data).map(SwapOrchestratorInstruction::TransferToSponsor),
+                13 => unwrap_sol::UnwrapSol::decode(data).map(SwapOrchestratorInstruction::UnwrapSol),
+                14 => wrap_sol::WrapSol::decode(data).map(SwapOrchestratorInstruction::WrapSol),
+                15 => cpi_event::CpiEvent::decode(data).map(SwapOrchestratorInstruction::CpiEvent),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: SwapOrchestratorInstruction::CpiEvent(decoded),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -98,6 +98,49 @@ impl carbon_core::postgres::operations::Upsert for CpiEventRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(6)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO cpi_events (
+            __signature, __instruction_index, __stack_height, __slot, "name", "data"
+        ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.instruction_metadata.signature.clone())
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(row.instruction_metadata.slot.clone())
+                    .push_bind(row.name.clone())
+                    .push_bind(row.data.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (__signature, __instruction_index, __stack_height) DO UPDATE SET
+            __slot = EXCLUDED.__slot,
+            "name" = EXCLUDED."name",
+            "data" = EXCLUDED."data"
+        "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
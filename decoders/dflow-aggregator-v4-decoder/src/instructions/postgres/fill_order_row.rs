@@ -99,6 +99,52 @@ impl carbon_core::postgres::operations::Upsert for FillOrderRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(5)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO fill_order_instruction (
+                "params",
+                __signature, __instruction_index, __stack_height, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.params.clone())
+                    .push_bind(row.instruction_metadata.signature.clone())
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(row.instruction_metadata.slot.clone());
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __signature, __instruction_index, __stack_height
+            ) DO UPDATE SET
+                "params" = EXCLUDED."params",
+                __instruction_index = EXCLUDED.__instruction_index,
+                __stack_height = EXCLUDED.__stack_height,
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -331,4 +331,164 @@ impl carbon_core::postgres::operations::Upsert for SwapOrchestratorInstructionWi
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut close_order_rows = Vec::new();
+        let mut create_referral_token_account_idempotent_rows = Vec::new();
+        let mut fill_order_rows = Vec::new();
+        let mut open_order_rows = Vec::new();
+        let mut swap_rows = Vec::new();
+        let mut swap2_rows = Vec::new();
+        let mut swap2_with_destination_rows = Vec::new();
+        let mut swap2_with_destination_native_rows = Vec::new();
+        let mut swap_with_destination_rows = Vec::new();
+        let mut swap_with_destination_native_rows = Vec::new();
+        let mut transfer_fee_rows = Vec::new();
+        let mut transfer_sol_rows = Vec::new();
+        let mut transfer_to_sponsor_rows = Vec::new();
+        let mut unwrap_sol_rows = Vec::new();
+        let mut wrap_sol_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for SwapOrchestratorInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                SwapOrchestratorInstruction::CloseOrder(instruction) => {
+                    close_order_rows.push(close_order_row::CloseOrderRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::CreateReferralTokenAccountIdempotent(instruction) => {
+                    create_referral_token_account_idempotent_rows.push(create_referral_token_account_idempotent_row::CreateReferralTokenAccountIdempotentRow::from_parts(instruction.clone(), metadata.clone()));
+                }
+                SwapOrchestratorInstruction::FillOrder(instruction) => {
+                    fill_order_rows.push(fill_order_row::FillOrderRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::OpenOrder(instruction) => {
+                    open_order_rows.push(open_order_row::OpenOrderRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::Swap(instruction) => {
+                    swap_rows.push(swap_row::SwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::Swap2(instruction) => {
+                    swap2_rows.push(swap2_row::Swap2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::Swap2WithDestination(instruction) => {
+                    swap2_with_destination_rows.push(
+                        swap2_with_destination_row::Swap2WithDestinationRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwapOrchestratorInstruction::Swap2WithDestinationNative(instruction) => {
+                    swap2_with_destination_native_rows.push(swap2_with_destination_native_row::Swap2WithDestinationNativeRow::from_parts(instruction.clone(), metadata.clone()));
+                }
+                SwapOrchestratorInstruction::SwapWithDestination(instruction) => {
+                    swap_with_destination_rows.push(
+                        swap_with_destination_row::SwapWithDestinationRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwapOrchestratorInstruction::SwapWithDestinationNative(instruction) => {
+                    swap_with_destination_native_rows.push(
+                        swap_with_destination_native_row::SwapWithDestinationNativeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwapOrchestratorInstruction::TransferFee(instruction) => {
+                    transfer_fee_rows.push(transfer_fee_row::TransferFeeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::TransferSol(instruction) => {
+                    transfer_sol_rows.push(transfer_sol_row::TransferSolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::TransferToSponsor(instruction) => {
+                    transfer_to_sponsor_rows.push(
+                        transfer_to_sponsor_row::TransferToSponsorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwapOrchestratorInstruction::UnwrapSol(instruction) => {
+                    unwrap_sol_rows.push(unwrap_sol_row::UnwrapSolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::WrapSol(instruction) => {
+                    wrap_sol_rows.push(wrap_sol_row::WrapSolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwapOrchestratorInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        close_order_row::CloseOrderRow::upsert_many(&close_order_rows, pool).await?;
+        create_referral_token_account_idempotent_row::CreateReferralTokenAccountIdempotentRow::upsert_many(&create_referral_token_account_idempotent_rows, pool).await?;
+        fill_order_row::FillOrderRow::upsert_many(&fill_order_rows, pool).await?;
+        open_order_row::OpenOrderRow::upsert_many(&open_order_rows, pool).await?;
+        swap_row::SwapRow::upsert_many(&swap_rows, pool).await?;
+        swap2_row::Swap2Row::upsert_many(&swap2_rows, pool).await?;
+        swap2_with_destination_row::Swap2WithDestinationRow::upsert_many(
+            &swap2_with_destination_rows,
+            pool,
+        )
+        .await?;
+        swap2_with_destination_native_row::Swap2WithDestinationNativeRow::upsert_many(
+            &swap2_with_destination_native_rows,
+            pool,
+        )
+        .await?;
+        swap_with_destination_row::SwapWithDestinationRow::upsert_many(
+            &swap_with_destination_rows,
+            pool,
+        )
+        .await?;
+        swap_with_destination_native_row::SwapWithDestinationNativeRow::upsert_many(
+            &swap_with_destination_native_rows,
+            pool,
+        )
+        .await?;
+        transfer_fee_row::TransferFeeRow::upsert_many(&transfer_fee_rows, pool).await?;
+        transfer_sol_row::TransferSolRow::upsert_many(&transfer_sol_rows, pool).await?;
+        transfer_to_sponsor_row::TransferToSponsorRow::upsert_many(&transfer_to_sponsor_rows, pool)
+            .await?;
+        unwrap_sol_row::UnwrapSolRow::upsert_many(&unwrap_sol_rows, pool).await?;
+        wrap_sol_row::WrapSolRow::upsert_many(&wrap_sol_rows, pool).await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
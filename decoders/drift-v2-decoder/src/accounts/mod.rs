@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -46,6 +45,11 @@ pub enum DriftAccount {
 
 impl AccountDecoder<'_> for DriftDecoder {
     type AccountType = DriftAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -54,212 +58,24 @@ impl AccountDecoder<'_> for DriftDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            openbook_v2_fulfillment_config::OpenbookV2FulfillmentConfig::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::OpenbookV2FulfillmentConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            phoenix_v1_fulfillment_config::PhoenixV1FulfillmentConfig::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::PhoenixV1FulfillmentConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            serum_v3_fulfillment_config::SerumV3FulfillmentConfig::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::SerumV3FulfillmentConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            high_leverage_mode_config::HighLeverageModeConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::HighLeverageModeConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            insurance_fund_stake::InsuranceFundStake::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::InsuranceFundStake(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_if_shares_transfer_config::ProtocolIfSharesTransferConfig::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::ProtocolIfSharesTransferConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            prelaunch_oracle::PrelaunchOracle::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::PrelaunchOracle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = perp_market::PerpMarket::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::PerpMarket(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protected_maker_mode_config::ProtectedMakerModeConfig::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::ProtectedMakerModeConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            pyth_lazer_oracle::PythLazerOracle::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::PythLazerOracle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            signed_msg_user_orders::SignedMsgUserOrders::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::SignedMsgUserOrders(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = spot_market::SpotMarket::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::SpotMarket(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = state::State::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::State(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = user::User::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::User(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = user_stats::UserStats::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::UserStats(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            referrer_name::ReferrerName::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::ReferrerName(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            fuel_overflow::FuelOverflow::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: DriftAccount::FuelOverflow(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            DriftAccount::OpenbookV2FulfillmentConfig => openbook_v2_fulfillment_config::OpenbookV2FulfillmentConfig,
+            DriftAccount::PhoenixV1FulfillmentConfig => phoenix_v1_fulfillment_config::PhoenixV1FulfillmentConfig,
+            DriftAccount::SerumV3FulfillmentConfig => serum_v3_fulfillment_config::SerumV3FulfillmentConfig,
+            DriftAccount::HighLeverageModeConfig => high_leverage_mode_config::HighLeverageModeConfig,
+            DriftAccount::InsuranceFundStake => insurance_fund_stake::InsuranceFundStake,
+            DriftAccount::ProtocolIfSharesTransferConfig => protocol_if_shares_transfer_config::ProtocolIfSharesTransferConfig,
+            DriftAccount::PrelaunchOracle => prelaunch_oracle::PrelaunchOracle,
+            DriftAccount::PerpMarket => perp_market::PerpMarket,
+            DriftAccount::ProtectedMakerModeConfig => protected_maker_mode_config::ProtectedMakerModeConfig,
+            DriftAccount::PythLazerOracle => pyth_lazer_oracle::PythLazerOracle,
+            DriftAccount::SignedMsgUserOrders => signed_msg_user_orders::SignedMsgUserOrders,
+            DriftAccount::SpotMarket => spot_market::SpotMarket,
+            DriftAccount::State => state::State,
+            DriftAccount::User => user::User,
+            DriftAccount::UserStats => user_stats::UserStats,
+            DriftAccount::ReferrerName => referrer_name::ReferrerName,
+            DriftAccount::FuelOverflow => fuel_overflow::FuelOverflow,
+        )
     }
 }
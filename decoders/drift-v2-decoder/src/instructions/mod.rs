@@ -449,6 +449,10 @@ pub enum DriftInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for DriftDecoder {
     type InstructionType = DriftInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
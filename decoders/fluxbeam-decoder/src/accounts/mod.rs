@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -12,6 +11,11 @@ pub enum FluxbeamAccount {
 
 impl AccountDecoder<'_> for FluxbeamDecoder {
     type AccountType = FluxbeamAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -20,17 +24,9 @@ impl AccountDecoder<'_> for FluxbeamDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = swap_v1::SwapV1::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: FluxbeamAccount::SwapV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            FluxbeamAccount::SwapV1 => swap_v1::SwapV1,
+        )
     }
 }
 
@@ -34,6 +34,10 @@ pub enum FluxbeamInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for FluxbeamDecoder {
     type InstructionType = FluxbeamInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -14,6 +13,11 @@ pub enum GavelAccount {
 
 impl AccountDecoder<'_> for GavelDecoder {
     type AccountType = GavelAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,30 +26,9 @@ impl AccountDecoder<'_> for GavelDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            pool_account::PoolAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: GavelAccount::PoolAccount(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            lp_position_account::LpPositionAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: GavelAccount::LpPositionAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            GavelAccount::PoolAccount => pool_account::PoolAccount,
+            GavelAccount::LpPositionAccount => lp_position_account::LpPositionAccount,
+        )
     }
 }
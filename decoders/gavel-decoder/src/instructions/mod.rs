@@ -38,6 +38,10 @@ pub enum GavelInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for GavelDecoder {
     type InstructionType = GavelInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
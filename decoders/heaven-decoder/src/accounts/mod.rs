@@ -1,8 +1,4 @@
-use {
-    super::HeavenDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::HeavenDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod liquidity_pool_state;
 pub mod msol_ticket_sol_spent;
 pub mod protocol_admin_state;
@@ -20,6 +16,11 @@ pub enum HeavenAccount {
 
 impl AccountDecoder<'_> for HeavenDecoder {
     type AccountType = HeavenAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -28,66 +29,12 @@ impl AccountDecoder<'_> for HeavenDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            liquidity_pool_state::LiquidityPoolState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: HeavenAccount::LiquidityPoolState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            msol_ticket_sol_spent::MsolTicketSolSpent::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: HeavenAccount::MsolTicketSolSpent(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_admin_state::ProtocolAdminState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: HeavenAccount::ProtocolAdminState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_config::ProtocolConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: HeavenAccount::ProtocolConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_owner_state::ProtocolOwnerState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: HeavenAccount::ProtocolOwnerState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            HeavenAccount::LiquidityPoolState => liquidity_pool_state::LiquidityPoolState,
+            HeavenAccount::MsolTicketSolSpent => msol_ticket_sol_spent::MsolTicketSolSpent,
+            HeavenAccount::ProtocolAdminState => protocol_admin_state::ProtocolAdminState,
+            HeavenAccount::ProtocolConfig => protocol_config::ProtocolConfig,
+            HeavenAccount::ProtocolOwnerState => protocol_owner_state::ProtocolOwnerState,
+        )
     }
 }
@@ -85,6 +85,10 @@ pub enum HeavenInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for HeavenDecoder {
     type InstructionType = HeavenInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
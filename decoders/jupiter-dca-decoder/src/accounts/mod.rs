@@ -1,8 +1,4 @@
-use {
-    super::JupiterDcaDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::JupiterDcaDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod dca;
 
 pub enum JupiterDcaAccount {
@@ -11,6 +7,11 @@ pub enum JupiterDcaAccount {
 
 impl AccountDecoder<'_> for JupiterDcaDecoder {
     type AccountType = JupiterDcaAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -19,17 +20,9 @@ impl AccountDecoder<'_> for JupiterDcaDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = dca::Dca::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: JupiterDcaAccount::Dca(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            JupiterDcaAccount::Dca => dca::Dca,
+        )
     }
 }
 
@@ -131,4 +124,32 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_decodes_dca_account_in_pipeline() {
+        let pubkey = solana_pubkey::Pubkey::new_unique();
+        let update = carbon_pipeline_harness::read_account_update(
+            "tests/fixtures/dca_account.json",
+            pubkey,
+            1,
+        )
+        .expect("read fixture");
+        let recorder = carbon_pipeline_harness::Recorder::new();
+
+        carbon_pipeline_harness::run_pipeline(
+            carbon_core::pipeline::Pipeline::builder().account(JupiterDcaDecoder, recorder.clone()),
+            vec![update],
+        )
+        .await
+        .expect("run pipeline");
+
+        let records: Vec<carbon_core::account::AccountProcessorInputType<JupiterDcaAccount>> =
+            recorder.take();
+        assert_eq!(records.len(), 1);
+        let (metadata, decoded_account, _) = &records[0];
+        assert_eq!(metadata.pubkey, pubkey);
+        match &decoded_account.data {
+            JupiterDcaAccount::Dca(dca_account) => assert_eq!(dca_account.bump, 249),
+        }
+    }
 }
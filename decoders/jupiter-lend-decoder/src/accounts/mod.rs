@@ -31,6 +31,10 @@ pub enum LiquidityAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for LiquidityDecoder {
     type AccountType = LiquidityAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -41,77 +45,40 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for LiquidityDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = authorization_list::AuthorizationList::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::AuthorizationList(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = liquidity::Liquidity::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::Liquidity(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = rate_model::RateModel::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::RateModel(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = token_reserve::TokenReserve::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::TokenReserve(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = user_borrow_position::UserBorrowPosition::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::UserBorrowPosition(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = user_claim::UserClaim::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: LiquidityAccount::UserClaim(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = user_supply_position::UserSupplyPosition::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[19, 157, 117, 43, 236, 167, 251, 69],
+                &[54, 252, 249, 226, 137, 172, 121, 58],
+                &[94, 3, 203, 219, 107, 137, 4, 162],
+                &[21, 18, 59, 135, 120, 20, 31, 12],
+                &[73, 126, 65, 123, 220, 126, 197, 24],
+                &[228, 142, 195, 181, 228, 147, 32, 209],
+                &[202, 219, 136, 118, 61, 177, 21, 146],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => authorization_list::AuthorizationList::decode(data)
+                    .map(|decoded| LiquidityAccount::AuthorizationList(Box::new(decoded))),
+                1 => liquidity::Liquidity::decode(data)
+                    .map(|decoded| LiquidityAccount::Liquidity(Box::new(decoded))),
+                2 => rate_model::RateModel::decode(data)
+                    .map(|decoded| LiquidityAccount::RateModel(Box::new(decoded))),
+                3 => token_reserve::TokenReserve::decode(data)
+                    .map(|decoded| LiquidityAccount::TokenReserve(Box::new(decoded))),
+                4 => user_borrow_position::UserBorrowPosition::decode(data)
+                    .map(|decoded| LiquidityAccount::UserBorrowPosition(Box::new(decoded))),
+                5 => user_claim::UserClaim::decode(data)
+                    .map(|decoded| LiquidityAccount::UserClaim(Box::new(decoded))),
+                6 => user_supply_position::UserSupplyPosition::decode(data)
+                    .map(|decoded| LiquidityAccount::UserSupplyPosition(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: LiquidityAccount::UserSupplyPosition(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
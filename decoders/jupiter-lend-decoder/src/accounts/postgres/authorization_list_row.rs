@@ -131,6 +131,50 @@ impl carbon_core::postgres::operations::Upsert for AuthorizationListRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(5)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO authorization_list_account (
+                "auth_users",
+                "guardians",
+                "user_classes",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.auth_users)
+                    .push_bind(&row.guardians)
+                    .push_bind(&row.user_classes)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "auth_users" = EXCLUDED."auth_users",
+                "guardians" = EXCLUDED."guardians",
+                "user_classes" = EXCLUDED."user_classes",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
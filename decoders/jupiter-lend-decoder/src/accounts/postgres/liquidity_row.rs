@@ -126,6 +126,53 @@ impl carbon_core::postgres::operations::Upsert for LiquidityRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(6)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO liquidity_account (
+                "authority",
+                "revenue_collector",
+                "status",
+                "bump",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.authority)
+                    .push_bind(row.revenue_collector)
+                    .push_bind(row.status)
+                    .push_bind(row.bump)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "authority" = EXCLUDED."authority",
+                "revenue_collector" = EXCLUDED."revenue_collector",
+                "status" = EXCLUDED."status",
+                "bump" = EXCLUDED."bump",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
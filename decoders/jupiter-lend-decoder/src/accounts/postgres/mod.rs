@@ -171,4 +171,88 @@ impl carbon_core::postgres::operations::Upsert for LiquidityAccountWithMetadata
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut authorization_list_rows = Vec::new();
+        let mut liquidity_rows = Vec::new();
+        let mut rate_model_rows = Vec::new();
+        let mut token_reserve_rows = Vec::new();
+        let mut user_borrow_position_rows = Vec::new();
+        let mut user_claim_rows = Vec::new();
+        let mut user_supply_position_rows = Vec::new();
+
+        for LiquidityAccountWithMetadata(account, metadata) in rows {
+            match account {
+                LiquidityAccount::AuthorizationList(account) => {
+                    authorization_list_rows.push(
+                        authorization_list_row::AuthorizationListRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                LiquidityAccount::Liquidity(account) => {
+                    liquidity_rows.push(liquidity_row::LiquidityRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                LiquidityAccount::RateModel(account) => {
+                    rate_model_rows.push(rate_model_row::RateModelRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                LiquidityAccount::TokenReserve(account) => {
+                    token_reserve_rows.push(token_reserve_row::TokenReserveRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                LiquidityAccount::UserBorrowPosition(account) => {
+                    user_borrow_position_rows.push(
+                        user_borrow_position_row::UserBorrowPositionRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                LiquidityAccount::UserClaim(account) => {
+                    user_claim_rows.push(user_claim_row::UserClaimRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                LiquidityAccount::UserSupplyPosition(account) => {
+                    user_supply_position_rows.push(
+                        user_supply_position_row::UserSupplyPositionRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        authorization_list_row::AuthorizationListRow::upsert_many(&authorization_list_rows, pool)
+            .await?;
+        liquidity_row::LiquidityRow::upsert_many(&liquidity_rows, pool).await?;
+        rate_model_row::RateModelRow::upsert_many(&rate_model_rows, pool).await?;
+        token_reserve_row::TokenReserveRow::upsert_many(&token_reserve_rows, pool).await?;
+        user_borrow_position_row::UserBorrowPositionRow::upsert_many(
+            &user_borrow_position_rows,
+            pool,
+        )
+        .await?;
+        user_claim_row::UserClaimRow::upsert_many(&user_claim_rows, pool).await?;
+        user_supply_position_row::UserSupplyPositionRow::upsert_many(
+            &user_supply_position_rows,
+            pool,
+        )
+        .await?;
+        Ok(())
+    }
 }
@@ -186,6 +186,65 @@ impl carbon_core::postgres::operations::Upsert for RateModelRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(10)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO rate_model_account (
+                "mint",
+                "version",
+                "rate_at_zero",
+                "kink1_utilization",
+                "rate_at_kink1",
+                "rate_at_max",
+                "kink2_utilization",
+                "rate_at_kink2",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.mint)
+                    .push_bind(row.version)
+                    .push_bind(row.rate_at_zero)
+                    .push_bind(row.kink1_utilization)
+                    .push_bind(row.rate_at_kink1)
+                    .push_bind(row.rate_at_max)
+                    .push_bind(row.kink2_utilization)
+                    .push_bind(row.rate_at_kink2)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "mint" = EXCLUDED."mint",
+                "version" = EXCLUDED."version",
+                "rate_at_zero" = EXCLUDED."rate_at_zero",
+                "kink1_utilization" = EXCLUDED."kink1_utilization",
+                "rate_at_kink1" = EXCLUDED."rate_at_kink1",
+                "rate_at_max" = EXCLUDED."rate_at_max",
+                "kink2_utilization" = EXCLUDED."kink2_utilization",
+                "rate_at_kink2" = EXCLUDED."rate_at_kink2",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -255,6 +255,92 @@ impl carbon_core::postgres::operations::Upsert for TokenReserveRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(19)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO token_reserve_account (
+                "mint",
+                "vault",
+                "borrow_rate",
+                "fee_on_interest",
+                "last_utilization",
+                "last_update_timestamp",
+                "supply_exchange_price",
+                "borrow_exchange_price",
+                "max_utilization",
+                "total_supply_with_interest",
+                "total_supply_interest_free",
+                "total_borrow_with_interest",
+                "total_borrow_interest_free",
+                "total_claim_amount",
+                "interacting_protocol",
+                "interacting_timestamp",
+                "interacting_balance",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.mint)
+                    .push_bind(row.vault)
+                    .push_bind(row.borrow_rate)
+                    .push_bind(row.fee_on_interest)
+                    .push_bind(row.last_utilization)
+                    .push_bind(&row.last_update_timestamp)
+                    .push_bind(&row.supply_exchange_price)
+                    .push_bind(&row.borrow_exchange_price)
+                    .push_bind(row.max_utilization)
+                    .push_bind(&row.total_supply_with_interest)
+                    .push_bind(&row.total_supply_interest_free)
+                    .push_bind(&row.total_borrow_with_interest)
+                    .push_bind(&row.total_borrow_interest_free)
+                    .push_bind(&row.total_claim_amount)
+                    .push_bind(row.interacting_protocol)
+                    .push_bind(&row.interacting_timestamp)
+                    .push_bind(&row.interacting_balance)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "mint" = EXCLUDED."mint",
+                "vault" = EXCLUDED."vault",
+                "borrow_rate" = EXCLUDED."borrow_rate",
+                "fee_on_interest" = EXCLUDED."fee_on_interest",
+                "last_utilization" = EXCLUDED."last_utilization",
+                "last_update_timestamp" = EXCLUDED."last_update_timestamp",
+                "supply_exchange_price" = EXCLUDED."supply_exchange_price",
+                "borrow_exchange_price" = EXCLUDED."borrow_exchange_price",
+                "max_utilization" = EXCLUDED."max_utilization",
+                "total_supply_with_interest" = EXCLUDED."total_supply_with_interest",
+                "total_supply_interest_free" = EXCLUDED."total_supply_interest_free",
+                "total_borrow_with_interest" = EXCLUDED."total_borrow_with_interest",
+                "total_borrow_interest_free" = EXCLUDED."total_borrow_interest_free",
+                "total_claim_amount" = EXCLUDED."total_claim_amount",
+                "interacting_protocol" = EXCLUDED."interacting_protocol",
+                "interacting_timestamp" = EXCLUDED."interacting_timestamp",
+                "interacting_balance" = EXCLUDED."interacting_balance",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -203,6 +203,74 @@ impl carbon_core::postgres::operations::Upsert for UserBorrowPositionRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(13)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO user_borrow_position_account (
+                "protocol",
+                "mint",
+                "with_interest",
+                "amount",
+                "debt_ceiling",
+                "last_update",
+                "expand_pct",
+                "expand_duration",
+                "base_debt_ceiling",
+                "max_debt_ceiling",
+                "status",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.protocol)
+                    .push_bind(row.mint)
+                    .push_bind(row.with_interest)
+                    .push_bind(&row.amount)
+                    .push_bind(&row.debt_ceiling)
+                    .push_bind(&row.last_update)
+                    .push_bind(row.expand_pct)
+                    .push_bind(row.expand_duration)
+                    .push_bind(&row.base_debt_ceiling)
+                    .push_bind(&row.max_debt_ceiling)
+                    .push_bind(row.status)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "protocol" = EXCLUDED."protocol",
+                "mint" = EXCLUDED."mint",
+                "with_interest" = EXCLUDED."with_interest",
+                "amount" = EXCLUDED."amount",
+                "debt_ceiling" = EXCLUDED."debt_ceiling",
+                "last_update" = EXCLUDED."last_update",
+                "expand_pct" = EXCLUDED."expand_pct",
+                "expand_duration" = EXCLUDED."expand_duration",
+                "base_debt_ceiling" = EXCLUDED."base_debt_ceiling",
+                "max_debt_ceiling" = EXCLUDED."max_debt_ceiling",
+                "status" = EXCLUDED."status",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -78,6 +78,10 @@ pub enum LiquidityInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for LiquidityDecoder {
     type InstructionType = LiquidityInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -88,229 +92,92 @@ impl carbon_core::instruction::InstructionDecoder<'_> for LiquidityDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = change_status::ChangeStatus::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::ChangeStatus(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim::Claim::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::Claim(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_claim_account::CloseClaimAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::CloseClaimAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = collect_revenue::CollectRevenue::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::CollectRevenue(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = init_claim_account::InitClaimAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::InitClaimAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = init_liquidity::InitLiquidity::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::InitLiquidity(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = init_new_protocol::InitNewProtocol::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::InitNewProtocol(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = init_token_reserve::InitTokenReserve::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::InitTokenReserve(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = operate::Operate::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::Operate(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pause_user::PauseUser::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::PauseUser(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pre_operate::PreOperate::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::PreOperate(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = unpause_user::UnpauseUser::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UnpauseUser(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_authority::UpdateAuthority::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_auths::UpdateAuths::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateAuths(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_exchange_price::UpdateExchangePrice::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateExchangePrice(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_guardians::UpdateGuardians::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateGuardians(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_rate_data_v1::UpdateRateDataV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateRateDataV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_rate_data_v2::UpdateRateDataV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateRateDataV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_revenue_collector::UpdateRevenueCollector::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateRevenueCollector(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_token_config::UpdateTokenConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateTokenConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_user_borrow_config::UpdateUserBorrowConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateUserBorrowConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_user_class::UpdateUserClass::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateUserClass(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_user_supply_config::UpdateUserSupplyConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateUserSupplyConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_user_withdrawal_limit::UpdateUserWithdrawalLimit::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: LiquidityInstruction::UpdateUserWithdrawalLimit(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[236, 145, 131, 228, 227, 17, 192, 255],
+                &[62, 198, 214, 193, 213, 159, 108, 210],
+                &[241, 146, 203, 216, 58, 222, 91, 118],
+                &[87, 96, 211, 36, 240, 43, 246, 87],
+                &[112, 141, 47, 170, 42, 99, 144, 145],
+                &[95, 189, 216, 183, 188, 62, 244, 108],
+                &[193, 147, 5, 32, 138, 135, 213, 158],
+                &[228, 235, 65, 129, 159, 15, 6, 84],
+                &[217, 106, 208, 99, 116, 151, 42, 135],
+                &[18, 63, 43, 94, 239, 53, 101, 14],
+                &[129, 205, 158, 155, 198, 155, 72, 133],
+                &[71, 115, 128, 252, 182, 126, 234, 62],
+                &[32, 46, 64, 28, 149, 75, 243, 88],
+                &[93, 96, 178, 156, 57, 117, 253, 209],
+                &[239, 244, 10, 248, 116, 25, 53, 150],
+                &[43, 62, 250, 138, 141, 117, 132, 97],
+                &[6, 20, 34, 122, 22, 150, 180, 22],
+                &[116, 73, 53, 146, 216, 45, 228, 124],
+                &[167, 142, 124, 240, 220, 113, 141, 59],
+                &[231, 122, 181, 79, 255, 79, 144, 167],
+                &[100, 176, 201, 174, 247, 2, 54, 168],
+                &[12, 206, 68, 135, 63, 212, 48, 119],
+                &[217, 239, 225, 218, 33, 49, 234, 183],
+                &[162, 9, 186, 9, 213, 30, 173, 78],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => change_status::ChangeStatus::decode(data)
+                    .map(LiquidityInstruction::ChangeStatus),
+                1 => claim::Claim::decode(data).map(LiquidityInstruction::Claim),
+                2 => close_claim_account::CloseClaimAccount::decode(data)
+                    .map(LiquidityInstruction::CloseClaimAccount),
+                3 => collect_revenue::CollectRevenue::decode(data)
+                    .map(LiquidityInstruction::CollectRevenue),
+                4 => init_claim_account::InitClaimAccount::decode(data)
+                    .map(LiquidityInstruction::InitClaimAccount),
+                5 => init_liquidity::InitLiquidity::decode(data)
+                    .map(LiquidityInstruction::InitLiquidity),
+                6 => init_new_protocol::InitNewProtocol::decode(data)
+                    .map(LiquidityInstruction::InitNewProtocol),
+                7 => init_token_reserve::InitTokenReserve::decode(data)
+                    .map(LiquidityInstruction::InitTokenReserve),
+                8 => operate::Operate::decode(data).map(LiquidityInstruction::Operate),
+                9 => pause_user::PauseUser::decode(data).map(LiquidityInstruction::PauseUser),
+                10 => pre_operate::PreOperate::decode(data).map(LiquidityInstruction::PreOperate),
+                11 => {
+                    unpause_user::UnpauseUser::decode(data).map(LiquidityInstruction::UnpauseUser)
+                }
+                12 => update_authority::UpdateAuthority::decode(data)
+                    .map(LiquidityInstruction::UpdateAuthority),
+                13 => {
+                    update_auths::UpdateAuths::decode(data).map(LiquidityInstruction::UpdateAuths)
+                }
+                14 => update_exchange_price::UpdateExchangePrice::decode(data)
+                    .map(LiquidityInstruction::UpdateExchangePrice),
+                15 => update_guardians::UpdateGuardians::decode(data)
+                    .map(LiquidityInstruction::UpdateGuardians),
+                16 => update_rate_data_v1::UpdateRateDataV1::decode(data)
+                    .map(LiquidityInstruction::UpdateRateDataV1),
+                17 => update_rate_data_v2::UpdateRateDataV2::decode(data)
+                    .map(LiquidityInstruction::UpdateRateDataV2),
+                18 => update_revenue_collector::UpdateRevenueCollector::decode(data)
+                    .map(LiquidityInstruction::UpdateRevenueCollector),
+                19 => update_token_config::UpdateTokenConfig::decode(data)
+                    .map(LiquidityInstruction::UpdateTokenConfig),
+                20 => update_user_borrow_config::UpdateUserBorrowConfig::decode(data)
+                    .map(LiquidityInstruction::UpdateUserBorrowConfig),
+                21 => update_user_class::UpdateUserClass::decode(data)
+                    .map(LiquidityInstruction::UpdateUserClass),
+                22 => update_user_supply_config::UpdateUserSupplyConfig::decode(data)
+                    .map(LiquidityInstruction::UpdateUserSupplyConfig),
+                23 => update_user_withdrawal_limit::UpdateUserWithdrawalLimit::decode(data)
+                    .map(LiquidityInstruction::UpdateUserWithdrawalLimit),
+                24 => cpi_event::CpiEvent::decode(data)
+                    .map(|decoded| LiquidityInstruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: LiquidityInstruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -575,4 +575,300 @@ impl carbon_core::postgres::operations::Upsert for LiquidityInstructionWithMetad
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut change_status_rows = Vec::new();
+        let mut claim_rows = Vec::new();
+        let mut close_claim_account_rows = Vec::new();
+        let mut collect_revenue_rows = Vec::new();
+        let mut init_claim_account_rows = Vec::new();
+        let mut init_liquidity_rows = Vec::new();
+        let mut init_new_protocol_rows = Vec::new();
+        let mut init_token_reserve_rows = Vec::new();
+        let mut operate_rows = Vec::new();
+        let mut pause_user_rows = Vec::new();
+        let mut pre_operate_rows = Vec::new();
+        let mut unpause_user_rows = Vec::new();
+        let mut update_authority_rows = Vec::new();
+        let mut update_auths_rows = Vec::new();
+        let mut update_exchange_price_rows = Vec::new();
+        let mut update_guardians_rows = Vec::new();
+        let mut update_rate_data_v1_rows = Vec::new();
+        let mut update_rate_data_v2_rows = Vec::new();
+        let mut update_revenue_collector_rows = Vec::new();
+        let mut update_token_config_rows = Vec::new();
+        let mut update_user_borrow_config_rows = Vec::new();
+        let mut update_user_class_rows = Vec::new();
+        let mut update_user_supply_config_rows = Vec::new();
+        let mut update_user_withdrawal_limit_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for LiquidityInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                LiquidityInstruction::ChangeStatus(instruction) => {
+                    change_status_rows.push(change_status_row::ChangeStatusRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::Claim(instruction) => {
+                    claim_rows.push(claim_row::ClaimRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::CloseClaimAccount(instruction) => {
+                    close_claim_account_rows.push(
+                        close_claim_account_row::CloseClaimAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::CollectRevenue(instruction) => {
+                    collect_revenue_rows.push(collect_revenue_row::CollectRevenueRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::InitClaimAccount(instruction) => {
+                    init_claim_account_rows.push(
+                        init_claim_account_row::InitClaimAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::InitLiquidity(instruction) => {
+                    init_liquidity_rows.push(init_liquidity_row::InitLiquidityRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::InitNewProtocol(instruction) => {
+                    init_new_protocol_rows.push(
+                        init_new_protocol_row::InitNewProtocolRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::InitTokenReserve(instruction) => {
+                    init_token_reserve_rows.push(
+                        init_token_reserve_row::InitTokenReserveRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::Operate(instruction) => {
+                    operate_rows.push(operate_row::OperateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::PauseUser(instruction) => {
+                    pause_user_rows.push(pause_user_row::PauseUserRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::PreOperate(instruction) => {
+                    pre_operate_rows.push(pre_operate_row::PreOperateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::UnpauseUser(instruction) => {
+                    unpause_user_rows.push(unpause_user_row::UnpauseUserRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::UpdateAuthority(instruction) => {
+                    update_authority_rows.push(
+                        update_authority_row::UpdateAuthorityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateAuths(instruction) => {
+                    update_auths_rows.push(update_auths_row::UpdateAuthsRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                LiquidityInstruction::UpdateExchangePrice(instruction) => {
+                    update_exchange_price_rows.push(
+                        update_exchange_price_row::UpdateExchangePriceRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateGuardians(instruction) => {
+                    update_guardians_rows.push(
+                        update_guardians_row::UpdateGuardiansRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateRateDataV1(instruction) => {
+                    update_rate_data_v1_rows.push(
+                        update_rate_data_v1_row::UpdateRateDataV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateRateDataV2(instruction) => {
+                    update_rate_data_v2_rows.push(
+                        update_rate_data_v2_row::UpdateRateDataV2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateRevenueCollector(instruction) => {
+                    update_revenue_collector_rows.push(
+                        update_revenue_collector_row::UpdateRevenueCollectorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateTokenConfig(instruction) => {
+                    update_token_config_rows.push(
+                        update_token_config_row::UpdateTokenConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateUserBorrowConfig(instruction) => {
+                    update_user_borrow_config_rows.push(
+                        update_user_borrow_config_row::UpdateUserBorrowConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateUserClass(instruction) => {
+                    update_user_class_rows.push(
+                        update_user_class_row::UpdateUserClassRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateUserSupplyConfig(instruction) => {
+                    update_user_supply_config_rows.push(
+                        update_user_supply_config_row::UpdateUserSupplyConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::UpdateUserWithdrawalLimit(instruction) => {
+                    update_user_withdrawal_limit_rows.push(
+                        update_user_withdrawal_limit_row::UpdateUserWithdrawalLimitRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                LiquidityInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        change_status_row::ChangeStatusRow::upsert_many(&change_status_rows, pool).await?;
+        claim_row::ClaimRow::upsert_many(&claim_rows, pool).await?;
+        close_claim_account_row::CloseClaimAccountRow::upsert_many(&close_claim_account_rows, pool)
+            .await?;
+        collect_revenue_row::CollectRevenueRow::upsert_many(&collect_revenue_rows, pool).await?;
+        init_claim_account_row::InitClaimAccountRow::upsert_many(&init_claim_account_rows, pool)
+            .await?;
+        init_liquidity_row::InitLiquidityRow::upsert_many(&init_liquidity_rows, pool).await?;
+        init_new_protocol_row::InitNewProtocolRow::upsert_many(&init_new_protocol_rows, pool)
+            .await?;
+        init_token_reserve_row::InitTokenReserveRow::upsert_many(&init_token_reserve_rows, pool)
+            .await?;
+        operate_row::OperateRow::upsert_many(&operate_rows, pool).await?;
+        pause_user_row::PauseUserRow::upsert_many(&pause_user_rows, pool).await?;
+        pre_operate_row::PreOperateRow::upsert_many(&pre_operate_rows, pool).await?;
+        unpause_user_row::UnpauseUserRow::upsert_many(&unpause_user_rows, pool).await?;
+        update_authority_row::UpdateAuthorityRow::upsert_many(&update_authority_rows, pool).await?;
+        update_auths_row::UpdateAuthsRow::upsert_many(&update_auths_rows, pool).await?;
+        update_exchange_price_row::UpdateExchangePriceRow::upsert_many(
+            &update_exchange_price_rows,
+            pool,
+        )
+        .await?;
+        update_guardians_row::UpdateGuardiansRow::upsert_many(&update_guardians_rows, pool).await?;
+        update_rate_data_v1_row::UpdateRateDataV1Row::upsert_many(&update_rate_data_v1_rows, pool)
+            .await?;
+        update_rate_data_v2_row::UpdateRateDataV2Row::upsert_many(&update_rate_data_v2_rows, pool)
+            .await?;
+        update_revenue_collector_row::UpdateRevenueCollectorRow::upsert_many(
+            &update_revenue_collector_rows,
+            pool,
+        )
+        .await?;
+        update_token_config_row::UpdateTokenConfigRow::upsert_many(&update_token_config_rows, pool)
+            .await?;
+        update_user_borrow_config_row::UpdateUserBorrowConfigRow::upsert_many(
+            &update_user_borrow_config_rows,
+            pool,
+        )
+        .await?;
+        update_user_class_row::UpdateUserClassRow::upsert_many(&update_user_class_rows, pool)
+            .await?;
+        update_user_supply_config_row::UpdateUserSupplyConfigRow::upsert_many(
+            &update_user_supply_config_rows,
+            pool,
+        )
+        .await?;
+        update_user_withdrawal_limit_row::UpdateUserWithdrawalLimitRow::upsert_many(
+            &update_user_withdrawal_limit_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -1,8 +1,4 @@
-use {
-    super::JupiterLimitOrder2Decoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::JupiterLimitOrder2Decoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod fee;
 pub mod order;
 
@@ -13,6 +9,11 @@ pub enum JupiterLimitOrder2Account {
 
 impl AccountDecoder<'_> for JupiterLimitOrder2Decoder {
     type AccountType = JupiterLimitOrder2Account;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -21,26 +22,9 @@ impl AccountDecoder<'_> for JupiterLimitOrder2Decoder {
             return None;
         }
 
-        if let Some(decoded_account) = order::Order::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: JupiterLimitOrder2Account::Order(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = fee::Fee::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: JupiterLimitOrder2Account::Fee(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            JupiterLimitOrder2Account::Order => order::Order,
+            JupiterLimitOrder2Account::Fee => fee::Fee,
+        )
     }
 }
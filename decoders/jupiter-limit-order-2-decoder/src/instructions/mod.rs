@@ -36,6 +36,10 @@ pub enum JupiterLimitOrder2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for JupiterLimitOrder2Decoder {
     type InstructionType = JupiterLimitOrder2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,4 @@
-use {
-    super::JupiterLimitOrderDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::JupiterLimitOrderDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod fee;
 pub mod order;
 
@@ -13,6 +9,11 @@ pub enum JupiterLimitOrderAccount {
 
 impl AccountDecoder<'_> for JupiterLimitOrderDecoder {
     type AccountType = JupiterLimitOrderAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -21,26 +22,9 @@ impl AccountDecoder<'_> for JupiterLimitOrderDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = fee::Fee::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: JupiterLimitOrderAccount::Fee(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = order::Order::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: JupiterLimitOrderAccount::Order(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            JupiterLimitOrderAccount::Fee => fee::Fee,
+            JupiterLimitOrderAccount::Order => order::Order,
+        )
     }
 }
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -22,6 +21,11 @@ pub enum PerpetualsAccount {
 
 impl AccountDecoder<'_> for PerpetualsDecoder {
     type AccountType = PerpetualsAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -30,71 +34,13 @@ impl AccountDecoder<'_> for PerpetualsDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = custody::Custody::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::Custody(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = perpetuals::Perpetuals::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::Perpetuals(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool::Pool::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::Pool(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            position_request::PositionRequest::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::PositionRequest(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = position::Position::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::Position(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            token_ledger::TokenLedger::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PerpetualsAccount::TokenLedger(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            PerpetualsAccount::Custody => custody::Custody,
+            PerpetualsAccount::Perpetuals => perpetuals::Perpetuals,
+            PerpetualsAccount::Pool => pool::Pool,
+            PerpetualsAccount::PositionRequest => position_request::PositionRequest,
+            PerpetualsAccount::Position => position::Position,
+            PerpetualsAccount::TokenLedger => token_ledger::TokenLedger,
+        )
     }
 }
@@ -142,6 +142,10 @@ pub enum PerpetualsInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PerpetualsDecoder {
     type InstructionType = PerpetualsInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -20,6 +20,10 @@ pub enum JupiterSwapAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for JupiterSwapDecoder {
     type AccountType = JupiterSwapAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -30,11 +34,22 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for JupiterSwapDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = token_ledger::TokenLedger::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[&[
+                156, 247, 9, 188, 54, 108, 85, 77,
+            ]]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => token_ledger::TokenLedger::decode(data)
+                    .map(|decoded| JupiterSwapAccount::TokenLedger(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: JupiterSwapAccount::TokenLedger(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
@@ -71,4 +71,25 @@ impl carbon_core::postgres::operations::Upsert for JupiterSwapAccountWithMetadat
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut token_ledger_rows = Vec::new();
+
+        for JupiterSwapAccountWithMetadata(account, metadata) in rows {
+            match account {
+                JupiterSwapAccount::TokenLedger(account) => {
+                    token_ledger_rows.push(token_ledger_row::TokenLedgerRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        token_ledger_row::TokenLedgerRow::upsert_many(&token_ledger_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -71,6 +71,10 @@ pub enum JupiterSwapInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for JupiterSwapDecoder {
     type InstructionType = JupiterSwapInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -81,163 +85,53 @@ impl carbon_core::instruction::InstructionDecoder<'_> for JupiterSwapDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = claim::Claim::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::Claim(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_token::ClaimToken::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::ClaimToken(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_token::CloseToken::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::CloseToken(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_token_ledger::CreateTokenLedger::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::CreateTokenLedger(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_token_account::CreateTokenAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::CreateTokenAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = exact_out_route::ExactOutRoute::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::ExactOutRoute(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = route::Route::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::Route(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = route_with_token_ledger::RouteWithTokenLedger::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::RouteWithTokenLedger(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_token_ledger::SetTokenLedger::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SetTokenLedger(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                shared_accounts_exact_out_route::SharedAccountsExactOutRoute::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SharedAccountsExactOutRoute(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = shared_accounts_route::SharedAccountsRoute::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SharedAccountsRoute(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                shared_accounts_route_with_token_ledger::SharedAccountsRouteWithTokenLedger::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SharedAccountsRouteWithTokenLedger(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = exact_out_route_v2::ExactOutRouteV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::ExactOutRouteV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = route_v2::RouteV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::RouteV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                shared_accounts_exact_out_route_v2::SharedAccountsExactOutRouteV2::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SharedAccountsExactOutRouteV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = shared_accounts_route_v2::SharedAccountsRouteV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::SharedAccountsRouteV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[62, 198, 214, 193, 213, 159, 108, 210],
+                &[116, 206, 27, 191, 166, 19, 0, 73],
+                &[26, 74, 236, 151, 104, 64, 183, 249],
+                &[232, 242, 197, 253, 240, 143, 129, 52],
+                &[147, 241, 123, 100, 244, 132, 174, 118],
+                &[208, 51, 239, 151, 123, 43, 237, 92],
+                &[229, 23, 203, 151, 122, 227, 173, 42],
+                &[150, 86, 71, 116, 167, 93, 14, 104],
+                &[228, 85, 185, 112, 78, 79, 77, 2],
+                &[176, 209, 105, 168, 154, 125, 69, 62],
+                &[193, 32, 155, 51, 65, 214, 156, 129],
+                &[230, 121, 143, 80, 119, 159, 106, 170],
+                &[157, 138, 184, 82, 21, 244, 243, 36],
+                &[187, 100, 250, 204, 49, 196, 175, 20],
+                &[53, 96, 229, 202, 216, 187, 250, 24],
+                &[209, 152, 83, 147, 124, 254, 216, 233],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => claim::Claim::decode(data).map(JupiterSwapInstruction::Claim),
+                1 => claim_token::ClaimToken::decode(data).map(JupiterSwapInstruction::ClaimToken),
+                2 => close_token::CloseToken::decode(data).map(JupiterSwapInstruction::CloseToken),
+                3 => create_token_ledger::CreateTokenLedger::decode(data).map(JupiterSwapInstruction::CreateTokenLedger),
+                4 => create_token_account::CreateTokenAccount::decode(data).map(JupiterSwapInstruction::CreateTokenAccount),
+                5 => exact_out_route::ExactOutRoute::decode(data).map(JupiterSwapInstruction::ExactOutRoute),
+                6 => route::Route::decode(data).map(JupiterSwapInstruction::Route),
+                7 => route_with_token_ledger::RouteWithTokenLedger::decode(data).map(JupiterSwapInstruction::RouteWithTokenLedger),
+                8 => set_token_ledger::SetTokenLedger::decode(data).map(JupiterSwapInstruction::SetTokenLedger),
+                9 => shared_accounts_exact_out_route::SharedAccountsExactOutRoute::decode(data).map(JupiterSwapInstruction::SharedAccountsExactOutRoute),
+                10 => shared_accounts_route::SharedAccountsRoute::decode(data).map(JupiterSwapInstruction::SharedAccountsRoute),
+                11 => shared_accounts_route_with_token_ledger::SharedAccountsRouteWithTokenLedger::decode(data).map(JupiterSwapInstruction::SharedAccountsRouteWithTokenLedger),
+                12 => exact_out_route_v2::ExactOutRouteV2::decode(data).map(JupiterSwapInstruction::ExactOutRouteV2),
+                13 => route_v2::RouteV2::decode(data).map(JupiterSwapInstruction::RouteV2),
+                14 => shared_accounts_exact_out_route_v2::SharedAccountsExactOutRouteV2::decode(data).map(JupiterSwapInstruction::SharedAccountsExactOutRouteV2),
+                15 => shared_accounts_route_v2::SharedAccountsRouteV2::decode(data).map(JupiterSwapInstruction::SharedAccountsRouteV2),
+                16 => cpi_event::CpiEvent::decode(data).map(|decoded| JupiterSwapInstruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: JupiterSwapInstruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
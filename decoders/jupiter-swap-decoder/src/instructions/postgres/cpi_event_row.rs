@@ -114,6 +114,51 @@ impl carbon_core::postgres::operations::Upsert for CpiEventRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(7)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO cpi_events (
+            __signature, __instruction_index, __stack_height, __slot, "name", "data", __accounts
+        ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.instruction_metadata.signature)
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(&row.instruction_metadata.slot)
+                    .push_bind(&row.name)
+                    .push_bind(&row.data)
+                    .push_bind(&row.accounts);
+            });
+            query.push(
+                r#" ON CONFLICT (__signature, __instruction_index, __stack_height) DO UPDATE SET
+            __slot = EXCLUDED.__slot,
+            "name" = EXCLUDED."name",
+            "data" = EXCLUDED."data",
+            __accounts = EXCLUDED.__accounts
+        "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -407,4 +407,198 @@ impl carbon_core::postgres::operations::Upsert for JupiterSwapInstructionWithMet
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut claim_rows = Vec::new();
+        let mut claim_token_rows = Vec::new();
+        let mut close_token_rows = Vec::new();
+        let mut create_token_ledger_rows = Vec::new();
+        let mut create_token_account_rows = Vec::new();
+        let mut exact_out_route_rows = Vec::new();
+        let mut route_rows = Vec::new();
+        let mut route_with_token_ledger_rows = Vec::new();
+        let mut set_token_ledger_rows = Vec::new();
+        let mut shared_accounts_exact_out_route_rows = Vec::new();
+        let mut shared_accounts_route_rows = Vec::new();
+        let mut shared_accounts_route_with_token_ledger_rows = Vec::new();
+        let mut exact_out_route_v2_rows = Vec::new();
+        let mut route_v2_rows = Vec::new();
+        let mut shared_accounts_exact_out_route_v2_rows = Vec::new();
+        let mut shared_accounts_route_v2_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for JupiterSwapInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                JupiterSwapInstruction::Claim(instruction) => {
+                    claim_rows.push(claim_row::ClaimRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::ClaimToken(instruction) => {
+                    claim_token_rows.push(claim_token_row::ClaimTokenRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::CloseToken(instruction) => {
+                    close_token_rows.push(close_token_row::CloseTokenRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::CreateTokenLedger(instruction) => {
+                    create_token_ledger_rows.push(
+                        create_token_ledger_row::CreateTokenLedgerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::CreateTokenAccount(instruction) => {
+                    create_token_account_rows.push(
+                        create_token_account_row::CreateTokenAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::ExactOutRoute(instruction) => {
+                    exact_out_route_rows.push(exact_out_route_row::ExactOutRouteRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::Route(instruction) => {
+                    route_rows.push(route_row::RouteRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::RouteWithTokenLedger(instruction) => {
+                    route_with_token_ledger_rows.push(
+                        route_with_token_ledger_row::RouteWithTokenLedgerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::SetTokenLedger(instruction) => {
+                    set_token_ledger_rows.push(
+                        set_token_ledger_row::SetTokenLedgerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::SharedAccountsExactOutRoute(instruction) => {
+                    shared_accounts_exact_out_route_rows.push(shared_accounts_exact_out_route_row::SharedAccountsExactOutRouteRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                JupiterSwapInstruction::SharedAccountsRoute(instruction) => {
+                    shared_accounts_route_rows.push(
+                        shared_accounts_route_row::SharedAccountsRouteRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::SharedAccountsRouteWithTokenLedger(instruction) => {
+                    shared_accounts_route_with_token_ledger_rows.push(shared_accounts_route_with_token_ledger_row::SharedAccountsRouteWithTokenLedgerRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                JupiterSwapInstruction::ExactOutRouteV2(instruction) => {
+                    exact_out_route_v2_rows.push(
+                        exact_out_route_v2_row::ExactOutRouteV2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::RouteV2(instruction) => {
+                    route_v2_rows.push(route_v2_row::RouteV2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                JupiterSwapInstruction::SharedAccountsExactOutRouteV2(instruction) => {
+                    shared_accounts_exact_out_route_v2_rows.push(shared_accounts_exact_out_route_v2_row::SharedAccountsExactOutRouteV2Row::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                JupiterSwapInstruction::SharedAccountsRouteV2(instruction) => {
+                    shared_accounts_route_v2_rows.push(
+                        shared_accounts_route_v2_row::SharedAccountsRouteV2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                JupiterSwapInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        claim_row::ClaimRow::upsert_many(&claim_rows, pool).await?;
+        claim_token_row::ClaimTokenRow::upsert_many(&claim_token_rows, pool).await?;
+        close_token_row::CloseTokenRow::upsert_many(&close_token_rows, pool).await?;
+        create_token_ledger_row::CreateTokenLedgerRow::upsert_many(&create_token_ledger_rows, pool)
+            .await?;
+        create_token_account_row::CreateTokenAccountRow::upsert_many(
+            &create_token_account_rows,
+            pool,
+        )
+        .await?;
+        exact_out_route_row::ExactOutRouteRow::upsert_many(&exact_out_route_rows, pool).await?;
+        route_row::RouteRow::upsert_many(&route_rows, pool).await?;
+        route_with_token_ledger_row::RouteWithTokenLedgerRow::upsert_many(
+            &route_with_token_ledger_rows,
+            pool,
+        )
+        .await?;
+        set_token_ledger_row::SetTokenLedgerRow::upsert_many(&set_token_ledger_rows, pool).await?;
+        shared_accounts_exact_out_route_row::SharedAccountsExactOutRouteRow::upsert_many(
+            &shared_accounts_exact_out_route_rows,
+            pool,
+        )
+        .await?;
+        shared_accounts_route_row::SharedAccountsRouteRow::upsert_many(
+            &shared_accounts_route_rows,
+            pool,
+        )
+        .await?;
+        shared_accounts_route_with_token_ledger_row::SharedAccountsRouteWithTokenLedgerRow::upsert_many(&shared_accounts_route_with_token_ledger_rows, pool).await?;
+        exact_out_route_v2_row::ExactOutRouteV2Row::upsert_many(&exact_out_route_v2_rows, pool)
+            .await?;
+        route_v2_row::RouteV2Row::upsert_many(&route_v2_rows, pool).await?;
+        shared_accounts_exact_out_route_v2_row::SharedAccountsExactOutRouteV2Row::upsert_many(
+            &shared_accounts_exact_out_route_v2_rows,
+            pool,
+        )
+        .await?;
+        shared_accounts_route_v2_row::SharedAccountsRouteV2Row::upsert_many(
+            &shared_accounts_route_v2_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -166,6 +166,69 @@ impl carbon_core::postgres::operations::Upsert for RouteV2Row {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(11)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO route_v2_instruction (
+                "in_amount",
+                "quoted_out_amount",
+                "slippage_bps",
+                "platform_fee_bps",
+                "positive_slippage_bps",
+                "route_plan",
+                __signature, __instruction_index, __stack_height, __slot, __accounts
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.in_amount)
+                    .push_bind(&row.quoted_out_amount)
+                    .push_bind(row.slippage_bps)
+                    .push_bind(row.platform_fee_bps)
+                    .push_bind(row.positive_slippage_bps)
+                    .push_bind(&row.route_plan)
+                    .push_bind(&row.instruction_metadata.signature)
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(&row.instruction_metadata.slot)
+                    .push_bind(&row.accounts);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __signature, __instruction_index, __stack_height
+            ) DO UPDATE SET
+                "in_amount" = EXCLUDED."in_amount",
+                "quoted_out_amount" = EXCLUDED."quoted_out_amount",
+                "slippage_bps" = EXCLUDED."slippage_bps",
+                "platform_fee_bps" = EXCLUDED."platform_fee_bps",
+                "positive_slippage_bps" = EXCLUDED."positive_slippage_bps",
+                "route_plan" = EXCLUDED."route_plan",
+                __instruction_index = EXCLUDED.__instruction_index,
+                __stack_height = EXCLUDED.__stack_height,
+                __slot = EXCLUDED.__slot,
+                __accounts = EXCLUDED.__accounts
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -1,8 +1,4 @@
-use {
-    super::KaminoFarmsDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::KaminoFarmsDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod farm_state;
 pub mod global_config;
 pub mod oracle_prices;
@@ -17,6 +13,11 @@ pub enum KaminoFarmsAccount {
 
 impl AccountDecoder<'_> for KaminoFarmsDecoder {
     type AccountType = KaminoFarmsAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -24,50 +25,11 @@ impl AccountDecoder<'_> for KaminoFarmsDecoder {
         if !account.owner.eq(&PROGRAM_ID) {
             return None;
         }
-        if let Some(decoded_account) = farm_state::FarmState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoFarmsAccount::FarmState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            global_config::GlobalConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoFarmsAccount::GlobalConfig(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = user_state::UserState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoFarmsAccount::UserState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            oracle_prices::OraclePrices::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoFarmsAccount::OraclePrices(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            KaminoFarmsAccount::FarmState => farm_state::FarmState,
+            KaminoFarmsAccount::GlobalConfig => global_config::GlobalConfig,
+            KaminoFarmsAccount::UserState => user_state::UserState,
+            KaminoFarmsAccount::OraclePrices => oracle_prices::OraclePrices,
+        )
     }
 }
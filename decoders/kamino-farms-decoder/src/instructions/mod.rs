@@ -66,6 +66,10 @@ pub enum KaminoFarmsInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for KaminoFarmsDecoder {
     type InstructionType = KaminoFarmsInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
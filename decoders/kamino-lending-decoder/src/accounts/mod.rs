@@ -1,8 +1,4 @@
-use {
-    super::KaminoLendingDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::KaminoLendingDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod lending_market;
 pub mod obligation;
 pub mod referrer_state;
@@ -25,6 +21,11 @@ pub enum KaminoLendingAccount {
 
 impl AccountDecoder<'_> for KaminoLendingDecoder {
     type AccountType = KaminoLendingAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -33,95 +34,15 @@ impl AccountDecoder<'_> for KaminoLendingDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = user_state::UserState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::UserState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            lending_market::LendingMarket::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::LendingMarket(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = obligation::Obligation::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::Obligation(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            referrer_state::ReferrerState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::ReferrerState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            referrer_token_state::ReferrerTokenState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::ReferrerTokenState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = short_url::ShortUrl::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::ShortUrl(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            user_metadata::UserMetadata::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::UserMetadata(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = reserve::Reserve::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLendingAccount::Reserve(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            KaminoLendingAccount::UserState => user_state::UserState,
+            KaminoLendingAccount::LendingMarket => lending_market::LendingMarket,
+            KaminoLendingAccount::Obligation => obligation::Obligation,
+            KaminoLendingAccount::ReferrerState => referrer_state::ReferrerState,
+            KaminoLendingAccount::ReferrerTokenState => referrer_token_state::ReferrerTokenState,
+            KaminoLendingAccount::ShortUrl => short_url::ShortUrl,
+            KaminoLendingAccount::UserMetadata => user_metadata::UserMetadata,
+            KaminoLendingAccount::Reserve => reserve::Reserve,
+        )
     }
 }
@@ -1,8 +1,4 @@
-use {
-    super::KaminoLimitOrderDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::KaminoLimitOrderDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod global_config;
 pub mod order;
 
@@ -13,6 +9,11 @@ pub enum KaminoLimitOrderAccount {
 
 impl AccountDecoder<'_> for KaminoLimitOrderDecoder {
     type AccountType = KaminoLimitOrderAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -20,28 +21,9 @@ impl AccountDecoder<'_> for KaminoLimitOrderDecoder {
         if !account.owner.eq(&PROGRAM_ID) {
             return None;
         }
-        if let Some(decoded_account) = order::Order::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLimitOrderAccount::Order(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            global_config::GlobalConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoLimitOrderAccount::GlobalConfig(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            KaminoLimitOrderAccount::Order => order::Order,
+            KaminoLimitOrderAccount::GlobalConfig => global_config::GlobalConfig,
+        )
     }
 }
@@ -42,6 +42,10 @@ pub enum KaminoLimitOrderInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for KaminoLimitOrderDecoder {
     type InstructionType = KaminoLimitOrderInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
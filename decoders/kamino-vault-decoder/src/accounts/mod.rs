@@ -1,8 +1,4 @@
-use {
-    super::KaminoVaultDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::KaminoVaultDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod reserve;
 pub mod vault_state;
 
@@ -13,6 +9,11 @@ pub enum KaminoVaultAccount {
 
 impl AccountDecoder<'_> for KaminoVaultDecoder {
     type AccountType = KaminoVaultAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -21,27 +22,9 @@ impl AccountDecoder<'_> for KaminoVaultDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = reserve::Reserve::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoVaultAccount::Reserve(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = vault_state::VaultState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: KaminoVaultAccount::VaultState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            KaminoVaultAccount::Reserve => reserve::Reserve,
+            KaminoVaultAccount::VaultState => vault_state::VaultState,
+        )
     }
 }
@@ -24,6 +24,10 @@ pub enum LifinityAmmV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for LifinityAmmV2Decoder {
     type InstructionType = LifinityAmmV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,4 @@
-use {
-    super::MarginfiV2Decoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MarginfiV2Decoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod bank;
 pub mod marginfi_account;
 pub mod marginfi_group;
@@ -15,6 +11,11 @@ pub enum MarginfiV2Account {
 
 impl AccountDecoder<'_> for MarginfiV2Decoder {
     type AccountType = MarginfiV2Account;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,40 +23,10 @@ impl AccountDecoder<'_> for MarginfiV2Decoder {
         if !account.owner.eq(&PROGRAM_ID) {
             return None;
         }
-        if let Some(decoded_account) =
-            marginfi_account::MarginfiAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MarginfiV2Account::MarginfiAccount(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            marginfi_group::MarginfiGroup::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MarginfiV2Account::MarginfiGroup(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = bank::Bank::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MarginfiV2Account::Bank(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MarginfiV2Account::MarginfiAccount => marginfi_account::MarginfiAccount,
+            MarginfiV2Account::MarginfiGroup => marginfi_group::MarginfiGroup,
+            MarginfiV2Account::Bank => bank::Bank,
+        )
     }
 }
@@ -1,8 +1,4 @@
-use {
-    super::MarinadeFinanceDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MarinadeFinanceDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod state;
 pub mod ticket_account_data;
 
@@ -13,6 +9,11 @@ pub enum MarinadeFinanceAccount {
 
 impl AccountDecoder<'_> for MarinadeFinanceDecoder {
     type AccountType = MarinadeFinanceAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -20,28 +21,9 @@ impl AccountDecoder<'_> for MarinadeFinanceDecoder {
         if !account.owner.eq(&PROGRAM_ID) {
             return None;
         }
-        if let Some(decoded_account) =
-            ticket_account_data::TicketAccountData::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MarinadeFinanceAccount::TicketAccountData(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = state::State::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MarinadeFinanceAccount::State(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MarinadeFinanceAccount::TicketAccountData => ticket_account_data::TicketAccountData,
+            MarinadeFinanceAccount::State => state::State,
+        )
     }
 }
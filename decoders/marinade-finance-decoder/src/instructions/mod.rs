@@ -122,6 +122,10 @@ pub enum MarinadeFinanceInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MarinadeFinanceDecoder {
     type InstructionType = MarinadeFinanceInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -17,6 +17,10 @@ pub enum MemoProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MemoProgramDecoder {
     type InstructionType = MemoProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![spl_memo_interface::v3::ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
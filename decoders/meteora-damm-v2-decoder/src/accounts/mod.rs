@@ -37,6 +37,10 @@ pub enum MeteoraDammV2Account {
 impl<'a> carbon_core::account::AccountDecoder<'a> for MeteoraDammV2Decoder {
     type AccountType = MeteoraDammV2Account;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -47,105 +51,52 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for MeteoraDammV2Decoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = config::Config::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::Config(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = operator::Operator::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::Operator(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                pod_aligned_fee_market_cap_scheduler::PodAlignedFeeMarketCapScheduler::decode(data)
-            {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::PodAlignedFeeMarketCapScheduler(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                pod_aligned_fee_rate_limiter::PodAlignedFeeRateLimiter::decode(data)
-            {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::PodAlignedFeeRateLimiter(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                pod_aligned_fee_time_scheduler::PodAlignedFeeTimeScheduler::decode(data)
-            {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::PodAlignedFeeTimeScheduler(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pool::Pool::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::Pool(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = position::Position::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::Position(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = token_badge::TokenBadge::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: MeteoraDammV2Account::TokenBadge(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = vesting::Vesting::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[155, 12, 170, 224, 30, 250, 204, 130],
+                &[219, 31, 188, 145, 69, 139, 204, 117],
+                &[251, 130, 208, 253, 245, 27, 145, 203],
+                &[160, 219, 8, 251, 179, 7, 16, 117],
+                &[239, 132, 138, 213, 67, 154, 130, 70],
+                &[241, 154, 109, 4, 17, 177, 109, 188],
+                &[170, 188, 143, 228, 122, 64, 247, 208],
+                &[116, 219, 204, 229, 249, 116, 255, 150],
+                &[100, 149, 66, 138, 95, 200, 128, 241],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => config::Config::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::Config(Box::new(decoded))),
+                1 => operator::Operator::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::Operator(Box::new(decoded))),
+                2 => pod_aligned_fee_market_cap_scheduler::PodAlignedFeeMarketCapScheduler::decode(
+                    data,
+                )
+                .map(|decoded| {
+                    MeteoraDammV2Account::PodAlignedFeeMarketCapScheduler(Box::new(decoded))
+                }),
+                3 => pod_aligned_fee_rate_limiter::PodAlignedFeeRateLimiter::decode(data).map(
+                    |decoded| MeteoraDammV2Account::PodAlignedFeeRateLimiter(Box::new(decoded)),
+                ),
+                4 => pod_aligned_fee_time_scheduler::PodAlignedFeeTimeScheduler::decode(data).map(
+                    |decoded| MeteoraDammV2Account::PodAlignedFeeTimeScheduler(Box::new(decoded)),
+                ),
+                5 => pool::Pool::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::Pool(Box::new(decoded))),
+                6 => position::Position::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::Position(Box::new(decoded))),
+                7 => token_badge::TokenBadge::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::TokenBadge(Box::new(decoded))),
+                8 => vesting::Vesting::decode(data)
+                    .map(|decoded| MeteoraDammV2Account::Vesting(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: MeteoraDammV2Account::Vesting(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
@@ -221,6 +221,74 @@ impl carbon_core::postgres::operations::Upsert for ConfigRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(13)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO config_account (
+                "vault_config_key",
+                "pool_creator_authority",
+                "pool_fees",
+                "activation_type",
+                "collect_fee_mode",
+                "config_type",
+                "padding0",
+                "index",
+                "sqrt_min_price",
+                "sqrt_max_price",
+                "padding1",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.vault_config_key)
+                    .push_bind(row.pool_creator_authority)
+                    .push_bind(&row.pool_fees)
+                    .push_bind(row.activation_type)
+                    .push_bind(row.collect_fee_mode)
+                    .push_bind(row.config_type)
+                    .push_bind(&row.padding0)
+                    .push_bind(&row.index)
+                    .push_bind(&row.sqrt_min_price)
+                    .push_bind(&row.sqrt_max_price)
+                    .push_bind(&row.padding1)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "vault_config_key" = EXCLUDED."vault_config_key",
+                "pool_creator_authority" = EXCLUDED."pool_creator_authority",
+                "pool_fees" = EXCLUDED."pool_fees",
+                "activation_type" = EXCLUDED."activation_type",
+                "collect_fee_mode" = EXCLUDED."collect_fee_mode",
+                "config_type" = EXCLUDED."config_type",
+                "padding0" = EXCLUDED."padding0",
+                "index" = EXCLUDED."index",
+                "sqrt_min_price" = EXCLUDED."sqrt_min_price",
+                "sqrt_max_price" = EXCLUDED."sqrt_max_price",
+                "padding1" = EXCLUDED."padding1",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -182,4 +182,97 @@ impl carbon_core::postgres::operations::Upsert for MeteoraDammV2AccountWithMetad
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut config_rows = Vec::new();
+        let mut operator_rows = Vec::new();
+        let mut pod_aligned_fee_market_cap_scheduler_rows = Vec::new();
+        let mut pod_aligned_fee_rate_limiter_rows = Vec::new();
+        let mut pod_aligned_fee_time_scheduler_rows = Vec::new();
+        let mut pool_rows = Vec::new();
+        let mut position_rows = Vec::new();
+        let mut token_badge_rows = Vec::new();
+        let mut vesting_rows = Vec::new();
+
+        for MeteoraDammV2AccountWithMetadata(account, metadata) in rows {
+            match account {
+                MeteoraDammV2Account::Config(account) => {
+                    config_rows.push(config_row::ConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                MeteoraDammV2Account::Operator(account) => {
+                    operator_rows.push(operator_row::OperatorRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                MeteoraDammV2Account::PodAlignedFeeMarketCapScheduler(account) => {
+                    pod_aligned_fee_market_cap_scheduler_rows.push(pod_aligned_fee_market_cap_scheduler_row::PodAlignedFeeMarketCapSchedulerRow::from_parts(*account.clone(), metadata.clone()));
+                }
+                MeteoraDammV2Account::PodAlignedFeeRateLimiter(account) => {
+                    pod_aligned_fee_rate_limiter_rows.push(
+                        pod_aligned_fee_rate_limiter_row::PodAlignedFeeRateLimiterRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Account::PodAlignedFeeTimeScheduler(account) => {
+                    pod_aligned_fee_time_scheduler_rows.push(pod_aligned_fee_time_scheduler_row::PodAlignedFeeTimeSchedulerRow::from_parts(*account.clone(), metadata.clone()));
+                }
+                MeteoraDammV2Account::Pool(account) => {
+                    pool_rows.push(pool_row::PoolRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                MeteoraDammV2Account::Position(account) => {
+                    position_rows.push(position_row::PositionRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                MeteoraDammV2Account::TokenBadge(account) => {
+                    token_badge_rows.push(token_badge_row::TokenBadgeRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                MeteoraDammV2Account::Vesting(account) => {
+                    vesting_rows.push(vesting_row::VestingRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        config_row::ConfigRow::upsert_many(&config_rows, pool).await?;
+        operator_row::OperatorRow::upsert_many(&operator_rows, pool).await?;
+        pod_aligned_fee_market_cap_scheduler_row::PodAlignedFeeMarketCapSchedulerRow::upsert_many(
+            &pod_aligned_fee_market_cap_scheduler_rows,
+            pool,
+        )
+        .await?;
+        pod_aligned_fee_rate_limiter_row::PodAlignedFeeRateLimiterRow::upsert_many(
+            &pod_aligned_fee_rate_limiter_rows,
+            pool,
+        )
+        .await?;
+        pod_aligned_fee_time_scheduler_row::PodAlignedFeeTimeSchedulerRow::upsert_many(
+            &pod_aligned_fee_time_scheduler_rows,
+            pool,
+        )
+        .await?;
+        pool_row::PoolRow::upsert_many(&pool_rows, pool).await?;
+        position_row::PositionRow::upsert_many(&position_rows, pool).await?;
+        token_badge_row::TokenBadgeRow::upsert_many(&token_badge_rows, pool).await?;
+        vesting_row::VestingRow::upsert_many(&vesting_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -174,6 +174,62 @@ impl carbon_core::postgres::operations::Upsert for PodAlignedFeeRateLimiterRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(9)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO pod_aligned_fee_rate_limiter_account (
+                "cliff_fee_numerator",
+                "base_fee_mode",
+                "padding",
+                "fee_increment_bps",
+                "max_limiter_duration",
+                "max_fee_bps",
+                "reference_amount",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.cliff_fee_numerator)
+                    .push_bind(row.base_fee_mode)
+                    .push_bind(&row.padding)
+                    .push_bind(row.fee_increment_bps)
+                    .push_bind(row.max_limiter_duration)
+                    .push_bind(row.max_fee_bps)
+                    .push_bind(&row.reference_amount)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "cliff_fee_numerator" = EXCLUDED."cliff_fee_numerator",
+                "base_fee_mode" = EXCLUDED."base_fee_mode",
+                "padding" = EXCLUDED."padding",
+                "fee_increment_bps" = EXCLUDED."fee_increment_bps",
+                "max_limiter_duration" = EXCLUDED."max_limiter_duration",
+                "max_fee_bps" = EXCLUDED."max_fee_bps",
+                "reference_amount" = EXCLUDED."reference_amount",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
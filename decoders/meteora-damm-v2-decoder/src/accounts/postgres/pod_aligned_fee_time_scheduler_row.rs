@@ -157,6 +157,59 @@ impl carbon_core::postgres::operations::Upsert for PodAlignedFeeTimeSchedulerRow
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(8)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO pod_aligned_fee_time_scheduler_account (
+                "cliff_fee_numerator",
+                "base_fee_mode",
+                "padding",
+                "number_of_period",
+                "period_frequency",
+                "reduction_factor",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.cliff_fee_numerator)
+                    .push_bind(row.base_fee_mode)
+                    .push_bind(&row.padding)
+                    .push_bind(row.number_of_period)
+                    .push_bind(&row.period_frequency)
+                    .push_bind(&row.reduction_factor)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "cliff_fee_numerator" = EXCLUDED."cliff_fee_numerator",
+                "base_fee_mode" = EXCLUDED."base_fee_mode",
+                "padding" = EXCLUDED."padding",
+                "number_of_period" = EXCLUDED."number_of_period",
+                "period_frequency" = EXCLUDED."period_frequency",
+                "reduction_factor" = EXCLUDED."reduction_factor",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -443,6 +443,137 @@ impl carbon_core::postgres::operations::Upsert for PoolRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(34)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO pool_account (
+                "pool_fees",
+                "token_a_mint",
+                "token_b_mint",
+                "token_a_vault",
+                "token_b_vault",
+                "whitelisted_vault",
+                "partner",
+                "liquidity",
+                "padding",
+                "protocol_a_fee",
+                "protocol_b_fee",
+                "partner_a_fee",
+                "partner_b_fee",
+                "sqrt_min_price",
+                "sqrt_max_price",
+                "sqrt_price",
+                "activation_point",
+                "activation_type",
+                "pool_status",
+                "token_a_flag",
+                "token_b_flag",
+                "collect_fee_mode",
+                "pool_type",
+                "version",
+                "padding0",
+                "fee_a_per_liquidity",
+                "fee_b_per_liquidity",
+                "permanent_lock_liquidity",
+                "metrics",
+                "creator",
+                "padding1",
+                "reward_infos",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.pool_fees)
+                    .push_bind(row.token_a_mint)
+                    .push_bind(row.token_b_mint)
+                    .push_bind(row.token_a_vault)
+                    .push_bind(row.token_b_vault)
+                    .push_bind(row.whitelisted_vault)
+                    .push_bind(row.partner)
+                    .push_bind(&row.liquidity)
+                    .push_bind(&row.padding)
+                    .push_bind(&row.protocol_a_fee)
+                    .push_bind(&row.protocol_b_fee)
+                    .push_bind(&row.partner_a_fee)
+                    .push_bind(&row.partner_b_fee)
+                    .push_bind(&row.sqrt_min_price)
+                    .push_bind(&row.sqrt_max_price)
+                    .push_bind(&row.sqrt_price)
+                    .push_bind(&row.activation_point)
+                    .push_bind(row.activation_type)
+                    .push_bind(row.pool_status)
+                    .push_bind(row.token_a_flag)
+                    .push_bind(row.token_b_flag)
+                    .push_bind(row.collect_fee_mode)
+                    .push_bind(row.pool_type)
+                    .push_bind(row.version)
+                    .push_bind(row.padding0)
+                    .push_bind(&row.fee_a_per_liquidity)
+                    .push_bind(&row.fee_b_per_liquidity)
+                    .push_bind(&row.permanent_lock_liquidity)
+                    .push_bind(&row.metrics)
+                    .push_bind(row.creator)
+                    .push_bind(&row.padding1)
+                    .push_bind(&row.reward_infos)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "pool_fees" = EXCLUDED."pool_fees",
+                "token_a_mint" = EXCLUDED."token_a_mint",
+                "token_b_mint" = EXCLUDED."token_b_mint",
+                "token_a_vault" = EXCLUDED."token_a_vault",
+                "token_b_vault" = EXCLUDED."token_b_vault",
+                "whitelisted_vault" = EXCLUDED."whitelisted_vault",
+                "partner" = EXCLUDED."partner",
+                "liquidity" = EXCLUDED."liquidity",
+                "padding" = EXCLUDED."padding",
+                "protocol_a_fee" = EXCLUDED."protocol_a_fee",
+                "protocol_b_fee" = EXCLUDED."protocol_b_fee",
+                "partner_a_fee" = EXCLUDED."partner_a_fee",
+                "partner_b_fee" = EXCLUDED."partner_b_fee",
+                "sqrt_min_price" = EXCLUDED."sqrt_min_price",
+                "sqrt_max_price" = EXCLUDED."sqrt_max_price",
+                "sqrt_price" = EXCLUDED."sqrt_price",
+                "activation_point" = EXCLUDED."activation_point",
+                "activation_type" = EXCLUDED."activation_type",
+                "pool_status" = EXCLUDED."pool_status",
+                "token_a_flag" = EXCLUDED."token_a_flag",
+                "token_b_flag" = EXCLUDED."token_b_flag",
+                "collect_fee_mode" = EXCLUDED."collect_fee_mode",
+                "pool_type" = EXCLUDED."pool_type",
+                "version" = EXCLUDED."version",
+                "padding0" = EXCLUDED."padding0",
+                "fee_a_per_liquidity" = EXCLUDED."fee_a_per_liquidity",
+                "fee_b_per_liquidity" = EXCLUDED."fee_b_per_liquidity",
+                "permanent_lock_liquidity" = EXCLUDED."permanent_lock_liquidity",
+                "metrics" = EXCLUDED."metrics",
+                "creator" = EXCLUDED."creator",
+                "padding1" = EXCLUDED."padding1",
+                "reward_infos" = EXCLUDED."reward_infos",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
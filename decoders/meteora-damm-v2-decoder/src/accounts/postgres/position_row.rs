@@ -244,6 +244,77 @@ impl carbon_core::postgres::operations::Upsert for PositionRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(14)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO position_account (
+                "pool",
+                "nft_mint",
+                "fee_a_per_token_checkpoint",
+                "fee_b_per_token_checkpoint",
+                "fee_a_pending",
+                "fee_b_pending",
+                "unlocked_liquidity",
+                "vested_liquidity",
+                "permanent_locked_liquidity",
+                "metrics",
+                "reward_infos",
+                "padding",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.pool)
+                    .push_bind(row.nft_mint)
+                    .push_bind(&row.fee_a_per_token_checkpoint)
+                    .push_bind(&row.fee_b_per_token_checkpoint)
+                    .push_bind(&row.fee_a_pending)
+                    .push_bind(&row.fee_b_pending)
+                    .push_bind(&row.unlocked_liquidity)
+                    .push_bind(&row.vested_liquidity)
+                    .push_bind(&row.permanent_locked_liquidity)
+                    .push_bind(&row.metrics)
+                    .push_bind(&row.reward_infos)
+                    .push_bind(&row.padding)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "pool" = EXCLUDED."pool",
+                "nft_mint" = EXCLUDED."nft_mint",
+                "fee_a_per_token_checkpoint" = EXCLUDED."fee_a_per_token_checkpoint",
+                "fee_b_per_token_checkpoint" = EXCLUDED."fee_b_per_token_checkpoint",
+                "fee_a_pending" = EXCLUDED."fee_a_pending",
+                "fee_b_pending" = EXCLUDED."fee_b_pending",
+                "unlocked_liquidity" = EXCLUDED."unlocked_liquidity",
+                "vested_liquidity" = EXCLUDED."vested_liquidity",
+                "permanent_locked_liquidity" = EXCLUDED."permanent_locked_liquidity",
+                "metrics" = EXCLUDED."metrics",
+                "reward_infos" = EXCLUDED."reward_infos",
+                "padding" = EXCLUDED."padding",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
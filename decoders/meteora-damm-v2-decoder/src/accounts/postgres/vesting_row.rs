@@ -195,6 +195,68 @@ impl carbon_core::postgres::operations::Upsert for VestingRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(11)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO vesting_account (
+                "position",
+                "cliff_point",
+                "period_frequency",
+                "cliff_unlock_liquidity",
+                "liquidity_per_period",
+                "total_released_liquidity",
+                "number_of_period",
+                "padding",
+                "padding2",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.position)
+                    .push_bind(&row.cliff_point)
+                    .push_bind(&row.period_frequency)
+                    .push_bind(&row.cliff_unlock_liquidity)
+                    .push_bind(&row.liquidity_per_period)
+                    .push_bind(&row.total_released_liquidity)
+                    .push_bind(row.number_of_period)
+                    .push_bind(&row.padding)
+                    .push_bind(&row.padding2)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "position" = EXCLUDED."position",
+                "cliff_point" = EXCLUDED."cliff_point",
+                "period_frequency" = EXCLUDED."period_frequency",
+                "cliff_unlock_liquidity" = EXCLUDED."cliff_unlock_liquidity",
+                "liquidity_per_period" = EXCLUDED."liquidity_per_period",
+                "total_released_liquidity" = EXCLUDED."total_released_liquidity",
+                "number_of_period" = EXCLUDED."number_of_period",
+                "padding" = EXCLUDED."padding",
+                "padding2" = EXCLUDED."padding2",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
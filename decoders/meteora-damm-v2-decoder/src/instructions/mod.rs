@@ -101,6 +101,10 @@ pub enum MeteoraDammV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MeteoraDammV2Decoder {
     type InstructionType = MeteoraDammV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -111,323 +115,124 @@ impl carbon_core::instruction::InstructionDecoder<'_> for MeteoraDammV2Decoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = add_liquidity::AddLiquidity::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::AddLiquidity(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_partner_fee::ClaimPartnerFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::ClaimPartnerFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_position_fee::ClaimPositionFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::ClaimPositionFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_protocol_fee::ClaimProtocolFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::ClaimProtocolFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_reward::ClaimReward::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::ClaimReward(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_config::CloseConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CloseConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_operator_account::CloseOperatorAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CloseOperatorAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_position::ClosePosition::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::ClosePosition(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_token_badge::CloseTokenBadge::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CloseTokenBadge(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_config::CreateConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CreateConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_dynamic_config::CreateDynamicConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CreateDynamicConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_operator_account::CreateOperatorAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CreateOperatorAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_position::CreatePosition::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CreatePosition(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_token_badge::CreateTokenBadge::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CreateTokenBadge(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = dummy_ix::DummyIx::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::DummyIx(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fund_reward::FundReward::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::FundReward(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_customizable_pool::InitializeCustomizablePool::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::InitializeCustomizablePool(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_pool::InitializePool::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::InitializePool(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_pool_with_dynamic_config::InitializePoolWithDynamicConfig::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::InitializePoolWithDynamicConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_reward::InitializeReward::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::InitializeReward(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = lock_position::LockPosition::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::LockPosition(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = permanent_lock_position::PermanentLockPosition::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::PermanentLockPosition(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = refresh_vesting::RefreshVesting::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::RefreshVesting(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = remove_all_liquidity::RemoveAllLiquidity::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::RemoveAllLiquidity(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = remove_liquidity::RemoveLiquidity::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::RemoveLiquidity(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_pool_status::SetPoolStatus::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::SetPoolStatus(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = split_position::SplitPosition::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::SplitPosition(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = split_position2::SplitPosition2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::SplitPosition2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap::Swap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::Swap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap2::Swap2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::Swap2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_pool_fees::UpdatePoolFees::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::UpdatePoolFees(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_reward_duration::UpdateRewardDuration::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::UpdateRewardDuration(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_reward_funder::UpdateRewardFunder::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::UpdateRewardFunder(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                withdraw_ineligible_reward::WithdrawIneligibleReward::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::WithdrawIneligibleReward(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[181, 157, 89, 67, 143, 182, 52, 72],
+                &[97, 206, 39, 105, 94, 94, 126, 148],
+                &[180, 38, 154, 17, 133, 33, 162, 211],
+                &[165, 228, 133, 48, 99, 249, 255, 33],
+                &[149, 95, 181, 242, 94, 90, 158, 162],
+                &[145, 9, 72, 157, 95, 125, 61, 85],
+                &[171, 9, 213, 74, 120, 23, 3, 29],
+                &[123, 134, 81, 0, 49, 68, 98, 98],
+                &[108, 146, 86, 110, 179, 254, 10, 104],
+                &[201, 207, 243, 114, 75, 111, 47, 189],
+                &[81, 251, 122, 78, 66, 57, 208, 82],
+                &[221, 64, 246, 149, 240, 153, 229, 163],
+                &[48, 215, 197, 153, 96, 203, 180, 133],
+                &[88, 206, 0, 91, 60, 175, 151, 118],
+                &[234, 95, 176, 185, 7, 42, 35, 159],
+                &[188, 50, 249, 165, 93, 151, 38, 63],
+                &[20, 161, 241, 24, 189, 221, 180, 2],
+                &[95, 180, 10, 172, 84, 174, 232, 40],
+                &[149, 82, 72, 197, 253, 252, 68, 15],
+                &[95, 135, 192, 196, 242, 129, 230, 68],
+                &[227, 62, 2, 252, 247, 10, 171, 185],
+                &[165, 176, 125, 6, 231, 171, 186, 213],
+                &[9, 94, 216, 14, 116, 204, 247, 0],
+                &[10, 51, 61, 35, 112, 105, 24, 85],
+                &[80, 85, 209, 72, 24, 206, 177, 108],
+                &[112, 87, 135, 223, 83, 204, 132, 53],
+                &[172, 241, 221, 138, 161, 29, 253, 42],
+                &[221, 147, 228, 207, 140, 212, 17, 119],
+                &[248, 198, 158, 145, 225, 117, 135, 200],
+                &[65, 75, 63, 76, 235, 91, 91, 136],
+                &[118, 217, 203, 179, 60, 8, 70, 89],
+                &[138, 174, 196, 169, 213, 235, 254, 107],
+                &[211, 28, 48, 32, 215, 160, 35, 23],
+                &[148, 206, 42, 195, 247, 49, 103, 8],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => add_liquidity::AddLiquidity::decode(data)
+                    .map(MeteoraDammV2Instruction::AddLiquidity),
+                1 => claim_partner_fee::ClaimPartnerFee::decode(data)
+                    .map(MeteoraDammV2Instruction::ClaimPartnerFee),
+                2 => claim_position_fee::ClaimPositionFee::decode(data)
+                    .map(MeteoraDammV2Instruction::ClaimPositionFee),
+                3 => claim_protocol_fee::ClaimProtocolFee::decode(data)
+                    .map(MeteoraDammV2Instruction::ClaimProtocolFee),
+                4 => claim_reward::ClaimReward::decode(data)
+                    .map(MeteoraDammV2Instruction::ClaimReward),
+                5 => close_config::CloseConfig::decode(data)
+                    .map(MeteoraDammV2Instruction::CloseConfig),
+                6 => close_operator_account::CloseOperatorAccount::decode(data)
+                    .map(MeteoraDammV2Instruction::CloseOperatorAccount),
+                7 => close_position::ClosePosition::decode(data)
+                    .map(MeteoraDammV2Instruction::ClosePosition),
+                8 => close_token_badge::CloseTokenBadge::decode(data)
+                    .map(MeteoraDammV2Instruction::CloseTokenBadge),
+                9 => create_config::CreateConfig::decode(data)
+                    .map(MeteoraDammV2Instruction::CreateConfig),
+                10 => create_dynamic_config::CreateDynamicConfig::decode(data)
+                    .map(MeteoraDammV2Instruction::CreateDynamicConfig),
+                11 => create_operator_account::CreateOperatorAccount::decode(data)
+                    .map(MeteoraDammV2Instruction::CreateOperatorAccount),
+                12 => create_position::CreatePosition::decode(data)
+                    .map(MeteoraDammV2Instruction::CreatePosition),
+                13 => create_token_badge::CreateTokenBadge::decode(data)
+                    .map(MeteoraDammV2Instruction::CreateTokenBadge),
+                14 => dummy_ix::DummyIx::decode(data).map(MeteoraDammV2Instruction::DummyIx),
+                15 => {
+                    fund_reward::FundReward::decode(data).map(MeteoraDammV2Instruction::FundReward)
+                }
+                16 => initialize_customizable_pool::InitializeCustomizablePool::decode(data)
+                    .map(MeteoraDammV2Instruction::InitializeCustomizablePool),
+                17 => initialize_pool::InitializePool::decode(data)
+                    .map(MeteoraDammV2Instruction::InitializePool),
+                18 => initialize_pool_with_dynamic_config::InitializePoolWithDynamicConfig::decode(
+                    data,
+                )
+                .map(MeteoraDammV2Instruction::InitializePoolWithDynamicConfig),
+                19 => initialize_reward::InitializeReward::decode(data)
+                    .map(MeteoraDammV2Instruction::InitializeReward),
+                20 => lock_position::LockPosition::decode(data)
+                    .map(MeteoraDammV2Instruction::LockPosition),
+                21 => permanent_lock_position::PermanentLockPosition::decode(data)
+                    .map(MeteoraDammV2Instruction::PermanentLockPosition),
+                22 => refresh_vesting::RefreshVesting::decode(data)
+                    .map(MeteoraDammV2Instruction::RefreshVesting),
+                23 => remove_all_liquidity::RemoveAllLiquidity::decode(data)
+                    .map(MeteoraDammV2Instruction::RemoveAllLiquidity),
+                24 => remove_liquidity::RemoveLiquidity::decode(data)
+                    .map(MeteoraDammV2Instruction::RemoveLiquidity),
+                25 => set_pool_status::SetPoolStatus::decode(data)
+                    .map(MeteoraDammV2Instruction::SetPoolStatus),
+                26 => split_position::SplitPosition::decode(data)
+                    .map(MeteoraDammV2Instruction::SplitPosition),
+                27 => split_position2::SplitPosition2::decode(data)
+                    .map(MeteoraDammV2Instruction::SplitPosition2),
+                28 => swap::Swap::decode(data).map(MeteoraDammV2Instruction::Swap),
+                29 => swap2::Swap2::decode(data).map(MeteoraDammV2Instruction::Swap2),
+                30 => update_pool_fees::UpdatePoolFees::decode(data)
+                    .map(MeteoraDammV2Instruction::UpdatePoolFees),
+                31 => update_reward_duration::UpdateRewardDuration::decode(data)
+                    .map(MeteoraDammV2Instruction::UpdateRewardDuration),
+                32 => update_reward_funder::UpdateRewardFunder::decode(data)
+                    .map(MeteoraDammV2Instruction::UpdateRewardFunder),
+                33 => withdraw_ineligible_reward::WithdrawIneligibleReward::decode(data)
+                    .map(MeteoraDammV2Instruction::WithdrawIneligibleReward),
+                34 => cpi_event::CpiEvent::decode(data)
+                    .map(|decoded| MeteoraDammV2Instruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: MeteoraDammV2Instruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
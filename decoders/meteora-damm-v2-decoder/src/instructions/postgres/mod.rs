@@ -771,4 +771,408 @@ impl carbon_core::postgres::operations::Upsert for MeteoraDammV2InstructionWithM
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut add_liquidity_rows = Vec::new();
+        let mut claim_partner_fee_rows = Vec::new();
+        let mut claim_position_fee_rows = Vec::new();
+        let mut claim_protocol_fee_rows = Vec::new();
+        let mut claim_reward_rows = Vec::new();
+        let mut close_config_rows = Vec::new();
+        let mut close_operator_account_rows = Vec::new();
+        let mut close_position_rows = Vec::new();
+        let mut close_token_badge_rows = Vec::new();
+        let mut create_config_rows = Vec::new();
+        let mut create_dynamic_config_rows = Vec::new();
+        let mut create_operator_account_rows = Vec::new();
+        let mut create_position_rows = Vec::new();
+        let mut create_token_badge_rows = Vec::new();
+        let mut dummy_ix_rows = Vec::new();
+        let mut fund_reward_rows = Vec::new();
+        let mut initialize_customizable_pool_rows = Vec::new();
+        let mut initialize_pool_rows = Vec::new();
+        let mut initialize_pool_with_dynamic_config_rows = Vec::new();
+        let mut initialize_reward_rows = Vec::new();
+        let mut lock_position_rows = Vec::new();
+        let mut permanent_lock_position_rows = Vec::new();
+        let mut refresh_vesting_rows = Vec::new();
+        let mut remove_all_liquidity_rows = Vec::new();
+        let mut remove_liquidity_rows = Vec::new();
+        let mut set_pool_status_rows = Vec::new();
+        let mut split_position_rows = Vec::new();
+        let mut split_position2_rows = Vec::new();
+        let mut swap_rows = Vec::new();
+        let mut swap2_rows = Vec::new();
+        let mut update_pool_fees_rows = Vec::new();
+        let mut update_reward_duration_rows = Vec::new();
+        let mut update_reward_funder_rows = Vec::new();
+        let mut withdraw_ineligible_reward_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for MeteoraDammV2InstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                MeteoraDammV2Instruction::AddLiquidity(instruction) => {
+                    add_liquidity_rows.push(add_liquidity_row::AddLiquidityRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::ClaimPartnerFee(instruction) => {
+                    claim_partner_fee_rows.push(
+                        claim_partner_fee_row::ClaimPartnerFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::ClaimPositionFee(instruction) => {
+                    claim_position_fee_rows.push(
+                        claim_position_fee_row::ClaimPositionFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::ClaimProtocolFee(instruction) => {
+                    claim_protocol_fee_rows.push(
+                        claim_protocol_fee_row::ClaimProtocolFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::ClaimReward(instruction) => {
+                    claim_reward_rows.push(claim_reward_row::ClaimRewardRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::CloseConfig(instruction) => {
+                    close_config_rows.push(close_config_row::CloseConfigRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::CloseOperatorAccount(instruction) => {
+                    close_operator_account_rows.push(
+                        close_operator_account_row::CloseOperatorAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::ClosePosition(instruction) => {
+                    close_position_rows.push(close_position_row::ClosePositionRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::CloseTokenBadge(instruction) => {
+                    close_token_badge_rows.push(
+                        close_token_badge_row::CloseTokenBadgeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::CreateConfig(instruction) => {
+                    create_config_rows.push(create_config_row::CreateConfigRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::CreateDynamicConfig(instruction) => {
+                    create_dynamic_config_rows.push(
+                        create_dynamic_config_row::CreateDynamicConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::CreateOperatorAccount(instruction) => {
+                    create_operator_account_rows.push(
+                        create_operator_account_row::CreateOperatorAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::CreatePosition(instruction) => {
+                    create_position_rows.push(create_position_row::CreatePositionRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::CreateTokenBadge(instruction) => {
+                    create_token_badge_rows.push(
+                        create_token_badge_row::CreateTokenBadgeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::DummyIx(instruction) => {
+                    dummy_ix_rows.push(dummy_ix_row::DummyIxRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::FundReward(instruction) => {
+                    fund_reward_rows.push(fund_reward_row::FundRewardRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::InitializeCustomizablePool(instruction) => {
+                    initialize_customizable_pool_rows.push(
+                        initialize_customizable_pool_row::InitializeCustomizablePoolRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::InitializePool(instruction) => {
+                    initialize_pool_rows.push(initialize_pool_row::InitializePoolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::InitializePoolWithDynamicConfig(instruction) => {
+                    initialize_pool_with_dynamic_config_rows.push(initialize_pool_with_dynamic_config_row::InitializePoolWithDynamicConfigRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                MeteoraDammV2Instruction::InitializeReward(instruction) => {
+                    initialize_reward_rows.push(
+                        initialize_reward_row::InitializeRewardRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::LockPosition(instruction) => {
+                    lock_position_rows.push(lock_position_row::LockPositionRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::PermanentLockPosition(instruction) => {
+                    permanent_lock_position_rows.push(
+                        permanent_lock_position_row::PermanentLockPositionRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::RefreshVesting(instruction) => {
+                    refresh_vesting_rows.push(refresh_vesting_row::RefreshVestingRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::RemoveAllLiquidity(instruction) => {
+                    remove_all_liquidity_rows.push(
+                        remove_all_liquidity_row::RemoveAllLiquidityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::RemoveLiquidity(instruction) => {
+                    remove_liquidity_rows.push(
+                        remove_liquidity_row::RemoveLiquidityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::SetPoolStatus(instruction) => {
+                    set_pool_status_rows.push(set_pool_status_row::SetPoolStatusRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::SplitPosition(instruction) => {
+                    split_position_rows.push(split_position_row::SplitPositionRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::SplitPosition2(instruction) => {
+                    split_position2_rows.push(split_position2_row::SplitPosition2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::Swap(instruction) => {
+                    swap_rows.push(swap_row::SwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::Swap2(instruction) => {
+                    swap2_rows.push(swap2_row::Swap2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                MeteoraDammV2Instruction::UpdatePoolFees(instruction) => {
+                    update_pool_fees_rows.push(
+                        update_pool_fees_row::UpdatePoolFeesRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::UpdateRewardDuration(instruction) => {
+                    update_reward_duration_rows.push(
+                        update_reward_duration_row::UpdateRewardDurationRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::UpdateRewardFunder(instruction) => {
+                    update_reward_funder_rows.push(
+                        update_reward_funder_row::UpdateRewardFunderRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::WithdrawIneligibleReward(instruction) => {
+                    withdraw_ineligible_reward_rows.push(
+                        withdraw_ineligible_reward_row::WithdrawIneligibleRewardRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                MeteoraDammV2Instruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        add_liquidity_row::AddLiquidityRow::upsert_many(&add_liquidity_rows, pool).await?;
+        claim_partner_fee_row::ClaimPartnerFeeRow::upsert_many(&claim_partner_fee_rows, pool)
+            .await?;
+        claim_position_fee_row::ClaimPositionFeeRow::upsert_many(&claim_position_fee_rows, pool)
+            .await?;
+        claim_protocol_fee_row::ClaimProtocolFeeRow::upsert_many(&claim_protocol_fee_rows, pool)
+            .await?;
+        claim_reward_row::ClaimRewardRow::upsert_many(&claim_reward_rows, pool).await?;
+        close_config_row::CloseConfigRow::upsert_many(&close_config_rows, pool).await?;
+        close_operator_account_row::CloseOperatorAccountRow::upsert_many(
+            &close_operator_account_rows,
+            pool,
+        )
+        .await?;
+        close_position_row::ClosePositionRow::upsert_many(&close_position_rows, pool).await?;
+        close_token_badge_row::CloseTokenBadgeRow::upsert_many(&close_token_badge_rows, pool)
+            .await?;
+        create_config_row::CreateConfigRow::upsert_many(&create_config_rows, pool).await?;
+        create_dynamic_config_row::CreateDynamicConfigRow::upsert_many(
+            &create_dynamic_config_rows,
+            pool,
+        )
+        .await?;
+        create_operator_account_row::CreateOperatorAccountRow::upsert_many(
+            &create_operator_account_rows,
+            pool,
+        )
+        .await?;
+        create_position_row::CreatePositionRow::upsert_many(&create_position_rows, pool).await?;
+        create_token_badge_row::CreateTokenBadgeRow::upsert_many(&create_token_badge_rows, pool)
+            .await?;
+        dummy_ix_row::DummyIxRow::upsert_many(&dummy_ix_rows, pool).await?;
+        fund_reward_row::FundRewardRow::upsert_many(&fund_reward_rows, pool).await?;
+        initialize_customizable_pool_row::InitializeCustomizablePoolRow::upsert_many(
+            &initialize_customizable_pool_rows,
+            pool,
+        )
+        .await?;
+        initialize_pool_row::InitializePoolRow::upsert_many(&initialize_pool_rows, pool).await?;
+        initialize_pool_with_dynamic_config_row::InitializePoolWithDynamicConfigRow::upsert_many(
+            &initialize_pool_with_dynamic_config_rows,
+            pool,
+        )
+        .await?;
+        initialize_reward_row::InitializeRewardRow::upsert_many(&initialize_reward_rows, pool)
+            .await?;
+        lock_position_row::LockPositionRow::upsert_many(&lock_position_rows, pool).await?;
+        permanent_lock_position_row::PermanentLockPositionRow::upsert_many(
+            &permanent_lock_position_rows,
+            pool,
+        )
+        .await?;
+        refresh_vesting_row::RefreshVestingRow::upsert_many(&refresh_vesting_rows, pool).await?;
+        remove_all_liquidity_row::RemoveAllLiquidityRow::upsert_many(
+            &remove_all_liquidity_rows,
+            pool,
+        )
+        .await?;
+        remove_liquidity_row::RemoveLiquidityRow::upsert_many(&remove_liquidity_rows, pool).await?;
+        set_pool_status_row::SetPoolStatusRow::upsert_many(&set_pool_status_rows, pool).await?;
+        split_position_row::SplitPositionRow::upsert_many(&split_position_rows, pool).await?;
+        split_position2_row::SplitPosition2Row::upsert_many(&split_position2_rows, pool).await?;
+        swap_row::SwapRow::upsert_many(&swap_rows, pool).await?;
+        swap2_row::Swap2Row::upsert_many(&swap2_rows, pool).await?;
+        update_pool_fees_row::UpdatePoolFeesRow::upsert_many(&update_pool_fees_rows, pool).await?;
+        update_reward_duration_row::UpdateRewardDurationRow::upsert_many(
+            &update_reward_duration_rows,
+            pool,
+        )
+        .await?;
+        update_reward_funder_row::UpdateRewardFunderRow::upsert_many(
+            &update_reward_funder_rows,
+            pool,
+        )
+        .await?;
+        withdraw_ineligible_reward_row::WithdrawIneligibleRewardRow::upsert_many(
+            &withdraw_ineligible_reward_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
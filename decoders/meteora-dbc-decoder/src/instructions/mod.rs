@@ -138,6 +138,10 @@ pub enum DynamicBondingCurveInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for DynamicBondingCurveDecoder {
     type InstructionType = DynamicBondingCurveInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
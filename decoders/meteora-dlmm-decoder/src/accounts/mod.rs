@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -31,6 +30,11 @@ pub enum MeteoraDlmmAccount {
 
 impl AccountDecoder<'_> for MeteoraDlmmDecoder {
     type AccountType = MeteoraDlmmAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -39,119 +43,18 @@ impl AccountDecoder<'_> for MeteoraDlmmDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            bin_array_bitmap_extension::BinArrayBitmapExtension::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::BinArrayBitmapExtension(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = bin_array::BinArray::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::BinArray(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            claim_fee_operator::ClaimFeeOperator::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::ClaimFeeOperator(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = lb_pair::LbPair::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::LbPair(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = oracle::Oracle::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::Oracle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = position::Position::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::Position(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = position_v2::PositionV2::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::PositionV2(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            preset_parameter2::PresetParameter2::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::PresetParameter2(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            preset_parameter::PresetParameter::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::PresetParameter(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = token_badge::TokenBadge::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraDlmmAccount::TokenBadge(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MeteoraDlmmAccount::BinArrayBitmapExtension => bin_array_bitmap_extension::BinArrayBitmapExtension,
+            MeteoraDlmmAccount::BinArray => bin_array::BinArray,
+            MeteoraDlmmAccount::ClaimFeeOperator => claim_fee_operator::ClaimFeeOperator,
+            MeteoraDlmmAccount::LbPair => lb_pair::LbPair,
+            MeteoraDlmmAccount::Oracle => oracle::Oracle,
+            MeteoraDlmmAccount::Position => position::Position,
+            MeteoraDlmmAccount::PositionV2 => position_v2::PositionV2,
+            MeteoraDlmmAccount::PresetParameter2 => preset_parameter2::PresetParameter2,
+            MeteoraDlmmAccount::PresetParameter => preset_parameter::PresetParameter,
+            MeteoraDlmmAccount::TokenBadge => token_badge::TokenBadge,
+        )
     }
 }
 
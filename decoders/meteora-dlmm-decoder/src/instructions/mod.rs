@@ -190,6 +190,10 @@ pub enum MeteoraDlmmInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MeteoraDlmmDecoder {
     type InstructionType = MeteoraDlmmInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
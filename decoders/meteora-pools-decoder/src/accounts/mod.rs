@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -16,6 +15,11 @@ pub enum MeteoraPoolsProgramAccount {
 
 impl AccountDecoder<'_> for MeteoraPoolsDecoder {
     type AccountType = MeteoraPoolsProgramAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -24,37 +28,10 @@ impl AccountDecoder<'_> for MeteoraPoolsDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = config::Config::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraPoolsProgramAccount::Config(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = lock_escrow::LockEscrow::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraPoolsProgramAccount::LockEscrow(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool::Pool::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraPoolsProgramAccount::Pool(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MeteoraPoolsProgramAccount::Config => config::Config,
+            MeteoraPoolsProgramAccount::LockEscrow => lock_escrow::LockEscrow,
+            MeteoraPoolsProgramAccount::Pool => pool::Pool,
+        )
     }
 }
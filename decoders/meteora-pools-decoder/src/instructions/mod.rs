@@ -99,6 +99,10 @@ pub enum MeteoraPoolsProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MeteoraPoolsDecoder {
     type InstructionType = MeteoraPoolsProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
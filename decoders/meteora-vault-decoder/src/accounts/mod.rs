@@ -1,8 +1,4 @@
-use {
-    super::MeteoraVaultDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MeteoraVaultDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod strategy;
 pub mod vault;
 
@@ -14,6 +10,11 @@ pub enum MeteoraVaultAccount {
 
 impl AccountDecoder<'_> for MeteoraVaultDecoder {
     type AccountType = MeteoraVaultAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,26 +23,9 @@ impl AccountDecoder<'_> for MeteoraVaultDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = vault::Vault::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraVaultAccount::Vault(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = strategy::Strategy::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MeteoraVaultAccount::Strategy(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MeteoraVaultAccount::Vault => vault::Vault,
+            MeteoraVaultAccount::Strategy => strategy::Strategy,
+        )
     }
 }
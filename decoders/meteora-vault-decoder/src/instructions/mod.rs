@@ -62,6 +62,10 @@ pub enum MeteoraVaultInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MeteoraVaultDecoder {
     type InstructionType = MeteoraVaultInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
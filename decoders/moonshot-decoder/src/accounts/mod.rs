@@ -1,8 +1,4 @@
-use {
-    super::MoonshotDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MoonshotDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod config_account;
 pub mod curve_account;
 
@@ -13,6 +9,11 @@ pub enum MoonshotAccount {
 
 impl AccountDecoder<'_> for MoonshotDecoder {
     type AccountType = MoonshotAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -21,31 +22,10 @@ impl AccountDecoder<'_> for MoonshotDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            config_account::ConfigAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MoonshotAccount::ConfigAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            curve_account::CurveAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MoonshotAccount::CurveAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MoonshotAccount::ConfigAccount => config_account::ConfigAccount,
+            MoonshotAccount::CurveAccount => curve_account::CurveAccount,
+        )
     }
 }
 
@@ -27,6 +27,10 @@ pub enum MoonshotInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MoonshotDecoder {
     type InstructionType = MoonshotInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,4 @@
-use {
-    super::MplCoreProgramDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::MplCoreProgramDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod asset_v1;
 pub mod collection_v1;
 pub mod hashed_asset_v1;
@@ -19,6 +15,11 @@ pub enum MplCoreProgramAccount {
 
 impl AccountDecoder<'_> for MplCoreProgramDecoder {
     type AccountType = MplCoreProgramAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -27,64 +28,12 @@ impl AccountDecoder<'_> for MplCoreProgramDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            plugin_header_v1::PluginHeaderV1::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MplCoreProgramAccount::PluginHeaderV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            plugin_registry_v1::PluginRegistryV1::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MplCoreProgramAccount::PluginRegistryV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = asset_v1::AssetV1::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MplCoreProgramAccount::AssetV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            collection_v1::CollectionV1::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MplCoreProgramAccount::CollectionV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            hashed_asset_v1::HashedAssetV1::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: MplCoreProgramAccount::HashedAssetV1(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            MplCoreProgramAccount::PluginHeaderV1 => plugin_header_v1::PluginHeaderV1,
+            MplCoreProgramAccount::PluginRegistryV1 => plugin_registry_v1::PluginRegistryV1,
+            MplCoreProgramAccount::AssetV1 => asset_v1::AssetV1,
+            MplCoreProgramAccount::CollectionV1 => collection_v1::CollectionV1,
+            MplCoreProgramAccount::HashedAssetV1 => hashed_asset_v1::HashedAssetV1,
+        )
     }
 }
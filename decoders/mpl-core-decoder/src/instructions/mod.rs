@@ -82,6 +82,10 @@ pub enum MplCoreProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for MplCoreProgramDecoder {
     type InstructionType = MplCoreProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
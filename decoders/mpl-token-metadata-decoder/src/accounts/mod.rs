@@ -38,6 +38,11 @@ pub enum TokenMetadataAccount {
 
 impl AccountDecoder<'_> for TokenMetadataDecoder {
     type AccountType = TokenMetadataAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
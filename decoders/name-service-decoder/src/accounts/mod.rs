@@ -1,8 +1,4 @@
-use {
-    super::NameDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::NameDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod name_record_header;
 
 pub enum NameAccount {
@@ -11,6 +7,11 @@ pub enum NameAccount {
 
 impl AccountDecoder<'_> for NameDecoder {
     type AccountType = NameAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -19,18 +20,8 @@ impl AccountDecoder<'_> for NameDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            name_record_header::NameRecordHeader::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: NameAccount::NameRecordHeader(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            NameAccount::NameRecordHeader => name_record_header::NameRecordHeader,
+        )
     }
 }
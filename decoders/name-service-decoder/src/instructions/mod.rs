@@ -28,6 +28,10 @@ pub enum NameInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for NameDecoder {
     type InstructionType = NameInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
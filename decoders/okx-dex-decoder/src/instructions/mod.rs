@@ -44,6 +44,10 @@ pub enum OkxDexInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for OkxDexDecoder {
     type InstructionType = OkxDexInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
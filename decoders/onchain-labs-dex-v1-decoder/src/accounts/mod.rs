@@ -15,6 +15,10 @@ pub enum OnchainLabsDexV1Account {}
 impl<'a> carbon_core::account::AccountDecoder<'a> for OnchainLabsDexV1Decoder {
     type AccountType = OnchainLabsDexV1Account;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
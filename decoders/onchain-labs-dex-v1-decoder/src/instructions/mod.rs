@@ -67,6 +67,10 @@ pub enum OnchainLabsDexV1Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for OnchainLabsDexV1Decoder {
     type InstructionType = OnchainLabsDexV1Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -77,192 +81,77 @@ impl carbon_core::instruction::InstructionDecoder<'_> for OnchainLabsDexV1Decode
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = claim::Claim::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::Claim(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = commission_sol_proxy_swap::CommissionSolProxySwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CommissionSolProxySwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = commission_sol_swap::CommissionSolSwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CommissionSolSwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = commission_spl_proxy_swap::CommissionSplProxySwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CommissionSplProxySwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = commission_spl_swap::CommissionSplSwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CommissionSplSwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = commission_wrap_unwrap::CommissionWrapUnwrap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CommissionWrapUnwrap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_token_account::CreateTokenAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CreateTokenAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                create_token_account_with_seed::CreateTokenAccountWithSeed::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CreateTokenAccountWithSeed(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                platform_fee_sol_proxy_swap_v2::PlatformFeeSolProxySwapV2::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::PlatformFeeSolProxySwapV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                platform_fee_sol_wrap_unwrap_v2::PlatformFeeSolWrapUnwrapV2::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::PlatformFeeSolWrapUnwrapV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                platform_fee_spl_proxy_swap_v2::PlatformFeeSplProxySwapV2::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::PlatformFeeSplProxySwapV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = proxy_swap::ProxySwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::ProxySwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap::Swap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::Swap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob_v3::SwapTobV3::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::SwapTobV3(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob_v3_enhanced::SwapTobV3Enhanced::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::SwapTobV3Enhanced(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob_v3_with_receiver::SwapTobV3WithReceiver::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::SwapTobV3WithReceiver(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_v3::SwapV3::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::SwapV3(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = wrap_unwrap_v3::WrapUnwrapV3::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::WrapUnwrapV3(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                wrap_unwrap_v3_with_receiver::WrapUnwrapV3WithReceiver::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::WrapUnwrapV3WithReceiver(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[62, 198, 214, 193, 213, 159, 108, 210],
+                &[30, 33, 208, 91, 31, 157, 37, 18],
+                &[81, 128, 134, 73, 114, 73, 45, 94],
+                &[96, 67, 12, 151, 129, 164, 18, 71],
+                &[235, 71, 211, 196, 114, 199, 143, 92],
+                &[12, 73, 156, 71, 233, 172, 189, 197],
+                &[147, 241, 123, 100, 244, 132, 174, 118],
+                &[125, 191, 239, 140, 66, 8, 9, 228],
+                &[69, 200, 254, 247, 40, 52, 118, 202],
+                &[196, 172, 152, 92, 60, 186, 64, 227],
+                &[69, 164, 210, 89, 146, 214, 173, 67],
+                &[19, 44, 130, 148, 72, 56, 44, 238],
+                &[248, 198, 158, 145, 225, 117, 135, 200],
+                &[14, 191, 44, 246, 142, 225, 224, 157],
+                &[236, 71, 155, 68, 198, 98, 14, 118],
+                &[63, 114, 246, 131, 51, 2, 247, 29],
+                &[240, 224, 38, 33, 176, 31, 241, 175],
+                &[180, 178, 191, 54, 70, 8, 13, 224],
+                &[70, 211, 190, 165, 47, 40, 213, 95],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded =
+                match position {
+                    0 => claim::Claim::decode(data).map(OnchainLabsDexV1Instruction::Claim),
+                    1 => commission_sol_proxy_swap::CommissionSolProxySwap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CommissionSolProxySwap),
+                    2 => commission_sol_swap::CommissionSolSwap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CommissionSolSwap),
+                    3 => commission_spl_proxy_swap::CommissionSplProxySwap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CommissionSplProxySwap),
+                    4 => commission_spl_swap::CommissionSplSwap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CommissionSplSwap),
+                    5 => commission_wrap_unwrap::CommissionWrapUnwrap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CommissionWrapUnwrap),
+                    6 => create_token_account::CreateTokenAccount::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CreateTokenAccount),
+                    7 => create_token_account_with_seed::CreateTokenAccountWithSeed::decode(data)
+                        .map(OnchainLabsDexV1Instruction::CreateTokenAccountWithSeed),
+                    8 => platform_fee_sol_proxy_swap_v2::PlatformFeeSolProxySwapV2::decode(data)
+                        .map(OnchainLabsDexV1Instruction::PlatformFeeSolProxySwapV2),
+                    9 => platform_fee_sol_wrap_unwrap_v2::PlatformFeeSolWrapUnwrapV2::decode(data)
+                        .map(OnchainLabsDexV1Instruction::PlatformFeeSolWrapUnwrapV2),
+                    10 => platform_fee_spl_proxy_swap_v2::PlatformFeeSplProxySwapV2::decode(data)
+                        .map(OnchainLabsDexV1Instruction::PlatformFeeSplProxySwapV2),
+                    11 => proxy_swap::ProxySwap::decode(data)
+                        .map(OnchainLabsDexV1Instruction::ProxySwap),
+                    12 => swap::Swap::decode(data).map(OnchainLabsDexV1Instruction::Swap),
+                    13 => swap_tob_v3::SwapTobV3::decode(data)
+                        .map(OnchainLabsDexV1Instruction::SwapTobV3),
+                    14 => swap_tob_v3_enhanced::SwapTobV3Enhanced::decode(data)
+                        .map(OnchainLabsDexV1Instruction::SwapTobV3Enhanced),
+                    15 => swap_tob_v3_with_receiver::SwapTobV3WithReceiver::decode(data)
+                        .map(OnchainLabsDexV1Instruction::SwapTobV3WithReceiver),
+                    16 => swap_v3::SwapV3::decode(data).map(OnchainLabsDexV1Instruction::SwapV3),
+                    17 => wrap_unwrap_v3::WrapUnwrapV3::decode(data)
+                        .map(OnchainLabsDexV1Instruction::WrapUnwrapV3),
+                    18 => wrap_unwrap_v3_with_receiver::WrapUnwrapV3WithReceiver::decode(data)
+                        .map(OnchainLabsDexV1Instruction::WrapUnwrapV3WithReceiver),
+                    19 => cpi_event::CpiEvent::decode(data)
+                        .map(|decoded| OnchainLabsDexV1Instruction::CpiEvent(Box::new(decoded))),
+                    _ => None,
+                };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: OnchainLabsDexV1Instruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -480,4 +480,245 @@ impl carbon_core::postgres::operations::Upsert for OnchainLabsDexV1InstructionWi
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut claim_rows = Vec::new();
+        let mut commission_sol_proxy_swap_rows = Vec::new();
+        let mut commission_sol_swap_rows = Vec::new();
+        let mut commission_spl_proxy_swap_rows = Vec::new();
+        let mut commission_spl_swap_rows = Vec::new();
+        let mut commission_wrap_unwrap_rows = Vec::new();
+        let mut create_token_account_rows = Vec::new();
+        let mut create_token_account_with_seed_rows = Vec::new();
+        let mut platform_fee_sol_proxy_swap_v2_rows = Vec::new();
+        let mut platform_fee_sol_wrap_unwrap_v2_rows = Vec::new();
+        let mut platform_fee_spl_proxy_swap_v2_rows = Vec::new();
+        let mut proxy_swap_rows = Vec::new();
+        let mut swap_rows = Vec::new();
+        let mut swap_tob_v3_rows = Vec::new();
+        let mut swap_tob_v3_enhanced_rows = Vec::new();
+        let mut swap_tob_v3_with_receiver_rows = Vec::new();
+        let mut swap_v3_rows = Vec::new();
+        let mut wrap_unwrap_v3_rows = Vec::new();
+        let mut wrap_unwrap_v3_with_receiver_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for OnchainLabsDexV1InstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                OnchainLabsDexV1Instruction::Claim(instruction) => {
+                    claim_rows.push(claim_row::ClaimRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::CommissionSolProxySwap(instruction) => {
+                    commission_sol_proxy_swap_rows.push(
+                        commission_sol_proxy_swap_row::CommissionSolProxySwapRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CommissionSolSwap(instruction) => {
+                    commission_sol_swap_rows.push(
+                        commission_sol_swap_row::CommissionSolSwapRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CommissionSplProxySwap(instruction) => {
+                    commission_spl_proxy_swap_rows.push(
+                        commission_spl_proxy_swap_row::CommissionSplProxySwapRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CommissionSplSwap(instruction) => {
+                    commission_spl_swap_rows.push(
+                        commission_spl_swap_row::CommissionSplSwapRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CommissionWrapUnwrap(instruction) => {
+                    commission_wrap_unwrap_rows.push(
+                        commission_wrap_unwrap_row::CommissionWrapUnwrapRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CreateTokenAccount(instruction) => {
+                    create_token_account_rows.push(
+                        create_token_account_row::CreateTokenAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CreateTokenAccountWithSeed(instruction) => {
+                    create_token_account_with_seed_rows.push(create_token_account_with_seed_row::CreateTokenAccountWithSeedRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                OnchainLabsDexV1Instruction::PlatformFeeSolProxySwapV2(instruction) => {
+                    platform_fee_sol_proxy_swap_v2_rows.push(platform_fee_sol_proxy_swap_v2_row::PlatformFeeSolProxySwapV2Row::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                OnchainLabsDexV1Instruction::PlatformFeeSolWrapUnwrapV2(instruction) => {
+                    platform_fee_sol_wrap_unwrap_v2_rows.push(platform_fee_sol_wrap_unwrap_v2_row::PlatformFeeSolWrapUnwrapV2Row::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                OnchainLabsDexV1Instruction::PlatformFeeSplProxySwapV2(instruction) => {
+                    platform_fee_spl_proxy_swap_v2_rows.push(platform_fee_spl_proxy_swap_v2_row::PlatformFeeSplProxySwapV2Row::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                OnchainLabsDexV1Instruction::ProxySwap(instruction) => {
+                    proxy_swap_rows.push(proxy_swap_row::ProxySwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::Swap(instruction) => {
+                    swap_rows.push(swap_row::SwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::SwapTobV3(instruction) => {
+                    swap_tob_v3_rows.push(swap_tob_v3_row::SwapTobV3Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::SwapTobV3Enhanced(instruction) => {
+                    swap_tob_v3_enhanced_rows.push(
+                        swap_tob_v3_enhanced_row::SwapTobV3EnhancedRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::SwapTobV3WithReceiver(instruction) => {
+                    swap_tob_v3_with_receiver_rows.push(
+                        swap_tob_v3_with_receiver_row::SwapTobV3WithReceiverRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::SwapV3(instruction) => {
+                    swap_v3_rows.push(swap_v3_row::SwapV3Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::WrapUnwrapV3(instruction) => {
+                    wrap_unwrap_v3_rows.push(wrap_unwrap_v3_row::WrapUnwrapV3Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV1Instruction::WrapUnwrapV3WithReceiver(instruction) => {
+                    wrap_unwrap_v3_with_receiver_rows.push(
+                        wrap_unwrap_v3_with_receiver_row::WrapUnwrapV3WithReceiverRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV1Instruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        claim_row::ClaimRow::upsert_many(&claim_rows, pool).await?;
+        commission_sol_proxy_swap_row::CommissionSolProxySwapRow::upsert_many(
+            &commission_sol_proxy_swap_rows,
+            pool,
+        )
+        .await?;
+        commission_sol_swap_row::CommissionSolSwapRow::upsert_many(&commission_sol_swap_rows, pool)
+            .await?;
+        commission_spl_proxy_swap_row::CommissionSplProxySwapRow::upsert_many(
+            &commission_spl_proxy_swap_rows,
+            pool,
+        )
+        .await?;
+        commission_spl_swap_row::CommissionSplSwapRow::upsert_many(&commission_spl_swap_rows, pool)
+            .await?;
+        commission_wrap_unwrap_row::CommissionWrapUnwrapRow::upsert_many(
+            &commission_wrap_unwrap_rows,
+            pool,
+        )
+        .await?;
+        create_token_account_row::CreateTokenAccountRow::upsert_many(
+            &create_token_account_rows,
+            pool,
+        )
+        .await?;
+        create_token_account_with_seed_row::CreateTokenAccountWithSeedRow::upsert_many(
+            &create_token_account_with_seed_rows,
+            pool,
+        )
+        .await?;
+        platform_fee_sol_proxy_swap_v2_row::PlatformFeeSolProxySwapV2Row::upsert_many(
+            &platform_fee_sol_proxy_swap_v2_rows,
+            pool,
+        )
+        .await?;
+        platform_fee_sol_wrap_unwrap_v2_row::PlatformFeeSolWrapUnwrapV2Row::upsert_many(
+            &platform_fee_sol_wrap_unwrap_v2_rows,
+            pool,
+        )
+        .await?;
+        platform_fee_spl_proxy_swap_v2_row::PlatformFeeSplProxySwapV2Row::upsert_many(
+            &platform_fee_spl_proxy_swap_v2_rows,
+            pool,
+        )
+        .await?;
+        proxy_swap_row::ProxySwapRow::upsert_many(&proxy_swap_rows, pool).await?;
+        swap_row::SwapRow::upsert_many(&swap_rows, pool).await?;
+        swap_tob_v3_row::SwapTobV3Row::upsert_many(&swap_tob_v3_rows, pool).await?;
+        swap_tob_v3_enhanced_row::SwapTobV3EnhancedRow::upsert_many(
+            &swap_tob_v3_enhanced_rows,
+            pool,
+        )
+        .await?;
+        swap_tob_v3_with_receiver_row::SwapTobV3WithReceiverRow::upsert_many(
+            &swap_tob_v3_with_receiver_rows,
+            pool,
+        )
+        .await?;
+        swap_v3_row::SwapV3Row::upsert_many(&swap_v3_rows, pool).await?;
+        wrap_unwrap_v3_row::WrapUnwrapV3Row::upsert_many(&wrap_unwrap_v3_rows, pool).await?;
+        wrap_unwrap_v3_with_receiver_row::WrapUnwrapV3WithReceiverRow::upsert_many(
+            &wrap_unwrap_v3_with_receiver_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
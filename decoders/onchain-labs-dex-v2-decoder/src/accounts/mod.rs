@@ -15,6 +15,10 @@ pub enum OnchainLabsDexV2Account {}
 impl<'a> carbon_core::account::AccountDecoder<'a> for OnchainLabsDexV2Decoder {
     type AccountType = OnchainLabsDexV2Account;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
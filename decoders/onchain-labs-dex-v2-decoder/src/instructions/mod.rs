@@ -50,6 +50,10 @@ pub enum OnchainLabsDexV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for OnchainLabsDexV2Decoder {
     type InstructionType = OnchainLabsDexV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -60,121 +64,55 @@ impl carbon_core::instruction::InstructionDecoder<'_> for OnchainLabsDexV2Decode
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = claim::Claim::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::Claim(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_token_account::CreateTokenAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::CreateTokenAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                create_token_account_with_seed::CreateTokenAccountWithSeed::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::CreateTokenAccountWithSeed(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = proxy_swap::ProxySwap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::ProxySwap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap::Swap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::Swap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob::SwapTob::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::SwapTob(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob_enhanced::SwapTobEnhanced::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::SwapTobEnhanced(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_tob_with_receiver::SwapTobWithReceiver::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::SwapTobWithReceiver(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_toc::SwapToc::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::SwapToc(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = swap_toc_v2::SwapTocV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::SwapTocV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = wrap_unwrap::WrapUnwrap::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::WrapUnwrap(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = wrap_unwrap_with_receiver::WrapUnwrapWithReceiver::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::WrapUnwrapWithReceiver(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[62, 198, 214, 193, 213, 159, 108, 210],
+                &[147, 241, 123, 100, 244, 132, 174, 118],
+                &[125, 191, 239, 140, 66, 8, 9, 228],
+                &[19, 44, 130, 148, 72, 56, 44, 238],
+                &[248, 198, 158, 145, 225, 117, 135, 200],
+                &[170, 41, 85, 177, 132, 80, 31, 53],
+                &[190, 156, 169, 176, 149, 154, 161, 108],
+                &[223, 170, 216, 234, 204, 6, 241, 25],
+                &[187, 201, 212, 51, 16, 155, 236, 60],
+                &[127, 214, 107, 189, 23, 90, 47, 104],
+                &[220, 101, 139, 249, 41, 190, 118, 199],
+                &[123, 25, 47, 134, 233, 167, 171, 170],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded =
+                match position {
+                    0 => claim::Claim::decode(data).map(OnchainLabsDexV2Instruction::Claim),
+                    1 => create_token_account::CreateTokenAccount::decode(data)
+                        .map(OnchainLabsDexV2Instruction::CreateTokenAccount),
+                    2 => create_token_account_with_seed::CreateTokenAccountWithSeed::decode(data)
+                        .map(OnchainLabsDexV2Instruction::CreateTokenAccountWithSeed),
+                    3 => proxy_swap::ProxySwap::decode(data)
+                        .map(OnchainLabsDexV2Instruction::ProxySwap),
+                    4 => swap::Swap::decode(data).map(OnchainLabsDexV2Instruction::Swap),
+                    5 => swap_tob::SwapTob::decode(data).map(OnchainLabsDexV2Instruction::SwapTob),
+                    6 => swap_tob_enhanced::SwapTobEnhanced::decode(data)
+                        .map(OnchainLabsDexV2Instruction::SwapTobEnhanced),
+                    7 => swap_tob_with_receiver::SwapTobWithReceiver::decode(data)
+                        .map(OnchainLabsDexV2Instruction::SwapTobWithReceiver),
+                    8 => swap_toc::SwapToc::decode(data).map(OnchainLabsDexV2Instruction::SwapToc),
+                    9 => swap_toc_v2::SwapTocV2::decode(data)
+                        .map(OnchainLabsDexV2Instruction::SwapTocV2),
+                    10 => wrap_unwrap::WrapUnwrap::decode(data)
+                        .map(OnchainLabsDexV2Instruction::WrapUnwrap),
+                    11 => wrap_unwrap_with_receiver::WrapUnwrapWithReceiver::decode(data)
+                        .map(OnchainLabsDexV2Instruction::WrapUnwrapWithReceiver),
+                    12 => cpi_event::CpiEvent::decode(data)
+                        .map(|decoded| OnchainLabsDexV2Instruction::CpiEvent(Box::new(decoded))),
+                    _ => None,
+                };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: OnchainLabsDexV2Instruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -331,4 +331,155 @@ impl carbon_core::postgres::operations::Upsert for OnchainLabsDexV2InstructionWi
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut claim_rows = Vec::new();
+        let mut create_token_account_rows = Vec::new();
+        let mut create_token_account_with_seed_rows = Vec::new();
+        let mut proxy_swap_rows = Vec::new();
+        let mut swap_rows = Vec::new();
+        let mut swap_tob_rows = Vec::new();
+        let mut swap_tob_enhanced_rows = Vec::new();
+        let mut swap_tob_with_receiver_rows = Vec::new();
+        let mut swap_toc_rows = Vec::new();
+        let mut swap_toc_v2_rows = Vec::new();
+        let mut wrap_unwrap_rows = Vec::new();
+        let mut wrap_unwrap_with_receiver_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for OnchainLabsDexV2InstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                OnchainLabsDexV2Instruction::Claim(instruction) => {
+                    claim_rows.push(claim_row::ClaimRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::CreateTokenAccount(instruction) => {
+                    create_token_account_rows.push(
+                        create_token_account_row::CreateTokenAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV2Instruction::CreateTokenAccountWithSeed(instruction) => {
+                    create_token_account_with_seed_rows.push(create_token_account_with_seed_row::CreateTokenAccountWithSeedRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                OnchainLabsDexV2Instruction::ProxySwap(instruction) => {
+                    proxy_swap_rows.push(proxy_swap_row::ProxySwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::Swap(instruction) => {
+                    swap_rows.push(swap_row::SwapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::SwapTob(instruction) => {
+                    swap_tob_rows.push(swap_tob_row::SwapTobRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::SwapTobEnhanced(instruction) => {
+                    swap_tob_enhanced_rows.push(
+                        swap_tob_enhanced_row::SwapTobEnhancedRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV2Instruction::SwapTobWithReceiver(instruction) => {
+                    swap_tob_with_receiver_rows.push(
+                        swap_tob_with_receiver_row::SwapTobWithReceiverRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV2Instruction::SwapToc(instruction) => {
+                    swap_toc_rows.push(swap_toc_row::SwapTocRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::SwapTocV2(instruction) => {
+                    swap_toc_v2_rows.push(swap_toc_v2_row::SwapTocV2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::WrapUnwrap(instruction) => {
+                    wrap_unwrap_rows.push(wrap_unwrap_row::WrapUnwrapRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                OnchainLabsDexV2Instruction::WrapUnwrapWithReceiver(instruction) => {
+                    wrap_unwrap_with_receiver_rows.push(
+                        wrap_unwrap_with_receiver_row::WrapUnwrapWithReceiverRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                OnchainLabsDexV2Instruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        claim_row::ClaimRow::upsert_many(&claim_rows, pool).await?;
+        create_token_account_row::CreateTokenAccountRow::upsert_many(
+            &create_token_account_rows,
+            pool,
+        )
+        .await?;
+        create_token_account_with_seed_row::CreateTokenAccountWithSeedRow::upsert_many(
+            &create_token_account_with_seed_rows,
+            pool,
+        )
+        .await?;
+        proxy_swap_row::ProxySwapRow::upsert_many(&proxy_swap_rows, pool).await?;
+        swap_row::SwapRow::upsert_many(&swap_rows, pool).await?;
+        swap_tob_row::SwapTobRow::upsert_many(&swap_tob_rows, pool).await?;
+        swap_tob_enhanced_row::SwapTobEnhancedRow::upsert_many(&swap_tob_enhanced_rows, pool)
+            .await?;
+        swap_tob_with_receiver_row::SwapTobWithReceiverRow::upsert_many(
+            &swap_tob_with_receiver_rows,
+            pool,
+        )
+        .await?;
+        swap_toc_row::SwapTocRow::upsert_many(&swap_toc_rows, pool).await?;
+        swap_toc_v2_row::SwapTocV2Row::upsert_many(&swap_toc_v2_rows, pool).await?;
+        wrap_unwrap_row::WrapUnwrapRow::upsert_many(&wrap_unwrap_rows, pool).await?;
+        wrap_unwrap_with_receiver_row::WrapUnwrapWithReceiverRow::upsert_many(
+            &wrap_unwrap_with_receiver_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -1,8 +1,4 @@
-use {
-    super::OpenbookV2Decoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::OpenbookV2Decoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod book_side;
 pub mod event_heap;
 pub mod market;
@@ -21,6 +17,11 @@ pub enum OpenbookV2Account {
 
 impl AccountDecoder<'_> for OpenbookV2Decoder {
     type AccountType = OpenbookV2Account;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -29,71 +30,13 @@ impl AccountDecoder<'_> for OpenbookV2Decoder {
             return None;
         }
 
-        if let Some(decoded_account) = market::Market::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::Market(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            open_orders_account::OpenOrdersAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::OpenOrdersAccount(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            open_orders_indexer::OpenOrdersIndexer::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::OpenOrdersIndexer(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = stub_oracle::StubOracle::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::StubOracle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = book_side::BookSide::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::BookSide(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = event_heap::EventHeap::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: OpenbookV2Account::EventHeap(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            OpenbookV2Account::Market => market::Market,
+            OpenbookV2Account::OpenOrdersAccount => open_orders_account::OpenOrdersAccount,
+            OpenbookV2Account::OpenOrdersIndexer => open_orders_indexer::OpenOrdersIndexer,
+            OpenbookV2Account::StubOracle => stub_oracle::StubOracle,
+            OpenbookV2Account::BookSide => book_side::BookSide,
+            OpenbookV2Account::EventHeap => event_heap::EventHeap,
+        )
     }
 }
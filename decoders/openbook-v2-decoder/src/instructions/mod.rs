@@ -85,6 +85,10 @@ pub enum OpenbookV2Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for OpenbookV2Decoder {
     type InstructionType = OpenbookV2Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use super::OrcaWhirlpoolDecoder;
 pub mod adaptive_fee_tier;
@@ -32,146 +31,28 @@ pub enum WhirlpoolAccount {
 
 impl AccountDecoder<'_> for OrcaWhirlpoolDecoder {
     type AccountType = WhirlpoolAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
     ) -> Option<carbon_core::account::DecodedAccount<Self::AccountType>> {
-        if let Some(decoded_account) =
-            adaptive_fee_tier::AdaptiveFeeTier::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::AdaptiveFeeTier(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            whirlpools_config::WhirlpoolsConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::WhirlpoolsConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            whirlpools_config_extension::WhirlpoolsConfigExtension::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::WhirlpoolsConfigExtension(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = fee_tier::FeeTier::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::FeeTier(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = lock_config::LockConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::LockConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = oracle::Oracle::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::Oracle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = position::Position::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::Position(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            position_bundle::PositionBundle::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::PositionBundle(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            fixed_tick_array::FixedTickArray::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::FixedTickArray(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            dynamic_tick_array::DynamicTickArray::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::DynamicTickArray(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = token_badge::TokenBadge::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::TokenBadge(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = whirlpool::Whirlpool::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WhirlpoolAccount::Whirlpool(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            WhirlpoolAccount::AdaptiveFeeTier => adaptive_fee_tier::AdaptiveFeeTier,
+            WhirlpoolAccount::WhirlpoolsConfig => whirlpools_config::WhirlpoolsConfig,
+            WhirlpoolAccount::WhirlpoolsConfigExtension => whirlpools_config_extension::WhirlpoolsConfigExtension,
+            WhirlpoolAccount::FeeTier => fee_tier::FeeTier,
+            WhirlpoolAccount::LockConfig => lock_config::LockConfig,
+            WhirlpoolAccount::Oracle => oracle::Oracle,
+            WhirlpoolAccount::Position => position::Position,
+            WhirlpoolAccount::PositionBundle => position_bundle::PositionBundle,
+            WhirlpoolAccount::FixedTickArray => fixed_tick_array::FixedTickArray,
+            WhirlpoolAccount::DynamicTickArray => dynamic_tick_array::DynamicTickArray,
+            WhirlpoolAccount::TokenBadge => token_badge::TokenBadge,
+            WhirlpoolAccount::Whirlpool => whirlpool::Whirlpool,
+        )
     }
 }
@@ -154,6 +154,10 @@ pub enum WhirlpoolInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for OrcaWhirlpoolDecoder {
     type InstructionType = WhirlpoolInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use super::PancakeSwapDecoder;
 pub mod amm_config;
@@ -29,130 +28,26 @@ pub enum PancakeSwapAccount {
 
 impl AccountDecoder<'_> for PancakeSwapDecoder {
     type AccountType = PancakeSwapAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
     ) -> Option<carbon_core::account::DecodedAccount<Self::AccountType>> {
-        if let Some(decoded_account) = amm_config::AmmConfig::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::AmmConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            observation_state::ObservationState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::ObservationState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            operation_state::OperationState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::OperationState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            permissionless_farm_switch::PermissionlessFarmSwitch::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::PermissionlessFarmSwitch(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            personal_position_state::PersonalPositionState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::PersonalPositionState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool_state::PoolState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::PoolState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_position_state::ProtocolPositionState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::ProtocolPositionState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            support_mint_associated::SupportMintAssociated::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::SupportMintAssociated(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            tick_array_bitmap_extension::TickArrayBitmapExtension::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::TickArrayBitmapExtension(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            tick_array_state::TickArrayState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PancakeSwapAccount::TickArrayState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            PancakeSwapAccount::AmmConfig => amm_config::AmmConfig,
+            PancakeSwapAccount::ObservationState => observation_state::ObservationState,
+            PancakeSwapAccount::OperationState => operation_state::OperationState,
+            PancakeSwapAccount::PermissionlessFarmSwitch => permissionless_farm_switch::PermissionlessFarmSwitch,
+            PancakeSwapAccount::PersonalPositionState => personal_position_state::PersonalPositionState,
+            PancakeSwapAccount::PoolState => pool_state::PoolState,
+            PancakeSwapAccount::ProtocolPositionState => protocol_position_state::ProtocolPositionState,
+            PancakeSwapAccount::SupportMintAssociated => support_mint_associated::SupportMintAssociated,
+            PancakeSwapAccount::TickArrayBitmapExtension => tick_array_bitmap_extension::TickArrayBitmapExtension,
+            PancakeSwapAccount::TickArrayState => tick_array_state::TickArrayState,
+        )
     }
 }
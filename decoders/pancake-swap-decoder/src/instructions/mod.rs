@@ -96,6 +96,10 @@ pub enum AmmV3Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PancakeSwapDecoder {
     type InstructionType = AmmV3Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
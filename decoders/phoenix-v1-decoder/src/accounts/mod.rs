@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -14,6 +13,11 @@ pub enum PhoenixAccount {
 
 impl AccountDecoder<'_> for PhoenixDecoder {
     type AccountType = PhoenixAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,28 +26,9 @@ impl AccountDecoder<'_> for PhoenixDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            market_header::MarketHeader::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PhoenixAccount::MarketHeader(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = seat::Seat::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: PhoenixAccount::Seat(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            PhoenixAccount::MarketHeader => market_header::MarketHeader,
+            PhoenixAccount::Seat => seat::Seat,
+        )
     }
 }
@@ -78,6 +78,10 @@ pub enum PhoenixInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PhoenixDecoder {
     type InstructionType = PhoenixInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -32,6 +32,10 @@ pub enum PumpFeesAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for PumpFeesDecoder {
     type AccountType = PumpFeesAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -42,77 +46,41 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for PumpFeesDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = bonding_curve::BondingCurve::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::BondingCurve(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fee_config::FeeConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::FeeConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fee_program_global::FeeProgramGlobal::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::FeeProgramGlobal(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = global::Global::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::Global(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pool::Pool::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::Pool(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sharing_config::SharingConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpFeesAccount::SharingConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = social_fee_pda::SocialFeePda::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[23, 183, 248, 55, 96, 216, 172, 96],
+                &[143, 52, 146, 187, 219, 123, 76, 155],
+                &[162, 165, 245, 49, 29, 37, 55, 242],
+                &[167, 232, 232, 177, 200, 108, 114, 127],
+                &[241, 154, 109, 4, 17, 177, 109, 188],
+                &[216, 74, 9, 0, 56, 140, 93, 75],
+                &[139, 96, 53, 17, 42, 169, 206, 150],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => bonding_curve::BondingCurve::decode(data)
+                    .map(|decoded| PumpFeesAccount::BondingCurve(Box::new(decoded))),
+                1 => fee_config::FeeConfig::decode(data)
+                    .map(|decoded| PumpFeesAccount::FeeConfig(Box::new(decoded))),
+                2 => fee_program_global::FeeProgramGlobal::decode(data)
+                    .map(|decoded| PumpFeesAccount::FeeProgramGlobal(Box::new(decoded))),
+                3 => global::Global::decode(data)
+                    .map(|decoded| PumpFeesAccount::Global(Box::new(decoded))),
+                4 => {
+                    pool::Pool::decode(data).map(|decoded| PumpFeesAccount::Pool(Box::new(decoded)))
+                }
+                5 => sharing_config::SharingConfig::decode(data)
+                    .map(|decoded| PumpFeesAccount::SharingConfig(Box::new(decoded))),
+                6 => social_fee_pda::SocialFeePda::decode(data)
+                    .map(|decoded| PumpFeesAccount::SocialFeePda(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: PumpFeesAccount::SocialFeePda(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
@@ -155,6 +155,65 @@ impl carbon_core::postgres::operations::Upsert for BondingCurveRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(10)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO bonding_curve_account (
+                "virtual_token_reserves",
+                "virtual_sol_reserves",
+                "real_token_reserves",
+                "real_sol_reserves",
+                "token_total_supply",
+                "complete",
+                "creator",
+                "is_mayhem_mode",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.virtual_token_reserves)
+                    .push_bind(&row.virtual_sol_reserves)
+                    .push_bind(&row.real_token_reserves)
+                    .push_bind(&row.real_sol_reserves)
+                    .push_bind(&row.token_total_supply)
+                    .push_bind(row.complete)
+                    .push_bind(row.creator)
+                    .push_bind(row.is_mayhem_mode)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "virtual_token_reserves" = EXCLUDED."virtual_token_reserves",
+                "virtual_sol_reserves" = EXCLUDED."virtual_sol_reserves",
+                "real_token_reserves" = EXCLUDED."real_token_reserves",
+                "real_sol_reserves" = EXCLUDED."real_sol_reserves",
+                "token_total_supply" = EXCLUDED."token_total_supply",
+                "complete" = EXCLUDED."complete",
+                "creator" = EXCLUDED."creator",
+                "is_mayhem_mode" = EXCLUDED."is_mayhem_mode",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -168,6 +168,59 @@ impl carbon_core::postgres::operations::Upsert for FeeProgramGlobalRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(8)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO fee_program_global_account (
+                "bump",
+                "authority",
+                "disable_flags",
+                "social_claim_authority",
+                "claim_rate_limit",
+                "reserved",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.bump)
+                    .push_bind(row.authority)
+                    .push_bind(row.disable_flags)
+                    .push_bind(row.social_claim_authority)
+                    .push_bind(&row.claim_rate_limit)
+                    .push_bind(&row.reserved)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "bump" = EXCLUDED."bump",
+                "authority" = EXCLUDED."authority",
+                "disable_flags" = EXCLUDED."disable_flags",
+                "social_claim_authority" = EXCLUDED."social_claim_authority",
+                "claim_rate_limit" = EXCLUDED."claim_rate_limit",
+                "reserved" = EXCLUDED."reserved",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -292,6 +292,101 @@ impl carbon_core::postgres::operations::Upsert for GlobalRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(22)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO global_account (
+                "initialized",
+                "authority",
+                "fee_recipient",
+                "initial_virtual_token_reserves",
+                "initial_virtual_sol_reserves",
+                "initial_real_token_reserves",
+                "token_total_supply",
+                "fee_basis_points",
+                "withdraw_authority",
+                "enable_migrate",
+                "pool_migration_fee",
+                "creator_fee_basis_points",
+                "fee_recipients",
+                "set_creator_authority",
+                "admin_set_creator_authority",
+                "create_v2_enabled",
+                "whitelist_pda",
+                "reserved_fee_recipient",
+                "mayhem_mode_enabled",
+                "reserved_fee_recipients",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.initialized)
+                    .push_bind(row.authority)
+                    .push_bind(row.fee_recipient)
+                    .push_bind(&row.initial_virtual_token_reserves)
+                    .push_bind(&row.initial_virtual_sol_reserves)
+                    .push_bind(&row.initial_real_token_reserves)
+                    .push_bind(&row.token_total_supply)
+                    .push_bind(&row.fee_basis_points)
+                    .push_bind(row.withdraw_authority)
+                    .push_bind(row.enable_migrate)
+                    .push_bind(&row.pool_migration_fee)
+                    .push_bind(&row.creator_fee_basis_points)
+                    .push_bind(&row.fee_recipients)
+                    .push_bind(row.set_creator_authority)
+                    .push_bind(row.admin_set_creator_authority)
+                    .push_bind(row.create_v2_enabled)
+                    .push_bind(row.whitelist_pda)
+                    .push_bind(row.reserved_fee_recipient)
+                    .push_bind(row.mayhem_mode_enabled)
+                    .push_bind(&row.reserved_fee_recipients)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "initialized" = EXCLUDED."initialized",
+                "authority" = EXCLUDED."authority",
+                "fee_recipient" = EXCLUDED."fee_recipient",
+                "initial_virtual_token_reserves" = EXCLUDED."initial_virtual_token_reserves",
+                "initial_virtual_sol_reserves" = EXCLUDED."initial_virtual_sol_reserves",
+                "initial_real_token_reserves" = EXCLUDED."initial_real_token_reserves",
+                "token_total_supply" = EXCLUDED."token_total_supply",
+                "fee_basis_points" = EXCLUDED."fee_basis_points",
+                "withdraw_authority" = EXCLUDED."withdraw_authority",
+                "enable_migrate" = EXCLUDED."enable_migrate",
+                "pool_migration_fee" = EXCLUDED."pool_migration_fee",
+                "creator_fee_basis_points" = EXCLUDED."creator_fee_basis_points",
+                "fee_recipients" = EXCLUDED."fee_recipients",
+                "set_creator_authority" = EXCLUDED."set_creator_authority",
+                "admin_set_creator_authority" = EXCLUDED."admin_set_creator_authority",
+                "create_v2_enabled" = EXCLUDED."create_v2_enabled",
+                "whitelist_pda" = EXCLUDED."whitelist_pda",
+                "reserved_fee_recipient" = EXCLUDED."reserved_fee_recipient",
+                "mayhem_mode_enabled" = EXCLUDED."mayhem_mode_enabled",
+                "reserved_fee_recipients" = EXCLUDED."reserved_fee_recipients",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
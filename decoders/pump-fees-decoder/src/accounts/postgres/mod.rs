@@ -171,4 +171,76 @@ impl carbon_core::postgres::operations::Upsert for PumpFeesAccountWithMetadata {
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut bonding_curve_rows = Vec::new();
+        let mut fee_config_rows = Vec::new();
+        let mut fee_program_global_rows = Vec::new();
+        let mut global_rows = Vec::new();
+        let mut pool_rows = Vec::new();
+        let mut sharing_config_rows = Vec::new();
+        let mut social_fee_pda_rows = Vec::new();
+
+        for PumpFeesAccountWithMetadata(account, metadata) in rows {
+            match account {
+                PumpFeesAccount::BondingCurve(account) => {
+                    bonding_curve_rows.push(bonding_curve_row::BondingCurveRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpFeesAccount::FeeConfig(account) => {
+                    fee_config_rows.push(fee_config_row::FeeConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpFeesAccount::FeeProgramGlobal(account) => {
+                    fee_program_global_rows.push(
+                        fee_program_global_row::FeeProgramGlobalRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                PumpFeesAccount::Global(account) => {
+                    global_rows.push(global_row::GlobalRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpFeesAccount::Pool(account) => {
+                    pool_rows.push(pool_row::PoolRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpFeesAccount::SharingConfig(account) => {
+                    sharing_config_rows.push(sharing_config_row::SharingConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpFeesAccount::SocialFeePda(account) => {
+                    social_fee_pda_rows.push(social_fee_pda_row::SocialFeePdaRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        bonding_curve_row::BondingCurveRow::upsert_many(&bonding_curve_rows, pool).await?;
+        fee_config_row::FeeConfigRow::upsert_many(&fee_config_rows, pool).await?;
+        fee_program_global_row::FeeProgramGlobalRow::upsert_many(&fee_program_global_rows, pool)
+            .await?;
+        global_row::GlobalRow::upsert_many(&global_rows, pool).await?;
+        pool_row::PoolRow::upsert_many(&pool_rows, pool).await?;
+        sharing_config_row::SharingConfigRow::upsert_many(&sharing_config_rows, pool).await?;
+        social_fee_pda_row::SocialFeePdaRow::upsert_many(&social_fee_pda_rows, pool).await?;
+        Ok(())
+    }
 }
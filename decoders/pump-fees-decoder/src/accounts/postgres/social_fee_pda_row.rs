@@ -178,6 +178,62 @@ impl carbon_core::postgres::operations::Upsert for SocialFeePdaRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(9)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO social_fee_pda_account (
+                "bump",
+                "version",
+                "user_id",
+                "platform",
+                "total_claimed",
+                "last_claimed",
+                "reserved",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.bump)
+                    .push_bind(row.version)
+                    .push_bind(&row.user_id)
+                    .push_bind(row.platform)
+                    .push_bind(&row.total_claimed)
+                    .push_bind(&row.last_claimed)
+                    .push_bind(&row.reserved)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "bump" = EXCLUDED."bump",
+                "version" = EXCLUDED."version",
+                "user_id" = EXCLUDED."user_id",
+                "platform" = EXCLUDED."platform",
+                "total_claimed" = EXCLUDED."total_claimed",
+                "last_claimed" = EXCLUDED."last_claimed",
+                "reserved" = EXCLUDED."reserved",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -74,6 +74,10 @@ pub enum PumpFeesInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PumpFeesDecoder {
     type InstructionType = PumpFeesInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -84,171 +88,72 @@ impl carbon_core::instruction::InstructionDecoder<'_> for PumpFeesDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = claim_social_fee_pda::ClaimSocialFeePda::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::ClaimSocialFeePda(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_fee_sharing_config::CreateFeeSharingConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::CreateFeeSharingConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_social_fee_pda::CreateSocialFeePda::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::CreateSocialFeePda(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = get_fees::GetFees::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::GetFees(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_fee_config::InitializeFeeConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::InitializeFeeConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_fee_program_global::InitializeFeeProgramGlobal::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::InitializeFeeProgramGlobal(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = reset_fee_sharing_config::ResetFeeSharingConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::ResetFeeSharingConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                revoke_fee_sharing_authority::RevokeFeeSharingAuthority::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::RevokeFeeSharingAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_authority::SetAuthority::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::SetAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_claim_rate_limit::SetClaimRateLimit::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::SetClaimRateLimit(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_disable_flags::SetDisableFlags::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::SetDisableFlags(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_social_claim_authority::SetSocialClaimAuthority::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::SetSocialClaimAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                transfer_fee_sharing_authority::TransferFeeSharingAuthority::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::TransferFeeSharingAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_admin::UpdateAdmin::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::UpdateAdmin(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_fee_config::UpdateFeeConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::UpdateFeeConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_fee_shares::UpdateFeeShares::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::UpdateFeeShares(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = upsert_fee_tiers::UpsertFeeTiers::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpFeesInstruction::UpsertFeeTiers(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[225, 21, 251, 133, 161, 30, 199, 226],
+                &[195, 78, 86, 76, 111, 52, 251, 213],
+                &[144, 224, 59, 211, 78, 248, 202, 220],
+                &[231, 37, 126, 85, 207, 91, 63, 52],
+                &[62, 162, 20, 133, 121, 65, 145, 27],
+                &[35, 215, 130, 84, 233, 56, 124, 167],
+                &[10, 2, 182, 95, 16, 127, 129, 186],
+                &[18, 233, 158, 39, 185, 207, 58, 104],
+                &[133, 250, 37, 21, 110, 163, 26, 121],
+                &[185, 211, 159, 174, 212, 49, 88, 4],
+                &[194, 217, 112, 35, 114, 222, 51, 190],
+                &[147, 54, 184, 154, 136, 237, 185, 153],
+                &[202, 10, 75, 200, 164, 34, 210, 96],
+                &[161, 176, 40, 213, 60, 184, 179, 228],
+                &[104, 184, 103, 242, 88, 151, 107, 20],
+                &[189, 13, 136, 99, 187, 164, 237, 35],
+                &[227, 23, 150, 12, 77, 86, 94, 4],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => claim_social_fee_pda::ClaimSocialFeePda::decode(data)
+                    .map(PumpFeesInstruction::ClaimSocialFeePda),
+                1 => create_fee_sharing_config::CreateFeeSharingConfig::decode(data)
+                    .map(PumpFeesInstruction::CreateFeeSharingConfig),
+                2 => create_social_fee_pda::CreateSocialFeePda::decode(data)
+                    .map(PumpFeesInstruction::CreateSocialFeePda),
+                3 => get_fees::GetFees::decode(data).map(PumpFeesInstruction::GetFees),
+                4 => initialize_fee_config::InitializeFeeConfig::decode(data)
+                    .map(PumpFeesInstruction::InitializeFeeConfig),
+                5 => initialize_fee_program_global::InitializeFeeProgramGlobal::decode(data)
+                    .map(PumpFeesInstruction::InitializeFeeProgramGlobal),
+                6 => reset_fee_sharing_config::ResetFeeSharingConfig::decode(data)
+                    .map(PumpFeesInstruction::ResetFeeSharingConfig),
+                7 => revoke_fee_sharing_authority::RevokeFeeSharingAuthority::decode(data)
+                    .map(PumpFeesInstruction::RevokeFeeSharingAuthority),
+                8 => {
+                    set_authority::SetAuthority::decode(data).map(PumpFeesInstruction::SetAuthority)
+                }
+                9 => set_claim_rate_limit::SetClaimRateLimit::decode(data)
+                    .map(PumpFeesInstruction::SetClaimRateLimit),
+                10 => set_disable_flags::SetDisableFlags::decode(data)
+                    .map(PumpFeesInstruction::SetDisableFlags),
+                11 => set_social_claim_authority::SetSocialClaimAuthority::decode(data)
+                    .map(PumpFeesInstruction::SetSocialClaimAuthority),
+                12 => transfer_fee_sharing_authority::TransferFeeSharingAuthority::decode(data)
+                    .map(PumpFeesInstruction::TransferFeeSharingAuthority),
+                13 => update_admin::UpdateAdmin::decode(data).map(PumpFeesInstruction::UpdateAdmin),
+                14 => update_fee_config::UpdateFeeConfig::decode(data)
+                    .map(PumpFeesInstruction::UpdateFeeConfig),
+                15 => update_fee_shares::UpdateFeeShares::decode(data)
+                    .map(PumpFeesInstruction::UpdateFeeShares),
+                16 => upsert_fee_tiers::UpsertFeeTiers::decode(data)
+                    .map(PumpFeesInstruction::UpsertFeeTiers),
+                17 => cpi_event::CpiEvent::decode(data)
+                    .map(|decoded| PumpFeesInstruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: PumpFeesInstruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -448,4 +448,238 @@ impl carbon_core::postgres::operations::Upsert for PumpFeesInstructionWithMetada
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut claim_social_fee_pda_rows = Vec::new();
+        let mut create_fee_sharing_config_rows = Vec::new();
+        let mut create_social_fee_pda_rows = Vec::new();
+        let mut get_fees_rows = Vec::new();
+        let mut initialize_fee_config_rows = Vec::new();
+        let mut initialize_fee_program_global_rows = Vec::new();
+        let mut reset_fee_sharing_config_rows = Vec::new();
+        let mut revoke_fee_sharing_authority_rows = Vec::new();
+        let mut set_authority_rows = Vec::new();
+        let mut set_claim_rate_limit_rows = Vec::new();
+        let mut set_disable_flags_rows = Vec::new();
+        let mut set_social_claim_authority_rows = Vec::new();
+        let mut transfer_fee_sharing_authority_rows = Vec::new();
+        let mut update_admin_rows = Vec::new();
+        let mut update_fee_config_rows = Vec::new();
+        let mut update_fee_shares_rows = Vec::new();
+        let mut upsert_fee_tiers_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for PumpFeesInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                PumpFeesInstruction::ClaimSocialFeePda(instruction) => {
+                    claim_social_fee_pda_rows.push(
+                        claim_social_fee_pda_row::ClaimSocialFeePdaRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::CreateFeeSharingConfig(instruction) => {
+                    create_fee_sharing_config_rows.push(
+                        create_fee_sharing_config_row::CreateFeeSharingConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::CreateSocialFeePda(instruction) => {
+                    create_social_fee_pda_rows.push(
+                        create_social_fee_pda_row::CreateSocialFeePdaRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::GetFees(instruction) => {
+                    get_fees_rows.push(get_fees_row::GetFeesRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpFeesInstruction::InitializeFeeConfig(instruction) => {
+                    initialize_fee_config_rows.push(
+                        initialize_fee_config_row::InitializeFeeConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::InitializeFeeProgramGlobal(instruction) => {
+                    initialize_fee_program_global_rows.push(initialize_fee_program_global_row::InitializeFeeProgramGlobalRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpFeesInstruction::ResetFeeSharingConfig(instruction) => {
+                    reset_fee_sharing_config_rows.push(
+                        reset_fee_sharing_config_row::ResetFeeSharingConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::RevokeFeeSharingAuthority(instruction) => {
+                    revoke_fee_sharing_authority_rows.push(
+                        revoke_fee_sharing_authority_row::RevokeFeeSharingAuthorityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::SetAuthority(instruction) => {
+                    set_authority_rows.push(set_authority_row::SetAuthorityRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpFeesInstruction::SetClaimRateLimit(instruction) => {
+                    set_claim_rate_limit_rows.push(
+                        set_claim_rate_limit_row::SetClaimRateLimitRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::SetDisableFlags(instruction) => {
+                    set_disable_flags_rows.push(
+                        set_disable_flags_row::SetDisableFlagsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::SetSocialClaimAuthority(instruction) => {
+                    set_social_claim_authority_rows.push(
+                        set_social_claim_authority_row::SetSocialClaimAuthorityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::TransferFeeSharingAuthority(instruction) => {
+                    transfer_fee_sharing_authority_rows.push(transfer_fee_sharing_authority_row::TransferFeeSharingAuthorityRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpFeesInstruction::UpdateAdmin(instruction) => {
+                    update_admin_rows.push(update_admin_row::UpdateAdminRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpFeesInstruction::UpdateFeeConfig(instruction) => {
+                    update_fee_config_rows.push(
+                        update_fee_config_row::UpdateFeeConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::UpdateFeeShares(instruction) => {
+                    update_fee_shares_rows.push(
+                        update_fee_shares_row::UpdateFeeSharesRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::UpsertFeeTiers(instruction) => {
+                    upsert_fee_tiers_rows.push(
+                        upsert_fee_tiers_row::UpsertFeeTiersRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpFeesInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        claim_social_fee_pda_row::ClaimSocialFeePdaRow::upsert_many(
+            &claim_social_fee_pda_rows,
+            pool,
+        )
+        .await?;
+        create_fee_sharing_config_row::CreateFeeSharingConfigRow::upsert_many(
+            &create_fee_sharing_config_rows,
+            pool,
+        )
+        .await?;
+        create_social_fee_pda_row::CreateSocialFeePdaRow::upsert_many(
+            &create_social_fee_pda_rows,
+            pool,
+        )
+        .await?;
+        get_fees_row::GetFeesRow::upsert_many(&get_fees_rows, pool).await?;
+        initialize_fee_config_row::InitializeFeeConfigRow::upsert_many(
+            &initialize_fee_config_rows,
+            pool,
+        )
+        .await?;
+        initialize_fee_program_global_row::InitializeFeeProgramGlobalRow::upsert_many(
+            &initialize_fee_program_global_rows,
+            pool,
+        )
+        .await?;
+        reset_fee_sharing_config_row::ResetFeeSharingConfigRow::upsert_many(
+            &reset_fee_sharing_config_rows,
+            pool,
+        )
+        .await?;
+        revoke_fee_sharing_authority_row::RevokeFeeSharingAuthorityRow::upsert_many(
+            &revoke_fee_sharing_authority_rows,
+            pool,
+        )
+        .await?;
+        set_authority_row::SetAuthorityRow::upsert_many(&set_authority_rows, pool).await?;
+        set_claim_rate_limit_row::SetClaimRateLimitRow::upsert_many(
+            &set_claim_rate_limit_rows,
+            pool,
+        )
+        .await?;
+        set_disable_flags_row::SetDisableFlagsRow::upsert_many(&set_disable_flags_rows, pool)
+            .await?;
+        set_social_claim_authority_row::SetSocialClaimAuthorityRow::upsert_many(
+            &set_social_claim_authority_rows,
+            pool,
+        )
+        .await?;
+        transfer_fee_sharing_authority_row::TransferFeeSharingAuthorityRow::upsert_many(
+            &transfer_fee_sharing_authority_rows,
+            pool,
+        )
+        .await?;
+        update_admin_row::UpdateAdminRow::upsert_many(&update_admin_rows, pool).await?;
+        update_fee_config_row::UpdateFeeConfigRow::upsert_many(&update_fee_config_rows, pool)
+            .await?;
+        update_fee_shares_row::UpdateFeeSharesRow::upsert_many(&update_fee_shares_rows, pool)
+            .await?;
+        upsert_fee_tiers_row::UpsertFeeTiersRow::upsert_many(&upsert_fee_tiers_rows, pool).await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -32,6 +32,10 @@ pub enum PumpSwapAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for PumpSwapDecoder {
     type AccountType = PumpSwapAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -42,78 +46,41 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for PumpSwapDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = bonding_curve::BondingCurve::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::BondingCurve(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fee_config::FeeConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::FeeConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = global_config::GlobalConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::GlobalConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = global_volume_accumulator::GlobalVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::GlobalVolumeAccumulator(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pool::Pool::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::Pool(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sharing_config::SharingConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpSwapAccount::SharingConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = user_volume_accumulator::UserVolumeAccumulator::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[23, 183, 248, 55, 96, 216, 172, 96],
+                &[143, 52, 146, 187, 219, 123, 76, 155],
+                &[149, 8, 156, 202, 160, 252, 176, 217],
+                &[202, 42, 246, 43, 142, 190, 30, 255],
+                &[241, 154, 109, 4, 17, 177, 109, 188],
+                &[216, 74, 9, 0, 56, 140, 93, 75],
+                &[86, 255, 112, 14, 102, 53, 154, 250],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => bonding_curve::BondingCurve::decode(data)
+                    .map(|decoded| PumpSwapAccount::BondingCurve(Box::new(decoded))),
+                1 => fee_config::FeeConfig::decode(data)
+                    .map(|decoded| PumpSwapAccount::FeeConfig(Box::new(decoded))),
+                2 => global_config::GlobalConfig::decode(data)
+                    .map(|decoded| PumpSwapAccount::GlobalConfig(Box::new(decoded))),
+                3 => global_volume_accumulator::GlobalVolumeAccumulator::decode(data)
+                    .map(|decoded| PumpSwapAccount::GlobalVolumeAccumulator(Box::new(decoded))),
+                4 => {
+                    pool::Pool::decode(data).map(|decoded| PumpSwapAccount::Pool(Box::new(decoded)))
+                }
+                5 => sharing_config::SharingConfig::decode(data)
+                    .map(|decoded| PumpSwapAccount::SharingConfig(Box::new(decoded))),
+                6 => user_volume_accumulator::UserVolumeAccumulator::decode(data)
+                    .map(|decoded| PumpSwapAccount::UserVolumeAccumulator(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: PumpSwapAccount::UserVolumeAccumulator(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
@@ -234,6 +234,77 @@ impl carbon_core::postgres::operations::Upsert for GlobalConfigRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(14)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO global_config_account (
+                "admin",
+                "lp_fee_basis_points",
+                "protocol_fee_basis_points",
+                "disable_flags",
+                "protocol_fee_recipients",
+                "coin_creator_fee_basis_points",
+                "admin_set_coin_creator_authority",
+                "whitelist_pda",
+                "reserved_fee_recipient",
+                "mayhem_mode_enabled",
+                "reserved_fee_recipients",
+                "is_cashback_enabled",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.admin)
+                    .push_bind(&row.lp_fee_basis_points)
+                    .push_bind(&row.protocol_fee_basis_points)
+                    .push_bind(row.disable_flags)
+                    .push_bind(&row.protocol_fee_recipients)
+                    .push_bind(&row.coin_creator_fee_basis_points)
+                    .push_bind(row.admin_set_coin_creator_authority)
+                    .push_bind(row.whitelist_pda)
+                    .push_bind(row.reserved_fee_recipient)
+                    .push_bind(row.mayhem_mode_enabled)
+                    .push_bind(&row.reserved_fee_recipients)
+                    .push_bind(row.is_cashback_enabled)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "admin" = EXCLUDED."admin",
+                "lp_fee_basis_points" = EXCLUDED."lp_fee_basis_points",
+                "protocol_fee_basis_points" = EXCLUDED."protocol_fee_basis_points",
+                "disable_flags" = EXCLUDED."disable_flags",
+                "protocol_fee_recipients" = EXCLUDED."protocol_fee_recipients",
+                "coin_creator_fee_basis_points" = EXCLUDED."coin_creator_fee_basis_points",
+                "admin_set_coin_creator_authority" = EXCLUDED."admin_set_coin_creator_authority",
+                "whitelist_pda" = EXCLUDED."whitelist_pda",
+                "reserved_fee_recipient" = EXCLUDED."reserved_fee_recipient",
+                "mayhem_mode_enabled" = EXCLUDED."mayhem_mode_enabled",
+                "reserved_fee_recipients" = EXCLUDED."reserved_fee_recipients",
+                "is_cashback_enabled" = EXCLUDED."is_cashback_enabled",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
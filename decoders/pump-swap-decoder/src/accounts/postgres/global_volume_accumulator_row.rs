@@ -179,6 +179,59 @@ impl carbon_core::postgres::operations::Upsert for GlobalVolumeAccumulatorRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(8)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO global_volume_accumulator_account (
+                "start_time",
+                "end_time",
+                "seconds_in_a_day",
+                "mint",
+                "total_token_supply",
+                "sol_volumes",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.start_time)
+                    .push_bind(row.end_time)
+                    .push_bind(row.seconds_in_a_day)
+                    .push_bind(row.mint)
+                    .push_bind(&row.total_token_supply)
+                    .push_bind(&row.sol_volumes)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "start_time" = EXCLUDED."start_time",
+                "end_time" = EXCLUDED."end_time",
+                "seconds_in_a_day" = EXCLUDED."seconds_in_a_day",
+                "mint" = EXCLUDED."mint",
+                "total_token_supply" = EXCLUDED."total_token_supply",
+                "sol_volumes" = EXCLUDED."sol_volumes",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
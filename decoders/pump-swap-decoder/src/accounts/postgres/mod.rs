@@ -177,4 +177,85 @@ impl carbon_core::postgres::operations::Upsert for PumpSwapAccountWithMetadata {
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut bonding_curve_rows = Vec::new();
+        let mut fee_config_rows = Vec::new();
+        let mut global_config_rows = Vec::new();
+        let mut global_volume_accumulator_rows = Vec::new();
+        let mut pool_rows = Vec::new();
+        let mut sharing_config_rows = Vec::new();
+        let mut user_volume_accumulator_rows = Vec::new();
+
+        for PumpSwapAccountWithMetadata(account, metadata) in rows {
+            match account {
+                PumpSwapAccount::BondingCurve(account) => {
+                    bonding_curve_rows.push(bonding_curve_row::BondingCurveRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpSwapAccount::FeeConfig(account) => {
+                    fee_config_rows.push(fee_config_row::FeeConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpSwapAccount::GlobalConfig(account) => {
+                    global_config_rows.push(global_config_row::GlobalConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpSwapAccount::GlobalVolumeAccumulator(account) => {
+                    global_volume_accumulator_rows.push(
+                        global_volume_accumulator_row::GlobalVolumeAccumulatorRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                PumpSwapAccount::Pool(account) => {
+                    pool_rows.push(pool_row::PoolRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpSwapAccount::SharingConfig(account) => {
+                    sharing_config_rows.push(sharing_config_row::SharingConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpSwapAccount::UserVolumeAccumulator(account) => {
+                    user_volume_accumulator_rows.push(
+                        user_volume_accumulator_row::UserVolumeAccumulatorRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        bonding_curve_row::BondingCurveRow::upsert_many(&bonding_curve_rows, pool).await?;
+        fee_config_row::FeeConfigRow::upsert_many(&fee_config_rows, pool).await?;
+        global_config_row::GlobalConfigRow::upsert_many(&global_config_rows, pool).await?;
+        global_volume_accumulator_row::GlobalVolumeAccumulatorRow::upsert_many(
+            &global_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        pool_row::PoolRow::upsert_many(&pool_rows, pool).await?;
+        sharing_config_row::SharingConfigRow::upsert_many(&sharing_config_rows, pool).await?;
+        user_volume_accumulator_row::UserVolumeAccumulatorRow::upsert_many(
+            &user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        Ok(())
+    }
 }
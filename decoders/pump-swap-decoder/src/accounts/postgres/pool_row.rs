@@ -198,6 +198,77 @@ impl carbon_core::postgres::operations::Upsert for PoolRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(14)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO pool_account (
+                "pool_bump",
+                "index",
+                "creator",
+                "base_mint",
+                "quote_mint",
+                "lp_mint",
+                "pool_base_token_account",
+                "pool_quote_token_account",
+                "lp_supply",
+                "coin_creator",
+                "is_mayhem_mode",
+                "is_cashback_coin",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.pool_bump)
+                    .push_bind(row.index)
+                    .push_bind(row.creator)
+                    .push_bind(row.base_mint)
+                    .push_bind(row.quote_mint)
+                    .push_bind(row.lp_mint)
+                    .push_bind(row.pool_base_token_account)
+                    .push_bind(row.pool_quote_token_account)
+                    .push_bind(&row.lp_supply)
+                    .push_bind(row.coin_creator)
+                    .push_bind(row.is_mayhem_mode)
+                    .push_bind(row.is_cashback_coin)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "pool_bump" = EXCLUDED."pool_bump",
+                "index" = EXCLUDED."index",
+                "creator" = EXCLUDED."creator",
+                "base_mint" = EXCLUDED."base_mint",
+                "quote_mint" = EXCLUDED."quote_mint",
+                "lp_mint" = EXCLUDED."lp_mint",
+                "pool_base_token_account" = EXCLUDED."pool_base_token_account",
+                "pool_quote_token_account" = EXCLUDED."pool_quote_token_account",
+                "lp_supply" = EXCLUDED."lp_supply",
+                "coin_creator" = EXCLUDED."coin_creator",
+                "is_mayhem_mode" = EXCLUDED."is_mayhem_mode",
+                "is_cashback_coin" = EXCLUDED."is_cashback_coin",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -156,6 +156,62 @@ impl carbon_core::postgres::operations::Upsert for SharingConfigRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(9)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO sharing_config_account (
+                "bump",
+                "version",
+                "status",
+                "mint",
+                "admin",
+                "admin_revoked",
+                "shareholders",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.bump)
+                    .push_bind(row.version)
+                    .push_bind(&row.status)
+                    .push_bind(row.mint)
+                    .push_bind(row.admin)
+                    .push_bind(row.admin_revoked)
+                    .push_bind(&row.shareholders)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "bump" = EXCLUDED."bump",
+                "version" = EXCLUDED."version",
+                "status" = EXCLUDED."status",
+                "mint" = EXCLUDED."mint",
+                "admin" = EXCLUDED."admin",
+                "admin_revoked" = EXCLUDED."admin_revoked",
+                "shareholders" = EXCLUDED."shareholders",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +98,10 @@ pub enum PumpSwapInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PumpSwapDecoder {
     type InstructionType = PumpSwapInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -108,248 +112,91 @@ impl carbon_core::instruction::InstructionDecoder<'_> for PumpSwapDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = admin_set_coin_creator::AdminSetCoinCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::AdminSetCoinCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                admin_update_token_incentives::AdminUpdateTokenIncentives::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::AdminUpdateTokenIncentives(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = buy::Buy::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::Buy(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = buy_exact_quote_in::BuyExactQuoteIn::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::BuyExactQuoteIn(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_cashback::ClaimCashback::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::ClaimCashback(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_token_incentives::ClaimTokenIncentives::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::ClaimTokenIncentives(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                close_user_volume_accumulator::CloseUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::CloseUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = collect_coin_creator_fee::CollectCoinCreatorFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::CollectCoinCreatorFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_config::CreateConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::CreateConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_pool::CreatePool::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::CreatePool(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = deposit::Deposit::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::Deposit(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = disable::Disable::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::Disable(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = extend_account::ExtendAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::ExtendAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                init_user_volume_accumulator::InitUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::InitUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = migrate_pool_coin_creator::MigratePoolCoinCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::MigratePoolCoinCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sell::Sell::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::Sell(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_coin_creator::SetCoinCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::SetCoinCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                set_reserved_fee_recipients::SetReservedFeeRecipients::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::SetReservedFeeRecipients(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                sync_user_volume_accumulator::SyncUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::SyncUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_cashback_enabled::ToggleCashbackEnabled::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::ToggleCashbackEnabled(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_mayhem_mode::ToggleMayhemMode::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::ToggleMayhemMode(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                transfer_creator_fees_to_pump::TransferCreatorFeesToPump::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::TransferCreatorFeesToPump(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_admin::UpdateAdmin::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::UpdateAdmin(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_fee_config::UpdateFeeConfig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::UpdateFeeConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = withdraw::Withdraw::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpSwapInstruction::Withdraw(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[242, 40, 117, 145, 73, 96, 105, 104],
+                &[209, 11, 115, 87, 213, 23, 124, 204],
+                &[102, 6, 61, 18, 1, 218, 235, 234],
+                &[198, 46, 21, 82, 180, 217, 232, 112],
+                &[37, 58, 35, 126, 190, 53, 228, 197],
+                &[16, 4, 71, 28, 204, 1, 40, 27],
+                &[249, 69, 164, 218, 150, 103, 84, 138],
+                &[160, 57, 89, 42, 181, 139, 43, 66],
+                &[201, 207, 243, 114, 75, 111, 47, 189],
+                &[233, 146, 209, 142, 207, 104, 64, 188],
+                &[242, 35, 198, 137, 82, 225, 242, 182],
+                &[185, 173, 187, 90, 216, 15, 238, 233],
+                &[234, 102, 194, 203, 150, 72, 62, 229],
+                &[94, 6, 202, 115, 255, 96, 232, 183],
+                &[208, 8, 159, 4, 74, 175, 16, 58],
+                &[51, 230, 133, 164, 1, 127, 131, 173],
+                &[210, 149, 128, 45, 188, 58, 78, 175],
+                &[111, 172, 162, 232, 114, 89, 213, 142],
+                &[86, 31, 192, 87, 163, 87, 79, 238],
+                &[115, 103, 224, 255, 189, 89, 86, 195],
+                &[1, 9, 111, 208, 100, 31, 255, 163],
+                &[139, 52, 134, 85, 228, 229, 108, 241],
+                &[161, 176, 40, 213, 60, 184, 179, 228],
+                &[104, 184, 103, 242, 88, 151, 107, 20],
+                &[183, 18, 70, 156, 148, 109, 161, 34],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => admin_set_coin_creator::AdminSetCoinCreator::decode(data)
+                    .map(PumpSwapInstruction::AdminSetCoinCreator),
+                1 => admin_update_token_incentives::AdminUpdateTokenIncentives::decode(data)
+                    .map(PumpSwapInstruction::AdminUpdateTokenIncentives),
+                2 => buy::Buy::decode(data).map(PumpSwapInstruction::Buy),
+                3 => buy_exact_quote_in::BuyExactQuoteIn::decode(data)
+                    .map(PumpSwapInstruction::BuyExactQuoteIn),
+                4 => claim_cashback::ClaimCashback::decode(data)
+                    .map(PumpSwapInstruction::ClaimCashback),
+                5 => claim_token_incentives::ClaimTokenIncentives::decode(data)
+                    .map(PumpSwapInstruction::ClaimTokenIncentives),
+                6 => close_user_volume_accumulator::CloseUserVolumeAccumulator::decode(data)
+                    .map(PumpSwapInstruction::CloseUserVolumeAccumulator),
+                7 => collect_coin_creator_fee::CollectCoinCreatorFee::decode(data)
+                    .map(PumpSwapInstruction::CollectCoinCreatorFee),
+                8 => {
+                    create_config::CreateConfig::decode(data).map(PumpSwapInstruction::CreateConfig)
+                }
+                9 => create_pool::CreatePool::decode(data).map(PumpSwapInstruction::CreatePool),
+                10 => deposit::Deposit::decode(data).map(PumpSwapInstruction::Deposit),
+                11 => disable::Disable::decode(data).map(PumpSwapInstruction::Disable),
+                12 => extend_account::ExtendAccount::decode(data)
+                    .map(PumpSwapInstruction::ExtendAccount),
+                13 => init_user_volume_accumulator::InitUserVolumeAccumulator::decode(data)
+                    .map(PumpSwapInstruction::InitUserVolumeAccumulator),
+                14 => migrate_pool_coin_creator::MigratePoolCoinCreator::decode(data)
+                    .map(PumpSwapInstruction::MigratePoolCoinCreator),
+                15 => sell::Sell::decode(data).map(PumpSwapInstruction::Sell),
+                16 => set_coin_creator::SetCoinCreator::decode(data)
+                    .map(PumpSwapInstruction::SetCoinCreator),
+                17 => set_reserved_fee_recipients::SetReservedFeeRecipients::decode(data)
+                    .map(PumpSwapInstruction::SetReservedFeeRecipients),
+                18 => sync_user_volume_accumulator::SyncUserVolumeAccumulator::decode(data)
+                    .map(PumpSwapInstruction::SyncUserVolumeAccumulator),
+                19 => toggle_cashback_enabled::ToggleCashbackEnabled::decode(data)
+                    .map(PumpSwapInstruction::ToggleCashbackEnabled),
+                20 => toggle_mayhem_mode::ToggleMayhemMode::decode(data)
+                    .map(PumpSwapInstruction::ToggleMayhemMode),
+                21 => transfer_creator_fees_to_pump::TransferCreatorFeesToPump::decode(data)
+                    .map(PumpSwapInstruction::TransferCreatorFeesToPump),
+                22 => update_admin::UpdateAdmin::decode(data).map(PumpSwapInstruction::UpdateAdmin),
+                23 => update_fee_config::UpdateFeeConfig::decode(data)
+                    .map(PumpSwapInstruction::UpdateFeeConfig),
+                24 => withdraw::Withdraw::decode(data).map(PumpSwapInstruction::Withdraw),
+                25 => cpi_event::CpiEvent::decode(data)
+                    .map(|decoded| PumpSwapInstruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: PumpSwapInstruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
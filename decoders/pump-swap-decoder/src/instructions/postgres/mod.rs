@@ -620,4 +620,316 @@ impl carbon_core::postgres::operations::Upsert for PumpSwapInstructionWithMetada
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut admin_set_coin_creator_rows = Vec::new();
+        let mut admin_update_token_incentives_rows = Vec::new();
+        let mut buy_rows = Vec::new();
+        let mut buy_exact_quote_in_rows = Vec::new();
+        let mut claim_cashback_rows = Vec::new();
+        let mut claim_token_incentives_rows = Vec::new();
+        let mut close_user_volume_accumulator_rows = Vec::new();
+        let mut collect_coin_creator_fee_rows = Vec::new();
+        let mut create_config_rows = Vec::new();
+        let mut create_pool_rows = Vec::new();
+        let mut deposit_rows = Vec::new();
+        let mut disable_rows = Vec::new();
+        let mut extend_account_rows = Vec::new();
+        let mut init_user_volume_accumulator_rows = Vec::new();
+        let mut migrate_pool_coin_creator_rows = Vec::new();
+        let mut sell_rows = Vec::new();
+        let mut set_coin_creator_rows = Vec::new();
+        let mut set_reserved_fee_recipients_rows = Vec::new();
+        let mut sync_user_volume_accumulator_rows = Vec::new();
+        let mut toggle_cashback_enabled_rows = Vec::new();
+        let mut toggle_mayhem_mode_rows = Vec::new();
+        let mut transfer_creator_fees_to_pump_rows = Vec::new();
+        let mut update_admin_rows = Vec::new();
+        let mut update_fee_config_rows = Vec::new();
+        let mut withdraw_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for PumpSwapInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                PumpSwapInstruction::AdminSetCoinCreator(instruction) => {
+                    admin_set_coin_creator_rows.push(
+                        admin_set_coin_creator_row::AdminSetCoinCreatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::AdminUpdateTokenIncentives(instruction) => {
+                    admin_update_token_incentives_rows.push(admin_update_token_incentives_row::AdminUpdateTokenIncentivesRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpSwapInstruction::Buy(instruction) => {
+                    buy_rows.push(buy_row::BuyRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::BuyExactQuoteIn(instruction) => {
+                    buy_exact_quote_in_rows.push(
+                        buy_exact_quote_in_row::BuyExactQuoteInRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::ClaimCashback(instruction) => {
+                    claim_cashback_rows.push(claim_cashback_row::ClaimCashbackRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::ClaimTokenIncentives(instruction) => {
+                    claim_token_incentives_rows.push(
+                        claim_token_incentives_row::ClaimTokenIncentivesRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::CloseUserVolumeAccumulator(instruction) => {
+                    close_user_volume_accumulator_rows.push(close_user_volume_accumulator_row::CloseUserVolumeAccumulatorRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpSwapInstruction::CollectCoinCreatorFee(instruction) => {
+                    collect_coin_creator_fee_rows.push(
+                        collect_coin_creator_fee_row::CollectCoinCreatorFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::CreateConfig(instruction) => {
+                    create_config_rows.push(create_config_row::CreateConfigRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::CreatePool(instruction) => {
+                    create_pool_rows.push(create_pool_row::CreatePoolRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::Deposit(instruction) => {
+                    deposit_rows.push(deposit_row::DepositRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::Disable(instruction) => {
+                    disable_rows.push(disable_row::DisableRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::ExtendAccount(instruction) => {
+                    extend_account_rows.push(extend_account_row::ExtendAccountRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::InitUserVolumeAccumulator(instruction) => {
+                    init_user_volume_accumulator_rows.push(
+                        init_user_volume_accumulator_row::InitUserVolumeAccumulatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::MigratePoolCoinCreator(instruction) => {
+                    migrate_pool_coin_creator_rows.push(
+                        migrate_pool_coin_creator_row::MigratePoolCoinCreatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::Sell(instruction) => {
+                    sell_rows.push(sell_row::SellRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::SetCoinCreator(instruction) => {
+                    set_coin_creator_rows.push(
+                        set_coin_creator_row::SetCoinCreatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::SetReservedFeeRecipients(instruction) => {
+                    set_reserved_fee_recipients_rows.push(
+                        set_reserved_fee_recipients_row::SetReservedFeeRecipientsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::SyncUserVolumeAccumulator(instruction) => {
+                    sync_user_volume_accumulator_rows.push(
+                        sync_user_volume_accumulator_row::SyncUserVolumeAccumulatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::ToggleCashbackEnabled(instruction) => {
+                    toggle_cashback_enabled_rows.push(
+                        toggle_cashback_enabled_row::ToggleCashbackEnabledRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::ToggleMayhemMode(instruction) => {
+                    toggle_mayhem_mode_rows.push(
+                        toggle_mayhem_mode_row::ToggleMayhemModeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::TransferCreatorFeesToPump(instruction) => {
+                    transfer_creator_fees_to_pump_rows.push(
+                        transfer_creator_fees_to_pump_row::TransferCreatorFeesToPumpRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::UpdateAdmin(instruction) => {
+                    update_admin_rows.push(update_admin_row::UpdateAdminRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::UpdateFeeConfig(instruction) => {
+                    update_fee_config_rows.push(
+                        update_fee_config_row::UpdateFeeConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpSwapInstruction::Withdraw(instruction) => {
+                    withdraw_rows.push(withdraw_row::WithdrawRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpSwapInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        admin_set_coin_creator_row::AdminSetCoinCreatorRow::upsert_many(
+            &admin_set_coin_creator_rows,
+            pool,
+        )
+        .await?;
+        admin_update_token_incentives_row::AdminUpdateTokenIncentivesRow::upsert_many(
+            &admin_update_token_incentives_rows,
+            pool,
+        )
+        .await?;
+        buy_row::BuyRow::upsert_many(&buy_rows, pool).await?;
+        buy_exact_quote_in_row::BuyExactQuoteInRow::upsert_many(&buy_exact_quote_in_rows, pool)
+            .await?;
+        claim_cashback_row::ClaimCashbackRow::upsert_many(&claim_cashback_rows, pool).await?;
+        claim_token_incentives_row::ClaimTokenIncentivesRow::upsert_many(
+            &claim_token_incentives_rows,
+            pool,
+        )
+        .await?;
+        close_user_volume_accumulator_row::CloseUserVolumeAccumulatorRow::upsert_many(
+            &close_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        collect_coin_creator_fee_row::CollectCoinCreatorFeeRow::upsert_many(
+            &collect_coin_creator_fee_rows,
+            pool,
+        )
+        .await?;
+        create_config_row::CreateConfigRow::upsert_many(&create_config_rows, pool).await?;
+        create_pool_row::CreatePoolRow::upsert_many(&create_pool_rows, pool).await?;
+        deposit_row::DepositRow::upsert_many(&deposit_rows, pool).await?;
+        disable_row::DisableRow::upsert_many(&disable_rows, pool).await?;
+        extend_account_row::ExtendAccountRow::upsert_many(&extend_account_rows, pool).await?;
+        init_user_volume_accumulator_row::InitUserVolumeAccumulatorRow::upsert_many(
+            &init_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        migrate_pool_coin_creator_row::MigratePoolCoinCreatorRow::upsert_many(
+            &migrate_pool_coin_creator_rows,
+            pool,
+        )
+        .await?;
+        sell_row::SellRow::upsert_many(&sell_rows, pool).await?;
+        set_coin_creator_row::SetCoinCreatorRow::upsert_many(&set_coin_creator_rows, pool).await?;
+        set_reserved_fee_recipients_row::SetReservedFeeRecipientsRow::upsert_many(
+            &set_reserved_fee_recipients_rows,
+            pool,
+        )
+        .await?;
+        sync_user_volume_accumulator_row::SyncUserVolumeAccumulatorRow::upsert_many(
+            &sync_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        toggle_cashback_enabled_row::ToggleCashbackEnabledRow::upsert_many(
+            &toggle_cashback_enabled_rows,
+            pool,
+        )
+        .await?;
+        toggle_mayhem_mode_row::ToggleMayhemModeRow::upsert_many(&toggle_mayhem_mode_rows, pool)
+            .await?;
+        transfer_creator_fees_to_pump_row::TransferCreatorFeesToPumpRow::upsert_many(
+            &transfer_creator_fees_to_pump_rows,
+            pool,
+        )
+        .await?;
+        update_admin_row::UpdateAdminRow::upsert_many(&update_admin_rows, pool).await?;
+        update_fee_config_row::UpdateFeeConfigRow::upsert_many(&update_fee_config_rows, pool)
+            .await?;
+        withdraw_row::WithdrawRow::upsert_many(&withdraw_rows, pool).await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -165,6 +165,66 @@ impl carbon_core::postgres::operations::Upsert for UpdateFeeConfigRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(10)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO update_fee_config_instruction (
+                "lp_fee_basis_points",
+                "protocol_fee_basis_points",
+                "protocol_fee_recipients",
+                "coin_creator_fee_basis_points",
+                "admin_set_coin_creator_authority",
+                __signature, __instruction_index, __stack_height, __slot, __accounts
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.lp_fee_basis_points)
+                    .push_bind(&row.protocol_fee_basis_points)
+                    .push_bind(&row.protocol_fee_recipients)
+                    .push_bind(&row.coin_creator_fee_basis_points)
+                    .push_bind(row.admin_set_coin_creator_authority)
+                    .push_bind(&row.instruction_metadata.signature)
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(&row.instruction_metadata.slot)
+                    .push_bind(&row.accounts);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __signature, __instruction_index, __stack_height
+            ) DO UPDATE SET
+                "lp_fee_basis_points" = EXCLUDED."lp_fee_basis_points",
+                "protocol_fee_basis_points" = EXCLUDED."protocol_fee_basis_points",
+                "protocol_fee_recipients" = EXCLUDED."protocol_fee_recipients",
+                "coin_creator_fee_basis_points" = EXCLUDED."coin_creator_fee_basis_points",
+                "admin_set_coin_creator_authority" = EXCLUDED."admin_set_coin_creator_authority",
+                __instruction_index = EXCLUDED.__instruction_index,
+                __stack_height = EXCLUDED.__stack_height,
+                __slot = EXCLUDED.__slot,
+                __accounts = EXCLUDED.__accounts
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -30,6 +30,10 @@ pub enum PumpfunAccount {
 impl<'a> carbon_core::account::AccountDecoder<'a> for PumpfunDecoder {
     type AccountType = PumpfunAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -40,67 +44,37 @@ impl<'a> carbon_core::account::AccountDecoder<'a> for PumpfunDecoder {
 
         let data = account.data.as_slice();
 
-        {
-            if let Some(decoded) = bonding_curve::BondingCurve::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpfunAccount::BondingCurve(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = fee_config::FeeConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpfunAccount::FeeConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = global::Global::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpfunAccount::Global(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = global_volume_accumulator::GlobalVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpfunAccount::GlobalVolumeAccumulator(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sharing_config::SharingConfig::decode(data) {
-                return Some(carbon_core::account::DecodedAccount {
-                    lamports: account.lamports,
-                    data: PumpfunAccount::SharingConfig(Box::new(decoded)),
-                    owner: account.owner,
-                    executable: account.executable,
-                    rent_epoch: account.rent_epoch,
-                });
-            }
-        }
-        {
-            if let Some(decoded) = user_volume_accumulator::UserVolumeAccumulator::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[23, 183, 248, 55, 96, 216, 172, 96],
+                &[143, 52, 146, 187, 219, 123, 76, 155],
+                &[167, 232, 232, 177, 200, 108, 114, 127],
+                &[202, 42, 246, 43, 142, 190, 30, 255],
+                &[216, 74, 9, 0, 56, 140, 93, 75],
+                &[86, 255, 112, 14, 102, 53, 154, 250],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => bonding_curve::BondingCurve::decode(data)
+                    .map(|decoded| PumpfunAccount::BondingCurve(Box::new(decoded))),
+                1 => fee_config::FeeConfig::decode(data)
+                    .map(|decoded| PumpfunAccount::FeeConfig(Box::new(decoded))),
+                2 => global::Global::decode(data)
+                    .map(|decoded| PumpfunAccount::Global(Box::new(decoded))),
+                3 => global_volume_accumulator::GlobalVolumeAccumulator::decode(data)
+                    .map(|decoded| PumpfunAccount::GlobalVolumeAccumulator(Box::new(decoded))),
+                4 => sharing_config::SharingConfig::decode(data)
+                    .map(|decoded| PumpfunAccount::SharingConfig(Box::new(decoded))),
+                5 => user_volume_accumulator::UserVolumeAccumulator::decode(data)
+                    .map(|decoded| PumpfunAccount::UserVolumeAccumulator(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::account::DecodedAccount {
                     lamports: account.lamports,
-                    data: PumpfunAccount::UserVolumeAccumulator(Box::new(decoded)),
+                    data: decoded,
                     owner: account.owner,
                     executable: account.executable,
                     rent_epoch: account.rent_epoch,
@@ -156,4 +156,77 @@ impl carbon_core::postgres::operations::Upsert for PumpfunAccountWithMetadata {
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut bonding_curve_rows = Vec::new();
+        let mut fee_config_rows = Vec::new();
+        let mut global_rows = Vec::new();
+        let mut global_volume_accumulator_rows = Vec::new();
+        let mut sharing_config_rows = Vec::new();
+        let mut user_volume_accumulator_rows = Vec::new();
+
+        for PumpfunAccountWithMetadata(account, metadata) in rows {
+            match account {
+                PumpfunAccount::BondingCurve(account) => {
+                    bonding_curve_rows.push(bonding_curve_row::BondingCurveRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpfunAccount::FeeConfig(account) => {
+                    fee_config_rows.push(fee_config_row::FeeConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpfunAccount::Global(account) => {
+                    global_rows.push(global_row::GlobalRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpfunAccount::GlobalVolumeAccumulator(account) => {
+                    global_volume_accumulator_rows.push(
+                        global_volume_accumulator_row::GlobalVolumeAccumulatorRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                PumpfunAccount::SharingConfig(account) => {
+                    sharing_config_rows.push(sharing_config_row::SharingConfigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                PumpfunAccount::UserVolumeAccumulator(account) => {
+                    user_volume_accumulator_rows.push(
+                        user_volume_accumulator_row::UserVolumeAccumulatorRow::from_parts(
+                            *account.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        bonding_curve_row::BondingCurveRow::upsert_many(&bonding_curve_rows, pool).await?;
+        fee_config_row::FeeConfigRow::upsert_many(&fee_config_rows, pool).await?;
+        global_row::GlobalRow::upsert_many(&global_rows, pool).await?;
+        global_volume_accumulator_row::GlobalVolumeAccumulatorRow::upsert_many(
+            &global_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        sharing_config_row::SharingConfigRow::upsert_many(&sharing_config_rows, pool).await?;
+        user_volume_accumulator_row::UserVolumeAccumulatorRow::upsert_many(
+            &user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        Ok(())
+    }
 }
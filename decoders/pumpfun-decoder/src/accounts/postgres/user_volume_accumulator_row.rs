@@ -168,6 +168,68 @@ impl carbon_core::postgres::operations::Upsert for UserVolumeAccumulatorRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(11)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO user_volume_accumulator_account (
+                "user",
+                "needs_claim",
+                "total_unclaimed_tokens",
+                "total_claimed_tokens",
+                "current_sol_volume",
+                "last_update_timestamp",
+                "has_total_claimed_tokens",
+                "cashback_earned",
+                "total_cashback_claimed",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.user)
+                    .push_bind(row.needs_claim)
+                    .push_bind(&row.total_unclaimed_tokens)
+                    .push_bind(&row.total_claimed_tokens)
+                    .push_bind(&row.current_sol_volume)
+                    .push_bind(row.last_update_timestamp)
+                    .push_bind(row.has_total_claimed_tokens)
+                    .push_bind(&row.cashback_earned)
+                    .push_bind(&row.total_cashback_claimed)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "user" = EXCLUDED."user",
+                "needs_claim" = EXCLUDED."needs_claim",
+                "total_unclaimed_tokens" = EXCLUDED."total_unclaimed_tokens",
+                "total_claimed_tokens" = EXCLUDED."total_claimed_tokens",
+                "current_sol_volume" = EXCLUDED."current_sol_volume",
+                "last_update_timestamp" = EXCLUDED."last_update_timestamp",
+                "has_total_claimed_tokens" = EXCLUDED."has_total_claimed_tokens",
+                "cashback_earned" = EXCLUDED."cashback_earned",
+                "total_cashback_claimed" = EXCLUDED."total_cashback_claimed",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -110,6 +110,10 @@ pub enum PumpfunInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for PumpfunDecoder {
     type InstructionType = PumpfunInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -120,286 +124,101 @@ impl carbon_core::instruction::InstructionDecoder<'_> for PumpfunDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = admin_set_creator::AdminSetCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::AdminSetCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = admin_set_idl_authority::AdminSetIdlAuthority::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::AdminSetIdlAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                admin_update_token_incentives::AdminUpdateTokenIncentives::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::AdminUpdateTokenIncentives(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = buy::Buy::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::Buy(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = buy_exact_sol_in::BuyExactSolIn::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::BuyExactSolIn(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_cashback::ClaimCashback::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ClaimCashback(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = claim_token_incentives::ClaimTokenIncentives::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ClaimTokenIncentives(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                close_user_volume_accumulator::CloseUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::CloseUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = collect_creator_fee::CollectCreatorFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::CollectCreatorFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create::Create::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::Create(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_v2::CreateV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::CreateV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = distribute_creator_fees::DistributeCreatorFees::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::DistributeCreatorFees(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = extend_account::ExtendAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ExtendAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                get_minimum_distributable_fee::GetMinimumDistributableFee::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::GetMinimumDistributableFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                init_user_volume_accumulator::InitUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::InitUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize::Initialize::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::Initialize(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = migrate::Migrate::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::Migrate(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                migrate_bonding_curve_creator::MigrateBondingCurveCreator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::MigrateBondingCurveCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sell::Sell::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::Sell(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_creator::SetCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SetCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_mayhem_virtual_params::SetMayhemVirtualParams::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SetMayhemVirtualParams(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_metaplex_creator::SetMetaplexCreator::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SetMetaplexCreator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_params::SetParams::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SetParams(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                set_reserved_fee_recipients::SetReservedFeeRecipients::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SetReservedFeeRecipients(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                sync_user_volume_accumulator::SyncUserVolumeAccumulator::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::SyncUserVolumeAccumulator(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_cashback_enabled::ToggleCashbackEnabled::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ToggleCashbackEnabled(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_create_v2::ToggleCreateV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ToggleCreateV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_mayhem_mode::ToggleMayhemMode::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::ToggleMayhemMode(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_global_authority::UpdateGlobalAuthority::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: PumpfunInstruction::UpdateGlobalAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = cpi_event::CpiEvent::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[69, 25, 171, 142, 57, 239, 13, 4],
+                &[8, 217, 96, 231, 144, 104, 192, 5],
+                &[209, 11, 115, 87, 213, 23, 124, 204],
+                &[102, 6, 61, 18, 1, 218, 235, 234],
+                &[56, 252, 116, 8, 158, 223, 205, 95],
+                &[37, 58, 35, 126, 190, 53, 228, 197],
+                &[16, 4, 71, 28, 204, 1, 40, 27],
+                &[249, 69, 164, 218, 150, 103, 84, 138],
+                &[20, 22, 86, 123, 198, 28, 219, 132],
+                &[24, 30, 200, 40, 5, 28, 7, 119],
+                &[214, 144, 76, 236, 95, 139, 49, 180],
+                &[165, 114, 103, 0, 121, 206, 247, 81],
+                &[234, 102, 194, 203, 150, 72, 62, 229],
+                &[117, 225, 127, 202, 134, 95, 68, 35],
+                &[94, 6, 202, 115, 255, 96, 232, 183],
+                &[175, 175, 109, 31, 13, 152, 155, 237],
+                &[155, 234, 231, 146, 236, 158, 162, 30],
+                &[87, 124, 52, 191, 52, 38, 214, 232],
+                &[51, 230, 133, 164, 1, 127, 131, 173],
+                &[254, 148, 255, 112, 207, 142, 170, 165],
+                &[61, 169, 188, 191, 153, 149, 42, 97],
+                &[138, 96, 174, 217, 48, 85, 197, 246],
+                &[27, 234, 178, 52, 147, 2, 187, 141],
+                &[111, 172, 162, 232, 114, 89, 213, 142],
+                &[86, 31, 192, 87, 163, 87, 79, 238],
+                &[115, 103, 224, 255, 189, 89, 86, 195],
+                &[28, 255, 230, 240, 172, 107, 203, 171],
+                &[1, 9, 111, 208, 100, 31, 255, 163],
+                &[227, 181, 74, 196, 208, 21, 97, 213],
+                &[],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => admin_set_creator::AdminSetCreator::decode(data)
+                    .map(PumpfunInstruction::AdminSetCreator),
+                1 => admin_set_idl_authority::AdminSetIdlAuthority::decode(data)
+                    .map(PumpfunInstruction::AdminSetIdlAuthority),
+                2 => admin_update_token_incentives::AdminUpdateTokenIncentives::decode(data)
+                    .map(PumpfunInstruction::AdminUpdateTokenIncentives),
+                3 => buy::Buy::decode(data).map(PumpfunInstruction::Buy),
+                4 => buy_exact_sol_in::BuyExactSolIn::decode(data)
+                    .map(PumpfunInstruction::BuyExactSolIn),
+                5 => claim_cashback::ClaimCashback::decode(data)
+                    .map(PumpfunInstruction::ClaimCashback),
+                6 => claim_token_incentives::ClaimTokenIncentives::decode(data)
+                    .map(PumpfunInstruction::ClaimTokenIncentives),
+                7 => close_user_volume_accumulator::CloseUserVolumeAccumulator::decode(data)
+                    .map(PumpfunInstruction::CloseUserVolumeAccumulator),
+                8 => collect_creator_fee::CollectCreatorFee::decode(data)
+                    .map(PumpfunInstruction::CollectCreatorFee),
+                9 => create::Create::decode(data).map(PumpfunInstruction::Create),
+                10 => create_v2::CreateV2::decode(data).map(PumpfunInstruction::CreateV2),
+                11 => distribute_creator_fees::DistributeCreatorFees::decode(data)
+                    .map(PumpfunInstruction::DistributeCreatorFees),
+                12 => extend_account::ExtendAccount::decode(data)
+                    .map(PumpfunInstruction::ExtendAccount),
+                13 => get_minimum_distributable_fee::GetMinimumDistributableFee::decode(data)
+                    .map(PumpfunInstruction::GetMinimumDistributableFee),
+                14 => init_user_volume_accumulator::InitUserVolumeAccumulator::decode(data)
+                    .map(PumpfunInstruction::InitUserVolumeAccumulator),
+                15 => initialize::Initialize::decode(data).map(PumpfunInstruction::Initialize),
+                16 => migrate::Migrate::decode(data).map(PumpfunInstruction::Migrate),
+                17 => migrate_bonding_curve_creator::MigrateBondingCurveCreator::decode(data)
+                    .map(PumpfunInstruction::MigrateBondingCurveCreator),
+                18 => sell::Sell::decode(data).map(PumpfunInstruction::Sell),
+                19 => set_creator::SetCreator::decode(data).map(PumpfunInstruction::SetCreator),
+                20 => set_mayhem_virtual_params::SetMayhemVirtualParams::decode(data)
+                    .map(PumpfunInstruction::SetMayhemVirtualParams),
+                21 => set_metaplex_creator::SetMetaplexCreator::decode(data)
+                    .map(PumpfunInstruction::SetMetaplexCreator),
+                22 => set_params::SetParams::decode(data).map(PumpfunInstruction::SetParams),
+                23 => set_reserved_fee_recipients::SetReservedFeeRecipients::decode(data)
+                    .map(PumpfunInstruction::SetReservedFeeRecipients),
+                24 => sync_user_volume_accumulator::SyncUserVolumeAccumulator::decode(data)
+                    .map(PumpfunInstruction::SyncUserVolumeAccumulator),
+                25 => toggle_cashback_enabled::ToggleCashbackEnabled::decode(data)
+                    .map(PumpfunInstruction::ToggleCashbackEnabled),
+                26 => toggle_create_v2::ToggleCreateV2::decode(data)
+                    .map(PumpfunInstruction::ToggleCreateV2),
+                27 => toggle_mayhem_mode::ToggleMayhemMode::decode(data)
+                    .map(PumpfunInstruction::ToggleMayhemMode),
+                28 => update_global_authority::UpdateGlobalAuthority::decode(data)
+                    .map(PumpfunInstruction::UpdateGlobalAuthority),
+                29 => cpi_event::CpiEvent::decode(data)
+                    .map(|decoded| PumpfunInstruction::CpiEvent(Box::new(decoded))),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: PumpfunInstruction::CpiEvent(Box::new(decoded)),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
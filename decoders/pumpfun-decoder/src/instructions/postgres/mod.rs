@@ -706,4 +706,358 @@ impl carbon_core::postgres::operations::Upsert for PumpfunInstructionWithMetadat
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut admin_set_creator_rows = Vec::new();
+        let mut admin_set_idl_authority_rows = Vec::new();
+        let mut admin_update_token_incentives_rows = Vec::new();
+        let mut buy_rows = Vec::new();
+        let mut buy_exact_sol_in_rows = Vec::new();
+        let mut claim_cashback_rows = Vec::new();
+        let mut claim_token_incentives_rows = Vec::new();
+        let mut close_user_volume_accumulator_rows = Vec::new();
+        let mut collect_creator_fee_rows = Vec::new();
+        let mut create_rows = Vec::new();
+        let mut create_v2_rows = Vec::new();
+        let mut distribute_creator_fees_rows = Vec::new();
+        let mut extend_account_rows = Vec::new();
+        let mut get_minimum_distributable_fee_rows = Vec::new();
+        let mut init_user_volume_accumulator_rows = Vec::new();
+        let mut initialize_rows = Vec::new();
+        let mut migrate_rows = Vec::new();
+        let mut migrate_bonding_curve_creator_rows = Vec::new();
+        let mut sell_rows = Vec::new();
+        let mut set_creator_rows = Vec::new();
+        let mut set_mayhem_virtual_params_rows = Vec::new();
+        let mut set_metaplex_creator_rows = Vec::new();
+        let mut set_params_rows = Vec::new();
+        let mut set_reserved_fee_recipients_rows = Vec::new();
+        let mut sync_user_volume_accumulator_rows = Vec::new();
+        let mut toggle_cashback_enabled_rows = Vec::new();
+        let mut toggle_create_v2_rows = Vec::new();
+        let mut toggle_mayhem_mode_rows = Vec::new();
+        let mut update_global_authority_rows = Vec::new();
+        let mut cpi_event_rows = Vec::new();
+
+        for PumpfunInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                PumpfunInstruction::AdminSetCreator(instruction) => {
+                    admin_set_creator_rows.push(
+                        admin_set_creator_row::AdminSetCreatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::AdminSetIdlAuthority(instruction) => {
+                    admin_set_idl_authority_rows.push(
+                        admin_set_idl_authority_row::AdminSetIdlAuthorityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::AdminUpdateTokenIncentives(instruction) => {
+                    admin_update_token_incentives_rows.push(admin_update_token_incentives_row::AdminUpdateTokenIncentivesRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpfunInstruction::Buy(instruction) => {
+                    buy_rows.push(buy_row::BuyRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::BuyExactSolIn(instruction) => {
+                    buy_exact_sol_in_rows.push(buy_exact_sol_in_row::BuyExactSolInRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::ClaimCashback(instruction) => {
+                    claim_cashback_rows.push(claim_cashback_row::ClaimCashbackRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::ClaimTokenIncentives(instruction) => {
+                    claim_token_incentives_rows.push(
+                        claim_token_incentives_row::ClaimTokenIncentivesRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::CloseUserVolumeAccumulator(instruction) => {
+                    close_user_volume_accumulator_rows.push(close_user_volume_accumulator_row::CloseUserVolumeAccumulatorRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpfunInstruction::CollectCreatorFee(instruction) => {
+                    collect_creator_fee_rows.push(
+                        collect_creator_fee_row::CollectCreatorFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::Create(instruction) => {
+                    create_rows.push(create_row::CreateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::CreateV2(instruction) => {
+                    create_v2_rows.push(create_v2_row::CreateV2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::DistributeCreatorFees(instruction) => {
+                    distribute_creator_fees_rows.push(
+                        distribute_creator_fees_row::DistributeCreatorFeesRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::ExtendAccount(instruction) => {
+                    extend_account_rows.push(extend_account_row::ExtendAccountRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::GetMinimumDistributableFee(instruction) => {
+                    get_minimum_distributable_fee_rows.push(get_minimum_distributable_fee_row::GetMinimumDistributableFeeRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpfunInstruction::InitUserVolumeAccumulator(instruction) => {
+                    init_user_volume_accumulator_rows.push(
+                        init_user_volume_accumulator_row::InitUserVolumeAccumulatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::Initialize(instruction) => {
+                    initialize_rows.push(initialize_row::InitializeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::Migrate(instruction) => {
+                    migrate_rows.push(migrate_row::MigrateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::MigrateBondingCurveCreator(instruction) => {
+                    migrate_bonding_curve_creator_rows.push(migrate_bonding_curve_creator_row::MigrateBondingCurveCreatorRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                PumpfunInstruction::Sell(instruction) => {
+                    sell_rows.push(sell_row::SellRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::SetCreator(instruction) => {
+                    set_creator_rows.push(set_creator_row::SetCreatorRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::SetMayhemVirtualParams(instruction) => {
+                    set_mayhem_virtual_params_rows.push(
+                        set_mayhem_virtual_params_row::SetMayhemVirtualParamsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::SetMetaplexCreator(instruction) => {
+                    set_metaplex_creator_rows.push(
+                        set_metaplex_creator_row::SetMetaplexCreatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::SetParams(instruction) => {
+                    set_params_rows.push(set_params_row::SetParamsRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                PumpfunInstruction::SetReservedFeeRecipients(instruction) => {
+                    set_reserved_fee_recipients_rows.push(
+                        set_reserved_fee_recipients_row::SetReservedFeeRecipientsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::SyncUserVolumeAccumulator(instruction) => {
+                    sync_user_volume_accumulator_rows.push(
+                        sync_user_volume_accumulator_row::SyncUserVolumeAccumulatorRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::ToggleCashbackEnabled(instruction) => {
+                    toggle_cashback_enabled_rows.push(
+                        toggle_cashback_enabled_row::ToggleCashbackEnabledRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::ToggleCreateV2(instruction) => {
+                    toggle_create_v2_rows.push(
+                        toggle_create_v2_row::ToggleCreateV2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::ToggleMayhemMode(instruction) => {
+                    toggle_mayhem_mode_rows.push(
+                        toggle_mayhem_mode_row::ToggleMayhemModeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::UpdateGlobalAuthority(instruction) => {
+                    update_global_authority_rows.push(
+                        update_global_authority_row::UpdateGlobalAuthorityRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                PumpfunInstruction::CpiEvent(instruction) => {
+                    cpi_event_rows.push(cpi_event_row::CpiEventRow::from_parts(
+                        (**instruction).clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+            }
+        }
+
+        admin_set_creator_row::AdminSetCreatorRow::upsert_many(&admin_set_creator_rows, pool)
+            .await?;
+        admin_set_idl_authority_row::AdminSetIdlAuthorityRow::upsert_many(
+            &admin_set_idl_authority_rows,
+            pool,
+        )
+        .await?;
+        admin_update_token_incentives_row::AdminUpdateTokenIncentivesRow::upsert_many(
+            &admin_update_token_incentives_rows,
+            pool,
+        )
+        .await?;
+        buy_row::BuyRow::upsert_many(&buy_rows, pool).await?;
+        buy_exact_sol_in_row::BuyExactSolInRow::upsert_many(&buy_exact_sol_in_rows, pool).await?;
+        claim_cashback_row::ClaimCashbackRow::upsert_many(&claim_cashback_rows, pool).await?;
+        claim_token_incentives_row::ClaimTokenIncentivesRow::upsert_many(
+            &claim_token_incentives_rows,
+            pool,
+        )
+        .await?;
+        close_user_volume_accumulator_row::CloseUserVolumeAccumulatorRow::upsert_many(
+            &close_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        collect_creator_fee_row::CollectCreatorFeeRow::upsert_many(&collect_creator_fee_rows, pool)
+            .await?;
+        create_row::CreateRow::upsert_many(&create_rows, pool).await?;
+        create_v2_row::CreateV2Row::upsert_many(&create_v2_rows, pool).await?;
+        distribute_creator_fees_row::DistributeCreatorFeesRow::upsert_many(
+            &distribute_creator_fees_rows,
+            pool,
+        )
+        .await?;
+        extend_account_row::ExtendAccountRow::upsert_many(&extend_account_rows, pool).await?;
+        get_minimum_distributable_fee_row::GetMinimumDistributableFeeRow::upsert_many(
+            &get_minimum_distributable_fee_rows,
+            pool,
+        )
+        .await?;
+        init_user_volume_accumulator_row::InitUserVolumeAccumulatorRow::upsert_many(
+            &init_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        initialize_row::InitializeRow::upsert_many(&initialize_rows, pool).await?;
+        migrate_row::MigrateRow::upsert_many(&migrate_rows, pool).await?;
+        migrate_bonding_curve_creator_row::MigrateBondingCurveCreatorRow::upsert_many(
+            &migrate_bonding_curve_creator_rows,
+            pool,
+        )
+        .await?;
+        sell_row::SellRow::upsert_many(&sell_rows, pool).await?;
+        set_creator_row::SetCreatorRow::upsert_many(&set_creator_rows, pool).await?;
+        set_mayhem_virtual_params_row::SetMayhemVirtualParamsRow::upsert_many(
+            &set_mayhem_virtual_params_rows,
+            pool,
+        )
+        .await?;
+        set_metaplex_creator_row::SetMetaplexCreatorRow::upsert_many(
+            &set_metaplex_creator_rows,
+            pool,
+        )
+        .await?;
+        set_params_row::SetParamsRow::upsert_many(&set_params_rows, pool).await?;
+        set_reserved_fee_recipients_row::SetReservedFeeRecipientsRow::upsert_many(
+            &set_reserved_fee_recipients_rows,
+            pool,
+        )
+        .await?;
+        sync_user_volume_accumulator_row::SyncUserVolumeAccumulatorRow::upsert_many(
+            &sync_user_volume_accumulator_rows,
+            pool,
+        )
+        .await?;
+        toggle_cashback_enabled_row::ToggleCashbackEnabledRow::upsert_many(
+            &toggle_cashback_enabled_rows,
+            pool,
+        )
+        .await?;
+        toggle_create_v2_row::ToggleCreateV2Row::upsert_many(&toggle_create_v2_rows, pool).await?;
+        toggle_mayhem_mode_row::ToggleMayhemModeRow::upsert_many(&toggle_mayhem_mode_rows, pool)
+            .await?;
+        update_global_authority_row::UpdateGlobalAuthorityRow::upsert_many(
+            &update_global_authority_rows,
+            pool,
+        )
+        .await?;
+        cpi_event_row::CpiEventRow::upsert_many(&cpi_event_rows, pool).await?;
+        Ok(())
+    }
 }
@@ -198,6 +198,84 @@ impl carbon_core::postgres::operations::Upsert for SetParamsRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(16)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO set_params_instruction (
+                "initial_virtual_token_reserves",
+                "initial_virtual_sol_reserves",
+                "initial_real_token_reserves",
+                "token_total_supply",
+                "fee_basis_points",
+                "withdraw_authority",
+                "enable_migrate",
+                "pool_migration_fee",
+                "creator_fee_basis_points",
+                "set_creator_authority",
+                "admin_set_creator_authority",
+                __signature, __instruction_index, __stack_height, __slot, __accounts
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.initial_virtual_token_reserves)
+                    .push_bind(&row.initial_virtual_sol_reserves)
+                    .push_bind(&row.initial_real_token_reserves)
+                    .push_bind(&row.token_total_supply)
+                    .push_bind(&row.fee_basis_points)
+                    .push_bind(row.withdraw_authority)
+                    .push_bind(row.enable_migrate)
+                    .push_bind(&row.pool_migration_fee)
+                    .push_bind(&row.creator_fee_basis_points)
+                    .push_bind(row.set_creator_authority)
+                    .push_bind(row.admin_set_creator_authority)
+                    .push_bind(&row.instruction_metadata.signature)
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(&row.instruction_metadata.slot)
+                    .push_bind(&row.accounts);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __signature, __instruction_index, __stack_height
+            ) DO UPDATE SET
+                "initial_virtual_token_reserves" = EXCLUDED."initial_virtual_token_reserves",
+                "initial_virtual_sol_reserves" = EXCLUDED."initial_virtual_sol_reserves",
+                "initial_real_token_reserves" = EXCLUDED."initial_real_token_reserves",
+                "token_total_supply" = EXCLUDED."token_total_supply",
+                "fee_basis_points" = EXCLUDED."fee_basis_points",
+                "withdraw_authority" = EXCLUDED."withdraw_authority",
+                "enable_migrate" = EXCLUDED."enable_migrate",
+                "pool_migration_fee" = EXCLUDED."pool_migration_fee",
+                "creator_fee_basis_points" = EXCLUDED."creator_fee_basis_points",
+                "set_creator_authority" = EXCLUDED."set_creator_authority",
+                "admin_set_creator_authority" = EXCLUDED."admin_set_creator_authority",
+                __instruction_index = EXCLUDED.__instruction_index,
+                __stack_height = EXCLUDED.__stack_height,
+                __slot = EXCLUDED.__slot,
+                __accounts = EXCLUDED.__accounts
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
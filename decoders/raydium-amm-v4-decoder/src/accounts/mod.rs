@@ -16,6 +16,11 @@ pub enum RaydiumAmmV4Account {
 
 impl AccountDecoder<'_> for RaydiumAmmV4Decoder {
     type AccountType = RaydiumAmmV4Account;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -54,6 +54,10 @@ pub enum RaydiumAmmV4Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for RaydiumAmmV4Decoder {
     type InstructionType = RaydiumAmmV4Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,6 @@
 use {
-    super::RaydiumClmmDecoder,
-    crate::PROGRAM_ID,
-    alloc::boxed::Box,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
+    super::RaydiumClmmDecoder, crate::PROGRAM_ID, alloc::boxed::Box,
+    carbon_core::account::AccountDecoder,
 };
 pub mod amm_config;
 pub mod observation_state;
@@ -27,6 +25,11 @@ pub enum RaydiumClmmAccount {
 
 impl AccountDecoder<'_> for RaydiumClmmDecoder {
     type AccountType = RaydiumClmmAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -35,101 +38,16 @@ impl AccountDecoder<'_> for RaydiumClmmDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = amm_config::AmmConfig::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::AmmConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            operation_state::OperationState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::OperationState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            observation_state::ObservationState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::ObservationState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            personal_position_state::PersonalPositionState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::PersonalPositionState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool_state::PoolState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::PoolState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            protocol_position_state::ProtocolPositionState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::ProtocolPositionState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            tick_array_state::TickArrayState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::TickArrayState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            tick_array_bitmap_extension::TickArrayBitmapExtension::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumClmmAccount::TickArrayBitmapExtension(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            RaydiumClmmAccount::AmmConfig => amm_config::AmmConfig,
+            RaydiumClmmAccount::OperationState => operation_state::OperationState,
+            RaydiumClmmAccount::ObservationState => observation_state::ObservationState,
+            RaydiumClmmAccount::PersonalPositionState => personal_position_state::PersonalPositionState,
+            RaydiumClmmAccount::PoolState => pool_state::PoolState,
+            RaydiumClmmAccount::ProtocolPositionState => protocol_position_state::ProtocolPositionState,
+            RaydiumClmmAccount::TickArrayState => tick_array_state::TickArrayState,
+            RaydiumClmmAccount::TickArrayBitmapExtension => tick_array_bitmap_extension::TickArrayBitmapExtension,
+        )
     }
 }
 
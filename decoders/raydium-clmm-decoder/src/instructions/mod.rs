@@ -88,6 +88,10 @@ pub enum RaydiumClmmInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for RaydiumClmmDecoder {
     type InstructionType = RaydiumClmmInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,4 @@
-use {
-    super::RaydiumCpmmDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::RaydiumCpmmDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod amm_config;
 pub mod observation_state;
 pub mod permission;
@@ -18,6 +14,11 @@ pub enum RaydiumCpmmAccount {
 
 impl AccountDecoder<'_> for RaydiumCpmmDecoder {
     type AccountType = RaydiumCpmmAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -26,49 +27,11 @@ impl AccountDecoder<'_> for RaydiumCpmmDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = amm_config::AmmConfig::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumCpmmAccount::AmmConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            observation_state::ObservationState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumCpmmAccount::ObservationState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = permission::Permission::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumCpmmAccount::Permission(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool_state::PoolState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumCpmmAccount::PoolState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            RaydiumCpmmAccount::AmmConfig => amm_config::AmmConfig,
+            RaydiumCpmmAccount::ObservationState => observation_state::ObservationState,
+            RaydiumCpmmAccount::Permission => permission::Permission,
+            RaydiumCpmmAccount::PoolState => pool_state::PoolState,
+        )
     }
 }
@@ -50,6 +50,10 @@ pub enum RaydiumCpmmInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for RaydiumCpmmDecoder {
     type InstructionType = RaydiumCpmmInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,6 +1,5 @@
 use alloc::boxed::Box;
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -19,6 +18,11 @@ pub enum RaydiumLaunchpadAccount {
 
 impl AccountDecoder<'_> for RaydiumLaunchpadDecoder {
     type AccountType = RaydiumLaunchpadAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -27,52 +31,11 @@ impl AccountDecoder<'_> for RaydiumLaunchpadDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            global_config::GlobalConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLaunchpadAccount::GlobalConfig(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            platform_config::PlatformConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLaunchpadAccount::PlatformConfig(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = pool_state::PoolState::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLaunchpadAccount::PoolState(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            vesting_record::VestingRecord::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLaunchpadAccount::VestingRecord(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            RaydiumLaunchpadAccount::GlobalConfig => global_config::GlobalConfig,
+            RaydiumLaunchpadAccount::PlatformConfig => platform_config::PlatformConfig,
+            RaydiumLaunchpadAccount::PoolState => pool_state::PoolState,
+            RaydiumLaunchpadAccount::VestingRecord => vesting_record::VestingRecord,
+        )
     }
 }
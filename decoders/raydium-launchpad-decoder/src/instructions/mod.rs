@@ -70,6 +70,10 @@ pub enum RaydiumLaunchpadInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for RaydiumLaunchpadDecoder {
     type InstructionType = RaydiumLaunchpadInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
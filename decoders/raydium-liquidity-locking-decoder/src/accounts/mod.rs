@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -14,6 +13,11 @@ pub enum RaydiumLiquidityLockingAccount {
 
 impl AccountDecoder<'_> for RaydiumLiquidityLockingDecoder {
     type AccountType = RaydiumLiquidityLockingAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,32 +26,9 @@ impl AccountDecoder<'_> for RaydiumLiquidityLockingDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            locked_cp_liquidity_state::LockedCpLiquidityState::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLiquidityLockingAccount::LockedCpLiquidityState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            locked_clmm_position_state::LockedClmmPositionState::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: RaydiumLiquidityLockingAccount::LockedClmmPositionState(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            RaydiumLiquidityLockingAccount::LockedCpLiquidityState => locked_cp_liquidity_state::LockedCpLiquidityState,
+            RaydiumLiquidityLockingAccount::LockedClmmPositionState => locked_clmm_position_state::LockedClmmPositionState,
+        )
     }
 }
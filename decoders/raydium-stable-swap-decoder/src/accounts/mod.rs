@@ -6,6 +6,11 @@ pub enum RaydiumStableSwapAmmAccount {}
 
 impl AccountDecoder<'_> for RaydiumStableSwapAmmDecoder {
     type AccountType = RaydiumStableSwapAmmAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         _account: &solana_account::Account,
@@ -30,6 +30,10 @@ pub enum RaydiumStableSwapAmmInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for RaydiumStableSwapAmmDecoder {
     type InstructionType = RaydiumStableSwapAmmInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,8 +1,4 @@
-use {
-    super::SharkyDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
-};
+use {super::SharkyDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder};
 pub mod escrow_pda;
 pub mod loan;
 pub mod nft_list;
@@ -19,6 +15,11 @@ pub enum SharkyAccount {
 
 impl AccountDecoder<'_> for SharkyDecoder {
     type AccountType = SharkyAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -27,58 +28,12 @@ impl AccountDecoder<'_> for SharkyDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = order_book::OrderBook::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SharkyAccount::OrderBook(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = loan::Loan::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SharkyAccount::Loan(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = nft_list::NftList::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SharkyAccount::NftList(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = escrow_pda::EscrowPda::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SharkyAccount::EscrowPda(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            program_version::ProgramVersion::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SharkyAccount::ProgramVersion(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            SharkyAccount::OrderBook => order_book::OrderBook,
+            SharkyAccount::Loan => loan::Loan,
+            SharkyAccount::NftList => nft_list::NftList,
+            SharkyAccount::EscrowPda => escrow_pda::EscrowPda,
+            SharkyAccount::ProgramVersion => program_version::ProgramVersion,
+        )
     }
 }
@@ -54,6 +54,10 @@ pub enum SharkyInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SharkyDecoder {
     type InstructionType = SharkyInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
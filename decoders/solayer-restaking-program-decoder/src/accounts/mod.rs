@@ -1,7 +1,5 @@
 use {
-    super::SolayerRestakingProgramDecoder,
-    crate::PROGRAM_ID,
-    carbon_core::{account::AccountDecoder, deserialize::CarbonDeserialize},
+    super::SolayerRestakingProgramDecoder, crate::PROGRAM_ID, carbon_core::account::AccountDecoder,
 };
 pub mod restaking_pool;
 
@@ -11,6 +9,11 @@ pub enum SolayerRestakingProgramAccount {
 
 impl AccountDecoder<'_> for SolayerRestakingProgramDecoder {
     type AccountType = SolayerRestakingProgramAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -18,18 +21,8 @@ impl AccountDecoder<'_> for SolayerRestakingProgramDecoder {
         if !account.owner.eq(&PROGRAM_ID) {
             return None;
         }
-        if let Some(decoded_account) =
-            restaking_pool::RestakingPool::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SolayerRestakingProgramAccount::RestakingPool(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            SolayerRestakingProgramAccount::RestakingPool => restaking_pool::RestakingPool,
+        )
     }
 }
@@ -24,6 +24,10 @@ pub enum SolayerRestakingProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SolayerRestakingProgramDecoder {
     type InstructionType = SolayerRestakingProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -16,6 +15,11 @@ pub enum StableSwapAccount {
 
 impl AccountDecoder<'_> for StableSwapDecoder {
     type AccountType = StableSwapAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -24,36 +28,10 @@ impl AccountDecoder<'_> for StableSwapDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = pool::Pool::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: StableSwapAccount::Pool(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = strategy::Strategy::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: StableSwapAccount::Strategy(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = vault::Vault::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: StableSwapAccount::Vault(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            StableSwapAccount::Pool => pool::Pool,
+            StableSwapAccount::Strategy => strategy::Strategy,
+            StableSwapAccount::Vault => vault::Vault,
+        )
     }
 }
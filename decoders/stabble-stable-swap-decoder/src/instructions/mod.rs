@@ -56,6 +56,10 @@ pub enum StableSwapInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for StableSwapDecoder {
     type InstructionType = StableSwapInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
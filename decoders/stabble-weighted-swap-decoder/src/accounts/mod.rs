@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -14,6 +13,11 @@ pub enum WeightedSwapAccount {
 
 impl AccountDecoder<'_> for WeightedSwapDecoder {
     type AccountType = WeightedSwapAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -22,26 +26,9 @@ impl AccountDecoder<'_> for WeightedSwapDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = pool::Pool::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WeightedSwapAccount::Pool(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = vault::Vault::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WeightedSwapAccount::Vault(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            WeightedSwapAccount::Pool => pool::Pool,
+            WeightedSwapAccount::Vault => vault::Vault,
+        )
     }
 }
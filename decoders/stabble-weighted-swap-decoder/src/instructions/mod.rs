@@ -48,6 +48,10 @@ pub enum WeightedSwapInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for WeightedSwapDecoder {
     type InstructionType = WeightedSwapInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
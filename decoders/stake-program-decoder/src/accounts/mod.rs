@@ -6,6 +6,11 @@ pub enum StakeProgramAccount {}
 
 impl AccountDecoder<'_> for StakeProgramDecoder {
     type AccountType = StakeProgramAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         _account: &solana_account::Account,
@@ -48,6 +48,10 @@ pub enum StakeProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for StakeProgramDecoder {
     type InstructionType = StakeProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -16,6 +16,10 @@ pub enum SwigAccount {}
 impl<'a> carbon_core::account::AccountDecoder<'a> for SwigDecoder {
     type AccountType = SwigAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -57,6 +57,10 @@ pub enum SwigInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SwigDecoder {
     type InstructionType = SwigInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -67,123 +71,55 @@ impl carbon_core::instruction::InstructionDecoder<'_> for SwigDecoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = create_v1::CreateV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::CreateV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = add_authority_v1::AddAuthorityV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::AddAuthorityV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = remove_authority_v1::RemoveAuthorityV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::RemoveAuthorityV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_authority_v1::UpdateAuthorityV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::UpdateAuthorityV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sign_v1::SignV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::SignV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sign_v2::SignV2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::SignV2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_session_v1::CreateSessionV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::CreateSessionV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_sub_account_v1::CreateSubAccountV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::CreateSubAccountV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                withdraw_from_sub_account_v1::WithdrawFromSubAccountV1::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::WithdrawFromSubAccountV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sub_account_sign_v1::SubAccountSignV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::SubAccountSignV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = toggle_sub_account_v1::ToggleSubAccountV1::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::ToggleSubAccountV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                migrate_to_wallet_address_v1::MigrateToWalletAddressV1::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: SwigInstruction::MigrateToWalletAddressV1(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_assets_v1::TransferAssetsV1::decode(data) {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[0],
+                &[1],
+                &[2],
+                &[3],
+                &[4],
+                &[11],
+                &[5],
+                &[6],
+                &[7],
+                &[9],
+                &[10],
+                &[12],
+                &[13],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => create_v1::CreateV1::decode(data).map(SwigInstruction::CreateV1),
+                1 => add_authority_v1::AddAuthorityV1::decode(data)
+                    .map(SwigInstruction::AddAuthorityV1),
+                2 => remove_authority_v1::RemoveAuthorityV1::decode(data)
+                    .map(SwigInstruction::RemoveAuthorityV1),
+                3 => update_authority_v1::UpdateAuthorityV1::decode(data)
+                    .map(SwigInstruction::UpdateAuthorityV1),
+                4 => sign_v1::SignV1::decode(data).map(SwigInstruction::SignV1),
+                5 => sign_v2::SignV2::decode(data).map(SwigInstruction::SignV2),
+                6 => create_session_v1::CreateSessionV1::decode(data)
+                    .map(SwigInstruction::CreateSessionV1),
+                7 => create_sub_account_v1::CreateSubAccountV1::decode(data)
+                    .map(SwigInstruction::CreateSubAccountV1),
+                8 => withdraw_from_sub_account_v1::WithdrawFromSubAccountV1::decode(data)
+                    .map(SwigInstruction::WithdrawFromSubAccountV1),
+                9 => sub_account_sign_v1::SubAccountSignV1::decode(data)
+                    .map(SwigInstruction::SubAccountSignV1),
+                10 => toggle_sub_account_v1::ToggleSubAccountV1::decode(data)
+                    .map(SwigInstruction::ToggleSubAccountV1),
+                11 => migrate_to_wallet_address_v1::MigrateToWalletAddressV1::decode(data)
+                    .map(SwigInstruction::MigrateToWalletAddressV1),
+                12 => transfer_assets_v1::TransferAssetsV1::decode(data)
+                    .map(SwigInstruction::TransferAssetsV1),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: SwigInstruction::TransferAssetsV1(decoded),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
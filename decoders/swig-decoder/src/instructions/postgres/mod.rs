@@ -292,4 +292,162 @@ impl carbon_core::postgres::operations::Upsert for SwigInstructionWithMetadata {
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut create_v1_rows = Vec::new();
+        let mut add_authority_v1_rows = Vec::new();
+        let mut remove_authority_v1_rows = Vec::new();
+        let mut update_authority_v1_rows = Vec::new();
+        let mut sign_v1_rows = Vec::new();
+        let mut sign_v2_rows = Vec::new();
+        let mut create_session_v1_rows = Vec::new();
+        let mut create_sub_account_v1_rows = Vec::new();
+        let mut withdraw_from_sub_account_v1_rows = Vec::new();
+        let mut sub_account_sign_v1_rows = Vec::new();
+        let mut toggle_sub_account_v1_rows = Vec::new();
+        let mut migrate_to_wallet_address_v1_rows = Vec::new();
+        let mut transfer_assets_v1_rows = Vec::new();
+
+        for SwigInstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                SwigInstruction::CreateV1(instruction) => {
+                    create_v1_rows.push(create_v1_row::CreateV1Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwigInstruction::AddAuthorityV1(instruction) => {
+                    add_authority_v1_rows.push(
+                        add_authority_v1_row::AddAuthorityV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::RemoveAuthorityV1(instruction) => {
+                    remove_authority_v1_rows.push(
+                        remove_authority_v1_row::RemoveAuthorityV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::UpdateAuthorityV1(instruction) => {
+                    update_authority_v1_rows.push(
+                        update_authority_v1_row::UpdateAuthorityV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::SignV1(instruction) => {
+                    sign_v1_rows.push(sign_v1_row::SignV1Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwigInstruction::SignV2(instruction) => {
+                    sign_v2_rows.push(sign_v2_row::SignV2Row::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                SwigInstruction::CreateSessionV1(instruction) => {
+                    create_session_v1_rows.push(
+                        create_session_v1_row::CreateSessionV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::CreateSubAccountV1(instruction) => {
+                    create_sub_account_v1_rows.push(
+                        create_sub_account_v1_row::CreateSubAccountV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::WithdrawFromSubAccountV1(instruction) => {
+                    withdraw_from_sub_account_v1_rows.push(
+                        withdraw_from_sub_account_v1_row::WithdrawFromSubAccountV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::SubAccountSignV1(instruction) => {
+                    sub_account_sign_v1_rows.push(
+                        sub_account_sign_v1_row::SubAccountSignV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::ToggleSubAccountV1(instruction) => {
+                    toggle_sub_account_v1_rows.push(
+                        toggle_sub_account_v1_row::ToggleSubAccountV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::MigrateToWalletAddressV1(instruction) => {
+                    migrate_to_wallet_address_v1_rows.push(
+                        migrate_to_wallet_address_v1_row::MigrateToWalletAddressV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+                SwigInstruction::TransferAssetsV1(instruction) => {
+                    transfer_assets_v1_rows.push(
+                        transfer_assets_v1_row::TransferAssetsV1Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+
+        create_v1_row::CreateV1Row::upsert_many(&create_v1_rows, pool).await?;
+        add_authority_v1_row::AddAuthorityV1Row::upsert_many(&add_authority_v1_rows, pool).await?;
+        remove_authority_v1_row::RemoveAuthorityV1Row::upsert_many(&remove_authority_v1_rows, pool)
+            .await?;
+        update_authority_v1_row::UpdateAuthorityV1Row::upsert_many(&update_authority_v1_rows, pool)
+            .await?;
+        sign_v1_row::SignV1Row::upsert_many(&sign_v1_rows, pool).await?;
+        sign_v2_row::SignV2Row::upsert_many(&sign_v2_rows, pool).await?;
+        create_session_v1_row::CreateSessionV1Row::upsert_many(&create_session_v1_rows, pool)
+            .await?;
+        create_sub_account_v1_row::CreateSubAccountV1Row::upsert_many(
+            &create_sub_account_v1_rows,
+            pool,
+        )
+        .await?;
+        withdraw_from_sub_account_v1_row::WithdrawFromSubAccountV1Row::upsert_many(
+            &withdraw_from_sub_account_v1_rows,
+            pool,
+        )
+        .await?;
+        sub_account_sign_v1_row::SubAccountSignV1Row::upsert_many(&sub_account_sign_v1_rows, pool)
+            .await?;
+        toggle_sub_account_v1_row::ToggleSubAccountV1Row::upsert_many(
+            &toggle_sub_account_v1_rows,
+            pool,
+        )
+        .await?;
+        migrate_to_wallet_address_v1_row::MigrateToWalletAddressV1Row::upsert_many(
+            &migrate_to_wallet_address_v1_rows,
+            pool,
+        )
+        .await?;
+        transfer_assets_v1_row::TransferAssetsV1Row::upsert_many(&transfer_assets_v1_rows, pool)
+            .await?;
+        Ok(())
+    }
 }
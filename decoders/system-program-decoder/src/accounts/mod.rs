@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use super::SystemProgramDecoder;
 
@@ -13,6 +12,11 @@ pub enum SystemAccount {
 
 impl AccountDecoder<'_> for SystemProgramDecoder {
     type AccountType = SystemAccount;
+
+    fn program_ids(&self) -> alloc::vec::Vec<solana_pubkey::Pubkey> {
+        alloc::vec![solana_system_interface::program::id()]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -31,16 +35,8 @@ impl AccountDecoder<'_> for SystemProgramDecoder {
             });
         }
 
-        if let Some(decoded_account) = nonce::Nonce::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: SystemAccount::Nonce(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            SystemAccount::Nonce => nonce::Nonce,
+        )
     }
 }
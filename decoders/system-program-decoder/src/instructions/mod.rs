@@ -42,6 +42,10 @@ pub enum SystemProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for SystemProgramDecoder {
     type InstructionType = SystemProgramInstruction;
 
+    fn program_ids(&self) -> alloc::vec::Vec<solana_pubkey::Pubkey> {
+        alloc::vec![solana_system_interface::program::id()]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
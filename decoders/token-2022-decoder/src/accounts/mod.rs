@@ -26,6 +26,10 @@ pub enum Token2022Account {
 impl<'a> carbon_core::account::AccountDecoder<'a> for Token2022Decoder {
     type AccountType = Token2022Account;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &'a solana_account::Account,
@@ -144,6 +144,59 @@ impl carbon_core::postgres::operations::Upsert for MintRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(8)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO mint_account (
+                "mint_authority",
+                "supply",
+                "decimals",
+                "is_initialized",
+                "freeze_authority",
+                "extensions",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.mint_authority)
+                    .push_bind(&row.supply)
+                    .push_bind(row.decimals)
+                    .push_bind(row.is_initialized)
+                    .push_bind(row.freeze_authority)
+                    .push_bind(&row.extensions)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "mint_authority" = EXCLUDED."mint_authority",
+                "supply" = EXCLUDED."supply",
+                "decimals" = EXCLUDED."decimals",
+                "is_initialized" = EXCLUDED."is_initialized",
+                "freeze_authority" = EXCLUDED."freeze_authority",
+                "extensions" = EXCLUDED."extensions",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -90,4 +90,41 @@ impl carbon_core::postgres::operations::Upsert for Token2022AccountWithMetadata
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut mint_rows = Vec::new();
+        let mut token_rows = Vec::new();
+        let mut multisig_rows = Vec::new();
+
+        for Token2022AccountWithMetadata(account, metadata) in rows {
+            match account {
+                Token2022Account::Mint(account) => {
+                    mint_rows.push(mint_row::MintRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                Token2022Account::Token(account) => {
+                    token_rows.push(token_row::TokenRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+                Token2022Account::Multisig(account) => {
+                    multisig_rows.push(multisig_row::MultisigRow::from_parts(
+                        *account.clone(),
+                        metadata.clone(),
+                    ));
+                }
+            }
+        }
+
+        mint_row::MintRow::upsert_many(&mint_rows, pool).await?;
+        token_row::TokenRow::upsert_many(&token_rows, pool).await?;
+        multisig_row::MultisigRow::upsert_many(&multisig_rows, pool).await?;
+        Ok(())
+    }
 }
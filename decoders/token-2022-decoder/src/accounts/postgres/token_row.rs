@@ -167,6 +167,68 @@ impl carbon_core::postgres::operations::Upsert for TokenRow {
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            row.account_metadata.pubkey.0
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(11)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO token_account (
+                "mint",
+                "owner",
+                "amount",
+                "delegate",
+                "state",
+                "is_native",
+                "delegated_amount",
+                "close_authority",
+                "extensions",
+                __pubkey, __slot
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(row.mint)
+                    .push_bind(row.owner)
+                    .push_bind(&row.amount)
+                    .push_bind(row.delegate)
+                    .push_bind(&row.state)
+                    .push_bind(&row.is_native)
+                    .push_bind(&row.delegated_amount)
+                    .push_bind(row.close_authority)
+                    .push_bind(&row.extensions)
+                    .push_bind(row.account_metadata.pubkey)
+                    .push_bind(&row.account_metadata.slot);
+            });
+            query.push(
+                r#" ON CONFLICT (
+                __pubkey
+            ) DO UPDATE SET
+                "mint" = EXCLUDED."mint",
+                "owner" = EXCLUDED."owner",
+                "amount" = EXCLUDED."amount",
+                "delegate" = EXCLUDED."delegate",
+                "state" = EXCLUDED."state",
+                "is_native" = EXCLUDED."is_native",
+                "delegated_amount" = EXCLUDED."delegated_amount",
+                "close_authority" = EXCLUDED."close_authority",
+                "extensions" = EXCLUDED."extensions",
+                __slot = EXCLUDED.__slot
+            "#,
+            );
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
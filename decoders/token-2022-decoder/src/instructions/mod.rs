@@ -231,6 +231,10 @@ pub enum Token2022Instruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for Token2022Decoder {
     type InstructionType = Token2022Instruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -241,864 +245,193 @@ impl carbon_core::instruction::InstructionDecoder<'_> for Token2022Decoder {
 
         let data = instruction.data.as_slice();
 
-        {
-            if let Some(decoded) = initialize_mint::InitializeMint::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_account::InitializeAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_multisig::InitializeMultisig::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMultisig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer::Transfer::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Transfer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = approve::Approve::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Approve(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = revoke::Revoke::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Revoke(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_authority::SetAuthority::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::SetAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = mint_to::MintTo::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::MintTo(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = burn::Burn::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Burn(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = close_account::CloseAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::CloseAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = freeze_account::FreezeAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::FreezeAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = thaw_account::ThawAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ThawAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_checked::TransferChecked::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::TransferChecked(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = approve_checked::ApproveChecked::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ApproveChecked(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = mint_to_checked::MintToChecked::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::MintToChecked(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = burn_checked::BurnChecked::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::BurnChecked(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_account2::InitializeAccount2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeAccount2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = sync_native::SyncNative::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::SyncNative(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_account3::InitializeAccount3::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeAccount3(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_multisig2::InitializeMultisig2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMultisig2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_mint2::InitializeMint2::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMint2(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = get_account_data_size::GetAccountDataSize::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::GetAccountDataSize(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_immutable_owner::InitializeImmutableOwner::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeImmutableOwner(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = amount_to_ui_amount::AmountToUiAmount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::AmountToUiAmount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = ui_amount_to_amount::UiAmountToAmount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UiAmountToAmount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_mint_close_authority::InitializeMintCloseAuthority::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMintCloseAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_transfer_fee_config::InitializeTransferFeeConfig::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeTransferFeeConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = transfer_checked_with_fee::TransferCheckedWithFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::TransferCheckedWithFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                withdraw_withheld_tokens_from_mint::WithdrawWithheldTokensFromMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::WithdrawWithheldTokensFromMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                withdraw_withheld_tokens_from_accounts::WithdrawWithheldTokensFromAccounts::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::WithdrawWithheldTokensFromAccounts(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                harvest_withheld_tokens_to_mint::HarvestWithheldTokensToMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::HarvestWithheldTokensToMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = set_transfer_fee::SetTransferFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::SetTransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_confidential_transfer_mint::InitializeConfidentialTransferMint::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeConfidentialTransferMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_confidential_transfer_mint::UpdateConfidentialTransferMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateConfidentialTransferMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = configure_confidential_transfer_account::ConfigureConfidentialTransferAccount::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ConfigureConfidentialTransferAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                approve_confidential_transfer_account::ApproveConfidentialTransferAccount::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ApproveConfidentialTransferAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                empty_confidential_transfer_account::EmptyConfidentialTransferAccount::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EmptyConfidentialTransferAccount(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = confidential_deposit::ConfidentialDeposit::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ConfidentialDeposit(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = confidential_withdraw::ConfidentialWithdraw::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ConfidentialWithdraw(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = confidential_transfer::ConfidentialTransfer::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ConfidentialTransfer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                apply_confidential_pending_balance::ApplyConfidentialPendingBalance::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ApplyConfidentialPendingBalance(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                enable_confidential_credits::EnableConfidentialCredits::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EnableConfidentialCredits(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                disable_confidential_credits::DisableConfidentialCredits::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::DisableConfidentialCredits(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                enable_non_confidential_credits::EnableNonConfidentialCredits::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EnableNonConfidentialCredits(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                disable_non_confidential_credits::DisableNonConfidentialCredits::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::DisableNonConfidentialCredits(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                confidential_transfer_with_fee::ConfidentialTransferWithFee::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::ConfidentialTransferWithFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_default_account_state::InitializeDefaultAccountState::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeDefaultAccountState(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_default_account_state::UpdateDefaultAccountState::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateDefaultAccountState(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = reallocate::Reallocate::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Reallocate(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = enable_memo_transfers::EnableMemoTransfers::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EnableMemoTransfers(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = disable_memo_transfers::DisableMemoTransfers::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::DisableMemoTransfers(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = create_native_mint::CreateNativeMint::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::CreateNativeMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_non_transferable_mint::InitializeNonTransferableMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeNonTransferableMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_interest_bearing_mint::InitializeInterestBearingMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeInterestBearingMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_rate_interest_bearing_mint::UpdateRateInterestBearingMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateRateInterestBearingMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = enable_cpi_guard::EnableCpiGuard::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EnableCpiGuard(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = disable_cpi_guard::DisableCpiGuard::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::DisableCpiGuard(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_permanent_delegate::InitializePermanentDelegate::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializePermanentDelegate(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_transfer_hook::InitializeTransferHook::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeTransferHook(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_transfer_hook::UpdateTransferHook::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateTransferHook(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_confidential_transfer_fee::InitializeConfidentialTransferFee::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeConfidentialTransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = withdraw_withheld_tokens_from_mint_for_confidential_transfer_fee::WithdrawWithheldTokensFromMintForConfidentialTransferFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::WithdrawWithheldTokensFromMintForConfidentialTransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = withdraw_withheld_tokens_from_accounts_for_confidential_transfer_fee::WithdrawWithheldTokensFromAccountsForConfidentialTransferFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::WithdrawWithheldTokensFromAccountsForConfidentialTransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = harvest_withheld_tokens_to_mint_for_confidential_transfer_fee::HarvestWithheldTokensToMintForConfidentialTransferFee::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::HarvestWithheldTokensToMintForConfidentialTransferFee(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = enable_harvest_to_mint::EnableHarvestToMint::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EnableHarvestToMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = disable_harvest_to_mint::DisableHarvestToMint::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::DisableHarvestToMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = withdraw_excess_lamports::WithdrawExcessLamports::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::WithdrawExcessLamports(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_metadata_pointer::InitializeMetadataPointer::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeMetadataPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_metadata_pointer::UpdateMetadataPointer::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateMetadataPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_group_pointer::InitializeGroupPointer::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeGroupPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = update_group_pointer::UpdateGroupPointer::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateGroupPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_group_member_pointer::InitializeGroupMemberPointer::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeGroupMemberPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_group_member_pointer::UpdateGroupMemberPointer::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateGroupMemberPointer(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_scaled_ui_amount_mint::InitializeScaledUiAmountMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeScaledUiAmountMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_multiplier_scaled_ui_mint::UpdateMultiplierScaledUiMint::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateMultiplierScaledUiMint(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_pausable_config::InitializePausableConfig::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializePausableConfig(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = pause::Pause::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Pause(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = resume::Resume::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::Resume(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_token_metadata::InitializeTokenMetadata::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeTokenMetadata(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_token_metadata_field::UpdateTokenMetadataField::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateTokenMetadataField(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = remove_token_metadata_key::RemoveTokenMetadataKey::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::RemoveTokenMetadataKey(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_token_metadata_update_authority::UpdateTokenMetadataUpdateAuthority::decode(
-                    data,
-                )
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateTokenMetadataUpdateAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = emit_token_metadata::EmitTokenMetadata::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::EmitTokenMetadata(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) = initialize_token_group::InitializeTokenGroup::decode(data) {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeTokenGroup(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_token_group_max_size::UpdateTokenGroupMaxSize::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateTokenGroupMaxSize(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                update_token_group_update_authority::UpdateTokenGroupUpdateAuthority::decode(data)
-            {
-                return Some(carbon_core::instruction::DecodedInstruction {
-                    program_id: instruction.program_id,
-                    data: Token2022Instruction::UpdateTokenGroupUpdateAuthority(decoded),
-                    accounts: instruction.accounts.clone(),
-                });
-            }
-        }
-        {
-            if let Some(decoded) =
-                initialize_token_group_member::InitializeTokenGroupMember::decode(data)
-            {
+        static INDEX: carbon_core::deserialize::LazyDiscriminatorIndex =
+            carbon_core::deserialize::LazyDiscriminatorIndex::new(&[
+                &[210, 225, 30, 162, 88, 184, 77, 141],
+                &[221, 233, 49, 45, 181, 202, 220, 200],
+                &[234, 18, 32, 56, 89, 141, 37, 181],
+                &[215, 228, 166, 228, 84, 100, 86, 123],
+                &[250, 166, 180, 250, 13, 12, 184, 70],
+                &[121, 113, 108, 39, 54, 51, 0, 4],
+                &[108, 37, 171, 143, 248, 30, 18, 110],
+                &[161, 105, 88, 1, 237, 221, 216, 203],
+                &[152, 32, 222, 176, 223, 237, 116, 134],
+                &[26, 0],
+                &[26, 1],
+                &[26, 2],
+                &[26, 3],
+                &[26, 4],
+                &[26, 5],
+                &[27, 0],
+                &[27, 1],
+                &[27, 2],
+                &[27, 3],
+                &[27, 4],
+                &[27, 5],
+                &[27, 6],
+                &[27, 7],
+                &[27, 8],
+                &[27, 9],
+                &[27, 10],
+                &[27, 11],
+                &[27, 12],
+                &[27, 13],
+                &[28, 0],
+                &[28, 1],
+                &[30, 0],
+                &[30, 1],
+                &[33, 0],
+                &[33, 1],
+                &[34, 0],
+                &[34, 1],
+                &[36, 0],
+                &[36, 1],
+                &[37, 0],
+                &[37, 1],
+                &[37, 2],
+                &[37, 3],
+                &[37, 4],
+                &[37, 5],
+                &[39, 0],
+                &[39, 1],
+                &[40, 0],
+                &[40, 1],
+                &[41, 0],
+                &[41, 1],
+                &[43, 0],
+                &[43, 1],
+                &[44, 0],
+                &[44, 1],
+                &[44, 2],
+                &[0],
+                &[1],
+                &[2],
+                &[3],
+                &[4],
+                &[5],
+                &[6],
+                &[7],
+                &[8],
+                &[9],
+                &[10],
+                &[11],
+                &[12],
+                &[13],
+                &[14],
+                &[15],
+                &[16],
+                &[17],
+                &[18],
+                &[19],
+                &[20],
+                &[21],
+                &[22],
+                &[23],
+                &[24],
+                &[25],
+                &[29],
+                &[31],
+                &[32],
+                &[35],
+                &[38],
+            ]);
+
+        for position in INDEX.get().candidates(data) {
+            let decoded = match position {
+                0 => initialize_token_metadata::InitializeTokenMetadata::decode(data).map(Token2022Instruction::InitializeTokenMetadata),
+                1 => update_token_metadata_field::UpdateTokenMetadataField::decode(data).map(Token2022Instruction::UpdateTokenMetadataField),
+                2 => remove_token_metadata_key::RemoveTokenMetadataKey::decode(data).map(Token2022Instruction::RemoveTokenMetadataKey),
+                3 => update_token_metadata_update_authority::UpdateTokenMetadataUpdateAuthority::decode(data).map(Token2022Instruction::UpdateTokenMetadataUpdateAuthority),
+                4 => emit_token_metadata::EmitTokenMetadata::decode(data).map(Token2022Instruction::EmitTokenMetadata),
+                5 => initialize_token_group::InitializeTokenGroup::decode(data).map(Token2022Instruction::InitializeTokenGroup),
+                6 => update_token_group_max_size::UpdateTokenGroupMaxSize::decode(data).map(Token2022Instruction::UpdateTokenGroupMaxSize),
+                7 => update_token_group_update_authority::UpdateTokenGroupUpdateAuthority::decode(data).map(Token2022Instruction::UpdateTokenGroupUpdateAuthority),
+                8 => initialize_token_group_member::InitializeTokenGroupMember::decode(data).map(Token2022Instruction::InitializeTokenGroupMember),
+                9 => initialize_transfer_fee_config::InitializeTransferFeeConfig::decode(data).map(Token2022Instruction::InitializeTransferFeeConfig),
+                10 => transfer_checked_with_fee::TransferCheckedWithFee::decode(data).map(Token2022Instruction::TransferCheckedWithFee),
+                11 => withdraw_withheld_tokens_from_mint::WithdrawWithheldTokensFromMint::decode(data).map(Token2022Instruction::WithdrawWithheldTokensFromMint),
+                12 => withdraw_withheld_tokens_from_accounts::WithdrawWithheldTokensFromAccounts::decode(data).map(Token2022Instruction::WithdrawWithheldTokensFromAccounts),
+                13 => harvest_withheld_tokens_to_mint::HarvestWithheldTokensToMint::decode(data).map(Token2022Instruction::HarvestWithheldTokensToMint),
+                14 => set_transfer_fee::SetTransferFee::decode(data).map(Token2022Instruction::SetTransferFee),
+                15 => initialize_confidential_transfer_mint::InitializeConfidentialTransferMint::decode(data).map(Token2022Instruction::InitializeConfidentialTransferMint),
+                16 => update_confidential_transfer_mint::UpdateConfidentialTransferMint::decode(data).map(Token2022Instruction::UpdateConfidentialTransferMint),
+                17 => configure_confidential_transfer_account::ConfigureConfidentialTransferAccount::decode(data).map(Token2022Instruction::ConfigureConfidentialTransferAccount),
+                18 => approve_confidential_transfer_account::ApproveConfidentialTransferAccount::decode(data).map(Token2022Instruction::ApproveConfidentialTransferAccount),
+                19 => empty_confidential_transfer_account::EmptyConfidentialTransferAccount::decode(data).map(Token2022Instruction::EmptyConfidentialTransferAccount),
+                20 => confidential_deposit::ConfidentialDeposit::decode(data).map(Token2022Instruction::ConfidentialDeposit),
+                21 => confidential_withdraw::ConfidentialWithdraw::decode(data).map(Token2022Instruction::ConfidentialWithdraw),
+                22 => confidential_transfer::ConfidentialTransfer::decode(data).map(Token2022Instruction::ConfidentialTransfer),
+                23 => apply_confidential_pending_balance::ApplyConfidentialPendingBalance::decode(data).map(Token2022Instruction::ApplyConfidentialPendingBalance),
+                24 => enable_confidential_credits::EnableConfidentialCredits::decode(data).map(Token2022Instruction::EnableConfidentialCredits),
+                25 => disable_confidential_credits::DisableConfidentialCredits::decode(data).map(Token2022Instruction::DisableConfidentialCredits),
+                26 => enable_non_confidential_credits::EnableNonConfidentialCredits::decode(data).map(Token2022Instruction::EnableNonConfidentialCredits),
+                27 => disable_non_confidential_credits::DisableNonConfidentialCredits::decode(data).map(Token2022Instruction::DisableNonConfidentialCredits),
+                28 => confidential_transfer_with_fee::ConfidentialTransferWithFee::decode(data).map(Token2022Instruction::ConfidentialTransferWithFee),
+                29 => initialize_default_account_state::InitializeDefaultAccountState::decode(data).map(Token2022Instruction::InitializeDefaultAccountState),
+                30 => update_default_account_state::UpdateDefaultAccountState::decode(data).map(Token2022Instruction::UpdateDefaultAccountState),
+                31 => enable_memo_transfers::EnableMemoTransfers::decode(data).map(Token2022Instruction::EnableMemoTransfers),
+                32 => disable_memo_transfers::DisableMemoTransfers::decode(data).map(Token2022Instruction::DisableMemoTransfers),
+                33 => initialize_interest_bearing_mint::InitializeInterestBearingMint::decode(data).map(Token2022Instruction::InitializeInterestBearingMint),
+                34 => update_rate_interest_bearing_mint::UpdateRateInterestBearingMint::decode(data).map(Token2022Instruction::UpdateRateInterestBearingMint),
+                35 => enable_cpi_guard::EnableCpiGuard::decode(data).map(Token2022Instruction::EnableCpiGuard),
+                36 => disable_cpi_guard::DisableCpiGuard::decode(data).map(Token2022Instruction::DisableCpiGuard),
+                37 => initialize_transfer_hook::InitializeTransferHook::decode(data).map(Token2022Instruction::InitializeTransferHook),
+                38 => update_transfer_hook::UpdateTransferHook::decode(data).map(Token2022Instruction::UpdateTransferHook),
+                39 => initialize_confidential_transfer_fee::InitializeConfidentialTransferFee::decode(data).map(Token2022Instruction::InitializeConfidentialTransferFee),
+                40 => withdraw_withheld_tokens_from_mint_for_confidential_transfer_fee::WithdrawWithheldTokensFromMintForConfidentialTransferFee::decode(data).map(Token2022Instruction::WithdrawWithheldTokensFromMintForConfidentialTransferFee),
+                41 => withdraw_withheld_tokens_from_accounts_for_confidential_transfer_fee::WithdrawWithheldTokensFromAccountsForConfidentialTransferFee::decode(data).map(Token2022Instruction::WithdrawWithheldTokensFromAccountsForConfidentialTransferFee),
+                42 => harvest_withheld_tokens_to_mint_for_confidential_transfer_fee::HarvestWithheldTokensToMintForConfidentialTransferFee::decode(data).map(Token2022Instruction::HarvestWithheldTokensToMintForConfidentialTransferFee),
+                43 => enable_harvest_to_mint::EnableHarvestToMint::decode(data).map(Token2022Instruction::EnableHarvestToMint),
+                44 => disable_harvest_to_mint::DisableHarvestToMint::decode(data).map(Token2022Instruction::DisableHarvestToMint),
+                45 => initialize_metadata_pointer::InitializeMetadataPointer::decode(data).map(Token2022Instruction::InitializeMetadataPointer),
+                46 => update_metadata_pointer::UpdateMetadataPointer::decode(data).map(Token2022Instruction::UpdateMetadataPointer),
+                47 => initialize_group_pointer::InitializeGroupPointer::decode(data).map(Token2022Instruction::InitializeGroupPointer),
+                48 => update_group_pointer::UpdateGroupPointer::decode(data).map(Token2022Instruction::UpdateGroupPointer),
+                49 => initialize_group_member_pointer::InitializeGroupMemberPointer::decode(data).map(Token2022Instruction::InitializeGroupMemberPointer),
+                50 => update_group_member_pointer::UpdateGroupMemberPointer::decode(data).map(Token2022Instruction::UpdateGroupMemberPointer),
+                51 => initialize_scaled_ui_amount_mint::InitializeScaledUiAmountMint::decode(data).map(Token2022Instruction::InitializeScaledUiAmountMint),
+                52 => update_multiplier_scaled_ui_mint::UpdateMultiplierScaledUiMint::decode(data).map(Token2022Instruction::UpdateMultiplierScaledUiMint),
+                53 => initialize_pausable_config::InitializePausableConfig::decode(data).map(Token2022Instruction::InitializePausableConfig),
+                54 => pause::Pause::decode(data).map(Token2022Instruction::Pause),
+                55 => resume::Resume::decode(data).map(Token2022Instruction::Resume),
+                56 => initialize_mint::InitializeMint::decode(data).map(Token2022Instruction::InitializeMint),
+                57 => initialize_account::InitializeAccount::decode(data).map(Token2022Instruction::InitializeAccount),
+                58 => initialize_multisig::InitializeMultisig::decode(data).map(Token2022Instruction::InitializeMultisig),
+                59 => transfer::Transfer::decode(data).map(Token2022Instruction::Transfer),
+                60 => approve::Approve::decode(data).map(Token2022Instruction::Approve),
+                61 => revoke::Revoke::decode(data).map(Token2022Instruction::Revoke),
+                62 => set_authority::SetAuthority::decode(data).map(Token2022Instruction::SetAuthority),
+                63 => mint_to::MintTo::decode(data).map(Token2022Instruction::MintTo),
+                64 => burn::Burn::decode(data).map(Token2022Instruction::Burn),
+                65 => close_account::CloseAccount::decode(data).map(Token2022Instruction::CloseAccount),
+                66 => freeze_account::FreezeAccount::decode(data).map(Token2022Instruction::FreezeAccount),
+                67 => thaw_account::ThawAccount::decode(data).map(Token2022Instruction::ThawAccount),
+                68 => transfer_checked::TransferChecked::decode(data).map(Token2022Instruction::TransferChecked),
+                69 => approve_checked::ApproveChecked::decode(data).map(Token2022Instruction::ApproveChecked),
+                70 => mint_to_checked::MintToChecked::decode(data).map(Token2022Instruction::MintToChecked),
+                71 => burn_checked::BurnChecked::decode(data).map(Token2022Instruction::BurnChecked),
+                72 => initialize_account2::InitializeAccount2::decode(data).map(Token2022Instruction::InitializeAccount2),
+                73 => sync_native::SyncNative::decode(data).map(Token2022Instruction::SyncNative),
+                74 => initialize_account3::InitializeAccount3::decode(data).map(Token2022Instruction::InitializeAccount3),
+                75 => initialize_multisig2::InitializeMultisig2::decode(data).map(Token2022Instruction::InitializeMultisig2),
+                76 => initialize_mint2::InitializeMint2::decode(data).map(Token2022Instruction::InitializeMint2),
+                77 => get_account_data_size::GetAccountDataSize::decode(data).map(Token2022Instruction::GetAccountDataSize),
+                78 => initialize_immutable_owner::InitializeImmutableOwner::decode(data).map(Token2022Instruction::InitializeImmutableOwner),
+                79 => amount_to_ui_amount::AmountToUiAmount::decode(data).map(Token2022Instruction::AmountToUiAmount),
+                80 => ui_amount_to_amount::UiAmountToAmount::decode(data).map(Token2022Instruction::UiAmountToAmount),
+                81 => initialize_mint_close_authority::InitializeMintCloseAuthority::decode(data).map(Token2022Instruction::InitializeMintCloseAuthority),
+                82 => reallocate::Reallocate::decode(data).map(Token2022Instruction::Reallocate),
+                83 => create_native_mint::CreateNativeMint::decode(data).map(Token2022Instruction::CreateNativeMint),
+                84 => initialize_non_transferable_mint::InitializeNonTransferableMint::decode(data).map(Token2022Instruction::InitializeNonTransferableMint),
+                85 => initialize_permanent_delegate::InitializePermanentDelegate::decode(data).map(Token2022Instruction::InitializePermanentDelegate),
+                86 => withdraw_excess_lamports::WithdrawExcessLamports::decode(data).map(Token2022Instruction::WithdrawExcessLamports),
+                _ => None,
+            };
+
+            if let Some(decoded) = decoded {
                 return Some(carbon_core::instruction::DecodedInstruction {
                     program_id: instruction.program_id,
-                    data: Token2022Instruction::InitializeTokenGroupMember(decoded),
+                    data: decoded,
                     accounts: instruction.accounts.clone(),
                 });
             }
@@ -162,6 +162,67 @@ impl carbon_core::postgres::operations::Upsert for ConfidentialTransferWithFeeRo
         .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
         Ok(())
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let rows = carbon_core::postgres::operations::last_per_key(rows, |row| {
+            (
+                row.instruction_metadata.signature.clone(),
+                row.instruction_metadata.instruction_index.0,
+                row.instruction_metadata.stack_height.0,
+            )
+        });
+
+        for chunk in rows.chunks(carbon_core::postgres::operations::rows_per_statement(11)) {
+            let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                r#"INSERT INTO confidential_transfer_with_fee_instruction (
+                "new_source_decryptable_available_balance",
+                "equality_proof_instruction_offset",
+                "transfer_amount_ciphertext_validity_proof_instruction_offset",
+                "fee_sigma_proof_instruction_offset",
+                "fee_ciphertext_validity_proof_instruction_offset",
+                "range_proof_instruction_offset",
+                __signature, __instruction_index, __stack_height, __slot, __accounts
+            ) "#,
+            );
+            query.push_values(chunk.iter().copied(), |mut values, row| {
+                values
+                    .push_bind(&row.new_source_decryptable_available_balance)
+                    .push_bind(row.equality_proof_instruction_offset)
+                    .push_bind(row.transfer_amount_ciphertext_validity_proof_instruction_offset)
+                    .push_bind(row.fee_sigma_proof_instruction_offset)
+                    .push_bind(row.fee_ciphertext_validity_proof_instruction_offset)
+                    .push_bind(row.range_proof_instruction_offset)
+                    .push_bind(&row.instruction_metadata.signature)
+                    .push_bind(row.instruction_metadata.instruction_index)
+                    .push_bind(row.instruction_metadata.stack_height)
+                    .push_bind(&row.instruction_metadata.slot)
+                    .push_bind(&row.accounts);
+            });
+            query.push(r#" ON CONFLICT (
+                __signature, __instruction_index, __stack_height
+            ) DO UPDATE SET
+                "new_source_decryptable_available_balance" = EXCLUDED."new_source_decryptable_available_balance",
+                "equality_proof_instruction_offset" = EXCLUDED."equality_proof_instruction_offset",
+                "transfer_amount_ciphertext_validity_proof_instruction_offset" = EXCLUDED."transfer_amount_ciphertext_validity_proof_instruction_offset",
+                "fee_sigma_proof_instruction_offset" = EXCLUDED."fee_sigma_proof_instruction_offset",
+                "fee_ciphertext_validity_proof_instruction_offset" = EXCLUDED."fee_ciphertext_validity_proof_instruction_offset",
+                "range_proof_instruction_offset" = EXCLUDED."range_proof_instruction_offset",
+                __instruction_index = EXCLUDED.__instruction_index,
+                __stack_height = EXCLUDED.__stack_height,
+                __slot = EXCLUDED.__slot,
+                __accounts = EXCLUDED.__accounts
+            "#);
+            query
+                .build()
+                .execute(pool)
+                .await
+                .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
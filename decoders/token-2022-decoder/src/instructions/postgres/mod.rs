@@ -1680,4 +1680,961 @@ impl carbon_core::postgres::operations::Upsert for Token2022InstructionWithMetad
             }
         }
     }
+
+    async fn upsert_many(rows: &[Self], pool: &sqlx::PgPool) -> carbon_core::error::CarbonResult<()>
+    where
+        Self: Sized + Sync,
+    {
+        let mut initialize_mint_rows = Vec::new();
+        let mut initialize_account_rows = Vec::new();
+        let mut initialize_multisig_rows = Vec::new();
+        let mut transfer_rows = Vec::new();
+        let mut approve_rows = Vec::new();
+        let mut revoke_rows = Vec::new();
+        let mut set_authority_rows = Vec::new();
+        let mut mint_to_rows = Vec::new();
+        let mut burn_rows = Vec::new();
+        let mut close_account_rows = Vec::new();
+        let mut freeze_account_rows = Vec::new();
+        let mut thaw_account_rows = Vec::new();
+        let mut transfer_checked_rows = Vec::new();
+        let mut approve_checked_rows = Vec::new();
+        let mut mint_to_checked_rows = Vec::new();
+        let mut burn_checked_rows = Vec::new();
+        let mut initialize_account2_rows = Vec::new();
+        let mut sync_native_rows = Vec::new();
+        let mut initialize_account3_rows = Vec::new();
+        let mut initialize_multisig2_rows = Vec::new();
+        let mut initialize_mint2_rows = Vec::new();
+        let mut get_account_data_size_rows = Vec::new();
+        let mut initialize_immutable_owner_rows = Vec::new();
+        let mut amount_to_ui_amount_rows = Vec::new();
+        let mut ui_amount_to_amount_rows = Vec::new();
+        let mut initialize_mint_close_authority_rows = Vec::new();
+        let mut initialize_transfer_fee_config_rows = Vec::new();
+        let mut transfer_checked_with_fee_rows = Vec::new();
+        let mut withdraw_withheld_tokens_from_mint_rows = Vec::new();
+        let mut withdraw_withheld_tokens_from_accounts_rows = Vec::new();
+        let mut harvest_withheld_tokens_to_mint_rows = Vec::new();
+        let mut set_transfer_fee_rows = Vec::new();
+        let mut initialize_confidential_transfer_mint_rows = Vec::new();
+        let mut update_confidential_transfer_mint_rows = Vec::new();
+        let mut configure_confidential_transfer_account_rows = Vec::new();
+        let mut approve_confidential_transfer_account_rows = Vec::new();
+        let mut empty_confidential_transfer_account_rows = Vec::new();
+        let mut confidential_deposit_rows = Vec::new();
+        let mut confidential_withdraw_rows = Vec::new();
+        let mut confidential_transfer_rows = Vec::new();
+        let mut apply_confidential_pending_balance_rows = Vec::new();
+        let mut enable_confidential_credits_rows = Vec::new();
+        let mut disable_confidential_credits_rows = Vec::new();
+        let mut enable_non_confidential_credits_rows = Vec::new();
+        let mut disable_non_confidential_credits_rows = Vec::new();
+        let mut confidential_transfer_with_fee_rows = Vec::new();
+        let mut initialize_default_account_state_rows = Vec::new();
+        let mut update_default_account_state_rows = Vec::new();
+        let mut reallocate_rows = Vec::new();
+        let mut enable_memo_transfers_rows = Vec::new();
+        let mut disable_memo_transfers_rows = Vec::new();
+        let mut create_native_mint_rows = Vec::new();
+        let mut initialize_non_transferable_mint_rows = Vec::new();
+        let mut initialize_interest_bearing_mint_rows = Vec::new();
+        let mut update_rate_interest_bearing_mint_rows = Vec::new();
+        let mut enable_cpi_guard_rows = Vec::new();
+        let mut disable_cpi_guard_rows = Vec::new();
+        let mut initialize_permanent_delegate_rows = Vec::new();
+        let mut initialize_transfer_hook_rows = Vec::new();
+        let mut update_transfer_hook_rows = Vec::new();
+        let mut initialize_confidential_transfer_fee_rows = Vec::new();
+        let mut enable_harvest_to_mint_rows = Vec::new();
+        let mut disable_harvest_to_mint_rows = Vec::new();
+        let mut withdraw_excess_lamports_rows = Vec::new();
+        let mut initialize_metadata_pointer_rows = Vec::new();
+        let mut update_metadata_pointer_rows = Vec::new();
+        let mut initialize_group_pointer_rows = Vec::new();
+        let mut update_group_pointer_rows = Vec::new();
+        let mut initialize_group_member_pointer_rows = Vec::new();
+        let mut update_group_member_pointer_rows = Vec::new();
+        let mut initialize_scaled_ui_amount_mint_rows = Vec::new();
+        let mut update_multiplier_scaled_ui_mint_rows = Vec::new();
+        let mut initialize_pausable_config_rows = Vec::new();
+        let mut pause_rows = Vec::new();
+        let mut resume_rows = Vec::new();
+        let mut initialize_token_metadata_rows = Vec::new();
+        let mut update_token_metadata_field_rows = Vec::new();
+        let mut remove_token_metadata_key_rows = Vec::new();
+        let mut update_token_metadata_update_authority_rows = Vec::new();
+        let mut emit_token_metadata_rows = Vec::new();
+        let mut initialize_token_group_rows = Vec::new();
+        let mut update_token_group_max_size_rows = Vec::new();
+        let mut update_token_group_update_authority_rows = Vec::new();
+        let mut initialize_token_group_member_rows = Vec::new();
+
+        for Token2022InstructionWithMetadata(instruction, metadata, accounts) in rows {
+            match instruction {
+                Token2022Instruction::InitializeMint(instruction) => {
+                    initialize_mint_rows.push(initialize_mint_row::InitializeMintRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::InitializeAccount(instruction) => {
+                    initialize_account_rows.push(
+                        initialize_account_row::InitializeAccountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeMultisig(instruction) => {
+                    initialize_multisig_rows.push(
+                        initialize_multisig_row::InitializeMultisigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::Transfer(instruction) => {
+                    transfer_rows.push(transfer_row::TransferRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::Approve(instruction) => {
+                    approve_rows.push(approve_row::ApproveRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::Revoke(instruction) => {
+                    revoke_rows.push(revoke_row::RevokeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::SetAuthority(instruction) => {
+                    set_authority_rows.push(set_authority_row::SetAuthorityRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::MintTo(instruction) => {
+                    mint_to_rows.push(mint_to_row::MintToRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::Burn(instruction) => {
+                    burn_rows.push(burn_row::BurnRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::CloseAccount(instruction) => {
+                    close_account_rows.push(close_account_row::CloseAccountRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::FreezeAccount(instruction) => {
+                    freeze_account_rows.push(freeze_account_row::FreezeAccountRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::ThawAccount(instruction) => {
+                    thaw_account_rows.push(thaw_account_row::ThawAccountRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::TransferChecked(instruction) => {
+                    transfer_checked_rows.push(
+                        transfer_checked_row::TransferCheckedRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::ApproveChecked(instruction) => {
+                    approve_checked_rows.push(approve_checked_row::ApproveCheckedRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::MintToChecked(instruction) => {
+                    mint_to_checked_rows.push(mint_to_checked_row::MintToCheckedRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::BurnChecked(instruction) => {
+                    burn_checked_rows.push(burn_checked_row::BurnCheckedRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::InitializeAccount2(instruction) => {
+                    initialize_account2_rows.push(
+                        initialize_account2_row::InitializeAccount2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::SyncNative(instruction) => {
+                    sync_native_rows.push(sync_native_row::SyncNativeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::InitializeAccount3(instruction) => {
+                    initialize_account3_rows.push(
+                        initialize_account3_row::InitializeAccount3Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeMultisig2(instruction) => {
+                    initialize_multisig2_rows.push(
+                        initialize_multisig2_row::InitializeMultisig2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeMint2(instruction) => {
+                    initialize_mint2_rows.push(
+                        initialize_mint2_row::InitializeMint2Row::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::GetAccountDataSize(instruction) => {
+                    get_account_data_size_rows.push(
+                        get_account_data_size_row::GetAccountDataSizeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeImmutableOwner(instruction) => {
+                    initialize_immutable_owner_rows.push(
+                        initialize_immutable_owner_row::InitializeImmutableOwnerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::AmountToUiAmount(instruction) => {
+                    amount_to_ui_amount_rows.push(
+                        amount_to_ui_amount_row::AmountToUiAmountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UiAmountToAmount(instruction) => {
+                    ui_amount_to_amount_rows.push(
+                        ui_amount_to_amount_row::UiAmountToAmountRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeMintCloseAuthority(instruction) => {
+                    initialize_mint_close_authority_rows.push(initialize_mint_close_authority_row::InitializeMintCloseAuthorityRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializeTransferFeeConfig(instruction) => {
+                    initialize_transfer_fee_config_rows.push(initialize_transfer_fee_config_row::InitializeTransferFeeConfigRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::TransferCheckedWithFee(instruction) => {
+                    transfer_checked_with_fee_rows.push(
+                        transfer_checked_with_fee_row::TransferCheckedWithFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::WithdrawWithheldTokensFromMint(instruction) => {
+                    withdraw_withheld_tokens_from_mint_rows.push(withdraw_withheld_tokens_from_mint_row::WithdrawWithheldTokensFromMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::WithdrawWithheldTokensFromAccounts(instruction) => {
+                    withdraw_withheld_tokens_from_accounts_rows.push(withdraw_withheld_tokens_from_accounts_row::WithdrawWithheldTokensFromAccountsRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::HarvestWithheldTokensToMint(instruction) => {
+                    harvest_withheld_tokens_to_mint_rows.push(harvest_withheld_tokens_to_mint_row::HarvestWithheldTokensToMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::SetTransferFee(instruction) => {
+                    set_transfer_fee_rows.push(
+                        set_transfer_fee_row::SetTransferFeeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeConfidentialTransferMint(instruction) => {
+                    initialize_confidential_transfer_mint_rows.push(initialize_confidential_transfer_mint_row::InitializeConfidentialTransferMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::UpdateConfidentialTransferMint(instruction) => {
+                    update_confidential_transfer_mint_rows.push(update_confidential_transfer_mint_row::UpdateConfidentialTransferMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::ConfigureConfidentialTransferAccount(instruction) => {
+                    configure_confidential_transfer_account_rows.push(configure_confidential_transfer_account_row::ConfigureConfidentialTransferAccountRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::ApproveConfidentialTransferAccount(instruction) => {
+                    approve_confidential_transfer_account_rows.push(approve_confidential_transfer_account_row::ApproveConfidentialTransferAccountRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::EmptyConfidentialTransferAccount(instruction) => {
+                    empty_confidential_transfer_account_rows.push(empty_confidential_transfer_account_row::EmptyConfidentialTransferAccountRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::ConfidentialDeposit(instruction) => {
+                    confidential_deposit_rows.push(
+                        confidential_deposit_row::ConfidentialDepositRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::ConfidentialWithdraw(instruction) => {
+                    confidential_withdraw_rows.push(
+                        confidential_withdraw_row::ConfidentialWithdrawRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::ConfidentialTransfer(instruction) => {
+                    confidential_transfer_rows.push(
+                        confidential_transfer_row::ConfidentialTransferRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::ApplyConfidentialPendingBalance(instruction) => {
+                    apply_confidential_pending_balance_rows.push(apply_confidential_pending_balance_row::ApplyConfidentialPendingBalanceRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::EnableConfidentialCredits(instruction) => {
+                    enable_confidential_credits_rows.push(
+                        enable_confidential_credits_row::EnableConfidentialCreditsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::DisableConfidentialCredits(instruction) => {
+                    disable_confidential_credits_rows.push(
+                        disable_confidential_credits_row::DisableConfidentialCreditsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::EnableNonConfidentialCredits(instruction) => {
+                    enable_non_confidential_credits_rows.push(enable_non_confidential_credits_row::EnableNonConfidentialCreditsRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::DisableNonConfidentialCredits(instruction) => {
+                    disable_non_confidential_credits_rows.push(disable_non_confidential_credits_row::DisableNonConfidentialCreditsRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::ConfidentialTransferWithFee(instruction) => {
+                    confidential_transfer_with_fee_rows.push(confidential_transfer_with_fee_row::ConfidentialTransferWithFeeRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializeDefaultAccountState(instruction) => {
+                    initialize_default_account_state_rows.push(initialize_default_account_state_row::InitializeDefaultAccountStateRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::UpdateDefaultAccountState(instruction) => {
+                    update_default_account_state_rows.push(
+                        update_default_account_state_row::UpdateDefaultAccountStateRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::Reallocate(instruction) => {
+                    reallocate_rows.push(reallocate_row::ReallocateRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::EnableMemoTransfers(instruction) => {
+                    enable_memo_transfers_rows.push(
+                        enable_memo_transfers_row::EnableMemoTransfersRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::DisableMemoTransfers(instruction) => {
+                    disable_memo_transfers_rows.push(
+                        disable_memo_transfers_row::DisableMemoTransfersRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::CreateNativeMint(instruction) => {
+                    create_native_mint_rows.push(
+                        create_native_mint_row::CreateNativeMintRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeNonTransferableMint(instruction) => {
+                    initialize_non_transferable_mint_rows.push(initialize_non_transferable_mint_row::InitializeNonTransferableMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializeInterestBearingMint(instruction) => {
+                    initialize_interest_bearing_mint_rows.push(initialize_interest_bearing_mint_row::InitializeInterestBearingMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::UpdateRateInterestBearingMint(instruction) => {
+                    update_rate_interest_bearing_mint_rows.push(update_rate_interest_bearing_mint_row::UpdateRateInterestBearingMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::EnableCpiGuard(instruction) => {
+                    enable_cpi_guard_rows.push(
+                        enable_cpi_guard_row::EnableCpiGuardRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::DisableCpiGuard(instruction) => {
+                    disable_cpi_guard_rows.push(
+                        disable_cpi_guard_row::DisableCpiGuardRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializePermanentDelegate(instruction) => {
+                    initialize_permanent_delegate_rows.push(initialize_permanent_delegate_row::InitializePermanentDelegateRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializeTransferHook(instruction) => {
+                    initialize_transfer_hook_rows.push(
+                        initialize_transfer_hook_row::InitializeTransferHookRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateTransferHook(instruction) => {
+                    update_transfer_hook_rows.push(
+                        update_transfer_hook_row::UpdateTransferHookRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeConfidentialTransferFee(instruction) => {
+                    initialize_confidential_transfer_fee_rows.push(initialize_confidential_transfer_fee_row::InitializeConfidentialTransferFeeRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::EnableHarvestToMint(instruction) => {
+                    enable_harvest_to_mint_rows.push(
+                        enable_harvest_to_mint_row::EnableHarvestToMintRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::DisableHarvestToMint(instruction) => {
+                    disable_harvest_to_mint_rows.push(
+                        disable_harvest_to_mint_row::DisableHarvestToMintRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::WithdrawExcessLamports(instruction) => {
+                    withdraw_excess_lamports_rows.push(
+                        withdraw_excess_lamports_row::WithdrawExcessLamportsRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeMetadataPointer(instruction) => {
+                    initialize_metadata_pointer_rows.push(
+                        initialize_metadata_pointer_row::InitializeMetadataPointerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateMetadataPointer(instruction) => {
+                    update_metadata_pointer_rows.push(
+                        update_metadata_pointer_row::UpdateMetadataPointerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeGroupPointer(instruction) => {
+                    initialize_group_pointer_rows.push(
+                        initialize_group_pointer_row::InitializeGroupPointerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateGroupPointer(instruction) => {
+                    update_group_pointer_rows.push(
+                        update_group_pointer_row::UpdateGroupPointerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeGroupMemberPointer(instruction) => {
+                    initialize_group_member_pointer_rows.push(initialize_group_member_pointer_row::InitializeGroupMemberPointerRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::UpdateGroupMemberPointer(instruction) => {
+                    update_group_member_pointer_rows.push(
+                        update_group_member_pointer_row::UpdateGroupMemberPointerRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeScaledUiAmountMint(instruction) => {
+                    initialize_scaled_ui_amount_mint_rows.push(initialize_scaled_ui_amount_mint_row::InitializeScaledUiAmountMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::UpdateMultiplierScaledUiMint(instruction) => {
+                    update_multiplier_scaled_ui_mint_rows.push(update_multiplier_scaled_ui_mint_row::UpdateMultiplierScaledUiMintRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializePausableConfig(instruction) => {
+                    initialize_pausable_config_rows.push(
+                        initialize_pausable_config_row::InitializePausableConfigRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::Pause(instruction) => {
+                    pause_rows.push(pause_row::PauseRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::Resume(instruction) => {
+                    resume_rows.push(resume_row::ResumeRow::from_parts(
+                        instruction.clone(),
+                        metadata.clone(),
+                        accounts.clone(),
+                    ));
+                }
+                Token2022Instruction::InitializeTokenMetadata(instruction) => {
+                    initialize_token_metadata_rows.push(
+                        initialize_token_metadata_row::InitializeTokenMetadataRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateTokenMetadataField(instruction) => {
+                    update_token_metadata_field_rows.push(
+                        update_token_metadata_field_row::UpdateTokenMetadataFieldRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::RemoveTokenMetadataKey(instruction) => {
+                    remove_token_metadata_key_rows.push(
+                        remove_token_metadata_key_row::RemoveTokenMetadataKeyRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateTokenMetadataUpdateAuthority(instruction) => {
+                    update_token_metadata_update_authority_rows.push(update_token_metadata_update_authority_row::UpdateTokenMetadataUpdateAuthorityRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::EmitTokenMetadata(instruction) => {
+                    emit_token_metadata_rows.push(
+                        emit_token_metadata_row::EmitTokenMetadataRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::InitializeTokenGroup(instruction) => {
+                    initialize_token_group_rows.push(
+                        initialize_token_group_row::InitializeTokenGroupRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateTokenGroupMaxSize(instruction) => {
+                    update_token_group_max_size_rows.push(
+                        update_token_group_max_size_row::UpdateTokenGroupMaxSizeRow::from_parts(
+                            instruction.clone(),
+                            metadata.clone(),
+                            accounts.clone(),
+                        ),
+                    );
+                }
+                Token2022Instruction::UpdateTokenGroupUpdateAuthority(instruction) => {
+                    update_token_group_update_authority_rows.push(update_token_group_update_authority_row::UpdateTokenGroupUpdateAuthorityRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+                Token2022Instruction::InitializeTokenGroupMember(instruction) => {
+                    initialize_token_group_member_rows.push(initialize_token_group_member_row::InitializeTokenGroupMemberRow::from_parts(instruction.clone(), metadata.clone(), accounts.clone()));
+                }
+            }
+        }
+
+        initialize_mint_row::InitializeMintRow::upsert_many(&initialize_mint_rows, pool).await?;
+        initialize_account_row::InitializeAccountRow::upsert_many(&initialize_account_rows, pool)
+            .await?;
+        initialize_multisig_row::InitializeMultisigRow::upsert_many(
+            &initialize_multisig_rows,
+            pool,
+        )
+        .await?;
+        transfer_row::TransferRow::upsert_many(&transfer_rows, pool).await?;
+        approve_row::ApproveRow::upsert_many(&approve_rows, pool).await?;
+        revoke_row::RevokeRow::upsert_many(&revoke_rows, pool).await?;
+        set_authority_row::SetAuthorityRow::upsert_many(&set_authority_rows, pool).await?;
+        mint_to_row::MintToRow::upsert_many(&mint_to_rows, pool).await?;
+        burn_row::BurnRow::upsert_many(&burn_rows, pool).await?;
+        close_account_row::CloseAccountRow::upsert_many(&close_account_rows, pool).await?;
+        freeze_account_row::FreezeAccountRow::upsert_many(&freeze_account_rows, pool).await?;
+        thaw_account_row::ThawAccountRow::upsert_many(&thaw_account_rows, pool).await?;
+        transfer_checked_row::TransferCheckedRow::upsert_many(&transfer_checked_rows, pool).await?;
+        approve_checked_row::ApproveCheckedRow::upsert_many(&approve_checked_rows, pool).await?;
+        mint_to_checked_row::MintToCheckedRow::upsert_many(&mint_to_checked_rows, pool).await?;
+        burn_checked_row::BurnCheckedRow::upsert_many(&burn_checked_rows, pool).await?;
+        initialize_account2_row::InitializeAccount2Row::upsert_many(
+            &initialize_account2_rows,
+            pool,
+        )
+        .await?;
+        sync_native_row::SyncNativeRow::upsert_many(&sync_native_rows, pool).await?;
+        initialize_account3_row::InitializeAccount3Row::upsert_many(
+            &initialize_account3_rows,
+            pool,
+        )
+        .await?;
+        initialize_multisig2_row::InitializeMultisig2Row::upsert_many(
+            &initialize_multisig2_rows,
+            pool,
+        )
+        .await?;
+        initialize_mint2_row::InitializeMint2Row::upsert_many(&initialize_mint2_rows, pool).await?;
+        get_account_data_size_row::GetAccountDataSizeRow::upsert_many(
+            &get_account_data_size_rows,
+            pool,
+        )
+        .await?;
+        initialize_immutable_owner_row::InitializeImmutableOwnerRow::upsert_many(
+            &initialize_immutable_owner_rows,
+            pool,
+        )
+        .await?;
+        amount_to_ui_amount_row::AmountToUiAmountRow::upsert_many(&amount_to_ui_amount_rows, pool)
+            .await?;
+        ui_amount_to_amount_row::UiAmountToAmountRow::upsert_many(&ui_amount_to_amount_rows, pool)
+            .await?;
+        initialize_mint_close_authority_row::InitializeMintCloseAuthorityRow::upsert_many(
+            &initialize_mint_close_authority_rows,
+            pool,
+        )
+        .await?;
+        initialize_transfer_fee_config_row::InitializeTransferFeeConfigRow::upsert_many(
+            &initialize_transfer_fee_config_rows,
+            pool,
+        )
+        .await?;
+        transfer_checked_with_fee_row::TransferCheckedWithFeeRow::upsert_many(
+            &transfer_checked_with_fee_rows,
+            pool,
+        )
+        .await?;
+        withdraw_withheld_tokens_from_mint_row::WithdrawWithheldTokensFromMintRow::upsert_many(
+            &withdraw_withheld_tokens_from_mint_rows,
+            pool,
+        )
+        .await?;
+        withdraw_withheld_tokens_from_accounts_row::WithdrawWithheldTokensFromAccountsRow::upsert_many(&withdraw_withheld_tokens_from_accounts_rows, pool).await?;
+        harvest_withheld_tokens_to_mint_row::HarvestWithheldTokensToMintRow::upsert_many(
+            &harvest_withheld_tokens_to_mint_rows,
+            pool,
+        )
+        .await?;
+        set_transfer_fee_row::SetTransferFeeRow::upsert_many(&set_transfer_fee_rows, pool).await?;
+        initialize_confidential_transfer_mint_row::InitializeConfidentialTransferMintRow::upsert_many(&initialize_confidential_transfer_mint_rows, pool).await?;
+        update_confidential_transfer_mint_row::UpdateConfidentialTransferMintRow::upsert_many(
+            &update_confidential_transfer_mint_rows,
+            pool,
+        )
+        .await?;
+        configure_confidential_transfer_account_row::ConfigureConfidentialTransferAccountRow::upsert_many(&configure_confidential_transfer_account_rows, pool).await?;
+        approve_confidential_transfer_account_row::ApproveConfidentialTransferAccountRow::upsert_many(&approve_confidential_transfer_account_rows, pool).await?;
+        empty_confidential_transfer_account_row::EmptyConfidentialTransferAccountRow::upsert_many(
+            &empty_confidential_transfer_account_rows,
+            pool,
+        )
+        .await?;
+        confidential_deposit_row::ConfidentialDepositRow::upsert_many(
+            &confidential_deposit_rows,
+            pool,
+        )
+        .await?;
+        confidential_withdraw_row::ConfidentialWithdrawRow::upsert_many(
+            &confidential_withdraw_rows,
+            pool,
+        )
+        .await?;
+        confidential_transfer_row::ConfidentialTransferRow::upsert_many(
+            &confidential_transfer_rows,
+            pool,
+        )
+        .await?;
+        apply_confidential_pending_balance_row::ApplyConfidentialPendingBalanceRow::upsert_many(
+            &apply_confidential_pending_balance_rows,
+            pool,
+        )
+        .await?;
+        enable_confidential_credits_row::EnableConfidentialCreditsRow::upsert_many(
+            &enable_confidential_credits_rows,
+            pool,
+        )
+        .await?;
+        disable_confidential_credits_row::DisableConfidentialCreditsRow::upsert_many(
+            &disable_confidential_credits_rows,
+            pool,
+        )
+        .await?;
+        enable_non_confidential_credits_row::EnableNonConfidentialCreditsRow::upsert_many(
+            &enable_non_confidential_credits_rows,
+            pool,
+        )
+        .await?;
+        disable_non_confidential_credits_row::DisableNonConfidentialCreditsRow::upsert_many(
+            &disable_non_confidential_credits_rows,
+            pool,
+        )
+        .await?;
+        confidential_transfer_with_fee_row::ConfidentialTransferWithFeeRow::upsert_many(
+            &confidential_transfer_with_fee_rows,
+            pool,
+        )
+        .await?;
+        initialize_default_account_state_row::InitializeDefaultAccountStateRow::upsert_many(
+            &initialize_default_account_state_rows,
+            pool,
+        )
+        .await?;
+        update_default_account_state_row::UpdateDefaultAccountStateRow::upsert_many(
+            &update_default_account_state_rows,
+            pool,
+        )
+        .await?;
+        reallocate_row::ReallocateRow::upsert_many(&reallocate_rows, pool).await?;
+        enable_memo_transfers_row::EnableMemoTransfersRow::upsert_many(
+            &enable_memo_transfers_rows,
+            pool,
+        )
+        .await?;
+        disable_memo_transfers_row::DisableMemoTransfersRow::upsert_many(
+            &disable_memo_transfers_rows,
+            pool,
+        )
+        .await?;
+        create_native_mint_row::CreateNativeMintRow::upsert_many(&create_native_mint_rows, pool)
+            .await?;
+        initialize_non_transferable_mint_row::InitializeNonTransferableMintRow::upsert_many(
+            &initialize_non_transferable_mint_rows,
+            pool,
+        )
+        .await?;
+        initialize_interest_bearing_mint_row::InitializeInterestBearingMintRow::upsert_many(
+            &initialize_interest_bearing_mint_rows,
+            pool,
+        )
+        .await?;
+        update_rate_interest_bearing_mint_row::UpdateRateInterestBearingMintRow::upsert_many(
+            &update_rate_interest_bearing_mint_rows,
+            pool,
+        )
+        .await?;
+        enable_cpi_guard_row::EnableCpiGuardRow::upsert_many(&enable_cpi_guard_rows, pool).await?;
+        disable_cpi_guard_row::DisableCpiGuardRow::upsert_many(&disable_cpi_guard_rows, pool)
+            .await?;
+        initialize_permanent_delegate_row::InitializePermanentDelegateRow::upsert_many(
+            &initialize_permanent_delegate_rows,
+            pool,
+        )
+        .await?;
+        initialize_transfer_hook_row::InitializeTransferHookRow::upsert_many(
+            &initialize_transfer_hook_rows,
+            pool,
+        )
+        .await?;
+        update_transfer_hook_row::UpdateTransferHookRow::upsert_many(
+            &update_transfer_hook_rows,
+            pool,
+        )
+        .await?;
+        initialize_confidential_transfer_fee_row::InitializeConfidentialTransferFeeRow::upsert_many(&initialize_confidential_transfer_fee_rows, pool).await?;
+        enable_harvest_to_mint_row::EnableHarvestToMintRow::upsert_many(
+            &enable_harvest_to_mint_rows,
+            pool,
+        )
+        .await?;
+        disable_harvest_to_mint_row::DisableHarvestToMintRow::upsert_many(
+            &disable_harvest_to_mint_rows,
+            pool,
+        )
+        .await?;
+        withdraw_excess_lamports_row::WithdrawExcessLamportsRow::upsert_many(
+            &withdraw_excess_lamports_rows,
+            pool,
+        )
+        .await?;
+        initialize_metadata_pointer_row::InitializeMetadataPointerRow::upsert_many(
+            &initialize_metadata_pointer_rows,
+            pool,
+        )
+        .await?;
+        update_metadata_pointer_row::UpdateMetadataPointerRow::upsert_many(
+            &update_metadata_pointer_rows,
+            pool,
+        )
+        .await?;
+        initialize_group_pointer_row::InitializeGroupPointerRow::upsert_many(
+            &initialize_group_pointer_rows,
+            pool,
+        )
+        .await?;
+        update_group_pointer_row::UpdateGroupPointerRow::upsert_many(
+            &update_group_pointer_rows,
+            pool,
+        )
+        .await?;
+        initialize_group_member_pointer_row::InitializeGroupMemberPointerRow::upsert_many(
+            &initialize_group_member_pointer_rows,
+            pool,
+        )
+        .await?;
+        update_group_member_pointer_row::UpdateGroupMemberPointerRow::upsert_many(
+            &update_group_member_pointer_rows,
+            pool,
+        )
+        .await?;
+        initialize_scaled_ui_amount_mint_row::InitializeScaledUiAmountMintRow::upsert_many(
+            &initialize_scaled_ui_amount_mint_rows,
+            pool,
+        )
+        .await?;
+        update_multiplier_scaled_ui_mint_row::UpdateMultiplierScaledUiMintRow::upsert_many(
+            &update_multiplier_scaled_ui_mint_rows,
+            pool,
+        )
+        .await?;
+        initialize_pausable_config_row::InitializePausableConfigRow::upsert_many(
+            &initialize_pausable_config_rows,
+            pool,
+        )
+        .await?;
+        pause_row::PauseRow::upsert_many(&pause_rows, pool).await?;
+        resume_row::ResumeRow::upsert_many(&resume_rows, pool).await?;
+        initialize_token_metadata_row::InitializeTokenMetadataRow::upsert_many(
+            &initialize_token_metadata_rows,
+            pool,
+        )
+        .await?;
+        update_token_metadata_field_row::UpdateTokenMetadataFieldRow::upsert_many(
+            &update_token_metadata_field_rows,
+            pool,
+        )
+        .await?;
+        remove_token_metadata_key_row::RemoveTokenMetadataKeyRow::upsert_many(
+            &remove_token_metadata_key_rows,
+            pool,
+        )
+        .await?;
+        update_token_metadata_update_authority_row::UpdateTokenMetadataUpdateAuthorityRow::upsert_many(&update_token_metadata_update_authority_rows, pool).await?;
+        emit_token_metadata_row::EmitTokenMetadataRow::upsert_many(&emit_token_metadata_rows, pool)
+            .await?;
+        initialize_token_group_row::InitializeTokenGroupRow::upsert_many(
+            &initialize_token_group_rows,
+            pool,
+        )
+        .await?;
+        update_token_group_max_size_row::UpdateTokenGroupMaxSizeRow::upsert_many(
+            &update_token_group_max_size_rows,
+            pool,
+        )
+        .await?;
+        update_token_group_update_authority_row::UpdateTokenGroupUpdateAuthorityRow::upsert_many(
+            &update_token_group_update_authority_rows,
+            pool,
+        )
+        .await?;
+        initialize_token_group_member_row::InitializeTokenGroupMemberRow::upsert_many(
+            &initialize_token_group_member_rows,
+            pool,
+        )
+        .await?;
+        Ok(())
+    }
 }
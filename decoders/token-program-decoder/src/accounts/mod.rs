@@ -14,6 +14,10 @@ pub enum TokenProgramAccount {
 impl AccountDecoder<'_> for TokenProgramDecoder {
     type AccountType = TokenProgramAccount;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![spl_token_interface::id()]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -67,6 +67,10 @@ pub enum TokenProgramInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for TokenProgramDecoder {
     type InstructionType = TokenProgramInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![spl_token_interface::id()]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
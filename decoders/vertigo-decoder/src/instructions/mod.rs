@@ -27,6 +27,10 @@ pub enum VertigoInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for VertigoDecoder {
     type InstructionType = VertigoInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![crate::PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -12,6 +11,11 @@ pub enum VirtualsAccount {
 
 impl AccountDecoder<'_> for VirtualsDecoder {
     type AccountType = VirtualsAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -20,18 +24,8 @@ impl AccountDecoder<'_> for VirtualsDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            virtuals_pool::VirtualsPool::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: VirtualsAccount::VirtualsPool(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            VirtualsAccount::VirtualsPool => virtuals_pool::VirtualsPool,
+        )
     }
 }
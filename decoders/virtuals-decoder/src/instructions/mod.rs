@@ -42,6 +42,10 @@ pub enum VirtualsInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for VirtualsDecoder {
     type InstructionType = VirtualsInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
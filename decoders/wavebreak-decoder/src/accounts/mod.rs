@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -20,6 +19,11 @@ pub enum WavebreakAccount {
 
 impl AccountDecoder<'_> for WavebreakDecoder {
     type AccountType = WavebreakAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -28,65 +32,12 @@ impl AccountDecoder<'_> for WavebreakDecoder {
             return None;
         }
 
-        if let Some(decoded_account) =
-            authority_config::AuthorityConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WavebreakAccount::AuthorityConfig(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            bonding_curve::BondingCurve::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WavebreakAccount::BondingCurve(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = mint_config::MintConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WavebreakAccount::MintConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            permission_config::PermissionConfig::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WavebreakAccount::PermissionConfig(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            consumed_permission::ConsumedPermission::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: WavebreakAccount::ConsumedPermission(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            WavebreakAccount::AuthorityConfig => authority_config::AuthorityConfig,
+            WavebreakAccount::BondingCurve => bonding_curve::BondingCurve,
+            WavebreakAccount::MintConfig => mint_config::MintConfig,
+            WavebreakAccount::PermissionConfig => permission_config::PermissionConfig,
+            WavebreakAccount::ConsumedPermission => consumed_permission::ConsumedPermission,
+        )
     }
 }
@@ -130,6 +130,10 @@ pub enum WavebreakInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for WavebreakDecoder {
     type InstructionType = WavebreakInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -1,6 +1,5 @@
 use alloc::boxed::Box;
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use crate::PROGRAM_ID;
 
@@ -57,6 +56,11 @@ pub enum ZetaAccount {
 
 impl AccountDecoder<'_> for ZetaDecoder {
     type AccountType = ZetaAccount;
+
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_account(
         &self,
         account: &solana_account::Account,
@@ -65,278 +69,30 @@ impl AccountDecoder<'_> for ZetaDecoder {
             return None;
         }
 
-        if let Some(decoded_account) = pricing::Pricing::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::Pricing(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = greeks::Greeks::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::Greeks(Box::new(decoded_account)),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            market_indexes::MarketIndexes::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::MarketIndexes(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            open_orders_map::OpenOrdersMap::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::OpenOrdersMap(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            cross_open_orders_map::CrossOpenOrdersMap::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::CrossOpenOrdersMap(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = state::State::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::State(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = underlying::Underlying::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::Underlying(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            settlement_account::SettlementAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::SettlementAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            perp_sync_queue::PerpSyncQueue::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::PerpSyncQueue(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = zeta_group::ZetaGroup::deserialize(account.data.as_slice()) {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::ZetaGroup(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) = market_node::MarketNode::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::MarketNode(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            spread_account::SpreadAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::SpreadAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            cross_margin_account_manager::CrossMarginAccountManager::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::CrossMarginAccountManager(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            cross_margin_account::CrossMarginAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::CrossMarginAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            margin_account::MarginAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::MarginAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            trigger_order::TriggerOrder::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::TriggerOrder(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            socialized_loss_account::SocializedLossAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::SocializedLossAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            whitelist_deposit_account::WhitelistDepositAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::WhitelistDepositAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            whitelist_insurance_account::WhitelistInsuranceAccount::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::WhitelistInsuranceAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            insurance_deposit_account::InsuranceDepositAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::InsuranceDepositAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            whitelist_trading_fees_account::WhitelistTradingFeesAccount::deserialize(
-                account.data.as_slice(),
-            )
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::WhitelistTradingFeesAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            referrer_id_account::ReferrerIdAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::ReferrerIdAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        if let Some(decoded_account) =
-            referrer_pubkey_account::ReferrerPubkeyAccount::deserialize(account.data.as_slice())
-        {
-            return Some(carbon_core::account::DecodedAccount {
-                lamports: account.lamports,
-                data: ZetaAccount::ReferrerPubkeyAccount(decoded_account),
-                owner: account.owner,
-                executable: account.executable,
-                rent_epoch: account.rent_epoch,
-            });
-        }
-
-        None
+        carbon_core::try_decode_accounts!(account,
+            ZetaAccount::Pricing => pricing::Pricing,
+            ZetaAccount::Greeks => greeks::Greeks,
+            ZetaAccount::MarketIndexes => market_indexes::MarketIndexes,
+            ZetaAccount::OpenOrdersMap => open_orders_map::OpenOrdersMap,
+            ZetaAccount::CrossOpenOrdersMap => cross_open_orders_map::CrossOpenOrdersMap,
+            ZetaAccount::State => state::State,
+            ZetaAccount::Underlying => underlying::Underlying,
+            ZetaAccount::SettlementAccount => settlement_account::SettlementAccount,
+            ZetaAccount::PerpSyncQueue => perp_sync_queue::PerpSyncQueue,
+            ZetaAccount::ZetaGroup => zeta_group::ZetaGroup,
+            ZetaAccount::MarketNode => market_node::MarketNode,
+            ZetaAccount::SpreadAccount => spread_account::SpreadAccount,
+            ZetaAccount::CrossMarginAccountManager => cross_margin_account_manager::CrossMarginAccountManager,
+            ZetaAccount::CrossMarginAccount => cross_margin_account::CrossMarginAccount,
+            ZetaAccount::MarginAccount => margin_account::MarginAccount,
+            ZetaAccount::TriggerOrder => trigger_order::TriggerOrder,
+            ZetaAccount::SocializedLossAccount => socialized_loss_account::SocializedLossAccount,
+            ZetaAccount::WhitelistDepositAccount => whitelist_deposit_account::WhitelistDepositAccount,
+            ZetaAccount::WhitelistInsuranceAccount => whitelist_insurance_account::WhitelistInsuranceAccount,
+            ZetaAccount::InsuranceDepositAccount => insurance_deposit_account::InsuranceDepositAccount,
+            ZetaAccount::WhitelistTradingFeesAccount => whitelist_trading_fees_account::WhitelistTradingFeesAccount,
+            ZetaAccount::ReferrerIdAccount => referrer_id_account::ReferrerIdAccount,
+            ZetaAccount::ReferrerPubkeyAccount => referrer_pubkey_account::ReferrerPubkeyAccount,
+        )
     }
 }
@@ -352,6 +352,10 @@ pub enum ZetaInstruction {
 impl carbon_core::instruction::InstructionDecoder<'_> for ZetaDecoder {
     type InstructionType = ZetaInstruction;
 
+    fn program_ids(&self) -> Vec<solana_pubkey::Pubkey> {
+        vec![PROGRAM_ID]
+    }
+
     fn decode_instruction(
         &self,
         instruction: &solana_instruction::Instruction,
@@ -161,7 +161,7 @@ impl Processor for KaminoLendingRealtimeAccountProcessor {
     type InputType = (
         AccountMetadata,
         DecodedAccount<KaminoLendingAccount>,
-        solana_account::Account,
+        Arc<solana_account::Account>,
     );
 
     async fn process(
@@ -222,7 +222,7 @@ impl Processor for KaminoLendingStartupAccountProcessor {
     type InputType = (
         AccountMetadata,
         DecodedAccount<KaminoLendingAccount>,
-        solana_account::Account,
+        Arc<solana_account::Account>,
     );
 
     async fn process(
@@ -130,7 +130,7 @@ impl Processor for KaminoLendingAccountProcessor {
     type InputType = (
         AccountMetadata,
         DecodedAccount<KaminoLendingAccount>,
-        solana_account::Account,
+        Arc<solana_account::Account>,
     );
 
     async fn process(
@@ -241,7 +241,7 @@ impl Processor for RaydiumAmmV4AccountProcessor {
     type InputType = (
         AccountMetadata,
         DecodedAccount<RaydiumAmmV4Account>,
-        solana_account::Account,
+        Arc<solana_account::Account>,
     );
 
     async fn process(
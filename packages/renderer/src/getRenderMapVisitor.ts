@@ -21,7 +21,7 @@ import { extendVisitor, pipe, staticVisitor, visit } from '@codama/visitors-core
 import { DiscriminatorManifest, getDiscriminatorManifest, getTypeManifestVisitor } from './getTypeManifestVisitor';
 import { getGraphQLTypeManifestVisitor } from './getGraphQLTypeManifestVisitor';
 import { ImportMap } from './ImportMap';
-import { partition, render } from './utils';
+import { getDiscriminatorPrefix, getDispatch, partition, render } from './utils';
 import { isToken2022Program } from './utils/helpers';
 import { getPostgresTypeManifestVisitor, PostgresTypeManifest } from './getPostgresTypeManifestVisitor';
 import { FlattenedGraphQLField, flattenTypeForGraphQL } from './utils/flattenGraphqlFields';
@@ -686,6 +686,18 @@ export function getRenderMapVisitor(options: GetRenderMapOptions = {}) {
                     });
                     const definedTypesToExport = allDefinedTypes;
 
+                    // Index accounts and instructions by discriminator, so that the decoders
+                    // only try the types whose discriminator prefixes the data
+                    const accountDispatch = getDispatch(accountsToExport, account =>
+                        getDiscriminatorPrefix(
+                            account.discriminators,
+                            account.data.kind === 'structTypeNode' ? account.data.fields : [],
+                        ),
+                    );
+                    const instructionDispatch = getDispatch(instructionsToExport, instruction =>
+                        getDiscriminatorPrefix(instruction.discriminators, instruction.arguments),
+                    );
+
                     // Compute hasGraphQLFields: whether any GraphQL query fields will be generated
                     const hasAnchorEvents = (options.anchorEvents?.length ?? 0) > 0;
                     const hasGraphQLFields = (() => {
@@ -705,8 +717,10 @@ export function getRenderMapVisitor(options: GetRenderMapOptions = {}) {
 
                     const ctx = {
                         accountsToExport,
+                        accountDispatch,
                         definedTypesToExport,
                         instructionsToExport,
+                        instructionDispatch,
                         program: programWithCustomName,
                         root: node,
                         packageName: options.packageName,
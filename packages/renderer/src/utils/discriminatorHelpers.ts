@@ -1,4 +1,4 @@
-import { ConstantValueNode, isNode } from '@codama/nodes';
+import { ConstantValueNode, DiscriminatorNode, isNode, TypeNode, ValueNode } from '@codama/nodes';
 
 export function getDiscriminatorBytes(constant: ConstantValueNode): number[] {
     if (isNode(constant.value, 'bytesValueNode')) {
@@ -49,3 +49,66 @@ function numberToBytes(num: number, format: string): number[] {
 function stringToBytes(str: string): number[] {
     return Array.from(new TextEncoder().encode(str));
 }
+
+export type DiscriminatorField = {
+    name: string;
+    type: TypeNode;
+    defaultValue?: unknown;
+};
+
+export type DispatchEntry<T> = {
+    discriminator: string;
+    item: T;
+};
+
+/**
+ * Returns the bytes that the constant discriminators of an account or instruction put at the start of its
+ * data, or an empty array if it has no constant discriminator at offset 0. Field discriminators are resolved
+ * from the default value of their field. Bytes after a gap are left out, since the generated `decode`
+ * functions check the full discriminator anyway.
+ */
+export function getDiscriminatorPrefix(
+    discriminators: DiscriminatorNode[] | undefined,
+    fields: DiscriminatorField[],
+): number[] {
+    const bytesAtOffset = new Map<number, number>();
+
+    for (const discriminator of discriminators ?? []) {
+        let constant: ConstantValueNode | null = null;
+        let offset = 0;
+        if (discriminator.kind === 'constantDiscriminatorNode') {
+            constant = discriminator.constant;
+            offset = discriminator.offset;
+        } else if (discriminator.kind === 'fieldDiscriminatorNode') {
+            const field = fields.find(f => f.name === discriminator.name);
+            if (field?.defaultValue) {
+                constant = {
+                    kind: 'constantValueNode',
+                    type: field.type,
+                    value: field.defaultValue as ValueNode,
+                } as ConstantValueNode;
+            }
+            offset = discriminator.offset;
+        }
+        if (!constant) continue;
+
+        getDiscriminatorBytes(constant).forEach((byte, i) => bytesAtOffset.set(offset + i, byte));
+    }
+
+    const prefix: number[] = [];
+    while (bytesAtOffset.has(prefix.length)) {
+        prefix.push(bytesAtOffset.get(prefix.length)!);
+    }
+
+    return prefix;
+}
+
+/**
+ * Pairs accounts or instructions with the Rust slice of their discriminator prefix, e.g. `&[26, 0]`, in the
+ * order of the `LazyDiscriminatorIndex` the decoders dispatch through. The index tries longer discriminators
+ * before shorter ones sharing their prefix and items without a discriminator last, like
+ * `try_decode_instructions!` does.
+ */
+export function getDispatch<T>(items: T[], getPrefix: (item: T) => number[]): DispatchEntry<T>[] {
+    return items.map(item => ({ discriminator: `&[${getPrefix(item).join(', ')}]`, item }));
+}